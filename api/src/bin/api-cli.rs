@@ -52,6 +52,9 @@ pub fn main() {
     (@subcommand generate_genre_slugs =>
       (name: "generate-genre-slugs")
       (about: "Creates any missing genre and city genre slugs"))
+    (@subcommand ledger_integrity_check =>
+      (name: "ledger-integrity-check")
+      (about: "Checks the accounting ledger for unbalanced entries and paid orders missing journal entries"))
     (@subcommand update_customer_io_webhooks =>
       (name: "update-customer-io-webhooks")
       (about: "Creates any missing Customer.io webhooks needed for communications")
@@ -81,6 +84,7 @@ pub fn main() {
             schedule_missing_domain_actions(args.is_present("holds"), config, database)
         }
         ("generate-genre-slugs", Some(_)) => generate_genre_slugs(database),
+        ("ledger-integrity-check", Some(_)) => ledger_integrity_check(database),
         ("version", Some(_)) => version(),
         ("update-customer-io-webhooks", Some(args)) => {
             update_customer_io_webhooks(args.value_of("site_id"), args.value_of("api_key"), database)
@@ -155,6 +159,24 @@ fn generate_genre_slugs(database: Database) {
     println!("Generated: {:?}", slug_strings);
 }
 
+fn ledger_integrity_check(database: Database) {
+    info!("Checking ledger integrity");
+    let connection = database.get_connection().expect("Expected connection to establish");
+    let connection = connection.get();
+
+    let issues = LedgerJournalEntry::check_integrity(connection).expect("Expected to check ledger integrity");
+    for issue in &issues {
+        println!(
+            "{}: {} {} {}",
+            issue.issue_type, issue.source_table, issue.source_id, issue.details
+        );
+    }
+    println!("Found {} ledger integrity issues", issues.len());
+    if !issues.is_empty() {
+        std::process::exit(1);
+    }
+}
+
 fn backpopulate_temporary_user_data(database: Database) {
     info!("Backpopulating temporary user data");
     let connection = database.get_connection().expect("Expected connection to establish");
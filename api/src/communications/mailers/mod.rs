@@ -1,14 +1,78 @@
+use crate::config::Config;
 use crate::errors::ApiError;
 use db::models::*;
+use db::utils::errors::Optional;
+use db::utils::localization;
 use diesel::PgConnection;
 use url::form_urlencoded::byte_serialize;
 
 pub mod orders;
 pub mod organization_invites;
 pub mod reports;
+pub mod settlements;
 pub mod tickets;
 pub mod user;
 
+/// Locale for an email recipient, their own preference when they have an account otherwise the
+/// organization default
+pub fn recipient_locale(
+    email: &str,
+    organization: Option<&Organization>,
+    conn: &PgConnection,
+) -> Result<Locales, ApiError> {
+    let user_locale = User::find_by_email(email, false, conn)
+        .optional()?
+        .and_then(|u| u.locale);
+    Ok(localization::recipient_locale(
+        user_locale,
+        organization.map(|o| o.default_locale),
+    ))
+}
+
+pub fn organization_branding(
+    organization: Option<&Organization>,
+    conn: &PgConnection,
+) -> Result<Option<OrganizationBranding>, ApiError> {
+    Ok(match organization {
+        Some(organization) => organization.branding(conn)?,
+        None => None,
+    })
+}
+
+/// Sender for an organization's emails, its branded sender when configured otherwise the platform default
+pub fn source_address(branding: Option<&OrganizationBranding>, config: &Config) -> CommAddress {
+    match branding.and_then(|b| b.sender_email.clone()) {
+        Some(sender_email) => CommAddress::from(sender_email).with_name(branding.and_then(|b| b.sender_name.clone())),
+        None => CommAddress::from(config.communication_default_source_email.clone()),
+    }
+}
+
+pub fn front_end_url(branding: Option<&OrganizationBranding>, config: &Config) -> String {
+    branding
+        .and_then(|b| b.front_end_url())
+        .unwrap_or(config.front_end_url.clone())
+}
+
+/// Logo, colours and support contacts are only added when the organization has configured them so templates
+/// can fall back to the platform defaults
+pub fn insert_branding_template_data(template_data: &mut TemplateData, branding: Option<&OrganizationBranding>) {
+    if let Some(branding) = branding {
+        let values = vec![
+            ("branding_logo_url", &branding.logo_url),
+            ("branding_primary_color", &branding.primary_color),
+            ("branding_secondary_color", &branding.secondary_color),
+            ("support_email", &branding.support_email),
+            ("support_phone", &branding.support_phone),
+            ("support_url", &branding.support_url),
+        ];
+        for (key, value) in values {
+            if let Some(value) = value {
+                template_data.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+}
+
 pub fn insert_event_template_data(
     template_data: &mut TemplateData,
     event: &Event,
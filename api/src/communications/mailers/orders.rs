@@ -1,8 +1,12 @@
+use crate::communications::mailers::{
+    front_end_url, insert_branding_template_data, organization_branding, recipient_locale, source_address,
+};
 use crate::config::Config;
 use crate::errors::*;
 use crate::SITE_NAME;
 use db::models::*;
-use db::prelude::{DisplayOrder, OrderItem, Refund};
+use db::prelude::{DisplayOrder, Order, OrderItem, Refund};
+use db::utils::localization;
 use diesel::PgConnection;
 use itertools::Itertools;
 
@@ -13,11 +17,15 @@ pub fn confirmation_email(
     config: &Config,
     conn: &PgConnection,
 ) -> Result<Communication, ApiError> {
-    let source = CommAddress::from(config.communication_default_source_email.clone());
+    let organizations = Order::find(display_order.id, conn)?.organizations(conn)?;
+    let branding = organization_branding(organizations.first(), conn)?;
+    let source = source_address(branding.as_ref(), config);
+    let locale = recipient_locale(&user_email, organizations.first(), conn)?;
     let destinations = CommAddress::from(user_email);
-    let title = format!("{} Purchase Completed", SITE_NAME);
+    let title = localization::translate(locale, "email.orders.confirmation.title", &[("site_name", SITE_NAME)]);
     let template_id = config.sendgrid_template_bn_purchase_completed.clone();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert(String::from("name"), user_first_name.clone());
     //Construct an itemised breakdown using a HTML table
     let mut item_breakdown = r#"<table style="width:100%"><tbody>"#.to_string();
@@ -25,6 +33,8 @@ pub fn confirmation_email(
     let mut total_fees = 0;
     let mut total_initial_fees = 0;
     let mut total_refunded_fees = 0;
+    let mut taxes: Vec<(String, i64)> = Vec::new();
+    let symbol = display_order.currency.symbol();
 
     for oi in &display_order.items {
         match oi.item_type {
@@ -34,6 +44,7 @@ pub fn confirmation_email(
                     oi.quantity,
                     oi.unit_price_in_cents,
                     false,
+                    symbol,
                 ));
                 let mut discount_per_ticket = 0;
 
@@ -44,6 +55,7 @@ pub fn confirmation_email(
                         discount_item.quantity,
                         discount_item.unit_price_in_cents,
                         false,
+                        symbol,
                     ));
                 }
 
@@ -53,11 +65,37 @@ pub fn confirmation_email(
                         oi.refunded_quantity,
                         oi.unit_price_in_cents + discount_per_ticket,
                         true,
+                        symbol,
+                    ));
+                }
+            }
+            OrderItemTypes::AddOns => {
+                item_breakdown.push_str(&generate_item_row(
+                    &oi.description,
+                    oi.quantity,
+                    oi.unit_price_in_cents,
+                    false,
+                    symbol,
+                ));
+                if oi.refunded_quantity > 0 {
+                    item_breakdown.push_str(&generate_item_row(
+                        "Refunded",
+                        oi.refunded_quantity,
+                        oi.unit_price_in_cents,
+                        true,
+                        symbol,
                     ));
                 }
             }
             // Do nothing, included above with ticket for display
             OrderItemTypes::Discount => (),
+            OrderItemTypes::Tax => {
+                let tax_in_cents = (oi.quantity - oi.refunded_quantity) * oi.tax_in_cents;
+                match taxes.iter_mut().find(|(description, _)| description == &oi.description) {
+                    Some(tax) => tax.1 += tax_in_cents,
+                    None => taxes.push((oi.description.clone(), tax_in_cents)),
+                }
+            }
             _ => {
                 //Accumulate fees
                 total_initial_fees += oi.quantity * oi.unit_price_in_cents;
@@ -77,10 +115,18 @@ pub fn confirmation_email(
     }
     if total_refunded_fees > 0 {
         total_breakdown.push_str(&format!(
-            r#"<tr style="color: red"><th>Refunded</th><td>(${})</td></tr>"#,
+            r#"<tr style="color: red"><th>Refunded</th><td>({}{})</td></tr>"#,
+            symbol,
             format!("{:.*}", 2, total_refunded_fees as f64 / 100.0)
         ));
     }
+    for (description, tax_in_cents) in &taxes {
+        total_breakdown.push_str(&format!(
+            "<tr><th>{}</th><td>{}</td></tr>",
+            description,
+            format!("{:.*}", 2, *tax_in_cents as f64 / 100.0)
+        ));
+    }
     total_breakdown.push_str(&format!(
         "<tr><th>Order Total</th><td>{}</td></tr>",
         format!("{:.*}", 2, display_order.total_in_cents as f64 / 100.0)
@@ -107,13 +153,23 @@ pub fn confirmation_email(
         format!("{:.*}", 2, total_refunded_fees as f64 / 100.0),
     );
     template_data.insert("total_fees".to_string(), format!("{:.*}", 2, total_fees as f64 / 100.0));
+    template_data.insert(
+        "total_tax".to_string(),
+        format!("{:.*}", 2, display_order.total_tax_in_cents as f64 / 100.0),
+    );
     template_data.insert(
         "total_price".to_string(),
         format!("{:.*}", 2, display_order.total_in_cents as f64 / 100.0),
     );
+    template_data.insert("currency".to_string(), display_order.currency.to_string());
+    template_data.insert("currency_symbol".to_string(), symbol.to_string());
     template_data.insert("item_breakdown".to_string(), item_breakdown);
     template_data.insert("total_breakdown".to_string(), total_breakdown);
-    template_data.insert("tickets_link".to_string(), format!("{}/hub", config.front_end_url));
+    template_data.insert(
+        "tickets_link".to_string(),
+        format!("{}/hub", front_end_url(branding.as_ref(), config)),
+    );
+    insert_branding_template_data(&mut template_data, branding.as_ref());
 
     // TODO: Perhaps move this to an event subscription
     Ok(Communication::new(
@@ -129,7 +185,13 @@ pub fn confirmation_email(
     ))
 }
 
-fn generate_item_row(description: &str, quantity: i64, unit_price_in_cents: i64, refund: bool) -> String {
+fn generate_item_row(
+    description: &str,
+    quantity: i64,
+    unit_price_in_cents: i64,
+    refund: bool,
+    currency_symbol: &str,
+) -> String {
     let mut item_row = "".to_string();
 
     if refund {
@@ -143,14 +205,19 @@ fn generate_item_row(description: &str, quantity: i64, unit_price_in_cents: i64,
     item_row.push_str(&description);
     item_row.push_str(r#"</td><td align="right">"#);
 
-    let mut unit_price_display = format!("${:.*}", 2, unit_price_in_cents.abs() as f64 / 100.0);
+    let mut unit_price_display = format!("{}{:.*}", currency_symbol, 2, unit_price_in_cents.abs() as f64 / 100.0);
     if unit_price_in_cents < 0 || refund {
         unit_price_display = format!("({})", unit_price_display);
     }
     item_row.push_str(&unit_price_display);
     item_row.push_str(r#"</td><td align="right">"#);
 
-    let mut total_price_display = format!("${:.*}", 2, (quantity * unit_price_in_cents.abs()) as f64 / 100.0);
+    let mut total_price_display = format!(
+        "{}{:.*}",
+        currency_symbol,
+        2,
+        (quantity * unit_price_in_cents.abs()) as f64 / 100.0
+    );
     if unit_price_in_cents < 0 || refund {
         total_price_display = format!("({})", total_price_display);
     }
@@ -167,12 +234,18 @@ pub fn refund_email(
     config: &Config,
     conn: &PgConnection,
 ) -> Result<(), ApiError> {
-    let source = CommAddress::from(config.communication_default_source_email.clone());
+    let order = Order::find(refund.order_id, conn)?;
+    let organizations = order.organizations(conn)?;
+    let branding = organization_branding(organizations.first(), conn)?;
+    let source = source_address(branding.as_ref(), config);
+    let locale = recipient_locale(&user_email, organizations.first(), conn)?;
     let destinations = CommAddress::from(user_email);
-    let title = format!("{} Refund", SITE_NAME);
+    let title = localization::translate(locale, "email.orders.refund.title", &[("site_name", SITE_NAME)]);
     let template_id = config.sendgrid_template_bn_refund.clone();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert(String::from("name"), user_first_name.clone());
+    let currency = order.currency;
     //Construct an itemised breakdown using a HTML table
     let mut item_breakdown = r#"<table style="width:100%"><tbody>"#.to_string();
     item_breakdown.push_str("<tr><th>Units Refunded</th><th>Description</th><th>Total</th></tr>");
@@ -198,7 +271,8 @@ pub fn refund_email(
         item_breakdown.push_str(&item.quantity.to_string());
         item_breakdown.push_str("</th><th>");
         item_breakdown.push_str(&oi.description(conn)?);
-        item_breakdown.push_str(r#"</th><th align="right">$"#);
+        item_breakdown.push_str(r#"</th><th align="right">"#);
+        item_breakdown.push_str(currency.symbol());
         item_breakdown.push_str(&format!("{:.*}", 2, item.amount as f64 / 100.0));
 
         item_breakdown.push_str("</th></tr>");
@@ -222,10 +296,23 @@ pub fn refund_email(
         .map(|i| i.0.amount)
         .sum::<i64>();
 
+    let total_tax = items
+        .iter()
+        .filter(|i| i.1.item_type == OrderItemTypes::Tax)
+        .map(|i| i.0.quantity * i.1.tax_in_cents)
+        .sum::<i64>();
+
     template_data.insert("total_fees".to_string(), format!("{:.*}", 2, total_fees as f64 / 100.0));
+    template_data.insert("total_tax".to_string(), format!("{:.*}", 2, total_tax as f64 / 100.0));
     template_data.insert("total_price".to_string(), format!("{:.*}", 2, amount));
+    template_data.insert("currency".to_string(), currency.to_string());
+    template_data.insert("currency_symbol".to_string(), currency.symbol().to_string());
     template_data.insert("item_breakdown".to_string(), item_breakdown);
-    template_data.insert("tickets_link".to_string(), format!("{}/orders", config.front_end_url));
+    template_data.insert(
+        "tickets_link".to_string(),
+        format!("{}/orders", front_end_url(branding.as_ref(), config)),
+    );
+    insert_branding_template_data(&mut template_data, branding.as_ref());
 
     // TODO: Perhaps move this to an event subscription
     Communication::new(
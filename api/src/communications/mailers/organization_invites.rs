@@ -1,7 +1,9 @@
+use crate::communications::mailers::recipient_locale;
 use crate::config::Config;
 use crate::errors::*;
 use crate::SITE_NAME;
 use db::models::*;
+use db::utils::localization;
 use diesel::pg::PgConnection;
 
 pub fn invite_user_to_organization_email(
@@ -19,9 +21,11 @@ pub fn invite_user_to_organization_email(
 
     let source = CommAddress::from(config.communication_default_source_email.clone());
     let destinations = CommAddress::from(invite.user_email.clone());
-    let title = format!("{} Invites", SITE_NAME);
+    let locale = recipient_locale(&invite.user_email, Some(org), conn)?;
+    let title = localization::translate(locale, "email.organization_invites.title", &[("site_name", SITE_NAME)]);
     let template_id = config.email_templates.org_invite.to_string();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert("name".to_string(), recipient_name.into());
     template_data.insert("org".to_string(), org.name.clone());
     template_data.insert("invite_link_accept".to_string(), invite_link_accept);
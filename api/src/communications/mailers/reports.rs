@@ -1,9 +1,11 @@
+use crate::communications::mailers::recipient_locale;
 use crate::config::Config;
 use crate::errors::*;
 use crate::models::*;
 use crate::SITE_NAME;
 use chrono::prelude::*;
 use db::models::*;
+use db::utils::localization;
 use diesel::PgConnection;
 use serde_json;
 use std::collections::HashMap;
@@ -16,10 +18,12 @@ pub fn ticket_counts(
     conn: &PgConnection,
 ) -> Result<(), ApiError> {
     let source = CommAddress::from(config.communication_default_source_email.clone());
+    let locale = recipient_locale(&email, Some(&event.organization(conn)?), conn)?;
     let destinations = CommAddress::from(email);
-    let title = format!("{} Ticket Counts", SITE_NAME);
+    let title = localization::translate(locale, "email.reports.ticket_counts.title", &[("site_name", SITE_NAME)]);
     let template_id = config.email_templates.ticket_count_report.to_string();
     let mut extra_data: HashMap<String, serde_json::Value> = HashMap::new();
+    extra_data.insert("locale".to_string(), json!(locale.language_code()));
     Event::event_payload_data(&event, &config.front_end_url, &mut extra_data, conn)?;
     extra_data.insert("report".to_string(), json!(ticket_count_report));
     extra_data.insert("timestamp".to_string(), json!(Utc::now().timestamp()));
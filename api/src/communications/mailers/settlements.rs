@@ -0,0 +1,70 @@
+use crate::communications::mailers::recipient_locale;
+use crate::config::Config;
+use crate::errors::*;
+use crate::SITE_NAME;
+use chrono::prelude::*;
+use db::models::*;
+use db::utils::localization;
+use diesel::PgConnection;
+use serde_json;
+use std::collections::HashMap;
+
+pub fn statement(
+    email: String,
+    organization: &Organization,
+    settlement: &Settlement,
+    config: &Config,
+    conn: &PgConnection,
+) -> Result<(), ApiError> {
+    let source = CommAddress::from(config.communication_default_source_email.clone());
+    let locale = recipient_locale(&email, Some(organization), conn)?;
+    let destinations = CommAddress::from(email);
+    let title = localization::translate(locale, "email.settlements.statement.title", &[("site_name", SITE_NAME)]);
+    let template_id = config.email_templates.settlement_statement.to_string();
+    let statement_url = format!("{}/settlements/{}/statement", config.api_base_url, settlement.id);
+    let net_totals = settlement
+        .payout_totals(conn)?
+        .into_iter()
+        .map(|(currency, amount_in_cents)| format_amount(amount_in_cents, currency))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let mut extra_data: HashMap<String, serde_json::Value> = HashMap::new();
+    extra_data.insert("locale".to_string(), json!(locale.language_code()));
+    extra_data.insert("organization_id".to_string(), json!(organization.id.to_string()));
+    extra_data.insert("organization_name".to_string(), json!(organization.name.clone()));
+    extra_data.insert("settlement_id".to_string(), json!(settlement.id.to_string()));
+    extra_data.insert(
+        "settlement_start_date".to_string(),
+        json!(settlement.start_time.format("%Y-%m-%d").to_string()),
+    );
+    extra_data.insert(
+        "settlement_end_date".to_string(),
+        json!(settlement.end_time.format("%Y-%m-%d").to_string()),
+    );
+    extra_data.insert("net_total".to_string(), json!(net_totals));
+    extra_data.insert(
+        "statement_pdf_url".to_string(),
+        json!(format!("{}?format=pdf", statement_url)),
+    );
+    extra_data.insert(
+        "statement_csv_url".to_string(),
+        json!(format!("{}?format=csv", statement_url)),
+    );
+    extra_data.insert("timestamp".to_string(), json!(Utc::now().timestamp()));
+
+    Communication::new(
+        CommunicationType::EmailTemplate,
+        title,
+        None,
+        Some(source),
+        destinations,
+        Some(template_id),
+        None,
+        Some(vec!["settlement_statements", "reports"]),
+        Some(extra_data),
+    )
+    .queue(conn)?;
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
-use crate::communications::mailers::insert_event_template_data;
+use crate::communications::mailers::{
+    front_end_url, insert_branding_template_data, insert_event_template_data, organization_branding, source_address,
+};
 use crate::config::Config;
 use crate::errors::*;
 use crate::SITE_NAME;
 use chrono::prelude::*;
 use db::models::*;
+use db::utils::localization;
 use diesel::pg::PgConnection;
 use itertools::Itertools;
 
@@ -15,13 +18,17 @@ pub fn send_tickets(
     conn: &PgConnection,
 ) -> Result<(), ApiError> {
     let receive_tickets_link = transfer.receive_url(&config.front_end_url, conn)?;
-    let source = CommAddress::from(config.communication_default_source_email.clone());
+    let branding = organization_branding(transfer.organizations(conn)?.first(), conn)?;
+    let source = source_address(branding.as_ref(), config);
     let destinations = CommAddress::from(email);
-    let title = "{sender_name} has sent you some tickets".to_string();
+    let locale = transfer.locale(SourceOrDestination::Destination, conn)?;
+    let title = localization::translate(locale, "email.transfers.sent.title", &[]);
     let template_id = config.sendgrid_template_bn_transfer_tickets.clone();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert("sender_name".to_string(), from_user.full_name());
     template_data.insert("receive_tickets_link".to_string(), receive_tickets_link);
+    insert_branding_template_data(&mut template_data, branding.as_ref());
     let events = transfer.events(conn)?;
     let event_ids = events.iter().map(|e| e.id.to_string()).join(",");
     let days_until_event = events
@@ -61,21 +68,32 @@ pub fn transfer_drip_reminder(
     conn: &PgConnection,
 ) -> Result<(), ApiError> {
     let receive_tickets_link = transfer.receive_url(&config.front_end_url, conn)?;
-    let source = CommAddress::from(config.communication_default_source_email.clone());
+    let branding = organization_branding(transfer.organizations(conn)?.first(), conn)?;
+    let source = source_address(branding.as_ref(), config);
     let destinations = CommAddress::from(email.clone());
-    let title = format!("{}: Ticket transfer reminder", SITE_NAME);
+    let locale = transfer.locale(source_or_destination, conn)?;
+    let title = localization::translate(
+        locale,
+        "email.transfers.drip_reminder.title",
+        &[("site_name", SITE_NAME)],
+    );
     let user = User::find(transfer.source_user_id, conn)?;
     let template_id = if source_or_destination == SourceOrDestination::Source {
         config.sendgrid_template_bn_transfer_tickets_drip_source.clone()
     } else {
         config.sendgrid_template_bn_transfer_tickets_drip_destination.clone()
     };
-    let transfer_cancel_url = format!("{}/my-events?event_id={}", config.front_end_url.clone(), event.id,);
+    let transfer_cancel_url = format!(
+        "{}/my-events?event_id={}",
+        front_end_url(branding.as_ref(), config),
+        event.id
+    );
 
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert(
         "header".to_string(),
-        transfer.drip_header(event, source_or_destination, true, config.environment, conn)?,
+        transfer.drip_header(event, source_or_destination, true, locale, config.environment, conn)?,
     );
     template_data.insert("sender_name".to_string(), Transfer::sender_name(&user));
     template_data.insert(
@@ -85,6 +103,7 @@ pub fn transfer_drip_reminder(
     template_data.insert("transfer_accept_url".to_string(), receive_tickets_link);
     template_data.insert("transfer_cancel_url".to_string(), transfer_cancel_url);
     template_data.insert("transfer_id".to_string(), transfer.id.to_string());
+    insert_branding_template_data(&mut template_data, branding.as_ref());
     insert_event_template_data(&mut template_data, event, conn)?;
 
     Communication::new(
@@ -110,13 +129,24 @@ pub fn transfer_sent_receipt(
     config: &Config,
     conn: &PgConnection,
 ) -> Result<(), ApiError> {
-    let source = CommAddress::from(config.communication_default_source_email.clone());
+    let branding = organization_branding(transfer.organizations(conn)?.first(), conn)?;
+    let source = source_address(branding.as_ref(), config);
     if let Some(email) = user.email.clone() {
         let destinations = CommAddress::from(email);
-        let title = format!("{}: Ticket transfer sent", SITE_NAME);
+        let locale = transfer.locale(SourceOrDestination::Source, conn)?;
+        let title = localization::translate(
+            locale,
+            "email.transfers.sent_receipt.title",
+            &[("site_name", SITE_NAME)],
+        );
         let template_id = config.sendgrid_template_bn_transfer_tickets_receipt.clone();
-        let transfer_cancel_url = format!("{}/my-events?event_id={}", config.front_end_url.clone(), event.id,);
+        let transfer_cancel_url = format!(
+            "{}/my-events?event_id={}",
+            front_end_url(branding.as_ref(), config),
+            event.id
+        );
         let mut template_data = TemplateData::new();
+        template_data.insert("locale".to_string(), locale.language_code().to_string());
         template_data.insert("sender_name".to_string(), Transfer::sender_name(&user));
         template_data.insert(
             "receiver_address".to_string(),
@@ -124,6 +154,7 @@ pub fn transfer_sent_receipt(
         );
         template_data.insert("transfer_cancel_url".to_string(), transfer_cancel_url);
         template_data.insert("transfer_id".to_string(), transfer.id.to_string());
+        insert_branding_template_data(&mut template_data, branding.as_ref());
         insert_event_template_data(&mut template_data, event, conn)?;
 
         Communication::new(
@@ -149,17 +180,25 @@ pub fn transfer_cancelled_receipt(
     transfer: &Transfer,
     conn: &PgConnection,
 ) -> Result<(), ApiError> {
-    let source = CommAddress::from(config.communication_default_source_email.clone());
+    let branding = organization_branding(transfer.organizations(conn)?.first(), conn)?;
+    let source = source_address(branding.as_ref(), config);
     let destinations = CommAddress::from(email);
-    let title = format!("{}: Cancelled ticket transfer", SITE_NAME);
+    let locale = transfer.locale(SourceOrDestination::Source, conn)?;
+    let title = localization::translate(
+        locale,
+        "email.transfers.cancelled_receipt.title",
+        &[("site_name", SITE_NAME)],
+    );
     let template_id = config.sendgrid_template_bn_cancel_transfer_tickets_receipt.clone();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert("sender_name".to_string(), Transfer::sender_name(&from_user));
     template_data.insert(
         "receiver_address".to_string(),
         transfer.transfer_address.clone().unwrap_or("".to_string()),
     );
     template_data.insert("transfer_id".to_string(), transfer.id.to_string());
+    insert_branding_template_data(&mut template_data, branding.as_ref());
     Communication::new(
         CommunicationType::EmailTemplate,
         title,
@@ -183,17 +222,21 @@ pub fn transfer_cancelled(
     transfer: &Transfer,
     conn: &PgConnection,
 ) -> Result<(), ApiError> {
-    let source = CommAddress::from(config.communication_default_source_email.clone());
+    let branding = organization_branding(transfer.organizations(conn)?.first(), conn)?;
+    let source = source_address(branding.as_ref(), config);
     let destinations = CommAddress::from(email);
-    let title = "{sender_name} has cancelled their transfer of tickets".to_string();
+    let locale = transfer.locale(SourceOrDestination::Destination, conn)?;
+    let title = localization::translate(locale, "email.transfers.cancelled.title", &[]);
     let template_id = config.sendgrid_template_bn_cancel_transfer_tickets.clone();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert("sender_name".to_string(), Transfer::sender_name(&from_user));
     template_data.insert(
         "receiver_address".to_string(),
         transfer.transfer_address.clone().unwrap_or("".to_string()),
     );
     template_data.insert("transfer_id".to_string(), transfer.id.to_string());
+    insert_branding_template_data(&mut template_data, branding.as_ref());
     Communication::new(
         CommunicationType::EmailTemplate,
         title,
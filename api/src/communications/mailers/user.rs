@@ -1,8 +1,10 @@
+use crate::communications::mailers::recipient_locale;
 use crate::config::Config;
 use crate::errors::*;
 use crate::utils::deep_linker::DeepLinker;
 use crate::SITE_NAME;
 use db::models::*;
+use db::utils::localization;
 use diesel::PgConnection;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -14,10 +16,12 @@ pub fn user_registered(
     conn: &PgConnection,
 ) -> Result<(), ApiError> {
     let source = CommAddress::from(config.communication_default_source_email.clone());
+    let locale = recipient_locale(&user_email, None, conn)?;
     let destinations = CommAddress::from(user_email);
-    let title = format!("{} Registration", SITE_NAME);
+    let title = localization::translate(locale, "email.users.registered.title", &[("site_name", SITE_NAME)]);
     let template_id = config.sendgrid_template_bn_user_registered.clone();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert("name".to_string(), user_first_name.clone());
     Communication::new(
         CommunicationType::EmailTemplate,
@@ -44,9 +48,11 @@ pub fn password_reset_email(config: &Config, user: &User) -> Communication {
     let email: &str = user.email.as_ref().expect("Email is not set");
     let source = CommAddress::from(config.communication_default_source_email.clone());
     let destinations = CommAddress::from(email.to_string());
-    let title = format!("{} Password reset request", SITE_NAME);
+    let locale = user.locale.unwrap_or_default();
+    let title = localization::translate(locale, "email.users.password_reset.title", &[("site_name", SITE_NAME)]);
     let template_id = config.email_templates.password_reset.to_string();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert("name".to_string(), user.full_name());
     template_data.insert("password_reset_link".to_string(), password_reset_link);
     Communication::new(
@@ -72,9 +78,11 @@ pub fn invite_user_email(config: &Config, user: &User, conn: &PgConnection) -> R
     let email: &str = user.email.as_ref().expect("Email is not set");
     let source = CommAddress::from(config.communication_default_source_email.clone());
     let destinations = CommAddress::from(email.to_string());
-    let title = format!("{} Invite", SITE_NAME);
+    let locale = user.locale.unwrap_or_default();
+    let title = localization::translate(locale, "email.users.invite.title", &[("site_name", SITE_NAME)]);
     let template_id = config.sendgrid_template_bn_user_invite.clone();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert("name".to_string(), user.full_name());
     template_data.insert("invite_link".to_string(), invite_link);
     Communication::new(
@@ -110,10 +118,12 @@ pub fn user_registered_magic_link(
     let link = deep_linker.create_with_custom_data(&desktop_url, custom_data)?;
 
     let source = CommAddress::from(config.communication_default_source_email.clone());
+    let locale = recipient_locale(email, None, conn)?;
     let destinations = CommAddress::from(email.to_string());
-    let title = format!("Welcome to {}", SITE_NAME);
+    let title = localization::translate(locale, "email.users.magic_link.title", &[("site_name", SITE_NAME)]);
     let template_id = config.email_templates.user_registered_magic_link.to_string();
     let mut template_data = TemplateData::new();
+    template_data.insert("locale".to_string(), locale.language_code().to_string());
     template_data.insert("download_link".to_string(), link);
     template_data.insert("refresh_token".to_string(), refresh_token);
     Communication::new(
@@ -1,5 +1,6 @@
 use crate::errors::*;
 use db::models::*;
+use db::utils::localization;
 use diesel::pg::PgConnection;
 use itertools::Itertools;
 
@@ -11,7 +12,11 @@ pub fn tickets_received(to_user: &User, from_user: &User, conn: &PgConnection) -
         .collect_vec();
 
     if tokens.len() > 0 {
-        let body = format!("{} has sent you some tickets.", from_user.full_name(),);
+        let body = localization::translate(
+            to_user.locale.unwrap_or_default(),
+            "push.transfers.received",
+            &[("sender", &from_user.full_name())],
+        );
 
         Communication::new(
             CommunicationType::Push,
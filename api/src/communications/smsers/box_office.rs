@@ -1,6 +1,7 @@
 use crate::config::Config;
 use crate::errors::*;
 use db::models::*;
+use db::utils::localization;
 use diesel::pg::PgConnection;
 use phonenumber::{Mode, PhoneNumber};
 use uuid::Uuid;
@@ -13,10 +14,16 @@ pub fn checkin_instructions(
 ) -> Result<(), ApiError> {
     let source = CommAddress::from(config.communication_default_source_phone.clone());
     let destinations = CommAddress::from(phone.format().mode(Mode::E164).to_string());
-    let body = format!(
-        "Thank you for your purchase! Your order number is #{}. Please head to the entry and let the door person know your first and last name so that they can check you in.",
-        Order::parse_order_number(order_id)
-
+    let order = Order::find(order_id, conn)?;
+    let user = User::find(order.on_behalf_of_user_id.unwrap_or(order.user_id), conn)?;
+    let locale = localization::recipient_locale(
+        user.locale,
+        order.organizations(conn)?.first().map(|o| o.default_locale),
+    );
+    let body = localization::translate(
+        locale,
+        "sms.box_office.checkin_instructions",
+        &[("order_number", &Order::parse_order_number(order_id))],
     );
 
     Communication::new(
@@ -2,17 +2,20 @@ use crate::config::Config;
 use crate::errors::*;
 use crate::utils::deep_linker::DeepLinker;
 use db::models::*;
+use db::utils::localization;
 use diesel::pg::PgConnection;
 
 pub fn transfer_cancelled(
     config: &Config,
     phone: String,
     from_user: &User,
+    transfer: &Transfer,
     conn: &PgConnection,
 ) -> Result<(), ApiError> {
     let source = CommAddress::from(config.communication_default_source_phone.clone());
     let destinations = CommAddress::from(phone);
-    let body = format!("{} has cancelled their transferred tickets.", from_user.full_name());
+    let locale = transfer.locale(SourceOrDestination::Destination, conn)?;
+    let body = localization::translate(locale, "sms.transfers.cancelled", &[("sender", &from_user.full_name())]);
     Communication::new(
         CommunicationType::Sms,
         body,
@@ -41,10 +44,19 @@ pub fn transfer_drip_reminder(
     let link = deep_linker.create_deep_link_with_fallback(&receive_tickets_link);
     let source = CommAddress::from(config.communication_default_source_phone.clone());
     let destinations = CommAddress::from(phone);
-    let body = format!(
-        "{} Follow this link to receive them: {}",
-        transfer.drip_header(event, SourceOrDestination::Destination, false, config.environment, conn)?,
-        link
+    let locale = transfer.locale(SourceOrDestination::Destination, conn)?;
+    let header = transfer.drip_header(
+        event,
+        SourceOrDestination::Destination,
+        false,
+        locale,
+        config.environment,
+        conn,
+    )?;
+    let body = localization::translate(
+        locale,
+        "sms.transfers.drip_reminder",
+        &[("header", &header), ("link", &link)],
     );
     Communication::new(
         CommunicationType::Sms,
@@ -75,10 +87,11 @@ pub fn send_tickets(
 
     let source = CommAddress::from(config.communication_default_source_phone.clone());
     let destinations = CommAddress::from(phone);
-    let body = format!(
-        "{} has sent you some tickets. Follow this link to receive them: {}",
-        from_user.full_name(),
-        link
+    let locale = transfer.locale(SourceOrDestination::Destination, conn)?;
+    let body = localization::translate(
+        locale,
+        "sms.transfers.sent",
+        &[("sender", &from_user.full_name()), ("link", &link)],
     );
     Communication::new(
         CommunicationType::Sms,
@@ -2,7 +2,7 @@ use crate::auth::default_token_issuer::DefaultTokenIssuer;
 use crate::errors::{ApiError, ApplicationError};
 use crate::SITE_NAME;
 use chrono::Duration;
-use db::models::{EmailProvider, Environment};
+use db::models::{EmailProvider, Environment, PayoutOriginator};
 use db::utils::errors::EnumParseError;
 use dotenv::dotenv;
 use itertools::Itertools;
@@ -10,7 +10,7 @@ use std::env;
 use std::fmt;
 use std::str;
 use std::str::FromStr;
-use tari_client::{HttpTariClient, TariClient, TariTestClient};
+use tari_client::{HttpTariClient, TariClient, TariSimulator, TariTestClient};
 
 #[derive(Clone)]
 pub struct Config {
@@ -60,6 +60,7 @@ pub struct Config {
     pub sendgrid_template_bn_transfer_tickets_drip_destination: String,
     pub sendgrid_template_bn_user_invite: String,
     pub settlement_period_in_days: Option<u32>,
+    pub payout_originator: PayoutOriginator,
     pub spotify_auth_token: Option<String>,
     pub static_file_path: Option<String>,
     pub twilio_account_id: String,
@@ -76,6 +77,7 @@ pub struct Config {
     pub customer_io: CustomerIoSettings,
     pub sharetribe: SharetribeConfig,
     pub product_context: ProductContext,
+    pub wallet_passes: WalletPassConfig,
 }
 
 #[derive(Clone)]
@@ -107,6 +109,7 @@ pub struct EmailTemplates {
     pub custom_broadcast: EmailTemplate,
     pub org_invite: EmailTemplate,
     pub password_reset: EmailTemplate,
+    pub settlement_statement: EmailTemplate,
     pub ticket_count_report: EmailTemplate,
     pub resend_download_link: EmailTemplate,
     pub user_registered_magic_link: EmailTemplate,
@@ -150,6 +153,32 @@ pub struct CustomerIoSettings {
     pub site_id: String,
 }
 
+/// Credentials used to issue ticket passes, passes for a wallet app are disabled when it is not configured
+#[derive(Clone, Default)]
+pub struct WalletPassConfig {
+    pub apple: Option<AppleWalletConfig>,
+    pub google: Option<GoogleWalletConfig>,
+}
+
+#[derive(Clone)]
+pub struct AppleWalletConfig {
+    pub pass_type_identifier: String,
+    pub team_identifier: String,
+    // PEM encoded pass type certificate and its private key, used to sign passes and for push updates
+    pub certificate: String,
+    pub private_key: String,
+    // PEM encoded Apple Worldwide Developer Relations intermediate certificate
+    pub wwdr_certificate: String,
+}
+
+#[derive(Clone)]
+pub struct GoogleWalletConfig {
+    pub issuer_id: String,
+    pub service_account_email: String,
+    // PEM encoded private key of the service account
+    pub private_key: String,
+}
+
 #[derive(Clone)]
 pub enum ProductContext {
     Collectibles,
@@ -180,6 +209,7 @@ const EMAIL_ONLY_REGISTRATION_ALLOWED: &str = "EMAIL_ONLY_REGISTRATION_ALLOWED";
 const EMAIL_TEMPLATES_CUSTOM_BROADCAST: &str = "EMAIL_TEMPLATES_CUSTOM_BROADCAST";
 const EMAIL_TEMPLATES_ORG_INVITE: &str = "EMAIL_TEMPLATES_ORG_INVITE";
 const EMAIL_TEMPLATES_PASSWORD_RESET: &str = "EMAIL_TEMPLATES_PASSWORD_RESET";
+const EMAIL_TEMPLATES_SETTLEMENT_STATEMENT: &str = "EMAIL_TEMPLATES_SETTLEMENT_STATEMENT";
 const EMAIL_TEMPLATES_TICKET_COUNT_REPORT: &str = "EMAIL_TEMPLATES_TICKET_COUNT_REPORT";
 const EMAIL_TEMPLATES_RESEND_DOWNLOAD_LINK: &str = "EMAIL_TEMPLATES_RESEND_DOWNLOAD_LINK";
 const EMAIL_TEMPLATES_USER_REGISTERED_MAGIC_LINK: &str = "EMAIL_TEMPLATES_USER_REGISTERED_MAGIC_LINK";
@@ -225,6 +255,11 @@ const SENDGRID_TEMPLATE_BN_USER_INVITE: &str = "SENDGRID_TEMPLATE_BN_USER_INVITE
 
 // Settlement period settings
 const SETTLEMENT_PERIOD_IN_DAYS: &str = "SETTLEMENT_PERIOD_IN_DAYS";
+const PAYOUT_ORIGINATOR_NAME: &str = "PAYOUT_ORIGINATOR_NAME";
+const PAYOUT_ORIGINATOR_ROUTING_NUMBER: &str = "PAYOUT_ORIGINATOR_ROUTING_NUMBER";
+const PAYOUT_ORIGINATOR_COMPANY_ID: &str = "PAYOUT_ORIGINATOR_COMPANY_ID";
+const PAYOUT_ORIGINATOR_IBAN: &str = "PAYOUT_ORIGINATOR_IBAN";
+const PAYOUT_ORIGINATOR_BIC: &str = "PAYOUT_ORIGINATOR_BIC";
 
 //Spotify settings
 const SPOTIFY_AUTH_TOKEN: &str = "SPOTIFY_AUTH_TOKEN";
@@ -251,10 +286,24 @@ const SHARETRIBE_CLIENT_SECRET: &str = "SHARETRIBE_CLIENT_SECRET";
 
 const PRODUCT_CONTEXT: &str = "PRODUCT_CONTEXT";
 
+// Wallet pass settings, PEM values may have their newlines escaped as \n
+const APPLE_WALLET_PASS_TYPE_IDENTIFIER: &str = "APPLE_WALLET_PASS_TYPE_IDENTIFIER";
+const APPLE_WALLET_TEAM_IDENTIFIER: &str = "APPLE_WALLET_TEAM_IDENTIFIER";
+const APPLE_WALLET_CERTIFICATE: &str = "APPLE_WALLET_CERTIFICATE";
+const APPLE_WALLET_PRIVATE_KEY: &str = "APPLE_WALLET_PRIVATE_KEY";
+const APPLE_WALLET_WWDR_CERTIFICATE: &str = "APPLE_WALLET_WWDR_CERTIFICATE";
+const GOOGLE_WALLET_ISSUER_ID: &str = "GOOGLE_WALLET_ISSUER_ID";
+const GOOGLE_WALLET_SERVICE_ACCOUNT_EMAIL: &str = "GOOGLE_WALLET_SERVICE_ACCOUNT_EMAIL";
+const GOOGLE_WALLET_PRIVATE_KEY: &str = "GOOGLE_WALLET_PRIVATE_KEY";
+
 fn get_env_var(var: &str) -> String {
     env::var(var).unwrap_or_else(|_| panic!("{} must be defined", var))
 }
 
+fn get_pem_env_var(var: &str) -> String {
+    get_env_var(var).replace("\\n", "\n")
+}
+
 impl Config {
     pub fn parse_environment() -> Result<Environment, EnumParseError> {
         if let Ok(environment_value) = env::var(&ENVIRONMENT) {
@@ -355,6 +404,8 @@ impl Config {
             _ => {
                 if tari_uri == "TEST" {
                     Box::new(TariTestClient::new(tari_uri)) as Box<dyn TariClient + Send + Sync>
+                } else if tari_uri == "SIMULATOR" {
+                    Box::new(TariSimulator::new()) as Box<dyn TariClient + Send + Sync>
                 } else {
                     Box::new(HttpTariClient::new(tari_uri)) as Box<dyn TariClient + Send + Sync>
                 }
@@ -393,6 +444,7 @@ impl Config {
             custom_broadcast: get_env_var(EMAIL_TEMPLATES_CUSTOM_BROADCAST).parse().unwrap(),
             org_invite: get_env_var(EMAIL_TEMPLATES_ORG_INVITE).parse().unwrap(),
             password_reset: get_env_var(EMAIL_TEMPLATES_PASSWORD_RESET).parse().unwrap(),
+            settlement_statement: get_env_var(EMAIL_TEMPLATES_SETTLEMENT_STATEMENT).parse().unwrap(),
             ticket_count_report: get_env_var(EMAIL_TEMPLATES_TICKET_COUNT_REPORT).parse().unwrap(),
             resend_download_link: get_env_var(EMAIL_TEMPLATES_RESEND_DOWNLOAD_LINK).parse().unwrap(),
             user_registered_magic_link: get_env_var(EMAIL_TEMPLATES_USER_REGISTERED_MAGIC_LINK).parse().unwrap(),
@@ -436,6 +488,15 @@ impl Config {
                 .map(|s| s.parse().expect("Not a valid integer for settlement period in days"))
         };
 
+        // Bank account settlement payouts are sent from, used for the NACHA and SEPA batch files
+        let payout_originator = PayoutOriginator {
+            name: env::var(&PAYOUT_ORIGINATOR_NAME).unwrap_or_else(|_| app_name.clone()),
+            routing_number: env::var(&PAYOUT_ORIGINATOR_ROUTING_NUMBER).ok(),
+            company_id: env::var(&PAYOUT_ORIGINATOR_COMPANY_ID).ok(),
+            iban: env::var(&PAYOUT_ORIGINATOR_IBAN).ok(),
+            bic: env::var(&PAYOUT_ORIGINATOR_BIC).ok(),
+        };
+
         let spotify_auth_token = env::var(&SPOTIFY_AUTH_TOKEN).ok();
 
         let twilio_api_key = get_env_var(TWILIO_API_KEY);
@@ -491,6 +552,25 @@ impl Config {
             _ => panic!("Invalid value for PRODUCT_CONTEXT"),
         };
 
+        let wallet_passes = WalletPassConfig {
+            apple: env::var(&APPLE_WALLET_PASS_TYPE_IDENTIFIER)
+                .ok()
+                .map(|pass_type_identifier| AppleWalletConfig {
+                    pass_type_identifier,
+                    team_identifier: get_env_var(APPLE_WALLET_TEAM_IDENTIFIER),
+                    certificate: get_pem_env_var(APPLE_WALLET_CERTIFICATE),
+                    private_key: get_pem_env_var(APPLE_WALLET_PRIVATE_KEY),
+                    wwdr_certificate: get_pem_env_var(APPLE_WALLET_WWDR_CERTIFICATE),
+                }),
+            google: env::var(&GOOGLE_WALLET_ISSUER_ID)
+                .ok()
+                .map(|issuer_id| GoogleWalletConfig {
+                    issuer_id,
+                    service_account_email: get_env_var(GOOGLE_WALLET_SERVICE_ACCOUNT_EMAIL),
+                    private_key: get_pem_env_var(GOOGLE_WALLET_PRIVATE_KEY),
+                }),
+        };
+
         Config {
             actix: Actix {
                 workers,
@@ -544,6 +624,7 @@ impl Config {
             sendgrid_template_bn_transfer_tickets_drip_source,
             sendgrid_template_bn_user_invite,
             settlement_period_in_days,
+            payout_originator,
             spotify_auth_token,
             static_file_path,
             twilio_api_key,
@@ -558,6 +639,7 @@ impl Config {
             ssr_trigger_value,
             sharetribe,
             product_context,
+            wallet_passes,
         }
     }
 }
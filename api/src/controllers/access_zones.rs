@@ -0,0 +1,110 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::PathParameters;
+use actix_web::{web::Path, HttpResponse};
+use db::prelude::*;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateAccessZoneRequest {
+    pub name: String,
+    #[serde(default)]
+    pub re_entry_policy: ReEntryPolicies,
+    #[serde(default)]
+    pub ticket_type_ids: Vec<Uuid>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateAccessZoneRequest {
+    pub name: Option<String>,
+    pub re_entry_policy: Option<ReEntryPolicies>,
+    pub ticket_type_ids: Option<Vec<Uuid>>,
+}
+
+impl From<UpdateAccessZoneRequest> for AccessZoneEditableAttributes {
+    fn from(attributes: UpdateAccessZoneRequest) -> Self {
+        AccessZoneEditableAttributes {
+            name: attributes.name,
+            re_entry_policy: attributes.re_entry_policy,
+        }
+    }
+}
+
+pub async fn index(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event = Event::find(path.id, connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventScan,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let access_zones = AccessZone::find_for_event(event.id, connection)?
+        .iter()
+        .map(|access_zone| access_zone.for_display(connection))
+        .collect::<Result<Vec<DisplayAccessZone>, DatabaseError>>()?;
+    Ok(HttpResponse::Ok().json(access_zones))
+}
+
+pub async fn create(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<CreateAccessZoneRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event = Event::find(path.id, connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventWrite,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let json = json.into_inner();
+    let access_zone =
+        AccessZone::create(event.id, json.name, json.re_entry_policy).commit(Some(user.id()), connection)?;
+    access_zone.update_ticket_types(json.ticket_type_ids, connection)?;
+    Ok(HttpResponse::Created().json(access_zone.for_display(connection)?))
+}
+
+pub async fn update(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<UpdateAccessZoneRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let access_zone = AccessZone::find(path.id, connection)?;
+    let event = access_zone.event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventWrite,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let mut json = json.into_inner();
+    let ticket_type_ids = json.ticket_type_ids.take();
+    let access_zone = access_zone.update(json.into(), Some(user.id()), connection)?;
+    if let Some(ticket_type_ids) = ticket_type_ids {
+        access_zone.update_ticket_types(ticket_type_ids, connection)?;
+    }
+    Ok(HttpResponse::Ok().json(access_zone.for_display(connection)?))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let access_zone = AccessZone::find(path.id, connection)?;
+    let event = access_zone.event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventWrite,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    access_zone.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
@@ -0,0 +1,39 @@
+use crate::auth::user::User as AuthUser;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::PathParameters;
+use actix_web::{web::Path, HttpResponse};
+use db::models::*;
+
+pub async fn index(
+    (connection, path, user): (Connection, Path<PathParameters>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFinancialReports, &organization, connection)?;
+
+    let mappings = AccountingAccountMapping::resolve_for_organization(organization.id, connection)?;
+    Ok(HttpResponse::Ok().json(&mappings))
+}
+
+pub async fn update(
+    (connection, path, json, user): (
+        Connection,
+        Path<PathParameters>,
+        Json<Vec<NewAccountingAccountMapping>>,
+        AuthUser,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &organization, connection)?;
+
+    for mut new_mapping in json.into_inner() {
+        new_mapping.organization_id = organization.id;
+        new_mapping.commit(Some(user.id()), connection)?;
+    }
+
+    let mappings = AccountingAccountMapping::resolve_for_organization(organization.id, connection)?;
+    Ok(HttpResponse::Ok().json(&mappings))
+}
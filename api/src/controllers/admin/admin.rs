@@ -1,9 +1,10 @@
 use crate::auth::user::{User as AuthUser, User};
 use crate::database::Connection;
 use crate::errors::*;
+use crate::extractors::*;
 use crate::models::WebPayload;
 use actix_web::{http::StatusCode, web::Query, HttpResponse};
-use db::models::{DomainAction, Report, Scopes};
+use db::models::{AssetStatus, AssetSyncIssue, DomainAction, DomainActionTypes, Report, Scopes};
 use db::prelude::{DisplayOrder, Event, Order, Paging, PagingParameters, Payload};
 
 pub async fn admin_ticket_count((connection, user): (Connection, AuthUser)) -> Result<HttpResponse, ApiError> {
@@ -22,6 +23,45 @@ pub async fn admin_stuck_domain_actions(connection: Connection, user: AuthUser)
     Ok(HttpResponse::Ok().json(result))
 }
 
+#[derive(Deserialize)]
+pub struct AssetSyncParameters {
+    pub status: Option<AssetStatus>,
+}
+
+#[derive(Deserialize)]
+pub struct ReconcileAssetsRequest {
+    #[serde(default)]
+    pub repair: bool,
+}
+
+/// Assets whose last reconciliation with the blockchain ended in the given status, defaulting to
+/// those with unresolved differences
+pub async fn admin_asset_sync(
+    (connection, query, user): (Connection, Query<AssetSyncParameters>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::OrgAdmin)?;
+    let result = AssetSyncIssue::report(query.status.unwrap_or(AssetStatus::Error), connection)?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+pub async fn admin_reconcile_assets(
+    (connection, json, user): (Connection, Json<ReconcileAssetsRequest>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::OrgAdmin)?;
+    let action = DomainAction::create(
+        None,
+        DomainActionTypes::ReconcileAssets,
+        None,
+        json!({ "repair": json.repair, "requested_by_user_id": user.id() }),
+        None,
+        None,
+    )
+    .commit(connection)?;
+    Ok(HttpResponse::Created().json(action))
+}
+
 pub async fn orders(
     (conn, query, user): (Connection, Query<PagingParameters>, User),
 ) -> Result<WebPayload<DisplayOrder>, ApiError> {
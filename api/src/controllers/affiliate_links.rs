@@ -0,0 +1,163 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::{PathParameters, WebPayload};
+use crate::server::AppState;
+use actix_web::{
+    http::StatusCode,
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use db::models::*;
+use diesel::PgConnection;
+
+/// Affiliate link with the URL the promoter shares, using the organization's custom domain if it has one
+#[derive(Serialize)]
+pub struct DisplayAffiliateLink {
+    #[serde(flatten)]
+    pub affiliate_link: AffiliateLink,
+    pub url: String,
+}
+
+impl DisplayAffiliateLink {
+    fn new(affiliate_link: AffiliateLink, state: &AppState, conn: &PgConnection) -> Result<Self, ApiError> {
+        let front_end_url = affiliate_link
+            .organization(conn)?
+            .front_end_url(&state.config.front_end_url, conn)?;
+        Ok(DisplayAffiliateLink {
+            url: affiliate_link.url(&front_end_url),
+            affiliate_link,
+        })
+    }
+}
+
+pub async fn index(
+    (connection, path, query, user): (Connection, Path<PathParameters>, Query<PagingParameters>, User),
+) -> Result<WebPayload<AffiliateLink>, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgRead, &organization, connection)?;
+
+    let payload = AffiliateLink::find_for_organization(organization.id, query.page(), query.limit(), connection)?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+pub async fn create(
+    (state, connection, path, json, user): (
+        Data<AppState>,
+        Connection,
+        Path<PathParameters>,
+        Json<NewAffiliateLink>,
+        User,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &organization, connection)?;
+
+    let mut new_affiliate_link = json.into_inner();
+    new_affiliate_link.organization_id = organization.id;
+    let affiliate_link = new_affiliate_link.commit(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(DisplayAffiliateLink::new(affiliate_link, &state, connection)?))
+}
+
+pub async fn show(
+    (state, connection, path, user): (Data<AppState>, Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let affiliate_link = AffiliateLink::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgRead, &affiliate_link.organization(connection)?, connection)?;
+
+    Ok(HttpResponse::Ok().json(DisplayAffiliateLink::new(affiliate_link, &state, connection)?))
+}
+
+pub async fn update(
+    (state, connection, path, json, user): (
+        Data<AppState>,
+        Connection,
+        Path<PathParameters>,
+        Json<AffiliateLinkEditableAttributes>,
+        User,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let affiliate_link = AffiliateLink::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &affiliate_link.organization(connection)?, connection)?;
+
+    let affiliate_link = affiliate_link.update(json.into_inner(), Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(DisplayAffiliateLink::new(affiliate_link, &state, connection)?))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let affiliate_link = AffiliateLink::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &affiliate_link.organization(connection)?, connection)?;
+
+    affiliate_link.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
+
+pub async fn commission_rules(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let affiliate_link = AffiliateLink::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgRead, &affiliate_link.organization(connection)?, connection)?;
+
+    Ok(HttpResponse::Ok().json(&affiliate_link.commission_rules(connection)?))
+}
+
+pub async fn create_commission_rule(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<NewAffiliateCommissionRule>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let affiliate_link = AffiliateLink::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &affiliate_link.organization(connection)?, connection)?;
+
+    let mut new_rule = json.into_inner();
+    new_rule.affiliate_link_id = affiliate_link.id;
+    let rule = new_rule.commit(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(&rule))
+}
+
+pub async fn destroy_commission_rule(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let rule = AffiliateCommissionRule::find(path.id, connection)?;
+    let affiliate_link = rule.affiliate_link(connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &affiliate_link.organization(connection)?, connection)?;
+
+    rule.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
+
+/// Commission ledger for the link, refunds are listed as negative commissions
+pub async fn commissions(
+    (connection, path, query, user): (Connection, Path<PathParameters>, Query<PagingParameters>, User),
+) -> Result<WebPayload<AffiliateCommission>, ApiError> {
+    let connection = connection.get();
+    let affiliate_link = AffiliateLink::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgRead, &affiliate_link.organization(connection)?, connection)?;
+
+    let payload =
+        AffiliateCommission::find_for_affiliate_link(affiliate_link.id, query.page(), query.limit(), connection)?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+/// Promoter facing earnings report for the current user's affiliate links
+pub async fn earnings((state, connection, user): (Data<AppState>, Connection, User)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let affiliate_links = AffiliateLink::find_for_user(user.id(), connection)?
+        .into_iter()
+        .map(|affiliate_link| DisplayAffiliateLink::new(affiliate_link, &state, connection))
+        .collect::<Result<Vec<DisplayAffiliateLink>, ApiError>>()?;
+    let earnings = AffiliateCommission::earnings_for_user(user.id(), connection)?;
+    Ok(HttpResponse::Ok().json(json!({
+        "affiliate_links": affiliate_links,
+        "earnings": earnings,
+    })))
+}
@@ -68,6 +68,10 @@ pub async fn track(
         None => None,
     };
     let is_facebook = extract_param(&params, "fbclid").map(|_| "facebook.com".to_string());
+    let affiliate_link = match extract_param(&params, AFFILIATE_CODE_PARAM) {
+        Some(affiliate_code) => AffiliateLink::find_by_code(&affiliate_code, conn).optional()?,
+        None => None,
+    };
 
     PageView::create(
         Utc::now().naive_utc(),
@@ -99,6 +103,13 @@ pub async fn track(
     )
     .commit(conn)?;
 
+    // The order is attributed at checkout from the code the front end keeps, only the visit is recorded here
+    if let Some(affiliate_link) = affiliate_link {
+        if affiliate_link.applies_to_event(&Event::find(query.event_id, conn)?) {
+            affiliate_link.record_visit(conn)?;
+        }
+    }
+
     Ok(HttpResponse::Ok().finish())
 }
 
@@ -25,6 +25,7 @@ pub struct NewBroadcastData {
     pub audience: Option<BroadcastAudience>,
     pub subject: Option<String>,
     pub preview_email: Option<String>,
+    pub fan_segment_id: Option<Uuid>,
 }
 
 pub async fn create(
@@ -36,7 +37,7 @@ pub async fn create(
 
     user.requires_scope_for_organization(Scopes::EventBroadcast, &organization, connection)?;
 
-    let broadcast = Broadcast::create(
+    let mut new_broadcast = Broadcast::create(
         path.id,
         json.notification_type.clone(),
         channel,
@@ -47,8 +48,9 @@ pub async fn create(
         json.subject.clone(),
         json.audience.clone().unwrap_or(BroadcastAudience::PeopleAtTheEvent),
         json.preview_email.clone(),
-    )
-    .commit(connection)?;
+    );
+    new_broadcast.fan_segment_id = json.fan_segment_id;
+    let broadcast = new_broadcast.commit(connection)?;
     Ok(HttpResponse::Created().json(json!(broadcast)))
 }
 
@@ -45,6 +45,16 @@ pub struct UpdateCartRequest {
     pub tracking_data: Option<Value>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct UpdateCartAddOnsRequest {
+    pub add_ons: Vec<UpdateAddOnItem>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UpdateCartPackagesRequest {
+    pub packages: Vec<UpdatePackageItem>,
+}
+
 pub async fn update_cart(
     (connection, json, user, request_info): (Connection, Json<UpdateCartRequest>, User, RequestInfo),
 ) -> Result<HttpResponse, ApiError> {
@@ -206,6 +216,70 @@ pub enum PaymentRequest {
     },
     // Only for 0 amount carts
     Free,
+    // Paid from the user's credit balance
+    Credit,
+}
+
+/// Replaces the add-ons in the cart. Add-ons must be for the event of the tickets already in the cart.
+pub async fn update_add_ons(
+    (connection, json, user): (Connection, Json<UpdateCartAddOnsRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let json = json.into_inner();
+    jlog!(Debug, "Update Cart Add-ons", {"add_ons": json.add_ons, "user_id": user.id()});
+    let connection = connection.get();
+    let mut cart = match Order::find_cart_for_user(user.id(), connection)? {
+        Some(o) => o,
+        None => return application::unprocessable("No cart exists for user"),
+    };
+
+    cart.update_add_on_quantities(user.id(), &json.add_ons, true, connection)?;
+    Ok(HttpResponse::Ok().json(Order::find(cart.id, connection)?.for_display(None, user.id(), connection)?))
+}
+
+/// Replaces the packages in the cart, reserving every ticket included in each package.
+pub async fn update_packages(
+    (connection, json, user): (Connection, Json<UpdateCartPackagesRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let json = json.into_inner();
+    jlog!(Debug, "Update Cart Packages", {"packages": json.packages, "user_id": user.id()});
+    let connection = connection.get();
+
+    for item in &json.packages {
+        for package_ticket_type in Package::find(item.package_id, connection)?.ticket_types(connection)? {
+            if !Dbticket_types::is_event_available_for_sale(&package_ticket_type.ticket_type_id, connection)? {
+                return Ok(
+                    HttpResponse::BadRequest().json(json!({"error": "Event has not been published.".to_string()}))
+                );
+            }
+        }
+    }
+
+    let mut cart = Order::find_or_create_cart(&user.user, connection)?;
+    cart.update_package_quantities(user.id(), &json.packages, true, connection)?;
+    Ok(HttpResponse::Ok().json(Order::find(cart.id, connection)?.for_display(None, user.id(), connection)?))
+}
+
+pub async fn checkout_answers((connection, user): (Connection, User)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let order = match Order::find_cart_for_user(user.id(), connection)? {
+        Some(o) => o,
+        None => return application::unprocessable("No cart exists for user"),
+    };
+
+    Ok(HttpResponse::Ok().json(CheckoutAnswersResponse::for_order(&order, connection)?))
+}
+
+pub async fn update_checkout_answers(
+    (connection, json, user): (Connection, Json<CheckoutAnswersRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let order = match Order::find_cart_for_user(user.id(), connection)? {
+        Some(o) => o,
+        None => return application::unprocessable("No cart exists for user"),
+    };
+
+    CheckoutAnswer::save_for_order(&order, json.into_inner().answers, Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(CheckoutAnswersResponse::for_order(&order, connection)?))
 }
 
 pub async fn clear_invalid_items((connection, user): (Connection, User)) -> Result<HttpResponse, ApiError> {
@@ -247,6 +321,8 @@ pub async fn checkout(
         return application::unprocessable("Could not complete this checkout because it contains invalid order items");
     }
 
+    CheckoutAnswer::validate_for_checkout(&order, connection.get())?;
+
     order.set_tracking_data(req.tracking_data.clone(), Some(user.id()), connection.get())?;
 
     let order_items = order.items(connection.get())?;
@@ -275,11 +351,17 @@ pub async fn checkout(
         }
     }
 
+    // Charges are made in the currency the cart was priced in
+    let currency = order.currency.to_string().to_lowercase();
     let payment_response = match &req.method {
         PaymentRequest::Free => {
             info!("CART: Received checkout for free cart");
             checkout_free(&connection, order, &user, &request_info)?
         }
+        PaymentRequest::Credit => {
+            info!("CART: Received credit payment");
+            checkout_credit(&connection, order, &user, &request_info)?
+        }
         PaymentRequest::External {
             reference,
             external_payment_type,
@@ -323,7 +405,7 @@ pub async fn checkout(
                 &mut order,
                 None,
                 &user,
-                &state.config.primary_currency,
+                &currency,
                 provider.clone(),
                 true,
                 false,
@@ -340,7 +422,7 @@ pub async fn checkout(
                 &mut order,
                 None,
                 &user,
-                &state.config.primary_currency,
+                &currency,
                 *provider,
                 false,
                 false,
@@ -362,7 +444,7 @@ pub async fn checkout(
                 &mut order,
                 Some(&token),
                 &user,
-                &state.config.primary_currency,
+                &currency,
                 *provider,
                 false,
                 *save_payment_method,
@@ -400,6 +482,23 @@ fn checkout_free(
     Ok(HttpResponse::Ok().json(json!(order.for_display(None, user.id(), conn)?)))
 }
 
+fn checkout_credit(
+    conn: &Connection,
+    mut order: Order,
+    user: &User,
+    request_info: &RequestInfo,
+) -> Result<HttpResponse, ApiError> {
+    let conn = conn.get();
+    if order.status != OrderStatus::Draft {
+        return application::unprocessable("Could not complete this cart because it is not in the correct status");
+    }
+    order.add_credit_payment(user.id(), conn)?;
+
+    let mut order = Order::find(order.id, conn)?;
+    order.set_browser_data(request_info.user_agent.clone(), true, conn)?;
+    Ok(HttpResponse::Ok().json(json!(order.for_display(None, user.id(), conn)?)))
+}
+
 // TODO: This should actually probably move to an `orders` controller, since the
 // user will not be calling this.
 fn checkout_external(
@@ -668,6 +767,7 @@ async fn redirect_to_payment_page(
     let response = client
         .create_payment_request(
             amount as f64 / 100_f64,
+            &order.currency.to_string(),
             email,
             order.id,
             ipn,
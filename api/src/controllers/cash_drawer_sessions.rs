@@ -0,0 +1,140 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::{PathParameters, WebPayload};
+use actix_web::{
+    web::{Path, Query},
+    HttpResponse,
+};
+use db::prelude::*;
+use diesel::pg::PgConnection;
+use reqwest::StatusCode;
+
+#[derive(Deserialize, Serialize)]
+pub struct OpenCashDrawerSessionRequest {
+    pub opening_float_in_cents: i64,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CashDrawerMovementRequest {
+    pub movement_type: CashDrawerMovementTypes,
+    pub amount_in_cents: i64,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CloseCashDrawerSessionRequest {
+    pub counted_cash_in_cents: i64,
+    #[serde(default)]
+    pub closing_notes: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CashDrawerSessionFilterParameters {
+    pub status: Option<CashDrawerSessionStatus>,
+}
+
+/// Box office staff only see their own drawers, report readers see every drawer for the organization
+pub async fn index(
+    (connection, path, query, filter, user): (
+        Connection,
+        Path<PathParameters>,
+        Query<PagingParameters>,
+        Query<CashDrawerSessionFilterParameters>,
+        User,
+    ),
+) -> Result<WebPayload<CashDrawerSession>, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrderMakeExternalPayment, &organization, connection)?;
+
+    let user_id = if user.has_scope_for_organization(Scopes::OrgReports, &organization, connection)? {
+        None
+    } else {
+        Some(user.id())
+    };
+    let payload = CashDrawerSession::find_for_organization(
+        organization.id,
+        user_id,
+        filter.status,
+        query.page(),
+        query.limit(),
+        connection,
+    )?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+pub async fn create(
+    (connection, path, json, user): (
+        Connection,
+        Path<PathParameters>,
+        Json<OpenCashDrawerSessionRequest>,
+        User,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrderMakeExternalPayment, &organization, connection)?;
+
+    let session =
+        CashDrawerSession::create(organization.id, user.id(), json.opening_float_in_cents).commit(connection)?;
+    Ok(HttpResponse::Created().json(session.for_display(connection)?))
+}
+
+/// Shift report for the drawer
+pub async fn show(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let session = CashDrawerSession::find(path.id, connection)?;
+    requires_drawer_access(&user, &session, connection)?;
+
+    Ok(HttpResponse::Ok().json(session.for_display(connection)?))
+}
+
+pub async fn create_movement(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<CashDrawerMovementRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let session = CashDrawerSession::find(path.id, connection)?;
+    requires_drawer_access(&user, &session, connection)?;
+
+    let json = json.into_inner();
+    let movement = session.add_movement(
+        json.movement_type,
+        json.amount_in_cents,
+        json.reason,
+        user.id(),
+        connection,
+    )?;
+    Ok(HttpResponse::Created().json(movement))
+}
+
+pub async fn close(
+    (connection, path, json, user): (
+        Connection,
+        Path<PathParameters>,
+        Json<CloseCashDrawerSessionRequest>,
+        User,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let session = CashDrawerSession::find(path.id, connection)?;
+    requires_drawer_access(&user, &session, connection)?;
+
+    let json = json.into_inner();
+    let session = session.close(json.counted_cash_in_cents, json.closing_notes, user.id(), connection)?;
+    Ok(HttpResponse::Ok().json(session.for_display(connection)?))
+}
+
+/// Staff manage their own drawer, managing another staff member's drawer requires report access
+fn requires_drawer_access(user: &User, session: &CashDrawerSession, connection: &PgConnection) -> Result<(), ApiError> {
+    let organization = session.organization(connection)?;
+    user.requires_scope_for_organization(Scopes::OrderMakeExternalPayment, &organization, connection)?;
+    if session.user_id != user.id() {
+        user.requires_scope_for_organization(Scopes::OrgReports, &organization, connection)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::PathParameters;
+use actix_web::{web::Path, HttpResponse};
+use db::models::*;
+
+pub async fn index((connection, path): (Connection, Path<PathParameters>)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event = Event::find(path.id, connection)?;
+    Ok(HttpResponse::Ok().json(CheckoutQuestion::find_for_event(event.id, connection)?))
+}
+
+pub async fn create(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<NewCheckoutQuestion>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event = Event::find(path.id, connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventWrite,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let mut new_checkout_question = json.into_inner();
+    new_checkout_question.event_id = event.id;
+    let checkout_question = new_checkout_question.commit(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(&checkout_question))
+}
+
+pub async fn update(
+    (connection, path, json, user): (
+        Connection,
+        Path<PathParameters>,
+        Json<CheckoutQuestionEditableAttributes>,
+        User,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let checkout_question = CheckoutQuestion::find(path.id, connection)?;
+    let event = checkout_question.event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventWrite,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let checkout_question = checkout_question.update(json.into_inner(), Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(&checkout_question))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let checkout_question = CheckoutQuestion::find(path.id, connection)?;
+    let event = checkout_question.event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventWrite,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    checkout_question.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
@@ -98,11 +98,12 @@ pub async fn link(
     let conn = conn.get();
     let code = Code::find(path.id, conn)?;
     let event = code.event(conn)?;
-    user.requires_scope_for_organization_event(Scopes::CodeRead, &code.organization(conn)?, &event, conn)?;
+    let organization = code.organization(conn)?;
+    user.requires_scope_for_organization_event(Scopes::CodeRead, &organization, &event, conn)?;
     let linker = state.service_locator.create_deep_linker()?;
     let raw_url = format!(
         "{}/events/{}/tickets?code={}",
-        &state.config.front_end_url,
+        organization.front_end_url(&state.config.front_end_url, conn)?,
         event.slug(conn)?,
         &code.redemption_code
     );
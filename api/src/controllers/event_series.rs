@@ -0,0 +1,192 @@
+use crate::auth::user::User;
+use crate::config::Config;
+use crate::controllers::ticket_types;
+use crate::database::Connection;
+use crate::domain_events::executors::UpdateGenresPayload;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::PathParameters;
+use crate::server::AppState;
+use actix_web::{
+    web::{Data, Path},
+    HttpResponse,
+};
+use db::prelude::*;
+use diesel::PgConnection;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateEventSeriesRequest {
+    pub template_event_id: Uuid,
+    pub name: Option<String>,
+    pub recurrence_rule: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdateEventSeriesEventsRequest {
+    pub event_id: Uuid,
+    pub scope: EventSeriesEditScopes,
+    #[serde(flatten)]
+    pub attributes: EventSeriesEventAttributes,
+}
+
+pub async fn index(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgReadEvents, &organization, connection)?;
+
+    let event_series = EventSeries::find_for_organization(organization.id, connection)?
+        .iter()
+        .map(|event_series| event_series.for_display(connection))
+        .collect::<Result<Vec<DisplayEventSeries>, DatabaseError>>()?;
+    Ok(HttpResponse::Ok().json(event_series))
+}
+
+pub async fn create(
+    (connection, path, json, user, state): (
+        Connection,
+        Path<PathParameters>,
+        Json<CreateEventSeriesRequest>,
+        User,
+        Data<AppState>,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    let json = json.into_inner();
+    let template_event = Event::find(json.template_event_id, connection)?;
+    user.requires_scope_for_organization_event(Scopes::EventClone, &organization, &template_event, connection)?;
+
+    let event_series = EventSeries::create(
+        organization.id,
+        template_event.id,
+        json.name.unwrap_or(template_event.name.clone()),
+        json.recurrence_rule,
+    )
+    .commit(Some(user.id()), connection)?;
+    generate_occurrences(&event_series, Some(user.id()), &state.config, connection)?;
+    Ok(HttpResponse::Created().json(event_series.for_display(connection)?))
+}
+
+pub async fn show(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event_series = EventSeries::find(path.id, connection)?;
+    user.requires_scope_for_organization(
+        Scopes::OrgReadEvents,
+        &event_series.organization(connection)?,
+        connection,
+    )?;
+
+    Ok(HttpResponse::Ok().json(event_series.for_display(connection)?))
+}
+
+pub async fn update(
+    (connection, path, json, user, state): (
+        Connection,
+        Path<PathParameters>,
+        Json<EventSeriesEditableAttributes>,
+        User,
+        Data<AppState>,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event_series = EventSeries::find(path.id, connection)?;
+    let template_event = event_series.template_event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventClone,
+        &event_series.organization(connection)?,
+        &template_event,
+        connection,
+    )?;
+
+    let event_series = event_series.update(json.into_inner(), user.id(), connection)?;
+    generate_occurrences(&event_series, Some(user.id()), &state.config, connection)?;
+    Ok(HttpResponse::Ok().json(event_series.for_display(connection)?))
+}
+
+pub async fn update_events(
+    (connection, path, json, user): (
+        Connection,
+        Path<PathParameters>,
+        Json<UpdateEventSeriesEventsRequest>,
+        User,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event_series = EventSeries::find(path.id, connection)?;
+    let json = json.into_inner();
+    let event = Event::find(json.event_id, connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventWrite,
+        &event_series.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let artists_changed = json.attributes.artists.is_some();
+    let events = event_series.update_events(&event, json.scope, json.attributes, Some(user.id()), connection)?;
+    if artists_changed {
+        for event in &events {
+            DomainAction::create(
+                None,
+                DomainActionTypes::UpdateGenres,
+                None,
+                json!(UpdateGenresPayload { user_id: user.id() }),
+                Some(Tables::Events),
+                Some(event.id),
+            )
+            .commit(connection)?;
+        }
+    }
+    Ok(HttpResponse::Ok().json(events))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event_series = EventSeries::find(path.id, connection)?;
+    let template_event = event_series.template_event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::EventClone,
+        &event_series.organization(connection)?,
+        &template_event,
+        connection,
+    )?;
+
+    event_series.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
+
+pub async fn dashboard(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event_series = EventSeries::find(path.id, connection)?;
+    user.requires_scope_for_organization(
+        Scopes::DashboardRead,
+        &event_series.organization(connection)?,
+        connection,
+    )?;
+
+    Ok(HttpResponse::Ok().json(event_series.dashboard(connection)?))
+}
+
+/// Generated events need blockchain assets for their ticket types like any other cloned event
+pub(crate) fn generate_occurrences(
+    event_series: &EventSeries,
+    current_user_id: Option<Uuid>,
+    config: &Config,
+    connection: &PgConnection,
+) -> Result<Vec<Event>, ApiError> {
+    let events = event_series.generate_occurrences(current_user_id, connection)?;
+    for event in &events {
+        let ticket_types = event.ticket_types(false, None, connection)?;
+        ticket_types::create_ticket_type_blockchain_assets(event, &ticket_types, config, connection)?;
+    }
+    Ok(events)
+}
@@ -121,6 +121,7 @@ pub struct EventExportData {
     pub localized_times: EventLocalizedTimeStrings,
     pub event_type: EventTypes,
     pub slug: String,
+    pub checkout_answers: Vec<DisplayCheckoutAnswer>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -171,6 +172,7 @@ impl From<EventSummaryResult> for EventExportData {
             localized_times: e.localized_times.clone(),
             event_type: e.event_type.clone(),
             slug: e.slug.clone(),
+            checkout_answers: Vec::new(),
         }
     }
 }
@@ -212,7 +214,10 @@ pub async fn export_event_data(
         conn,
     )?;
 
-    let export_data: Vec<EventExportData> = events.data.into_iter().map(|e| e.into()).collect();
+    let mut export_data: Vec<EventExportData> = events.data.into_iter().map(|e| e.into()).collect();
+    for event_data in export_data.iter_mut() {
+        event_data.checkout_answers = CheckoutAnswer::find_display_for_event(event_data.id, conn)?;
+    }
     Ok(WebPayload::new(
         StatusCode::OK,
         Payload::from_data(export_data, paging.page(), paging.limit(), None),
@@ -406,7 +411,7 @@ pub async fn show(
                     continue;
                 }
             }
-            Platforms::Web => {
+            Platforms::Web | Platforms::Partner => {
                 if !ticket_type.web_sales_enabled {
                     continue;
                 }
@@ -490,6 +495,7 @@ pub async fn show(
             .company_fee_in_cents
             .unwrap_or(organization.company_event_fee_in_cents);
     let slug = event.slug(connection)?;
+    let front_end_url = organization.front_end_url(&state.config.front_end_url, connection)?;
 
     let status = if event_ended {
         EventStatus::Closed
@@ -546,7 +552,7 @@ pub async fn show(
         tracking_keys,
         event_type: event.event_type,
         sales_start_date,
-        url: format!("{}/tickets/{}", &state.config.front_end_url, &slug),
+        url: format!("{}/tickets/{}", front_end_url, &slug),
         slug,
         facebook_pixel_key: event.facebook_pixel_key,
         extra_admin_data: event
@@ -576,7 +582,7 @@ pub async fn clone(
 
     // Clone tickets on blockchain (TODO: should be moved to background job as part of ticket type create)
     let ticket_types = event.ticket_types(false, None, connection)?;
-    ticket_types::create_ticket_type_blockchain_assets(&event, &ticket_types, &state, connection)?;
+    ticket_types::create_ticket_type_blockchain_assets(&event, &ticket_types, &state.config, connection)?;
 
     // Update genres for associated event given new artists
     let action = DomainAction::create(
@@ -637,7 +643,51 @@ pub async fn ticket_holder_count(
 #[derive(Deserialize, Serialize, Debug)]
 pub struct TicketRedeemRequest {
     pub redeem_key: String,
+    /// Rotating code shown by the holder's app, required for events using rotating barcodes
+    #[serde(default)]
+    pub redeem_code: Option<String>,
     pub check_in_source: Option<CheckInSource>,
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RedeemAddOnItem {
+    pub order_item_id: Uuid,
+    pub quantity: i64,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RedeemAddOnsRequest {
+    pub redeem_key: String,
+    #[serde(default)]
+    pub items: Vec<RedeemAddOnItem>,
+}
+
+/// Redeems add-ons bought with the scanned ticket. Sending no items returns the ticket's add-ons
+/// and their remaining quantities so door staff can see what to hand out.
+pub async fn redeem_add_ons(
+    (connection, parameters, json, auth_user): (Connection, Path<PathParameters>, Json<RedeemAddOnsRequest>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let db_event = Event::find(parameters.id, connection)?;
+    let organization = db_event.organization(connection)?;
+    auth_user.requires_scope_for_organization_event(Scopes::RedeemTicket, &organization, &db_event, connection)?;
+    let json = json.into_inner();
+    let ticket = TicketInstance::find_by_event_id_redeem_key(db_event.id, json.redeem_key, connection)?;
+
+    for item in json.items {
+        RedeemableAddOn::redeem(
+            &ticket,
+            db_event.id,
+            item.order_item_id,
+            item.quantity,
+            auth_user.id(),
+            connection,
+        )?;
+    }
+
+    Ok(HttpResponse::Ok().json(RedeemableAddOn::find_for_ticket(&ticket, db_event.id, connection)?))
 }
 
 pub async fn redeem_ticket(
@@ -654,8 +704,39 @@ pub async fn redeem_ticket(
     let db_event = Event::find(parameters.id, connection)?;
     let organization = db_event.organization(connection)?;
     auth_user.requires_scope_for_organization_event(Scopes::RedeemTicket, &organization, &db_event, connection)?;
-    let ticket =
-        TicketInstance::find_by_event_id_redeem_key(parameters.id, redeem_parameters.redeem_key.clone(), connection)?;
+    let ticket = match TicketInstance::find_by_event_id_redeem_key(
+        parameters.id,
+        redeem_parameters.redeem_key.clone(),
+        connection,
+    )
+    .optional()?
+    {
+        Some(ticket) => ticket,
+        None => {
+            log_redeem_scan(
+                db_event.id,
+                None,
+                Some(ScanRejectionReasons::InvalidTicket),
+                &redeem_parameters,
+                auth_user.id(),
+                connection,
+            )?;
+            return application::not_found();
+        }
+    };
+    if !ticket.verify_redeem_code(redeem_parameters.redeem_code.as_ref().map(|c| c.as_str()), connection)? {
+        log_redeem_scan(
+            db_event.id,
+            Some(ticket.id),
+            Some(ScanRejectionReasons::ExpiredCode),
+            &redeem_parameters,
+            auth_user.id(),
+            connection,
+        )?;
+        return Ok(HttpResponse::BadRequest().json(
+            json!({"error": "Barcode has expired, ask the ticket holder to show the current code.".to_string()}),
+        ));
+    }
     let redeemable = TicketInstance::show_redeemable_ticket(ticket.id, connection)?;
 
     let result = TicketInstance::redeem_ticket(
@@ -665,6 +746,14 @@ pub async fn redeem_ticket(
         redeem_parameters.check_in_source.unwrap_or(CheckInSource::GuestList),
         connection,
     )?;
+    log_redeem_scan(
+        db_event.id,
+        Some(ticket.id),
+        result.scan_rejection_reason(),
+        &redeem_parameters,
+        auth_user.id(),
+        connection,
+    )?;
 
     match result {
         RedeemResults::TicketRedeemSuccess => {
@@ -711,6 +800,28 @@ pub async fn redeem_ticket(
     }
 }
 
+/// Redemptions are recorded in the scan log as entries that aren't tied to an access zone
+fn log_redeem_scan(
+    event_id: Uuid,
+    ticket_instance_id: Option<Uuid>,
+    rejection_reason: Option<ScanRejectionReasons>,
+    redeem_parameters: &TicketRedeemRequest,
+    user_id: Uuid,
+    connection: &PgConnection,
+) -> Result<TicketScan, DatabaseError> {
+    TicketScan::create(
+        event_id,
+        ticket_instance_id,
+        None,
+        ScanDirections::Entry,
+        rejection_reason,
+        Some(redeem_parameters.redeem_key.clone()),
+        user_id,
+        redeem_parameters.device_id.clone(),
+    )
+    .commit(connection)
+}
+
 pub async fn show_from_organizations(
     (connection, path, paging, user): (Connection, Path<PathParameters>, Query<PagingParameters>, AuthUser),
 ) -> Result<WebPayload<EventSummaryResult>, ApiError> {
@@ -771,6 +882,7 @@ pub struct DashboardParameters {
 pub struct DashboardResult {
     pub event: EventSummaryResult,
     pub day_stats: Vec<DayStats>,
+    pub capacity: EventCapacity,
     pub cube_js_token: String,
 }
 
@@ -799,10 +911,13 @@ pub async fn dashboard(
 
     let day_stats = event.get_sales_by_date_range(start_utc, end_utc, conn)?;
 
+    let capacity = event.capacity_summary(conn)?;
+
     let cube_js_token = create_cube_js_token(event.id, &state.config.cube_js.secret)?;
     Ok(HttpResponse::Ok().json(DashboardResult {
         event: summary,
         day_stats,
+        capacity,
         cube_js_token,
     }))
 }
@@ -1113,6 +1228,7 @@ pub async fn guest_list(
         #[serde(flatten)]
         pending_transfer: PendingTransfer,
         refund_supported: bool,
+        checkout_answers: Vec<DisplayCheckoutAnswer>,
     }
 
     let mut tickets_refund: Vec<TicketRefundable> = Vec::new();
@@ -1132,6 +1248,7 @@ pub async fn guest_list(
                 .clone()
                 .unwrap_or(PendingTransfer { ..Default::default() }),
             refund_supported: refundable,
+            checkout_answers: t.checkout_answers,
         });
     }
 
@@ -1331,14 +1448,15 @@ pub async fn create_link(
 ) -> Result<HttpResponse, ApiError> {
     let conn = conn.get();
     let event = Event::find(path.id, conn)?;
+    let organization = event.organization(conn)?;
 
-    user.requires_scope_for_organization_event(Scopes::EventWrite, &event.organization(conn)?, &event, conn)?;
+    user.requires_scope_for_organization_event(Scopes::EventWrite, &organization, &event, conn)?;
 
     let query = query.into_inner();
     let slug = event.slug(conn).unwrap_or(path.id.to_string());
     let long_link_raw = format!(
         "{}/tickets/{}?utm_source={}&utm_medium={}&utm_campaign={}&utm_term={}&utm_content={}",
-        state.config.front_end_url,
+        organization.front_end_url(&state.config.front_end_url, conn)?,
         slug,
         query.source.as_ref().unwrap_or(&"".to_string()),
         query.medium.as_ref().unwrap_or(&"".to_string()),
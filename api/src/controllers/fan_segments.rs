@@ -0,0 +1,182 @@
+use crate::auth::user::User;
+use crate::database::{Connection, ReadonlyConnection};
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::{PathParameters, WebPayload};
+use crate::server::AppState;
+use crate::utils::sendgrid::contacts::{SGContact, SGContactList};
+use actix_web::{
+    http::{header, StatusCode},
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use db::models::*;
+use db::utils::csv::CsvWriter;
+
+#[derive(Deserialize, Serialize)]
+pub struct SendgridSyncResponse {
+    pub sendgrid_list_id: i64,
+    pub synced_count: usize,
+}
+
+pub async fn index(
+    (connection, path, query, user): (Connection, Path<PathParameters>, Query<PagingParameters>, User),
+) -> Result<WebPayload<FanSegment>, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFans, &organization, connection)?;
+
+    let payload = FanSegment::find_for_organization(organization.id, query.page(), query.limit(), connection)?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+pub async fn create(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<NewFanSegment>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::FanSegmentWrite, &organization, connection)?;
+
+    let mut new_fan_segment = json.into_inner();
+    new_fan_segment.organization_id = organization.id;
+    let fan_segment = new_fan_segment.commit(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(&fan_segment))
+}
+
+pub async fn show(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let fan_segment = FanSegment::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFans, &fan_segment.organization(connection)?, connection)?;
+
+    Ok(HttpResponse::Ok().json(&fan_segment))
+}
+
+pub async fn update(
+    (connection, path, json, user): (
+        Connection,
+        Path<PathParameters>,
+        Json<FanSegmentEditableAttributes>,
+        User,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let fan_segment = FanSegment::find(path.id, connection)?;
+    user.requires_scope_for_organization(
+        Scopes::FanSegmentWrite,
+        &fan_segment.organization(connection)?,
+        connection,
+    )?;
+
+    let fan_segment = fan_segment.update(json.into_inner(), Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(&fan_segment))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let fan_segment = FanSegment::find(path.id, connection)?;
+    user.requires_scope_for_organization(
+        Scopes::FanSegmentWrite,
+        &fan_segment.organization(connection)?,
+        connection,
+    )?;
+
+    fan_segment.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
+
+pub async fn fans(
+    (connection, path, query, user): (ReadonlyConnection, Path<PathParameters>, Query<PagingParameters>, User),
+) -> Result<WebPayload<DisplayFan>, ApiError> {
+    let connection = connection.get();
+    let fan_segment = FanSegment::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFans, &fan_segment.organization(connection)?, connection)?;
+
+    let payload = fan_segment.fans(query.page(), query.limit(), connection)?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+pub async fn export(
+    (connection, path, user): (ReadonlyConnection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let fan_segment = FanSegment::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFans, &fan_segment.organization(connection)?, connection)?;
+
+    let mut writer = CsvWriter::new();
+    writer.write_row(&[
+        "First Name",
+        "Last Name",
+        "Email",
+        "Phone",
+        "Orders",
+        "Revenue",
+        "First Order",
+        "Last Order",
+        "Last Interaction",
+    ]);
+    for fan in fan_segment.all_fans(connection)? {
+        writer.write_row(&[
+            fan.first_name.unwrap_or_default(),
+            fan.last_name.unwrap_or_default(),
+            fan.email.unwrap_or_default(),
+            fan.phone.unwrap_or_default(),
+            fan.order_count.unwrap_or(0).to_string(),
+            format!("{:.2}", fan.revenue_in_cents.unwrap_or(0) as f64 / 100.0),
+            fan.first_order_time.map(|t| t.to_string()).unwrap_or_default(),
+            fan.last_order_time.map(|t| t.to_string()).unwrap_or_default(),
+            fan.last_interaction_time.map(|t| t.to_string()).unwrap_or_default(),
+        ]);
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"fan-segment-{}.csv\"", fan_segment.id),
+        )
+        .body(writer.into_string()))
+}
+
+pub async fn sync_sendgrid(
+    (connection, path, user, state): (Connection, Path<PathParameters>, User, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let fan_segment = FanSegment::find(path.id, connection)?;
+    let mut organization = fan_segment.organization(connection)?;
+    user.requires_scope_for_organization(Scopes::FanSegmentWrite, &organization, connection)?;
+
+    organization.decrypt(&state.config.api_keys_encryption_key)?;
+    let api_key = organization
+        .sendgrid_api_key
+        .clone()
+        .ok_or_else(|| ApplicationError::unprocessable("Organization does not have a Sendgrid API key configured"))?;
+
+    let contacts: Vec<SGContact> = fan_segment
+        .all_fans(connection)?
+        .into_iter()
+        .filter_map(|fan| {
+            fan.email
+                .map(|email| SGContact::new(email, fan.first_name, fan.last_name))
+        })
+        .collect();
+    let synced_count = contacts.len();
+
+    let list = match fan_segment.sendgrid_list_id {
+        Some(sendgrid_list_id) => SGContactList::get_by_id(&api_key, sendgrid_list_id as u64)?,
+        None => SGContactList::new(fan_segment.name.clone()).create_or_return(&api_key)?,
+    };
+    if !contacts.is_empty() {
+        let created = SGContact::create_many(&api_key, contacts)?;
+        list.add_recipients(&api_key, created.persisted_recipients)?;
+    }
+    let fan_segment = fan_segment.set_sendgrid_list_id(list.id as i64, connection)?;
+
+    Ok(HttpResponse::Ok().json(SendgridSyncResponse {
+        sendgrid_list_id: fan_segment.sendgrid_list_id.unwrap_or(list.id as i64),
+        synced_count,
+    }))
+}
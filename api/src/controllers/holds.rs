@@ -185,7 +185,8 @@ pub async fn link(
     let conn = conn.get();
     let hold = Hold::find(path.id, conn)?;
     let event = hold.event(conn)?;
-    user.requires_scope_for_organization_event(Scopes::HoldRead, &hold.organization(conn)?, &event, conn)?;
+    let organization = hold.organization(conn)?;
+    user.requires_scope_for_organization_event(Scopes::HoldRead, &organization, &event, conn)?;
     if hold.redemption_code.is_none() {
         return application::not_found();
     }
@@ -193,7 +194,7 @@ pub async fn link(
     let linker = state.service_locator.create_deep_linker()?;
     let raw_url = format!(
         "{}/{}/tickets?code={}",
-        &state.config.front_end_url,
+        organization.front_end_url(&state.config.front_end_url, conn)?,
         event.slug(conn)?,
         hold.redemption_code.as_ref().unwrap()
     );
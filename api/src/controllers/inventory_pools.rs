@@ -0,0 +1,119 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::PathParameters;
+use actix_web::{web::Path, HttpResponse};
+use db::prelude::*;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize)]
+pub struct CreateInventoryPoolRequest {
+    pub name: String,
+    #[serde(default)]
+    pub ticket_type_ids: Vec<Uuid>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateInventoryPoolRequest {
+    pub name: Option<String>,
+    pub ticket_type_ids: Option<Vec<Uuid>>,
+}
+
+impl From<UpdateInventoryPoolRequest> for InventoryPoolEditableAttributes {
+    fn from(attributes: UpdateInventoryPoolRequest) -> Self {
+        InventoryPoolEditableAttributes { name: attributes.name }
+    }
+}
+
+pub async fn index(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event = Event::find(path.id, connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::TicketTypeRead,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let inventory_pools = InventoryPool::find_for_event(event.id, connection)?
+        .iter()
+        .map(|inventory_pool| inventory_pool.for_display(connection))
+        .collect::<Result<Vec<DisplayInventoryPool>, DatabaseError>>()?;
+    Ok(HttpResponse::Ok().json(inventory_pools))
+}
+
+pub async fn show(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let inventory_pool = InventoryPool::find(path.id, connection)?;
+    let event = inventory_pool.event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::TicketTypeRead,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    Ok(HttpResponse::Ok().json(inventory_pool.for_display(connection)?))
+}
+
+pub async fn create(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<CreateInventoryPoolRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event = Event::find(path.id, connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::TicketTypeWrite,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let json = json.into_inner();
+    let inventory_pool = InventoryPool::create(event.id, json.name).commit(Some(user.id()), connection)?;
+    inventory_pool.update_ticket_types(json.ticket_type_ids, Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(inventory_pool.for_display(connection)?))
+}
+
+pub async fn update(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<UpdateInventoryPoolRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let inventory_pool = InventoryPool::find(path.id, connection)?;
+    let event = inventory_pool.event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::TicketTypeWrite,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let mut json = json.into_inner();
+    let ticket_type_ids = json.ticket_type_ids.take();
+    let inventory_pool = inventory_pool.update(json.into(), Some(user.id()), connection)?;
+    if let Some(ticket_type_ids) = ticket_type_ids {
+        inventory_pool.update_ticket_types(ticket_type_ids, Some(user.id()), connection)?;
+    }
+    Ok(HttpResponse::Ok().json(inventory_pool.for_display(connection)?))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let inventory_pool = InventoryPool::find(path.id, connection)?;
+    let event = inventory_pool.event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::TicketTypeWrite,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    inventory_pool.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
@@ -0,0 +1,32 @@
+use crate::auth::user::User as AuthUser;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::models::PathParameters;
+use actix_web::{
+    web::{Path, Query},
+    HttpResponse,
+};
+use db::models::*;
+
+#[derive(Deserialize)]
+pub struct TrialBalanceParameters {
+    /// Defaults to the organization's currency
+    pub currency: Option<Currencies>,
+}
+
+pub async fn trial_balance(
+    (connection, query, path, user): (
+        Connection,
+        Query<TrialBalanceParameters>,
+        Path<PathParameters>,
+        AuthUser,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFinancialReports, &organization, connection)?;
+
+    let currency = query.currency.unwrap_or(organization.currency);
+    let trial_balance = LedgerAccount::trial_balance(organization.id, currency, connection)?;
+    Ok(HttpResponse::Ok().json(&trial_balance))
+}
@@ -1,59 +1,95 @@
-use animo_db::prelude::*;
-use actix_web::{HttpResponse};
-use auth::user::User;
-use errors::AnimoError;
-use extractors::Json;
-use db::Connection;
-use uuid::Uuid;
+use crate::auth::user::User;
+use crate::controllers::tickets::{transfer_tickets_on_blockchain, ShowTicketResponse};
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::helpers::application;
+use crate::models::PathParameters;
+use crate::server::AppState;
+use actix_web::{
+    web::{Data, Path},
+    HttpResponse,
+};
+use db::prelude::*;
+use itertools::Itertools;
 
 #[derive(Deserialize)]
-pub struct CreateLootBoxRequest {
-    pub name: String,
-    pub promo_image_url: String,
-    pub quantity: i64,
-    pub price_in_cents: i64,
-    pub contents: Vec<CreateLootBoxContentRequest>
+pub struct OpenLootBoxRequest {
+    pub client_seed: Option<String>,
 }
 
-#[derive(Deserialize)]
-pub struct CreateLootBoxContentRequest {
-    pub event_id: Uuid,
-    pub min_rarity_id: Uuid,
-    pub max_rarity_id: Uuid,
-    pub quantity_per_box: i32
+#[derive(Serialize)]
+pub struct OpenLootBoxResponse {
+    pub draw: DisplayLootBoxDraw,
+    pub tickets: Vec<ShowTicketResponse>,
 }
 
-pub fn create((connection, data, user) : (Connection,  Json<CreateLootBoxRequest>, User)) -> Result<HttpResponse, AnimoError> {
-    let conn = connection.get();
-    let model = NewLootBox{
-        name: data.name.clone(),
-        promo_image_url: Some(data.promo_image_url.clone()),
-        price_in_cents: data.price_in_cents
-    };
-
-    let loot_box  = model.commit(conn)?;
-
-    let quantity = data.quantity;
-    for content in data.into_inner().contents {
-
-
-        let event = Event::find(content.event_id, conn)?;
-
-        let org_wallet = Wallet::find_default_for_organization(event.organization_id, conn)?;
+/// Hash of the server seed the box's contents will be drawn with, along with the revealed seeds
+/// once it has been opened
+pub async fn show(
+    (connection, parameters, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let ticket = TicketInstance::find(parameters.id, connection)?;
+    if ticket.owner(connection)?.id != user.id() {
+        let organization = ticket.organization(connection)?;
+        user.requires_scope_for_organization(Scopes::TicketRead, &organization, connection)?;
+    }
+    if ticket.ticket_type(connection)?.ticket_type_type != TicketTypeType::LootBox {
+        return application::not_found();
+    }
 
-        user.requires_scope_for_organization_event(Scopes::LootBoxWrite, &event.organization(conn)?, &event, conn)?;
-        let new_content = NewLootBoxContent{
-            loot_box_id: loot_box.id,
-            event_id: content.event_id,
-            min_rarity_id: content.min_rarity_id,
-            max_rarity_id: content.max_rarity_id,
-            quantity_per_box: content.quantity_per_box
-        };
-        let c = new_content.commit(conn)?;
+    let draw = LootBoxDraw::find_or_create_for_ticket_instance(ticket.id, connection)?;
+    Ok(HttpResponse::Ok().json(draw.for_display()))
+}
 
-        LootBoxInstance::create_multiple(Some(user.id()), loot_box.id, quantity, &c, org_wallet.id, conn)?;
+/// Draws the box's contents into the opener's wallet and moves their tokens out of the
+/// organizations' wallets on the blockchain
+pub async fn open(
+    (connection, parameters, json, user, state): (
+        Connection,
+        Path<PathParameters>,
+        Json<OpenLootBoxRequest>,
+        User,
+        Data<AppState>,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::TicketWriteOwn)?;
+    let ticket = TicketInstance::find(parameters.id, connection)?;
+    if ticket.owner(connection)?.id != user.id() {
+        return application::unauthorized(Some(user), None);
     }
 
-    Ok(HttpResponse::Created().json(&loot_box))
+    let (draw, tickets) = LootBoxDraw::open(ticket.id, json.into_inner().client_seed, user.id(), connection)?;
+    let user_wallet = Wallet::find(ticket.wallet_id, connection)?;
+    let mut tickets_per_organization = Vec::new();
+    for ticket in &tickets {
+        tickets_per_organization.push((ticket.organization(connection)?.id, ticket.clone()));
+    }
+    for (organization_id, organization_tickets) in &tickets_per_organization
+        .into_iter()
+        .sorted_by_key(|(id, _)| *id)
+        .into_iter()
+        .group_by(|(id, _)| *id)
+    {
+        let organization_tickets = organization_tickets.map(|(_, t)| t).collect_vec();
+        transfer_tickets_on_blockchain(
+            &organization_tickets,
+            connection,
+            &*state.config.tari_client,
+            &Wallet::find_default_for_organization(organization_id, connection)?,
+            &user_wallet,
+        )?;
+    }
+    let mut ticket_responses = Vec::new();
+    for ticket in tickets {
+        let (event, user, ticket) = TicketInstance::find_for_display(ticket.id, connection)?;
+        ticket_responses.push(ShowTicketResponse { event, user, ticket });
+    }
 
+    Ok(HttpResponse::Ok().json(OpenLootBoxResponse {
+        draw: draw.for_display(),
+        tickets: ticket_responses,
+    }))
 }
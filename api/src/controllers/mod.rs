@@ -1,44 +1,67 @@
+pub mod access_zones;
+pub mod accounting_account_mappings;
 pub mod admin;
+pub mod affiliate_links;
 pub mod analytics;
 pub mod announcements;
 pub mod artists;
 pub mod auth;
 pub mod broadcasts;
 pub mod cart;
+pub mod cash_drawer_sessions;
+pub mod checkout_questions;
 pub mod codes;
 pub mod collection_items;
 pub mod collections;
 pub mod comps;
 pub mod event_report_subscribers;
+pub mod event_series;
 pub mod events;
 pub mod external;
+pub mod fan_segments;
 pub mod genres;
 pub mod holds;
+pub mod inventory_pools;
 pub mod ipns;
+pub mod ledger;
 pub mod listings;
+pub mod loot_boxes;
 pub mod notes;
 pub mod orders;
+pub mod organization_bank_accounts;
+pub mod organization_brandings;
 pub mod organization_invites;
 pub mod organization_venues;
 pub mod organizations;
+pub mod packages;
+pub mod partner_cart;
+pub mod partner_checkout_sessions;
+pub mod partners;
 pub mod password_resets;
 pub mod payment_methods;
 pub mod payments;
+pub mod products;
 pub mod rarities;
 pub mod redemption_codes;
 pub mod regions;
 pub mod reports;
 pub mod send_download_link;
 pub mod settlement_adjustments;
+pub mod settlement_payouts;
+pub mod settlement_statement_subscribers;
 pub mod settlements;
 pub mod sitemap_gen;
 pub mod slugs;
 pub mod stages;
 pub mod status;
+pub mod tax_rules;
+pub mod ticket_scans;
 pub mod ticket_types;
 pub mod tickets;
+pub mod trades;
 pub mod transfers;
 pub mod user_invites;
 pub mod users;
 pub mod venues;
+pub mod wallet_passes;
 pub mod websockets;
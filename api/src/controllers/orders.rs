@@ -79,6 +79,7 @@ pub async fn show(
         app_download_link: Option<String>,
     }
 
+    let front_end_url = order.front_end_url(&state.config.front_end_url, connection)?;
     let order = order.for_display(organization_id_filter, auth_user.id(), connection)?;
     let order_id = order.id;
     let mut result = R {
@@ -93,7 +94,7 @@ pub async fn show(
         let refresh_token = user.create_magic_link_token(token_issuer, Duration::minutes(60), false, connection)?;
         let fallback_url = format!(
             "{}/send-download-link?refresh_token={}",
-            &state.config.front_end_url,
+            front_end_url,
             refresh_token.unwrap_or("".to_string())
         );
         let mut data = HashMap::new();
@@ -105,6 +106,34 @@ pub async fn show(
     Ok(HttpResponse::Ok().json(json!(result)))
 }
 
+pub async fn checkout_answers(
+    (conn, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = conn.get();
+    let order = Order::find(path.id, connection)?;
+    if order.on_behalf_of_user_id.unwrap_or(order.user_id) != user.id() {
+        user.requires_scope_for_order(Scopes::OrderRead, &order, connection)?;
+    }
+
+    Ok(HttpResponse::Ok().json(CheckoutAnswersResponse::for_order(&order, connection)?))
+}
+
+pub async fn update_checkout_answers(
+    (conn, path, json, user): (Connection, Path<PathParameters>, Json<CheckoutAnswersRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = conn.get();
+    let order = Order::find(path.id, connection)?;
+    if order.on_behalf_of_user_id.unwrap_or(order.user_id) != user.id() {
+        return application::forbidden("You do not have access to this order");
+    }
+    if order.status != OrderStatus::Paid {
+        return application::unprocessable("Checkout answers can only be changed on paid orders");
+    }
+
+    CheckoutAnswer::save_for_order(&order, json.into_inner().answers, Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(CheckoutAnswersResponse::for_order(&order, connection)?))
+}
+
 pub async fn resend_confirmation(
     (conn, path, auth_user, state): (Connection, Path<PathParameters>, User, Data<AppState>),
 ) -> Result<HttpResponse, ApiError> {
@@ -343,6 +372,19 @@ pub async fn refund(
                     }
                 };
             }
+            // Credit spent on the order is returned to the user's balance
+            if payment.payment_method == PaymentMethods::Credit {
+                UserCreditEntry::create(
+                    order.on_behalf_of_user_id.unwrap_or(order.user_id),
+                    amount_to_refund,
+                    order.currency,
+                    Tables::Refunds,
+                    refund.id,
+                    "Refunded to credit".to_string(),
+                    Some(user.id()),
+                )
+                .commit(connection)?;
+            }
             payment.log_refund(user.id(), &refund, amount_to_refund, refund_data, connection)?;
             *refund_breakdown.entry(payment.payment_method).or_insert(0) += amount_to_refund;
             amount_refunded += amount_to_refund;
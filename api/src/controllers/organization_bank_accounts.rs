@@ -0,0 +1,44 @@
+use crate::auth::user::User as AuthUser;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::PathParameters;
+use crate::server::AppState;
+use actix_web::{
+    web::{Data, Path},
+    HttpResponse,
+};
+use db::models::*;
+
+pub async fn show(
+    (connection, path, user, state): (Connection, Path<PathParameters>, AuthUser, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFinancialReports, &organization, connection)?;
+
+    let mut bank_account = OrganizationBankAccount::find_for_organization(organization.id, connection)?;
+    bank_account.decrypt(&state.config.api_keys_encryption_key)?;
+    Ok(HttpResponse::Ok().json(&bank_account.for_display()))
+}
+
+pub async fn update(
+    (connection, path, json, user, state): (
+        Connection,
+        Path<PathParameters>,
+        Json<NewOrganizationBankAccount>,
+        AuthUser,
+        Data<AppState>,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &organization, connection)?;
+
+    let mut new_bank_account = json.into_inner();
+    new_bank_account.organization_id = organization.id;
+    let mut bank_account =
+        new_bank_account.commit(Some(user.id()), &state.config.api_keys_encryption_key, connection)?;
+    bank_account.decrypt(&state.config.api_keys_encryption_key)?;
+    Ok(HttpResponse::Ok().json(&bank_account.for_display()))
+}
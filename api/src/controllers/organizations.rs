@@ -28,6 +28,7 @@ pub struct FeeScheduleWithRanges {
     pub name: String,
     pub version: i16,
     pub created_at: NaiveDateTime,
+    pub currency: Currencies,
     pub ranges: Vec<FeeScheduleRange>,
 }
 
@@ -62,6 +63,8 @@ pub struct NewOrganizationRequest {
     pub cc_fee_percent: Option<f32>,
     pub max_instances_per_ticket_type: Option<i64>,
     pub settlement_type: Option<SettlementTypes>,
+    pub currency: Option<Currencies>,
+    pub default_locale: Option<Locales>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -155,6 +158,10 @@ pub async fn create(
             None => state.config.max_instances_per_ticket_type,
         }),
         settlement_type: new_organization.settlement_type,
+        currency: new_organization
+            .currency
+            .or_else(|| state.config.primary_currency.to_uppercase().parse().ok()),
+        default_locale: new_organization.default_locale,
     };
 
     let mut organization = new_organization_with_fee_schedule.commit(
@@ -333,6 +340,7 @@ pub async fn show_fee_schedule(
         name: fee_schedule.name,
         version: fee_schedule.version,
         created_at: fee_schedule.created_at,
+        currency: fee_schedule.currency,
         ranges: fee_schedule_ranges,
     }))
 }
@@ -358,6 +366,7 @@ pub async fn add_fee_schedule(
         name: fee_schedule.name,
         version: fee_schedule.version,
         created_at: fee_schedule.created_at,
+        currency: fee_schedule.currency,
         ranges: fee_schedule_ranges,
     }))
 }
@@ -0,0 +1,110 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::helpers::application;
+use crate::models::{CreatePackageRequest, PathParameters, WebPayload};
+use actix_web::{
+    http::StatusCode,
+    web::{Path, Query},
+    HttpResponse,
+};
+use db::models::*;
+
+pub async fn index(
+    (connection, path, query, user): (Connection, Path<PathParameters>, Query<PagingParameters>, User),
+) -> Result<WebPayload<Package>, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgRead, &organization, connection)?;
+
+    let payload = Package::find_for_organization(organization.id, query.page(), query.limit(), connection)?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+pub async fn index_for_event((connection, path): (Connection, Path<PathParameters>)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event = Event::find(path.id, connection)?;
+
+    let mut packages = Vec::new();
+    for package in Package::find_for_event(event.id, connection)? {
+        packages.push(package.for_display(connection)?);
+    }
+    Ok(HttpResponse::Ok().json(&packages))
+}
+
+pub async fn create(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<CreatePackageRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &organization, connection)?;
+
+    let request = json.into_inner();
+    if request.ticket_types.is_empty() {
+        return application::unprocessable("Packages require at least one ticket type");
+    }
+
+    let mut new_package = request.package;
+    new_package.organization_id = organization.id;
+    let package = new_package.commit(Some(user.id()), connection)?;
+    for mut new_package_ticket_type in request.ticket_types {
+        new_package_ticket_type.package_id = package.id;
+        new_package_ticket_type.commit(Some(user.id()), connection)?;
+    }
+
+    Ok(HttpResponse::Created().json(&package.for_display(connection)?))
+}
+
+pub async fn show((connection, path): (Connection, Path<PathParameters>)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let package = Package::find(path.id, connection)?;
+    Ok(HttpResponse::Ok().json(&package.for_display(connection)?))
+}
+
+pub async fn update(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<PackageEditableAttributes>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let package = Package::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &package.organization(connection)?, connection)?;
+
+    let package = package.update(json.into_inner(), Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(&package.for_display(connection)?))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let package = Package::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &package.organization(connection)?, connection)?;
+
+    package.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
+
+pub async fn add_ticket_type(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<NewPackageTicketType>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let package = Package::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &package.organization(connection)?, connection)?;
+
+    let mut new_package_ticket_type = json.into_inner();
+    new_package_ticket_type.package_id = package.id;
+    new_package_ticket_type.commit(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(&package.for_display(connection)?))
+}
+
+pub async fn remove_ticket_type(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let package_ticket_type = PackageTicketType::find(path.id, connection)?;
+    let package = Package::find(package_ticket_type.package_id, connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &package.organization(connection)?, connection)?;
+
+    package_ticket_type.destroy(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(&package.for_display(connection)?))
+}
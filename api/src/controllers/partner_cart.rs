@@ -0,0 +1,80 @@
+use crate::auth::user::User;
+use crate::controllers::cart::{self, CheckoutCartRequest, UpdateCartRequest};
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::*;
+use crate::server::AppState;
+use actix_web::{web::Data, HttpResponse};
+use db::prelude::*;
+use diesel::PgConnection;
+
+pub async fn show(
+    (connection, user, partner): (Connection, User, AuthorizedPartner),
+) -> Result<HttpResponse, ApiError> {
+    if let Some(cart) = Order::find_cart_for_user(user.id(), connection.get())? {
+        verify_partner(&cart, &partner)?;
+    }
+    cart::show((connection, user)).await
+}
+
+pub async fn update_cart(
+    (connection, json, user, request_info, partner): (
+        Connection,
+        Json<UpdateCartRequest>,
+        User,
+        RequestInfo,
+        AuthorizedPartner,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    partner_cart(&user, &partner, connection.get())?;
+    cart::update_cart((connection, json, user, request_info)).await
+}
+
+pub async fn replace_cart(
+    (connection, json, user, request_info, partner): (
+        Connection,
+        Json<UpdateCartRequest>,
+        User,
+        RequestInfo,
+        AuthorizedPartner,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    partner_cart(&user, &partner, connection.get())?;
+    cart::replace_cart((connection, json, user, request_info)).await
+}
+
+pub async fn checkout(
+    (connection, json, user, state, request_info, partner): (
+        Connection,
+        Json<CheckoutCartRequest>,
+        User,
+        Data<AppState>,
+        RequestInfo,
+        AuthorizedPartner,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    if Order::find_cart_for_user(user.id(), connection.get())?.is_some() {
+        partner_cart(&user, &partner, connection.get())?;
+    }
+    cart::checkout((connection, json, user, state, request_info)).await
+}
+
+/// Attributes the user's cart to the partner if it was started outside of a checkout session
+fn partner_cart(user: &User, partner: &AuthorizedPartner, conn: &PgConnection) -> Result<(), ApiError> {
+    let mut cart = Order::find_or_create_cart(&user.user, conn)?;
+    verify_partner(&cart, partner)?;
+    if cart.partner_id.is_none() {
+        cart.set_partner(&partner.partner, conn)?;
+    }
+    Ok(())
+}
+
+fn verify_partner(cart: &Order, partner: &AuthorizedPartner) -> Result<(), ApiError> {
+    match cart.partner_id {
+        Some(partner_id) if partner_id != partner.partner.id => {
+            Err(AuthError::unauthorized("Cart belongs to another partner").into())
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,57 @@
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::helpers::application;
+use crate::models::*;
+use crate::server::AppState;
+use actix_web::{web::Data, HttpResponse};
+use chrono::Duration;
+use db::prelude::*;
+
+const CHECKOUT_SESSION_EXPIRY_MINUTES: i64 = 120;
+
+/// Starts a guest checkout on a partner's site. A user is registered with only their email, or the
+/// partner's returning guest is found by their email, and given a short lived token limited to their own
+/// orders. The `/partner/cart` endpoints are then used with it.
+pub async fn create(
+    (state, connection, parameters, partner): (
+        Data<AppState>,
+        Connection,
+        Json<RegisterEmailOnlyRequest>,
+        AuthorizedPartner,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let user = match partner.partner.find_guest_by_email(&parameters.email, connection)? {
+        Some(user) => user,
+        None => {
+            let new_user: NewUser = parameters.into_inner().into();
+            let user = match new_user.commit(None, connection) {
+                Ok(user) => user,
+                Err(e) => match e.error_code {
+                    // Existing accounts must sign in, a partner cannot obtain a token for them by email alone
+                    ErrorCode::DuplicateKeyError => {
+                        return application::unprocessable("A user with this email already exists");
+                    }
+                    _ => return Err(e.into()),
+                },
+            };
+            partner.partner.add_guest(user.id, connection)?;
+            user
+        }
+    };
+
+    let mut cart = Order::find_or_create_cart(&user, connection)?;
+    cart.set_partner(&partner.partner, connection)?;
+
+    let access_token = state.config.token_issuer.issue_with_limited_scopes(
+        user.id,
+        vec![Scopes::OrderReadOwn],
+        Duration::minutes(CHECKOUT_SESSION_EXPIRY_MINUTES),
+    )?;
+    let cart = Order::find(cart.id, connection)?.for_display(None, user.id, connection)?;
+    Ok(HttpResponse::Created().json(json!({
+        "access_token": access_token,
+        "cart": cart,
+    })))
+}
@@ -0,0 +1,69 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::PathParameters;
+use actix_web::{web::Path, HttpResponse};
+use db::models::*;
+
+pub async fn index((connection, user): (Connection, User)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::OrgAdmin)?;
+
+    Ok(HttpResponse::Ok().json(&Partner::all(connection)?))
+}
+
+pub async fn create((connection, json, user): (Connection, Json<NewPartner>, User)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::OrgAdmin)?;
+
+    let partner = json.into_inner().commit(Some(user.id()), connection)?;
+    let api_key = partner.generate_api_key(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(json!({
+        "partner": partner,
+        "api_key": api_key,
+    })))
+}
+
+pub async fn show(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::OrgAdmin)?;
+
+    Ok(HttpResponse::Ok().json(&Partner::find(path.id, connection)?))
+}
+
+pub async fn update(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<PartnerEditableAttributes>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::OrgAdmin)?;
+
+    let partner = Partner::find(path.id, connection)?;
+    let partner = partner.update(json.into_inner(), Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(&partner))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::OrgAdmin)?;
+
+    let partner = Partner::find(path.id, connection)?;
+    partner.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
+
+/// Replaces the partner's API key, the new key is only returned in this response
+pub async fn create_api_key(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::OrgAdmin)?;
+
+    let partner = Partner::find(path.id, connection)?;
+    let api_key = partner.generate_api_key(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(json!({ "api_key": api_key })))
+}
@@ -78,7 +78,7 @@ pub async fn callback(
         }
         application::redirect(&format!(
             "{}/tickets/{}/tickets/success?order_id={}",
-            state.config.front_end_url,
+            order.front_end_url(&state.config.front_end_url, conn)?,
             order.event_slug(conn)?,
             order.id
         ))
@@ -91,7 +91,7 @@ pub async fn callback(
         // order.reset_to_draft(None, conn)?;
         application::redirect(&format!(
             "{}/tickets/{}/tickets/confirmation",
-            state.config.front_end_url,
+            order.front_end_url(&state.config.front_end_url, conn)?,
             order.event_slug(conn)?
         ))
     }
@@ -0,0 +1,127 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::helpers::application;
+use crate::models::{CreateProductRequest, PathParameters, WebPayload};
+use actix_web::{
+    http::StatusCode,
+    web::{Path, Query},
+    HttpResponse,
+};
+use db::models::*;
+
+pub async fn index(
+    (connection, path, query, user): (Connection, Path<PathParameters>, Query<PagingParameters>, User),
+) -> Result<WebPayload<Product>, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgRead, &organization, connection)?;
+
+    let payload = Product::find_for_organization(organization.id, query.page(), query.limit(), connection)?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+pub async fn index_for_event((connection, path): (Connection, Path<PathParameters>)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let event = Event::find(path.id, connection)?;
+
+    let mut products = Vec::new();
+    for product in Product::find_for_event(event.id, connection)? {
+        products.push(product.for_display(connection)?);
+    }
+    Ok(HttpResponse::Ok().json(&products))
+}
+
+pub async fn create(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<CreateProductRequest>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &organization, connection)?;
+
+    let request = json.into_inner();
+    if request.variants.is_empty() {
+        return application::unprocessable("Products require at least one variant");
+    }
+
+    let mut new_product = request.product;
+    new_product.organization_id = organization.id;
+    let product = new_product.commit(Some(user.id()), connection)?;
+    for mut new_variant in request.variants {
+        new_variant.product_id = product.id;
+        new_variant.commit(Some(user.id()), connection)?;
+    }
+
+    Ok(HttpResponse::Created().json(&product.for_display(connection)?))
+}
+
+pub async fn show((connection, path): (Connection, Path<PathParameters>)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let product = Product::find(path.id, connection)?;
+    Ok(HttpResponse::Ok().json(&product.for_display(connection)?))
+}
+
+pub async fn update(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<ProductEditableAttributes>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let product = Product::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &product.organization(connection)?, connection)?;
+
+    let product = product.update(json.into_inner(), Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(&product.for_display(connection)?))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let product = Product::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &product.organization(connection)?, connection)?;
+
+    product.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
+
+pub async fn create_variant(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<NewProductVariant>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let product = Product::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &product.organization(connection)?, connection)?;
+
+    let mut new_variant = json.into_inner();
+    new_variant.product_id = product.id;
+    let variant = new_variant.commit(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(&variant.for_display(&product, connection)?))
+}
+
+pub async fn update_variant(
+    (connection, path, json, user): (
+        Connection,
+        Path<PathParameters>,
+        Json<ProductVariantEditableAttributes>,
+        User,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let variant = ProductVariant::find(path.id, connection)?;
+    let product = variant.product(connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &product.organization(connection)?, connection)?;
+
+    let variant = variant.update(json.into_inner(), Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(&variant.for_display(&product, connection)?))
+}
+
+pub async fn destroy_variant(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let variant = ProductVariant::find(path.id, connection)?;
+    let product = variant.product(connection)?;
+    user.requires_scope_for_organization(Scopes::EventWrite, &product.organization(connection)?, connection)?;
+
+    variant.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
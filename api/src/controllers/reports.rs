@@ -4,7 +4,7 @@ use crate::errors::*;
 use crate::helpers::application;
 use crate::models::{PathParameters, WebPayload};
 use actix_web::{
-    http::StatusCode,
+    http::{header, StatusCode},
     web::{Path, Query},
     HttpResponse,
 };
@@ -21,6 +21,9 @@ pub struct ReportQueryParameters {
     pub start_utc: Option<NaiveDateTime>,
     pub end_utc: Option<NaiveDateTime>,
     pub event_id: Option<Uuid>,
+    pub event_series_id: Option<Uuid>,
+    pub period: Option<ReportPeriods>,
+    pub currency: Option<Currencies>,
     #[serde(default, deserialize_with = "deserialize_unless_blank")]
     query: Option<String>,
     page: Option<u32>,
@@ -62,6 +65,7 @@ pub async fn get_report(
         "box_office_sales_summary" => box_office_sales_summary((connection, query, path, user)),
         "transaction_details" => Ok(transaction_detail_report((connection, query, path, user))?.into_http_response()?),
         "event_summary" => event_summary_report((connection, query, path, user)),
+        "event_series" => event_series_report((connection, query, path, user)),
         "scan_count" => scan_counts((connection, query, user)),
         "weekly_settlement" => weekly_settlement_report((connection, query, path, user)),
         "ticket_count" => ticket_counts((connection, query, path, user)),
@@ -69,6 +73,11 @@ pub async fn get_report(
         "reconciliation_summary" => reconciliation_summary_report((connection, query, path, user)),
         "reconciliation_details" => reconciliation_detail_report((connection, query, path, user)),
         "promo_code" => promo_code_report((connection, query, path, user)),
+        "tax_liability" => tax_liability_report((connection, query, path, user)),
+        "quickbooks_journal" => {
+            accounting_export((connection, query, path, user), AccountingExportFormats::QuickbooksIif)
+        }
+        "xero_journal" => accounting_export((connection, query, path, user), AccountingExportFormats::XeroCsv),
         _ => application::not_found(),
     }
 }
@@ -134,6 +143,24 @@ pub fn event_summary_report(
     Ok(HttpResponse::Ok().json(result))
 }
 
+pub fn event_series_report(
+    (connection, query, path, user): (Connection, Query<ReportQueryParameters>, Path<PathParameters>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgReports, &organization, connection)?;
+    let event_series = match query.event_series_id {
+        Some(event_series_id) => EventSeries::find(event_series_id, connection)?,
+        None => return application::bad_request("event_series_id parameter is required"),
+    };
+    if event_series.organization_id != organization.id {
+        return application::not_found();
+    }
+
+    let result = Report::event_series_report(event_series.id, connection)?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
 pub fn audit_report(
     (connection, query, path, user): (Connection, Query<ReportQueryParameters>, Path<PathParameters>, AuthUser),
 ) -> Result<HttpResponse, ApiError> {
@@ -268,3 +295,45 @@ pub fn reconciliation_detail_report(
     let result = Report::reconciliation_detail_report(path.id, query.start_utc, query.end_utc, connection)?;
     Ok(HttpResponse::Ok().json(result))
 }
+
+pub fn tax_liability_report(
+    (connection, query, path, user): (Connection, Query<ReportQueryParameters>, Path<PathParameters>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFinancialReports, &organization, connection)?;
+
+    let result = Report::tax_liability_report(
+        path.id,
+        query.start_utc,
+        query.end_utc,
+        query.period.unwrap_or(ReportPeriods::Month),
+        connection,
+    )?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+pub fn accounting_export(
+    (connection, query, path, user): (Connection, Query<ReportQueryParameters>, Path<PathParameters>, AuthUser),
+    format: AccountingExportFormats,
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFinancialReports, &organization, connection)?;
+
+    let export = AccountingExport::generate(
+        &organization,
+        query.currency.unwrap_or(organization.currency),
+        query.start_utc,
+        query.end_utc,
+        format,
+        connection,
+    )?;
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", export.file_name),
+        )
+        .body(export.content))
+}
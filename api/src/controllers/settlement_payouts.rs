@@ -0,0 +1,106 @@
+use crate::auth::user::User as AuthUser;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::{PathParameters, WebPayload};
+use crate::server::AppState;
+use actix_web::{
+    http::{header, StatusCode},
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use db::models::*;
+
+pub async fn index(
+    (connection, path, user): (Connection, Path<PathParameters>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let settlement = Settlement::find(path.id, connection)?;
+    let organization = Organization::find(settlement.organization_id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgFinancialReports, &organization, connection)?;
+    Ok(HttpResponse::Ok().json(settlement.payouts(connection)?))
+}
+
+/// Creates payouts for finalized settlements processed before the organization added their bank account
+pub async fn create(
+    (connection, path, user): (Connection, Path<PathParameters>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    user.requires_scope(Scopes::SettlementWrite)?;
+    let connection = connection.get();
+    let settlement = Settlement::find(path.id, connection)?;
+    let payouts = settlement.create_payouts(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(&payouts))
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct UpdateSettlementPayoutRequest {
+    pub status: SettlementPayoutStatus,
+    pub note: Option<String>,
+}
+
+pub async fn update(
+    (connection, path, json, user): (
+        Connection,
+        Path<PathParameters>,
+        Json<UpdateSettlementPayoutRequest>,
+        AuthUser,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    user.requires_scope(Scopes::SettlementWrite)?;
+    let connection = connection.get();
+    let payout = SettlementPayout::find(path.id, connection)?;
+    let payout = payout.update_status(json.status, json.note.clone(), Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(&payout))
+}
+
+pub async fn batches(
+    (connection, query, user): (Connection, Query<PagingParameters>, AuthUser),
+) -> Result<WebPayload<SettlementPayoutBatch>, ApiError> {
+    user.requires_scope(Scopes::SettlementWrite)?;
+    let connection = connection.get();
+    let payload = SettlementPayoutBatch::find_all(query.page(), query.limit(), connection)?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct CreateSettlementPayoutBatchRequest {
+    pub payout_method: PayoutMethods,
+}
+
+/// Batches the initiated payouts of the payout method that have not been exported yet, the bank
+/// transfer file for the batch is then downloaded with `batch_file`
+pub async fn create_batch(
+    (connection, json, user): (Connection, Json<CreateSettlementPayoutBatchRequest>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    user.requires_scope(Scopes::SettlementWrite)?;
+    let connection = connection.get();
+    let batch = SettlementPayoutBatch::create_for_unbatched_payouts(json.payout_method, Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(&batch))
+}
+
+/// Downloads the bank transfer file for the batch, batches can be downloaded again without
+/// including any payouts exported since
+pub async fn batch_file(
+    (connection, path, user, state): (Connection, Path<PathParameters>, AuthUser, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    user.requires_scope(Scopes::SettlementWrite)?;
+    let connection = connection.get();
+    let batch = SettlementPayoutBatch::find(path.id, connection)?;
+    let file = batch.file(
+        &state.config.payout_originator,
+        &state.config.api_keys_encryption_key,
+        connection,
+    )?;
+
+    let (content_type, extension) = match batch.payout_method {
+        PayoutMethods::Nacha => ("text/plain", "ach"),
+        PayoutMethods::Sepa => ("application/xml", "xml"),
+    };
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"payouts-{}.{}\"", batch.id, extension),
+        )
+        .body(file))
+}
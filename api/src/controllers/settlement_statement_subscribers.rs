@@ -0,0 +1,59 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::ApiError;
+use crate::extractors::*;
+use crate::models::{PathParameters, WebPayload, WebResult};
+use actix_web::{http::StatusCode, web::Path, HttpResponse};
+use db::models::*;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize)]
+pub struct NewSettlementStatementSubscriberRequest {
+    /// Defaults to the current user, subscribing other members requires organization write access
+    pub user_id: Option<Uuid>,
+}
+
+pub async fn index(
+    (conn, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<WebPayload<SettlementStatementSubscriber>, ApiError> {
+    let conn = conn.get();
+    let organization = Organization::find(path.id, conn)?;
+    user.requires_scope_for_organization(Scopes::SettlementRead, &organization, conn)?;
+
+    let subscribers = SettlementStatementSubscriber::find_for_organization(organization.id, conn)?;
+    let payload: Payload<SettlementStatementSubscriber> = subscribers.into();
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+pub async fn create(
+    (conn, subscriber_request, path, user): (
+        Connection,
+        Json<NewSettlementStatementSubscriberRequest>,
+        Path<PathParameters>,
+        User,
+    ),
+) -> Result<WebResult<SettlementStatementSubscriber>, ApiError> {
+    let conn = conn.get();
+    let organization = Organization::find(path.id, conn)?;
+    user.requires_scope_for_organization(Scopes::SettlementRead, &organization, conn)?;
+    let user_id = subscriber_request.user_id.unwrap_or(user.id());
+    if user_id != user.id() {
+        user.requires_scope_for_organization(Scopes::OrgWrite, &organization, conn)?;
+    }
+
+    let subscriber = SettlementStatementSubscriber::create(organization.id, user_id).commit(Some(user.id()), conn)?;
+    Ok(WebResult::new(StatusCode::CREATED, subscriber))
+}
+
+pub async fn destroy((conn, path, user): (Connection, Path<PathParameters>, User)) -> Result<HttpResponse, ApiError> {
+    let conn = conn.get();
+    let subscriber = SettlementStatementSubscriber::find(path.id, conn)?;
+    let organization = Organization::find(subscriber.organization_id, conn)?;
+    user.requires_scope_for_organization(Scopes::SettlementRead, &organization, conn)?;
+    if subscriber.user_id != user.id() {
+        user.requires_scope_for_organization(Scopes::OrgWrite, &organization, conn)?;
+    }
+
+    subscriber.destroy(Some(user.id()), conn)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
@@ -5,12 +5,13 @@ use crate::extractors::*;
 use crate::helpers::application;
 use crate::models::{PathParameters, WebPayload};
 use actix_web::{
-    http::StatusCode,
+    http::{header, StatusCode},
     web::{Path, Query},
     HttpResponse,
 };
 use chrono::prelude::*;
 use db::models::*;
+use db::utils::errors::Optional;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct NewSettlementRequest {
@@ -19,6 +20,18 @@ pub struct NewSettlementRequest {
     pub comment: Option<String>,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementFormat {
+    Csv,
+    Pdf,
+}
+
+#[derive(Deserialize)]
+pub struct StatementParameters {
+    pub format: StatementFormat,
+}
+
 pub async fn index(
     (connection, query, path, user): (Connection, Query<PagingParameters>, Path<PathParameters>, AuthUser),
 ) -> Result<WebPayload<Settlement>, ApiError> {
@@ -75,6 +88,46 @@ pub async fn show(
     Ok(HttpResponse::Ok().json(&display_settlement))
 }
 
+pub async fn statement(
+    (connection, path, query, user): (Connection, Path<PathParameters>, Query<StatementParameters>, AuthUser),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let settlement = Settlement::find(path.id, connection)?;
+    let organization = Organization::find(settlement.organization_id, connection)?;
+    user.requires_scope_for_organization(Scopes::SettlementRead, &organization, connection)?;
+
+    if !user.has_scope_for_organization(Scopes::SettlementReadEarly, &organization, connection)?
+        && settlement.status != SettlementStatus::FinalizedSettlement
+    {
+        return application::unauthorized_with_message("Unauthorized access of settlement", None, None);
+    }
+
+    // Statements are stored when settlements are finalized, pending settlements are rendered on request
+    let (csv_content, pdf_content) =
+        match SettlementStatement::find_for_settlement(settlement.id, connection).optional()? {
+            Some(statement) => (statement.csv_content, statement.pdf_content),
+            None => {
+                let statement = SettlementStatement::render(&settlement, connection)?;
+                (statement.csv_content, statement.pdf_content)
+            }
+        };
+
+    let (content_type, extension, body) = match query.format {
+        StatementFormat::Csv => ("text/csv", "csv", csv_content.into_bytes()),
+        StatementFormat::Pdf => ("application/pdf", "pdf", pdf_content),
+    };
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}\"",
+                SettlementStatement::file_name(&settlement, extension)
+            ),
+        )
+        .body(body))
+}
+
 pub async fn destroy(
     (connection, path, user): (Connection, Path<PathParameters>, AuthUser),
 ) -> Result<HttpResponse, ApiError> {
@@ -0,0 +1,67 @@
+use crate::auth::user::User;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::models::{PathParameters, WebPayload};
+use actix_web::{
+    http::StatusCode,
+    web::{Path, Query},
+    HttpResponse,
+};
+use db::models::*;
+
+pub async fn index(
+    (connection, path, query, user): (Connection, Path<PathParameters>, Query<PagingParameters>, User),
+) -> Result<WebPayload<TaxRule>, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgRead, &organization, connection)?;
+
+    let payload = TaxRule::find_for_organization(organization.id, query.page(), query.limit(), connection)?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+pub async fn create(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<NewTaxRule>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let organization = Organization::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &organization, connection)?;
+
+    let mut new_tax_rule = json.into_inner();
+    new_tax_rule.organization_id = organization.id;
+    let tax_rule = new_tax_rule.commit(Some(user.id()), connection)?;
+    Ok(HttpResponse::Created().json(&tax_rule))
+}
+
+pub async fn show(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let tax_rule = TaxRule::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgRead, &tax_rule.organization(connection)?, connection)?;
+
+    Ok(HttpResponse::Ok().json(&tax_rule))
+}
+
+pub async fn update(
+    (connection, path, json, user): (Connection, Path<PathParameters>, Json<TaxRuleEditableAttributes>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let tax_rule = TaxRule::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &tax_rule.organization(connection)?, connection)?;
+
+    let tax_rule = tax_rule.update(json.into_inner(), Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(&tax_rule))
+}
+
+pub async fn destroy(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let tax_rule = TaxRule::find(path.id, connection)?;
+    user.requires_scope_for_organization(Scopes::OrgWrite, &tax_rule.organization(connection)?, connection)?;
+
+    tax_rule.delete(Some(user.id()), connection)?;
+    Ok(HttpResponse::Ok().json(json!({})))
+}
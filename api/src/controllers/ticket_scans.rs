@@ -0,0 +1,148 @@
+use crate::auth::user::User;
+use crate::database::{CacheDatabase, Connection};
+use crate::errors::*;
+use crate::extractors::*;
+use crate::helpers::caching;
+use crate::models::{PathParameters, WebPayload};
+use crate::server::AppState;
+use crate::utils::redis::*;
+use actix_web::{
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use db::prelude::*;
+use reqwest::StatusCode;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize)]
+pub struct TicketScanRequest {
+    pub redeem_key: String,
+    /// Rotating code shown by the holder's app, required for events using rotating barcodes
+    #[serde(default)]
+    pub redeem_code: Option<String>,
+    pub direction: ScanDirections,
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct TicketScanFilterParameters {
+    pub access_zone_id: Option<Uuid>,
+    pub result: Option<ScanResults>,
+}
+
+/// Scan log of accepted and rejected attempts for the event
+pub async fn index(
+    (connection, path, query, filter, user): (
+        Connection,
+        Path<PathParameters>,
+        Query<PagingParameters>,
+        Query<TicketScanFilterParameters>,
+        User,
+    ),
+) -> Result<WebPayload<TicketScan>, ApiError> {
+    let connection = connection.get();
+    let event = Event::find(path.id, connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::ScanReportRead,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let payload = TicketScan::find_for_event(
+        event.id,
+        filter.access_zone_id,
+        filter.result,
+        query.page(),
+        query.limit(),
+        connection,
+    )?;
+    Ok(WebPayload::new(StatusCode::OK, payload))
+}
+
+/// Scans a ticket into or out of the access zone. Rejected scans are still recorded in the scan log
+/// so they are returned with an error status rather than as an error.
+pub async fn create(
+    (connection, path, json, user, state, cache_database): (
+        Connection,
+        Path<PathParameters>,
+        Json<TicketScanRequest>,
+        User,
+        Data<AppState>,
+        CacheDatabase,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let access_zone = AccessZone::find(path.id, connection)?;
+    let event = access_zone.event(connection)?;
+    user.requires_scope_for_organization_event(
+        Scopes::RedeemTicket,
+        &event.organization(connection)?,
+        &event,
+        connection,
+    )?;
+
+    let json = json.into_inner();
+    let ticket =
+        TicketInstance::find_by_event_id_redeem_key(event.id, json.redeem_key.clone(), connection).optional()?;
+    let scan = TicketScan::scan(
+        &access_zone,
+        ticket.as_ref(),
+        json.redeem_key,
+        json.redeem_code.as_ref().map(|c| c.as_str()),
+        json.direction,
+        user.id(),
+        json.device_id,
+        connection,
+    )?;
+    if scan.result == ScanResults::Rejected {
+        return Ok(HttpResponse::BadRequest().json(&scan));
+    }
+
+    if let Some(ticket) = ticket.filter(|t| t.status == TicketInstanceStatus::Purchased) {
+        // The ticket was redeemed by this scan so redeem it on chain as well
+        let asset = Asset::find(ticket.asset_id, connection)?;
+        let blockchain_asset_id = asset.blockchain_asset_id.ok_or_else(|| {
+            ApplicationError::new(
+                "Could not redeem because the asset has not been assigned on the blockchain.".to_string(),
+            )
+        })?;
+        let wallet = Wallet::find(ticket.wallet_id, connection)?;
+        state.config.tari_client.modify_asset_redeem_token(
+            &wallet.secret_key,
+            &wallet.public_key,
+            &blockchain_asset_id,
+            vec![ticket.token_id as u64],
+        )?;
+
+        cache_database.inner.clone().and_then(|conn| {
+            caching::publish(
+                conn,
+                RedisPubSubChannel::TicketRedemptions,
+                messages::TicketRedemption {
+                    ticket_id: ticket.id,
+                    event_id: event.id,
+                    redeemer_id: user.id(),
+                },
+            )
+            .ok()
+        });
+    }
+
+    let occupancy = access_zone.occupancy(connection)?;
+    cache_database.inner.clone().and_then(|conn| {
+        caching::publish(
+            conn,
+            RedisPubSubChannel::ZoneOccupancy,
+            messages::ZoneOccupancy {
+                event_id: event.id,
+                access_zone_id: access_zone.id,
+                occupancy,
+            },
+        )
+        .ok()
+    });
+
+    Ok(HttpResponse::Ok().json(&scan))
+}
@@ -1,4 +1,5 @@
 use crate::auth::user::User;
+use crate::config::Config;
 use crate::database::Connection;
 use crate::errors::*;
 use crate::extractors::*;
@@ -69,6 +70,8 @@ pub struct CreateTicketTypeRequest {
     pub rarity_id: Option<Uuid>,
     #[serde(default)]
     pub promo_image_url: Option<String>,
+    #[serde(default)]
+    pub stage_id: Option<Uuid>,
 }
 
 impl Default for CreateTicketTypeRequest {
@@ -95,6 +98,7 @@ impl Default for CreateTicketTypeRequest {
             contents: vec![],
             rarity_id: None,
             promo_image_url: None,
+            stage_id: None,
         }
     }
 }
@@ -158,6 +162,8 @@ pub struct UpdateTicketTypeRequest {
     #[serde(default)]
     pub app_sales_enabled: Option<bool>,
     pub rank: Option<i32>,
+    #[serde(default, deserialize_with = "double_option::deserialize")]
+    pub stage_id: Option<Option<Uuid>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -365,7 +371,10 @@ pub async fn update(
         app_sales_enabled: data.app_sales_enabled,
         rank: data.rank,
     };
-    let updated_ticket_type = ticket_type.update(update_parameters, Some(user.id()), connection)?;
+    let mut updated_ticket_type = ticket_type.update(update_parameters, Some(user.id()), connection)?;
+    if let Some(stage_id) = data.stage_id {
+        updated_ticket_type = updated_ticket_type.update_stage(stage_id, Some(user.id()), connection)?;
+    }
 
     if let Some(ref data_ticket_pricing) = data.ticket_pricing {
         //Retrieve the current list of pricing associated with this ticket_type and remove unwanted pricing
@@ -487,7 +496,7 @@ fn nullify_tickets(
 pub(crate) fn create_ticket_type_blockchain_assets(
     event: &Event,
     ticket_types: &[TicketType],
-    state: &Data<AppState>,
+    config: &Config,
     connection: &PgConnection,
 ) -> Result<(), ApiError> {
     //Retrieve default wallet
@@ -495,7 +504,7 @@ pub(crate) fn create_ticket_type_blockchain_assets(
 
     // Only create the blockchain assets after all of the ticket types have succeeded
     for data in ticket_types {
-        let tari_asset_id = state.config.tari_client.create_asset(
+        let tari_asset_id = config.tari_client.create_asset(
             &org_wallet.secret_key,
             &org_wallet.public_key,
             TariNewAsset {
@@ -571,6 +580,10 @@ fn create_ticket_types(
             Some(user.id()),
             connection,
         )?;
+        let ticket_type = match ticket_type_data.stage_id {
+            Some(stage_id) => ticket_type.update_stage(Some(stage_id), Some(user.id()), connection)?,
+            None => ticket_type,
+        };
         //Add each ticket pricing entry for newly created ticket type
         for current_pricing_entry in &ticket_type_data.ticket_pricing {
             let _pricing_result = ticket_type.add_ticket_pricing(
@@ -589,7 +602,7 @@ fn create_ticket_types(
         results.push(ticket_type);
     }
 
-    create_ticket_type_blockchain_assets(event, &results, state, connection)?;
+    create_ticket_type_blockchain_assets(event, &results, &state.config, connection)?;
 
     Ok(results.iter().map(|r| DisplayCreatedTicket { id: r.id }).collect())
 }
@@ -0,0 +1,210 @@
+use crate::auth::user::User;
+use crate::controllers::tickets::transfer_tickets_on_blockchain;
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::helpers::application;
+use crate::models::PathParameters;
+use crate::payments::{PaymentProcessor, PaymentProcessorBehavior};
+use crate::server::AppState;
+use crate::utils::ServiceLocator;
+use crate::SITE_NAME;
+use actix_web::{
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use db::prelude::*;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct ProposeTradeRequest {
+    pub recipient_user_id: Uuid,
+    #[serde(default)]
+    pub offered_ticket_ids: Vec<Uuid>,
+    pub requested_ticket_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub cash_in_cents: i64,
+    pub message: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TradeParameters {
+    pub status: Option<TradeStatus>,
+}
+
+pub async fn index(
+    (connection, query, user): (Connection, Query<TradeParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let mut trades = Vec::new();
+    for trade in Trade::find_for_user(user.id(), query.status, connection)? {
+        trades.push(trade.for_display(connection)?);
+    }
+    Ok(HttpResponse::Ok().json(trades))
+}
+
+pub async fn show(
+    (connection, path, user): (Connection, Path<PathParameters>, User),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let trade = Trade::find(path.id, connection)?;
+    if trade.proposed_by_user_id != user.id() && trade.recipient_user_id != user.id() {
+        return application::unauthorized(Some(user), None);
+    }
+    Ok(HttpResponse::Ok().json(trade.for_display(connection)?))
+}
+
+/// Proposes a trade, placing a hold on the proposer's saved card for any cash offered
+pub async fn create(
+    (connection, json, user, state): (Connection, Json<ProposeTradeRequest>, User, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::TicketTransfer)?;
+    let request = json.into_inner();
+    let trade = Trade::propose(
+        user.id(),
+        request.recipient_user_id,
+        &request.offered_ticket_ids,
+        &request.requested_ticket_ids,
+        request.cash_in_cents,
+        request.message,
+        connection,
+    )?;
+
+    let trade = if trade.cash_in_cents > 0 {
+        let payment_method = match user
+            .user
+            .payment_method(PaymentProviders::Stripe, connection)
+            .optional()?
+        {
+            Some(payment_method) => payment_method,
+            None => return application::unprocessable("A saved card is required to offer cash in a trade"),
+        };
+        let client = payment_processor_for_trade(&trade, &state.service_locator)?;
+        let behavior = match client.behavior() {
+            PaymentProcessorBehavior::AuthThenComplete(behavior) => behavior,
+            _ => return application::unprocessable("Cash cannot be offered with this payment processor"),
+        };
+        let auth_result = behavior
+            .auth(
+                &payment_method.provider,
+                trade.cash_in_cents,
+                &trade.currency.to_string(),
+                SITE_NAME,
+                vec![("trade_id".to_string(), trade.id.to_string())],
+            )
+            .await?;
+        match trade.set_payment(behavior.payment_provider(), auth_result.id.clone(), connection) {
+            Ok(trade) => trade,
+            Err(e) => {
+                client.refund(&auth_result.id).await?;
+                return Err(e.into());
+            }
+        }
+    } else {
+        trade
+    };
+
+    Ok(HttpResponse::Created().json(trade.for_display(connection)?))
+}
+
+/// Swaps the tickets, captures the cash held in escrow into the recipient's credit balance and moves
+/// the tokens on the blockchain
+pub async fn accept(
+    (connection, path, user, state): (Connection, Path<PathParameters>, User, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::TicketTransfer)?;
+    let trade = Trade::find(path.id, connection)?.accept(user.id(), connection)?;
+    let proposer_wallet = Wallet::find_default_for_user(trade.proposed_by_user_id, connection)?;
+    let recipient_wallet = Wallet::find_default_for_user(trade.recipient_user_id, connection)?;
+    let offered_tickets = trade.tickets_from(trade.proposed_by_user_id, connection)?;
+    let requested_tickets = trade.tickets_from(trade.recipient_user_id, connection)?;
+
+    // Cash is captured before any tokens move so a failed capture leaves the blockchain untouched
+    let escrow = match trade.external_payment_reference.clone() {
+        Some(reference) => {
+            let client = payment_processor_for_trade(&trade, &state.service_locator)?;
+            match client.behavior() {
+                PaymentProcessorBehavior::AuthThenComplete(behavior) => {
+                    behavior.complete_authed_charge(&reference).await?;
+                }
+                _ => return application::unprocessable("Cash cannot be captured with this payment processor"),
+            }
+            Some((client, reference))
+        }
+        None => None,
+    };
+
+    // Tokens moved before a failure are picked up by the asset reconciliation job
+    let blockchain_result = transfer_tickets_on_blockchain(
+        &offered_tickets,
+        connection,
+        &*state.config.tari_client,
+        &proposer_wallet,
+        &recipient_wallet,
+    )
+    .and_then(|_| {
+        transfer_tickets_on_blockchain(
+            &requested_tickets,
+            connection,
+            &*state.config.tari_client,
+            &recipient_wallet,
+            &proposer_wallet,
+        )
+    });
+    if let Err(error) = blockchain_result {
+        if let Some((client, reference)) = escrow {
+            client.refund(&reference).await?;
+        }
+        return Err(error);
+    }
+
+    Ok(HttpResponse::Ok().json(trade.for_display(connection)?))
+}
+
+pub async fn decline(
+    (connection, path, user, state): (Connection, Path<PathParameters>, User, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::TicketTransfer)?;
+    let trade = Trade::find(path.id, connection)?.decline(user.id(), connection)?;
+    release_escrow(&trade, &state.service_locator).await?;
+    Ok(HttpResponse::Ok().json(trade.for_display(connection)?))
+}
+
+pub async fn cancel(
+    (connection, path, user, state): (Connection, Path<PathParameters>, User, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    user.requires_scope(Scopes::TicketTransfer)?;
+    let trade = Trade::find(path.id, connection)?.cancel(user.id(), connection)?;
+    release_escrow(&trade, &state.service_locator).await?;
+    Ok(HttpResponse::Ok().json(trade.for_display(connection)?))
+}
+
+/// Balances of the cash the current user received in trades
+pub async fn credits((connection, user): (Connection, User)) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    Ok(HttpResponse::Ok().json(json!({
+        "balances": UserCreditEntry::balances_for_user(user.id(), connection)?,
+        "entries": UserCreditEntry::find_for_user(user.id(), connection)?,
+    })))
+}
+
+async fn release_escrow(trade: &Trade, service_locator: &ServiceLocator) -> Result<(), ApiError> {
+    if let Some(reference) = &trade.external_payment_reference {
+        payment_processor_for_trade(trade, service_locator)?
+            .refund(reference)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Trades are between fans so the cash is held by the platform's processor, never an organization's
+pub(crate) fn payment_processor_for_trade(
+    trade: &Trade,
+    service_locator: &ServiceLocator,
+) -> Result<Box<dyn PaymentProcessor>, ApiError> {
+    service_locator.create_platform_payment_processor(trade.payment_provider.unwrap_or(PaymentProviders::Stripe))
+}
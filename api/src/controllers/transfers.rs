@@ -161,6 +161,7 @@ pub async fn cancel(
                         &state.config,
                         transfer_address.clone(),
                         &source_user,
+                        &transfer,
                         connection,
                     )?;
                 }
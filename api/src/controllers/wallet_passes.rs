@@ -0,0 +1,288 @@
+use crate::auth::user::User;
+use crate::config::{AppleWalletConfig, Config};
+use crate::database::Connection;
+use crate::errors::*;
+use crate::extractors::*;
+use crate::helpers::application;
+use crate::models::PathParameters;
+use crate::server::AppState;
+use crate::utils::wallet_passes::{apple, google};
+use actix_web::{
+    http::header,
+    web::{Data, Path, Query},
+    HttpRequest, HttpResponse,
+};
+use chrono::prelude::*;
+use db::prelude::*;
+use diesel::pg::PgConnection;
+use log::Level::Warn;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct DevicePathParameters {
+    pub device_library_identifier: String,
+    pub pass_type_identifier: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeviceRegistrationPathParameters {
+    pub device_library_identifier: String,
+    pub pass_type_identifier: String,
+    pub serial_number: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct PassPathParameters {
+    pub pass_type_identifier: String,
+    pub serial_number: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct DeviceRegistrationRequest {
+    #[serde(rename = "pushToken")]
+    pub push_token: String,
+}
+
+#[derive(Deserialize)]
+pub struct UpdatedPassesParameters {
+    #[serde(rename = "passesUpdatedSince")]
+    pub passes_updated_since: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct UpdatedPassesResponse {
+    #[serde(rename = "serialNumbers")]
+    pub serial_numbers: Vec<Uuid>,
+    #[serde(rename = "lastUpdated")]
+    pub last_updated: String,
+}
+
+#[derive(Deserialize)]
+pub struct LogRequest {
+    pub logs: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct GooglePassResponse {
+    pub save_url: String,
+}
+
+/// Signed `.pkpass` bundle for the ticket that the holder adds to Apple Wallet
+pub async fn apple_pass(
+    (connection, path, user, state): (Connection, Path<PathParameters>, User, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let config = match &state.config.wallet_passes.apple {
+        Some(config) => config,
+        None => return application::unprocessable("Apple Wallet passes are not enabled"),
+    };
+    let ticket = TicketInstance::find(path.id, connection)?;
+    if ticket.owner(connection)?.id != user.id() {
+        return application::unauthorized(Some(user), None);
+    }
+    let pass = WalletPass::find_or_create_for_ticket(&ticket, WalletPassTypes::Apple, connection)?;
+    pkpass_response(config, &state.config, &pass, connection)
+}
+
+/// Link that saves the ticket to Google Pay
+pub async fn google_pass(
+    (connection, path, user, state): (Connection, Path<PathParameters>, User, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let config = match &state.config.wallet_passes.google {
+        Some(config) => config,
+        None => return application::unprocessable("Google Pay passes are not enabled"),
+    };
+    let ticket = TicketInstance::find(path.id, connection)?;
+    if ticket.owner(connection)?.id != user.id() {
+        return application::unauthorized(Some(user), None);
+    }
+    let pass = WalletPass::find_or_create_for_ticket(&ticket, WalletPassTypes::Google, connection)?;
+    let save_url = google::save_url(config, &state.config.front_end_url, &pass.details(connection)?)?;
+    Ok(HttpResponse::Ok().json(GooglePassResponse { save_url }))
+}
+
+// The handlers below implement the web service Apple Wallet calls to register devices for
+// updates and to download the latest version of a pass
+
+pub async fn register_device(
+    (connection, path, json, request, state): (
+        Connection,
+        Path<DeviceRegistrationPathParameters>,
+        Json<DeviceRegistrationRequest>,
+        HttpRequest,
+        Data<AppState>,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let pass = match authenticated_pass(
+        &request,
+        &path.pass_type_identifier,
+        path.serial_number,
+        &state,
+        connection,
+    )? {
+        Some(pass) => pass,
+        None => return Ok(HttpResponse::Unauthorized().finish()),
+    };
+    if pass.register_device(&path.device_library_identifier, &json.push_token, connection)? {
+        Ok(HttpResponse::Created().finish())
+    } else {
+        Ok(HttpResponse::Ok().finish())
+    }
+}
+
+pub async fn unregister_device(
+    (connection, path, request, state): (
+        Connection,
+        Path<DeviceRegistrationPathParameters>,
+        HttpRequest,
+        Data<AppState>,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let pass = match authenticated_pass(
+        &request,
+        &path.pass_type_identifier,
+        path.serial_number,
+        &state,
+        connection,
+    )? {
+        Some(pass) => pass,
+        None => return Ok(HttpResponse::Unauthorized().finish()),
+    };
+    pass.unregister_device(&path.device_library_identifier, connection)?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Serial numbers of the device's passes that changed since the tag it last received
+pub async fn updated_passes(
+    (connection, path, query, state): (
+        Connection,
+        Path<DevicePathParameters>,
+        Query<UpdatedPassesParameters>,
+        Data<AppState>,
+    ),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    if !is_configured_pass_type(&state.config, &path.pass_type_identifier) {
+        return application::not_found();
+    }
+    // Tags are the microsecond timestamp of the most recent update the device has seen
+    let updated_since = query
+        .passes_updated_since
+        .as_ref()
+        .and_then(|tag| tag.parse::<i64>().ok())
+        .map(|tag| NaiveDateTime::from_timestamp(tag / 1_000_000, (tag % 1_000_000) as u32 * 1_000));
+    let passes = WalletPass::find_updated_for_device(
+        &path.device_library_identifier,
+        WalletPassTypes::Apple,
+        updated_since,
+        connection,
+    )?;
+    let last_updated = match passes.iter().map(|pass| pass.updated_at).max() {
+        Some(last_updated) => last_updated,
+        None => return application::no_content(),
+    };
+
+    Ok(HttpResponse::Ok().json(UpdatedPassesResponse {
+        serial_numbers: passes.iter().map(|pass| pass.id).collect(),
+        last_updated: (last_updated.timestamp() * 1_000_000 + last_updated.timestamp_subsec_micros() as i64)
+            .to_string(),
+    }))
+}
+
+pub async fn latest_pass(
+    (connection, path, request, state): (Connection, Path<PassPathParameters>, HttpRequest, Data<AppState>),
+) -> Result<HttpResponse, ApiError> {
+    let connection = connection.get();
+    let pass = match authenticated_pass(
+        &request,
+        &path.pass_type_identifier,
+        path.serial_number,
+        &state,
+        connection,
+    )? {
+        Some(pass) => pass,
+        None => return Ok(HttpResponse::Unauthorized().finish()),
+    };
+
+    let if_modified_since = request
+        .headers()
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok());
+    if let Some(if_modified_since) = if_modified_since {
+        if pass.updated_at.timestamp() <= if_modified_since.timestamp() {
+            return Ok(HttpResponse::NotModified().finish());
+        }
+    }
+
+    match &state.config.wallet_passes.apple {
+        Some(config) => pkpass_response(config, &state.config, &pass, connection),
+        None => application::not_found(),
+    }
+}
+
+/// Errors reported by devices while talking to the web service
+pub async fn log(json: Json<LogRequest>) -> Result<HttpResponse, ApiError> {
+    for message in &json.logs {
+        jlog!(Warn, "Apple Wallet log", { "message": message });
+    }
+    Ok(HttpResponse::Ok().finish())
+}
+
+fn pkpass_response(
+    config: &AppleWalletConfig,
+    app_config: &Config,
+    pass: &WalletPass,
+    connection: &PgConnection,
+) -> Result<HttpResponse, ApiError> {
+    let web_service_url = format!("{}/wallet_passes", app_config.api_base_url);
+    let bundle = apple::pkpass(config, &web_service_url, &pass.details(connection)?)?;
+    let last_modified = pass.updated_at.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+    Ok(HttpResponse::Ok()
+        .content_type(apple::PKPASS_CONTENT_TYPE)
+        .header(header::LAST_MODIFIED, last_modified)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.pkpass\"", pass.ticket_instance_id),
+        )
+        .body(bundle))
+}
+
+fn is_configured_pass_type(config: &Config, pass_type_identifier: &str) -> bool {
+    config
+        .wallet_passes
+        .apple
+        .as_ref()
+        .map_or(false, |apple| apple.pass_type_identifier == pass_type_identifier)
+}
+
+fn authenticated_pass(
+    request: &HttpRequest,
+    pass_type_identifier: &str,
+    serial_number: Uuid,
+    state: &AppState,
+    connection: &PgConnection,
+) -> Result<Option<WalletPass>, ApiError> {
+    if !is_configured_pass_type(&state.config, pass_type_identifier) {
+        return Ok(None);
+    }
+    let authentication_token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| value.starts_with(apple::AUTHORIZATION_SCHEME))
+        .map(|value| value[apple::AUTHORIZATION_SCHEME.len()..].trim().to_string())
+        .filter(|token| !token.is_empty());
+    match authentication_token {
+        Some(authentication_token) => Ok(WalletPass::find_authenticated(
+            serial_number,
+            WalletPassTypes::Apple,
+            &authentication_token,
+            connection,
+        )?),
+        None => Ok(None),
+    }
+}
@@ -65,6 +65,14 @@ impl BroadcastPushNotificationExecutor {
             BroadcastAudience::TicketHolders => {
                 Event::find_all_ticket_holders(broadcast.event_id, conn, TicketHoldersCountType::WithEmailAddress)?
             }
+            BroadcastAudience::FanSegment => match broadcast.fan_segment_id {
+                Some(fan_segment_id) => FanSegment::find(fan_segment_id, conn)?
+                    .all_users(conn)?
+                    .into_iter()
+                    .map(|u| (u, Vec::new(), None))
+                    .collect_vec(),
+                None => Vec::new(),
+            },
         }
         .into_iter()
         .map(|aud| aud.0)
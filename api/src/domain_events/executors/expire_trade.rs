@@ -0,0 +1,54 @@
+use crate::config::Config;
+use crate::controllers::trades::payment_processor_for_trade;
+use crate::database::Connection;
+use crate::domain_events::executor_future::ExecutorFuture;
+use crate::domain_events::routing::DomainActionExecutor;
+use crate::errors::*;
+use crate::utils::ServiceLocator;
+use db::prelude::*;
+use futures::future;
+use log::Level::Error;
+
+pub struct ExpireTradeExecutor {
+    config: Config,
+}
+
+impl DomainActionExecutor for ExpireTradeExecutor {
+    fn execute(&self, action: DomainAction, conn: Connection) -> ExecutorFuture {
+        match self.perform_job(&action, &conn) {
+            Ok(_) => ExecutorFuture::new(action, conn, Box::pin(future::ok(()))),
+            Err(e) => {
+                jlog!(Error, "Expire trade action failed", {"action_id": action.id, "main_table_id": action.main_table_id, "error": e.to_string()});
+                ExecutorFuture::new(action, conn, Box::pin(future::err(e)))
+            }
+        }
+    }
+}
+
+impl ExpireTradeExecutor {
+    pub fn new(config: Config) -> ExpireTradeExecutor {
+        ExpireTradeExecutor { config }
+    }
+
+    pub fn perform_job(&self, action: &DomainAction, conn: &Connection) -> Result<(), ApiError> {
+        let conn = conn.get();
+        let id = action
+            .main_table_id
+            .clone()
+            .ok_or(ApplicationError::new("No id supplied in the action".to_string()))?;
+        let trade = Trade::find(id, conn)?;
+        // Trades resolved before their expiry date have nothing left to release
+        if trade.status != TradeStatus::Pending {
+            return Ok(());
+        }
+
+        let trade = trade.expire(conn)?;
+        if let Some(reference) = &trade.external_payment_reference {
+            let service_locator = ServiceLocator::new(&self.config)?;
+            payment_processor_for_trade(&trade, &service_locator)?
+                .partial_refund_blocking(reference, trade.cash_in_cents)?;
+        }
+
+        Ok(())
+    }
+}
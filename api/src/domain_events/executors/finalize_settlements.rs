@@ -1,3 +1,5 @@
+use crate::communications::mailers;
+use crate::config::Config;
 use crate::database::Connection;
 use crate::domain_events::executor_future::ExecutorFuture;
 use crate::domain_events::routing::DomainActionExecutor;
@@ -6,7 +8,9 @@ use db::prelude::*;
 use futures::future;
 use log::Level::Error;
 
-pub struct FinalizeSettlementsExecutor {}
+pub struct FinalizeSettlementsExecutor {
+    config: Config,
+}
 
 impl DomainActionExecutor for FinalizeSettlementsExecutor {
     fn execute(&self, action: DomainAction, conn: Connection) -> ExecutorFuture {
@@ -21,13 +25,26 @@ impl DomainActionExecutor for FinalizeSettlementsExecutor {
 }
 
 impl FinalizeSettlementsExecutor {
-    pub fn new() -> FinalizeSettlementsExecutor {
-        FinalizeSettlementsExecutor {}
+    pub fn new(config: Config) -> FinalizeSettlementsExecutor {
+        FinalizeSettlementsExecutor { config }
     }
 
     pub fn perform_job(&self, conn: &Connection) -> Result<(), ApiError> {
         let conn = conn.get();
-        Settlement::finalize_settlements(conn)?;
+        for settlement in Settlement::finalize_settlements(conn)? {
+            let organization = Organization::find(settlement.organization_id, conn)?;
+            for subscriber in SettlementStatementSubscriber::find_users_for_organization(organization.id, conn)? {
+                let email = match subscriber.email {
+                    Some(email) => email,
+                    None => continue,
+                };
+                if let Err(error) =
+                    mailers::settlements::statement(email.clone(), &organization, &settlement, &self.config, conn)
+                {
+                    jlog!(Error, "Failed to send settlement statement to subscriber", {"settlement_id": settlement.id, "email": email, "error": error.to_string()});
+                }
+            }
+        }
         Settlement::create_next_finalize_settlements_domain_action(conn)?;
 
         Ok(())
@@ -0,0 +1,48 @@
+use crate::config::Config;
+use crate::controllers::event_series;
+use crate::database::Connection;
+use crate::domain_events::executor_future::ExecutorFuture;
+use crate::domain_events::routing::DomainActionExecutor;
+use crate::errors::*;
+use db::prelude::*;
+use db::utils::errors::Optional;
+use futures::future;
+use log::Level::Error;
+
+pub struct GenerateEventSeriesOccurrencesExecutor {
+    config: Config,
+}
+
+impl DomainActionExecutor for GenerateEventSeriesOccurrencesExecutor {
+    fn execute(&self, action: DomainAction, conn: Connection) -> ExecutorFuture {
+        match self.perform_job(&action, &conn) {
+            Ok(_) => ExecutorFuture::new(action, conn, Box::pin(future::ok(()))),
+            Err(e) => {
+                jlog!(Error, "Generate event series occurrences action failed", {"action_id": action.id, "main_table_id": action.main_table_id, "error": e.to_string()});
+                ExecutorFuture::new(action, conn, Box::pin(future::err(e)))
+            }
+        }
+    }
+}
+
+impl GenerateEventSeriesOccurrencesExecutor {
+    pub fn new(config: Config) -> GenerateEventSeriesOccurrencesExecutor {
+        GenerateEventSeriesOccurrencesExecutor { config }
+    }
+
+    /// Tops up the series' events to the generation horizon, generation schedules the next run itself
+    pub fn perform_job(&self, action: &DomainAction, conn: &Connection) -> Result<(), ApiError> {
+        let conn = conn.get();
+        let id = action
+            .main_table_id
+            .clone()
+            .ok_or(ApplicationError::new("No id supplied in the action".to_string()))?;
+
+        // Deleted series are no longer generated
+        if let Some(event_series) = EventSeries::find(id, conn).optional()? {
+            event_series::generate_occurrences(&event_series, None, &self.config, conn)?;
+        }
+
+        Ok(())
+    }
+}
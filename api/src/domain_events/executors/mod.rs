@@ -1,8 +1,11 @@
 pub use self::broadcast_push_notification::*;
+pub use self::expire_trade::*;
 pub use self::finalize_settlements::*;
+pub use self::generate_event_series_occurrences::*;
 pub use self::process_payment_ipn::*;
 pub use self::process_settlement_report::*;
 pub use self::process_transfer_drip_event::*;
+pub use self::reconcile_assets::*;
 pub use self::regenerate_drip_actions::*;
 pub use self::release_hold_inventory::*;
 pub use self::retarget_abandoned_orders::*;
@@ -11,12 +14,16 @@ pub use self::send_communication::*;
 pub use self::send_order_complete::*;
 pub use self::submit_sitemap_to_search_engines::*;
 pub use self::update_genres::*;
+pub use self::update_wallet_passes::*;
 
 mod broadcast_push_notification;
+mod expire_trade;
 mod finalize_settlements;
+mod generate_event_series_occurrences;
 mod process_payment_ipn;
 mod process_settlement_report;
 mod process_transfer_drip_event;
+mod reconcile_assets;
 mod regenerate_drip_actions;
 mod release_hold_inventory;
 mod retarget_abandoned_orders;
@@ -25,3 +32,4 @@ mod send_communication;
 mod send_order_complete;
 mod submit_sitemap_to_search_engines;
 mod update_genres;
+mod update_wallet_passes;
@@ -0,0 +1,55 @@
+use crate::config::Config;
+use crate::database::Connection;
+use crate::domain_events::executor_future::ExecutorFuture;
+use crate::domain_events::routing::DomainActionExecutor;
+use crate::errors::*;
+use db::prelude::*;
+use futures::future;
+use log::Level::{Error, Info};
+
+pub struct ReconcileAssetsExecutor {
+    config: Config,
+}
+
+impl DomainActionExecutor for ReconcileAssetsExecutor {
+    fn execute(&self, action: DomainAction, conn: Connection) -> ExecutorFuture {
+        match self.perform_job(&action, &conn) {
+            Ok(_) => ExecutorFuture::new(action, conn, Box::pin(future::ok(()))),
+            Err(e) => {
+                jlog!(Error, "Reconcile assets action failed", {"action_id": action.id, "main_table_id": action.main_table_id, "error": e.to_string()});
+                ExecutorFuture::new(action, conn, Box::pin(future::err(e)))
+            }
+        }
+    }
+}
+
+impl ReconcileAssetsExecutor {
+    pub fn new(config: Config) -> ReconcileAssetsExecutor {
+        ReconcileAssetsExecutor { config }
+    }
+
+    pub fn perform_job(&self, action: &DomainAction, conn: &Connection) -> Result<(), ApiError> {
+        let conn = conn.get();
+        let repair = action.payload["repair"].as_bool().unwrap_or(false);
+        for asset in Asset::find_on_blockchain(conn)? {
+            // Each asset records its own failure so one bad asset does not stop the others
+            match asset.reconcile(&*self.config.tari_client, repair, conn) {
+                Ok(asset) => {
+                    if asset.status == AssetStatus::Error {
+                        jlog!(Info, "Asset does not match the blockchain", {"asset_id": asset.id, "sync_error": asset.sync_error});
+                    }
+                }
+                Err(error) => {
+                    jlog!(Error, "Could not reconcile asset", {"asset_id": asset.id, "error": error.to_string()});
+                }
+            }
+        }
+
+        // Runs requested by an admin do not affect the daily schedule
+        if action.payload["requested_by_user_id"].is_null() {
+            Asset::create_next_reconcile_assets_domain_action(conn)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use crate::config::Config;
+use crate::database::Connection;
+use crate::domain_events::executor_future::ExecutorFuture;
+use crate::domain_events::routing::DomainActionExecutor;
+use crate::errors::*;
+use crate::utils::wallet_passes::{apple, google};
+use db::prelude::*;
+use futures::future;
+use log::Level::{Error, Warn};
+use uuid::Uuid;
+
+pub struct UpdateWalletPassesExecutor {
+    config: Config,
+}
+
+impl DomainActionExecutor for UpdateWalletPassesExecutor {
+    fn execute(&self, action: DomainAction, conn: Connection) -> ExecutorFuture {
+        match self.perform_job(&action, &conn) {
+            Ok(_) => ExecutorFuture::new(action, conn, Box::pin(future::ok(()))),
+            Err(e) => {
+                jlog!(Error, "Update wallet passes action failed", {"action_id": action.id, "error": e.to_string()});
+                ExecutorFuture::new(action, conn, Box::pin(future::err(e)))
+            }
+        }
+    }
+}
+
+impl UpdateWalletPassesExecutor {
+    pub fn new(config: Config) -> UpdateWalletPassesExecutor {
+        UpdateWalletPassesExecutor { config }
+    }
+
+    /// Tells the wallet apps holding the passes to show their latest version. Apple Wallet devices are
+    /// pushed a notification and download the new pass, Google Pay passes are updated in place.
+    pub fn perform_job(&self, action: &DomainAction, conn: &Connection) -> Result<(), ApiError> {
+        if self.config.block_external_comms {
+            return Ok(());
+        }
+
+        let conn = conn.get();
+        let wallet_pass_ids: Vec<Uuid> = serde_json::from_value(action.payload["wallet_pass_ids"].clone())?;
+        for wallet_pass_id in wallet_pass_ids {
+            let pass = WalletPass::find(wallet_pass_id, conn)?;
+            match pass.pass_type {
+                WalletPassTypes::Apple => {
+                    let config = match &self.config.wallet_passes.apple {
+                        Some(config) => config,
+                        None => continue,
+                    };
+                    for registration in pass.registrations(conn)? {
+                        if !apple::push_update(config, &registration.push_token)? {
+                            jlog!(Warn, "Removing wallet pass registration for device that no longer has the pass", {"wallet_pass_id": pass.id, "device_library_identifier": &registration.device_library_identifier});
+                            pass.unregister_device(&registration.device_library_identifier, conn)?;
+                        }
+                    }
+                }
+                WalletPassTypes::Google => {
+                    if let Some(config) = &self.config.wallet_passes.google {
+                        google::update_pass(config, &pass.details(conn)?)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -47,15 +47,19 @@ impl DomainActionRouter {
             let conf = conf.clone();
             match action_type {
                 Communication => Box::new(SendCommunicationExecutor::new(conf)),
+                ExpireTrade => Box::new(ExpireTradeExecutor::new(conf)),
                 BroadcastPushNotification => Box::new(BroadcastPushNotificationExecutor::new(&conf)),
-                FinalizeSettlements => Box::new(FinalizeSettlementsExecutor::new()),
+                FinalizeSettlements => Box::new(FinalizeSettlementsExecutor::new(conf)),
+                GenerateEventSeriesOccurrences => Box::new(GenerateEventSeriesOccurrencesExecutor::new(conf)),
                 PaymentProviderIPN => Box::new(ProcessPaymentIPNExecutor::new(&conf)),
                 RegenerateDripActions => Box::new(RegenerateDripActionsExecutor::new(conf)),
                 ReleaseHoldInventory => Box::new(ReleaseHoldInventoryExecutor::new()),
                 SendPurchaseCompletedCommunication => Box::new(SendOrderCompleteExecutor::new(conf)),
                 UpdateGenres => Box::new(UpdateGenresExecutor::new()),
+                UpdateWalletPasses => Box::new(UpdateWalletPassesExecutor::new(conf)),
                 ProcessSettlementReport => Box::new(ProcessSettlementReportExecutor::new(conf)),
                 ProcessTransferDrip => Box::new(ProcessTransferDripEventExecutor::new(conf)),
+                ReconcileAssets => Box::new(ReconcileAssetsExecutor::new(conf)),
                 RetargetAbandonedOrders => Box::new(RetargetAbandonedOrdersExecutor::new()),
                 SendAutomaticReportEmails => Box::new(SendAutomaticReportEmailsExecutor::new(conf)),
                 SubmitSitemapToSearchEngines => Box::new(SubmitSitemapToSearchEnginesExecutor::new(
@@ -73,9 +77,18 @@ impl DomainActionRouter {
         self.add_executor(BroadcastPushNotification, find_executor(BroadcastPushNotification))
             .expect("Configuration error");
 
+        self.add_executor(ExpireTrade, find_executor(ExpireTrade))
+            .expect("Configuration error");
+
         self.add_executor(FinalizeSettlements, find_executor(FinalizeSettlements))
             .expect("Configuration error");
 
+        self.add_executor(
+            GenerateEventSeriesOccurrences,
+            find_executor(GenerateEventSeriesOccurrences),
+        )
+        .expect("Configuration error");
+
         self.add_executor(PaymentProviderIPN, find_executor(PaymentProviderIPN))
             .expect("Configuration error");
 
@@ -85,6 +98,9 @@ impl DomainActionRouter {
         self.add_executor(ProcessTransferDrip, find_executor(ProcessTransferDrip))
             .expect("Configuration error");
 
+        self.add_executor(ReconcileAssets, find_executor(ReconcileAssets))
+            .expect("Configuration error");
+
         self.add_executor(RegenerateDripActions, find_executor(RegenerateDripActions))
             .expect("Configuration error");
 
@@ -97,6 +113,9 @@ impl DomainActionRouter {
         self.add_executor(UpdateGenres, find_executor(UpdateGenres))
             .expect("Configuration error");
 
+        self.add_executor(UpdateWalletPasses, find_executor(UpdateWalletPasses))
+            .expect("Configuration error");
+
         self.add_executor(SendAutomaticReportEmails, find_executor(SendAutomaticReportEmails))
             .expect("Configuration error");
 
@@ -118,7 +118,7 @@ impl WebhookPublisher {
 
                 let desktop_url = format!(
                     "{}/send-download-link?refresh_token={}",
-                    self.front_end_url,
+                    order.front_end_url(&self.front_end_url, conn)?,
                     refresh_token.clone().unwrap_or("".to_string())
                 );
 
@@ -201,7 +201,7 @@ impl WebhookPublisher {
 
                     self.recipient_payload_data(
                         &transfer,
-                        &self.front_end_url,
+                        &transfer.front_end_url(&self.front_end_url, conn)?,
                         domain_event.event_type,
                         &mut recipient_data,
                         conn,
@@ -265,6 +265,8 @@ impl WebhookPublisher {
         let mut refunded_fees_total = 0;
         let mut discount_total = 0;
         let mut refunded_discount_total = 0;
+        let mut tax_total = 0;
+        let mut refunded_tax_total = 0;
         let mut j_items = Vec::<R>::new();
         for item in order.items(conn)? {
             let item_total = item.unit_price_in_cents * item.quantity;
@@ -284,6 +286,10 @@ impl WebhookPublisher {
                     sub_total = sub_total + item_total;
                     refunded_sub_total = refunded_sub_total + refunded_total;
                 }
+                OrderItemTypes::AddOns => {
+                    sub_total = sub_total + item_total;
+                    refunded_sub_total = refunded_sub_total + refunded_total;
+                }
                 OrderItemTypes::Discount => {
                     discount_total = discount_total + item_total;
                     refunded_discount_total = refunded_discount_total + refunded_total;
@@ -292,6 +298,10 @@ impl WebhookPublisher {
                     fees_total = fees_total + item_total;
                     refunded_fees_total = refunded_fees_total + refunded_total;
                 }
+                OrderItemTypes::Tax => {
+                    tax_total = tax_total + item.tax_in_cents * item.quantity;
+                    refunded_tax_total = refunded_tax_total + item.tax_in_cents * item.refunded_quantity;
+                }
             }
         }
 
@@ -303,6 +313,12 @@ impl WebhookPublisher {
         data.insert("refunded_fees_total".to_string(), json!(refunded_fees_total));
         data.insert("discount_total".to_string(), json!(discount_total));
         data.insert("refunded_discount_total".to_string(), json!(refunded_discount_total));
+        data.insert("tax_total".to_string(), json!(tax_total));
+        data.insert("refunded_tax_total".to_string(), json!(refunded_tax_total));
+        data.insert(
+            "checkout_answers".to_string(),
+            json!(CheckoutAnswer::find_display_for_orders(vec![order.id], conn)?),
+        );
 
         data.insert(
             "user_id".to_string(),
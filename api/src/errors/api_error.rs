@@ -57,6 +57,8 @@ error_conversion!(reqwest::Error);
 error_conversion!(url::ParseError);
 error_conversion!(ToStrError);
 error_conversion!(ShareTribeError);
+error_conversion!(openssl::error::ErrorStack);
+error_conversion!(zip::result::ZipError);
 
 impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -104,6 +106,20 @@ impl ConvertToWebError for std::io::Error {
     }
 }
 
+impl ConvertToWebError for openssl::error::ErrorStack {
+    fn to_response(&self) -> HttpResponse {
+        error!("OpenSSL error: {}", self);
+        HttpResponse::InternalServerError().json(json!({"error": "Internal error"}))
+    }
+}
+
+impl ConvertToWebError for zip::result::ZipError {
+    fn to_response(&self) -> HttpResponse {
+        error!("Zip error: {}", self);
+        HttpResponse::InternalServerError().json(json!({"error": "Internal error"}))
+    }
+}
+
 impl From<TwilioError> for ApiError {
     fn from(e: TwilioError) -> Self {
         ApiError::new(Box::new(e))
@@ -4,8 +4,10 @@ use crate::payments::PaymentProcessorError;
 use actix_web::{http::StatusCode, HttpResponse};
 use branch_rs::BranchError;
 use customer_io::CustomerIoError;
+use db::models::Locales;
 use db::utils::errors::ErrorCode::ValidationError;
 use db::utils::errors::*;
+use db::utils::localization;
 use diesel::result::Error as DieselError;
 use facebook::prelude::FacebookError;
 use globee::GlobeeError;
@@ -14,6 +16,7 @@ use reqwest::header::ToStrError as ReqwestToStrError;
 use reqwest::Error as ReqwestError;
 use serde_json::Error as SerdeError;
 use sharetribe_flex::ShareTribeError;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Debug;
@@ -21,12 +24,18 @@ use std::string::ToString;
 use stripe::StripeError;
 use tari_client::TariError;
 use uuid::ParseError as UuidParseError;
+use validator::ValidationError as FieldValidationError;
 
 pub trait ConvertToWebError: Debug + Error + ToString {
     fn status_code(&self) -> StatusCode {
         StatusCode::INTERNAL_SERVER_ERROR
     }
     fn to_response(&self) -> HttpResponse;
+    /// Response with user facing messages translated to the locale, `None` when the error has no
+    /// translated messages and the default response should be used
+    fn to_localized_response(&self, _locale: Locales) -> Option<HttpResponse> {
+        None
+    }
 }
 
 fn internal_error(message: &str) -> HttpResponse {
@@ -37,8 +46,11 @@ fn unauthorized(message: &str) -> HttpResponse {
     status_code_and_message(StatusCode::UNAUTHORIZED, message)
 }
 
-fn not_found() -> HttpResponse {
-    status_code_and_message(StatusCode::NOT_FOUND, "Not found")
+fn not_found(locale: Locales) -> HttpResponse {
+    status_code_and_message(
+        StatusCode::NOT_FOUND,
+        &localization::translate(locale, "errors.not_found", &[]),
+    )
 }
 
 fn status_code_and_message(code: StatusCode, message: &str) -> HttpResponse {
@@ -102,7 +114,10 @@ impl ConvertToWebError for NotFoundError {
         StatusCode::NOT_FOUND
     }
     fn to_response(&self) -> HttpResponse {
-        not_found()
+        not_found(Locales::En)
+    }
+    fn to_localized_response(&self, locale: Locales) -> Option<HttpResponse> {
+        Some(not_found(locale))
     }
 }
 
@@ -277,31 +292,63 @@ impl ConvertToWebError for DatabaseError {
         }
     }
     fn to_response(&self) -> HttpResponse {
-        let message = match self.code {
-            1000 => "Invalid input",
-            1100 => "Missing input",
-            2000 => "No results",
-            3000 => "Query error",
-            3100 => "Could not insert record",
-            3200 => "Could not update record",
-            3300 => "Could not delete record",
-            3400 => self
-                .cause
-                .as_ref()
-                .map(|s| s.as_str())
-                .unwrap_or("Duplicate record exists"),
-            4000 => "Connection error",
-            7000 => self.cause.as_ref().map(|s| s.as_str()).unwrap_or("Unknown Cause"),
-            7200 => match &self.error_code {
-                ValidationError { errors } => {
-                    return HttpResponse::UnprocessableEntity()
-                        .json(json!({"error": "Validation error".to_string(), "fields": errors}))
-                }
-                _ => "Validation error",
-            },
-            5000 | 7300 => "Internal error",
-            _ => "Unknown error",
-        };
-        status_code_and_message(self.status_code(), message)
+        database_error_response(self, Locales::En)
+    }
+    fn to_localized_response(&self, locale: Locales) -> Option<HttpResponse> {
+        Some(database_error_response(self, locale))
     }
 }
+
+fn database_error_response(error: &DatabaseError, locale: Locales) -> HttpResponse {
+    let message_key = match error.code {
+        1000 => "errors.invalid_input",
+        1100 => "errors.missing_input",
+        2000 => "errors.no_results",
+        3000 => "errors.query",
+        3100 => "errors.insert",
+        3200 => "errors.update",
+        3300 => "errors.delete",
+        3400 | 7000 => match &error.cause {
+            Some(cause) => {
+                return status_code_and_message(error.status_code(), &localization::translate_message(locale, cause))
+            }
+            None if error.code == 3400 => "errors.duplicate_record",
+            None => "errors.unknown_cause",
+        },
+        4000 => "errors.connection",
+        7200 => match &error.error_code {
+            ValidationError { errors } => {
+                return HttpResponse::UnprocessableEntity().json(json!({
+                    "error": localization::translate(locale, "errors.validation", &[]),
+                    "fields": localized_validation_errors(errors, locale)
+                }))
+            }
+            _ => "errors.validation",
+        },
+        5000 | 7300 => "errors.internal",
+        _ => "errors.unknown",
+    };
+    status_code_and_message(error.status_code(), &localization::translate(locale, message_key, &[]))
+}
+
+fn localized_validation_errors(
+    errors: &HashMap<&'static str, Vec<FieldValidationError>>,
+    locale: Locales,
+) -> HashMap<&'static str, Vec<FieldValidationError>> {
+    errors
+        .iter()
+        .map(|(field, field_errors)| {
+            let field_errors = field_errors
+                .iter()
+                .cloned()
+                .map(|mut field_error| {
+                    field_error.message = field_error
+                        .message
+                        .map(|message| Cow::from(localization::translate_message(locale, &message)));
+                    field_error
+                })
+                .collect();
+            (*field, field_errors)
+        })
+        .collect()
+}
@@ -1,11 +1,13 @@
 pub(crate) use self::access_token::*;
 pub use self::json::*;
 pub use self::optional_user::*;
+pub use self::partner::*;
 pub use self::request_info::*;
 pub use self::user::*;
 
 mod access_token;
 mod json;
 mod optional_user;
+mod partner;
 mod request_info;
 mod user;
@@ -0,0 +1,58 @@
+use crate::errors::{ApiError, AuthError};
+use crate::middleware::{PartnerOrigin, RequestConnection};
+use actix_web::http::header::ORIGIN;
+use actix_web::{dev::Payload, FromRequest, HttpMessage, HttpRequest};
+use db::models::Partner;
+use futures::future::{err, ok, Ready};
+
+const PARTNER_KEY_HEADER: &str = "X-Partner-Key";
+
+/// Partner identified by the `X-Partner-Key` header, requests made from a browser must come from one of
+/// the partner's allowed origins
+#[derive(Clone, Debug)]
+pub struct AuthorizedPartner {
+    pub partner: Partner,
+    pub origin: Option<String>,
+}
+
+impl FromRequest for AuthorizedPartner {
+    type Config = ();
+    type Error = ApiError;
+    type Future = Ready<Result<AuthorizedPartner, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let api_key = match req.headers().get(PARTNER_KEY_HEADER).and_then(|key| key.to_str().ok()) {
+            Some(api_key) => api_key.to_string(),
+            None => return err(AuthError::unauthorized("Missing partner key").into()),
+        };
+
+        let connection = match req.connection() {
+            Ok(conn) => conn,
+            Err(e) => return err(e),
+        };
+
+        let partner = match Partner::find_by_api_key(&api_key, connection.get()) {
+            Ok(partner) => partner,
+            Err(_) => return err(AuthError::unauthorized("Invalid partner key").into()),
+        };
+
+        // The partner CORS middleware moves the origin header into the request extensions
+        let origin = req
+            .extensions()
+            .get::<PartnerOrigin>()
+            .map(|origin| origin.0.clone())
+            .or_else(|| {
+                req.headers()
+                    .get(ORIGIN)
+                    .and_then(|origin| origin.to_str().ok())
+                    .map(|origin| origin.to_string())
+            });
+        if let Some(ref origin) = origin {
+            if !partner.origin_allowed(origin) {
+                return err(AuthError::unauthorized("Origin is not allowed for this partner").into());
+            }
+        }
+
+        ok(AuthorizedPartner { partner, origin })
+    }
+}
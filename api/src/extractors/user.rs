@@ -35,6 +35,10 @@ impl FromRequest for User {
         if user.deleted_at.is_some() {
             err(AuthError::unauthorized("User account is disabled").into())
         } else {
+            // Used by the localization middleware to translate error responses
+            if let Some(locale) = user.locale {
+                req.extensions_mut().insert(locale);
+            }
             ready(
                 User::new(user, is_public_user, req, token.scopes)
                     .map_err(|_| AuthError::unauthorized("User has invalid role data").into()),
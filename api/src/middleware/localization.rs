@@ -0,0 +1,107 @@
+use crate::errors::ApiError;
+use actix_service::Service;
+use actix_web::dev;
+use actix_web::error::{self, InternalError};
+use actix_web::http::header::{HeaderValue, ACCEPT_LANGUAGE, CONTENT_LANGUAGE};
+use db::models::Locales;
+use db::utils::localization;
+use futures::future::{ok, Ready};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pub struct Localization;
+
+impl Localization {
+    pub fn new() -> Localization {
+        Localization {}
+    }
+
+    // Replace error messages with the requesting user's language, the authenticated user's preference is
+    // stored in the request extensions when the user is extracted
+    pub fn complete<B>(response: dev::ServiceResponse<B>) -> error::Result<dev::ServiceResponse<B>> {
+        let request = response.request();
+        let locale = localization::resolve_locale(
+            request.extensions().get::<Locales>().cloned(),
+            request
+                .headers()
+                .get(ACCEPT_LANGUAGE)
+                .and_then(|accept_language| accept_language.to_str().ok()),
+            None,
+        );
+        if locale == Locales::En {
+            return Ok(response);
+        }
+
+        let localized_error = response
+            .response()
+            .error()
+            .and_then(|e| e.as_error::<ApiError>())
+            .and_then(|api_error| {
+                api_error
+                    .into_inner()
+                    .to_localized_response(locale)
+                    .map(|localized_response| (api_error.to_string(), localized_response))
+            });
+
+        match localized_error {
+            Some((cause, mut localized_response)) => {
+                localized_response
+                    .headers_mut()
+                    .insert(CONTENT_LANGUAGE, HeaderValue::from_static(locale.language_code()));
+                Ok(response.error_response(InternalError::from_response(cause, localized_response)))
+            }
+            None => Ok(response),
+        }
+    }
+}
+
+impl<S, B> dev::Transform<S> for Localization
+where
+    S: Service<Request = dev::ServiceRequest, Response = dev::ServiceResponse<B>, Error = error::Error> + 'static,
+    B: dev::MessageBody,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type InitError = ();
+    type Transform = LocalizationService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(LocalizationService::new(service))
+    }
+}
+
+pub struct LocalizationService<S> {
+    service: S,
+}
+
+impl<S> LocalizationService<S> {
+    fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S, B> Service for LocalizationService<S>
+where
+    S: Service<Request = dev::ServiceRequest, Response = dev::ServiceResponse<B>, Error = error::Error> + 'static,
+    B: dev::MessageBody,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx).map_err(error::Error::from)
+    }
+
+    fn call(&mut self, request: Self::Request) -> Self::Future {
+        let fut = self.service.call(request);
+        Box::pin(async move {
+            let response = fut.await?;
+            Localization::complete(response)
+        })
+    }
+}
@@ -2,10 +2,14 @@ pub use self::api_logger::*;
 pub use self::app_version_header::*;
 pub use self::cache_resource::*;
 pub use self::database_transaction::*;
+pub use self::localization::*;
 pub use self::metatags::*;
+pub use self::partner_cors::*;
 
 mod api_logger;
 mod app_version_header;
 mod cache_resource;
 mod database_transaction;
+mod localization;
 mod metatags;
+mod partner_cors;
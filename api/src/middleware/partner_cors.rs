@@ -0,0 +1,129 @@
+use crate::errors::{ApiError, AuthError, AuthErrorType};
+use crate::middleware::RequestConnection;
+use actix_service::Service;
+use actix_web::dev;
+use actix_web::error;
+use actix_web::http::header::{
+    HeaderValue, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+    ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+};
+use actix_web::http::Method;
+use actix_web::{HttpMessage, HttpResponse};
+use db::models::Partner;
+use futures::future::{ok, Ready};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pub const PARTNER_PATH_PREFIX: &str = "/partner/";
+const ALLOWED_METHODS: &str = "GET, POST, PUT, DELETE";
+const ALLOWED_HEADERS: &str = "Authorization, Accept, Accept-Language, Content-Type, X-Partner-Key";
+
+/// Origin of a partner API request, the header itself is removed once the origin is allowed so the
+/// application wide CORS configuration does not reject it
+#[derive(Clone, Debug)]
+pub struct PartnerOrigin(pub String);
+
+/// CORS for the partner checkout API, origins are allowed when a partner embeds the checkout on them. The
+/// partner extractor then checks the origin belongs to the partner whose key was sent.
+pub struct PartnerCors;
+
+impl PartnerCors {
+    pub fn new() -> PartnerCors {
+        PartnerCors {}
+    }
+
+    fn origin_allowed(http_request: &actix_web::HttpRequest, origin: &str) -> Result<bool, ApiError> {
+        let connection = http_request.connection()?;
+        Ok(Partner::origin_allowed_for_any(origin, connection.get())?)
+    }
+
+    fn preflight_response(origin: &str) -> HttpResponse {
+        HttpResponse::Ok()
+            .header(ACCESS_CONTROL_ALLOW_ORIGIN, origin)
+            .header(ACCESS_CONTROL_ALLOW_METHODS, ALLOWED_METHODS)
+            .header(ACCESS_CONTROL_ALLOW_HEADERS, ALLOWED_HEADERS)
+            .header(ACCESS_CONTROL_MAX_AGE, "3600")
+            .header(VARY, "Origin")
+            .finish()
+    }
+}
+
+impl<S> dev::Transform<S> for PartnerCors
+where
+    S: Service<Request = dev::ServiceRequest, Response = dev::ServiceResponse, Error = error::Error> + 'static,
+{
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type InitError = ();
+    type Transform = PartnerCorsService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(PartnerCorsService::new(service))
+    }
+}
+
+pub struct PartnerCorsService<S> {
+    service: S,
+}
+
+impl<S> PartnerCorsService<S> {
+    fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S> Service for PartnerCorsService<S>
+where
+    S: Service<Request = dev::ServiceRequest, Response = dev::ServiceResponse, Error = error::Error> + 'static,
+{
+    type Request = S::Request;
+    type Response = dev::ServiceResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx).map_err(error::Error::from)
+    }
+
+    fn call(&mut self, request: Self::Request) -> Self::Future {
+        let origin = match request.headers().get(ORIGIN).and_then(|origin| origin.to_str().ok()) {
+            Some(origin) if request.path().starts_with(PARTNER_PATH_PREFIX) => origin.to_string(),
+            _ => return Box::pin(self.service.call(request)),
+        };
+        let is_preflight =
+            request.method() == Method::OPTIONS && request.headers().contains_key(ACCESS_CONTROL_REQUEST_METHOD);
+
+        let (http_request, payload) = request.into_parts();
+        let origin_allowed = PartnerCors::origin_allowed(&http_request, &origin);
+        let mut request = dev::ServiceRequest::from_parts(http_request, payload)
+            .unwrap_or_else(|_| unreachable!("Failed to recompose request in PartnerCorsService::call"));
+        match origin_allowed {
+            Ok(true) => (),
+            Ok(false) => {
+                let error: ApiError =
+                    AuthError::new(AuthErrorType::Forbidden, "Origin is not allowed".to_string()).into();
+                return Box::pin(ok(request.error_response(error)));
+            }
+            Err(error) => return Box::pin(ok(request.error_response(error))),
+        }
+
+        if is_preflight {
+            return Box::pin(ok(request.into_response(PartnerCors::preflight_response(&origin))));
+        }
+
+        request.headers_mut().remove(ORIGIN);
+        request.extensions_mut().insert(PartnerOrigin(origin.clone()));
+        let fut = self.service.call(request);
+        Box::pin(async move {
+            let mut response = fut.await?;
+            if let Ok(origin) = HeaderValue::from_str(&origin) {
+                response.headers_mut().insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+                response.headers_mut().insert(VARY, HeaderValue::from_static("Origin"));
+            }
+            Ok(response)
+        })
+    }
+}
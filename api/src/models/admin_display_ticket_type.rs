@@ -20,6 +20,7 @@ pub struct AdminDisplayTicketType {
     pub limit_per_person: u32,
     pub ticket_pricing: Vec<DisplayTicketPricing>,
     pub price_in_cents: i64,
+    pub currency: Currencies,
     pub visibility: TicketTypeVisibility,
     pub parent_id: Option<Uuid>,
     pub parent_name: Option<String>,
@@ -28,6 +29,8 @@ pub struct AdminDisplayTicketType {
     pub app_sales_enabled: bool,
     pub web_sales_enabled: bool,
     pub box_office_sales_enabled: bool,
+    pub stage_id: Option<Uuid>,
+    pub inventory_pool_id: Option<Uuid>,
 }
 
 impl AdminDisplayTicketType {
@@ -74,12 +77,15 @@ impl AdminDisplayTicketType {
             increment: ticket_type.increment as u32,
             limit_per_person: ticket_type.limit_per_person as u32,
             price_in_cents: ticket_type.price_in_cents,
+            currency: ticket_type.currency(conn)?,
             visibility: ticket_type.visibility,
             additional_fee_in_cents: ticket_type.additional_fee_in_cents,
             rank: ticket_type.rank,
             app_sales_enabled: ticket_type.app_sales_enabled,
             web_sales_enabled: ticket_type.web_sales_enabled,
             box_office_sales_enabled: ticket_type.box_office_sales_enabled,
+            stage_id: ticket_type.stage_id,
+            inventory_pool_id: ticket_type.inventory_pool_id,
         };
         Ok(result)
     }
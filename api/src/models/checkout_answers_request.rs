@@ -0,0 +1,22 @@
+use db::prelude::*;
+use diesel::PgConnection;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CheckoutAnswersRequest {
+    pub answers: Vec<CheckoutAnswerAttributes>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct CheckoutAnswersResponse {
+    pub questions: Vec<CheckoutQuestion>,
+    pub answers: Vec<CheckoutAnswer>,
+}
+
+impl CheckoutAnswersResponse {
+    pub fn for_order(order: &Order, conn: &PgConnection) -> Result<CheckoutAnswersResponse, DatabaseError> {
+        Ok(CheckoutAnswersResponse {
+            questions: CheckoutQuestion::find_for_order(order, conn)?,
+            answers: CheckoutAnswer::find_for_order(order.id, conn)?,
+        })
+    }
+}
@@ -0,0 +1,8 @@
+use db::prelude::*;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatePackageRequest {
+    #[serde(flatten)]
+    pub package: NewPackage,
+    pub ticket_types: Vec<NewPackageTicketType>,
+}
@@ -0,0 +1,8 @@
+use db::prelude::*;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateProductRequest {
+    #[serde(flatten)]
+    pub product: NewProduct,
+    pub variants: Vec<NewProductVariant>,
+}
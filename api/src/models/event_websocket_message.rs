@@ -6,6 +6,7 @@ use serde_json::Value;
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub enum EventWebSocketType {
     TicketRedemption,
+    ZoneOccupancy,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -1,6 +1,9 @@
 pub use self::add_venue_to_organization_request::*;
 pub use self::admin_display_ticket_type::*;
+pub use self::checkout_answers_request::*;
 pub use self::create_artist_request::*;
+pub use self::create_package_request::*;
+pub use self::create_product_request::*;
 pub use self::display_ticket_pricing::*;
 pub use self::event_show_result::*;
 pub use self::event_venue_entry::*;
@@ -19,7 +22,10 @@ pub use self::user_profile_attributes::*;
 
 mod add_venue_to_organization_request;
 mod admin_display_ticket_type;
+mod checkout_answers_request;
 mod create_artist_request;
+mod create_package_request;
+mod create_product_request;
 mod display_ticket_pricing;
 mod event_show_result;
 mod event_venue_entry;
@@ -16,6 +16,7 @@ pub struct UserDisplayTicketType {
     pub increment: i32,
     pub limit_per_person: u32,
     pub ticket_pricing: Option<DisplayTicketPricing>,
+    pub currency: Currencies,
     pub redemption_code: Option<String>,
     pub event_id: Uuid,
     pub rank: i32,
@@ -55,6 +56,7 @@ impl UserDisplayTicketType {
             start_date: ticket_type.start_date,
             end_date: ticket_type.end_date(conn)?,
             ticket_pricing,
+            currency: ticket_type.currency(conn)?,
             available,
             redemption_code: None,
             increment: ticket_type.increment,
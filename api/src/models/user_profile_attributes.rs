@@ -1,4 +1,4 @@
-use db::models::{deserialize_unless_blank, double_option_deserialize_unless_blank, UserEditableAttributes};
+use db::models::{deserialize_unless_blank, double_option_deserialize_unless_blank, Locales, UserEditableAttributes};
 use validator::Validate;
 
 #[derive(Default, Deserialize, Validate)]
@@ -21,6 +21,8 @@ pub struct UserProfileAttributes {
     #[validate(url(message = "Cover photo URL is invalid"))]
     #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
     pub cover_photo_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub locale: Option<Option<Locales>>,
 }
 
 impl From<UserProfileAttributes> for UserEditableAttributes {
@@ -33,6 +35,7 @@ impl From<UserProfileAttributes> for UserEditableAttributes {
             profile_pic_url: attributes.profile_pic_url,
             thumb_profile_pic_url: attributes.thumb_profile_pic_url,
             cover_photo_url: attributes.cover_photo_url,
+            locale: attributes.locale,
             ..Default::default()
         }
     }
@@ -76,13 +76,14 @@ impl RedirectToPaymentPageBehavior for GlobeePaymentProcessorBehavior {
     async fn create_payment_request(
         &self,
         amount: f64,
+        currency: &str,
         email: String,
         payment_id: Uuid,
         ipn_url: Option<String>,
         success_url: Option<String>,
         cancel_url: Option<String>,
     ) -> Result<RedirectInfo, PaymentProcessorError> {
-        let payment_request = PaymentRequest::new(
+        let mut payment_request = PaymentRequest::new(
             amount,
             email,
             Some(payment_id.to_string()),
@@ -90,6 +91,7 @@ impl RedirectToPaymentPageBehavior for GlobeePaymentProcessorBehavior {
             success_url,
             cancel_url,
         );
+        payment_request.currency = Some(currency.to_uppercase());
         let result = self.client.create_payment_request(payment_request).await?;
         Ok(RedirectInfo {
             id: result.id,
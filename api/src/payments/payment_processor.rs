@@ -43,6 +43,7 @@ pub trait RedirectToPaymentPageBehavior {
     async fn create_payment_request(
         &self,
         total: f64,
+        currency: &str,
         email: String,
         order_id: Uuid,
         ipn_url: Option<String>,
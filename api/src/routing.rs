@@ -7,11 +7,46 @@ pub fn routes(app: &mut web::ServiceConfig) {
     // Please try to keep in alphabetical order
 
     app.service(
+        web::resource("/access_zones/{id}")
+            .route(web::put().to(access_zones::update))
+            .route(web::delete().to(access_zones::destroy)),
+    )
+    .service(web::resource("/access_zones/{id}/scans").route(web::post().to(ticket_scans::create)))
+    .service(
+        web::resource("/affiliate_commission_rules/{id}")
+            .route(web::delete().to(affiliate_links::destroy_commission_rule)),
+    )
+    .service(
+        web::resource("/affiliate_links/{id}")
+            .route(web::get().to(affiliate_links::show))
+            .route(web::put().to(affiliate_links::update))
+            .route(web::delete().to(affiliate_links::destroy)),
+    )
+    .service(
+        web::resource("/affiliate_links/{id}/commission_rules")
+            .route(web::get().to(affiliate_links::commission_rules))
+            .route(web::post().to(affiliate_links::create_commission_rule)),
+    )
+    .service(web::resource("/affiliate_links/{id}/commissions").route(web::get().to(affiliate_links::commissions)))
+    .service(
+        web::resource("/admin/asset_sync")
+            .route(web::get().to(admin::admin::admin_asset_sync))
+            .route(web::post().to(admin::admin::admin_reconcile_assets)),
+    )
+    .service(
         web::resource("/admin/stuck_domain_actions").route(web::get().to(admin::admin::admin_stuck_domain_actions)),
     )
     .service(web::resource("/admin/ticket_count").route(web::get().to(admin::admin::admin_ticket_count)))
     .service(web::resource("/admin/orders").route(web::get().to(admin::admin::orders)))
     .service(web::resource("/admin/reports").route(web::get().to(admin::reports::get_report)))
+    .service(
+        web::resource("/admin/settlement_payout_batches")
+            .route(web::get().to(settlement_payouts::batches))
+            .route(web::post().to(settlement_payouts::create_batch)),
+    )
+    .service(
+        web::resource("/admin/settlement_payout_batches/{id}/file").route(web::get().to(settlement_payouts::batch_file)),
+    )
     .service(
         web::resource("/admin/organizations/{id}/reports")
             .route(web::get().to(admin::reports::get_organization_report)),
@@ -63,9 +98,27 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::put().to(cart::replace_cart))
             .route(web::get().to(cart::show)),
     )
+    .service(web::resource("/cart/add_ons").route(web::put().to(cart::update_add_ons)))
+    .service(web::resource("/cart/packages").route(web::put().to(cart::update_packages)))
     .service(web::resource("/cart/{id}/duplicate").route(web::post().to(cart::duplicate)))
     .service(web::resource("/cart/clear_invalid_items").route(web::delete().to(cart::clear_invalid_items)))
     .service(web::resource("/cart/checkout").route(web::post().to(cart::checkout)))
+    .service(
+        web::resource("/cart/checkout_answers")
+            .route(web::get().to(cart::checkout_answers))
+            .route(web::put().to(cart::update_checkout_answers)),
+    )
+    .service(web::resource("/cash_drawer_sessions/{id}").route(web::get().to(cash_drawer_sessions::show)))
+    .service(web::resource("/cash_drawer_sessions/{id}/close").route(web::post().to(cash_drawer_sessions::close)))
+    .service(
+        web::resource("/cash_drawer_sessions/{id}/movements")
+            .route(web::post().to(cash_drawer_sessions::create_movement)),
+    )
+    .service(
+        web::resource("/checkout_questions/{id}")
+            .route(web::put().to(checkout_questions::update))
+            .route(web::delete().to(checkout_questions::destroy)),
+    )
     .service(web::resource("/codes/{id}/link").route(web::get().to(codes::link)))
     .service(
         web::resource("/codes/{id}")
@@ -80,6 +133,14 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::delete().to(comps::destroy)),
     )
     .service(web::resource("/event_report_subscribers/{id}").route(web::delete().to(event_report_subscribers::destroy)))
+    .service(
+        web::resource("/event_series/{id}")
+            .route(web::get().to(event_series::show))
+            .route(web::put().to(event_series::update))
+            .route(web::delete().to(event_series::destroy)),
+    )
+    .service(web::resource("/event_series/{id}/dashboard").route(web::get().to(event_series::dashboard)))
+    .service(web::resource("/event_series/{id}/events").route(web::put().to(event_series::update_events)))
     .service(
         web::resource("/events")
         // In future it may be better to cache this for every user to save the database hit
@@ -97,6 +158,11 @@ pub fn routes(app: &mut web::ServiceConfig) {
         .route(web::delete().to(events::cancel)),
     )
     .service(web::resource("/events/{id}/delete").route(web::delete().to(events::delete)))
+    .service(
+        web::resource("/events/{id}/access_zones")
+            .route(web::get().to(access_zones::index))
+            .route(web::post().to(access_zones::create)),
+    )
     .service(
         web::resource("/events/{id}/artists")
             .route(web::post().to(events::add_artist))
@@ -104,6 +170,11 @@ pub fn routes(app: &mut web::ServiceConfig) {
     )
     .service(web::resource("/events/{id}/ticket_holder_count").route(web::get().to(events::ticket_holder_count)))
     .service(web::resource("/events/{id}/clone").route(web::post().to(events::clone)))
+    .service(
+        web::resource("/events/{id}/checkout_questions")
+            .route(web::get().to(checkout_questions::index))
+            .route(web::post().to(checkout_questions::create)),
+    )
     .service(
         web::resource("/events/{id}/codes")
             .route(web::get().to(events::codes))
@@ -122,6 +193,13 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::post().to(events::add_interest))
             .route(web::delete().to(events::remove_interest)),
     )
+    .service(
+        web::resource("/events/{id}/inventory_pools")
+            .route(web::get().to(inventory_pools::index))
+            .route(web::post().to(inventory_pools::create)),
+    )
+    .service(web::resource("/events/{id}/packages").route(web::get().to(packages::index_for_event)))
+    .service(web::resource("/events/{id}/products").route(web::get().to(products::index_for_event)))
     .service(web::resource("/events/{id}/publish").route(web::post().to(events::publish)))
     .service(
         web::resource("/events/{id}/broadcasts")
@@ -133,11 +211,13 @@ pub fn routes(app: &mut web::ServiceConfig) {
     .service(web::resource("/events/{id}/rarities").route(web::post().to(rarities::create)))
     .service(web::resource("/events/{id}/redeem/{ticket_instance_id}").route(web::post().to(events::redeem_ticket)))
     .service(web::resource("/events/{id}/redeem").route(web::post().to(events::redeem_ticket)))
+    .service(web::resource("/events/{id}/redeem_add_ons").route(web::post().to(events::redeem_add_ons)))
     .service(
         web::resource("/events/{id}/report_subscribers")
             .route(web::get().to(event_report_subscribers::index))
             .route(web::post().to(event_report_subscribers::create)),
     )
+    .service(web::resource("/events/{id}/scans").route(web::get().to(ticket_scans::index)))
     .service(web::resource("/events/{id}/tickets").route(web::get().to(tickets::index)))
     .service(
         web::resource("/events/{id}/ticket_types")
@@ -163,11 +243,26 @@ pub fn routes(app: &mut web::ServiceConfig) {
     .service(web::resource("/external/facebook/web_login").route(web::post().to(external::facebook::web_login)))
     .service(web::resource("/external/facebook/scopes").route(web::get().to(external::facebook::scopes)))
     .service(web::resource("/external/facebook").route(web::delete().to(external::facebook::disconnect)))
+    .service(web::resource("/fan_segments/{id}/export").route(web::get().to(fan_segments::export)))
+    .service(web::resource("/fan_segments/{id}/fans").route(web::get().to(fan_segments::fans)))
+    .service(web::resource("/fan_segments/{id}/sendgrid_sync").route(web::post().to(fan_segments::sync_sendgrid)))
+    .service(
+        web::resource("/fan_segments/{id}")
+            .route(web::get().to(fan_segments::show))
+            .route(web::put().to(fan_segments::update))
+            .route(web::delete().to(fan_segments::destroy)),
+    )
     .service(
         web::resource("/genres")
             .wrap(CacheResource::new(CacheUsersBy::None))
             .route(web::get().to(genres::index)),
     )
+    .service(
+        web::resource("/inventory_pools/{id}")
+            .route(web::get().to(inventory_pools::show))
+            .route(web::put().to(inventory_pools::update))
+            .route(web::delete().to(inventory_pools::destroy)),
+    )
     .service(web::resource("/invitations/{id}").route(web::get().to(organization_invites::view)))
     .service(web::resource("/invitations").route(web::post().to(organization_invites::accept_request)))
     .service(web::resource("/ipns/globee").route(web::post().to(ipns::globee)))
@@ -195,6 +290,11 @@ pub fn routes(app: &mut web::ServiceConfig) {
     )
     .service(web::resource("/orders").route(web::get().to(orders::index)))
     .service(web::resource("/orders/{id}/activity").route(web::get().to(orders::activity)))
+    .service(
+        web::resource("/orders/{id}/checkout_answers")
+            .route(web::get().to(orders::checkout_answers))
+            .route(web::put().to(orders::update_checkout_answers)),
+    )
     .service(web::resource("/orders/{id}/details").route(web::get().to(orders::details)))
     .service(web::resource("/orders/{id}/refund").route(web::patch().to(orders::refund)))
     .service(web::resource("/orders/{id}/resend_confirmation").route(web::post().to(orders::resend_confirmation)))
@@ -210,6 +310,11 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::get().to(organization_venues::show))
             .route(web::delete().to(organization_venues::destroy)),
     )
+    .service(
+        web::resource("/organizations/{id}/affiliate_links")
+            .route(web::get().to(affiliate_links::index))
+            .route(web::post().to(affiliate_links::create)),
+    )
     .service(
         web::resource("/organizations/{id}/announcements").route(web::get().to(announcements::show_from_organization)),
     )
@@ -218,8 +323,38 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::get().to(artists::show_from_organizations))
             .route(web::post().to(organizations::add_artist)),
     )
+    .service(
+        web::resource("/organizations/{id}/accounting_account_mappings")
+            .route(web::get().to(accounting_account_mappings::index))
+            .route(web::put().to(accounting_account_mappings::update)),
+    )
+    .service(
+        web::resource("/organizations/{id}/bank_account")
+            .route(web::get().to(organization_bank_accounts::show))
+            .route(web::put().to(organization_bank_accounts::update)),
+    )
+    .service(
+        web::resource("/organizations/{id}/branding")
+            .route(web::get().to(organization_brandings::show))
+            .route(web::put().to(organization_brandings::update)),
+    )
+    .service(
+        web::resource("/organizations/{id}/cash_drawer_sessions")
+            .route(web::get().to(cash_drawer_sessions::index))
+            .route(web::post().to(cash_drawer_sessions::create)),
+    )
+    .service(
+        web::resource("/organizations/{id}/event_series")
+            .route(web::get().to(event_series::index))
+            .route(web::post().to(event_series::create)),
+    )
     .service(web::resource("/organizations/{id}/events").route(web::get().to(events::show_from_organizations)))
     .service(web::resource("/organizations/{id}/export_event_data").route(web::get().to(events::export_event_data)))
+    .service(
+        web::resource("/organizations/{id}/fan_segments")
+            .route(web::get().to(fan_segments::index))
+            .route(web::post().to(fan_segments::create)),
+    )
     .service(
         web::resource("/organizations/{id}/fans/{user_id}/activity")
             .wrap(CacheResource::new(CacheUsersBy::OrganizationScopePresence(
@@ -260,16 +395,37 @@ pub fn routes(app: &mut web::ServiceConfig) {
     .service(
         web::resource("/organizations/{id}/invites/{invite_id}").route(web::delete().to(organization_invites::destroy)),
     )
+    .service(web::resource("/organizations/{id}/ledger/trial_balance").route(web::get().to(ledger::trial_balance)))
     .service(
         web::resource("/organizations/{id}/organization_venues")
             .route(web::get().to(organization_venues::organizations_index))
             .route(web::post().to(organization_venues::create)),
     )
+    .service(
+        web::resource("/organizations/{id}/packages")
+            .route(web::get().to(packages::index))
+            .route(web::post().to(packages::create)),
+    )
+    .service(
+        web::resource("/organizations/{id}/products")
+            .route(web::get().to(products::index))
+            .route(web::post().to(products::create)),
+    )
+    .service(
+        web::resource("/organizations/{id}/settlement_statement_subscribers")
+            .route(web::get().to(settlement_statement_subscribers::index))
+            .route(web::post().to(settlement_statement_subscribers::create)),
+    )
     .service(
         web::resource("/organizations/{id}/settlements")
             .route(web::get().to(settlements::index))
             .route(web::post().to(settlements::create)),
     )
+    .service(
+        web::resource("/organizations/{id}/tax_rules")
+            .route(web::get().to(tax_rules::index))
+            .route(web::post().to(tax_rules::create)),
+    )
     .service(
         web::resource("/organizations/{id}/invites")
             .route(web::get().to(organization_invites::index))
@@ -293,6 +449,34 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::get().to(organizations::index))
             .route(web::post().to(organizations::create)),
     )
+    .service(web::resource("/package_ticket_types/{id}").route(web::delete().to(packages::remove_ticket_type)))
+    .service(web::resource("/packages/{id}/ticket_types").route(web::post().to(packages::add_ticket_type)))
+    .service(
+        web::resource("/packages/{id}")
+            .route(web::get().to(packages::show))
+            .route(web::put().to(packages::update))
+            .route(web::delete().to(packages::destroy)),
+    )
+    .service(
+        web::resource("/partner/cart")
+            .route(web::post().to(partner_cart::update_cart))
+            .route(web::put().to(partner_cart::replace_cart))
+            .route(web::get().to(partner_cart::show)),
+    )
+    .service(web::resource("/partner/cart/checkout").route(web::post().to(partner_cart::checkout)))
+    .service(web::resource("/partner/checkout_sessions").route(web::post().to(partner_checkout_sessions::create)))
+    .service(
+        web::resource("/partners")
+            .route(web::get().to(partners::index))
+            .route(web::post().to(partners::create)),
+    )
+    .service(web::resource("/partners/{id}/api_key").route(web::post().to(partners::create_api_key)))
+    .service(
+        web::resource("/partners/{id}")
+            .route(web::get().to(partners::show))
+            .route(web::put().to(partners::update))
+            .route(web::delete().to(partners::destroy)),
+    )
     .service(
         web::resource("/password_reset")
             .route(web::post().to(password_resets::create))
@@ -300,6 +484,18 @@ pub fn routes(app: &mut web::ServiceConfig) {
     )
     .service(web::resource("/payments/callback/{nonce}/{id}").route(web::get().to(payments::callback)))
     .service(web::resource("/payment_methods").route(web::get().to(payment_methods::index)))
+    .service(
+        web::resource("/product_variants/{id}")
+            .route(web::put().to(products::update_variant))
+            .route(web::delete().to(products::destroy_variant)),
+    )
+    .service(web::resource("/products/{id}/variants").route(web::post().to(products::create_variant)))
+    .service(
+        web::resource("/products/{id}")
+            .route(web::get().to(products::show))
+            .route(web::put().to(products::update))
+            .route(web::delete().to(products::destroy)),
+    )
     .service(web::resource("/redemption_codes/{code}").route(web::get().to(redemption_codes::show)))
     .service(
         web::resource("/regions/{id}")
@@ -335,11 +531,28 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::get().to(settlement_adjustments::index))
             .route(web::post().to(settlement_adjustments::create)),
     )
+    .service(web::resource("/settlement_payouts/{id}").route(web::put().to(settlement_payouts::update)))
+    .service(
+        web::resource("/settlements/{id}/payouts")
+            .route(web::get().to(settlement_payouts::index))
+            .route(web::post().to(settlement_payouts::create)),
+    )
+    .service(
+        web::resource("/settlement_statement_subscribers/{id}")
+            .route(web::delete().to(settlement_statement_subscribers::destroy)),
+    )
+    .service(web::resource("/settlements/{id}/statement").route(web::get().to(settlements::statement)))
     .service(
         web::resource("/settlements/{id}")
             .route(web::get().to(settlements::show))
             .route(web::delete().to(settlements::destroy)),
     )
+    .service(
+        web::resource("/tax_rules/{id}")
+            .route(web::get().to(tax_rules::show))
+            .route(web::put().to(tax_rules::update))
+            .route(web::delete().to(tax_rules::destroy)),
+    )
     .service(web::resource("/tickets/transfer").route(web::post().to(tickets::transfer_authorization)))
     .service(web::resource("/tickets/receive").route(web::post().to(tickets::receive_transfer)))
     .service(web::resource("/tickets/send").route(web::post().to(tickets::send_via_email_or_phone)))
@@ -349,7 +562,23 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::patch().to(tickets::update)),
     )
     .service(web::resource("/tickets").route(web::get().to(tickets::index)))
+    .service(web::resource("/tickets/{id}/loot_box").route(web::get().to(loot_boxes::show)))
+    .service(web::resource("/tickets/{id}/open").route(web::post().to(loot_boxes::open)))
     .service(web::resource("/tickets/{id}/redeem").route(web::get().to(tickets::show_redeemable_ticket)))
+    .service(web::resource("/tickets/{id}/wallet_passes/apple").route(web::get().to(wallet_passes::apple_pass)))
+    .service(web::resource("/tickets/{id}/wallet_passes/google").route(web::get().to(wallet_passes::google_pass)))
+    .service(
+        web::resource("/trades")
+            .route(web::get().to(trades::index))
+            .route(web::post().to(trades::create)),
+    )
+    .service(
+        web::resource("/trades/{id}")
+            .route(web::get().to(trades::show))
+            .route(web::delete().to(trades::cancel)),
+    )
+    .service(web::resource("/trades/{id}/accept").route(web::post().to(trades::accept)))
+    .service(web::resource("/trades/{id}/decline").route(web::post().to(trades::decline)))
     .service(web::resource("/transfers/transfer_key/{id}").route(web::get().to(transfers::show_by_transfer_key)))
     .service(web::resource("/transfers/activity").route(web::get().to(transfers::activity)))
     .service(web::resource("/transfers/{id}").route(web::delete().to(transfers::cancel)))
@@ -359,6 +588,8 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::get().to(users::current_user))
             .route(web::put().to(users::update_current_user)),
     )
+    .service(web::resource("/users/me/affiliate_earnings").route(web::get().to(affiliate_links::earnings)))
+    .service(web::resource("/users/me/credits").route(web::get().to(trades::credits)))
     .service(web::resource("/users/register").route(web::post().to(users::register)))
     .service(web::resource("/users/{id}/tokens").route(web::get().to(users::show_push_notification_tokens_for_user_id)))
     .service(
@@ -400,6 +631,22 @@ pub fn routes(app: &mut web::ServiceConfig) {
             .route(web::get().to(venues::index))
             .route(web::post().to(venues::create)),
     )
+    .service(
+        web::resource(
+            "/wallet_passes/v1/devices/{device_library_identifier}/registrations/{pass_type_identifier}/{serial_number}",
+        )
+        .route(web::post().to(wallet_passes::register_device))
+        .route(web::delete().to(wallet_passes::unregister_device)),
+    )
+    .service(
+        web::resource("/wallet_passes/v1/devices/{device_library_identifier}/registrations/{pass_type_identifier}")
+            .route(web::get().to(wallet_passes::updated_passes)),
+    )
+    .service(
+        web::resource("/wallet_passes/v1/passes/{pass_type_identifier}/{serial_number}")
+            .route(web::get().to(wallet_passes::latest_pass)),
+    )
+    .service(web::resource("/wallet_passes/v1/log").route(web::post().to(wallet_passes::log)))
     .service(
         web::resource("/sitemap.xml")
             .wrap(CacheResource::new(CacheUsersBy::None))
@@ -1,7 +1,7 @@
 use crate::config::{Config, ProductContext};
 use crate::database::*;
 use crate::domain_events::DomainActionMonitor;
-use crate::middleware::{ApiLogger, AppVersionHeader, DatabaseTransaction, Metatags};
+use crate::middleware::{ApiLogger, AppVersionHeader, DatabaseTransaction, Localization, Metatags, PartnerCors};
 use crate::models::*;
 use crate::utils::redis::*;
 use crate::utils::spotify;
@@ -130,6 +130,7 @@ impl Server {
                                 .allowed_headers(vec![
                                     http::header::AUTHORIZATION,
                                     http::header::ACCEPT,
+                                    http::header::ACCEPT_LANGUAGE,
                                     "X-API-Client-Version".parse::<http::header::HeaderName>().unwrap(),
                                 ])
                                 .allowed_header(http::header::CONTENT_TYPE)
@@ -137,9 +138,11 @@ impl Server {
                                 .max_age(3600)
                                 .finish()
                         })
+                        .wrap(PartnerCors::new())
                         .wrap(Logger::new(LOGGER_FORMAT).exclude("/status"))
                         .wrap(ApiLogger::new())
                         .wrap(DatabaseTransaction::new())
+                        .wrap(Localization::new())
                         .wrap(AppVersionHeader::new())
                         .wrap(Metatags::new(&conf));
 
@@ -144,6 +144,7 @@ async fn send_email_template(
             sendgrid::send_email_template_async(
                 &config.sendgrid_api_key,
                 communication.source.as_ref().unwrap().get_first().unwrap(),
+                communication.source.as_ref().and_then(|s| s.name.clone()),
                 &destination_addresses,
                 template.template_id.clone(),
                 communication.template_data.as_ref().unwrap(),
@@ -16,5 +16,6 @@ mod service_locator;
 pub mod sharetribe_marketplace_api;
 pub mod spotify;
 pub mod twilio;
+pub mod wallet_passes;
 pub mod webhook;
 mod webhook_adapters;
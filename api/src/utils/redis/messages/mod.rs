@@ -1,3 +1,5 @@
 pub use self::ticket_redemption::*;
+pub use self::zone_occupancy::*;
 
 pub mod ticket_redemption;
+pub mod zone_occupancy;
@@ -0,0 +1,8 @@
+use uuid::Uuid;
+
+#[derive(Deserialize, Default, Serialize)]
+pub struct ZoneOccupancy {
+    pub event_id: Uuid,
+    pub access_zone_id: Uuid,
+    pub occupancy: i64,
+}
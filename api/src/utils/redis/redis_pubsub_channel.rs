@@ -6,5 +6,6 @@ use std::str::FromStr;
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Eq, Hash)]
 pub enum RedisPubSubChannel {
     TicketRedemptions,
+    ZoneOccupancy,
 }
-string_enum! { RedisPubSubChannel[TicketRedemptions] }
+string_enum! { RedisPubSubChannel[TicketRedemptions, ZoneOccupancy] }
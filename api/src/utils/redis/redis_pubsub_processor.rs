@@ -57,6 +57,7 @@ impl RedisPubSubProcessor {
 
             // Todo: switch channels into enum
             pubsub.subscribe(RedisPubSubChannel::TicketRedemptions.to_string())?;
+            pubsub.subscribe(RedisPubSubChannel::ZoneOccupancy.to_string())?;
 
             loop {
                 if rx.try_recv().is_ok() {
@@ -88,6 +89,24 @@ impl RedisPubSubProcessor {
                                 );
                             }
                         }
+                        "ZoneOccupancy" => {
+                            let payload: messages::ZoneOccupancy =
+                                serde_json::from_str(&message.get_payload::<String>()?)?;
+                            let clients = websocket_clients.clone();
+                            let clients_mutex = clients.lock().unwrap();
+
+                            if let Some(listeners) = clients_mutex.get(&payload.event_id) {
+                                EventWebSocket::send_message(
+                                    &listeners,
+                                    EventWebSocketMessage::new(json!({
+                                            "event_id": payload.event_id,
+                                            "access_zone_id": payload.access_zone_id,
+                                            "occupancy": payload.occupancy,
+                                            "event_web_socket_type": EventWebSocketType::ZoneOccupancy
+                                    })),
+                                );
+                            }
+                        }
                         _ => (),
                     },
                     Err(_) => {
@@ -74,13 +74,25 @@ impl ServiceLocator {
                     self.globee_base_url.clone(),
                 )))
             }
-            // External is not valid for service locator
-            PaymentProviders::Free | PaymentProviders::External => {
+            // External and credit payments are not valid for service locator
+            PaymentProviders::Credit | PaymentProviders::Free | PaymentProviders::External => {
                 return Err(ApplicationError::new("Unknown payment provider".into()).into());
             }
         }
     }
 
+    /// Payment processor for charges made on behalf of the platform rather than an organization,
+    /// e.g. cash offered between fans in a trade
+    pub fn create_platform_payment_processor(
+        &self,
+        provider: PaymentProviders,
+    ) -> Result<Box<dyn PaymentProcessor>, ApiError> {
+        match provider {
+            PaymentProviders::Stripe => Ok(Box::new(StripePaymentProcessor::new(self.stripe_secret_key.clone()))),
+            _ => Err(ApplicationError::new("Unsupported platform payment provider".into()).into()),
+        }
+    }
+
     pub fn create_deep_linker(&self) -> Result<Box<dyn DeepLinker>, ApiError> {
         Ok(Box::new(BranchDeepLinker::new(
             self.branch_io_base_url.clone(),
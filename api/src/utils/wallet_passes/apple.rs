@@ -0,0 +1,185 @@
+use super::{barcode_message, format_date};
+use crate::config::AppleWalletConfig;
+use crate::errors::*;
+use db::prelude::*;
+use openssl::pkcs12::Pkcs12;
+use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+use openssl::pkey::PKey;
+use openssl::sha::sha1;
+use openssl::stack::Stack;
+use openssl::x509::X509;
+use reqwest::blocking::Client;
+use reqwest::{Identity, StatusCode};
+use serde_json::{Map, Value};
+use std::io::{Cursor, Write};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+pub const PKPASS_CONTENT_TYPE: &str = "application/vnd.apple.pkpass";
+// Authorization header scheme wallet apps use when calling the pass web service
+pub const AUTHORIZATION_SCHEME: &str = "ApplePass";
+const APNS_URL: &str = "https://api.push.apple.com/3/device";
+
+const IMAGES: [(&str, &[u8]); 4] = [
+    ("icon.png", include_bytes!("assets/icon.png")),
+    ("icon@2x.png", include_bytes!("assets/icon@2x.png")),
+    ("logo.png", include_bytes!("assets/logo.png")),
+    ("logo@2x.png", include_bytes!("assets/logo@2x.png")),
+];
+
+/// Builds the signed `.pkpass` bundle: the pass definition and images, a manifest of their SHA-1
+/// hashes and a detached PKCS #7 signature of the manifest
+pub fn pkpass(
+    config: &AppleWalletConfig,
+    web_service_url: &str,
+    details: &WalletPassDetails,
+) -> Result<Vec<u8>, ApiError> {
+    let pass = serde_json::to_vec(&pass_json(config, web_service_url, details))?;
+    let mut files: Vec<(&str, &[u8])> = vec![("pass.json", &pass[..])];
+    files.extend(IMAGES.iter().cloned());
+
+    let mut manifest = Map::new();
+    for (name, content) in &files {
+        manifest.insert(name.to_string(), json!(hex(&sha1(content))));
+    }
+    let manifest = serde_json::to_vec(&manifest)?;
+    let signature = sign(config, &manifest)?;
+    files.push(("manifest.json", &manifest[..]));
+    files.push(("signature", &signature[..]));
+
+    let mut bundle = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, content) in files {
+        bundle.start_file(name, options)?;
+        bundle.write_all(content)?;
+    }
+    Ok(bundle.finish()?.into_inner())
+}
+
+pub fn pass_json(config: &AppleWalletConfig, web_service_url: &str, details: &WalletPassDetails) -> Value {
+    let venue_name = details.venue.as_ref().map(|venue| venue.name.clone());
+    let mut secondary_fields = Vec::new();
+    if let Some(venue_name) = &venue_name {
+        secondary_fields.push(json!({"key": "venue", "label": "VENUE", "value": venue_name}));
+    }
+    let mut auxiliary_fields = Vec::new();
+    if let Some(event_start) = details.event_start {
+        auxiliary_fields.push(json!({
+            "key": "event_start",
+            "label": "DATE",
+            "value": format_date(event_start),
+            "dateStyle": "PKDateStyleMedium",
+            "timeStyle": "PKDateStyleShort"
+        }));
+    }
+    auxiliary_fields.push(json!({"key": "ticket_type", "label": "TICKET", "value": details.ticket_type_name}));
+    if let Some(holder_name) = &details.holder_name {
+        auxiliary_fields.push(json!({"key": "holder", "label": "NAME", "value": holder_name}));
+    }
+    let mut back_fields = Vec::new();
+    if let Some(door_time) = details.door_time {
+        back_fields.push(json!({
+            "key": "door_time",
+            "label": "Doors",
+            "value": format_date(door_time),
+            "dateStyle": "PKDateStyleMedium",
+            "timeStyle": "PKDateStyleShort"
+        }));
+    }
+    if let Some(venue) = &details.venue {
+        back_fields.push(json!({
+            "key": "venue_address",
+            "label": "Venue",
+            "value": format!("{}\n{}\n{}, {} {}", venue.name, venue.address, venue.city, venue.state, venue.postal_code)
+        }));
+    }
+    back_fields.push(json!({"key": "ticket_id", "label": "Ticket", "value": details.ticket_instance_id}));
+
+    let mut pass = json!({
+        "formatVersion": 1,
+        "passTypeIdentifier": config.pass_type_identifier,
+        "teamIdentifier": config.team_identifier,
+        "serialNumber": details.serial_number,
+        "authenticationToken": details.authentication_token,
+        "webServiceURL": web_service_url,
+        "organizationName": details.organization_name,
+        "description": format!("Ticket for {}", details.event_name),
+        "logoText": details.organization_name,
+        "foregroundColor": "rgb(255, 255, 255)",
+        "backgroundColor": "rgb(34, 34, 34)",
+        "labelColor": "rgb(255, 34, 178)",
+        "voided": details.voided || details.status == TicketInstanceStatus::Nullified,
+        "eventTicket": {
+            "primaryFields": [{"key": "event", "label": "EVENT", "value": details.event_name}],
+            "secondaryFields": secondary_fields,
+            "auxiliaryFields": auxiliary_fields,
+            "backFields": back_fields
+        }
+    });
+    if let Some(event_start) = details.event_start {
+        pass["relevantDate"] = json!(format_date(event_start));
+    }
+    if let Some(message) = barcode_message(details) {
+        let barcode = json!({
+            "format": "PKBarcodeFormatQR",
+            "message": message,
+            "messageEncoding": "iso-8859-1"
+        });
+        pass["barcodes"] = json!([barcode]);
+        // Devices older than iOS 9 only read the single barcode
+        pass["barcode"] = barcode;
+    }
+    if let Some(venue) = &details.venue {
+        if let (Some(latitude), Some(longitude)) = (venue.latitude, venue.longitude) {
+            pass["locations"] = json!([{
+                "latitude": latitude,
+                "longitude": longitude,
+                "relevantText": format!("{} at {}", details.event_name, venue.name)
+            }]);
+        }
+    }
+    pass
+}
+
+/// Asks a device to fetch the latest version of its passes. Returns false if the device no longer
+/// has the pass and its registration should be removed.
+pub fn push_update(config: &AppleWalletConfig, push_token: &str) -> Result<bool, ApiError> {
+    let certificate = X509::from_pem(config.certificate.as_bytes())?;
+    let private_key = PKey::private_key_from_pem(config.private_key.as_bytes())?;
+    let identity = Pkcs12::builder().build("", "wallet passes", &private_key, &certificate)?;
+    let client = Client::builder()
+        .identity(Identity::from_pkcs12_der(&identity.to_der()?, "")?)
+        .http2_prior_knowledge()
+        .build()?;
+
+    // Wallet pushes have an empty payload, the device calls back to the web service for changes
+    let response = client
+        .post(&format!("{}/{}", APNS_URL, push_token))
+        .header("apns-topic", config.pass_type_identifier.as_str())
+        .body("{}")
+        .send()?;
+    match response.status() {
+        StatusCode::GONE => Ok(false),
+        status if status.is_success() => Ok(true),
+        status => Err(ApplicationError::new(format!("Apple push notification failed with status {}", status)).into()),
+    }
+}
+
+fn sign(config: &AppleWalletConfig, manifest: &[u8]) -> Result<Vec<u8>, ApiError> {
+    let certificate = X509::from_pem(config.certificate.as_bytes())?;
+    let private_key = PKey::private_key_from_pem(config.private_key.as_bytes())?;
+    let mut chain = Stack::new()?;
+    chain.push(X509::from_pem(config.wwdr_certificate.as_bytes())?)?;
+    let signature = Pkcs7::sign(
+        &certificate,
+        &private_key,
+        &chain,
+        manifest,
+        Pkcs7Flags::BINARY | Pkcs7Flags::DETACHED,
+    )?;
+    Ok(signature.to_der()?)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,162 @@
+use super::{barcode_message, format_date};
+use crate::config::GoogleWalletConfig;
+use crate::errors::*;
+use crate::jwt::{encode, Algorithm, Header};
+use chrono::prelude::*;
+use db::prelude::*;
+use openssl::rsa::Rsa;
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+const SAVE_URL: &str = "https://pay.google.com/gp/v/save";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const WALLET_OBJECTS_URL: &str = "https://walletobjects.googleapis.com/walletobjects/v1";
+const WALLET_OBJECTS_SCOPE: &str = "https://www.googleapis.com/auth/wallet_object.issuer";
+const LANGUAGE: &str = "en-US";
+
+#[derive(Deserialize)]
+struct AccessTokenResponse {
+    access_token: String,
+}
+
+/// Every event has a single pass class holding the event details, ticket passes are objects of
+/// that class
+pub fn class_id(config: &GoogleWalletConfig, details: &WalletPassDetails) -> String {
+    format!("{}.{}", config.issuer_id, details.event_id)
+}
+
+pub fn object_id(config: &GoogleWalletConfig, details: &WalletPassDetails) -> String {
+    format!("{}.{}", config.issuer_id, details.serial_number)
+}
+
+pub fn event_ticket_class(config: &GoogleWalletConfig, details: &WalletPassDetails) -> Value {
+    let mut class = json!({
+        "id": class_id(config, details),
+        "issuerName": details.organization_name,
+        "reviewStatus": "UNDER_REVIEW",
+        "eventName": localized(&details.event_name),
+        "dateTime": {}
+    });
+    if let Some(door_time) = details.door_time {
+        class["dateTime"]["doorsOpen"] = json!(format_date(door_time));
+    }
+    if let Some(event_start) = details.event_start {
+        class["dateTime"]["start"] = json!(format_date(event_start));
+    }
+    if let Some(event_end) = details.event_end {
+        class["dateTime"]["end"] = json!(format_date(event_end));
+    }
+    if let Some(venue) = &details.venue {
+        class["venue"] = json!({
+            "name": localized(&venue.name),
+            "address": localized(&format!(
+                "{}\n{}, {} {}",
+                venue.address, venue.city, venue.state, venue.postal_code
+            ))
+        });
+        if let (Some(latitude), Some(longitude)) = (venue.latitude, venue.longitude) {
+            class["locations"] = json!([{"latitude": latitude, "longitude": longitude}]);
+        }
+    }
+    class
+}
+
+pub fn event_ticket_object(config: &GoogleWalletConfig, details: &WalletPassDetails) -> Value {
+    let active = !details.voided && details.status != TicketInstanceStatus::Nullified;
+    let mut object = json!({
+        "id": object_id(config, details),
+        "classId": class_id(config, details),
+        "state": if active { "ACTIVE" } else { "INACTIVE" },
+        "ticketType": localized(&details.ticket_type_name),
+        "hexBackgroundColor": "#222222"
+    });
+    if let Some(holder_name) = &details.holder_name {
+        object["ticketHolderName"] = json!(holder_name);
+    }
+    if let Some(message) = barcode_message(details) {
+        object["barcode"] = json!({"type": "QR_CODE", "value": message});
+    }
+    object
+}
+
+/// Link that adds the pass to the holder's Google Pay app. The class and object are embedded in
+/// a JWT signed with the service account key so nothing has to be created with Google upfront.
+pub fn save_url(config: &GoogleWalletConfig, origin: &str, details: &WalletPassDetails) -> Result<String, ApiError> {
+    let claims = json!({
+        "iss": config.service_account_email,
+        "aud": "google",
+        "typ": "savetowallet",
+        "iat": Utc::now().timestamp(),
+        "origins": [origin],
+        "payload": {
+            "eventTicketClasses": [event_ticket_class(config, details)],
+            "eventTicketObjects": [event_ticket_object(config, details)]
+        }
+    });
+    Ok(format!("{}/{}", SAVE_URL, sign(config, &claims)?))
+}
+
+/// Replaces the class and object saved with Google so the holder's pass shows the latest details.
+/// Passes that were never saved are skipped.
+pub fn update_pass(config: &GoogleWalletConfig, details: &WalletPassDetails) -> Result<(), ApiError> {
+    let client = Client::new();
+    let access_token = access_token(&client, config)?;
+    let resources = vec![
+        (
+            "eventTicketClass",
+            class_id(config, details),
+            event_ticket_class(config, details),
+        ),
+        (
+            "eventTicketObject",
+            object_id(config, details),
+            event_ticket_object(config, details),
+        ),
+    ];
+    for (resource, id, body) in resources {
+        let response = client
+            .put(&format!("{}/{}/{}", WALLET_OBJECTS_URL, resource, id))
+            .bearer_auth(&access_token)
+            .json(&body)
+            .send()?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        response.error_for_status()?;
+    }
+    Ok(())
+}
+
+fn access_token(client: &Client, config: &GoogleWalletConfig) -> Result<String, ApiError> {
+    let issued_at = Utc::now().timestamp();
+    let assertion = sign(
+        config,
+        &json!({
+            "iss": config.service_account_email,
+            "scope": WALLET_OBJECTS_SCOPE,
+            "aud": TOKEN_URL,
+            "iat": issued_at,
+            "exp": issued_at + 3600
+        }),
+    )?;
+    let response: AccessTokenResponse = client
+        .post(TOKEN_URL)
+        .form(&[
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", assertion.as_str()),
+        ])
+        .send()?
+        .error_for_status()?
+        .json()?;
+    Ok(response.access_token)
+}
+
+fn sign(config: &GoogleWalletConfig, claims: &Value) -> Result<String, ApiError> {
+    let private_key = Rsa::private_key_from_pem(config.private_key.as_bytes())?.private_key_to_der()?;
+    Ok(encode(&Header::new(Algorithm::RS256), claims, &private_key)?)
+}
+
+fn localized(value: &str) -> Value {
+    json!({"defaultValue": {"language": LANGUAGE, "value": value}})
+}
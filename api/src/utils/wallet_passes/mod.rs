@@ -0,0 +1,31 @@
+use chrono::prelude::*;
+use db::prelude::*;
+
+pub mod apple;
+pub mod google;
+
+/// Content of the QR code scanned at the door, the same payload the mobile apps encode for a ticket.
+/// Voided passes have no barcode. Neither do passes for events using rotating barcodes, a static
+/// code would be rejected at the door so holders show the code from the app instead.
+pub fn barcode_message(details: &WalletPassDetails) -> Option<String> {
+    if details.barcode_rotation_seconds.is_some() {
+        return None;
+    }
+    details.redeem_key.as_ref().map(|redeem_key| {
+        json!({
+            "type": 0,
+            "data": {
+                "redeem_key": redeem_key,
+                "id": details.ticket_instance_id,
+                "event_id": details.event_id,
+                "extra": ""
+            }
+        })
+        .to_string()
+    })
+}
+
+/// Event times are stored in UTC, wallet apps localize them for display
+fn format_date(date: NaiveDateTime) -> String {
+    DateTime::<Utc>::from_utc(date, Utc).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
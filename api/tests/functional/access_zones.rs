@@ -0,0 +1,205 @@
+use crate::support;
+use crate::support::database::TestDatabase;
+use crate::support::test_request::TestRequest;
+use actix_web::{
+    http::StatusCode,
+    web::{Path, Query},
+    FromRequest, HttpResponse,
+};
+use api::controllers::access_zones::{self, CreateAccessZoneRequest, UpdateAccessZoneRequest};
+use api::controllers::ticket_scans::{self, TicketScanFilterParameters, TicketScanRequest};
+use api::database::CacheDatabase;
+use api::extractors::*;
+use api::models::PathParameters;
+use db::prelude::*;
+
+#[actix_rt::test]
+async fn create_update_and_destroy() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgAdmin, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = event.id;
+    let response: HttpResponse = access_zones::create((
+        database.connection.clone().into(),
+        path,
+        Json(CreateAccessZoneRequest {
+            name: "VIP deck".to_string(),
+            re_entry_policy: ReEntryPolicies::NoReEntry,
+            ticket_type_ids: vec![ticket_type.id],
+        }),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let access_zone: DisplayAccessZone =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(access_zone.event_id, event.id);
+    assert_eq!(access_zone.re_entry_policy, ReEntryPolicies::NoReEntry);
+    assert_eq!(access_zone.ticket_type_ids, vec![ticket_type.id]);
+    assert_eq!(access_zone.occupancy, 0);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = access_zone.id;
+    let response: HttpResponse = access_zones::update((
+        database.connection.clone().into(),
+        path,
+        Json(UpdateAccessZoneRequest {
+            name: Some("Backstage".to_string()),
+            ticket_type_ids: Some(Vec::new()),
+            ..Default::default()
+        }),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let access_zone: DisplayAccessZone =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(access_zone.name, "Backstage".to_string());
+    assert_eq!(access_zone.re_entry_policy, ReEntryPolicies::NoReEntry);
+    assert!(access_zone.ticket_type_ids.is_empty());
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = access_zone.id;
+    let response: HttpResponse = access_zones::destroy((database.connection.clone().into(), path, auth_user.clone()))
+        .await
+        .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(AccessZone::find_for_event(event.id, connection).unwrap().is_empty());
+}
+
+#[actix_rt::test]
+async fn create_requires_event_write() {
+    let database = TestDatabase::new();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database.create_event().with_organization(&organization).finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::DoorPerson, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = event.id;
+    let response: HttpResponse = access_zones::create((
+        database.connection.clone().into(),
+        path,
+        Json(CreateAccessZoneRequest {
+            name: "VIP deck".to_string(),
+            re_entry_policy: ReEntryPolicies::Unlimited,
+            ticket_type_ids: Vec::new(),
+        }),
+        auth_user,
+    ))
+    .await
+    .into();
+    support::expects_unauthorized(&response);
+}
+
+#[actix_rt::test]
+async fn scan_and_index() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let user2 = database.create_user().finish();
+    let ticket = database.create_purchased_tickets(&user2, ticket_type.id, 1).remove(0);
+    let access_zone = AccessZone::create(event.id, "VIP deck".to_string(), ReEntryPolicies::Unlimited)
+        .commit(None, connection)
+        .unwrap();
+    access_zone
+        .update_ticket_types(vec![ticket_type.id], connection)
+        .unwrap();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::DoorPerson, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let scans = vec![
+        (
+            ticket.redeem_key.clone().unwrap(),
+            ScanDirections::Entry,
+            StatusCode::OK,
+        ),
+        // Already inside
+        (
+            ticket.redeem_key.clone().unwrap(),
+            ScanDirections::Entry,
+            StatusCode::BAD_REQUEST,
+        ),
+        ("UNKNOWN".to_string(), ScanDirections::Entry, StatusCode::BAD_REQUEST),
+        (ticket.redeem_key.clone().unwrap(), ScanDirections::Exit, StatusCode::OK),
+    ];
+    for (redeem_key, direction, status) in scans {
+        let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+        path.id = access_zone.id;
+        let response: HttpResponse = ticket_scans::create((
+            database.connection.clone().into(),
+            path,
+            Json(TicketScanRequest {
+                redeem_key,
+                redeem_code: None,
+                direction,
+                device_id: Some("gate-1".to_string()),
+            }),
+            auth_user.clone(),
+            test_request.extract_state().await,
+            CacheDatabase { inner: None },
+        ))
+        .await
+        .into();
+        assert_eq!(response.status(), status);
+    }
+    assert_eq!(
+        TicketInstance::find(ticket.id, connection).unwrap().status,
+        TicketInstanceStatus::Redeemed
+    );
+    assert_eq!(access_zone.occupancy(connection).unwrap(), 0);
+
+    // Door staff can scan but the scan log is for organization members
+    let admin = database.create_user().finish();
+    let auth_admin = support::create_auth_user_from_user(&admin, Roles::OrgAdmin, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/?result=Rejected", vec!["id"]);
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = event.id;
+    let response = ticket_scans::index((
+        database.connection.clone().into(),
+        path,
+        Query::<PagingParameters>::extract(&test_request.request).await.unwrap(),
+        Query::<TicketScanFilterParameters>::extract(&test_request.request)
+            .await
+            .unwrap(),
+        auth_admin,
+    ))
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let rejection_reasons: Vec<Option<ScanRejectionReasons>> =
+        response.payload().data.iter().map(|s| s.rejection_reason).collect();
+    assert_eq!(
+        rejection_reasons,
+        vec![
+            Some(ScanRejectionReasons::InvalidTicket),
+            Some(ScanRejectionReasons::AlreadyInside)
+        ]
+    );
+    assert!(response
+        .payload()
+        .data
+        .iter()
+        .all(|s| s.device_id == Some("gate-1".to_string()) && s.scanned_by_user_id == user.id));
+}
@@ -0,0 +1,214 @@
+use crate::support;
+use crate::support::database::TestDatabase;
+use crate::support::test_request::TestRequest;
+use actix_web::{
+    http::{header, StatusCode},
+    test,
+    web::{Path, Query},
+    FromRequest, HttpResponse,
+};
+use api::controllers::{affiliate_links, analytics, cart};
+use api::extractors::*;
+use api::models::{PathParameters, RequestInfo};
+use db::prelude::*;
+use serde_json::Value;
+use url::form_urlencoded;
+
+#[actix_rt::test]
+async fn create_and_update() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let promoter = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database.create_event().with_organization(&organization).finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgOwner, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+    let state = test_request.extract_state().await;
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = organization.id;
+    let response: HttpResponse = affiliate_links::create((
+        state.clone(),
+        database.connection.clone().into(),
+        path,
+        Json(AffiliateLink::create(
+            organization.id,
+            promoter.id,
+            Some(event.id),
+            "Newsletter".to_string(),
+        )),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body: Value = serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    let affiliate_link = AffiliateLink::find_by_code(body["code"].as_str().unwrap(), connection).unwrap();
+    assert_eq!(affiliate_link.user_id, promoter.id);
+    assert_eq!(
+        body["url"].as_str().unwrap(),
+        affiliate_link.url(&state.config.front_end_url)
+    );
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = affiliate_link.id;
+    let attributes = AffiliateLinkEditableAttributes {
+        name: Some("Instagram".to_string()),
+        event_id: Some(None),
+    };
+    let response: HttpResponse = affiliate_links::update((
+        state,
+        database.connection.clone().into(),
+        path,
+        Json(attributes),
+        auth_user,
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let affiliate_link = AffiliateLink::find(affiliate_link.id, connection).unwrap();
+    assert_eq!(affiliate_link.name, "Instagram".to_string());
+    assert_eq!(affiliate_link.event_id, None);
+}
+
+#[actix_rt::test]
+async fn create_requires_org_write() {
+    let database = TestDatabase::new();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgBoxOffice, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+    let state = test_request.extract_state().await;
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = organization.id;
+    let response: HttpResponse = affiliate_links::create((
+        state,
+        database.connection.clone().into(),
+        path,
+        Json(AffiliateLink::create(
+            organization.id,
+            user.id,
+            None,
+            "Newsletter".to_string(),
+        )),
+        auth_user,
+    ))
+    .await
+    .into();
+    support::expects_unauthorized(&response);
+}
+
+#[actix_rt::test]
+async fn earnings() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let promoter = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, None, "Newsletter".to_string())
+        .commit(None, connection)
+        .unwrap();
+    let auth_user = support::create_auth_user_from_user(&promoter, Roles::User, None, &database);
+    let test_request = TestRequest::create();
+    let state = test_request.extract_state().await;
+
+    let response: HttpResponse = affiliate_links::earnings((state, database.connection.clone().into(), auth_user))
+        .await
+        .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(
+        body["affiliate_links"][0]["code"].as_str().unwrap(),
+        affiliate_link.code
+    );
+    assert_eq!(
+        body["earnings"][0]["affiliate_link_name"].as_str().unwrap(),
+        "Newsletter"
+    );
+    assert_eq!(body["earnings"][0]["commission_in_cents"].as_i64().unwrap(), 0);
+}
+
+#[actix_rt::test]
+async fn link_through_tracking_to_checkout_records_commission() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let promoter = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, Some(event.id), "Newsletter".to_string())
+        .commit(None, connection)
+        .unwrap();
+    AffiliateCommissionRule::create(affiliate_link.id, None, Some(100), None)
+        .commit(None, connection)
+        .unwrap();
+    let test_request = TestRequest::create();
+    let state = test_request.extract_state().await;
+
+    // Visitor follows the link, the page view records a visit for the link
+    let link_path = format!("/events/{}?{}={}", event.id, AFFILIATE_CODE_PARAM, affiliate_link.code);
+    let query = Query::<analytics::PageViewTrackingData>::from_query(
+        &form_urlencoded::Serializer::new(String::new())
+            .append_pair("event_id", &event.id.to_string())
+            .append_pair("url", &link_path)
+            .finish(),
+    )
+    .unwrap();
+    let request = test::TestRequest::default()
+        .header(header::USER_AGENT, "Mozilla/5.0 (Windows NT 10.0; Win64; x64)")
+        .to_http_request();
+    let response: HttpResponse = analytics::track((state.clone(), query, request, database.connection.clone().into()))
+        .await
+        .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::SET_COOKIE).is_none());
+    let affiliate_link = AffiliateLink::find(affiliate_link.id, connection).unwrap();
+    assert_eq!(affiliate_link.visit_count, 1);
+
+    // Front end passes the code from the link in the tracking data at checkout
+    let buyer = database.create_user().finish();
+    let cart = database.create_cart().for_user(&buyer).for_event(&event).finish();
+    let buyer = support::create_auth_user_from_user(&buyer, Roles::Admin, None, &database);
+    let input = Json(cart::CheckoutCartRequest {
+        tracking_data: Some(json!({ "aff": affiliate_link.code })),
+        method: cart::PaymentRequest::External {
+            reference: Some("TestRef".to_string()),
+            external_payment_type: ExternalPaymentType::Voucher,
+            first_name: "First".to_string(),
+            last_name: "Last".to_string(),
+            email: None,
+            phone: None,
+            note: None,
+        },
+    });
+    let response: HttpResponse = cart::checkout((
+        database.connection.clone().into(),
+        input,
+        buyer,
+        state,
+        RequestInfo { user_agent: None },
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let order = Order::find(cart.id, connection).unwrap();
+    assert_eq!(order.status, OrderStatus::Paid);
+    assert_eq!(order.affiliate_link_id, Some(affiliate_link.id));
+    let ticket_quantity: i64 = order
+        .items(connection)
+        .unwrap()
+        .iter()
+        .filter(|item| item.item_type == OrderItemTypes::Tickets)
+        .map(|item| item.quantity)
+        .sum();
+    let commissions = AffiliateCommission::find_for_order(order.id, connection).unwrap();
+    assert_eq!(commissions.len(), 1);
+    assert_eq!(commissions[0].affiliate_link_id, affiliate_link.id);
+    assert_eq!(commissions[0].amount_in_cents, 100 * ticket_quantity);
+}
@@ -97,7 +97,9 @@ pub async fn redeem_ticket(role: Roles, should_test_succeed: bool) {
     //First try when Redeem code is wrong
     let request_data = TicketRedeemRequest {
         redeem_key: "WrongKey".to_string(),
+        redeem_code: None,
         check_in_source: Some(CheckInSource::Scanned),
+        device_id: None,
     };
 
     let response: HttpResponse = events::redeem_ticket((
@@ -116,7 +118,9 @@ pub async fn redeem_ticket(role: Roles, should_test_succeed: bool) {
         //Now try with redeem code being correct
         let request_data = TicketRedeemRequest {
             redeem_key: ticket.redeem_key.unwrap(),
+            redeem_code: None,
             check_in_source: Some(CheckInSource::Scanned),
+            device_id: None,
         };
 
         let response: HttpResponse = events::redeem_ticket((
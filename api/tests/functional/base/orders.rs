@@ -434,7 +434,8 @@ pub async fn refund(role: Roles, manual_override: bool, should_succeed: bool) {
         manual_override,
     });
 
-    let test_request = TestRequest::create();
+    let tari_simulator = support::tari_simulator_for_tickets(&tickets, connection);
+    let test_request = TestRequest::create_with_tari_client(Box::new(tari_simulator.clone()));
     let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
     path.id = cart.id;
     let response: HttpResponse = orders::refund((
@@ -477,4 +478,25 @@ pub async fn refund(role: Roles, manual_override: bool, should_succeed: bool) {
     } else {
         support::expects_unauthorized(&response);
     }
+
+    // Only the refunded token is returned to the organization on chain
+    let blockchain_asset_id = Asset::find(ticket.asset_id, connection)
+        .unwrap()
+        .blockchain_asset_id
+        .unwrap();
+    let organization_wallet = Wallet::find_default_for_organization(organization.id, connection).unwrap();
+    let user_wallet = Wallet::find(ticket.wallet_id, connection).unwrap();
+    let expected_owner = if should_succeed {
+        organization_wallet.public_key
+    } else {
+        user_wallet.public_key.clone()
+    };
+    assert_eq!(
+        tari_simulator.owner_of(&blockchain_asset_id, ticket.token_id as u64),
+        Some(expected_owner)
+    );
+    assert_eq!(
+        tari_simulator.owner_of(&blockchain_asset_id, tickets[1].token_id as u64),
+        Some(user_wallet.public_key.clone())
+    );
 }
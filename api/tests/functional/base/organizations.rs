@@ -186,6 +186,8 @@ pub async fn create(role: Roles, should_test_succeed: bool) {
         globee_api_key: None,
         max_instances_per_ticket_type: Some(11000),
         settlement_type: None,
+        currency: None,
+        default_locale: None,
     });
 
     let test_request = TestRequest::create_with_uri("/organizations");
@@ -499,6 +501,7 @@ pub async fn show_fee_schedule(role: Roles, should_succeed: bool) {
         name: String,
         version: i64,
         created_at: NaiveDateTime,
+        currency: Currencies,
         ranges: Vec<FeeScheduleRange>,
     }
 
@@ -507,6 +510,7 @@ pub async fn show_fee_schedule(role: Roles, should_succeed: bool) {
         name: fee_schedule.name,
         version: 0,
         created_at: fee_schedule.created_at,
+        currency: fee_schedule.currency,
         ranges: fee_schedule_ranges,
     };
 
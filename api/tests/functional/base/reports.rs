@@ -197,6 +197,7 @@ pub async fn box_office_sales_summary(role: Roles, should_succeed: bool) {
                     BoxOfficeSalesSummaryOperatorEventRow {
                         event_name: Some("Event1".to_string()),
                         event_date: Some(NaiveDate::from_ymd(2016, 7, 8).and_hms(9, 10, 11)),
+                        currency: Currencies::USD,
                         number_of_tickets: 2,
                         face_value_in_cents: 150,
                         revenue_share_value_in_cents: 0,
@@ -205,6 +206,7 @@ pub async fn box_office_sales_summary(role: Roles, should_succeed: bool) {
                     BoxOfficeSalesSummaryOperatorEventRow {
                         event_name: Some("Event2".to_string()),
                         event_date: Some(NaiveDate::from_ymd(2017, 7, 8).and_hms(9, 10, 11)),
+                        currency: Currencies::USD,
                         number_of_tickets: 2,
                         face_value_in_cents: 150,
                         revenue_share_value_in_cents: 0,
@@ -213,6 +215,7 @@ pub async fn box_office_sales_summary(role: Roles, should_succeed: bool) {
                 ],
                 payments: vec![BoxOfficeSalesSummaryPaymentRow {
                     payment_type: ExternalPaymentType::CreditCard,
+                    currency: Currencies::USD,
                     quantity: 4,
                     total_sales_in_cents: 600,
                 }],
@@ -223,6 +226,7 @@ pub async fn box_office_sales_summary(role: Roles, should_succeed: bool) {
                 events: vec![BoxOfficeSalesSummaryOperatorEventRow {
                     event_name: Some("Event1".to_string()),
                     event_date: Some(NaiveDate::from_ymd(2016, 7, 8).and_hms(9, 10, 11)),
+                    currency: Currencies::USD,
                     number_of_tickets: 2,
                     face_value_in_cents: 150,
                     revenue_share_value_in_cents: 0,
@@ -230,6 +234,7 @@ pub async fn box_office_sales_summary(role: Roles, should_succeed: bool) {
                 }],
                 payments: vec![BoxOfficeSalesSummaryPaymentRow {
                     payment_type: ExternalPaymentType::CreditCard,
+                    currency: Currencies::USD,
                     quantity: 2,
                     total_sales_in_cents: 300,
                 }],
@@ -237,9 +242,11 @@ pub async fn box_office_sales_summary(role: Roles, should_succeed: bool) {
         ],
         payments: vec![BoxOfficeSalesSummaryPaymentRow {
             payment_type: ExternalPaymentType::CreditCard,
+            currency: Currencies::USD,
             quantity: 6,
             total_sales_in_cents: 900,
         }],
+        shifts: Vec::new(),
     };
 
     assert_eq!(expected_report_data, report_data);
@@ -489,6 +496,7 @@ fn build_transaction_report_row(
         payment_method: Some(PaymentMethods::CreditCard.to_string()),
         payment_provider: Some(PaymentProviders::Stripe.to_string()),
         transaction_date: order.paid_at.clone().unwrap(),
+        currency: order.currency,
         redemption_code: None,
         order_id: order.id,
         event_id: event.id,
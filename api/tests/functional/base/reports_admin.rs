@@ -112,6 +112,7 @@ pub async fn domain_transaction_detail_report(role: Roles, should_succeed: bool)
             event_date: event.event_start.clone(),
             ticket_type_name: ticket_type.name.clone(),
             transaction_date: order.paid_at.unwrap(),
+            currency: order.currency,
             point_of_sale: None,
             payment_method: PaymentMethods::CreditCard.to_string(),
             qty_tickets_sold: 2,
@@ -47,6 +47,7 @@ pub async fn show_other_user_ticket(role: Roles, should_test_succeed: bool) {
             ticket_type_name: ticket_type.name.clone(),
             status: TicketInstanceStatus::Purchased,
             redeem_key: ticket_response.ticket.redeem_key.clone(),
+            redeem_secret: ticket_response.ticket.redeem_secret.clone(),
             pending_transfer: false,
             first_name_override: None,
             last_name_override: None,
@@ -115,6 +116,7 @@ pub async fn update(role: Roles, owns_ticket: bool, should_test_succeed: bool) {
             ticket_type_name: ticket_type.name.clone(),
             status: TicketInstanceStatus::Purchased,
             redeem_key: ticket_response.ticket.redeem_key.clone(),
+            redeem_secret: ticket_response.ticket.redeem_secret.clone(),
             pending_transfer: false,
             first_name_override: Some("First".to_string()),
             last_name_override: Some("Last".to_string()),
@@ -0,0 +1,173 @@
+use crate::support;
+use crate::support::database::TestDatabase;
+use crate::support::test_request::TestRequest;
+use actix_web::{
+    http::StatusCode,
+    web::{Path, Query},
+    FromRequest, HttpResponse,
+};
+use api::controllers::cash_drawer_sessions::{
+    self, CashDrawerMovementRequest, CashDrawerSessionFilterParameters, CloseCashDrawerSessionRequest,
+    OpenCashDrawerSessionRequest,
+};
+use api::extractors::*;
+use api::models::PathParameters;
+use db::prelude::*;
+
+#[actix_rt::test]
+async fn open_movement_and_close() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let box_office_user = database.create_user().finish();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let auth_user =
+        support::create_auth_user_from_user(&box_office_user, Roles::OrgBoxOffice, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = organization.id;
+    let response: HttpResponse = cash_drawer_sessions::create((
+        database.connection.clone().into(),
+        path,
+        Json(OpenCashDrawerSessionRequest {
+            opening_float_in_cents: 10000,
+        }),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let session: DisplayCashDrawerSession =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(session.user_id, box_office_user.id);
+    assert_eq!(session.status, CashDrawerSessionStatus::Open);
+    assert_eq!(session.expected_cash_in_cents, 10000);
+
+    let order = database
+        .create_order()
+        .for_event(&event)
+        .for_user(&box_office_user)
+        .on_behalf_of_user(&user)
+        .is_paid()
+        .with_external_payment_type(ExternalPaymentType::Cash)
+        .finish();
+    let cash_sales_in_cents = order.payments(connection).unwrap()[0].amount;
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = session.id;
+    let response: HttpResponse = cash_drawer_sessions::create_movement((
+        database.connection.clone().into(),
+        path,
+        Json(CashDrawerMovementRequest {
+            movement_type: CashDrawerMovementTypes::Drop,
+            amount_in_cents: 5000,
+            reason: None,
+        }),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = session.id;
+    let response: HttpResponse =
+        cash_drawer_sessions::show((database.connection.clone().into(), path, auth_user.clone()))
+            .await
+            .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let session: DisplayCashDrawerSession =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(session.cash_sales_in_cents, cash_sales_in_cents);
+    assert_eq!(session.drops_in_cents, 5000);
+    assert_eq!(session.expected_cash_in_cents, 5000 + cash_sales_in_cents);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = session.id;
+    let response: HttpResponse = cash_drawer_sessions::close((
+        database.connection.clone().into(),
+        path,
+        Json(CloseCashDrawerSessionRequest {
+            counted_cash_in_cents: 5100 + cash_sales_in_cents,
+            closing_notes: None,
+        }),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let session: DisplayCashDrawerSession =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(session.status, CashDrawerSessionStatus::Closed);
+    assert_eq!(session.counted_cash_in_cents, Some(5100 + cash_sales_in_cents));
+    assert_eq!(session.variance_in_cents, Some(100));
+}
+
+#[actix_rt::test]
+async fn drawer_access() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let box_office_user = database.create_user().finish();
+    let box_office_user2 = database.create_user().finish();
+    let admin = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let session = CashDrawerSession::create(organization.id, box_office_user.id, 10000)
+        .commit(connection)
+        .unwrap();
+    let session2 = CashDrawerSession::create(organization.id, box_office_user2.id, 10000)
+        .commit(connection)
+        .unwrap();
+    let auth_user =
+        support::create_auth_user_from_user(&box_office_user, Roles::OrgBoxOffice, Some(&organization), &database);
+    let auth_user2 =
+        support::create_auth_user_from_user(&box_office_user2, Roles::OrgBoxOffice, Some(&organization), &database);
+    let auth_admin = support::create_auth_user_from_user(&admin, Roles::OrgAdmin, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    // Staff can't see each other's drawers
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = session.id;
+    let response: HttpResponse = cash_drawer_sessions::show((database.connection.clone().into(), path, auth_user2))
+        .await
+        .into();
+    support::expects_unauthorized(&response);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = session.id;
+    let response: HttpResponse =
+        cash_drawer_sessions::show((database.connection.clone().into(), path, auth_admin.clone()))
+            .await
+            .into();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    for (auth_user, expected_sessions) in vec![
+        (auth_user, vec![session.clone()]),
+        (auth_admin, vec![session, session2]),
+    ] {
+        let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+        path.id = organization.id;
+        let response = cash_drawer_sessions::index((
+            database.connection.clone().into(),
+            path,
+            Query::<PagingParameters>::extract(&test_request.request).await.unwrap(),
+            Query::<CashDrawerSessionFilterParameters>::extract(&test_request.request)
+                .await
+                .unwrap(),
+            auth_user,
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.payload().paging.total as usize, expected_sessions.len());
+        for expected_session in expected_sessions {
+            assert!(response.payload().data.contains(&expected_session));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::support;
+use crate::support::database::TestDatabase;
+use crate::support::test_request::TestRequest;
+use actix_web::{http::StatusCode, web::Path, FromRequest, HttpResponse};
+use api::controllers::event_series::{self, CreateEventSeriesRequest, UpdateEventSeriesEventsRequest};
+use api::extractors::*;
+use api::models::PathParameters;
+use db::prelude::*;
+
+#[actix_rt::test]
+async fn create_update_and_destroy() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgAdmin, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = organization.id;
+    let response: HttpResponse = event_series::create((
+        database.connection.clone().into(),
+        path,
+        Json(CreateEventSeriesRequest {
+            template_event_id: event.id,
+            name: None,
+            recurrence_rule: "FREQ=WEEKLY;COUNT=3".to_string(),
+        }),
+        auth_user.clone(),
+        test_request.extract_state().await,
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let display_event_series: DisplayEventSeries =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(display_event_series.name, event.name);
+    assert_eq!(display_event_series.event_ids.len(), 3);
+    assert_eq!(display_event_series.event_ids[0], event.id);
+
+    // Generated events have the template's ticket types
+    let generated_event = Event::find(display_event_series.event_ids[1], connection).unwrap();
+    assert_eq!(generated_event.ticket_types(true, None, connection).unwrap().len(), 1);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = display_event_series.id;
+    let response: HttpResponse = event_series::update((
+        database.connection.clone().into(),
+        path,
+        Json(EventSeriesEditableAttributes {
+            name: Some("Weekly".to_string()),
+            recurrence_rule: Some("FREQ=WEEKLY;COUNT=4".to_string()),
+        }),
+        auth_user.clone(),
+        test_request.extract_state().await,
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let display_event_series: DisplayEventSeries =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(display_event_series.name, "Weekly".to_string());
+    assert_eq!(display_event_series.event_ids.len(), 4);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = display_event_series.id;
+    let response: HttpResponse = event_series::destroy((database.connection.clone().into(), path, auth_user.clone()))
+        .await
+        .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(EventSeries::find_for_organization(organization.id, connection)
+        .unwrap()
+        .is_empty());
+}
+
+#[actix_rt::test]
+async fn create_requires_event_clone() {
+    let database = TestDatabase::new();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database.create_event().with_organization(&organization).finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgBoxOffice, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = organization.id;
+    let response: HttpResponse = event_series::create((
+        database.connection.clone().into(),
+        path,
+        Json(CreateEventSeriesRequest {
+            template_event_id: event.id,
+            name: None,
+            recurrence_rule: "FREQ=WEEKLY;COUNT=3".to_string(),
+        }),
+        auth_user,
+        test_request.extract_state().await,
+    ))
+    .await
+    .into();
+    support::expects_unauthorized(&response);
+}
+
+#[actix_rt::test]
+async fn update_events() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let event_series = EventSeries::create(
+        organization.id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=DAILY;COUNT=3".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+    let events = event_series.generate_occurrences(None, connection).unwrap();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgAdmin, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = event_series.id;
+    let response: HttpResponse = event_series::update_events((
+        database.connection.clone().into(),
+        path,
+        Json(UpdateEventSeriesEventsRequest {
+            event_id: events[0].id,
+            scope: EventSeriesEditScopes::AllEvents,
+            attributes: EventSeriesEventAttributes {
+                age_limit: Some("21".to_string()),
+                ..Default::default()
+            },
+        }),
+        auth_user,
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    for event in event_series.events(connection).unwrap() {
+        assert_eq!(event.age_limit, Some("21".to_string()));
+    }
+}
+
+#[actix_rt::test]
+async fn dashboard() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let event_series = EventSeries::create(
+        organization.id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=WEEKLY;COUNT=2".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+    event_series.generate_occurrences(None, connection).unwrap();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgMember, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = event_series.id;
+    let response: HttpResponse = event_series::dashboard((database.connection.clone().into(), path, auth_user))
+        .await
+        .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let dashboard: EventSeriesDashboard =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(dashboard.event_series.id, event_series.id);
+    assert_eq!(dashboard.events.len(), 2);
+}
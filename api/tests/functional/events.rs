@@ -9,12 +9,14 @@ use actix_web::{
 };
 use api::controllers::events;
 use api::controllers::events::*;
+use api::database::CacheDatabase;
 use api::extractors::*;
 use api::models::*;
 use chrono::prelude::*;
 use chrono::Duration;
 use db::models::*;
 use db::utils::dates;
+use db::utils::totp;
 use diesel::PgConnection;
 use serde_json;
 use serde_json::Value;
@@ -2179,6 +2181,100 @@ mod redeem_ticket {
     }
 }
 
+#[actix_rt::test]
+pub async fn redeem_ticket_with_rotating_barcode() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let event = event
+        .update(
+            None,
+            EventEditableAttributes {
+                barcode_rotation_seconds: Some(Some(30)),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    let user2 = database.create_user().finish();
+    let ticket_type = event.ticket_types(true, None, connection).unwrap()[0].id;
+    let ticket = database.create_purchased_tickets(&user2, ticket_type, 1).remove(0);
+    let auth_user = support::create_auth_user_from_user(&user, Roles::DoorPerson, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+    let redeem_secret = ticket.redeem_secret.clone().unwrap();
+
+    // The static redeem key alone is rejected, as is a code from several periods ago
+    let stale_code = totp::code(&redeem_secret, Utc::now().timestamp() - 120, 30);
+    for redeem_code in vec![None, Some(stale_code)] {
+        let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+        path.id = event.id;
+        let response: HttpResponse = events::redeem_ticket((
+            database.connection.clone().into(),
+            path,
+            Json(TicketRedeemRequest {
+                redeem_key: ticket.redeem_key.clone().unwrap(),
+                redeem_code,
+                check_in_source: Some(CheckInSource::Scanned),
+                device_id: None,
+            }),
+            auth_user.clone(),
+            test_request.extract_state().await,
+            CacheDatabase { inner: None },
+        ))
+        .await
+        .into();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+    assert_eq!(
+        TicketInstance::find(ticket.id, connection).unwrap().status,
+        TicketInstanceStatus::Purchased
+    );
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = event.id;
+    let response: HttpResponse = events::redeem_ticket((
+        database.connection.clone().into(),
+        path,
+        Json(TicketRedeemRequest {
+            redeem_key: ticket.redeem_key.clone().unwrap(),
+            redeem_code: Some(totp::code(&redeem_secret, Utc::now().timestamp(), 30)),
+            check_in_source: Some(CheckInSource::Scanned),
+            device_id: None,
+        }),
+        auth_user,
+        test_request.extract_state().await,
+        CacheDatabase { inner: None },
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        TicketInstance::find(ticket.id, connection).unwrap().status,
+        TicketInstanceStatus::Redeemed
+    );
+
+    // Every attempt is recorded in the scan log
+    let rejection_reasons: Vec<Option<ScanRejectionReasons>> = TicketScan::find_for_ticket(ticket.id, connection)
+        .unwrap()
+        .into_iter()
+        .map(|s| s.rejection_reason)
+        .collect();
+    assert_eq!(
+        rejection_reasons,
+        vec![
+            Some(ScanRejectionReasons::ExpiredCode),
+            Some(ScanRejectionReasons::ExpiredCode),
+            None
+        ]
+    );
+}
+
 #[actix_rt::test]
 pub async fn delete_fails_has_ticket_in_cart() {
     let database = TestDatabase::new();
@@ -2326,6 +2422,8 @@ async fn dashboard_with_default_range() {
             ticket_sales: 10,
         }
     );
+    assert_eq!(dashboard_result.capacity.capacity, None);
+    assert_eq!(dashboard_result.capacity.used, 10);
 }
 
 #[actix_rt::test]
@@ -0,0 +1,108 @@
+use crate::support;
+use crate::support::database::TestDatabase;
+use crate::support::test_request::TestRequest;
+use actix_web::{http::StatusCode, web::Path, FromRequest, HttpResponse};
+use api::controllers::inventory_pools::{self, CreateInventoryPoolRequest, UpdateInventoryPoolRequest};
+use api::extractors::*;
+use api::models::PathParameters;
+use db::prelude::*;
+
+#[actix_rt::test]
+async fn create_update_and_destroy() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_type_count(2)
+        .with_ticket_pricing()
+        .finish();
+    let ticket_types = event.ticket_types(true, None, connection).unwrap();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgAdmin, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = event.id;
+    let response: HttpResponse = inventory_pools::create((
+        database.connection.clone().into(),
+        path,
+        Json(CreateInventoryPoolRequest {
+            name: "Main floor".to_string(),
+            ticket_type_ids: vec![ticket_types[0].id, ticket_types[1].id],
+        }),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let inventory_pool: DisplayInventoryPool =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(inventory_pool.event_id, event.id);
+    assert_eq!(
+        inventory_pool.ticket_type_ids,
+        vec![ticket_types[0].id, ticket_types[1].id]
+    );
+    assert_eq!(inventory_pool.capacity, 200);
+    assert_eq!(inventory_pool.available, 200);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = inventory_pool.id;
+    let response: HttpResponse = inventory_pools::update((
+        database.connection.clone().into(),
+        path,
+        Json(UpdateInventoryPoolRequest {
+            name: Some("Floor".to_string()),
+            ticket_type_ids: Some(vec![ticket_types[0].id]),
+        }),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let inventory_pool: DisplayInventoryPool =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(inventory_pool.name, "Floor".to_string());
+    assert_eq!(inventory_pool.ticket_type_ids, vec![ticket_types[0].id]);
+    assert_eq!(inventory_pool.capacity, 100);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = inventory_pool.id;
+    let response: HttpResponse = inventory_pools::destroy((database.connection.clone().into(), path, auth_user))
+        .await
+        .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(InventoryPool::find_for_event(event.id, connection).unwrap().is_empty());
+    assert_eq!(
+        TicketType::find(ticket_types[0].id, connection)
+            .unwrap()
+            .inventory_pool_id,
+        None
+    );
+}
+
+#[actix_rt::test]
+async fn create_requires_ticket_type_write() {
+    let database = TestDatabase::new();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database.create_event().with_organization(&organization).finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgBoxOffice, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = event.id;
+    let response: HttpResponse = inventory_pools::create((
+        database.connection.clone().into(),
+        path,
+        Json(CreateInventoryPoolRequest {
+            name: "Main floor".to_string(),
+            ticket_type_ids: vec![],
+        }),
+        auth_user,
+    ))
+    .await
+    .into();
+    support::expects_unauthorized(&response);
+}
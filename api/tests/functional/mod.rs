@@ -1,23 +1,30 @@
+mod access_zones;
 mod admin;
+mod affiliate_links;
 mod announcements;
 mod artists;
 mod auth;
 mod base;
 mod broadcast;
 mod cart;
+mod cash_drawer_sessions;
 mod codes;
 mod collection_items;
 mod collections;
 mod comps;
 mod event_report_subscribers;
+mod event_series;
 mod events;
 mod genres;
 mod holds;
+mod inventory_pools;
 mod notes;
 mod orders;
+mod organization_brandings;
 mod organization_invites;
 mod organization_venues;
 mod organizations;
+mod partners;
 mod password_resets;
 mod payment_methods;
 mod redemption_codes;
@@ -31,6 +38,7 @@ mod slugs;
 mod stages;
 mod ticket_types;
 mod tickets;
+mod trades;
 mod transfers;
 mod user_invites;
 mod users;
@@ -0,0 +1,67 @@
+use crate::support;
+use crate::support::database::TestDatabase;
+use crate::support::test_request::TestRequest;
+use actix_web::{http::StatusCode, web::Path, FromRequest, HttpResponse};
+use api::controllers::organization_brandings;
+use api::extractors::*;
+use api::models::PathParameters;
+use db::prelude::*;
+
+#[actix_rt::test]
+async fn update_and_show() {
+    let database = TestDatabase::new();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgOwner, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = organization.id;
+    let mut new_branding = OrganizationBranding::create(organization.id);
+    new_branding.custom_domain = Some("tickets.example.org".to_string());
+    new_branding.support_email = Some("help@example.org".to_string());
+    let response: HttpResponse = organization_brandings::update((
+        database.connection.clone().into(),
+        path,
+        Json(new_branding),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let branding: OrganizationBranding =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(branding.organization_id, organization.id);
+    assert_eq!(branding.custom_domain, Some("tickets.example.org".to_string()));
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = organization.id;
+    let response: HttpResponse = organization_brandings::show((database.connection.clone().into(), path, auth_user))
+        .await
+        .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let shown_branding: OrganizationBranding =
+        serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(shown_branding, branding);
+}
+
+#[actix_rt::test]
+async fn update_requires_org_write() {
+    let database = TestDatabase::new();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::OrgBoxOffice, Some(&organization), &database);
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = organization.id;
+    let response: HttpResponse = organization_brandings::update((
+        database.connection.clone().into(),
+        path,
+        Json(OrganizationBranding::create(organization.id)),
+        auth_user,
+    ))
+    .await
+    .into();
+    support::expects_unauthorized(&response);
+}
@@ -0,0 +1,275 @@
+use crate::support;
+use crate::support::database::TestDatabase;
+use crate::support::test_request::TestRequest;
+use actix_web::{http::StatusCode, web::Path, FromRequest, HttpResponse};
+use api::controllers::cart::{CartItem, UpdateCartRequest};
+use api::controllers::{partner_cart, partner_checkout_sessions, partners};
+use api::extractors::*;
+use api::models::{PathParameters, RegisterEmailOnlyRequest, RequestInfo};
+use db::prelude::*;
+use serde_json::Value;
+
+#[actix_rt::test]
+async fn create_update_and_destroy() {
+    let database = TestDatabase::new();
+    let auth_user = support::create_auth_user(Roles::Admin, None, &database);
+    let new_partner = Partner::create(
+        "Example Tickets".to_string(),
+        vec!["https://tickets.example.com".to_string()],
+        10f32,
+    );
+    let response: HttpResponse =
+        partners::create((database.connection.clone().into(), Json(new_partner), auth_user.clone()))
+            .await
+            .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body: Value = serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    let partner: Partner = serde_json::from_value(body["partner"].clone()).unwrap();
+    let api_key = body["api_key"].as_str().unwrap();
+    assert_eq!(partner.name, "Example Tickets".to_string());
+    assert_eq!(
+        Partner::find_by_api_key(api_key, database.connection.get()).unwrap().id,
+        partner.id
+    );
+
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["id"]);
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = partner.id;
+    let attributes = PartnerEditableAttributes {
+        commission_rate_percent: Some(12.5f32),
+        ..Default::default()
+    };
+    let response: HttpResponse = partners::update((
+        database.connection.clone().into(),
+        path,
+        Json(attributes),
+        auth_user.clone(),
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let updated_partner: Partner = serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(updated_partner.commission_rate_percent, 12.5f32);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = partner.id;
+    let response: HttpResponse = partners::destroy((database.connection.clone().into(), path, auth_user))
+        .await
+        .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(Partner::find(partner.id, database.connection.get()).is_err());
+}
+
+#[actix_rt::test]
+async fn create_requires_admin() {
+    let database = TestDatabase::new();
+    let organization = database.create_organization().finish();
+    let auth_user = support::create_auth_user(Roles::OrgOwner, Some(&organization), &database);
+    let new_partner = Partner::create("Example Tickets".to_string(), vec![], 0f32);
+    let response: HttpResponse = partners::create((database.connection.clone().into(), Json(new_partner), auth_user))
+        .await
+        .into();
+    support::expects_unauthorized(&response);
+}
+
+#[actix_rt::test]
+async fn create_checkout_session() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let partner = Partner::create(
+        "Example Tickets".to_string(),
+        vec!["https://tickets.example.com".to_string()],
+        10f32,
+    )
+    .commit(None, connection)
+    .unwrap();
+    let test_request = TestRequest::create();
+    let state = test_request.extract_state().await;
+    let parameters = RegisterEmailOnlyRequest {
+        email: "guest@example.com".to_string(),
+        first_name: Some("Guest".to_string()),
+        last_name: None,
+    };
+    let authorized_partner = AuthorizedPartner {
+        partner: partner.clone(),
+        origin: Some("https://tickets.example.com".to_string()),
+    };
+
+    let response: HttpResponse = partner_checkout_sessions::create((
+        state,
+        database.connection.clone().into(),
+        Json(parameters),
+        authorized_partner,
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body: Value = serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert!(body["access_token"].as_str().is_some());
+
+    let user = User::find_by_email("guest@example.com", false, connection).unwrap();
+    let cart = Order::find_cart_for_user(user.id, connection).unwrap().unwrap();
+    assert_eq!(body["cart"]["id"], json!(cart.id));
+    assert_eq!(cart.partner_id, Some(partner.id));
+    assert_eq!(cart.platform, Some(Platforms::Partner.to_string()));
+    assert_eq!(cart.source, Some("Example Tickets".to_string()));
+}
+
+#[actix_rt::test]
+async fn create_checkout_session_for_existing_user() {
+    let database = TestDatabase::new();
+    let user = database.create_user().finish();
+    let partner = Partner::create("Example Tickets".to_string(), vec![], 0f32)
+        .commit(None, database.connection.get())
+        .unwrap();
+    let test_request = TestRequest::create();
+    let state = test_request.extract_state().await;
+    let parameters = RegisterEmailOnlyRequest {
+        email: user.email.clone().unwrap(),
+        first_name: None,
+        last_name: None,
+    };
+
+    let response: HttpResponse = partner_checkout_sessions::create((
+        state,
+        database.connection.clone().into(),
+        Json(parameters),
+        AuthorizedPartner { partner, origin: None },
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(Order::find_cart_for_user(user.id, database.connection.get())
+        .unwrap()
+        .is_none());
+}
+
+#[actix_rt::test]
+async fn create_checkout_session_for_returning_guest() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let partner = Partner::create("Example Tickets".to_string(), vec![], 0f32)
+        .commit(None, connection)
+        .unwrap();
+    let test_request = TestRequest::create();
+    let parameters = || RegisterEmailOnlyRequest {
+        email: "guest@example.com".to_string(),
+        first_name: None,
+        last_name: None,
+    };
+
+    let response: HttpResponse = partner_checkout_sessions::create((
+        test_request.extract_state().await,
+        database.connection.clone().into(),
+        Json(parameters()),
+        AuthorizedPartner {
+            partner: partner.clone(),
+            origin: None,
+        },
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let user = User::find_by_email("guest@example.com", false, connection).unwrap();
+    assert_eq!(
+        partner.find_guest_by_email("Guest@example.com", connection).unwrap(),
+        Some(user.clone())
+    );
+
+    // Returning guests are given a new session for their existing account
+    let response: HttpResponse = partner_checkout_sessions::create((
+        test_request.extract_state().await,
+        database.connection.clone().into(),
+        Json(parameters()),
+        AuthorizedPartner {
+            partner: partner.clone(),
+            origin: None,
+        },
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body: Value = serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    let cart = Order::find_cart_for_user(user.id, connection).unwrap().unwrap();
+    assert_eq!(body["cart"]["id"], json!(cart.id));
+
+    // Other partners cannot obtain a token for the guest
+    let partner2 = Partner::create("Other Tickets".to_string(), vec![], 0f32)
+        .commit(None, connection)
+        .unwrap();
+    let response: HttpResponse = partner_checkout_sessions::create((
+        test_request.extract_state().await,
+        database.connection.clone().into(),
+        Json(parameters()),
+        AuthorizedPartner {
+            partner: partner2,
+            origin: None,
+        },
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[actix_rt::test]
+async fn partner_cart_requires_cart_partner() {
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::User, None, &database);
+    let event = database.create_event().with_tickets().with_ticket_pricing().finish();
+    let ticket_type_id = event.ticket_types(true, None, connection).unwrap()[0].id;
+    let partner = Partner::create("Example Tickets".to_string(), vec![], 0f32)
+        .commit(None, connection)
+        .unwrap();
+    let partner2 = Partner::create("Other Tickets".to_string(), vec![], 0f32)
+        .commit(None, connection)
+        .unwrap();
+
+    // Carts started outside of a checkout session are attributed to the partner
+    let response: HttpResponse = partner_cart::update_cart((
+        database.connection.clone().into(),
+        Json(UpdateCartRequest {
+            box_office_pricing: None,
+            items: vec![CartItem {
+                ticket_type_id,
+                quantity: 1,
+                redemption_code: None,
+            }],
+            tracking_data: None,
+        }),
+        auth_user.clone(),
+        RequestInfo { user_agent: None },
+        AuthorizedPartner {
+            partner: partner.clone(),
+            origin: None,
+        },
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let cart = Order::find_cart_for_user(user.id, connection).unwrap().unwrap();
+    assert_eq!(cart.partner_id, Some(partner.id));
+
+    let response: HttpResponse = partner_cart::show((
+        database.connection.clone().into(),
+        auth_user.clone(),
+        AuthorizedPartner { partner, origin: None },
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Carts of another partner cannot be used
+    let response: HttpResponse = partner_cart::show((
+        database.connection.clone().into(),
+        auth_user,
+        AuthorizedPartner {
+            partner: partner2,
+            origin: None,
+        },
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
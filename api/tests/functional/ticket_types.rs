@@ -9,7 +9,10 @@ use api::extractors::*;
 use api::models::{EventTicketPathParameters, PathParameters};
 use chrono::prelude::*;
 use db::models::*;
+use db::schema::ticket_instances;
+use diesel::prelude::*;
 use serde_json;
+use std::collections::HashSet;
 use uuid::Uuid;
 
 #[cfg(test)]
@@ -893,3 +896,62 @@ pub async fn cancel_with_no_sold_tickets_or_hold() {
     assert_eq!(updated_ticket_types.len(), 0);
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+#[actix_rt::test]
+pub async fn cancel_nullifies_unsold_tokens_on_blockchain() {
+    let database = TestDatabase::new();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::Admin, Some(&organization), &database);
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    database
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(2)
+        .is_paid()
+        .finish();
+
+    let conn = database.connection.get();
+    let created_ticket_type = &event.ticket_types(true, None, conn).unwrap()[0];
+    let sold_tickets = TicketInstance::find_for_user(user.id, conn).unwrap();
+    let tari_simulator = support::tari_simulator_for_tickets(&sold_tickets, conn);
+    let state = TestRequest::create_with_tari_client(Box::new(tari_simulator.clone()))
+        .extract_state()
+        .await;
+
+    let test_request = TestRequest::create_with_uri_custom_params("/", vec!["event_id", "ticket_type_id"]);
+    let mut path = Path::<EventTicketPathParameters>::extract(&test_request.request)
+        .await
+        .unwrap();
+    path.event_id = event.id;
+    path.ticket_type_id = created_ticket_type.id;
+
+    let response: HttpResponse = ticket_types::cancel((database.connection.clone().into(), path, auth_user, state))
+        .await
+        .into();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Every unsold token is nullified on the blockchain, sold tokens remain valid
+    let asset = Asset::find_by_ticket_type(created_ticket_type.id, conn).unwrap();
+    let nullified_token_ids: HashSet<u64> = ticket_instances::table
+        .filter(ticket_instances::asset_id.eq(asset.id))
+        .filter(ticket_instances::status.eq(TicketInstanceStatus::Nullified))
+        .select(ticket_instances::token_id)
+        .load::<i32>(conn)
+        .unwrap()
+        .into_iter()
+        .map(|token_id| token_id as u64)
+        .collect();
+    assert_eq!(nullified_token_ids.len(), 98);
+    let simulated_asset = tari_simulator.asset(&asset.blockchain_asset_id.unwrap()).unwrap();
+    assert_eq!(simulated_asset.nullified_tokens, nullified_token_ids);
+    for ticket in &sold_tickets {
+        assert!(!simulated_asset.nullified_tokens.contains(&(ticket.token_id as u64)));
+    }
+}
@@ -132,6 +132,7 @@ pub async fn index() {
         ticket_type_name: ticket_type.name.clone(),
         status: TicketInstanceStatus::Purchased,
         redeem_key: ticket.redeem_key,
+        redeem_secret: ticket.redeem_secret,
         pending_transfer: false,
         first_name_override: None,
         last_name_override: None,
@@ -163,6 +164,7 @@ pub async fn index() {
         ticket_type_name: ticket_type2.name.clone(),
         status: TicketInstanceStatus::Purchased,
         redeem_key: ticket2.redeem_key,
+        redeem_secret: ticket2.redeem_secret,
         pending_transfer: false,
         first_name_override: None,
         last_name_override: None,
@@ -286,6 +288,7 @@ pub async fn show() {
         ticket_type_name: ticket_type.name.clone(),
         status: TicketInstanceStatus::Purchased,
         redeem_key: ticket.redeem_key,
+        redeem_secret: ticket.redeem_secret,
         pending_transfer: false,
         first_name_override: None,
         last_name_override: None,
@@ -625,7 +628,6 @@ async fn send_to_new_user() {
 #[actix_rt::test]
 async fn receive_ticket_transfer() {
     let database = TestDatabase::new();
-    let request = TestRequest::create();
     let user = database.create_user().finish();
     let auth_user = support::create_auth_user_from_user(&user, Roles::User, None, &database);
     let organization = database.create_organization().finish();
@@ -667,6 +669,8 @@ async fn receive_ticket_transfer() {
     )
     .unwrap();
     let tickets = TicketInstance::find_for_user(user.id, conn).unwrap();
+    let tari_simulator = support::tari_simulator_for_tickets(&tickets, conn);
+    let request = TestRequest::create_with_tari_client(Box::new(tari_simulator.clone()));
 
     let transfer_auth: TransferAuthorization = TicketInstance::create_transfer(
         &auth_user.user,
@@ -694,6 +698,26 @@ async fn receive_ticket_transfer() {
     .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
+
+    // Transferred tokens now belong to the receiver on chain
+    let blockchain_asset_id = Asset::find(tickets[0].asset_id, conn)
+        .unwrap()
+        .blockchain_asset_id
+        .unwrap();
+    let sender_wallet = Wallet::find_default_for_user(user.id, conn).unwrap();
+    let receiver_wallet = Wallet::find_default_for_user(user2.id, conn).unwrap();
+    let mut received_token_ids = vec![tickets[0].token_id as u64, tickets[1].token_id as u64];
+    received_token_ids.sort();
+    assert_eq!(
+        tari_simulator.tokens_owned_by(&blockchain_asset_id, &receiver_wallet.public_key),
+        received_token_ids
+    );
+    assert_eq!(
+        tari_simulator
+            .tokens_owned_by(&blockchain_asset_id, &sender_wallet.public_key)
+            .len(),
+        3
+    );
 }
 
 #[actix_rt::test]
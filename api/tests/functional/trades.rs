@@ -0,0 +1,160 @@
+use crate::support;
+use crate::support::database::TestDatabase;
+use crate::support::test_request::TestRequest;
+use actix_web::{http::StatusCode, web::Path, FromRequest, HttpResponse};
+use api::controllers::trades::{self, ProposeTradeRequest};
+use api::extractors::*;
+use api::models::PathParameters;
+use db::prelude::*;
+
+#[actix_rt::test]
+async fn propose_and_accept() {
+    let database = TestDatabase::new();
+    let conn = database.connection.get();
+    let user = database.create_user().finish();
+    let user2 = database.create_user().finish();
+    let auth_user = support::create_auth_user_from_user(&user, Roles::User, None, &database);
+    let auth_user2 = support::create_auth_user_from_user(&user2, Roles::User, None, &database);
+    let event = database.create_event().with_ticket_pricing().finish();
+    let event2 = database.create_event().with_ticket_pricing().finish();
+    database
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    database
+        .create_order()
+        .for_event(&event2)
+        .for_user(&user2)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    let offered_ticket = TicketInstance::find_for_user(user.id, conn).unwrap().remove(0);
+    let requested_ticket = TicketInstance::find_for_user(user2.id, conn).unwrap().remove(0);
+    let mut tickets = vec![offered_ticket.clone()];
+    tickets.push(requested_ticket.clone());
+    let tari_simulator = support::tari_simulator_for_tickets(&tickets, conn);
+    let test_request = TestRequest::create_with_tari_client(Box::new(tari_simulator.clone()));
+
+    let response: HttpResponse = trades::create((
+        database.connection.clone().into(),
+        Json(ProposeTradeRequest {
+            recipient_user_id: user2.id,
+            offered_ticket_ids: vec![offered_ticket.id],
+            requested_ticket_ids: vec![requested_ticket.id],
+            cash_in_cents: 0,
+            message: Some("Swap?".to_string()),
+        }),
+        auth_user.clone(),
+        test_request.extract_state().await,
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let trade: DisplayTrade = serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(trade.status, TradeStatus::Pending);
+    assert_eq!(
+        TicketInstance::find(offered_ticket.id, conn).unwrap().trade_id,
+        Some(trade.id)
+    );
+
+    // Only the recipient can accept
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = trade.id;
+    let response: HttpResponse = trades::accept((
+        database.connection.clone().into(),
+        path,
+        auth_user.clone(),
+        test_request.extract_state().await,
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = trade.id;
+    let response: HttpResponse = trades::accept((
+        database.connection.clone().into(),
+        path,
+        auth_user2.clone(),
+        test_request.extract_state().await,
+    ))
+    .await
+    .into();
+    assert_eq!(response.status(), StatusCode::OK);
+    let trade: DisplayTrade = serde_json::from_str(&support::unwrap_body_to_string(&response).unwrap()).unwrap();
+    assert_eq!(trade.status, TradeStatus::Accepted);
+
+    let wallet = Wallet::find_default_for_user(user.id, conn).unwrap();
+    let wallet2 = Wallet::find_default_for_user(user2.id, conn).unwrap();
+    let offered_ticket = TicketInstance::find(offered_ticket.id, conn).unwrap();
+    let requested_ticket = TicketInstance::find(requested_ticket.id, conn).unwrap();
+    assert_eq!(offered_ticket.wallet_id, wallet2.id);
+    assert_eq!(offered_ticket.trade_id, None);
+    assert_eq!(requested_ticket.wallet_id, wallet.id);
+
+    // Both tokens changed hands on chain
+    let offered_asset_id = Asset::find(offered_ticket.asset_id, conn)
+        .unwrap()
+        .blockchain_asset_id
+        .unwrap();
+    let requested_asset_id = Asset::find(requested_ticket.asset_id, conn)
+        .unwrap()
+        .blockchain_asset_id
+        .unwrap();
+    assert_eq!(
+        tari_simulator.owner_of(&offered_asset_id, offered_ticket.token_id as u64),
+        Some(wallet2.public_key.clone())
+    );
+    assert_eq!(
+        tari_simulator.owner_of(&requested_asset_id, requested_ticket.token_id as u64),
+        Some(wallet.public_key.clone())
+    );
+}
+
+#[actix_rt::test]
+async fn show_requires_trading_user() {
+    let database = TestDatabase::new();
+    let conn = database.connection.get();
+    let user = database.create_user().finish();
+    let user2 = database.create_user().finish();
+    let user3 = database.create_user().finish();
+    let auth_user3 = support::create_auth_user_from_user(&user3, Roles::User, None, &database);
+    let event = database.create_event().with_ticket_pricing().finish();
+    database
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    database
+        .create_order()
+        .for_event(&event)
+        .for_user(&user2)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    let offered_ticket = TicketInstance::find_for_user(user.id, conn).unwrap().remove(0);
+    let requested_ticket = TicketInstance::find_for_user(user2.id, conn).unwrap().remove(0);
+    let trade = Trade::propose(
+        user.id,
+        user2.id,
+        &[offered_ticket.id],
+        &[requested_ticket.id],
+        0,
+        None,
+        conn,
+    )
+    .unwrap();
+
+    let test_request = TestRequest::create();
+    let mut path = Path::<PathParameters>::extract(&test_request.request).await.unwrap();
+    path.id = trade.id;
+    let response: HttpResponse = trades::show((database.connection.clone().into(), path, auth_user3))
+        .await
+        .into();
+    support::expects_unauthorized(&response);
+}
@@ -566,6 +566,7 @@ async fn current_user_organization_owner() {
             "event:scan",
             "event:view-guests",
             "event:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -668,6 +669,7 @@ async fn current_user_organization_member() {
             "event:scan",
             "event:view-guests",
             "event:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -724,6 +726,29 @@ pub async fn update_current_user() {
     assert_eq!(updated_user.user.email, Some(email.into()));
 }
 
+#[actix_rt::test]
+pub async fn update_current_user_locale() {
+    let database = TestDatabase::new();
+    let user = support::create_auth_user(Roles::User, None, &database);
+    let mut attributes: UserProfileAttributes = Default::default();
+    attributes.locale = Some(Some(Locales::Fr));
+    let json = Json(attributes);
+
+    let updated_user = users::update_current_user((database.connection.clone().into(), json, user.clone()))
+        .await
+        .unwrap();
+    assert_eq!(updated_user.user.locale, Some(Locales::Fr));
+
+    // Clearing the preference falls back to the browser language
+    let mut attributes: UserProfileAttributes = Default::default();
+    attributes.locale = Some(None);
+    let json = Json(attributes);
+    let updated_user = users::update_current_user((database.connection.into(), json, user))
+        .await
+        .unwrap();
+    assert_eq!(updated_user.user.locale, None);
+}
+
 #[actix_rt::test]
 pub async fn update_current_user_with_validation_errors() {
     let database = TestDatabase::new();
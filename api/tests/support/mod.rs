@@ -9,11 +9,14 @@ use actix_web::{
     HttpResponse,
 };
 use api::auth::user::User as AuthUser;
-use db::models::{Organization, Roles, User};
+use db::models::{Asset, Organization, Roles, TicketInstance, User, Wallet};
+use db::schema::ticket_instances;
+use diesel::prelude::*;
 use serde::Deserialize;
 use serde_json;
 use std::collections::HashMap;
 use std::str;
+use tari_client::{TariClient, TariSimulator};
 use uuid::Uuid;
 use validator::ValidationError;
 
@@ -101,3 +104,40 @@ pub fn expects_forbidden(response: &HttpResponse, message: Option<&str>) {
     let body = unwrap_body_to_string(&response).unwrap();
     assert_eq!(body, expected_text);
 }
+
+/// Tari simulator holding the assets of the given tickets with each token owned by the wallet its
+/// ticket is currently in
+pub fn tari_simulator_for_tickets(tickets: &[TicketInstance], connection: &PgConnection) -> TariSimulator {
+    let simulator = TariSimulator::new();
+    for ticket in tickets {
+        let asset = Asset::find(ticket.asset_id, connection).unwrap();
+        let blockchain_asset_id = asset.blockchain_asset_id.unwrap();
+        let organization = Organization::find_by_asset_id(asset.id, connection).unwrap();
+        let organization_wallet = Wallet::find_default_for_organization(organization.id, connection).unwrap();
+        if simulator.asset(&blockchain_asset_id).is_none() {
+            let total_supply: i64 = ticket_instances::table
+                .filter(ticket_instances::asset_id.eq(asset.id))
+                .count()
+                .get_result(connection)
+                .unwrap();
+            simulator.register_asset(
+                &blockchain_asset_id,
+                &organization_wallet.public_key,
+                total_supply as u64,
+            );
+        }
+        if ticket.wallet_id != organization_wallet.id {
+            let wallet = Wallet::find(ticket.wallet_id, connection).unwrap();
+            simulator
+                .transfer_tokens(
+                    &organization_wallet.secret_key,
+                    &organization_wallet.public_key,
+                    &blockchain_asset_id,
+                    vec![ticket.token_id as u64],
+                    wallet.public_key,
+                )
+                .unwrap();
+        }
+    }
+    simulator
+}
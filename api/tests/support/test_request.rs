@@ -12,6 +12,7 @@ use db::models::Environment;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use tari_client::TariClient;
 
 lazy_static::lazy_static! {
     static ref DB: Arc<Mutex<Option<Database>>> = Arc::new(Mutex::new(None));
@@ -33,6 +34,18 @@ impl TestRequest {
     }
 
     pub fn create_with_uri_custom_params(path: &str, params: Vec<&'static str>) -> TestRequest {
+        TestRequest::create_with_config(path, params, TestRequest::test_config())
+    }
+
+    /// Request whose app state talks to the given Tari client, e.g. a `TariSimulator` to assert on
+    /// token ownership
+    pub fn create_with_tari_client(tari_client: Box<dyn TariClient + Send + Sync>) -> TestRequest {
+        let mut config = TestRequest::test_config();
+        config.tari_client = tari_client;
+        TestRequest::create_with_config("/", vec!["id"], config)
+    }
+
+    fn test_config() -> Config {
         let mut config = Config::new(Environment::Test);
         config.token_issuer = Box::new(DefaultTokenIssuer::new("test_secret".into(), "bn-api-test".into()));
         config.api_keys_encryption_key = "test_encryption_key".to_string();
@@ -40,7 +53,10 @@ impl TestRequest {
         if config.spotify_auth_token.is_some() {
             spotify::SINGLETON.set_auth_token(&config.spotify_auth_token.clone().unwrap());
         }
+        config
+    }
 
+    fn create_with_config(path: &str, params: Vec<&'static str>, config: Config) -> TestRequest {
         let clients = Arc::new(Mutex::new(HashMap::new()));
         let dbs = get_dbs(&config);
         let test_request = test::TestRequest::get().data(
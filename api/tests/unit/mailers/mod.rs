@@ -1 +1,2 @@
+pub mod orders;
 pub mod user;
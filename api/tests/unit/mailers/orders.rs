@@ -0,0 +1,73 @@
+use crate::support::database::TestDatabase;
+use api::communications::mailers;
+use api::config::Config;
+use db::models::{CommAddress, Environment, OrganizationBranding};
+
+#[test]
+fn confirmation_email() {
+    let config = Config::new(Environment::Test);
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+    let user = database.create_user().finish();
+    let organization = database.create_organization().finish();
+    let event = database
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let order = database
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(1)
+        .is_paid()
+        .finish();
+
+    let display_order = order.for_display(None, user.id, connection).unwrap();
+    let email = mailers::orders::confirmation_email(
+        &user.first_name.clone().unwrap(),
+        user.email.clone().unwrap(),
+        display_order,
+        &config,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(
+        email.source,
+        Some(CommAddress::from(config.communication_default_source_email.clone()))
+    );
+    let template_data = &email.template_data.unwrap()[0];
+    assert_eq!(template_data["tickets_link"], format!("{}/hub", config.front_end_url));
+    assert_eq!(template_data.get("branding_logo_url"), None);
+
+    // Branded organizations send from their own address and link to their own storefront
+    let mut new_branding = OrganizationBranding::create(organization.id);
+    new_branding.custom_domain = Some("tickets.example.org".to_string());
+    new_branding.logo_url = Some("https://example.org/logo.png".to_string());
+    new_branding.sender_email = Some("tickets@example.org".to_string());
+    new_branding.sender_name = Some("Example Promotions".to_string());
+    new_branding.commit(None, connection).unwrap();
+
+    let display_order = order.for_display(None, user.id, connection).unwrap();
+    let email = mailers::orders::confirmation_email(
+        &user.first_name.clone().unwrap(),
+        user.email.clone().unwrap(),
+        display_order,
+        &config,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(
+        email.source,
+        Some(CommAddress::from("tickets@example.org".to_string()).with_name(Some("Example Promotions".to_string())))
+    );
+    let template_data = &email.template_data.unwrap()[0];
+    assert_eq!(
+        template_data["tickets_link"],
+        "https://tickets.example.org/hub".to_string()
+    );
+    assert_eq!(
+        template_data.get("branding_logo_url"),
+        Some(&"https://example.org/logo.png".to_string())
+    );
+}
@@ -2,7 +2,7 @@ use crate::support::database::TestDatabase;
 use api::communications::mailers;
 use api::config::Config;
 use db::models::concerns::users::password_resetable::PasswordResetable;
-use db::models::{CommAddress, Environment};
+use db::models::{CommAddress, Environment, Locales};
 
 #[test]
 fn password_reset_email() {
@@ -22,3 +22,25 @@ fn password_reset_email() {
         Some(CommAddress::from("noreply@bigneon.com".to_string()))
     );
 }
+
+#[test]
+fn password_reset_email_localized() {
+    let config = Config::new(Environment::Test);
+    let database = TestDatabase::new();
+    let connection = database.connection.get();
+
+    for (locale, title) in vec![
+        (Locales::Fr, "Big Neon : Demande de réinitialisation du mot de passe"),
+        (Locales::Es, "Big Neon: Solicitud para restablecer la contraseña"),
+    ] {
+        let user = database.create_user().with_locale(locale).finish();
+        let user = user.create_password_reset_token(connection).unwrap();
+
+        let password_reset_email = mailers::user::password_reset_email(&config, &user);
+        assert_eq!(password_reset_email.title, title);
+        assert_eq!(
+            password_reset_email.template_data.unwrap()[0].get("locale"),
+            Some(&locale.language_code().to_string())
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use actix_web::dev::Body;
+use actix_web::http::header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE};
+use actix_web::http::StatusCode;
+use actix_web::{test, HttpMessage};
+use api::errors::ApiError;
+use api::middleware::Localization;
+use db::models::Locales;
+use db::utils::errors::DatabaseError;
+use serde_json::Value;
+
+async fn localized_error(
+    accept_language: Option<&str>,
+    user_locale: Option<Locales>,
+    error: DatabaseError,
+) -> (StatusCode, Option<String>, Value) {
+    let mut test_request = test::TestRequest::default();
+    if let Some(accept_language) = accept_language {
+        test_request = test_request.header(ACCEPT_LANGUAGE, accept_language);
+    }
+    let request = test_request.to_srv_request();
+    if let Some(user_locale) = user_locale {
+        request.extensions_mut().insert(user_locale);
+    }
+    let error: ApiError = error.into();
+
+    let response = Localization::complete(request.error_response::<Body, _>(error)).unwrap();
+    let status = response.status();
+    let content_language = response
+        .headers()
+        .get(CONTENT_LANGUAGE)
+        .map(|h| h.to_str().unwrap().to_string());
+    let body = test::read_body(response).await;
+    (status, content_language, serde_json::from_slice(&body).unwrap())
+}
+
+#[actix_rt::test]
+async fn localizes_errors_from_accept_language() {
+    let (status, content_language, body) = localized_error(
+        Some("fr-CA,fr;q=0.9,en;q=0.8"),
+        None,
+        DatabaseError::no_results::<()>("Could not find event").unwrap_err(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(content_language, Some("fr".to_string()));
+    assert_eq!(body, json!({"error": "Aucun résultat"}));
+
+    let (status, content_language, body) = localized_error(
+        Some("es-MX"),
+        None,
+        DatabaseError::validation_error::<()>("email", "Email is invalid").unwrap_err(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(content_language, Some("es".to_string()));
+    assert_eq!(body["error"], json!("Error de validación"));
+    assert_eq!(
+        body["fields"]["email"][0]["message"],
+        json!("El correo electrónico no es válido")
+    );
+}
+
+#[actix_rt::test]
+async fn user_locale_takes_precedence() {
+    let (_, content_language, body) = localized_error(
+        Some("fr-CA"),
+        Some(Locales::Es),
+        DatabaseError::no_results::<()>("Could not find event").unwrap_err(),
+    )
+    .await;
+    assert_eq!(content_language, Some("es".to_string()));
+    assert_eq!(body, json!({"error": "Sin resultados"}));
+
+    // English responses are left untouched
+    let (_, content_language, body) = localized_error(
+        Some("fr-CA"),
+        Some(Locales::En),
+        DatabaseError::no_results::<()>("Could not find event").unwrap_err(),
+    )
+    .await;
+    assert_eq!(content_language, None);
+    assert_eq!(body, json!({"error": "No results"}));
+
+    let (_, content_language, body) = localized_error(
+        None,
+        None,
+        DatabaseError::no_results::<()>("Could not find event").unwrap_err(),
+    )
+    .await;
+    assert_eq!(content_language, None);
+    assert_eq!(body, json!({"error": "No results"}));
+}
@@ -2,4 +2,6 @@ pub mod database;
 pub mod domain_events;
 pub mod helpers;
 pub mod mailers;
+pub mod middleware;
 pub mod models;
+pub mod utils;
@@ -0,0 +1 @@
+pub mod wallet_passes;
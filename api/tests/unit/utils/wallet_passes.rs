@@ -0,0 +1,200 @@
+use api::config::{AppleWalletConfig, GoogleWalletConfig};
+use api::utils::wallet_passes::{apple, barcode_message, google};
+use chrono::prelude::*;
+use db::prelude::*;
+use openssl::asn1::Asn1Time;
+use openssl::hash::MessageDigest;
+use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+use openssl::pkey::PKey;
+use openssl::rsa::Rsa;
+use openssl::sha::sha1;
+use openssl::stack::Stack;
+use openssl::x509::store::X509StoreBuilder;
+use openssl::x509::{X509NameBuilder, X509};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use uuid::Uuid;
+use zip::ZipArchive;
+
+fn apple_config() -> AppleWalletConfig {
+    let private_key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+    let mut name = X509NameBuilder::new().unwrap();
+    name.append_entry_by_text("CN", "Pass Type ID: pass.com.bigneon.test")
+        .unwrap();
+    let name = name.build();
+    let mut certificate = X509::builder().unwrap();
+    certificate.set_version(2).unwrap();
+    certificate.set_subject_name(&name).unwrap();
+    certificate.set_issuer_name(&name).unwrap();
+    certificate.set_pubkey(&private_key).unwrap();
+    certificate
+        .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+        .unwrap();
+    certificate.set_not_after(&Asn1Time::days_from_now(1).unwrap()).unwrap();
+    certificate.sign(&private_key, MessageDigest::sha256()).unwrap();
+    let certificate = String::from_utf8(certificate.build().to_pem().unwrap()).unwrap();
+
+    AppleWalletConfig {
+        pass_type_identifier: "pass.com.bigneon.test".to_string(),
+        team_identifier: "TEAM123456".to_string(),
+        certificate: certificate.clone(),
+        private_key: String::from_utf8(private_key.private_key_to_pem_pkcs8().unwrap()).unwrap(),
+        // The test certificate is self signed so it stands in for the intermediate as well
+        wwdr_certificate: certificate,
+    }
+}
+
+fn pass_details(voided: bool) -> WalletPassDetails {
+    WalletPassDetails {
+        serial_number: Uuid::new_v4(),
+        pass_type: WalletPassTypes::Apple,
+        authentication_token: "authenticationtoken1234567890abc".to_string(),
+        voided,
+        ticket_instance_id: Uuid::new_v4(),
+        ticket_type_name: "General Admission".to_string(),
+        holder_name: Some("Jane Doe".to_string()),
+        status: TicketInstanceStatus::Purchased,
+        redeem_key: if voided { None } else { Some("REDEEMKEY".to_string()) },
+        barcode_rotation_seconds: None,
+        event_id: Uuid::new_v4(),
+        event_name: "Event".to_string(),
+        event_start: Some(NaiveDate::from_ymd(2020, 7, 8).and_hms(20, 0, 0)),
+        door_time: Some(NaiveDate::from_ymd(2020, 7, 8).and_hms(19, 0, 0)),
+        event_end: None,
+        organization_name: "Organization".to_string(),
+        venue: None,
+        updated_at: Utc::now().naive_utc(),
+    }
+}
+
+fn unzip(bundle: Vec<u8>) -> HashMap<String, Vec<u8>> {
+    let mut archive = ZipArchive::new(Cursor::new(bundle)).unwrap();
+    let mut files = HashMap::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).unwrap();
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).unwrap();
+        files.insert(file.name().to_string(), content);
+    }
+    files
+}
+
+#[test]
+fn pkpass() {
+    let config = apple_config();
+    let details = pass_details(false);
+    let files = unzip(apple::pkpass(&config, "https://api.bigneon.com/wallet_passes", &details).unwrap());
+
+    let mut names: Vec<&str> = files.keys().map(|name| name.as_str()).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "icon.png",
+            "icon@2x.png",
+            "logo.png",
+            "logo@2x.png",
+            "manifest.json",
+            "pass.json",
+            "signature"
+        ]
+    );
+
+    let pass: Value = serde_json::from_slice(&files["pass.json"]).unwrap();
+    assert_eq!(pass["passTypeIdentifier"], json!("pass.com.bigneon.test"));
+    assert_eq!(pass["teamIdentifier"], json!("TEAM123456"));
+    assert_eq!(pass["serialNumber"], json!(details.serial_number));
+    assert_eq!(pass["authenticationToken"], json!(details.authentication_token));
+    assert_eq!(pass["webServiceURL"], json!("https://api.bigneon.com/wallet_passes"));
+    assert_eq!(pass["voided"], json!(false));
+    assert_eq!(pass["relevantDate"], json!("2020-07-08T20:00:00Z"));
+    assert_eq!(pass["eventTicket"]["primaryFields"][0]["value"], json!("Event"));
+    assert_eq!(
+        pass["barcodes"][0]["message"],
+        json!(barcode_message(&details).unwrap())
+    );
+
+    // Every file other than the manifest and signature is listed with its SHA-1 hash
+    let manifest: HashMap<String, String> = serde_json::from_slice(&files["manifest.json"]).unwrap();
+    assert_eq!(manifest.len(), 5);
+    for (name, hash) in &manifest {
+        let expected_hash: String = sha1(&files[name]).iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(hash, &expected_hash);
+    }
+
+    // The signature is a detached signature of the manifest
+    let signature = Pkcs7::from_der(&files["signature"]).unwrap();
+    let certificate = X509::from_pem(config.certificate.as_bytes()).unwrap();
+    let mut store = X509StoreBuilder::new().unwrap();
+    store.add_cert(certificate).unwrap();
+    let store = store.build();
+    signature
+        .verify(
+            &Stack::new().unwrap(),
+            &store,
+            Some(&files["manifest.json"]),
+            None,
+            Pkcs7Flags::BINARY,
+        )
+        .unwrap();
+    assert!(signature
+        .verify(
+            &Stack::new().unwrap(),
+            &store,
+            Some(&files["pass.json"]),
+            None,
+            Pkcs7Flags::BINARY,
+        )
+        .is_err());
+}
+
+#[test]
+fn pass_json_voided() {
+    let config = apple_config();
+    let pass = apple::pass_json(&config, "https://api.bigneon.com/wallet_passes", &pass_details(true));
+    assert_eq!(pass["voided"], json!(true));
+    assert!(pass.get("barcodes").is_none());
+    assert!(pass.get("barcode").is_none());
+}
+
+#[test]
+fn pass_json_rotating_barcodes() {
+    let config = apple_config();
+    let mut details = pass_details(false);
+    details.barcode_rotation_seconds = Some(30);
+    let pass = apple::pass_json(&config, "https://api.bigneon.com/wallet_passes", &details);
+    assert_eq!(pass["voided"], json!(false));
+    assert!(pass.get("barcodes").is_none());
+    assert!(pass.get("barcode").is_none());
+}
+
+#[test]
+fn event_ticket_object() {
+    let config = GoogleWalletConfig {
+        issuer_id: "3388000000012345678".to_string(),
+        service_account_email: "wallet@bigneon.iam.gserviceaccount.com".to_string(),
+        private_key: "".to_string(),
+    };
+    let details = pass_details(false);
+
+    let class = google::event_ticket_class(&config, &details);
+    assert_eq!(class["id"], json!(format!("3388000000012345678.{}", details.event_id)));
+    assert_eq!(class["eventName"]["defaultValue"]["value"], json!("Event"));
+    assert_eq!(class["dateTime"]["start"], json!("2020-07-08T20:00:00Z"));
+    assert_eq!(class["dateTime"]["doorsOpen"], json!("2020-07-08T19:00:00Z"));
+
+    let object = google::event_ticket_object(&config, &details);
+    assert_eq!(
+        object["id"],
+        json!(format!("3388000000012345678.{}", details.serial_number))
+    );
+    assert_eq!(object["classId"], class["id"]);
+    assert_eq!(object["state"], json!("ACTIVE"));
+    assert_eq!(object["ticketHolderName"], json!("Jane Doe"));
+    assert_eq!(object["barcode"]["value"], json!(barcode_message(&details).unwrap()));
+
+    let object = google::event_ticket_object(&config, &pass_details(true));
+    assert_eq!(object["state"], json!("INACTIVE"));
+    assert!(object.get("barcode").is_none());
+}
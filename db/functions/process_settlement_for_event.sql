@@ -34,6 +34,8 @@ AND ($4 IS NULL OR o.paid_at <= $4)
 AND oi.event_id = $2
 AND (oi.item_type <> 'EventFees' OR oi.client_fee_in_cents > 0)
 AND oi.item_type <> 'CreditCardFees'
+-- Tax is settled with the item it was charged on below
+AND oi.item_type <> 'Tax'
 AND o.settlement_id IS NULL
 AND o.status = 'Paid'
 AND oi.parent_id IS NULL
@@ -49,6 +51,8 @@ INNER JOIN orders o on oi.order_id = o.id
 WHERE oi.event_id = $2
 AND (oi.item_type <> 'EventFees' OR oi.client_fee_in_cents > 0)
 AND oi.item_type <> 'CreditCardFees'
+-- Tax is refunded with the item it was charged on so that item's refund settles it below
+AND oi.item_type <> 'Tax'
 AND (start_override IS NULL OR r.created_at >= start_override)
 AND ($3 IS NULL OR r.created_at >= $3)
 AND ($4 IS NULL OR r.created_at <= $4)
@@ -57,22 +61,25 @@ AND ri.amount > 0
 AND r.settlement_id IS NULL
 AND o.box_office_pricing IS FALSE;
 
-INSERT INTO settlement_entries (settlement_id, event_id, ticket_type_id, face_value_in_cents, revenue_share_value_in_cents, online_sold_quantity, fee_sold_quantity, total_sales_in_cents, settlement_entry_type)
+INSERT INTO settlement_entries (settlement_id, event_id, ticket_type_id, product_variant_id, face_value_in_cents, revenue_share_value_in_cents, online_sold_quantity, fee_sold_quantity, total_sales_in_cents, settlement_entry_type, currency)
 SELECT -- Group result set by face price to prevent multiple records for holds that match code discounts
   entries.settlement_id,
   entries.event_id,
   entries.ticket_type_id,
+  entries.product_variant_id,
   entries.face_value_in_cents,
   entries.revenue_share_value_in_cents,
   SUM(online_sold_quantity),
   SUM(fee_sold_quantity),
   SUM(online_sold_quantity) * entries.face_value_in_cents + SUM(fee_sold_quantity) * entries.revenue_share_value_in_cents,
-  entries.settlement_entry_type
+  entries.settlement_entry_type,
+  entries.currency
 FROM (
   SELECT
     $1 as settlement_id,
     oi.event_id,
     oi.ticket_type_id,
+    oi.product_variant_id,
     CASE oi.item_type WHEN 'EventFees' THEN 0 ELSE CAST(oi.unit_price_in_cents + COALESCE(oi_promo_code.unit_price_in_cents, 0) AS BIGINT) END as face_value_in_cents,
     -- Event fees record list the fee as part of the revenue share for that item with 0 face value
     CASE oi.item_type WHEN 'EventFees' THEN CAST(oi.client_fee_in_cents AS BIGINT) ELSE CAST(COALESCE(oi_t_fees.client_fee_in_cents, 0) AS BIGINT) END as revenue_share_value_in_cents,
@@ -100,7 +107,10 @@ FROM (
           CAST(SUM(COALESCE(oi_t_fees.quantity, 0)) AS BIGINT)
         END
     END as fee_sold_quantity,
-    CASE oi.item_type WHEN 'EventFees' THEN 'EventFees' ELSE 'TicketType' END as settlement_entry_type
+    -- Add-ons are settled as their own rows per product variant
+    CASE oi.item_type WHEN 'EventFees' THEN 'EventFees' WHEN 'AddOns' THEN 'AddOn' ELSE 'TicketType' END as settlement_entry_type,
+    -- Amounts are settled in the currency the order was paid in
+    o.currency
   FROM order_items oi
   INNER JOIN order_item_ids oi_ids ON oi.id = oi_ids.id
   INNER JOIN orders o ON oi.order_id = o.id
@@ -110,8 +120,10 @@ FROM (
   LEFT JOIN refund_items oi_t_fees_r ON oi_t_fees_r.order_item_id = oi_t_fees.id AND oi_t_fees_r.refund_id = oi_ids.refund_id
   GROUP BY
     oi.item_type,
+    o.currency,
     oi.event_id,
     oi.ticket_type_id,
+    oi.product_variant_id,
     oi.unit_price_in_cents,
     oi.client_fee_in_cents,
     oi_t_fees.client_fee_in_cents,
@@ -123,9 +135,11 @@ FROM (
     entries.settlement_id,
     entries.event_id,
     entries.ticket_type_id,
+    entries.product_variant_id,
     entries.face_value_in_cents,
     entries.revenue_share_value_in_cents,
-    entries.settlement_entry_type
+    entries.settlement_entry_type,
+    entries.currency
   -- Filter out any records where the sum of their quantities is 0
   -- Negative indicates a refund settlement adjustment, positive purchases
   HAVING
@@ -134,6 +148,95 @@ FROM (
     (SUM(fee_sold_quantity) <> 0 AND revenue_share_value_in_cents > 0)
 ;
 
+-- Tax charged on top of settled items (and their per unit fees) is collected on the organization's behalf,
+-- refunds return it. Inclusive tax is already part of the face value settled above
+INSERT INTO settlement_entries (settlement_id, event_id, face_value_in_cents, revenue_share_value_in_cents, online_sold_quantity, fee_sold_quantity, total_sales_in_cents, settlement_entry_type, currency)
+SELECT
+  $1,
+  $2,
+  taxes.face_value_in_cents,
+  0,
+  SUM(taxes.quantity),
+  0,
+  SUM(taxes.quantity) * taxes.face_value_in_cents,
+  'Tax',
+  taxes.currency
+FROM (
+  SELECT
+    o.currency,
+    CAST(oi_tax.unit_price_in_cents AS BIGINT) as face_value_in_cents,
+    CASE WHEN oi_ids.refund_id IS NOT NULL THEN -COALESCE(oi_tax_r.quantity, 0) ELSE oi_tax.quantity END as quantity
+  FROM order_item_ids oi_ids
+  INNER JOIN order_items oi_taxed ON oi_taxed.id = oi_ids.id OR (oi_taxed.parent_id = oi_ids.id AND oi_taxed.item_type = 'PerUnitFees')
+  INNER JOIN order_items oi_tax ON oi_tax.parent_id = oi_taxed.id AND oi_tax.item_type = 'Tax'
+  INNER JOIN orders o ON oi_tax.order_id = o.id
+  LEFT JOIN refund_items oi_tax_r ON oi_tax_r.order_item_id = oi_tax.id AND oi_tax_r.refund_id = oi_ids.refund_id
+  WHERE oi_tax.unit_price_in_cents > 0
+) taxes
+GROUP BY taxes.face_value_in_cents, taxes.currency
+HAVING SUM(taxes.quantity) <> 0;
+
+-- Partner commissions are taken from the face value of tickets sold through the partner's checkout
+INSERT INTO settlement_entries (settlement_id, event_id, face_value_in_cents, revenue_share_value_in_cents, online_sold_quantity, fee_sold_quantity, total_sales_in_cents, settlement_entry_type, currency, partner_id)
+SELECT
+  $1,
+  $2,
+  0,
+  0,
+  SUM(commissions.quantity),
+  0,
+  -CAST(ROUND(SUM(commissions.quantity * commissions.face_value_in_cents) * p.commission_rate_percent / 100) AS BIGINT),
+  'PartnerCommission',
+  commissions.currency,
+  p.id
+FROM (
+  SELECT
+    o.partner_id,
+    o.currency,
+    CAST(oi.unit_price_in_cents + COALESCE(oi_promo_code.unit_price_in_cents, 0) AS BIGINT) as face_value_in_cents,
+    CASE WHEN oi_ids.refund_id IS NOT NULL THEN -COALESCE(oi_r.quantity, 0) ELSE oi.quantity END as quantity
+  FROM order_items oi
+  INNER JOIN order_item_ids oi_ids ON oi.id = oi_ids.id
+  INNER JOIN orders o ON oi.order_id = o.id
+  LEFT JOIN refund_items oi_r ON oi_r.order_item_id = oi.id AND oi_r.refund_id = oi_ids.refund_id
+  LEFT JOIN order_items oi_promo_code ON (oi_promo_code.item_type = 'Discount' AND oi.id = oi_promo_code.parent_id)
+  WHERE oi.item_type = 'Tickets'
+  AND o.partner_id IS NOT NULL
+) commissions
+INNER JOIN partners p ON p.id = commissions.partner_id
+GROUP BY p.id, p.commission_rate_percent, commissions.currency
+HAVING ROUND(SUM(commissions.quantity * commissions.face_value_in_cents) * p.commission_rate_percent / 100) <> 0;
+
+-- Affiliate commissions are settled from the commission ledger, refunds reverse the commission earned on the refunded tickets
+CREATE TEMP TABLE affiliate_commission_ids AS
+SELECT ac.id
+FROM affiliate_commissions ac
+INNER JOIN order_item_ids oi_ids ON oi_ids.id = ac.order_item_id AND oi_ids.refund_id IS NOT DISTINCT FROM ac.refund_id
+WHERE ac.settlement_id IS NULL;
+
+INSERT INTO settlement_entries (settlement_id, event_id, face_value_in_cents, revenue_share_value_in_cents, online_sold_quantity, fee_sold_quantity, total_sales_in_cents, settlement_entry_type, currency, affiliate_link_id)
+SELECT
+  $1,
+  $2,
+  0,
+  0,
+  SUM(ac.quantity),
+  0,
+  -SUM(ac.amount_in_cents),
+  'AffiliateCommission',
+  ac.currency,
+  ac.affiliate_link_id
+FROM affiliate_commissions ac
+INNER JOIN affiliate_commission_ids ac_ids ON ac_ids.id = ac.id
+GROUP BY ac.affiliate_link_id, ac.currency
+HAVING SUM(ac.amount_in_cents) <> 0;
+
+UPDATE affiliate_commissions SET settlement_id = $1, updated_at = now()
+FROM affiliate_commission_ids ac_ids
+WHERE affiliate_commissions.id = ac_ids.id;
+
+DROP TABLE affiliate_commission_ids;
+
 -- Update associated orders as part of this settlement
 UPDATE orders SET settlement_id = $1
 FROM order_item_ids oi_ids
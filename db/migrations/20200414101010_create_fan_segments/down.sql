@@ -0,0 +1,4 @@
+ALTER TABLE broadcasts
+  DROP fan_segment_id;
+
+DROP TABLE fan_segments;
@@ -0,0 +1,25 @@
+CREATE TABLE fan_segments (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  name TEXT NOT NULL,
+  min_events_attended INT NULL,
+  min_revenue_in_cents BIGINT NULL,
+  genre_id uuid NULL REFERENCES genres (id),
+  inactive_days INT NULL,
+  venue_id uuid NULL REFERENCES venues (id),
+  venue_radius_in_km DOUBLE PRECISION NULL,
+  redemption_code TEXT NULL,
+  sendgrid_list_id BIGINT NULL,
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_fan_segments_organization_id ON fan_segments (organization_id);
+CREATE INDEX index_fan_segments_genre_id ON fan_segments (genre_id);
+CREATE INDEX index_fan_segments_venue_id ON fan_segments (venue_id);
+
+ALTER TABLE broadcasts
+  ADD fan_segment_id uuid NULL REFERENCES fan_segments (id);
+
+CREATE INDEX index_broadcasts_fan_segment_id ON broadcasts (fan_segment_id);
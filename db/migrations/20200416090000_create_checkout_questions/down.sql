@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS checkout_answers;
+DROP TABLE IF EXISTS checkout_questions;
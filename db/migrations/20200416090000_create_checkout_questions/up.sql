@@ -0,0 +1,37 @@
+CREATE TABLE checkout_questions (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  event_id uuid NOT NULL REFERENCES events (id),
+  ticket_type_id uuid NULL REFERENCES ticket_types (id),
+  question TEXT NOT NULL,
+  question_type TEXT NOT NULL,
+  answer_scope TEXT NOT NULL,
+  options TEXT[] NOT NULL DEFAULT '{}',
+  required BOOLEAN NOT NULL DEFAULT false,
+  rank INT NOT NULL DEFAULT 0,
+  editable_until TIMESTAMP NULL,
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_checkout_questions_event_id ON checkout_questions (event_id);
+CREATE INDEX index_checkout_questions_ticket_type_id ON checkout_questions (ticket_type_id);
+
+CREATE TABLE checkout_answers (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  checkout_question_id uuid NOT NULL REFERENCES checkout_questions (id),
+  order_id uuid NOT NULL REFERENCES orders (id),
+  ticket_instance_id uuid NULL REFERENCES ticket_instances (id),
+  answer TEXT NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_checkout_answers_checkout_question_id ON checkout_answers (checkout_question_id);
+CREATE INDEX index_checkout_answers_order_id ON checkout_answers (order_id);
+CREATE INDEX index_checkout_answers_ticket_instance_id ON checkout_answers (ticket_instance_id);
+CREATE UNIQUE INDEX index_checkout_answers_question_order_ticket_instance ON checkout_answers (
+  checkout_question_id,
+  order_id,
+  COALESCE(ticket_instance_id, '00000000-0000-0000-0000-000000000000')
+);
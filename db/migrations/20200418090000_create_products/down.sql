@@ -0,0 +1,10 @@
+ALTER TABLE settlement_entries
+  DROP COLUMN product_variant_id;
+
+DROP INDEX IF EXISTS index_order_items_product_variant_id;
+ALTER TABLE order_items
+  DROP COLUMN product_variant_id,
+  DROP COLUMN redeemed_quantity;
+
+DROP TABLE IF EXISTS product_variants;
+DROP TABLE IF EXISTS products;
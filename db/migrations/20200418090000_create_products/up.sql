@@ -0,0 +1,37 @@
+CREATE TABLE products (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  event_id uuid NULL REFERENCES events (id),
+  name TEXT NOT NULL,
+  description TEXT NULL,
+  price_in_cents BIGINT NOT NULL,
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_products_organization_id ON products (organization_id);
+CREATE INDEX index_products_event_id ON products (event_id);
+
+CREATE TABLE product_variants (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  product_id uuid NOT NULL REFERENCES products (id),
+  name TEXT NOT NULL,
+  price_in_cents BIGINT NULL,
+  inventory BIGINT NOT NULL,
+  rank INT NOT NULL DEFAULT 0,
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_product_variants_product_id ON product_variants (product_id);
+
+ALTER TABLE order_items
+  ADD product_variant_id uuid NULL REFERENCES product_variants (id),
+  ADD redeemed_quantity BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX index_order_items_product_variant_id ON order_items (product_variant_id);
+
+ALTER TABLE settlement_entries
+  ADD product_variant_id uuid NULL REFERENCES product_variants (id);
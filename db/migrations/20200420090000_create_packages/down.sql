@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS index_order_items_package_id;
+ALTER TABLE order_items
+  DROP COLUMN package_id;
+
+DROP TABLE IF EXISTS package_ticket_types;
+DROP TABLE IF EXISTS packages;
@@ -0,0 +1,29 @@
+CREATE TABLE packages (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  name TEXT NOT NULL,
+  description TEXT NULL,
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_packages_organization_id ON packages (organization_id);
+
+CREATE TABLE package_ticket_types (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  package_id uuid NOT NULL REFERENCES packages (id) ON DELETE CASCADE,
+  ticket_type_id uuid NOT NULL REFERENCES ticket_types (id),
+  quantity BIGINT NOT NULL DEFAULT 1,
+  price_in_cents BIGINT NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_package_ticket_types_package_id_ticket_type_id ON package_ticket_types (package_id, ticket_type_id);
+CREATE INDEX index_package_ticket_types_ticket_type_id ON package_ticket_types (ticket_type_id);
+
+ALTER TABLE order_items
+  ADD package_id uuid NULL REFERENCES packages (id);
+
+CREATE INDEX index_order_items_package_id ON order_items (package_id);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS index_order_items_tax_rule_id;
+ALTER TABLE order_items
+  DROP COLUMN tax_rule_id,
+  DROP COLUMN tax_in_cents;
+
+DROP TABLE IF EXISTS tax_rules;
@@ -0,0 +1,22 @@
+CREATE TABLE tax_rules (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  venue_id uuid NULL REFERENCES venues (id),
+  name TEXT NOT NULL,
+  jurisdiction TEXT NOT NULL,
+  rate_percent REAL NOT NULL,
+  inclusive BOOLEAN NOT NULL DEFAULT false,
+  fees_taxable BOOLEAN NOT NULL DEFAULT false,
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_tax_rules_organization_id ON tax_rules (organization_id);
+CREATE INDEX index_tax_rules_venue_id ON tax_rules (venue_id);
+
+ALTER TABLE order_items
+  ADD tax_rule_id uuid NULL REFERENCES tax_rules (id),
+  ADD tax_in_cents BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX index_order_items_tax_rule_id ON order_items (tax_rule_id);
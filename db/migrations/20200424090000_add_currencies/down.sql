@@ -0,0 +1,14 @@
+ALTER TABLE settlement_entries
+  DROP currency;
+
+ALTER TABLE orders
+  DROP currency;
+
+ALTER TABLE events
+  DROP currency;
+
+ALTER TABLE fee_schedules
+  DROP currency;
+
+ALTER TABLE organizations
+  DROP currency;
@@ -0,0 +1,16 @@
+ALTER TABLE organizations
+  ADD currency TEXT NOT NULL DEFAULT 'USD';
+
+-- Fee schedule amounts are in the currency of the organization they belong to
+ALTER TABLE fee_schedules
+  ADD currency TEXT NOT NULL DEFAULT 'USD';
+
+-- Events are sold in their organization's currency unless overridden
+ALTER TABLE events
+  ADD currency TEXT NULL;
+
+ALTER TABLE orders
+  ADD currency TEXT NOT NULL DEFAULT 'USD';
+
+ALTER TABLE settlement_entries
+  ADD currency TEXT NOT NULL DEFAULT 'USD';
@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS index_settlement_payouts_settlement_payout_batch_id;
+DROP INDEX IF EXISTS index_settlement_payouts_status;
+DROP INDEX IF EXISTS index_settlement_payouts_organization_bank_account_id;
+DROP INDEX IF EXISTS index_settlement_payouts_settlement_id;
+DROP TABLE IF EXISTS settlement_payouts;
+DROP TABLE IF EXISTS settlement_payout_batches;
+
+DROP INDEX IF EXISTS index_organization_bank_accounts_organization_id;
+DROP TABLE IF EXISTS organization_bank_accounts;
@@ -0,0 +1,43 @@
+CREATE TABLE organization_bank_accounts (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  payout_method TEXT NOT NULL,
+  account_holder_name TEXT NOT NULL,
+  -- Account identifiers are stored encrypted
+  routing_number TEXT NULL,
+  account_number TEXT NULL,
+  iban TEXT NULL,
+  bic TEXT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_organization_bank_accounts_organization_id ON organization_bank_accounts (organization_id);
+
+-- Payouts are exported to the bank in batches, a payout is only ever included in one batch
+CREATE TABLE settlement_payout_batches (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  payout_method TEXT NOT NULL,
+  created_by_user_id uuid NULL REFERENCES users (id),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE TABLE settlement_payouts (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  settlement_id uuid NOT NULL REFERENCES settlements (id) ON DELETE CASCADE,
+  organization_bank_account_id uuid NOT NULL REFERENCES organization_bank_accounts (id),
+  payout_method TEXT NOT NULL,
+  amount_in_cents BIGINT NOT NULL,
+  currency TEXT NOT NULL,
+  status TEXT NOT NULL DEFAULT 'Initiated',
+  note TEXT NULL,
+  settlement_payout_batch_id uuid NULL REFERENCES settlement_payout_batches (id),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_settlement_payouts_settlement_id ON settlement_payouts (settlement_id);
+CREATE INDEX index_settlement_payouts_organization_bank_account_id ON settlement_payouts (organization_bank_account_id);
+CREATE INDEX index_settlement_payouts_status ON settlement_payouts (status);
+CREATE INDEX index_settlement_payouts_settlement_payout_batch_id ON settlement_payouts (settlement_payout_batch_id);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS index_settlement_statement_subscribers_user_id;
+DROP INDEX IF EXISTS index_settlement_statement_subscribers_organization_id_user_id;
+DROP TABLE IF EXISTS settlement_statement_subscribers;
+
+DROP INDEX IF EXISTS index_settlement_statements_settlement_id;
+DROP TABLE IF EXISTS settlement_statements;
@@ -0,0 +1,21 @@
+CREATE TABLE settlement_statements (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  settlement_id uuid NOT NULL REFERENCES settlements (id) ON DELETE CASCADE,
+  csv_content TEXT NOT NULL,
+  pdf_content BYTEA NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_settlement_statements_settlement_id ON settlement_statements (settlement_id);
+
+CREATE TABLE settlement_statement_subscribers (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  user_id uuid NOT NULL REFERENCES users (id),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_settlement_statement_subscribers_organization_id_user_id ON settlement_statement_subscribers (organization_id, user_id);
+CREATE INDEX index_settlement_statement_subscribers_user_id ON settlement_statement_subscribers (user_id);
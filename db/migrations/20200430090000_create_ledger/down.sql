@@ -0,0 +1,14 @@
+DROP TRIGGER IF EXISTS ledger_lines_append_only ON ledger_lines;
+DROP TRIGGER IF EXISTS ledger_journal_entries_append_only ON ledger_journal_entries;
+DROP FUNCTION IF EXISTS prevent_ledger_changes();
+
+DROP INDEX IF EXISTS index_ledger_lines_ledger_account_id;
+DROP INDEX IF EXISTS index_ledger_lines_ledger_journal_entry_id;
+DROP TABLE IF EXISTS ledger_lines;
+
+DROP INDEX IF EXISTS index_ledger_journal_entries_source_table_source_id;
+DROP INDEX IF EXISTS index_ledger_journal_entries_organization_id;
+DROP TABLE IF EXISTS ledger_journal_entries;
+
+DROP INDEX IF EXISTS index_ledger_accounts_organization_id_account_type_currency;
+DROP TABLE IF EXISTS ledger_accounts;
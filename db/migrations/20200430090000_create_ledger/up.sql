@@ -0,0 +1,49 @@
+CREATE TABLE ledger_accounts (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  account_type TEXT NOT NULL,
+  currency TEXT NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_ledger_accounts_organization_id_account_type_currency ON ledger_accounts (organization_id, account_type, currency);
+
+CREATE TABLE ledger_journal_entries (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  currency TEXT NOT NULL,
+  source_table TEXT NOT NULL,
+  source_id uuid NOT NULL,
+  description TEXT NOT NULL,
+  reverses_ledger_journal_entry_id uuid NULL REFERENCES ledger_journal_entries (id),
+  created_by uuid NULL REFERENCES users (id),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_ledger_journal_entries_organization_id ON ledger_journal_entries (organization_id);
+CREATE INDEX index_ledger_journal_entries_source_table_source_id ON ledger_journal_entries (source_table, source_id);
+
+CREATE TABLE ledger_lines (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  ledger_journal_entry_id uuid NOT NULL REFERENCES ledger_journal_entries (id),
+  ledger_account_id uuid NOT NULL REFERENCES ledger_accounts (id),
+  debit_in_cents BIGINT NOT NULL DEFAULT 0 CHECK (debit_in_cents >= 0),
+  credit_in_cents BIGINT NOT NULL DEFAULT 0 CHECK (credit_in_cents >= 0),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  CONSTRAINT ledger_lines_debit_or_credit CHECK (debit_in_cents = 0 OR credit_in_cents = 0)
+);
+
+CREATE INDEX index_ledger_lines_ledger_journal_entry_id ON ledger_lines (ledger_journal_entry_id);
+CREATE INDEX index_ledger_lines_ledger_account_id ON ledger_lines (ledger_account_id);
+
+-- The journal is append-only, corrections are made by recording reversing entries
+CREATE OR REPLACE FUNCTION prevent_ledger_changes() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'Ledger journal entries and lines can not be changed once recorded';
+END $$ LANGUAGE 'plpgsql';
+
+CREATE TRIGGER ledger_journal_entries_append_only BEFORE UPDATE OR DELETE ON ledger_journal_entries
+  FOR EACH ROW EXECUTE PROCEDURE prevent_ledger_changes();
+CREATE TRIGGER ledger_lines_append_only BEFORE UPDATE OR DELETE ON ledger_lines
+  FOR EACH ROW EXECUTE PROCEDURE prevent_ledger_changes();
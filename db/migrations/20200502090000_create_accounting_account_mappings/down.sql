@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS index_accounting_account_mappings_organization_id_account_type;
+DROP TABLE IF EXISTS accounting_account_mappings;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS index_loot_box_draws_ticket_instance_id;
+DROP TABLE IF EXISTS loot_box_draws;
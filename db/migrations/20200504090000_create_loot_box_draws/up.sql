@@ -0,0 +1,16 @@
+CREATE TABLE loot_box_draws (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  ticket_instance_id uuid NOT NULL REFERENCES ticket_instances (id),
+  server_seed TEXT NOT NULL,
+  server_seed_hash TEXT NOT NULL,
+  client_seed TEXT NULL,
+  -- Contents the draw was made from, in draw order, so the draw can be replayed from the revealed seeds
+  candidate_ticket_instance_ids uuid[] NOT NULL DEFAULT '{}',
+  drawn_ticket_instance_ids uuid[] NOT NULL DEFAULT '{}',
+  opened_by_user_id uuid NULL REFERENCES users (id),
+  opened_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_loot_box_draws_ticket_instance_id ON loot_box_draws (ticket_instance_id);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS index_asset_sync_issues_asset_id;
+DROP TABLE IF EXISTS asset_sync_issues;
+
+ALTER TABLE assets
+    DROP COLUMN last_synced_at,
+    DROP COLUMN sync_error;
@@ -0,0 +1,18 @@
+ALTER TABLE assets
+    ADD COLUMN last_synced_at TIMESTAMP NULL,
+    ADD COLUMN sync_error TEXT NULL;
+
+CREATE TABLE asset_sync_issues (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  asset_id uuid NOT NULL REFERENCES assets (id),
+  ticket_instance_id uuid NULL REFERENCES ticket_instances (id),
+  token_id BIGINT NULL,
+  issue_type TEXT NOT NULL,
+  database_value TEXT NULL,
+  blockchain_value TEXT NULL,
+  repaired BOOLEAN NOT NULL DEFAULT false,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_asset_sync_issues_asset_id ON asset_sync_issues (asset_id);
@@ -0,0 +1,11 @@
+DROP INDEX IF EXISTS index_user_credit_entries_source_table_source_id;
+DROP INDEX IF EXISTS index_user_credit_entries_user_id_currency;
+DROP TABLE IF EXISTS user_credit_entries;
+
+DROP INDEX IF EXISTS index_ticket_instances_trade_id;
+
+ALTER TABLE ticket_instances
+    DROP trade_id;
+
+DROP TABLE IF EXISTS trade_items;
+DROP TABLE IF EXISTS trades;
@@ -0,0 +1,53 @@
+CREATE TABLE trades (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  proposed_by_user_id uuid NOT NULL REFERENCES users (id),
+  recipient_user_id uuid NOT NULL REFERENCES users (id),
+  status TEXT NOT NULL DEFAULT 'Pending',
+  message TEXT NULL,
+  cash_in_cents BIGINT NOT NULL DEFAULT 0,
+  currency TEXT NOT NULL DEFAULT 'USD',
+  payment_provider TEXT NULL,
+  external_payment_reference TEXT NULL,
+  expires_at TIMESTAMP NOT NULL,
+  responded_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_trades_proposed_by_user_id ON trades (proposed_by_user_id);
+CREATE INDEX index_trades_recipient_user_id ON trades (recipient_user_id);
+
+CREATE TABLE trade_items (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  trade_id uuid NOT NULL REFERENCES trades (id),
+  ticket_instance_id uuid NOT NULL REFERENCES ticket_instances (id),
+  from_user_id uuid NOT NULL REFERENCES users (id),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_trade_items_trade_id_ticket_instance_id ON trade_items (trade_id, ticket_instance_id);
+CREATE INDEX index_trade_items_ticket_instance_id ON trade_items (ticket_instance_id);
+
+-- Offered tickets are locked while the trade is pending
+ALTER TABLE ticket_instances
+    ADD trade_id uuid NULL REFERENCES trades (id);
+
+CREATE INDEX index_ticket_instances_trade_id ON ticket_instances (trade_id);
+
+-- Cash received by fans, e.g. the cash offered in an accepted trade. Entries are never updated so
+-- the balance is the sum of the user's entries in the currency.
+CREATE TABLE user_credit_entries (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  user_id uuid NOT NULL REFERENCES users (id),
+  amount_in_cents BIGINT NOT NULL,
+  currency TEXT NOT NULL,
+  source_table TEXT NOT NULL,
+  source_id uuid NOT NULL,
+  description TEXT NOT NULL,
+  created_by uuid NULL REFERENCES users (id),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_user_credit_entries_user_id_currency ON user_credit_entries (user_id, currency);
+CREATE INDEX index_user_credit_entries_source_table_source_id ON user_credit_entries (source_table, source_id);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS wallet_pass_registrations;
+DROP TABLE IF EXISTS wallet_passes;
@@ -0,0 +1,26 @@
+CREATE TABLE wallet_passes (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  ticket_instance_id uuid NOT NULL REFERENCES ticket_instances (id),
+  wallet_id uuid NOT NULL REFERENCES wallets (id),
+  pass_type TEXT NOT NULL,
+  authentication_token TEXT NOT NULL,
+  voided_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Each owner holds at most one live pass per wallet app for a ticket, passes of previous owners are voided
+CREATE UNIQUE INDEX index_wallet_passes_ticket_instance_id_wallet_id_pass_type ON wallet_passes (ticket_instance_id, wallet_id, pass_type) WHERE voided_at IS NULL;
+CREATE INDEX index_wallet_passes_ticket_instance_id ON wallet_passes (ticket_instance_id);
+
+CREATE TABLE wallet_pass_registrations (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  wallet_pass_id uuid NOT NULL REFERENCES wallet_passes (id),
+  device_library_identifier TEXT NOT NULL,
+  push_token TEXT NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_wallet_pass_registrations_wallet_pass_id_device ON wallet_pass_registrations (wallet_pass_id, device_library_identifier);
+CREATE INDEX index_wallet_pass_registrations_device_library_identifier ON wallet_pass_registrations (device_library_identifier);
@@ -0,0 +1,2 @@
+ALTER TABLE ticket_instances
+    DROP redeem_secret;
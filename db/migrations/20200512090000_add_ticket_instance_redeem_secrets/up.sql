@@ -0,0 +1,7 @@
+ALTER TABLE ticket_instances
+    ADD redeem_secret TEXT NULL;
+
+-- Tickets that already have a redeem key get a secret so they can be scanned at events using rotating barcodes
+UPDATE ticket_instances
+SET redeem_secret = md5(random()::TEXT || id::TEXT)
+WHERE redeem_key IS NOT NULL;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS ticket_scans;
+DROP TABLE IF EXISTS access_zone_ticket_types;
+DROP TABLE IF EXISTS access_zones;
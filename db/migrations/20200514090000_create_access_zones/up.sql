@@ -0,0 +1,44 @@
+CREATE TABLE access_zones (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  event_id uuid NOT NULL REFERENCES events (id),
+  name TEXT NOT NULL,
+  re_entry_policy TEXT NOT NULL DEFAULT 'Unlimited',
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_access_zones_event_id ON access_zones (event_id);
+
+CREATE TABLE access_zone_ticket_types (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  access_zone_id uuid NOT NULL REFERENCES access_zones (id),
+  ticket_type_id uuid NOT NULL REFERENCES ticket_types (id),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_access_zone_ticket_types_access_zone_id_ticket_type_id ON access_zone_ticket_types (access_zone_id, ticket_type_id);
+CREATE INDEX index_access_zone_ticket_types_ticket_type_id ON access_zone_ticket_types (ticket_type_id);
+
+-- Every scan attempt is recorded, rejected scans may not resolve to a ticket
+CREATE TABLE ticket_scans (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  event_id uuid NOT NULL REFERENCES events (id),
+  ticket_instance_id uuid NULL REFERENCES ticket_instances (id),
+  access_zone_id uuid NULL REFERENCES access_zones (id),
+  direction TEXT NOT NULL,
+  result TEXT NOT NULL,
+  rejection_reason TEXT NULL,
+  redeem_key TEXT NULL,
+  scanned_by_user_id uuid NOT NULL REFERENCES users (id),
+  device_id TEXT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  -- Orders scans made within the same transaction, where created_at is identical
+  seq BIGSERIAL
+);
+
+CREATE INDEX index_ticket_scans_event_id_created_at ON ticket_scans (event_id, created_at);
+CREATE INDEX index_ticket_scans_ticket_instance_id ON ticket_scans (ticket_instance_id);
+CREATE INDEX index_ticket_scans_access_zone_id ON ticket_scans (access_zone_id);
@@ -0,0 +1,10 @@
+DROP INDEX IF EXISTS index_payments_cash_drawer_session_id;
+ALTER TABLE payments
+  DROP COLUMN cash_drawer_session_id;
+
+DROP INDEX IF EXISTS index_cash_drawer_movements_cash_drawer_session_id;
+DROP TABLE IF EXISTS cash_drawer_movements;
+
+DROP INDEX IF EXISTS index_cash_drawer_sessions_user_id_open;
+DROP INDEX IF EXISTS index_cash_drawer_sessions_organization_id_created_at;
+DROP TABLE IF EXISTS cash_drawer_sessions;
@@ -0,0 +1,36 @@
+CREATE TABLE cash_drawer_sessions (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  user_id uuid NOT NULL REFERENCES users (id),
+  status TEXT NOT NULL DEFAULT 'Open',
+  opening_float_in_cents BIGINT NOT NULL,
+  expected_cash_in_cents BIGINT NULL,
+  counted_cash_in_cents BIGINT NULL,
+  closing_notes TEXT NULL,
+  closed_by_user_id uuid NULL REFERENCES users (id),
+  closed_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_cash_drawer_sessions_organization_id_created_at ON cash_drawer_sessions (organization_id, created_at);
+-- A staff member can only work one drawer at a time
+CREATE UNIQUE INDEX index_cash_drawer_sessions_user_id_open ON cash_drawer_sessions (user_id) WHERE status = 'Open';
+
+CREATE TABLE cash_drawer_movements (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  cash_drawer_session_id uuid NOT NULL REFERENCES cash_drawer_sessions (id),
+  movement_type TEXT NOT NULL,
+  amount_in_cents BIGINT NOT NULL CHECK (amount_in_cents > 0),
+  reason TEXT NULL,
+  created_by_user_id uuid NOT NULL REFERENCES users (id),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_cash_drawer_movements_cash_drawer_session_id ON cash_drawer_movements (cash_drawer_session_id);
+
+ALTER TABLE payments
+  ADD cash_drawer_session_id uuid NULL REFERENCES cash_drawer_sessions (id);
+
+CREATE INDEX index_payments_cash_drawer_session_id ON payments (cash_drawer_session_id);
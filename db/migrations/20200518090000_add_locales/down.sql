@@ -0,0 +1,5 @@
+ALTER TABLE organizations
+  DROP default_locale;
+
+ALTER TABLE users
+  DROP locale;
@@ -0,0 +1,5 @@
+ALTER TABLE users
+  ADD locale TEXT NULL;
+
+ALTER TABLE organizations
+  ADD default_locale TEXT NOT NULL DEFAULT 'En';
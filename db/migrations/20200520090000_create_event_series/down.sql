@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS index_event_series_occurrences_event_series_id_occurrence_start;
+DROP INDEX IF EXISTS index_event_series_occurrences_event_id;
+DROP TABLE IF EXISTS event_series_occurrences;
+
+DROP INDEX IF EXISTS index_event_series_organization_id;
+DROP TABLE IF EXISTS event_series;
@@ -0,0 +1,27 @@
+CREATE TABLE event_series (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  template_event_id uuid NOT NULL REFERENCES events (id),
+  name TEXT NOT NULL,
+  recurrence_rule TEXT NOT NULL,
+  generated_until TIMESTAMP NULL,
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_event_series_organization_id ON event_series (organization_id);
+
+CREATE TABLE event_series_occurrences (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  event_series_id uuid NOT NULL REFERENCES event_series (id),
+  event_id uuid NOT NULL REFERENCES events (id),
+  occurrence_start TIMESTAMP NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- An event belongs to at most one series and each occurrence of the rule is generated once, deleting an
+-- occurrence's event keeps the row so it is not generated again
+CREATE UNIQUE INDEX index_event_series_occurrences_event_id ON event_series_occurrences (event_id);
+CREATE UNIQUE INDEX index_event_series_occurrences_event_series_id_occurrence_start ON event_series_occurrences (event_series_id, occurrence_start);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS index_ticket_types_stage_id;
+
+ALTER TABLE ticket_types
+    DROP stage_id;
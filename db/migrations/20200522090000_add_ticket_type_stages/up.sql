@@ -0,0 +1,5 @@
+-- Ticket types sold for a stage count towards its capacity as well as the event's
+ALTER TABLE ticket_types
+    ADD stage_id uuid NULL REFERENCES stages (id);
+
+CREATE INDEX index_ticket_types_stage_id ON ticket_types (stage_id);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS index_organization_brandings_custom_domain;
+DROP INDEX IF EXISTS index_organization_brandings_organization_id;
+DROP TABLE IF EXISTS organization_brandings;
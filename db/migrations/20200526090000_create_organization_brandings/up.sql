@@ -0,0 +1,18 @@
+CREATE TABLE organization_brandings (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  custom_domain TEXT NULL,
+  logo_url TEXT NULL,
+  primary_color TEXT NULL,
+  secondary_color TEXT NULL,
+  sender_email TEXT NULL,
+  sender_name TEXT NULL,
+  support_email TEXT NULL,
+  support_phone TEXT NULL,
+  support_url TEXT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_organization_brandings_organization_id ON organization_brandings (organization_id);
+CREATE UNIQUE INDEX index_organization_brandings_custom_domain ON organization_brandings (custom_domain);
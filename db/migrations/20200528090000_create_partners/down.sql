@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS index_partner_guests_user_id;
+DROP INDEX IF EXISTS index_partner_guests_partner_id_user_id;
+DROP TABLE IF EXISTS partner_guests;
+
+ALTER TABLE settlement_entries DROP COLUMN IF EXISTS partner_id;
+DROP INDEX IF EXISTS index_orders_partner_id;
+ALTER TABLE orders DROP COLUMN IF EXISTS partner_id;
+DROP INDEX IF EXISTS index_partners_api_key_hash;
+DROP TABLE IF EXISTS partners;
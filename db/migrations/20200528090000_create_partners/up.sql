@@ -0,0 +1,32 @@
+CREATE TABLE partners (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  name TEXT NOT NULL,
+  api_key_hash TEXT NULL,
+  allowed_origins TEXT[] NOT NULL DEFAULT '{}',
+  commission_rate_percent REAL NOT NULL DEFAULT 0,
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_partners_api_key_hash ON partners (api_key_hash);
+
+-- Orders placed through a partner's checkout are attributed to the partner
+ALTER TABLE orders ADD partner_id uuid NULL REFERENCES partners (id);
+CREATE INDEX index_orders_partner_id ON orders (partner_id);
+
+-- Partner commission settlement entries record the partner they are owed to
+ALTER TABLE settlement_entries ADD partner_id uuid NULL REFERENCES partners (id);
+
+-- Users registered by email only through a partner's checkout, the partner can start checkout sessions
+-- for returning guests until they set a password of their own
+CREATE TABLE partner_guests (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  partner_id uuid NOT NULL REFERENCES partners (id),
+  user_id uuid NOT NULL REFERENCES users (id),
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_partner_guests_partner_id_user_id ON partner_guests (partner_id, user_id);
+CREATE INDEX index_partner_guests_user_id ON partner_guests (user_id);
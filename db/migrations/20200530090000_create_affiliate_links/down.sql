@@ -0,0 +1,6 @@
+ALTER TABLE settlement_entries DROP COLUMN IF EXISTS affiliate_link_id;
+DROP INDEX IF EXISTS index_orders_affiliate_link_id;
+ALTER TABLE orders DROP COLUMN IF EXISTS affiliate_link_id;
+DROP TABLE IF EXISTS affiliate_commissions;
+DROP TABLE IF EXISTS affiliate_commission_rules;
+DROP TABLE IF EXISTS affiliate_links;
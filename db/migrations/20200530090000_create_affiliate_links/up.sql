@@ -0,0 +1,55 @@
+CREATE TABLE affiliate_links (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  organization_id uuid NOT NULL REFERENCES organizations (id),
+  user_id uuid NOT NULL REFERENCES users (id),
+  event_id uuid NULL REFERENCES events (id),
+  name TEXT NOT NULL,
+  code TEXT NOT NULL,
+  deleted_at TIMESTAMP NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX index_affiliate_links_code ON affiliate_links (code);
+CREATE INDEX index_affiliate_links_organization_id ON affiliate_links (organization_id);
+CREATE INDEX index_affiliate_links_user_id ON affiliate_links (user_id);
+
+-- Rules without a ticket type apply to any ticket type without its own rule
+CREATE TABLE affiliate_commission_rules (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  affiliate_link_id uuid NOT NULL REFERENCES affiliate_links (id),
+  ticket_type_id uuid NULL REFERENCES ticket_types (id),
+  commission_in_cents BIGINT NULL,
+  commission_as_percentage BIGINT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  CONSTRAINT affiliate_commission_rules_single_commission CHECK ((commission_in_cents IS NULL) <> (commission_as_percentage IS NULL))
+);
+
+CREATE UNIQUE INDEX index_affiliate_commission_rules_affiliate_link_id_ticket_type_id ON affiliate_commission_rules (affiliate_link_id, COALESCE(ticket_type_id, '00000000-0000-0000-0000-000000000000'));
+
+-- Commissions earned on paid orders, refunds are recorded as negative commissions
+CREATE TABLE affiliate_commissions (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+  affiliate_link_id uuid NOT NULL REFERENCES affiliate_links (id),
+  order_id uuid NOT NULL REFERENCES orders (id),
+  order_item_id uuid NOT NULL REFERENCES order_items (id),
+  event_id uuid NOT NULL REFERENCES events (id),
+  refund_id uuid NULL REFERENCES refunds (id),
+  settlement_id uuid NULL REFERENCES settlements (id),
+  quantity BIGINT NOT NULL,
+  amount_in_cents BIGINT NOT NULL,
+  currency TEXT NOT NULL,
+  created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+  updated_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX index_affiliate_commissions_affiliate_link_id ON affiliate_commissions (affiliate_link_id);
+CREATE INDEX index_affiliate_commissions_order_item_id ON affiliate_commissions (order_item_id);
+CREATE INDEX index_affiliate_commissions_settlement_id ON affiliate_commissions (settlement_id);
+
+ALTER TABLE orders ADD affiliate_link_id uuid NULL REFERENCES affiliate_links (id);
+CREATE INDEX index_orders_affiliate_link_id ON orders (affiliate_link_id);
+
+-- Affiliate commission settlement entries record the link the commission was earned through
+ALTER TABLE settlement_entries ADD affiliate_link_id uuid NULL REFERENCES affiliate_links (id);
@@ -0,0 +1 @@
+ALTER TABLE affiliate_links DROP COLUMN IF EXISTS visit_count;
@@ -0,0 +1 @@
+ALTER TABLE affiliate_links ADD visit_count BIGINT NOT NULL DEFAULT 0;
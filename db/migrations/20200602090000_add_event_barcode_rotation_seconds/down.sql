@@ -0,0 +1,2 @@
+ALTER TABLE events
+    DROP barcode_rotation_seconds;
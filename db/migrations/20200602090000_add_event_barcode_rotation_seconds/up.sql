@@ -0,0 +1,3 @@
+-- Redeem codes for events with a rotation period are derived from the ticket's redeem secret and the current time
+ALTER TABLE events
+    ADD barcode_rotation_seconds INTEGER NULL;
@@ -0,0 +1,3 @@
+-- Limit on tickets sold, reserved or held across all of the event's ticket types
+ALTER TABLE events
+    ADD capacity BIGINT NULL;
@@ -0,0 +1,300 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text, Uuid as dUuid};
+use models::*;
+use schema::{access_zone_ticket_types, access_zones};
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationError, ValidationErrors};
+use validators::{self, *};
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "access_zones"]
+pub struct AccessZone {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub name: String,
+    pub re_entry_policy: ReEntryPolicies,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "access_zones"]
+pub struct NewAccessZone {
+    #[serde(default)]
+    pub event_id: Uuid,
+    #[validate(length(min = 1, message = "Name cannot be blank"))]
+    pub name: String,
+    #[serde(default)]
+    pub re_entry_policy: ReEntryPolicies,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "access_zones"]
+pub struct AccessZoneEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, message = "Name cannot be blank"))]
+    pub name: Option<String>,
+    pub re_entry_policy: Option<ReEntryPolicies>,
+}
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "access_zone_ticket_types"]
+pub struct AccessZoneTicketType {
+    pub id: Uuid,
+    pub access_zone_id: Uuid,
+    pub ticket_type_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "access_zone_ticket_types"]
+struct NewAccessZoneTicketType {
+    access_zone_id: Uuid,
+    ticket_type_id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayAccessZone {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub name: String,
+    pub re_entry_policy: ReEntryPolicies,
+    pub ticket_type_ids: Vec<Uuid>,
+    pub occupancy: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, QueryableByName, Serialize)]
+pub struct AccessZoneOccupancy {
+    #[sql_type = "dUuid"]
+    pub access_zone_id: Uuid,
+    #[sql_type = "Text"]
+    pub name: String,
+    #[sql_type = "BigInt"]
+    pub occupancy: i64,
+}
+
+impl AccessZone {
+    pub fn create(event_id: Uuid, name: String, re_entry_policy: ReEntryPolicies) -> NewAccessZone {
+        NewAccessZone {
+            event_id,
+            name,
+            re_entry_policy,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<AccessZone, DatabaseError> {
+        access_zones::table
+            .filter(access_zones::id.eq(id))
+            .filter(access_zones::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load access zone")
+    }
+
+    pub fn find_for_event(event_id: Uuid, conn: &PgConnection) -> Result<Vec<AccessZone>, DatabaseError> {
+        access_zones::table
+            .filter(access_zones::event_id.eq(event_id))
+            .filter(access_zones::deleted_at.is_null())
+            .order_by(access_zones::name.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load access zones")
+    }
+
+    pub fn event(&self, conn: &PgConnection) -> Result<Event, DatabaseError> {
+        Event::find(self.event_id, conn)
+    }
+
+    pub fn ticket_type_ids(&self, conn: &PgConnection) -> Result<Vec<Uuid>, DatabaseError> {
+        access_zone_ticket_types::table
+            .filter(access_zone_ticket_types::access_zone_id.eq(self.id))
+            .select(access_zone_ticket_types::ticket_type_id)
+            .order_by(access_zone_ticket_types::created_at.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load access zone ticket types")
+    }
+
+    /// Only holders of the zone's ticket types are let in
+    pub fn admits(&self, ticket_type_id: Uuid, conn: &PgConnection) -> Result<bool, DatabaseError> {
+        Ok(self.ticket_type_ids(conn)?.contains(&ticket_type_id))
+    }
+
+    pub fn update_ticket_types(&self, ticket_type_ids: Vec<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        validators::append_validation_error(
+            Ok(()),
+            "ticket_type_ids",
+            AccessZone::ticket_types_valid(self.event_id, &ticket_type_ids, conn)?,
+        )?;
+
+        let existing_ticket_type_ids = self.ticket_type_ids(conn)?;
+        let pending_deletion = existing_ticket_type_ids
+            .iter()
+            .filter(|id| !ticket_type_ids.contains(id))
+            .cloned()
+            .collect::<Vec<Uuid>>();
+        let pending_addition = ticket_type_ids
+            .into_iter()
+            .filter(|id| !existing_ticket_type_ids.contains(id))
+            .map(|ticket_type_id| NewAccessZoneTicketType {
+                access_zone_id: self.id,
+                ticket_type_id,
+            })
+            .collect::<Vec<NewAccessZoneTicketType>>();
+
+        diesel::delete(
+            access_zone_ticket_types::table
+                .filter(access_zone_ticket_types::access_zone_id.eq(self.id))
+                .filter(access_zone_ticket_types::ticket_type_id.eq_any(pending_deletion)),
+        )
+        .execute(conn)
+        .to_db_error(ErrorCode::DeleteError, "Could not remove access zone ticket types")?;
+        diesel::insert_into(access_zone_ticket_types::table)
+            .values(&pending_addition)
+            .execute(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not add access zone ticket types")?;
+        Ok(())
+    }
+
+    /// Number of tickets whose most recent accepted scan for the zone was an entry
+    pub fn occupancy(&self, conn: &PgConnection) -> Result<i64, DatabaseError> {
+        Ok(AccessZone::occupancy_for_event(self.event_id, conn)?
+            .into_iter()
+            .find(|o| o.access_zone_id == self.id)
+            .map(|o| o.occupancy)
+            .unwrap_or(0))
+    }
+
+    pub fn occupancy_for_event(event_id: Uuid, conn: &PgConnection) -> Result<Vec<AccessZoneOccupancy>, DatabaseError> {
+        let query = r#"
+            SELECT az.id AS access_zone_id, az.name, COUNT(latest_scans.ticket_instance_id) AS occupancy
+            FROM access_zones az
+            LEFT JOIN (
+                SELECT DISTINCT ON (ts.access_zone_id, ts.ticket_instance_id)
+                    ts.access_zone_id, ts.ticket_instance_id, ts.direction
+                FROM ticket_scans ts
+                WHERE ts.event_id = $1
+                AND ts.access_zone_id IS NOT NULL
+                AND ts.result = 'Accepted'
+                ORDER BY ts.access_zone_id, ts.ticket_instance_id, ts.seq DESC
+            ) latest_scans ON latest_scans.access_zone_id = az.id AND latest_scans.direction = 'Entry'
+            WHERE az.event_id = $1
+            AND az.deleted_at IS NULL
+            GROUP BY az.id, az.name
+            ORDER BY az.name;"#;
+
+        diesel::sql_query(query)
+            .bind::<dUuid, _>(event_id)
+            .get_results(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load access zone occupancy")
+    }
+
+    pub fn for_display(&self, conn: &PgConnection) -> Result<DisplayAccessZone, DatabaseError> {
+        Ok(DisplayAccessZone {
+            id: self.id,
+            event_id: self.event_id,
+            name: self.name.clone(),
+            re_entry_policy: self.re_entry_policy,
+            ticket_type_ids: self.ticket_type_ids(conn)?,
+            occupancy: self.occupancy(conn)?,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
+    }
+
+    pub fn update(
+        &self,
+        attributes: AccessZoneEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<AccessZone, DatabaseError> {
+        AccessZone::validate_record(attributes.validate())?;
+
+        let access_zone: AccessZone = diesel::update(self)
+            .set((attributes, access_zones::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update access zone")?;
+
+        DomainEvent::create(
+            DomainEventTypes::AccessZoneUpdated,
+            "Access zone updated".to_string(),
+            Tables::AccessZones,
+            Some(access_zone.id),
+            current_user_id,
+            Some(json!(&access_zone)),
+        )
+        .commit(conn)?;
+
+        Ok(access_zone)
+    }
+
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::AccessZoneDeleted,
+            "Access zone deleted".to_string(),
+            Tables::AccessZones,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((
+                access_zones::deleted_at.eq(dsl::now),
+                access_zones::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete access zone")?;
+
+        Ok(())
+    }
+
+    fn validate_record(validation_errors: Result<(), ValidationErrors>) -> Result<(), DatabaseError> {
+        Ok(validation_errors?)
+    }
+
+    fn ticket_types_valid(
+        event_id: Uuid,
+        ticket_type_ids: &[Uuid],
+        conn: &PgConnection,
+    ) -> Result<Result<(), ValidationError>, DatabaseError> {
+        for ticket_type_id in ticket_type_ids {
+            if TicketType::find(*ticket_type_id, conn)?.event_id != event_id {
+                return Ok(Err(create_validation_error(
+                    "ticket_type_does_not_belong_to_event",
+                    "Ticket types must belong to the access zone's event",
+                )));
+            }
+        }
+        Ok(Ok(()))
+    }
+}
+
+impl NewAccessZone {
+    pub fn commit(self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<AccessZone, DatabaseError> {
+        AccessZone::validate_record(self.validate())?;
+
+        let access_zone: AccessZone = diesel::insert_into(access_zones::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create access zone")?;
+
+        DomainEvent::create(
+            DomainEventTypes::AccessZoneCreated,
+            "Access zone created".to_string(),
+            Tables::AccessZones,
+            Some(access_zone.id),
+            current_user_id,
+            Some(json!(&access_zone)),
+        )
+        .commit(conn)?;
+
+        Ok(access_zone)
+    }
+}
@@ -0,0 +1,196 @@
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::expression::dsl;
+use diesel::pg::upsert::excluded;
+use diesel::prelude::*;
+use models::*;
+use schema::accounting_account_mappings;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+use validators;
+
+pub const LEDGER_ACCOUNT_TYPES: [LedgerAccountTypes; 8] = [
+    LedgerAccountTypes::AffiliateCommissions,
+    LedgerAccountTypes::Cash,
+    LedgerAccountTypes::CreditCardFees,
+    LedgerAccountTypes::GrossSales,
+    LedgerAccountTypes::PartnerCommissions,
+    LedgerAccountTypes::PayableToOrganization,
+    LedgerAccountTypes::PlatformFees,
+    LedgerAccountTypes::TaxCollected,
+];
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "accounting_account_mappings"]
+pub struct AccountingAccountMapping {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub account_type: LedgerAccountTypes,
+    pub account_code: String,
+    pub account_name: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "accounting_account_mappings"]
+pub struct NewAccountingAccountMapping {
+    #[serde(default)]
+    pub organization_id: Uuid,
+    pub account_type: LedgerAccountTypes,
+    #[validate(length(min = 1, max = 50))]
+    pub account_code: String,
+    #[validate(length(min = 1, max = 255))]
+    pub account_name: String,
+}
+
+/// Account a ledger account is exported to, falling back to the default chart of accounts when
+/// the organization has not configured a mapping
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayAccountingAccountMapping {
+    pub account_type: LedgerAccountTypes,
+    pub account_code: String,
+    pub account_name: String,
+    pub is_default: bool,
+}
+
+impl AccountingAccountMapping {
+    pub fn create(
+        organization_id: Uuid,
+        account_type: LedgerAccountTypes,
+        account_code: String,
+        account_name: String,
+    ) -> NewAccountingAccountMapping {
+        NewAccountingAccountMapping {
+            organization_id,
+            account_type,
+            account_code,
+            account_name,
+        }
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<AccountingAccountMapping>, DatabaseError> {
+        accounting_account_mappings::table
+            .filter(accounting_account_mappings::organization_id.eq(organization_id))
+            .order_by(accounting_account_mappings::account_type)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load accounting account mappings")
+    }
+
+    /// Mappings for every ledger account type
+    pub fn resolve_for_organization(
+        organization_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<DisplayAccountingAccountMapping>, DatabaseError> {
+        let mappings = AccountingAccountMapping::find_for_organization(organization_id, conn)?;
+        Ok(LEDGER_ACCOUNT_TYPES
+            .iter()
+            .map(
+                |account_type| match mappings.iter().find(|m| m.account_type == *account_type) {
+                    Some(mapping) => DisplayAccountingAccountMapping {
+                        account_type: mapping.account_type,
+                        account_code: mapping.account_code.clone(),
+                        account_name: mapping.account_name.clone(),
+                        is_default: false,
+                    },
+                    None => AccountingAccountMapping::default_mapping(*account_type),
+                },
+            )
+            .collect())
+    }
+
+    /// Codes follow the default Xero chart of accounts
+    pub fn default_mapping(account_type: LedgerAccountTypes) -> DisplayAccountingAccountMapping {
+        let (account_code, account_name) = match account_type {
+            LedgerAccountTypes::AffiliateCommissions => ("815", "Affiliate Commissions Payable"),
+            LedgerAccountTypes::Cash => ("090", "Undeposited Funds"),
+            LedgerAccountTypes::CreditCardFees => ("404", "Credit Card Fees"),
+            LedgerAccountTypes::GrossSales => ("200", "Ticket Sales"),
+            LedgerAccountTypes::PartnerCommissions => ("810", "Partner Commissions Payable"),
+            LedgerAccountTypes::PayableToOrganization => ("800", "Accounts Payable"),
+            LedgerAccountTypes::PlatformFees => ("260", "Platform Fees"),
+            LedgerAccountTypes::TaxCollected => ("820", "Sales Tax"),
+        };
+        DisplayAccountingAccountMapping {
+            account_type,
+            account_code: account_code.to_string(),
+            account_name: account_name.to_string(),
+            is_default: true,
+        }
+    }
+}
+
+impl NewAccountingAccountMapping {
+    /// Creates the mapping for the account type, replacing any existing mapping
+    pub fn commit(
+        mut self,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<AccountingAccountMapping, DatabaseError> {
+        self.account_code = self.account_code.trim().to_string();
+        self.account_name = self.account_name.trim().to_string();
+        self.validate_record()?;
+
+        let mapping: AccountingAccountMapping = diesel::insert_into(accounting_account_mappings::table)
+            .values(&self)
+            .on_conflict((
+                accounting_account_mappings::organization_id,
+                accounting_account_mappings::account_type,
+            ))
+            .do_update()
+            .set((
+                accounting_account_mappings::account_code.eq(excluded(accounting_account_mappings::account_code)),
+                accounting_account_mappings::account_name.eq(excluded(accounting_account_mappings::account_name)),
+                accounting_account_mappings::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not save accounting account mapping")?;
+
+        DomainEvent::create(
+            DomainEventTypes::AccountingAccountMappingUpdated,
+            "Accounting account mapping updated".to_string(),
+            Tables::Organizations,
+            Some(mapping.organization_id),
+            current_user_id,
+            Some(json!({
+                "account_type": mapping.account_type,
+                "account_code": mapping.account_code,
+                "account_name": mapping.account_name
+            })),
+        )
+        .commit(conn)?;
+
+        Ok(mapping)
+    }
+
+    fn validate_record(&self) -> Result<(), DatabaseError> {
+        let mut validation_errors: Result<(), ValidationErrors> = self.validate();
+        // Tabs and line breaks would split the row in the exported IIF and CSV files
+        let has_control_characters = |value: &str| value.chars().any(|c| c.is_control());
+        if has_control_characters(&self.account_code) {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "account_code",
+                Err(validators::create_validation_error(
+                    "invalid_characters",
+                    "Account code cannot contain tabs or line breaks",
+                )),
+            );
+        }
+        if has_control_characters(&self.account_name) {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "account_name",
+                Err(validators::create_validation_error(
+                    "invalid_characters",
+                    "Account name cannot contain tabs or line breaks",
+                )),
+            );
+        }
+        Ok(validation_errors?)
+    }
+}
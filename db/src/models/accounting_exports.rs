@@ -0,0 +1,203 @@
+use chrono::prelude::*;
+use diesel::prelude::*;
+use models::*;
+use schema::{ledger_accounts, ledger_lines};
+use utils::csv::CsvWriter;
+use utils::errors::*;
+use uuid::Uuid;
+
+const XERO_TAX_RATE: &str = "Tax Exempt";
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum AccountingExportFormats {
+    QuickbooksIif,
+    XeroCsv,
+}
+
+impl AccountingExportFormats {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            AccountingExportFormats::QuickbooksIif => "text/plain",
+            AccountingExportFormats::XeroCsv => "text/csv",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            AccountingExportFormats::QuickbooksIif => "iif",
+            AccountingExportFormats::XeroCsv => "csv",
+        }
+    }
+}
+
+/// Ledger journal entries for an organization exported as general journal transactions that can
+/// be imported into QuickBooks Desktop (IIF) or Xero (manual journal CSV).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountingExport {
+    pub format: AccountingExportFormats,
+    pub file_name: String,
+    pub content: String,
+}
+
+struct ExportTransaction {
+    id: Uuid,
+    date: NaiveDate,
+    description: String,
+    /// Mapped account and signed amount, debits are positive and credits negative
+    lines: Vec<(DisplayAccountingAccountMapping, i64)>,
+}
+
+impl AccountingExport {
+    pub fn generate(
+        organization: &Organization,
+        currency: Currencies,
+        start_utc: Option<NaiveDateTime>,
+        end_utc: Option<NaiveDateTime>,
+        format: AccountingExportFormats,
+        conn: &PgConnection,
+    ) -> Result<AccountingExport, DatabaseError> {
+        let transactions = AccountingExport::transactions(organization, currency, start_utc, end_utc, conn)?;
+        let content = match format {
+            AccountingExportFormats::QuickbooksIif => AccountingExport::quickbooks_iif(&transactions),
+            AccountingExportFormats::XeroCsv => AccountingExport::xero_csv(&transactions),
+        };
+        let date_label = |date: Option<NaiveDateTime>| {
+            date.map(|d| d.format("%Y%m%d").to_string())
+                .unwrap_or("all".to_string())
+        };
+
+        Ok(AccountingExport {
+            format,
+            file_name: format!(
+                "journal-{}-{}-{}.{}",
+                currency.to_string().to_lowercase(),
+                date_label(start_utc),
+                date_label(end_utc),
+                format.extension()
+            ),
+            content,
+        })
+    }
+
+    fn transactions(
+        organization: &Organization,
+        currency: Currencies,
+        start_utc: Option<NaiveDateTime>,
+        end_utc: Option<NaiveDateTime>,
+        conn: &PgConnection,
+    ) -> Result<Vec<ExportTransaction>, DatabaseError> {
+        let entries = LedgerJournalEntry::find_for_organization(organization.id, currency, start_utc, end_utc, conn)?;
+        let entry_ids: Vec<Uuid> = entries.iter().map(|e| e.id).collect();
+        let lines: Vec<(Uuid, LedgerAccountTypes, i64, i64)> = ledger_lines::table
+            .inner_join(ledger_accounts::table)
+            .filter(ledger_lines::ledger_journal_entry_id.eq_any(&entry_ids))
+            .select((
+                ledger_lines::ledger_journal_entry_id,
+                ledger_accounts::account_type,
+                ledger_lines::debit_in_cents,
+                ledger_lines::credit_in_cents,
+            ))
+            .order_by(ledger_lines::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ledger lines")?;
+        let mappings = AccountingAccountMapping::resolve_for_organization(organization.id, conn)?;
+        let timezone = organization.timezone()?;
+
+        let mut transactions = Vec::new();
+        for entry in entries {
+            let mut entry_lines: Vec<(DisplayAccountingAccountMapping, i64)> = lines
+                .iter()
+                .filter(|(entry_id, _, _, _)| *entry_id == entry.id)
+                .map(|(_, account_type, debit_in_cents, credit_in_cents)| {
+                    let mapping = mappings
+                        .iter()
+                        .find(|m| m.account_type == *account_type)
+                        .cloned()
+                        .unwrap_or_else(|| AccountingAccountMapping::default_mapping(*account_type));
+                    (mapping, debit_in_cents - credit_in_cents)
+                })
+                .collect();
+            // Debits are listed first, QuickBooks treats the first line as the transaction
+            entry_lines.sort_by_key(|(_, amount)| -amount);
+            transactions.push(ExportTransaction {
+                id: entry.id,
+                date: timezone.from_utc_datetime(&entry.created_at).naive_local().date(),
+                description: entry.description,
+                lines: entry_lines,
+            });
+        }
+
+        Ok(transactions)
+    }
+
+    fn quickbooks_iif(transactions: &[ExportTransaction]) -> String {
+        let mut rows: Vec<Vec<String>> = vec![
+            iif_row(&[
+                "!TRNS", "TRNSID", "TRNSTYPE", "DATE", "ACCNT", "AMOUNT", "DOCNUM", "MEMO",
+            ]),
+            iif_row(&["!SPL", "SPLID", "TRNSTYPE", "DATE", "ACCNT", "AMOUNT", "DOCNUM", "MEMO"]),
+            iif_row(&["!ENDTRNS"]),
+        ];
+        for transaction in transactions {
+            let date = transaction.date.format("%m/%d/%Y").to_string();
+            let document_number = transaction.id.simple().to_string()[0..8].to_uppercase();
+            for (index, (mapping, amount_in_cents)) in transaction.lines.iter().enumerate() {
+                rows.push(iif_row(&[
+                    if index == 0 { "TRNS" } else { "SPL" },
+                    "",
+                    "GENERAL JOURNAL",
+                    &date,
+                    &mapping.account_name,
+                    &format_cents(*amount_in_cents),
+                    &document_number,
+                    &transaction.description,
+                ]));
+            }
+            rows.push(iif_row(&["ENDTRNS"]));
+        }
+
+        rows.iter().map(|row| format!("{}\r\n", row.join("\t"))).collect()
+    }
+
+    fn xero_csv(transactions: &[ExportTransaction]) -> String {
+        let mut writer = CsvWriter::new();
+        writer.write_row(&[
+            "*Narration",
+            "*Date",
+            "Description",
+            "*AccountCode",
+            "*TaxRate",
+            "*Amount",
+        ]);
+        for transaction in transactions {
+            let date = transaction.date.format("%Y-%m-%d").to_string();
+            for (mapping, amount_in_cents) in &transaction.lines {
+                writer.write_row(&[
+                    transaction.description.clone(),
+                    date.clone(),
+                    mapping.account_name.clone(),
+                    mapping.account_code.clone(),
+                    XERO_TAX_RATE.to_string(),
+                    format_cents(*amount_in_cents),
+                ]);
+            }
+        }
+        writer.into_string()
+    }
+}
+
+/// IIF files are tab delimited without any quoting so tabs and line breaks are replaced
+fn iif_row(fields: &[&str]) -> Vec<String> {
+    fields
+        .iter()
+        .map(|f| f.replace(|c: char| c.is_control(), " "))
+        .collect()
+}
+
+#[test]
+fn iif_row_replaces_control_characters() {
+    assert_eq!(
+        iif_row(&["TRNS", "Order\tpaid\r\n"]),
+        vec!["TRNS".to_string(), "Order paid  ".to_string()]
+    );
+}
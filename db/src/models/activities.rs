@@ -77,6 +77,17 @@ pub enum ActivityItem {
         transfer_key: Uuid,
         eligible_for_cancelling: bool,
     },
+    Trade {
+        trade_id: Uuid,
+        action: String,
+        status: TradeStatus,
+        offered_ticket_ids: Vec<Uuid>,
+        requested_ticket_ids: Vec<Uuid>,
+        cash_in_cents: i64,
+        proposed_by: UserActivityItem,
+        recipient: UserActivityItem,
+        occurred_at: NaiveDateTime,
+    },
     CheckIn {
         ticket_instance_id: Uuid,
         ticket_number: String,
@@ -133,6 +144,10 @@ impl ActivityItem {
             )?);
         }
 
+        if activity_type.is_none() || activity_type == Some(ActivityType::Trade) {
+            activity_items.append(&mut ActivityItem::load_trades(event_id, user_id, conn)?);
+        }
+
         if activity_type.is_none() || activity_type == Some(ActivityType::CheckIn) {
             activity_items.append(&mut ActivityItem::load_check_ins(
                 None,
@@ -572,6 +587,83 @@ impl ActivityItem {
         Ok(activity_items)
     }
 
+    fn load_trades(event_id: Uuid, user_id: Uuid, conn: &PgConnection) -> Result<Vec<ActivityItem>, DatabaseError> {
+        use schema::*;
+        let event_trade_ids = trade_items::table
+            .inner_join(ticket_instances::table.on(trade_items::ticket_instance_id.eq(ticket_instances::id)))
+            .inner_join(assets::table.on(ticket_instances::asset_id.eq(assets::id)))
+            .inner_join(ticket_types::table.on(assets::ticket_type_id.eq(ticket_types::id)))
+            .filter(ticket_types::event_id.eq(event_id))
+            .select(trade_items::trade_id);
+
+        let trade_data: Vec<(Trade, DomainEventTypes, NaiveDateTime)> = trades::table
+            .inner_join(
+                domain_events::table.on(domain_events::main_table
+                    .eq(Tables::Trades)
+                    .and(domain_events::main_id.eq(trades::id.nullable()))),
+            )
+            .filter(trades::id.eq_any(event_trade_ids))
+            .filter(
+                trades::proposed_by_user_id
+                    .eq(user_id)
+                    .or(trades::recipient_user_id.eq(user_id)),
+            )
+            .filter(domain_events::event_type.eq_any(vec![
+                DomainEventTypes::TradeAccepted,
+                DomainEventTypes::TradeCancelled,
+                DomainEventTypes::TradeDeclined,
+                DomainEventTypes::TradeExpired,
+                DomainEventTypes::TradeProposed,
+            ]))
+            .select((
+                trades::all_columns,
+                domain_events::event_type,
+                domain_events::created_at,
+            ))
+            .order_by(domain_events::created_at.desc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load trades for organization fan")?;
+
+        let mut user_ids: Vec<Uuid> = trade_data
+            .iter()
+            .flat_map(|(trade, _, _)| vec![trade.proposed_by_user_id, trade.recipient_user_id])
+            .collect();
+        user_ids.sort();
+        user_ids.dedup();
+        let mut user_map: HashMap<Uuid, UserActivityItem> = HashMap::new();
+        for user in User::find_by_ids(&user_ids, conn)? {
+            user_map.insert(user.id, user.into());
+        }
+        let find_user = |id: &Uuid| {
+            user_map.get(id).map(|u| u.clone()).ok_or_else(|| {
+                DatabaseError::new(
+                    ErrorCode::BusinessProcessError,
+                    Some("Unable to load trading user".to_string()),
+                )
+            })
+        };
+
+        let mut activity_items: Vec<ActivityItem> = Vec::new();
+        for (trade, event_type, occurred_at) in &trade_data {
+            let (offered_items, requested_items): (Vec<TradeItem>, Vec<TradeItem>) = trade
+                .items(conn)?
+                .into_iter()
+                .partition(|i| i.from_user_id == trade.proposed_by_user_id);
+            activity_items.push(ActivityItem::Trade {
+                trade_id: trade.id,
+                action: event_type.to_string().trim_start_matches("Trade").to_string(),
+                status: trade.status,
+                offered_ticket_ids: offered_items.iter().map(|i| i.ticket_instance_id).collect(),
+                requested_ticket_ids: requested_items.iter().map(|i| i.ticket_instance_id).collect(),
+                cash_in_cents: trade.cash_in_cents,
+                proposed_by: find_user(&trade.proposed_by_user_id)?,
+                recipient: find_user(&trade.recipient_user_id)?,
+                occurred_at: *occurred_at,
+            });
+        }
+        Ok(activity_items)
+    }
+
     fn load_check_ins(
         order_id: Option<Uuid>,
         event_id: Option<Uuid>,
@@ -785,6 +877,11 @@ impl ActivityItem {
             pub company_fee_in_cents: i64,
             pub client_fee_in_cents: i64,
             pub refunded_quantity: i64,
+            pub product_variant_id: Option<Uuid>,
+            pub redeemed_quantity: i64,
+            pub package_id: Option<Uuid>,
+            pub tax_rule_id: Option<Uuid>,
+            pub tax_in_cents: i64,
         };
 
         let refund_ids: Vec<Uuid> = refund_data.iter().map(|r| r.refund_id).collect();
@@ -814,6 +911,11 @@ impl ActivityItem {
                 order_items::company_fee_in_cents,
                 order_items::client_fee_in_cents,
                 order_items::refunded_quantity,
+                order_items::product_variant_id,
+                order_items::redeemed_quantity,
+                order_items::package_id,
+                order_items::tax_rule_id,
+                order_items::tax_in_cents,
             ))
             .order_by(refunds::id)
             .load(conn)
@@ -849,6 +951,11 @@ impl ActivityItem {
                     company_fee_in_cents: item.company_fee_in_cents,
                     client_fee_in_cents: item.client_fee_in_cents,
                     refunded_quantity: item.refunded_quantity,
+                    product_variant_id: item.product_variant_id,
+                    redeemed_quantity: item.redeemed_quantity,
+                    package_id: item.package_id,
+                    tax_rule_id: item.tax_rule_id,
+                    tax_in_cents: item.tax_in_cents,
                 };
                 refund_items.push(RefundActivityItem {
                     id: item.id,
@@ -968,6 +1075,7 @@ impl ActivityItem {
         match *self {
             ActivityItem::Purchase { occurred_at, .. } => occurred_at,
             ActivityItem::Transfer { occurred_at, .. } => occurred_at,
+            ActivityItem::Trade { occurred_at, .. } => occurred_at,
             ActivityItem::CheckIn { occurred_at, .. } => occurred_at,
             ActivityItem::Refund { occurred_at, .. } => occurred_at,
             ActivityItem::Note { occurred_at, .. } => occurred_at,
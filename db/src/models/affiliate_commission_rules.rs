@@ -0,0 +1,203 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::prelude::*;
+use models::*;
+use schema::affiliate_commission_rules;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+use validators;
+
+/// Commission earned per ticket sold through an affiliate link, either a flat amount or a percentage of
+/// the ticket's face value. A rule without a ticket type applies to ticket types without their own rule.
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "affiliate_commission_rules"]
+pub struct AffiliateCommissionRule {
+    pub id: Uuid,
+    pub affiliate_link_id: Uuid,
+    pub ticket_type_id: Option<Uuid>,
+    pub commission_in_cents: Option<i64>,
+    pub commission_as_percentage: Option<i64>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "affiliate_commission_rules"]
+pub struct NewAffiliateCommissionRule {
+    #[serde(default)]
+    pub affiliate_link_id: Uuid,
+    pub ticket_type_id: Option<Uuid>,
+    pub commission_in_cents: Option<i64>,
+    pub commission_as_percentage: Option<i64>,
+}
+
+impl AffiliateCommissionRule {
+    pub fn create(
+        affiliate_link_id: Uuid,
+        ticket_type_id: Option<Uuid>,
+        commission_in_cents: Option<i64>,
+        commission_as_percentage: Option<i64>,
+    ) -> NewAffiliateCommissionRule {
+        NewAffiliateCommissionRule {
+            affiliate_link_id,
+            ticket_type_id,
+            commission_in_cents,
+            commission_as_percentage,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<AffiliateCommissionRule, DatabaseError> {
+        affiliate_commission_rules::table
+            .filter(affiliate_commission_rules::id.eq(id))
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load affiliate commission rule")
+    }
+
+    pub fn find_for_affiliate_link(
+        affiliate_link_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<AffiliateCommissionRule>, DatabaseError> {
+        affiliate_commission_rules::table
+            .filter(affiliate_commission_rules::affiliate_link_id.eq(affiliate_link_id))
+            .order_by(affiliate_commission_rules::ticket_type_id.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load affiliate commission rules")
+    }
+
+    /// The ticket type's own rule, falling back to the link's default rule
+    pub fn rule_for_ticket_type(
+        rules: &[AffiliateCommissionRule],
+        ticket_type_id: Uuid,
+    ) -> Option<&AffiliateCommissionRule> {
+        rules
+            .iter()
+            .find(|rule| rule.ticket_type_id == Some(ticket_type_id))
+            .or_else(|| rules.iter().find(|rule| rule.ticket_type_id.is_none()))
+    }
+
+    /// Commission earned on a single ticket sold for `face_value_in_cents`
+    pub fn commission_for_unit_price(&self, face_value_in_cents: i64) -> i64 {
+        match (self.commission_in_cents, self.commission_as_percentage) {
+            (Some(commission_in_cents), _) => commission_in_cents.min(face_value_in_cents.max(0)),
+            (None, Some(percentage)) => (face_value_in_cents.max(0) as f64 * percentage as f64 / 100f64).round() as i64,
+            (None, None) => 0,
+        }
+    }
+
+    pub fn affiliate_link(&self, conn: &PgConnection) -> Result<AffiliateLink, DatabaseError> {
+        AffiliateLink::find(self.affiliate_link_id, conn)
+    }
+
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::AffiliateCommissionRuleDeleted,
+            "Affiliate commission rule deleted".to_string(),
+            Tables::AffiliateLinks,
+            Some(self.affiliate_link_id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::delete(self)
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete affiliate commission rule")?;
+
+        Ok(())
+    }
+}
+
+impl NewAffiliateCommissionRule {
+    pub fn commit(
+        self,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<AffiliateCommissionRule, DatabaseError> {
+        self.validate_record(conn)?;
+
+        let rule: AffiliateCommissionRule = diesel::insert_into(affiliate_commission_rules::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create affiliate commission rule")?;
+
+        DomainEvent::create(
+            DomainEventTypes::AffiliateCommissionRuleCreated,
+            "Affiliate commission rule created".to_string(),
+            Tables::AffiliateLinks,
+            Some(rule.affiliate_link_id),
+            current_user_id,
+            Some(json!(&rule)),
+        )
+        .commit(conn)?;
+
+        Ok(rule)
+    }
+
+    fn validate_record(&self, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let mut validation_errors: Result<(), ValidationErrors> = self.validate();
+        match (self.commission_in_cents, self.commission_as_percentage) {
+            (Some(_), Some(_)) | (None, None) => {
+                validation_errors = validators::append_validation_error(
+                    validation_errors,
+                    "commission_in_cents",
+                    Err(validators::create_validation_error(
+                        "only_single_commission_type_allowed",
+                        "Commission must be either a flat amount or a percentage",
+                    )),
+                );
+            }
+            (Some(commission_in_cents), None) if commission_in_cents < 0 => {
+                validation_errors = validators::append_validation_error(
+                    validation_errors,
+                    "commission_in_cents",
+                    Err(validators::create_validation_error(
+                        "invalid_commission",
+                        "Commission cannot be negative",
+                    )),
+                );
+            }
+            (None, Some(percentage)) if percentage < 0 || percentage > 100 => {
+                validation_errors = validators::append_validation_error(
+                    validation_errors,
+                    "commission_as_percentage",
+                    Err(validators::create_validation_error(
+                        "invalid_commission",
+                        "Commission must be between 0 and 100 percent",
+                    )),
+                );
+            }
+            _ => (),
+        }
+
+        let affiliate_link = AffiliateLink::find(self.affiliate_link_id, conn)?;
+        if let Some(ticket_type_id) = self.ticket_type_id {
+            let event = TicketType::find(ticket_type_id, conn)?.event(conn)?;
+            if !affiliate_link.applies_to_event(&event) {
+                validation_errors = validators::append_validation_error(
+                    validation_errors,
+                    "ticket_type_id",
+                    Err(validators::create_validation_error(
+                        "invalid_ticket_type",
+                        "Ticket type must be for an event the affiliate link applies to",
+                    )),
+                );
+            }
+        }
+        let existing_rules = affiliate_link.commission_rules(conn)?;
+        if existing_rules
+            .iter()
+            .any(|rule| rule.ticket_type_id == self.ticket_type_id)
+        {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "ticket_type_id",
+                Err(validators::create_validation_error(
+                    "duplicate_rule",
+                    "A commission rule already exists for this ticket type",
+                )),
+            );
+        }
+        Ok(validation_errors?)
+    }
+}
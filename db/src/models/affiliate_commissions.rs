@@ -0,0 +1,229 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable, Text, Uuid as dUuid};
+use models::*;
+use schema::affiliate_commissions;
+use utils::errors::*;
+use uuid::Uuid;
+
+/// Commission ledger, an entry is recorded for each ticket order item paid through an affiliate link.
+/// Refunds record negative entries so the ledger always sums to the commission owed.
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "affiliate_commissions"]
+pub struct AffiliateCommission {
+    pub id: Uuid,
+    pub affiliate_link_id: Uuid,
+    pub order_id: Uuid,
+    pub order_item_id: Uuid,
+    pub event_id: Uuid,
+    pub refund_id: Option<Uuid>,
+    pub settlement_id: Option<Uuid>,
+    pub quantity: i64,
+    pub amount_in_cents: i64,
+    pub currency: Currencies,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name = "affiliate_commissions"]
+pub struct NewAffiliateCommission {
+    pub affiliate_link_id: Uuid,
+    pub order_id: Uuid,
+    pub order_item_id: Uuid,
+    pub event_id: Uuid,
+    pub refund_id: Option<Uuid>,
+    pub quantity: i64,
+    pub amount_in_cents: i64,
+    pub currency: Currencies,
+}
+
+/// Promoter facing summary of commission earned per affiliate link and event
+#[derive(Clone, Debug, Deserialize, PartialEq, QueryableByName, Serialize)]
+pub struct AffiliateEarnings {
+    #[sql_type = "dUuid"]
+    pub affiliate_link_id: Uuid,
+    #[sql_type = "Text"]
+    pub affiliate_link_name: String,
+    #[sql_type = "Text"]
+    pub code: String,
+    #[sql_type = "dUuid"]
+    pub organization_id: Uuid,
+    #[sql_type = "Text"]
+    pub organization_name: String,
+    #[sql_type = "Nullable<dUuid>"]
+    pub event_id: Option<Uuid>,
+    #[sql_type = "Nullable<Text>"]
+    pub event_name: Option<String>,
+    #[sql_type = "Nullable<Text>"]
+    pub currency: Option<String>,
+    #[sql_type = "BigInt"]
+    pub tickets_sold: i64,
+    #[sql_type = "BigInt"]
+    pub commission_in_cents: i64,
+    #[sql_type = "BigInt"]
+    pub settled_commission_in_cents: i64,
+}
+
+impl AffiliateCommission {
+    pub fn find_for_affiliate_link(
+        affiliate_link_id: Uuid,
+        page: u32,
+        limit: u32,
+        conn: &PgConnection,
+    ) -> Result<Payload<AffiliateCommission>, DatabaseError> {
+        use utils::pagination::Paginate;
+
+        let (affiliate_commissions, total) = affiliate_commissions::table
+            .filter(affiliate_commissions::affiliate_link_id.eq(affiliate_link_id))
+            .order_by(affiliate_commissions::created_at.desc())
+            .then_order_by(affiliate_commissions::id)
+            .paginate(page as i64)
+            .per_page(limit as i64)
+            .load_and_count_pages(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load affiliate commissions")?;
+
+        Ok(Payload::from_data(
+            affiliate_commissions,
+            page,
+            limit,
+            Some(total as u64),
+        ))
+    }
+
+    pub fn find_for_order(order_id: Uuid, conn: &PgConnection) -> Result<Vec<AffiliateCommission>, DatabaseError> {
+        affiliate_commissions::table
+            .filter(affiliate_commissions::order_id.eq(order_id))
+            .order_by(affiliate_commissions::created_at.asc())
+            .then_order_by(affiliate_commissions::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load affiliate commissions for order")
+    }
+
+    /// Earnings for each of the user's affiliate links, links without sales are included with no event
+    pub fn earnings_for_user(user_id: Uuid, conn: &PgConnection) -> Result<Vec<AffiliateEarnings>, DatabaseError> {
+        let query = include_str!("../queries/affiliate_earnings_for_user.sql");
+        diesel::sql_query(query)
+            .bind::<dUuid, _>(user_id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load affiliate earnings")
+    }
+
+    /// Records the commission earned on a paid order attributed to an affiliate link. Promoters do not
+    /// earn commission on their own purchases.
+    pub fn record_for_order(order: &Order, conn: &PgConnection) -> Result<Vec<AffiliateCommission>, DatabaseError> {
+        let affiliate_link = match order.affiliate_link_id {
+            Some(affiliate_link_id) => AffiliateLink::find(affiliate_link_id, conn).optional()?,
+            None => None,
+        };
+        let affiliate_link = match affiliate_link {
+            Some(ref affiliate_link) if affiliate_link.user_id != order.user_id => affiliate_link,
+            _ => return Ok(Vec::new()),
+        };
+        let rules = affiliate_link.commission_rules(conn)?;
+        if rules.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut commissions = Vec::new();
+        for item in order.items(conn)? {
+            let (event_id, ticket_type_id) = match (item.item_type, item.event_id, item.ticket_type_id) {
+                (OrderItemTypes::Tickets, Some(event_id), Some(ticket_type_id)) => (event_id, ticket_type_id),
+                _ => continue,
+            };
+            if !affiliate_link.applies_to_event(&Event::find(event_id, conn)?) {
+                continue;
+            }
+            let rule = match AffiliateCommissionRule::rule_for_ticket_type(&rules, ticket_type_id) {
+                Some(rule) => rule,
+                None => continue,
+            };
+
+            let discount_in_cents = item
+                .find_discount_item(conn)?
+                .map(|discount_item| discount_item.unit_price_in_cents)
+                .unwrap_or(0);
+            let amount_in_cents =
+                rule.commission_for_unit_price(item.unit_price_in_cents + discount_in_cents) * item.quantity;
+            if amount_in_cents == 0 {
+                continue;
+            }
+
+            commissions.push(
+                NewAffiliateCommission {
+                    affiliate_link_id: affiliate_link.id,
+                    order_id: order.id,
+                    order_item_id: item.id,
+                    event_id,
+                    refund_id: None,
+                    quantity: item.quantity,
+                    amount_in_cents,
+                    currency: order.currency,
+                }
+                .commit(conn)?,
+            );
+        }
+
+        Ok(commissions)
+    }
+
+    /// Reverses the commission earned on refunded tickets
+    pub fn record_for_refund(refund: &Refund, conn: &PgConnection) -> Result<Vec<AffiliateCommission>, DatabaseError> {
+        let earned_commissions: Vec<AffiliateCommission> = AffiliateCommission::find_for_order(refund.order_id, conn)?
+            .into_iter()
+            .filter(|commission| commission.refund_id.is_none())
+            .collect();
+        if earned_commissions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut commissions = Vec::new();
+        for refund_item in refund.items(conn)? {
+            let earned_commission = match earned_commissions
+                .iter()
+                .find(|commission| commission.order_item_id == refund_item.order_item_id)
+            {
+                Some(earned_commission) => earned_commission,
+                None => continue,
+            };
+
+            let unit_commission_in_cents = earned_commission.amount_in_cents / earned_commission.quantity;
+            commissions.push(
+                NewAffiliateCommission {
+                    refund_id: Some(refund.id),
+                    quantity: -refund_item.quantity,
+                    amount_in_cents: -unit_commission_in_cents * refund_item.quantity,
+                    ..earned_commission.clone().into()
+                }
+                .commit(conn)?,
+            );
+        }
+
+        Ok(commissions)
+    }
+}
+
+impl From<AffiliateCommission> for NewAffiliateCommission {
+    fn from(commission: AffiliateCommission) -> Self {
+        NewAffiliateCommission {
+            affiliate_link_id: commission.affiliate_link_id,
+            order_id: commission.order_id,
+            order_item_id: commission.order_item_id,
+            event_id: commission.event_id,
+            refund_id: commission.refund_id,
+            quantity: commission.quantity,
+            amount_in_cents: commission.amount_in_cents,
+            currency: commission.currency,
+        }
+    }
+}
+
+impl NewAffiliateCommission {
+    pub fn commit(self, conn: &PgConnection) -> Result<AffiliateCommission, DatabaseError> {
+        diesel::insert_into(affiliate_commissions::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not record affiliate commission")
+    }
+}
@@ -0,0 +1,251 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::affiliate_links;
+use utils::errors::*;
+use utils::rand::random_alpha_string;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+use validators;
+
+/// Query string parameter affiliate links are shared with, e.g. `?aff=K3J9TQ2ZMA`
+pub const AFFILIATE_CODE_PARAM: &str = "aff";
+const AFFILIATE_CODE_LENGTH: usize = 10;
+
+/// Trackable link a promoter shares to earn commission on the organization's sales. Links limited
+/// to an event only earn commission on tickets for that event.
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "affiliate_links"]
+pub struct AffiliateLink {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub event_id: Option<Uuid>,
+    pub name: String,
+    pub code: String,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub visit_count: i64,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "affiliate_links"]
+pub struct NewAffiliateLink {
+    #[serde(default)]
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub event_id: Option<Uuid>,
+    #[validate(length(min = 1, max = 255, message = "Name cannot be blank"))]
+    pub name: String,
+    #[serde(skip_deserializing)]
+    pub code: String,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "affiliate_links"]
+pub struct AffiliateLinkEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, max = 255, message = "Name cannot be blank"))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub event_id: Option<Option<Uuid>>,
+}
+
+impl AffiliateLink {
+    pub fn create(organization_id: Uuid, user_id: Uuid, event_id: Option<Uuid>, name: String) -> NewAffiliateLink {
+        NewAffiliateLink {
+            organization_id,
+            user_id,
+            event_id,
+            name,
+            code: String::new(),
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<AffiliateLink, DatabaseError> {
+        affiliate_links::table
+            .filter(affiliate_links::id.eq(id))
+            .filter(affiliate_links::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load affiliate link")
+    }
+
+    pub fn find_by_code(code: &str, conn: &PgConnection) -> Result<AffiliateLink, DatabaseError> {
+        affiliate_links::table
+            .filter(affiliate_links::code.eq(code.trim().to_uppercase()))
+            .filter(affiliate_links::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load affiliate link")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        page: u32,
+        limit: u32,
+        conn: &PgConnection,
+    ) -> Result<Payload<AffiliateLink>, DatabaseError> {
+        use utils::pagination::Paginate;
+
+        let (affiliate_links, total) = affiliate_links::table
+            .filter(affiliate_links::organization_id.eq(organization_id))
+            .filter(affiliate_links::deleted_at.is_null())
+            .order_by(affiliate_links::name.asc())
+            .paginate(page as i64)
+            .per_page(limit as i64)
+            .load_and_count_pages(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load affiliate links for organization")?;
+
+        Ok(Payload::from_data(affiliate_links, page, limit, Some(total as u64)))
+    }
+
+    pub fn find_for_user(user_id: Uuid, conn: &PgConnection) -> Result<Vec<AffiliateLink>, DatabaseError> {
+        affiliate_links::table
+            .filter(affiliate_links::user_id.eq(user_id))
+            .filter(affiliate_links::deleted_at.is_null())
+            .order_by(affiliate_links::name.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load affiliate links for user")
+    }
+
+    pub fn organization(&self, conn: &PgConnection) -> Result<Organization, DatabaseError> {
+        Organization::find(self.organization_id, conn)
+    }
+
+    pub fn commission_rules(&self, conn: &PgConnection) -> Result<Vec<AffiliateCommissionRule>, DatabaseError> {
+        AffiliateCommissionRule::find_for_affiliate_link(self.id, conn)
+    }
+
+    /// Link to share, pointing at the event when the link is limited to one
+    pub fn url(&self, front_end_url: &str) -> String {
+        match self.event_id {
+            Some(event_id) => format!(
+                "{}/events/{}?{}={}",
+                front_end_url, event_id, AFFILIATE_CODE_PARAM, self.code
+            ),
+            None => format!("{}?{}={}", front_end_url, AFFILIATE_CODE_PARAM, self.code),
+        }
+    }
+
+    /// Whether tickets for the event earn commission through this link
+    pub fn applies_to_event(&self, event: &Event) -> bool {
+        event.organization_id == self.organization_id && self.event_id.map(|id| id == event.id).unwrap_or(true)
+    }
+
+    /// Counts a visit to the event page through the link
+    pub fn record_visit(&self, conn: &PgConnection) -> Result<AffiliateLink, DatabaseError> {
+        diesel::update(self)
+            .set((
+                affiliate_links::visit_count.eq(affiliate_links::visit_count + 1),
+                affiliate_links::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not record affiliate link visit")
+    }
+
+    pub fn update(
+        &self,
+        attributes: AffiliateLinkEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<AffiliateLink, DatabaseError> {
+        AffiliateLink::validate_record(
+            attributes.validate(),
+            self.organization_id,
+            attributes.event_id.unwrap_or(self.event_id),
+            conn,
+        )?;
+
+        let affiliate_link: AffiliateLink = diesel::update(self)
+            .set((attributes, affiliate_links::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update affiliate link")?;
+
+        DomainEvent::create(
+            DomainEventTypes::AffiliateLinkUpdated,
+            format!("Affiliate link '{}' updated", &affiliate_link.name),
+            Tables::AffiliateLinks,
+            Some(affiliate_link.id),
+            current_user_id,
+            Some(json!(&affiliate_link)),
+        )
+        .commit(conn)?;
+
+        Ok(affiliate_link)
+    }
+
+    /// Removes the link, orders already attributed to it keep earning commission
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::AffiliateLinkDeleted,
+            format!("Affiliate link '{}' deleted", &self.name),
+            Tables::AffiliateLinks,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((
+                affiliate_links::deleted_at.eq(dsl::now),
+                affiliate_links::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete affiliate link")?;
+
+        Ok(())
+    }
+
+    fn validate_record(
+        validation_errors: Result<(), ValidationErrors>,
+        organization_id: Uuid,
+        event_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        let mut validation_errors = validation_errors;
+        if let Some(event_id) = event_id {
+            if Event::find(event_id, conn)?.organization_id != organization_id {
+                validation_errors = validators::append_validation_error(
+                    validation_errors,
+                    "event_id",
+                    Err(validators::create_validation_error(
+                        "invalid_event",
+                        "Event must belong to the affiliate link's organization",
+                    )),
+                );
+            }
+        }
+        Ok(validation_errors?)
+    }
+}
+
+impl NewAffiliateLink {
+    pub fn commit(
+        mut self,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<AffiliateLink, DatabaseError> {
+        AffiliateLink::validate_record(self.validate(), self.organization_id, self.event_id, conn)?;
+        self.code = random_alpha_string(AFFILIATE_CODE_LENGTH).to_uppercase();
+
+        let affiliate_link: AffiliateLink = diesel::insert_into(affiliate_links::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create affiliate link")?;
+
+        DomainEvent::create(
+            DomainEventTypes::AffiliateLinkCreated,
+            format!("Affiliate link '{}' created", &affiliate_link.name),
+            Tables::AffiliateLinks,
+            Some(affiliate_link.id),
+            current_user_id,
+            Some(json!(&affiliate_link)),
+        )
+        .commit(conn)?;
+
+        Ok(affiliate_link)
+    }
+}
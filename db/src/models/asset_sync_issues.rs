@@ -0,0 +1,120 @@
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::prelude::*;
+use models::*;
+use schema::{asset_sync_issues, assets};
+use utils::errors::*;
+use uuid::Uuid;
+
+/// Difference found between the database and the blockchain when reconciling an asset
+#[derive(Clone, Debug, Deserialize, Identifiable, PartialEq, Queryable, Serialize)]
+pub struct AssetSyncIssue {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    pub ticket_instance_id: Option<Uuid>,
+    pub token_id: Option<i64>,
+    pub issue_type: AssetSyncIssueTypes,
+    pub database_value: Option<String>,
+    pub blockchain_value: Option<String>,
+    pub repaired: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable)]
+#[table_name = "asset_sync_issues"]
+pub struct NewAssetSyncIssue {
+    pub asset_id: Uuid,
+    pub ticket_instance_id: Option<Uuid>,
+    pub token_id: Option<i64>,
+    pub issue_type: AssetSyncIssueTypes,
+    pub database_value: Option<String>,
+    pub blockchain_value: Option<String>,
+    pub repaired: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AssetSyncReport {
+    pub asset_id: Uuid,
+    pub ticket_type_id: Uuid,
+    pub blockchain_asset_id: Option<String>,
+    pub status: AssetStatus,
+    pub last_synced_at: Option<NaiveDateTime>,
+    pub sync_error: Option<String>,
+    pub issues: Vec<AssetSyncIssue>,
+}
+
+impl AssetSyncIssue {
+    pub fn create(
+        asset_id: Uuid,
+        ticket_instance_id: Option<Uuid>,
+        token_id: Option<i64>,
+        issue_type: AssetSyncIssueTypes,
+        database_value: Option<String>,
+        blockchain_value: Option<String>,
+        repaired: bool,
+    ) -> NewAssetSyncIssue {
+        NewAssetSyncIssue {
+            asset_id,
+            ticket_instance_id,
+            token_id,
+            issue_type,
+            database_value,
+            blockchain_value,
+            repaired,
+        }
+    }
+
+    pub fn find_for_asset(asset_id: Uuid, conn: &PgConnection) -> Result<Vec<AssetSyncIssue>, DatabaseError> {
+        asset_sync_issues::table
+            .filter(asset_sync_issues::asset_id.eq(asset_id))
+            .order_by(asset_sync_issues::token_id.asc())
+            .then_order_by(asset_sync_issues::issue_type.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load asset sync issues")
+    }
+
+    /// Issues from an earlier reconciliation are replaced by the results of the next one
+    pub fn clear_for_asset(asset_id: Uuid, conn: &PgConnection) -> Result<(), DatabaseError> {
+        diesel::delete(asset_sync_issues::table.filter(asset_sync_issues::asset_id.eq(asset_id)))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not clear asset sync issues")?;
+        Ok(())
+    }
+
+    /// Reconciliation state of every asset on the blockchain with the given status, along with
+    /// the issues found the last time it was reconciled
+    pub fn report(status: AssetStatus, conn: &PgConnection) -> Result<Vec<AssetSyncReport>, DatabaseError> {
+        let assets: Vec<Asset> = assets::table
+            .filter(assets::blockchain_asset_id.is_not_null())
+            .filter(assets::status.eq(status))
+            .order_by(assets::last_synced_at.desc())
+            .then_order_by(assets::id.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load assets")?;
+
+        let mut report = Vec::new();
+        for asset in assets {
+            let issues = AssetSyncIssue::find_for_asset(asset.id, conn)?;
+            report.push(AssetSyncReport {
+                asset_id: asset.id,
+                ticket_type_id: asset.ticket_type_id,
+                blockchain_asset_id: asset.blockchain_asset_id,
+                status: asset.status,
+                last_synced_at: asset.last_synced_at,
+                sync_error: asset.sync_error,
+                issues,
+            });
+        }
+        Ok(report)
+    }
+}
+
+impl NewAssetSyncIssue {
+    pub fn commit(self, conn: &PgConnection) -> Result<AssetSyncIssue, DatabaseError> {
+        diesel::insert_into(asset_sync_issues::table)
+            .values(self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create asset sync issue")
+    }
+}
@@ -1,9 +1,13 @@
 use chrono::prelude::*;
+use chrono::Duration;
 use diesel;
 use diesel::expression::dsl;
 use diesel::prelude::*;
+use log::Level::Warn;
 use models::*;
-use schema::assets;
+use schema::{assets, ticket_instances, wallets};
+use std::collections::HashMap;
+use tari_client::*;
 use utils::errors::ConvertToDatabaseError;
 use utils::errors::DatabaseError;
 use utils::errors::ErrorCode;
@@ -13,13 +17,15 @@ use uuid::Uuid;
 #[table_name = "assets"]
 pub struct Asset {
     pub id: Uuid,
-    ticket_type_id: Uuid,
+    pub ticket_type_id: Uuid,
     blockchain_name: String,
     // TODO: This will be populated after it is created on the blockchain.
     pub blockchain_asset_id: Option<String>,
-    status: AssetStatus,
+    pub status: AssetStatus,
     created_at: NaiveDateTime,
     updated_at: NaiveDateTime,
+    pub last_synced_at: Option<NaiveDateTime>,
+    pub sync_error: Option<String>,
 }
 
 impl Asset {
@@ -53,6 +59,280 @@ impl Asset {
             .get_result(conn)
             .to_db_error(ErrorCode::UpdateError, "Could not update asset blockchain id")
     }
+
+    /// Assets that have been created on the blockchain and can be reconciled
+    pub fn find_on_blockchain(conn: &PgConnection) -> Result<Vec<Asset>, DatabaseError> {
+        assets::table
+            .filter(assets::blockchain_asset_id.is_not_null())
+            .order_by(assets::created_at.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Error loading assets")
+    }
+
+    /// Assets move to Syncing when a reconciliation starts and to Synced or Error when it finishes.
+    /// A reconciliation that was interrupted can be restarted from Syncing.
+    pub fn update_sync_status(
+        &self,
+        status: AssetStatus,
+        sync_error: Option<String>,
+        conn: &PgConnection,
+    ) -> Result<Asset, DatabaseError> {
+        let valid_transition = match (self.status, status) {
+            (_, AssetStatus::Syncing) => true,
+            (AssetStatus::Syncing, AssetStatus::Synced) | (AssetStatus::Syncing, AssetStatus::Error) => true,
+            _ => false,
+        };
+        if !valid_transition {
+            return DatabaseError::business_process_error(&format!(
+                "Asset cannot move from {} to {}",
+                self.status, status
+            ));
+        }
+
+        let last_synced_at = if status == AssetStatus::Syncing {
+            self.last_synced_at
+        } else {
+            Some(Utc::now().naive_utc())
+        };
+        diesel::update(self)
+            .set((
+                assets::status.eq(status),
+                assets::sync_error.eq(sync_error),
+                assets::last_synced_at.eq(last_synced_at),
+                assets::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update asset sync status")
+    }
+
+    /// Compares the ownership, redemption and nullification of every ticket with its token on the
+    /// blockchain, recording the differences as sync issues. When `repair` is set, differences where
+    /// the database is ahead of the blockchain are corrected on the blockchain. Differences where the
+    /// blockchain is ahead are only flagged as they need to be investigated.
+    pub fn reconcile(
+        &self,
+        tari_client: &dyn TariClient,
+        repair: bool,
+        conn: &PgConnection,
+    ) -> Result<Asset, DatabaseError> {
+        let blockchain_asset_id = match self.blockchain_asset_id.clone() {
+            Some(blockchain_asset_id) => blockchain_asset_id,
+            None => return DatabaseError::business_process_error("Asset has not been created on the blockchain"),
+        };
+
+        let asset = self.update_sync_status(AssetStatus::Syncing, None, conn)?;
+        AssetSyncIssue::clear_for_asset(asset.id, conn)?;
+        match asset.compare_with_blockchain(&blockchain_asset_id, tari_client, repair, conn) {
+            Ok(issues) => {
+                let unresolved = issues.iter().filter(|i| !i.repaired).count();
+                if unresolved == 0 {
+                    asset.update_sync_status(AssetStatus::Synced, None, conn)
+                } else {
+                    asset.update_sync_status(
+                        AssetStatus::Error,
+                        Some(format!("{} unresolved differences with the blockchain", unresolved)),
+                        conn,
+                    )
+                }
+            }
+            Err(error) => asset.update_sync_status(AssetStatus::Error, Some(error.to_string()), conn),
+        }
+    }
+
+    fn compare_with_blockchain(
+        &self,
+        blockchain_asset_id: &String,
+        tari_client: &dyn TariClient,
+        repair: bool,
+        conn: &PgConnection,
+    ) -> Result<Vec<AssetSyncIssue>, DatabaseError> {
+        let organization = Organization::find_by_asset_id(self.id, conn)?;
+        let issuer = Wallet::find_default_for_organization(organization.id, conn)?;
+        let tickets: Vec<TicketInstance> = ticket_instances::table
+            .filter(ticket_instances::asset_id.eq(self.id))
+            .order_by(ticket_instances::token_id.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load tickets for asset")?;
+        let wallets: HashMap<Uuid, Wallet> = wallets::table
+            .filter(wallets::id.eq_any(tickets.iter().map(|t| t.wallet_id).collect::<Vec<Uuid>>()))
+            .load::<Wallet>(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load wallets for asset")?
+            .into_iter()
+            .map(|w| (w.id, w))
+            .collect();
+
+        let mut issues = Vec::new();
+        let asset_info = tari_client.get_asset_info(&issuer.secret_key, &issuer.public_key, blockchain_asset_id)?;
+        let supply = tickets.len() as u64;
+        if asset_info.total_supply != supply {
+            // Supply can only be increased on the blockchain
+            let repaired = repair
+                && asset_info.total_supply < supply
+                && repair_succeeded(
+                    self.id,
+                    tari_client.modify_asset_increase_supply(
+                        &issuer.secret_key,
+                        &issuer.public_key,
+                        blockchain_asset_id,
+                        supply,
+                    ),
+                );
+            issues.push(AssetSyncIssue::create(
+                self.id,
+                None,
+                None,
+                AssetSyncIssueTypes::SupplyMismatch,
+                Some(supply.to_string()),
+                Some(asset_info.total_supply.to_string()),
+                repaired,
+            ));
+        }
+
+        let tokens: HashMap<u64, Token> = tari_client
+            .get_tokens(&issuer.secret_key, &issuer.public_key, blockchain_asset_id)?
+            .into_iter()
+            .map(|t| (t.id, t))
+            .collect();
+        for ticket in &tickets {
+            let token_id = ticket.token_id as u64;
+            let wallet = match wallets.get(&ticket.wallet_id) {
+                Some(wallet) => wallet,
+                None => return DatabaseError::business_process_error("Could not load wallet for ticket"),
+            };
+            let token = match tokens.get(&token_id) {
+                Some(token) => token,
+                None => {
+                    issues.push(AssetSyncIssue::create(
+                        self.id,
+                        Some(ticket.id),
+                        Some(token_id as i64),
+                        AssetSyncIssueTypes::MissingToken,
+                        Some(wallet.public_key.clone()),
+                        None,
+                        false,
+                    ));
+                    continue;
+                }
+            };
+
+            let mut owner_matches = token.owner == wallet.public_key;
+            if !owner_matches {
+                let mut repaired_owner = false;
+                if repair {
+                    if let Some(chain_owner) = Wallet::find_by_public_key(&token.owner, conn)? {
+                        repaired_owner = repair_succeeded(
+                            self.id,
+                            tari_client.transfer_tokens(
+                                &chain_owner.secret_key,
+                                &chain_owner.public_key,
+                                blockchain_asset_id,
+                                vec![token_id],
+                                wallet.public_key.clone(),
+                            ),
+                        );
+                    }
+                }
+                owner_matches = repaired_owner;
+                issues.push(AssetSyncIssue::create(
+                    self.id,
+                    Some(ticket.id),
+                    Some(token_id as i64),
+                    AssetSyncIssueTypes::OwnerMismatch,
+                    Some(wallet.public_key.clone()),
+                    Some(token.owner.clone()),
+                    repaired_owner,
+                ));
+            }
+
+            let redeemed = ticket.status == TicketInstanceStatus::Redeemed;
+            if redeemed != token.used {
+                let repaired = repair
+                    && redeemed
+                    && owner_matches
+                    && repair_succeeded(
+                        self.id,
+                        tari_client.modify_asset_redeem_token(
+                            &wallet.secret_key,
+                            &wallet.public_key,
+                            blockchain_asset_id,
+                            vec![token_id],
+                        ),
+                    );
+                issues.push(AssetSyncIssue::create(
+                    self.id,
+                    Some(ticket.id),
+                    Some(token_id as i64),
+                    AssetSyncIssueTypes::RedeemedMismatch,
+                    Some(redeemed.to_string()),
+                    Some(token.used.to_string()),
+                    repaired,
+                ));
+            }
+
+            let nullified = ticket.status == TicketInstanceStatus::Nullified;
+            if nullified == token.valid {
+                // Nullified tickets are held by the organization so the issuer can nullify the token
+                let repaired = repair
+                    && nullified
+                    && owner_matches
+                    && repair_succeeded(
+                        self.id,
+                        tari_client.modify_asset_nullify_tokens(
+                            &issuer.secret_key,
+                            &issuer.public_key,
+                            blockchain_asset_id,
+                            vec![token_id],
+                        ),
+                    );
+                issues.push(AssetSyncIssue::create(
+                    self.id,
+                    Some(ticket.id),
+                    Some(token_id as i64),
+                    AssetSyncIssueTypes::NullifiedMismatch,
+                    Some(nullified.to_string()),
+                    Some((!token.valid).to_string()),
+                    repaired,
+                ));
+            }
+        }
+
+        issues.into_iter().map(|issue| issue.commit(conn)).collect()
+    }
+
+    pub fn create_next_reconcile_assets_domain_action(conn: &PgConnection) -> Result<(), DatabaseError> {
+        let now = Utc::now().naive_utc();
+        if let Some(upcoming_domain_action) =
+            DomainAction::upcoming_domain_action(None, None, DomainActionTypes::ReconcileAssets, conn)?
+        {
+            if upcoming_domain_action.scheduled_at > now {
+                return DatabaseError::business_process_error("Reconcile assets domain action is already pending");
+            }
+        }
+
+        let mut action = DomainAction::create(
+            None,
+            DomainActionTypes::ReconcileAssets,
+            None,
+            json!({ "repair": false }),
+            None,
+            None,
+        );
+        action.schedule_at(now + Duration::days(1));
+        action.commit(conn)?;
+
+        Ok(())
+    }
+}
+
+/// Repairs that fail are left as unresolved issues rather than stopping the reconciliation
+fn repair_succeeded(asset_id: Uuid, result: Result<(), TariError>) -> bool {
+    match result {
+        Ok(_) => true,
+        Err(error) => {
+            jlog!(Warn, "Could not repair asset on the blockchain", {"asset_id": asset_id, "error": error.to_string()});
+            false
+        }
+    }
 }
 
 #[derive(Insertable)]
@@ -28,6 +28,7 @@ pub struct NewBroadcast {
     pub subject: Option<String>,
     pub audience: BroadcastAudience,
     pub preview_email: Option<String>,
+    pub fan_segment_id: Option<Uuid>,
 }
 
 #[derive(Queryable, Identifiable, Insertable, Serialize, Deserialize, PartialEq, Debug)]
@@ -49,6 +50,7 @@ pub struct Broadcast {
     pub subject: Option<String>,
     pub audience: BroadcastAudience,
     pub preview_email: Option<String>,
+    pub fan_segment_id: Option<Uuid>,
 }
 
 #[derive(AsChangeset, Default, Deserialize, Debug)]
@@ -95,6 +97,7 @@ impl Broadcast {
             subject,
             audience,
             preview_email,
+            fan_segment_id: None,
         }
     }
 
@@ -266,6 +269,36 @@ impl Broadcast {
         }
     }
 
+    fn fan_segment_valid_for_audience(
+        event_id: Uuid,
+        audience: BroadcastAudience,
+        fan_segment_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Result<(), ValidationError>, DatabaseError> {
+        match (audience, fan_segment_id) {
+            (BroadcastAudience::FanSegment, Some(fan_segment_id)) => {
+                let fan_segment = FanSegment::find(fan_segment_id, conn)?;
+                let organization = Organization::find_for_event(event_id, conn)?;
+                if fan_segment.organization_id != organization.id {
+                    return Ok(Err(create_validation_error(
+                        "fan_segment_invalid_organization",
+                        "Fan segment must belong to the event's organization",
+                    )));
+                }
+                Ok(Ok(()))
+            }
+            (BroadcastAudience::FanSegment, None) => Ok(Err(create_validation_error(
+                "fan_segment_required",
+                "A fan segment is required when broadcasting to a fan segment audience",
+            ))),
+            (_, Some(_)) => Ok(Err(create_validation_error(
+                "fan_segment_not_allowed",
+                "A fan segment can only be used with the fan segment audience",
+            ))),
+            (_, None) => Ok(Ok(())),
+        }
+    }
+
     fn send_at_has_not_passed(
         send_at: Option<NaiveDateTime>,
         new_send_at: &Option<NaiveDateTime>,
@@ -332,11 +365,16 @@ impl NewBroadcast {
     }
 
     pub fn validate_record(&self, conn: &PgConnection) -> Result<(), DatabaseError> {
-        let validation_errors = validators::append_validation_error(
+        let mut validation_errors = validators::append_validation_error(
             Ok(()),
             "message",
             Broadcast::custom_type_has_message(self.notification_type.clone(), self.message.clone(), conn)?,
         );
+        validation_errors = validators::append_validation_error(
+            validation_errors,
+            "fan_segment_id",
+            Broadcast::fan_segment_valid_for_audience(self.event_id, self.audience, self.fan_segment_id, conn)?,
+        );
         Ok(validation_errors?)
     }
 }
@@ -0,0 +1,366 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable, Text, Uuid as dUuid};
+use models::*;
+use schema::{cash_drawer_movements, cash_drawer_sessions};
+use utils::errors::*;
+use utils::pagination::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "cash_drawer_sessions"]
+pub struct CashDrawerSession {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub status: CashDrawerSessionStatus,
+    pub opening_float_in_cents: i64,
+    pub expected_cash_in_cents: Option<i64>,
+    pub counted_cash_in_cents: Option<i64>,
+    pub closing_notes: Option<String>,
+    pub closed_by_user_id: Option<Uuid>,
+    pub closed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name = "cash_drawer_sessions"]
+pub struct NewCashDrawerSession {
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub opening_float_in_cents: i64,
+}
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "cash_drawer_movements"]
+pub struct CashDrawerMovement {
+    pub id: Uuid,
+    pub cash_drawer_session_id: Uuid,
+    pub movement_type: CashDrawerMovementTypes,
+    pub amount_in_cents: i64,
+    pub reason: Option<String>,
+    pub created_by_user_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "cash_drawer_movements"]
+struct NewCashDrawerMovement {
+    cash_drawer_session_id: Uuid,
+    movement_type: CashDrawerMovementTypes,
+    amount_in_cents: i64,
+    reason: Option<String>,
+    created_by_user_id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, QueryableByName, Serialize)]
+pub struct CashDrawerSessionPaymentTotal {
+    #[sql_type = "Nullable<Text>"]
+    pub external_payment_type: Option<ExternalPaymentType>,
+    #[sql_type = "BigInt"]
+    pub payment_count: i64,
+    #[sql_type = "BigInt"]
+    pub sales_in_cents: i64,
+    #[sql_type = "BigInt"]
+    pub refunds_in_cents: i64,
+}
+
+/// Shift report for a drawer, totals are live while the drawer is open
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayCashDrawerSession {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub operator_name: String,
+    pub status: CashDrawerSessionStatus,
+    pub opening_float_in_cents: i64,
+    pub payments: Vec<CashDrawerSessionPaymentTotal>,
+    pub movements: Vec<CashDrawerMovement>,
+    pub cash_sales_in_cents: i64,
+    pub cash_refunds_in_cents: i64,
+    pub payouts_in_cents: i64,
+    pub drops_in_cents: i64,
+    pub expected_cash_in_cents: i64,
+    pub counted_cash_in_cents: Option<i64>,
+    pub variance_in_cents: Option<i64>,
+    pub closing_notes: Option<String>,
+    pub closed_by_user_id: Option<Uuid>,
+    pub closed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl CashDrawerSession {
+    pub fn create(organization_id: Uuid, user_id: Uuid, opening_float_in_cents: i64) -> NewCashDrawerSession {
+        NewCashDrawerSession {
+            organization_id,
+            user_id,
+            opening_float_in_cents,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<CashDrawerSession, DatabaseError> {
+        cash_drawer_sessions::table
+            .find(id)
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load cash drawer session")
+    }
+
+    pub fn find_open_for_user(user_id: Uuid, conn: &PgConnection) -> Result<Option<CashDrawerSession>, DatabaseError> {
+        cash_drawer_sessions::table
+            .filter(cash_drawer_sessions::user_id.eq(user_id))
+            .filter(cash_drawer_sessions::status.eq(CashDrawerSessionStatus::Open))
+            .first::<CashDrawerSession>(conn)
+            .optional()
+            .to_db_error(ErrorCode::QueryError, "Unable to load open cash drawer session")
+    }
+
+    /// Drawers opened for the organization, most recent first
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        user_id: Option<Uuid>,
+        status: Option<CashDrawerSessionStatus>,
+        page: u32,
+        limit: u32,
+        conn: &PgConnection,
+    ) -> Result<Payload<CashDrawerSession>, DatabaseError> {
+        let mut query = cash_drawer_sessions::table
+            .filter(cash_drawer_sessions::organization_id.eq(organization_id))
+            .into_boxed();
+        if let Some(user_id) = user_id {
+            query = query.filter(cash_drawer_sessions::user_id.eq(user_id));
+        }
+        if let Some(status) = status {
+            query = query.filter(cash_drawer_sessions::status.eq(status));
+        }
+
+        let (sessions, record_count): (Vec<CashDrawerSession>, i64) = query
+            .order_by(cash_drawer_sessions::created_at.desc())
+            .paginate(page as i64)
+            .per_page(limit as i64)
+            .load_and_count_pages(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load cash drawer sessions")?;
+
+        Ok(Payload::from_data(sessions, page, limit, Some(record_count as u64)))
+    }
+
+    /// Drawers opened for the organization within the period, oldest first
+    pub fn find_for_organization_in_period(
+        organization_id: Uuid,
+        start: Option<NaiveDateTime>,
+        end: Option<NaiveDateTime>,
+        conn: &PgConnection,
+    ) -> Result<Vec<CashDrawerSession>, DatabaseError> {
+        let mut query = cash_drawer_sessions::table
+            .filter(cash_drawer_sessions::organization_id.eq(organization_id))
+            .into_boxed();
+        if let Some(start) = start {
+            query = query.filter(cash_drawer_sessions::created_at.ge(start));
+        }
+        if let Some(end) = end {
+            query = query.filter(cash_drawer_sessions::created_at.le(end));
+        }
+
+        query
+            .order_by(cash_drawer_sessions::created_at.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load cash drawer sessions")
+    }
+
+    pub fn organization(&self, conn: &PgConnection) -> Result<Organization, DatabaseError> {
+        Organization::find(self.organization_id, conn)
+    }
+
+    pub fn movements(&self, conn: &PgConnection) -> Result<Vec<CashDrawerMovement>, DatabaseError> {
+        cash_drawer_movements::table
+            .filter(cash_drawer_movements::cash_drawer_session_id.eq(self.id))
+            .order_by(cash_drawer_movements::created_at.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load cash drawer movements")
+    }
+
+    /// Records cash leaving the drawer, either paid out or dropped into the safe
+    pub fn add_movement(
+        &self,
+        movement_type: CashDrawerMovementTypes,
+        amount_in_cents: i64,
+        reason: Option<String>,
+        current_user_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<CashDrawerMovement, DatabaseError> {
+        if self.status != CashDrawerSessionStatus::Open {
+            return DatabaseError::business_process_error("Cash drawer session is closed");
+        }
+        if amount_in_cents <= 0 {
+            return DatabaseError::validation_error("amount_in_cents", "Amount must be greater than 0");
+        }
+
+        let movement: CashDrawerMovement = diesel::insert_into(cash_drawer_movements::table)
+            .values(&NewCashDrawerMovement {
+                cash_drawer_session_id: self.id,
+                movement_type,
+                amount_in_cents,
+                reason,
+                created_by_user_id: current_user_id,
+            })
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create cash drawer movement")?;
+
+        DomainEvent::create(
+            DomainEventTypes::CashDrawerMovementCreated,
+            format!("Cash drawer {} recorded", movement_type),
+            Tables::CashDrawerSessions,
+            Some(self.id),
+            Some(current_user_id),
+            Some(json!(&movement)),
+        )
+        .commit(conn)?;
+
+        Ok(movement)
+    }
+
+    pub fn payment_totals(&self, conn: &PgConnection) -> Result<Vec<CashDrawerSessionPaymentTotal>, DatabaseError> {
+        let query = r#"
+            SELECT
+                o.external_payment_type,
+                CAST(COUNT(p.id) FILTER (WHERE p.refund_id IS NULL) AS BIGINT) AS payment_count,
+                CAST(COALESCE(SUM(p.amount) FILTER (WHERE p.refund_id IS NULL), 0) AS BIGINT) AS sales_in_cents,
+                CAST(COALESCE(-SUM(p.amount) FILTER (WHERE p.refund_id IS NOT NULL), 0) AS BIGINT) AS refunds_in_cents
+            FROM payments p
+            JOIN orders o ON o.id = p.order_id
+            WHERE p.cash_drawer_session_id = $1
+            AND p.status IN ('Completed', 'Refunded')
+            GROUP BY o.external_payment_type
+            ORDER BY o.external_payment_type;"#;
+
+        diesel::sql_query(query)
+            .bind::<dUuid, _>(self.id)
+            .get_results(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load cash drawer payment totals")
+    }
+
+    pub fn for_display(&self, conn: &PgConnection) -> Result<DisplayCashDrawerSession, DatabaseError> {
+        let payments = self.payment_totals(conn)?;
+        let movements = self.movements(conn)?;
+        let cash_totals = payments
+            .iter()
+            .find(|p| p.external_payment_type == Some(ExternalPaymentType::Cash));
+        let cash_sales_in_cents = cash_totals.map(|p| p.sales_in_cents).unwrap_or(0);
+        let cash_refunds_in_cents = cash_totals.map(|p| p.refunds_in_cents).unwrap_or(0);
+        let movement_total = |movement_type: CashDrawerMovementTypes| -> i64 {
+            movements
+                .iter()
+                .filter(|m| m.movement_type == movement_type)
+                .map(|m| m.amount_in_cents)
+                .sum()
+        };
+        let payouts_in_cents = movement_total(CashDrawerMovementTypes::Payout);
+        let drops_in_cents = movement_total(CashDrawerMovementTypes::Drop);
+        // Closed drawers report the expected amount captured at close-out
+        let expected_cash_in_cents = self.expected_cash_in_cents.unwrap_or(
+            self.opening_float_in_cents + cash_sales_in_cents
+                - cash_refunds_in_cents
+                - payouts_in_cents
+                - drops_in_cents,
+        );
+
+        Ok(DisplayCashDrawerSession {
+            id: self.id,
+            organization_id: self.organization_id,
+            user_id: self.user_id,
+            operator_name: User::find(self.user_id, conn)?.full_name(),
+            status: self.status,
+            opening_float_in_cents: self.opening_float_in_cents,
+            payments,
+            movements,
+            cash_sales_in_cents,
+            cash_refunds_in_cents,
+            payouts_in_cents,
+            drops_in_cents,
+            expected_cash_in_cents,
+            counted_cash_in_cents: self.counted_cash_in_cents,
+            variance_in_cents: self.counted_cash_in_cents.map(|c| c - expected_cash_in_cents),
+            closing_notes: self.closing_notes.clone(),
+            closed_by_user_id: self.closed_by_user_id,
+            closed_at: self.closed_at,
+            created_at: self.created_at,
+        })
+    }
+
+    /// Closes out the drawer recording the counted cash against what the drawer should hold
+    pub fn close(
+        &self,
+        counted_cash_in_cents: i64,
+        closing_notes: Option<String>,
+        current_user_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<CashDrawerSession, DatabaseError> {
+        if self.status != CashDrawerSessionStatus::Open {
+            return DatabaseError::business_process_error("Cash drawer session is already closed");
+        }
+        if counted_cash_in_cents < 0 {
+            return DatabaseError::validation_error("counted_cash_in_cents", "Counted cash cannot be negative");
+        }
+
+        let expected_cash_in_cents = self.for_display(conn)?.expected_cash_in_cents;
+        let session: CashDrawerSession = diesel::update(self)
+            .set((
+                cash_drawer_sessions::status.eq(CashDrawerSessionStatus::Closed),
+                cash_drawer_sessions::expected_cash_in_cents.eq(expected_cash_in_cents),
+                cash_drawer_sessions::counted_cash_in_cents.eq(counted_cash_in_cents),
+                cash_drawer_sessions::closing_notes.eq(closing_notes),
+                cash_drawer_sessions::closed_by_user_id.eq(current_user_id),
+                cash_drawer_sessions::closed_at.eq(dsl::now.nullable()),
+                cash_drawer_sessions::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not close cash drawer session")?;
+
+        DomainEvent::create(
+            DomainEventTypes::CashDrawerSessionClosed,
+            "Cash drawer session closed".to_string(),
+            Tables::CashDrawerSessions,
+            Some(session.id),
+            Some(current_user_id),
+            Some(json!({
+                "expected_cash_in_cents": expected_cash_in_cents,
+                "counted_cash_in_cents": counted_cash_in_cents,
+                "variance_in_cents": counted_cash_in_cents - expected_cash_in_cents,
+            })),
+        )
+        .commit(conn)?;
+
+        Ok(session)
+    }
+}
+
+impl NewCashDrawerSession {
+    pub fn commit(self, conn: &PgConnection) -> Result<CashDrawerSession, DatabaseError> {
+        if self.opening_float_in_cents < 0 {
+            return DatabaseError::validation_error("opening_float_in_cents", "Opening float cannot be negative");
+        }
+        if CashDrawerSession::find_open_for_user(self.user_id, conn)?.is_some() {
+            return DatabaseError::business_process_error("User already has an open cash drawer session");
+        }
+
+        let session: CashDrawerSession = diesel::insert_into(cash_drawer_sessions::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not open cash drawer session")?;
+
+        DomainEvent::create(
+            DomainEventTypes::CashDrawerSessionOpened,
+            "Cash drawer session opened".to_string(),
+            Tables::CashDrawerSessions,
+            Some(session.id),
+            Some(session.user_id),
+            Some(json!(&session)),
+        )
+        .commit(conn)?;
+
+        Ok(session)
+    }
+}
@@ -0,0 +1,302 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::{checkout_answers, checkout_questions, orders};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{ValidationError, ValidationErrors};
+use validators::{self, *};
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "checkout_answers"]
+pub struct CheckoutAnswer {
+    pub id: Uuid,
+    pub checkout_question_id: Uuid,
+    pub order_id: Uuid,
+    pub ticket_instance_id: Option<Uuid>,
+    pub answer: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "checkout_answers"]
+struct NewCheckoutAnswer {
+    checkout_question_id: Uuid,
+    order_id: Uuid,
+    ticket_instance_id: Option<Uuid>,
+    answer: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CheckoutAnswerAttributes {
+    pub checkout_question_id: Uuid,
+    #[serde(default)]
+    pub ticket_instance_id: Option<Uuid>,
+    pub answer: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Queryable, Serialize)]
+pub struct DisplayCheckoutAnswer {
+    pub order_id: Uuid,
+    pub ticket_instance_id: Option<Uuid>,
+    pub checkout_question_id: Uuid,
+    pub question: String,
+    pub answer: String,
+}
+
+impl CheckoutAnswer {
+    pub fn find_for_order(order_id: Uuid, conn: &PgConnection) -> Result<Vec<CheckoutAnswer>, DatabaseError> {
+        checkout_answers::table
+            .filter(checkout_answers::order_id.eq(order_id))
+            .order_by(checkout_answers::created_at.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load checkout answers")
+    }
+
+    pub fn find_display_for_orders(
+        order_ids: Vec<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Vec<DisplayCheckoutAnswer>, DatabaseError> {
+        checkout_answers::table
+            .inner_join(checkout_questions::table)
+            .filter(checkout_answers::order_id.eq_any(order_ids))
+            .filter(checkout_questions::deleted_at.is_null())
+            .order_by(checkout_answers::order_id)
+            .then_order_by(checkout_questions::rank.asc())
+            .then_order_by(checkout_questions::created_at.asc())
+            .select((
+                checkout_answers::order_id,
+                checkout_answers::ticket_instance_id,
+                checkout_answers::checkout_question_id,
+                checkout_questions::question,
+                checkout_answers::answer,
+            ))
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load checkout answers")
+    }
+
+    /// Answers given on paid orders for the event, used when exporting event data
+    pub fn find_display_for_event(
+        event_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<DisplayCheckoutAnswer>, DatabaseError> {
+        checkout_answers::table
+            .inner_join(checkout_questions::table)
+            .inner_join(orders::table)
+            .filter(checkout_questions::event_id.eq(event_id))
+            .filter(checkout_questions::deleted_at.is_null())
+            .filter(orders::status.eq(OrderStatus::Paid))
+            .order_by(checkout_answers::order_id)
+            .then_order_by(checkout_questions::rank.asc())
+            .then_order_by(checkout_questions::created_at.asc())
+            .select((
+                checkout_answers::order_id,
+                checkout_answers::ticket_instance_id,
+                checkout_answers::checkout_question_id,
+                checkout_questions::question,
+                checkout_answers::answer,
+            ))
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load checkout answers for event")
+    }
+
+    /// Creates or replaces answers for the order. Answers can be changed while the order
+    /// is still a cart and afterwards until each question's cutoff has passed.
+    pub fn save_for_order(
+        order: &Order,
+        answers: Vec<CheckoutAnswerAttributes>,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Vec<CheckoutAnswer>, DatabaseError> {
+        let ticket_types_by_instance = CheckoutAnswer::ticket_types_by_instance(order, conn)?;
+        let questions = CheckoutQuestion::find_for_order(order, conn)?;
+
+        let mut validation_errors: Result<(), ValidationErrors> = Ok(());
+        for attributes in &answers {
+            let question = questions.iter().find(|q| q.id == attributes.checkout_question_id);
+            let result = match question {
+                None => Err(create_validation_error(
+                    "question_not_in_order",
+                    "Question does not apply to this order",
+                )),
+                Some(question) => {
+                    if order.status != OrderStatus::Draft && !question.answers_editable(conn)? {
+                        Err(create_validation_error(
+                            "answers_locked",
+                            "Answers to this question can no longer be changed",
+                        ))
+                    } else {
+                        CheckoutAnswer::answer_target_valid(question, attributes, &ticket_types_by_instance)
+                            .and_then(|_| question.validate_answer(&attributes.answer))
+                    }
+                }
+            };
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "answers",
+                result.map_err(|mut e| {
+                    e.add_param(Cow::from("checkout_question_id"), &attributes.checkout_question_id);
+                    e.add_param(Cow::from("ticket_instance_id"), &attributes.ticket_instance_id);
+                    e
+                }),
+            );
+        }
+        validation_errors?;
+
+        let mut saved = Vec::new();
+        for attributes in answers {
+            saved.push(CheckoutAnswer::upsert(order.id, attributes, conn)?);
+        }
+
+        DomainEvent::create(
+            DomainEventTypes::CheckoutAnswersUpdated,
+            "Checkout answers updated".to_string(),
+            Tables::Orders,
+            Some(order.id),
+            current_user_id,
+            Some(json!(&saved)),
+        )
+        .commit(conn)?;
+
+        Ok(saved)
+    }
+
+    /// Removes answers for tickets released from the cart and confirms every required
+    /// question has been answered before the order can be paid for.
+    pub fn validate_for_checkout(order: &Order, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let ticket_types_by_instance = CheckoutAnswer::ticket_types_by_instance(order, conn)?;
+        let ticket_instance_ids: Vec<Uuid> = ticket_types_by_instance.keys().cloned().collect();
+        diesel::delete(
+            checkout_answers::table
+                .filter(checkout_answers::order_id.eq(order.id))
+                .filter(checkout_answers::ticket_instance_id.is_not_null())
+                .filter(dsl::not(
+                    checkout_answers::ticket_instance_id.eq_any(ticket_instance_ids),
+                )),
+        )
+        .execute(conn)
+        .to_db_error(
+            ErrorCode::DeleteError,
+            "Could not remove checkout answers for released tickets",
+        )?;
+
+        let answers = CheckoutAnswer::find_for_order(order.id, conn)?;
+        let mut validation_errors: Result<(), ValidationErrors> = Ok(());
+        for question in CheckoutQuestion::find_for_order(order, conn)? {
+            if !question.required {
+                continue;
+            }
+
+            let expected_ticket_instance_ids: Vec<Option<Uuid>> = match question.answer_scope {
+                CheckoutQuestionScope::Order => vec![None],
+                CheckoutQuestionScope::Attendee => ticket_types_by_instance
+                    .iter()
+                    .filter(|(_, ticket_type_id)| question.applies_to_ticket_type(Some(**ticket_type_id)))
+                    .map(|(ticket_instance_id, _)| Some(*ticket_instance_id))
+                    .collect(),
+            };
+
+            for ticket_instance_id in expected_ticket_instance_ids {
+                let answered = answers.iter().any(|a| {
+                    a.checkout_question_id == question.id
+                        && a.ticket_instance_id == ticket_instance_id
+                        && question.validate_answer(&a.answer).is_ok()
+                });
+                if !answered {
+                    let mut error = create_validation_error("required", "An answer is required");
+                    error.add_param(Cow::from("checkout_question_id"), &question.id);
+                    error.add_param(Cow::from("ticket_instance_id"), &ticket_instance_id);
+                    validation_errors = validators::append_validation_error(validation_errors, "answers", Err(error));
+                }
+            }
+        }
+
+        Ok(validation_errors?)
+    }
+
+    fn upsert(
+        order_id: Uuid,
+        attributes: CheckoutAnswerAttributes,
+        conn: &PgConnection,
+    ) -> Result<CheckoutAnswer, DatabaseError> {
+        let mut query = checkout_answers::table
+            .filter(checkout_answers::order_id.eq(order_id))
+            .filter(checkout_answers::checkout_question_id.eq(attributes.checkout_question_id))
+            .into_boxed();
+        query = match attributes.ticket_instance_id {
+            Some(ticket_instance_id) => query.filter(checkout_answers::ticket_instance_id.eq(ticket_instance_id)),
+            None => query.filter(checkout_answers::ticket_instance_id.is_null()),
+        };
+        let existing: Option<CheckoutAnswer> = query
+            .first(conn)
+            .optional()
+            .to_db_error(ErrorCode::QueryError, "Unable to load checkout answer")?;
+
+        match existing {
+            Some(existing) => diesel::update(&existing)
+                .set((
+                    checkout_answers::answer.eq(attributes.answer.trim()),
+                    checkout_answers::updated_at.eq(dsl::now),
+                ))
+                .get_result(conn)
+                .to_db_error(ErrorCode::UpdateError, "Could not update checkout answer"),
+            None => diesel::insert_into(checkout_answers::table)
+                .values(&NewCheckoutAnswer {
+                    checkout_question_id: attributes.checkout_question_id,
+                    order_id,
+                    ticket_instance_id: attributes.ticket_instance_id,
+                    answer: attributes.answer.trim().to_string(),
+                })
+                .get_result(conn)
+                .to_db_error(ErrorCode::InsertError, "Could not create checkout answer"),
+        }
+    }
+
+    fn answer_target_valid(
+        question: &CheckoutQuestion,
+        attributes: &CheckoutAnswerAttributes,
+        ticket_types_by_instance: &HashMap<Uuid, Uuid>,
+    ) -> Result<(), ValidationError> {
+        match (question.answer_scope, attributes.ticket_instance_id) {
+            (CheckoutQuestionScope::Order, None) => Ok(()),
+            (CheckoutQuestionScope::Order, Some(_)) => Err(create_validation_error(
+                "ticket_instance_not_allowed",
+                "Order questions are answered once per order",
+            )),
+            (CheckoutQuestionScope::Attendee, None) => Err(create_validation_error(
+                "ticket_instance_required",
+                "Attendee questions must be answered for a ticket",
+            )),
+            (CheckoutQuestionScope::Attendee, Some(ticket_instance_id)) => {
+                match ticket_types_by_instance.get(&ticket_instance_id) {
+                    Some(ticket_type_id) if question.applies_to_ticket_type(Some(*ticket_type_id)) => Ok(()),
+                    _ => Err(create_validation_error(
+                        "ticket_instance_not_in_order",
+                        "Ticket is not part of this order or the question does not apply to it",
+                    )),
+                }
+            }
+        }
+    }
+
+    fn ticket_types_by_instance(order: &Order, conn: &PgConnection) -> Result<HashMap<Uuid, Uuid>, DatabaseError> {
+        let mut result = HashMap::new();
+        for item in order
+            .items(conn)?
+            .into_iter()
+            .filter(|i| i.item_type == OrderItemTypes::Tickets)
+        {
+            if let Some(ticket_type_id) = item.ticket_type_id {
+                for ticket_instance in TicketInstance::find_for_order_item(item.id, conn)? {
+                    result.insert(ticket_instance.id, ticket_type_id);
+                }
+            }
+        }
+        Ok(result)
+    }
+}
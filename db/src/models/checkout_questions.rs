@@ -0,0 +1,308 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::checkout_questions;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationError, ValidationErrors};
+use validators::{self, *};
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "checkout_questions"]
+pub struct CheckoutQuestion {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub ticket_type_id: Option<Uuid>,
+    pub question: String,
+    pub question_type: CheckoutQuestionType,
+    pub answer_scope: CheckoutQuestionScope,
+    pub options: Vec<String>,
+    pub required: bool,
+    pub rank: i32,
+    pub editable_until: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "checkout_questions"]
+pub struct NewCheckoutQuestion {
+    #[serde(default)]
+    pub event_id: Uuid,
+    pub ticket_type_id: Option<Uuid>,
+    #[validate(length(min = 1, message = "Question cannot be blank"))]
+    pub question: String,
+    pub question_type: CheckoutQuestionType,
+    pub answer_scope: CheckoutQuestionScope,
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub rank: i32,
+    pub editable_until: Option<NaiveDateTime>,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "checkout_questions"]
+pub struct CheckoutQuestionEditableAttributes {
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub ticket_type_id: Option<Option<Uuid>>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, message = "Question cannot be blank"))]
+    pub question: Option<String>,
+    pub question_type: Option<CheckoutQuestionType>,
+    pub answer_scope: Option<CheckoutQuestionScope>,
+    pub options: Option<Vec<String>>,
+    pub required: Option<bool>,
+    pub rank: Option<i32>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub editable_until: Option<Option<NaiveDateTime>>,
+}
+
+impl CheckoutQuestion {
+    pub fn create(
+        event_id: Uuid,
+        question: String,
+        question_type: CheckoutQuestionType,
+        answer_scope: CheckoutQuestionScope,
+        required: bool,
+    ) -> NewCheckoutQuestion {
+        NewCheckoutQuestion {
+            event_id,
+            ticket_type_id: None,
+            question,
+            question_type,
+            answer_scope,
+            options: Vec::new(),
+            required,
+            rank: 0,
+            editable_until: None,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<CheckoutQuestion, DatabaseError> {
+        checkout_questions::table
+            .filter(checkout_questions::id.eq(id))
+            .filter(checkout_questions::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load checkout question")
+    }
+
+    pub fn find_for_event(event_id: Uuid, conn: &PgConnection) -> Result<Vec<CheckoutQuestion>, DatabaseError> {
+        CheckoutQuestion::find_for_events(vec![event_id], conn)
+    }
+
+    pub fn find_for_events(event_ids: Vec<Uuid>, conn: &PgConnection) -> Result<Vec<CheckoutQuestion>, DatabaseError> {
+        checkout_questions::table
+            .filter(checkout_questions::event_id.eq_any(event_ids))
+            .filter(checkout_questions::deleted_at.is_null())
+            .order_by(checkout_questions::rank.asc())
+            .then_order_by(checkout_questions::created_at.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load checkout questions")
+    }
+
+    /// Questions that need answering for the ticket types currently in the order
+    pub fn find_for_order(order: &Order, conn: &PgConnection) -> Result<Vec<CheckoutQuestion>, DatabaseError> {
+        let ticket_items: Vec<OrderItem> = order
+            .items(conn)?
+            .into_iter()
+            .filter(|i| i.item_type == OrderItemTypes::Tickets)
+            .collect();
+        let mut event_ids: Vec<Uuid> = ticket_items.iter().filter_map(|i| i.event_id).collect();
+        event_ids.sort();
+        event_ids.dedup();
+        let ticket_type_ids: Vec<Uuid> = ticket_items.iter().filter_map(|i| i.ticket_type_id).collect();
+
+        Ok(CheckoutQuestion::find_for_events(event_ids, conn)?
+            .into_iter()
+            .filter(|q| q.ticket_type_id.map(|id| ticket_type_ids.contains(&id)).unwrap_or(true))
+            .collect())
+    }
+
+    pub fn event(&self, conn: &PgConnection) -> Result<Event, DatabaseError> {
+        Event::find(self.event_id, conn)
+    }
+
+    pub fn applies_to_ticket_type(&self, ticket_type_id: Option<Uuid>) -> bool {
+        self.ticket_type_id.is_none() || self.ticket_type_id == ticket_type_id
+    }
+
+    /// Answers can be edited until `editable_until`, falling back to the start of the event
+    pub fn answers_editable(&self, conn: &PgConnection) -> Result<bool, DatabaseError> {
+        let cutoff = match self.editable_until {
+            Some(editable_until) => Some(editable_until),
+            None => self.event(conn)?.event_start,
+        };
+        Ok(cutoff.map(|cutoff| Utc::now().naive_utc() < cutoff).unwrap_or(true))
+    }
+
+    pub fn validate_answer(&self, answer: &str) -> Result<(), ValidationError> {
+        let answer = answer.trim();
+        if answer.is_empty() {
+            if self.required {
+                return Err(create_validation_error("required", "An answer is required"));
+            }
+            return Ok(());
+        }
+
+        match self.question_type {
+            CheckoutQuestionType::Text => Ok(()),
+            CheckoutQuestionType::Select => {
+                if self.options.iter().any(|o| o == answer) {
+                    Ok(())
+                } else {
+                    Err(create_validation_error(
+                        "invalid_option",
+                        "Answer must be one of the question's options",
+                    ))
+                }
+            }
+            CheckoutQuestionType::Checkbox => match answer {
+                "true" => Ok(()),
+                "false" if !self.required => Ok(()),
+                "false" => Err(create_validation_error("required", "An answer is required")),
+                _ => Err(create_validation_error(
+                    "invalid_checkbox",
+                    "Checkbox answers must be true or false",
+                )),
+            },
+        }
+    }
+
+    pub fn update(
+        &self,
+        attributes: CheckoutQuestionEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<CheckoutQuestion, DatabaseError> {
+        CheckoutQuestion::validate_record(
+            attributes.validate(),
+            self.event_id,
+            attributes.ticket_type_id.unwrap_or(self.ticket_type_id),
+            attributes.question_type.unwrap_or(self.question_type),
+            attributes.options.as_ref().unwrap_or(&self.options),
+            conn,
+        )?;
+
+        let question: CheckoutQuestion = diesel::update(self)
+            .set((attributes, checkout_questions::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update checkout question")?;
+
+        DomainEvent::create(
+            DomainEventTypes::CheckoutQuestionUpdated,
+            "Checkout question updated".to_string(),
+            Tables::CheckoutQuestions,
+            Some(question.id),
+            current_user_id,
+            Some(json!(&question)),
+        )
+        .commit(conn)?;
+
+        Ok(question)
+    }
+
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::CheckoutQuestionDeleted,
+            "Checkout question deleted".to_string(),
+            Tables::CheckoutQuestions,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((
+                checkout_questions::deleted_at.eq(dsl::now),
+                checkout_questions::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete checkout question")?;
+
+        Ok(())
+    }
+
+    fn validate_record(
+        validation_errors: Result<(), ValidationErrors>,
+        event_id: Uuid,
+        ticket_type_id: Option<Uuid>,
+        question_type: CheckoutQuestionType,
+        options: &[String],
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        let validation_errors = validators::append_validation_error(
+            validation_errors,
+            "options",
+            CheckoutQuestion::options_valid(question_type, options),
+        );
+        let validation_errors = validators::append_validation_error(
+            validation_errors,
+            "ticket_type_id",
+            CheckoutQuestion::ticket_type_valid(event_id, ticket_type_id, conn)?,
+        );
+        Ok(validation_errors?)
+    }
+
+    fn options_valid(question_type: CheckoutQuestionType, options: &[String]) -> Result<(), ValidationError> {
+        if question_type == CheckoutQuestionType::Select && options.iter().all(|o| o.trim().is_empty()) {
+            return Err(create_validation_error(
+                "options_required",
+                "Select questions require at least one option",
+            ));
+        }
+        Ok(())
+    }
+
+    fn ticket_type_valid(
+        event_id: Uuid,
+        ticket_type_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Result<(), ValidationError>, DatabaseError> {
+        if let Some(ticket_type_id) = ticket_type_id {
+            if TicketType::find(ticket_type_id, conn)?.event_id != event_id {
+                return Ok(Err(create_validation_error(
+                    "ticket_type_does_not_belong_to_event",
+                    "Ticket type must belong to the question's event",
+                )));
+            }
+        }
+        Ok(Ok(()))
+    }
+}
+
+impl NewCheckoutQuestion {
+    pub fn commit(self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<CheckoutQuestion, DatabaseError> {
+        CheckoutQuestion::validate_record(
+            self.validate(),
+            self.event_id,
+            self.ticket_type_id,
+            self.question_type,
+            &self.options,
+            conn,
+        )?;
+
+        let question: CheckoutQuestion = diesel::insert_into(checkout_questions::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create checkout question")?;
+
+        DomainEvent::create(
+            DomainEventTypes::CheckoutQuestionCreated,
+            "Checkout question created".to_string(),
+            Tables::CheckoutQuestions,
+            Some(question.id),
+            current_user_id,
+            Some(json!(&question)),
+        )
+        .commit(conn)?;
+
+        Ok(question)
+    }
+}
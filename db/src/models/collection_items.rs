@@ -78,9 +78,10 @@ inner join users u on c.user_id = u.id
 INNER JOIN (
         select count(ti.id) as number_owned, tt.id
         from ticket_instances ti
-        inner join order_items oi on ti.order_item_id = oi.id
-        inner join orders o on oi.order_id = o.id and o.user_id = $2
-        inner join ticket_types tt on oi.ticket_type_id = tt.id
+        inner join wallets w on ti.wallet_id = w.id and w.user_id = $2
+        inner join assets a on ti.asset_id = a.id
+        inner join ticket_types tt on a.ticket_type_id = tt.id
+        where ti.status in ('Purchased', 'Redeemed')
         group by tt.id
 ) as tic on ci.collectible_id = tic.id
 where c.id = $1
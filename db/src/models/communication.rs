@@ -10,21 +10,33 @@ pub type TemplateData = HashMap<String, String>;
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
 pub struct CommAddress {
     pub addresses: Vec<String>,
+    /// Display name shown alongside a source email address
+    #[serde(default)]
+    pub name: Option<String>,
 }
 
 impl CommAddress {
     pub fn new() -> CommAddress {
-        CommAddress { addresses: Vec::new() }
+        CommAddress {
+            addresses: Vec::new(),
+            name: None,
+        }
     }
 
     pub fn from(address: String) -> CommAddress {
         CommAddress {
             addresses: vec![address],
+            name: None,
         }
     }
 
     pub fn from_vec(addresses: Vec<String>) -> CommAddress {
-        CommAddress { addresses }
+        CommAddress { addresses, name: None }
+    }
+
+    pub fn with_name(mut self, name: Option<String>) -> CommAddress {
+        self.name = name;
+        self
     }
 
     pub fn get(&self) -> Vec<String> {
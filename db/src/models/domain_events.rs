@@ -120,7 +120,19 @@ impl DomainEvent {
                         )?;
                     }
                 }
+                DomainEventTypes::EventUpdated => {
+                    WalletPass::refresh_for_event(main_id, conn)?;
+                }
+                DomainEventTypes::TradeAccepted => {
+                    let ticket_ids: Vec<Uuid> = Trade::find(main_id, conn)?
+                        .items(conn)?
+                        .into_iter()
+                        .map(|item| item.ticket_instance_id)
+                        .collect();
+                    WalletPass::refresh_for_tickets(&ticket_ids, conn)?;
+                }
                 DomainEventTypes::TicketInstanceRedeemed => {
+                    WalletPass::refresh_for_tickets(&[main_id], conn)?;
                     let ticket = TicketInstance::find(main_id, conn)?;
                     let wallet = Wallet::find(ticket.wallet_id, conn)?;
                     if let Some(user_id) = wallet.user_id {
@@ -150,6 +162,11 @@ impl DomainEvent {
                             organization.log_interaction(destination_user_id, Utc::now().naive_utc(), conn)?;
                         }
                     }
+
+                    if self.event_type == DomainEventTypes::TransferTicketCompleted {
+                        let ticket_ids: Vec<Uuid> = transfer.tickets(conn)?.into_iter().map(|t| t.id).collect();
+                        WalletPass::refresh_for_tickets(&ticket_ids, conn)?;
+                    }
                 }
                 _ => (),
             };
@@ -92,18 +92,41 @@ macro_rules! string_enum {
     }
 }
 
-define_enum! { ActivityType [Purchase, Transfer, CheckIn, Refund, Note]}
+define_enum! { ActivityType [Purchase, Transfer, Trade, CheckIn, Refund, Note]}
 define_enum! { AnnouncementEngagementAction [Dismiss] }
-define_enum! { AssetStatus [Unsynced] }
-define_enum! { BroadcastAudience [ PeopleAtTheEvent, TicketHolders, OrganizationMembers ]}
+define_enum! { AssetStatus [Unsynced, Syncing, Synced, Error] }
+define_enum! { AssetSyncIssueTypes [MissingToken, NullifiedMismatch, OwnerMismatch, RedeemedMismatch, SupplyMismatch] }
+define_enum! { BroadcastAudience [ PeopleAtTheEvent, TicketHolders, OrganizationMembers, FanSegment ]}
 define_enum! { CartItemStatus [CodeExpired, HoldExpired, TicketNullified, TicketNotReserved, Valid] }
+define_enum! { CashDrawerMovementTypes [Drop, Payout] }
+define_enum! { CashDrawerSessionStatus [Open, Closed] }
 define_enum! { CheckInSource [GuestList, Scanned, LootBox] }
+define_enum! { CheckoutQuestionScope [Order, Attendee] }
+define_enum! { CheckoutQuestionType [Text, Select, Checkbox] }
 define_enum! { CodeTypes [Access, Discount] }
 define_enum! { CommunicationChannelType [Email, Sms, Push, Webhook]}
 define_enum! { CommunicationType [EmailTemplate, Sms, Push, Webhook]}
+define_enum! { Currencies [CAD, EUR, GBP, USD] }
 define_enum! { DomainEventTypes [
+    AccessZoneCreated,
+    AccessZoneDeleted,
+    AccessZoneUpdated,
+    AccountingAccountMappingUpdated,
+    AddOnRedeemed,
+    AffiliateCommissionRuleCreated,
+    AffiliateCommissionRuleDeleted,
+    AffiliateLinkCreated,
+    AffiliateLinkDeleted,
+    AffiliateLinkUpdated,
     AnnouncementCreated,
     AnnouncementDeleted,
+    CashDrawerMovementCreated,
+    CashDrawerSessionClosed,
+    CashDrawerSessionOpened,
+    CheckoutAnswersUpdated,
+    CheckoutQuestionCreated,
+    CheckoutQuestionDeleted,
+    CheckoutQuestionUpdated,
     CodeCreated,
     CodeDeleted,
     CodeUpdated,
@@ -117,16 +140,28 @@ define_enum! { DomainEventTypes [
     EventPublished,
     EventReportSubscriberCreated,
     EventReportSubscriberDeleted,
+    EventSeriesCreated,
+    EventSeriesDeleted,
+    EventSeriesOccurrencesGenerated,
+    EventSeriesOccurrencesUpdated,
+    EventSeriesUpdated,
     EventUpdated,
     EventUnpublished,
     ExternalLoginCreated,
     ExternalLoginDeleted,
+    FanSegmentCreated,
+    FanSegmentDeleted,
+    FanSegmentUpdated,
     FeeScheduleCreated,
     GenresUpdated,
     HoldAutomaticallyReleased,
     HoldCreated,
     HoldDeleted,
     HoldQuantityChanged,
+    InventoryPoolCreated,
+    InventoryPoolDeleted,
+    InventoryPoolTicketTypesUpdated,
+    InventoryPoolUpdated,
     OrderBehalfOfUserChanged,
     OrderCompleted,
     OrderCreated,
@@ -135,9 +170,18 @@ define_enum! { DomainEventTypes [
     OrderRetargetingEmailTriggered,
     OrderStatusUpdated,
     OrderUpdated,
+    OrganizationBankAccountUpdated,
+    OrganizationBrandingUpdated,
     OrganizationCreated,
     NoteCreated,
     NoteDeleted,
+    PackageCreated,
+    PackageDeleted,
+    PackageUpdated,
+    PartnerApiKeyGenerated,
+    PartnerCreated,
+    PartnerDeleted,
+    PartnerUpdated,
     PaymentCancelled,
     PaymentCreated,
     PaymentCompleted,
@@ -146,6 +190,12 @@ define_enum! { DomainEventTypes [
     PaymentMethodCreated,
     PaymentMethodUpdated,
     PaymentUpdated,
+    ProductCreated,
+    ProductDeleted,
+    ProductUpdated,
+    ProductVariantCreated,
+    ProductVariantDeleted,
+    ProductVariantUpdated,
     UserCreated,
     UserDisabled,
     UserLogin,
@@ -154,7 +204,22 @@ define_enum! { DomainEventTypes [
     LostPassword,
     PurchaseCompleted,
     PushNotificationTokenCreated,
+    SettlementPayoutBatchCreated,
+    SettlementPayoutInitiated,
+    SettlementPayoutPaid,
+    SettlementPayoutReturned,
     SettlementReportProcessed,
+    SettlementStatementGenerated,
+    SettlementStatementSubscriberCreated,
+    SettlementStatementSubscriberDeleted,
+    TaxRuleCreated,
+    TaxRuleDeleted,
+    TaxRuleUpdated,
+    TradeAccepted,
+    TradeCancelled,
+    TradeDeclined,
+    TradeExpired,
+    TradeProposed,
     TransferTicketDripSourceSent,
     TransferTicketDripDestinationSent,
     TransferTicketCancelled,
@@ -162,8 +227,10 @@ define_enum! { DomainEventTypes [
     TransferTicketStarted,
     TrackingDataUpdated,
     TemporaryUserCreated,
+    TicketInstanceAddedFromLootBox,
     TicketInstanceAddedToHold,
     TicketInstanceAddedToListing,
+    TicketInstanceLootBoxOpened,
     TicketInstanceNullified,
     TicketInstancePurchased,
     TicketInstanceRedeemed,
@@ -184,17 +251,21 @@ define_enum! { DomainActionTypes [
     BroadcastPushNotification,
     // Email/SMS/Push Communication
     Communication,
+    ExpireTrade,
     FinalizeSettlements,
+    GenerateEventSeriesOccurrences,
     PaymentProviderIPN,
     ProcessSettlementReport,
     ProcessTransferDrip,
+    ReconcileAssets,
     RegenerateDripActions,
     ReleaseHoldInventory,
     RetargetAbandonedOrders,
     SendAutomaticReportEmails,
     SendPurchaseCompletedCommunication,
     SubmitSitemapToSearchEngines,
-    UpdateGenres
+    UpdateGenres,
+    UpdateWalletPasses
 ]}
 define_enum! { BroadcastStatus [Pending, InProgress, Completed, Cancelled]}
 define_enum! { BroadcastChannel [PushNotification, Email]}
@@ -202,6 +273,7 @@ define_enum! { BroadcastType [Custom, LastCall]}
 define_enum! { DomainActionStatus [Pending, RetriesExceeded, Errored, Success, Cancelled]}
 define_enum! { EmailProvider [Sendgrid, CustomerIo]}
 define_enum! { Environment [Development, Production, Staging, Test]}
+define_enum! { EventSeriesEditScopes [ThisEvent, ThisAndFuture, AllEvents]}
 define_enum! { EventStatus [Draft,Closed,Published,Offline]}
 define_enum! { EventSearchSortField [ Name, EventStart]}
 define_enum! { EventOverrideStatus [PurchaseTickets,SoldOut,OnSaleSoon,TicketsAtTheDoor,Free,Rescheduled,Cancelled,OffSale,Ended]}
@@ -210,29 +282,38 @@ define_enum! { ExternalPaymentType [Cash, CreditCard, Voucher]}
 define_enum! { FanSortField [FirstName, LastName, Email, Phone, OrganizationId, UserCreated, Orders, FirstOrder, LastOrder, Revenue, FirstInteracted, LastInteracted] }
 define_enum! { HistoryType [Purchase]}
 define_enum! { HoldTypes [Discount, Comp] }
+define_enum! { LedgerAccountTypes [AffiliateCommissions, Cash, CreditCardFees, GrossSales, PartnerCommissions, PayableToOrganization, PlatformFees, TaxCollected] }
 define_enum! { ListingStatus [Pending, Published] }
+define_enum! { Locales [En, Es, Fr] }
 define_enum! { MarketplaceAccountStatus [ Pending, Linked ]}
 define_enum! { OrderStatus [Cancelled, Draft, Paid, PendingPayment] }
-define_enum! { OrderItemTypes [Tickets, PerUnitFees, EventFees, Discount, CreditCardFees]}
+define_enum! { OrderItemTypes [Tickets, PerUnitFees, EventFees, Discount, CreditCardFees, AddOns, Tax]}
 define_enum! { OrderTypes [Cart, BackOffice] }
-define_enum! { PaymentMethods [CreditCard, External, Free, Provider] }
-define_enum! { PaymentProviders [External, Globee, Free, Stripe] }
+define_enum! { PaymentMethods [CreditCard, Credit, External, Free, Provider] }
+define_enum! { PaymentProviders [Credit, External, Globee, Free, Stripe] }
 define_enum! { PaymentStatus [Authorized, Completed, Requested, Refunded, Unpaid, PendingConfirmation, Cancelled, Draft, Unknown, PendingIpn] }
+define_enum! { PayoutMethods [Nacha, Sepa] }
 define_enum! { PastOrUpcoming [Past,Upcoming]}
-define_enum! { Platforms [Web, App, BoxOffice]}
+define_enum! { Platforms [Web, App, BoxOffice, Partner]}
+define_enum! { ReEntryPolicies [NoReEntry, Unlimited] }
+define_enum! { ReportPeriods [Day, Week, Month, Quarter, Year] }
 define_enum! { ReportTypes [TicketCounts]}
 define_enum! { Roles [Admin, DoorPerson, OrgAdmin, OrgBoxOffice, OrgMember, OrgOwner, PrismIntegration, Promoter, PromoterReadOnly, User, Super] }
+define_enum! { ScanDirections [Entry, Exit] }
+define_enum! { ScanRejectionReasons [AlreadyInside, AlreadyRedeemed, ExpiredCode, InvalidTicket, NotInside, ReEntryNotAllowed, TransferInProgress, WrongZone] }
+define_enum! { ScanResults [Accepted, Rejected] }
 define_enum! { SettlementStatus[PendingSettlement, FinalizedSettlement] }
 define_enum! { SettlementTypes [Rolling, PostEvent]}
 define_enum! { SettlementAdjustmentTypes [ManualCredit, ManualDeduction, Chargeback]}
-define_enum! { SettlementEntryTypes [EventFees, TicketType]}
+define_enum! { SettlementEntryTypes [EventFees, TicketType, AddOn, PartnerCommission, AffiliateCommission, Tax]}
+define_enum! { SettlementPayoutStatus [Initiated, Paid, Returned]}
 define_enum! { SlugTypes[ Event, Organization, Venue, City, Genre, CityGenre ] }
 define_enum! { SortingDir[ Asc, Desc ] }
 define_enum! { SourceOrDestination [Destination,Source]}
 define_enum! { Tables [
-    Announcements, Artists, Broadcasts, Codes, DomainEventPublishers, Events, EventArtists, EventReportSubscribers, ExternalLogins, FeeSchedules,
-    Holds, Orders, Organizations, Notes, Payments, PaymentMethods, PushNotificationTokens, TemporaryUsers, TicketInstances, TicketTypes,
-    TicketPricing, Transfers, Users, Venues, Genres
+    AccessZones, AffiliateLinks, Announcements, Artists, Broadcasts, CashDrawerSessions, CheckoutQuestions, Codes, DomainEventPublishers, Events, EventArtists, EventSeries, EventReportSubscribers, ExternalLogins, FanSegments, FeeSchedules,
+    Holds, InventoryPools, Orders, Organizations, Notes, Packages, Partners, Payments, PaymentMethods, Products, ProductVariants, PushNotificationTokens, Refunds, SettlementAdjustments, SettlementPayoutBatches, SettlementPayouts, Settlements, TaxRules, TemporaryUsers, TicketInstances, TicketTypes,
+    TicketPricing, Trades, Transfers, Users, Venues, WalletPasses, Genres
 ] }
 define_enum! { TicketInstanceStatus [Available, Reserved, Purchased, Redeemed, Nullified]}
 define_enum! { TicketPricingStatus [Published, Deleted, Default] }
@@ -240,8 +321,10 @@ define_enum! { TicketTypeEndDateType [DoorTime, EventEnd, EventStart, Manual] }
 define_enum! { TicketTypeStatus [NoActivePricing, Published, SoldOut, OnSaleSoon, SaleEnded, Cancelled, Deleted] }
 define_enum! { TicketTypeType [ Token, LootBox ]}
 define_enum! { TicketTypeVisibility [ Always, Hidden, WhenAvailable ]}
+define_enum! { TradeStatus [Pending, Accepted, Declined, Cancelled, Expired] }
 define_enum! { TransferMessageType [Email, Phone] }
 define_enum! { TransferStatus [Pending, Cancelled, Completed, EventEnded] }
+define_enum! { WalletPassTypes [Apple, Google] }
 define_enum! { WebhookAdapters [CustomerIo]}
 
 impl Roles {
@@ -262,6 +345,18 @@ impl Default for EventTypes {
     }
 }
 
+impl Default for ReEntryPolicies {
+    fn default() -> ReEntryPolicies {
+        ReEntryPolicies::Unlimited
+    }
+}
+
+impl Default for Locales {
+    fn default() -> Locales {
+        Locales::En
+    }
+}
+
 impl Default for BroadcastType {
     fn default() -> BroadcastType {
         BroadcastType::LastCall
@@ -300,6 +395,58 @@ impl OrderItemTypes {
     }
 }
 
+impl Locales {
+    /// ISO 639-1 language code used in `Accept-Language` and `Content-Language` headers
+    pub fn language_code(self) -> &'static str {
+        match self {
+            Locales::En => "en",
+            Locales::Es => "es",
+            Locales::Fr => "fr",
+        }
+    }
+}
+
+impl Currencies {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Currencies::CAD => "CA$",
+            Currencies::EUR => "€",
+            Currencies::GBP => "£",
+            Currencies::USD => "$",
+        }
+    }
+}
+
+impl LedgerAccountTypes {
+    /// Cash is an asset so increases with debits, the remaining accounts are revenue and
+    /// liabilities which increase with credits
+    pub fn is_debit_normal(self) -> bool {
+        self == LedgerAccountTypes::Cash
+    }
+
+    /// Account that an order item's sale is recorded against
+    pub fn for_order_item_type(item_type: OrderItemTypes) -> LedgerAccountTypes {
+        match item_type {
+            OrderItemTypes::Tickets | OrderItemTypes::AddOns | OrderItemTypes::Discount => {
+                LedgerAccountTypes::GrossSales
+            }
+            OrderItemTypes::PerUnitFees | OrderItemTypes::EventFees => LedgerAccountTypes::PlatformFees,
+            OrderItemTypes::CreditCardFees => LedgerAccountTypes::CreditCardFees,
+            OrderItemTypes::Tax => LedgerAccountTypes::TaxCollected,
+        }
+    }
+}
+
+impl PayoutMethods {
+    /// Currency payouts are transferred in, ACH batches are USD only and SEPA transfers EUR only
+    pub fn currency(self) -> Currencies {
+        match self {
+            PayoutMethods::Nacha => Currencies::USD,
+            PayoutMethods::Sepa => Currencies::EUR,
+        }
+    }
+}
+
 #[test]
 fn get_event_limited_roles() {
     assert_eq!(
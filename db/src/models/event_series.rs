@@ -0,0 +1,799 @@
+use chrono::prelude::*;
+use chrono::Duration;
+use chrono_tz::Tz;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::{event_series, event_series_occurrences, events};
+use serde_with::rust::double_option;
+use std::borrow::Cow;
+use utils::errors::*;
+use utils::recurrence::RecurrenceRule;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+use validators::*;
+
+/// Occurrences are generated this far ahead, open ended series are topped up by a background action
+pub const EVENT_SERIES_GENERATION_HORIZON_DAYS: i64 = 365;
+/// Upper bound on the number of occurrences a series rule can produce
+pub const MAX_EVENT_SERIES_OCCURRENCES: usize = 366;
+const EVENT_SERIES_REGENERATION_INTERVAL_DAYS: i64 = 7;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "event_series"]
+pub struct EventSeries {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    /// New occurrences are cloned from this event, "this and future" edits move it forward
+    pub template_event_id: Uuid,
+    pub name: String,
+    pub recurrence_rule: String,
+    pub generated_until: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "event_series"]
+pub struct NewEventSeries {
+    pub organization_id: Uuid,
+    pub template_event_id: Uuid,
+    #[validate(length(min = 1, message = "Name cannot be blank"))]
+    pub name: String,
+    pub recurrence_rule: String,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "event_series"]
+pub struct EventSeriesEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, message = "Name cannot be blank"))]
+    pub name: Option<String>,
+    pub recurrence_rule: Option<String>,
+}
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "event_series_occurrences"]
+pub struct EventSeriesOccurrence {
+    pub id: Uuid,
+    pub event_series_id: Uuid,
+    pub event_id: Uuid,
+    /// Start of the occurrence when it was generated, kept when the event itself is moved
+    pub occurrence_start: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "event_series_occurrences"]
+struct NewEventSeriesOccurrence {
+    event_series_id: Uuid,
+    event_id: Uuid,
+    occurrence_start: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayEventSeries {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub template_event_id: Uuid,
+    pub name: String,
+    pub recurrence_rule: String,
+    pub generated_until: Option<NaiveDateTime>,
+    pub event_ids: Vec<Uuid>,
+    pub next_event_start: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EventSeriesArtist {
+    pub artist_id: Uuid,
+    pub importance: i32,
+    /// Set time for the edited event, other occurrences get the same time relative to their start
+    pub set_time: Option<NaiveDateTime>,
+    pub stage_id: Option<Uuid>,
+}
+
+/// Event details that can be applied across occurrences, dates are driven by the recurrence rule
+#[derive(Clone, Default, Deserialize, Serialize, Validate)]
+pub struct EventSeriesEventAttributes {
+    pub name: Option<String>,
+    pub venue_id: Option<Uuid>,
+    #[validate(url(message = "Promo image URL is invalid"))]
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub promo_image_url: Option<Option<String>>,
+    #[validate(url(message = "Cover image URL is invalid"))]
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub cover_image_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub additional_info: Option<Option<String>>,
+    #[serde(default, deserialize_with = "from_str_or_num_to_str")]
+    #[validate(length(max = "255", message = "Age limit must be less than 255 characters long"))]
+    pub age_limit: Option<String>,
+    #[validate(length(max = "100", message = "Top line info must be at most 100 characters long"))]
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub top_line_info: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    #[validate(url(message = "Video URL is invalid"))]
+    pub video_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub override_status: Option<Option<EventOverrideStatus>>,
+    pub event_type: Option<EventTypes>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub private_access_code: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub facebook_pixel_key: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option::deserialize")]
+    pub barcode_rotation_seconds: Option<Option<i32>>,
+    /// Replaces the lineup when present
+    pub artists: Option<Vec<EventSeriesArtist>>,
+}
+
+impl EventSeriesEventAttributes {
+    fn event_attributes(&self) -> EventEditableAttributes {
+        EventEditableAttributes {
+            name: self.name.clone(),
+            venue_id: self.venue_id,
+            promo_image_url: self.promo_image_url.clone(),
+            cover_image_url: self.cover_image_url.clone(),
+            additional_info: self.additional_info.clone(),
+            age_limit: self.age_limit.clone(),
+            top_line_info: self.top_line_info.clone(),
+            video_url: self.video_url.clone(),
+            override_status: self.override_status,
+            event_type: self.event_type,
+            private_access_code: self.private_access_code.clone(),
+            facebook_pixel_key: self.facebook_pixel_key.clone(),
+            barcode_rotation_seconds: self.barcode_rotation_seconds,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EventSeriesDashboard {
+    pub event_series: DisplayEventSeries,
+    pub events: Vec<EventSummaryResult>,
+    pub total_tickets: u32,
+    pub sold: u32,
+    pub tickets_redeemed: u32,
+    pub sales_total_in_cents: u32,
+}
+
+impl NewEventSeries {
+    pub fn commit(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<EventSeries, DatabaseError> {
+        let template_event = Event::find(self.template_event_id, conn)?;
+        let mut validation_errors = append_validation_error(
+            self.validate(),
+            "recurrence_rule",
+            EventSeries::recurrence_rule_valid(&self.recurrence_rule),
+        );
+        validation_errors = append_validation_error(
+            validation_errors,
+            "template_event_id",
+            EventSeries::template_event_valid(self.organization_id, &template_event, conn)?,
+        );
+        validation_errors?;
+
+        let event_series: EventSeries = diesel::insert_into(event_series::table)
+            .values(self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create event series")?;
+
+        // The template is the series' first occurrence, the rule is anchored on its start
+        if let Some(event_start) = template_event.event_start {
+            event_series.add_occurrence(template_event.id, event_start, conn)?;
+        }
+
+        DomainEvent::create(
+            DomainEventTypes::EventSeriesCreated,
+            "Event series created".to_string(),
+            Tables::EventSeries,
+            Some(event_series.id),
+            current_user_id,
+            Some(json!(&event_series)),
+        )
+        .commit(conn)?;
+
+        Ok(event_series)
+    }
+}
+
+impl EventSeries {
+    pub fn create(
+        organization_id: Uuid,
+        template_event_id: Uuid,
+        name: String,
+        recurrence_rule: String,
+    ) -> NewEventSeries {
+        NewEventSeries {
+            organization_id,
+            template_event_id,
+            name,
+            recurrence_rule,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<EventSeries, DatabaseError> {
+        event_series::table
+            .filter(event_series::id.eq(id))
+            .filter(event_series::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load event series")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<EventSeries>, DatabaseError> {
+        event_series::table
+            .filter(event_series::organization_id.eq(organization_id))
+            .filter(event_series::deleted_at.is_null())
+            .order_by(event_series::name.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load event series")
+    }
+
+    pub fn find_for_event(event_id: Uuid, conn: &PgConnection) -> Result<Option<EventSeries>, DatabaseError> {
+        event_series::table
+            .inner_join(event_series_occurrences::table)
+            .filter(event_series_occurrences::event_id.eq(event_id))
+            .filter(event_series::deleted_at.is_null())
+            .select(event_series::all_columns)
+            .first(conn)
+            .optional()
+            .to_db_error(ErrorCode::QueryError, "Unable to load event series for event")
+    }
+
+    pub fn organization(&self, conn: &PgConnection) -> Result<Organization, DatabaseError> {
+        Organization::find(self.organization_id, conn)
+    }
+
+    pub fn template_event(&self, conn: &PgConnection) -> Result<Event, DatabaseError> {
+        Event::find(self.template_event_id, conn)
+    }
+
+    pub fn rule(&self) -> Result<RecurrenceRule, DatabaseError> {
+        self.recurrence_rule.parse::<RecurrenceRule>().map_err(|e| {
+            DatabaseError::new(
+                ErrorCode::InternalError,
+                Some(format!("Invalid recurrence rule for event series {}: {}", self.id, e)),
+            )
+        })
+    }
+
+    pub fn occurrences(&self, conn: &PgConnection) -> Result<Vec<EventSeriesOccurrence>, DatabaseError> {
+        event_series_occurrences::table
+            .filter(event_series_occurrences::event_series_id.eq(self.id))
+            .order_by(event_series_occurrences::occurrence_start.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load event series occurrences")
+    }
+
+    /// Events of the series in occurrence order, events deleted from the series are excluded
+    pub fn events(&self, conn: &PgConnection) -> Result<Vec<Event>, DatabaseError> {
+        events::table
+            .inner_join(event_series_occurrences::table.on(event_series_occurrences::event_id.eq(events::id)))
+            .filter(event_series_occurrences::event_series_id.eq(self.id))
+            .filter(events::deleted_at.is_null())
+            .order_by(event_series_occurrences::occurrence_start.asc())
+            .select(events::all_columns)
+            .load::<EventData>(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load event series events")
+            .map(EventData::vec_into_events)
+    }
+
+    pub fn for_display(&self, conn: &PgConnection) -> Result<DisplayEventSeries, DatabaseError> {
+        let now = Utc::now().naive_utc();
+        let events = self.events(conn)?;
+        Ok(DisplayEventSeries {
+            id: self.id,
+            organization_id: self.organization_id,
+            template_event_id: self.template_event_id,
+            name: self.name.clone(),
+            recurrence_rule: self.recurrence_rule.clone(),
+            generated_until: self.generated_until,
+            event_ids: events.iter().map(|e| e.id).collect(),
+            next_event_start: events.iter().filter_map(|e| e.event_start).find(|s| *s >= now),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
+    }
+
+    /// Creates events for upcoming occurrences of the rule that have not been generated yet. Occurrences
+    /// whose event was deleted are not generated again.
+    pub fn generate_occurrences(
+        &self,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Vec<Event>, DatabaseError> {
+        let template_event = self.template_event(conn)?;
+        let template_occurrence_start = self.occurrence_start_for_event(template_event.id, conn)?;
+        let existing_occurrence_starts = self
+            .occurrences(conn)?
+            .into_iter()
+            .map(|o| o.occurrence_start)
+            .collect::<Vec<NaiveDateTime>>();
+        let now = Utc::now().naive_utc();
+        let generated_until = now + Duration::days(EVENT_SERIES_GENERATION_HORIZON_DAYS);
+
+        let mut events = vec![];
+        for occurrence_start in self.occurrence_starts(&template_event, generated_until, conn)? {
+            if occurrence_start < now || existing_occurrence_starts.contains(&occurrence_start) {
+                continue;
+            }
+            let event = self.create_occurrence_event(
+                &template_event,
+                template_occurrence_start,
+                occurrence_start,
+                current_user_id,
+                conn,
+            )?;
+            self.add_occurrence(event.id, occurrence_start, conn)?;
+            events.push(event);
+        }
+
+        let event_series: EventSeries = diesel::update(self)
+            .set((
+                event_series::generated_until.eq(generated_until),
+                event_series::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update event series")?;
+
+        if !events.is_empty() {
+            DomainEvent::create(
+                DomainEventTypes::EventSeriesOccurrencesGenerated,
+                format!("{} event series occurrences generated", events.len()),
+                Tables::EventSeries,
+                Some(self.id),
+                current_user_id,
+                Some(json!({ "event_ids": events.iter().map(|e| e.id).collect::<Vec<Uuid>>() })),
+            )
+            .commit(conn)?;
+        }
+
+        event_series.schedule_generation(conn)?;
+
+        Ok(events)
+    }
+
+    /// Rules that run past the generation horizon are topped up periodically
+    fn schedule_generation(&self, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let rule = self.rule()?;
+        let open_ended = rule.count.is_none()
+            && match (rule.until, self.generated_until) {
+                (Some(until), Some(generated_until)) => until > generated_until,
+                _ => true,
+            };
+        if !open_ended
+            || DomainAction::has_pending_action(
+                DomainActionTypes::GenerateEventSeriesOccurrences,
+                Tables::EventSeries,
+                self.id,
+                conn,
+            )?
+        {
+            return Ok(());
+        }
+
+        let mut action = DomainAction::create(
+            None,
+            DomainActionTypes::GenerateEventSeriesOccurrences,
+            None,
+            json!({}),
+            Some(Tables::EventSeries),
+            Some(self.id),
+        );
+        action.schedule_at(Utc::now().naive_utc() + Duration::days(EVENT_SERIES_REGENERATION_INTERVAL_DAYS));
+        action.commit(conn)?;
+        Ok(())
+    }
+
+    /// Start times (UTC) of the rule's occurrences up to `until`. The rule is evaluated in the venue's
+    /// timezone so a weekly 8pm show stays at 8pm across daylight saving changes.
+    fn occurrence_starts(
+        &self,
+        template_event: &Event,
+        until: NaiveDateTime,
+        conn: &PgConnection,
+    ) -> Result<Vec<NaiveDateTime>, DatabaseError> {
+        let anchor = match self.occurrences(conn)?.first() {
+            Some(occurrence) => occurrence.occurrence_start,
+            None => return Ok(vec![]),
+        };
+        let timezone = template_event
+            .venue(conn)?
+            .and_then(|venue| venue.timezone.parse::<Tz>().ok())
+            .unwrap_or(Tz::UTC);
+
+        Ok(self
+            .rule()?
+            .occurrences(
+                timezone.from_utc_datetime(&anchor).naive_local(),
+                timezone.from_utc_datetime(&until).naive_local(),
+                MAX_EVENT_SERIES_OCCURRENCES,
+            )
+            .into_iter()
+            .filter_map(|local| {
+                // Times skipped by a daylight saving change move forward an hour
+                timezone
+                    .from_local_datetime(&local)
+                    .earliest()
+                    .or_else(|| timezone.from_local_datetime(&(local + Duration::hours(1))).earliest())
+                    .map(|start| start.naive_utc())
+            })
+            .collect())
+    }
+
+    fn occurrence_start_for_event(&self, event_id: Uuid, conn: &PgConnection) -> Result<NaiveDateTime, DatabaseError> {
+        event_series_occurrences::table
+            .filter(event_series_occurrences::event_series_id.eq(self.id))
+            .filter(event_series_occurrences::event_id.eq(event_id))
+            .select(event_series_occurrences::occurrence_start)
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load event series occurrence")
+    }
+
+    fn add_occurrence(
+        &self,
+        event_id: Uuid,
+        occurrence_start: NaiveDateTime,
+        conn: &PgConnection,
+    ) -> Result<EventSeriesOccurrence, DatabaseError> {
+        diesel::insert_into(event_series_occurrences::table)
+            .values(&NewEventSeriesOccurrence {
+                event_series_id: self.id,
+                event_id,
+                occurrence_start,
+            })
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not add event series occurrence")
+    }
+
+    /// Clones the template with its ticket types, pricing periods and holds, shifting their dates by the
+    /// occurrence's offset from the template
+    fn create_occurrence_event(
+        &self,
+        template_event: &Event,
+        template_occurrence_start: NaiveDateTime,
+        occurrence_start: NaiveDateTime,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Event, DatabaseError> {
+        let offset = occurrence_start.signed_duration_since(template_occurrence_start);
+        let template_event_start = template_event.event_start.unwrap_or(template_occurrence_start);
+        let event_start = template_event_start + offset;
+        let event = template_event.clone_record(
+            &CloneFields {
+                name: template_event.name.clone(),
+                event_start,
+                event_end: template_event
+                    .event_end
+                    .map(|event_end| event_end + offset)
+                    .unwrap_or(event_start + Duration::days(1)),
+            },
+            current_user_id,
+            conn,
+        )?;
+
+        let ticket_types = event.ticket_types(false, None, conn)?;
+        for template_ticket_type in template_event.ticket_types(false, None, conn)? {
+            let ticket_type = match ticket_types.iter().find(|tt| tt.name == template_ticket_type.name) {
+                Some(ticket_type) => ticket_type,
+                // Cancelled and deleted ticket types are not cloned
+                None => continue,
+            };
+
+            for ticket_pricing in template_ticket_type.valid_ticket_pricing(false, conn)? {
+                ticket_type.add_ticket_pricing(
+                    ticket_pricing.name,
+                    ticket_pricing.start_date + offset,
+                    ticket_pricing.end_date + offset,
+                    ticket_pricing.price_in_cents,
+                    ticket_pricing.is_box_office_only,
+                    Some(ticket_pricing.status),
+                    current_user_id,
+                    conn,
+                )?;
+            }
+
+            for hold in Hold::find_by_ticket_type(template_ticket_type.id, conn)? {
+                // Comps are issued to a person for a specific date so are not carried over
+                if hold.parent_hold_id.is_some() || hold.deleted_at.is_some() {
+                    continue;
+                }
+                // The template's comps were taken from the hold so their quantity goes back to it
+                let (quantity, _) = hold.quantity(conn)?;
+                let (children_quantity, _) = hold.children_quantity(conn)?;
+                let new_hold = Hold::create_hold(
+                    hold.name.clone(),
+                    event.id,
+                    hold.redemption_code.clone(),
+                    hold.discount_in_cents.map(|d| d as u32),
+                    hold.end_at.map(|end_at| end_at + offset),
+                    hold.max_per_user.map(|m| m as u32),
+                    hold.hold_type,
+                    ticket_type.id,
+                )
+                .commit(current_user_id, conn)?;
+                new_hold.set_quantity(current_user_id, quantity + children_quantity, conn)?;
+            }
+        }
+
+        if template_event.status == EventStatus::Published {
+            return event.publish(current_user_id, conn);
+        }
+
+        Ok(event)
+    }
+
+    /// Changing the rule removes upcoming events that no longer match it, the user is recorded against
+    /// their deletion
+    pub fn update(
+        &self,
+        attributes: EventSeriesEditableAttributes,
+        user_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<EventSeries, DatabaseError> {
+        let mut validation_errors = attributes.validate();
+        if let Some(ref recurrence_rule) = attributes.recurrence_rule {
+            validation_errors = append_validation_error(
+                validation_errors,
+                "recurrence_rule",
+                EventSeries::recurrence_rule_valid(recurrence_rule),
+            );
+        }
+        validation_errors?;
+
+        let rule_changed = attributes
+            .recurrence_rule
+            .as_ref()
+            .map(|r| r != &self.recurrence_rule)
+            .unwrap_or(false);
+        let event_series: EventSeries = diesel::update(self)
+            .set((attributes, event_series::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update event series")?;
+
+        if rule_changed {
+            event_series.remove_unmatched_occurrences(user_id, conn)?;
+        }
+
+        DomainEvent::create(
+            DomainEventTypes::EventSeriesUpdated,
+            "Event series updated".to_string(),
+            Tables::EventSeries,
+            Some(event_series.id),
+            Some(user_id),
+            Some(json!(&event_series)),
+        )
+        .commit(conn)?;
+
+        Ok(event_series)
+    }
+
+    /// After a rule change upcoming events that no longer match it are removed, events that have
+    /// already sold tickets are kept in the series
+    fn remove_unmatched_occurrences(&self, user_id: Uuid, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let template_event = self.template_event(conn)?;
+        let now = Utc::now().naive_utc();
+        let occurrences = self.occurrences(conn)?;
+        let until = occurrences
+            .iter()
+            .map(|o| o.occurrence_start)
+            .max()
+            .unwrap_or(now)
+            .max(now + Duration::days(EVENT_SERIES_GENERATION_HORIZON_DAYS));
+        let occurrence_starts = self.occurrence_starts(&template_event, until, conn)?;
+
+        for occurrence in occurrences {
+            if occurrence.occurrence_start < now
+                || occurrence.event_id == self.template_event_id
+                || occurrence_starts.contains(&occurrence.occurrence_start)
+            {
+                continue;
+            }
+            let event = match Event::find(occurrence.event_id, conn).optional()? {
+                Some(event) => event,
+                None => continue,
+            };
+            if !event.eligible_for_deletion(conn)? {
+                continue;
+            }
+            event.delete(user_id, conn)?;
+            diesel::delete(&occurrence)
+                .execute(conn)
+                .to_db_error(ErrorCode::DeleteError, "Could not remove event series occurrence")?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies the changes to the event and, depending on the scope, its later or all other occurrences.
+    /// Occurrences generated afterwards use the edited event as their template.
+    pub fn update_events(
+        &self,
+        event: &Event,
+        scope: EventSeriesEditScopes,
+        attributes: EventSeriesEventAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Vec<Event>, DatabaseError> {
+        attributes.validate()?;
+        let occurrence_start = self.occurrence_start_for_event(event.id, conn)?;
+        let occurrences = self.occurrences(conn)?;
+        let event_ids = occurrences
+            .iter()
+            .filter(|o| match scope {
+                EventSeriesEditScopes::ThisEvent => o.event_id == event.id,
+                EventSeriesEditScopes::ThisAndFuture => o.occurrence_start >= occurrence_start,
+                EventSeriesEditScopes::AllEvents => true,
+            })
+            .map(|o| o.event_id)
+            .collect::<Vec<Uuid>>();
+
+        let mut updated_events = vec![];
+        for series_event in self.events(conn)? {
+            if !event_ids.contains(&series_event.id) {
+                continue;
+            }
+            let updated_event = series_event.update(current_user_id, attributes.event_attributes(), conn)?;
+            if let Some(ref artists) = attributes.artists {
+                // Set times keep their distance from the event start
+                let set_time_offset = match (series_event.event_start, event.event_start) {
+                    (Some(series_event_start), Some(event_start)) => {
+                        series_event_start.signed_duration_since(event_start)
+                    }
+                    _ => Duration::zero(),
+                };
+                EventArtist::clear_all_from_event(series_event.id, conn)?;
+                for (rank, artist) in artists.iter().enumerate() {
+                    EventArtist::create(
+                        series_event.id,
+                        artist.artist_id,
+                        rank as i32,
+                        artist.set_time.map(|set_time| set_time + set_time_offset),
+                        artist.importance,
+                        artist.stage_id,
+                    )
+                    .commit(current_user_id, conn)?;
+                }
+            }
+            updated_events.push(updated_event);
+        }
+
+        let event_series = if scope != EventSeriesEditScopes::ThisEvent && !event_ids.contains(&self.template_event_id)
+        {
+            diesel::update(self)
+                .set((
+                    event_series::template_event_id.eq(event.id),
+                    event_series::updated_at.eq(dsl::now),
+                ))
+                .get_result(conn)
+                .to_db_error(ErrorCode::UpdateError, "Could not update event series template")?
+        } else {
+            self.clone()
+        };
+
+        DomainEvent::create(
+            DomainEventTypes::EventSeriesOccurrencesUpdated,
+            "Event series occurrences updated".to_string(),
+            Tables::EventSeries,
+            Some(event_series.id),
+            current_user_id,
+            Some(json!({
+                "event_id": event.id,
+                "scope": scope,
+                "event_ids": updated_events.iter().map(|e| e.id).collect::<Vec<Uuid>>(),
+                "template_event_id": event_series.template_event_id
+            })),
+        )
+        .commit(conn)?;
+
+        Ok(updated_events)
+    }
+
+    /// Removes the series, its events are kept as standalone events
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::EventSeriesDeleted,
+            "Event series deleted".to_string(),
+            Tables::EventSeries,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((
+                event_series::deleted_at.eq(dsl::now.nullable()),
+                event_series::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete event series")?;
+        Ok(())
+    }
+
+    pub fn dashboard(&self, conn: &PgConnection) -> Result<EventSeriesDashboard, DatabaseError> {
+        let event_series = self.for_display(conn)?;
+        let mut events = Event::find_summary_data(
+            self.organization_id,
+            None,
+            Some(event_series.event_ids.clone()),
+            false,
+            0,
+            event_series.event_ids.len() as u32,
+            conn,
+        )?;
+        events.sort_by_key(|e| e.event_start);
+
+        Ok(EventSeriesDashboard {
+            total_tickets: events.iter().map(|e| e.total_tickets).sum(),
+            sold: events
+                .iter()
+                .map(|e| e.sold_unreserved.unwrap_or(0) + e.sold_held.unwrap_or(0))
+                .sum(),
+            tickets_redeemed: events.iter().map(|e| e.tickets_redeemed).sum(),
+            sales_total_in_cents: events.iter().map(|e| e.sales_total_in_cents.unwrap_or(0)).sum(),
+            event_series,
+            events,
+        })
+    }
+
+    pub fn recurrence_rule_valid(recurrence_rule: &str) -> Result<(), ValidationError> {
+        match recurrence_rule.parse::<RecurrenceRule>() {
+            Ok(rule) => {
+                if rule
+                    .count
+                    .map(|c| c as usize > MAX_EVENT_SERIES_OCCURRENCES)
+                    .unwrap_or(false)
+                {
+                    let mut validation_error = create_validation_error(
+                        "too_many_occurrences",
+                        "Event series cannot have more occurrences than the maximum",
+                    );
+                    validation_error.add_param(Cow::from("maximum"), &MAX_EVENT_SERIES_OCCURRENCES);
+                    return Err(validation_error);
+                }
+                Ok(())
+            }
+            Err(reason) => {
+                let mut validation_error =
+                    create_validation_error("invalid_recurrence_rule", "Recurrence rule is invalid");
+                validation_error.add_param(Cow::from("reason"), &reason);
+                Err(validation_error)
+            }
+        }
+    }
+
+    fn template_event_valid(
+        organization_id: Uuid,
+        template_event: &Event,
+        conn: &PgConnection,
+    ) -> Result<Result<(), ValidationError>, DatabaseError> {
+        if template_event.organization_id != organization_id {
+            return Ok(Err(create_validation_error(
+                "event_organization_mismatch",
+                "Event must belong to the series' organization",
+            )));
+        }
+        if template_event.event_start.is_none() {
+            return Ok(Err(create_validation_error(
+                "event_start_required",
+                "Event must have a start date to repeat",
+            )));
+        }
+        if EventSeries::find_for_event(template_event.id, conn)?.is_some() {
+            return Ok(Err(create_validation_error(
+                "event_already_in_series",
+                "Event is already part of a series",
+            )));
+        }
+        Ok(Ok(()))
+    }
+}
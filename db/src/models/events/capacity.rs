@@ -0,0 +1,148 @@
+use super::Event;
+use diesel;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable, Uuid as dUuid};
+use models::{Stage, TicketType};
+use schema::events;
+use std::borrow::Cow;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::ValidationError;
+use validators::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EventCapacity {
+    pub capacity: Option<i64>,
+    pub used: i64,
+    pub remaining: Option<i64>,
+    pub stages: Vec<StageCapacity>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StageCapacity {
+    pub stage_id: Uuid,
+    pub name: String,
+    pub capacity: Option<i64>,
+    pub used: i64,
+    pub remaining: Option<i64>,
+}
+
+#[derive(QueryableByName)]
+struct CapacityUsage {
+    #[sql_type = "Nullable<dUuid>"]
+    stage_id: Option<Uuid>,
+    #[sql_type = "BigInt"]
+    used: i64,
+}
+
+impl Event {
+    /// Tickets that are sold, in an unexpired cart or held count towards the event's capacity and the
+    /// capacity of their ticket type's stage
+    pub fn capacity_summary(&self, conn: &PgConnection) -> Result<EventCapacity, DatabaseError> {
+        let usage = self.capacity_usage(conn)?;
+        let used = usage.iter().map(|u| u.used).sum();
+
+        let mut stages = Vec::new();
+        for (stage_id, stage_usage) in usage.iter().filter_map(|u| u.stage_id.map(|id| (id, u))) {
+            let stage = Stage::find(stage_id, conn)?;
+            stages.push(StageCapacity {
+                stage_id: stage.id,
+                name: stage.name,
+                capacity: stage.capacity,
+                used: stage_usage.used,
+                remaining: stage.capacity.map(|c| (c - stage_usage.used).max(0)),
+            });
+        }
+        stages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(EventCapacity {
+            capacity: self.capacity,
+            used,
+            remaining: self.capacity.map(|c| (c - used).max(0)),
+            stages,
+        })
+    }
+
+    /// Checked before tickets are taken from general inventory, either into a cart or a hold. Tickets
+    /// reserved from a hold already count towards capacity.
+    pub(crate) fn check_capacity(
+        &self,
+        ticket_type: &TicketType,
+        quantity: u32,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        let stage = match ticket_type.stage_id {
+            Some(stage_id) => Some(Stage::find(stage_id, conn)?),
+            None => None,
+        };
+        let stage_capacity = stage.as_ref().and_then(|s| s.capacity);
+        if self.capacity.is_none() && stage_capacity.is_none() {
+            return Ok(());
+        }
+
+        // Concurrent checkouts for the event wait on this lock so the last places are only taken once
+        events::table
+            .filter(events::id.eq(self.id))
+            .select(events::id)
+            .for_update()
+            .first::<Uuid>(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not lock event for capacity check")?;
+
+        let usage = self.capacity_usage(conn)?;
+        let mut validation_errors = Ok(());
+        if let Some(capacity) = self.capacity {
+            validation_errors = append_validation_error(
+                validation_errors,
+                "quantity",
+                Event::capacity_valid(
+                    capacity,
+                    usage.iter().map(|u| u.used).sum(),
+                    quantity,
+                    "event_capacity_exceeded",
+                    "Not enough capacity remaining for the event",
+                ),
+            );
+        }
+        if let (Some(stage), Some(capacity)) = (stage, stage_capacity) {
+            validation_errors = append_validation_error(
+                validation_errors,
+                "quantity",
+                Event::capacity_valid(
+                    capacity,
+                    usage
+                        .iter()
+                        .filter(|u| u.stage_id == Some(stage.id))
+                        .map(|u| u.used)
+                        .sum(),
+                    quantity,
+                    "stage_capacity_exceeded",
+                    "Not enough capacity remaining for the stage",
+                ),
+            );
+        }
+        Ok(validation_errors?)
+    }
+
+    fn capacity_valid(
+        capacity: i64,
+        used: i64,
+        quantity: u32,
+        code: &'static str,
+        message: &'static str,
+    ) -> Result<(), ValidationError> {
+        if used + quantity as i64 <= capacity {
+            return Ok(());
+        }
+        let mut validation_error = create_validation_error(code, message);
+        validation_error.add_param(Cow::from("remaining"), &(capacity - used).max(0));
+        Err(validation_error)
+    }
+
+    fn capacity_usage(&self, conn: &PgConnection) -> Result<Vec<CapacityUsage>, DatabaseError> {
+        let query = include_str!("../../queries/event_capacity_usage.sql");
+        diesel::sql_query(query)
+            .bind::<dUuid, _>(self.id)
+            .get_results(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load event capacity usage")
+    }
+}
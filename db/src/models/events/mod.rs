@@ -1,4 +1,6 @@
+mod capacity;
 mod model;
+pub use self::capacity::*;
 pub(crate) use self::model::{EventData, EventEditableAttributesData, EventId, NewEventData};
 
 use chrono::prelude::*;
@@ -33,6 +35,11 @@ use validator::{Validate, ValidationErrors};
 use validators;
 use validators::*;
 
+// Rotating barcodes need to stay on screen long enough to be scanned but change before a
+// screenshot can be passed on
+const MIN_BARCODE_ROTATION_SECONDS: i32 = 10;
+const MAX_BARCODE_ROTATION_SECONDS: i32 = 300;
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct Event {
     pub id: Uuid,
@@ -70,6 +77,11 @@ pub struct Event {
     pub top_line_info: Option<String>,
     pub additional_info: Option<String>,
     pub promo_image_url: Option<String>,
+    pub currency: Option<Currencies>,
+    /// Redeem codes shown by the holder's app rotate this often, events without it use static barcodes
+    pub barcode_rotation_seconds: Option<i32>,
+    /// Limit on tickets sold, reserved or held across all of the event's ticket types
+    pub capacity: Option<i64>,
 }
 
 impl PartialOrd for Event {
@@ -123,6 +135,12 @@ pub struct NewEvent {
     #[serde(default, deserialize_with = "deserialize_unless_blank")]
     pub facebook_event_id: Option<String>,
     pub cloned_from_event_id: Option<Uuid>,
+    #[serde(default)]
+    pub currency: Option<Currencies>,
+    #[serde(default)]
+    pub barcode_rotation_seconds: Option<i32>,
+    #[serde(default)]
+    pub capacity: Option<i64>,
 }
 
 pub enum TicketHoldersCountType {
@@ -148,7 +166,7 @@ impl NewEvent {
             None => (),
         }
 
-        validators::append_validation_error(
+        let validation_errors = validators::append_validation_error(
             Ok(()),
             "event.event_end",
             validators::n_date_valid(
@@ -159,7 +177,10 @@ impl NewEvent {
                 "event_start",
                 "event_end",
             ),
-        )?;
+        );
+        let validation_errors =
+            Event::validate_barcode_rotation_seconds(validation_errors, new_event.barcode_rotation_seconds);
+        Event::validate_capacity(validation_errors, new_event.capacity)?;
 
         let event_json_data = Some(json!(&new_event)); // for back compatibility
         let data: NewEventData = new_event.into();
@@ -249,6 +270,12 @@ pub struct EventEditableAttributes {
     #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
     pub facebook_event_id: Option<Option<String>>,
     pub cloned_from_event_id: Option<Option<Uuid>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub currency: Option<Option<Currencies>>,
+    #[serde(default, deserialize_with = "double_option::deserialize")]
+    pub barcode_rotation_seconds: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option::deserialize")]
+    pub capacity: Option<Option<i64>>,
 }
 
 #[derive(Debug, Default, PartialEq, Serialize)]
@@ -311,14 +338,22 @@ impl Event {
         event.video_url = self.video_url.clone();
         event.is_external = self.is_external;
         event.external_url = self.external_url.clone();
+        event.currency = self.currency;
+        event.barcode_rotation_seconds = self.barcode_rotation_seconds;
+        event.capacity = self.capacity;
         let event = event.commit(current_user_id, conn)?;
 
+        // Set times move with the event
+        let set_time_offset = self
+            .event_start
+            .map(|event_start| clone_fields.event_start.signed_duration_since(event_start))
+            .unwrap_or_else(Duration::zero);
         for event_artist in EventArtist::find_all_from_event(self.id, conn)? {
             EventArtist::create(
                 event.id,
                 event_artist.artist.id,
                 event_artist.rank,
-                event_artist.set_time,
+                event_artist.set_time.map(|set_time| set_time + set_time_offset),
                 event_artist.importance,
                 event_artist.stage_id,
             )
@@ -393,6 +428,10 @@ impl Event {
             current_user_id,
             conn,
         )?;
+        let new_ticket_type = match ticket_type.stage_id {
+            Some(stage_id) => new_ticket_type.update_stage(Some(stage_id), current_user_id, conn)?,
+            None => new_ticket_type,
+        };
 
         for child_ticket_type in ticket_type.find_dependent_ticket_types(conn)? {
             if child_ticket_type.status == TicketTypeStatus::Cancelled
@@ -489,7 +528,11 @@ impl Event {
         let venue = event.venue(conn)?;
         let localized_times = event.get_all_localized_times(venue.as_ref());
 
-        let event_url = format!("{}/tickets/{}", front_end_url, event.slug(conn)?).to_string();
+        let event_url = format!(
+            "{}/tickets/{}",
+            organization.front_end_url(front_end_url, conn)?,
+            event.slug(conn)?
+        );
 
         data.insert("show_event_url".to_string(), json!(event_url));
         data.insert("show_id".to_string(), json!(event.id));
@@ -690,6 +733,13 @@ impl Event {
             ),
         );
 
+        if let Some(barcode_rotation_seconds) = attributes.barcode_rotation_seconds {
+            validation_errors = Event::validate_barcode_rotation_seconds(validation_errors, barcode_rotation_seconds);
+        }
+        if let Some(capacity) = attributes.capacity {
+            validation_errors = Event::validate_capacity(validation_errors, capacity);
+        }
+
         let associated_with_active_orders = self.associated_with_active_orders(conn)?;
 
         if associated_with_active_orders {
@@ -722,11 +772,74 @@ impl Event {
                     }
                 }
             }
+
+            if let Some(currency) = attributes.currency {
+                if currency != self.currency {
+                    validation_errors = validators::append_validation_error(
+                        validation_errors,
+                        "event.currency",
+                        Err(create_validation_error(
+                            "cannot_change_currency_with_sales",
+                            "Event with sales cannot change currency.",
+                        )),
+                    );
+                }
+            }
         }
 
         Ok(validation_errors?)
     }
 
+    fn validate_capacity(
+        validation_errors: Result<(), ValidationErrors>,
+        capacity: Option<i64>,
+    ) -> Result<(), ValidationErrors> {
+        let capacity = match capacity {
+            Some(capacity) => capacity,
+            None => return validation_errors,
+        };
+        validators::append_validation_error(
+            validation_errors,
+            "event.capacity",
+            validators::validate_greater_than_or_equal(
+                capacity,
+                1,
+                "capacity_less_than_one",
+                "Capacity must be at least 1",
+            ),
+        )
+    }
+
+    fn validate_barcode_rotation_seconds(
+        validation_errors: Result<(), ValidationErrors>,
+        barcode_rotation_seconds: Option<i32>,
+    ) -> Result<(), ValidationErrors> {
+        let barcode_rotation_seconds = match barcode_rotation_seconds {
+            Some(barcode_rotation_seconds) => barcode_rotation_seconds,
+            None => return validation_errors,
+        };
+        let validation_errors = validators::append_validation_error(
+            validation_errors,
+            "event.barcode_rotation_seconds",
+            validators::validate_greater_than_or_equal(
+                barcode_rotation_seconds,
+                MIN_BARCODE_ROTATION_SECONDS,
+                "barcode_rotation_too_short",
+                "Barcodes cannot rotate more often than every 10 seconds",
+            ),
+        );
+        validators::append_validation_error(
+            validation_errors,
+            "event.barcode_rotation_seconds",
+            validators::validate_less_than_or_equal(
+                barcode_rotation_seconds,
+                MAX_BARCODE_ROTATION_SECONDS,
+                "barcode_rotation_too_long",
+                "Barcodes must rotate at least every 5 minutes",
+            ),
+        )
+    }
+
     pub fn associated_with_active_orders(&self, conn: &PgConnection) -> Result<bool, DatabaseError> {
         select(exists(
             order_items::table
@@ -1419,7 +1532,7 @@ impl Event {
         Ok(results.remove(0))
     }
 
-    fn find_summary_data(
+    pub(crate) fn find_summary_data(
         organization_id: Uuid,
         past_or_upcoming: Option<PastOrUpcoming>,
         event_ids: Option<Vec<Uuid>>,
@@ -1773,7 +1886,9 @@ impl Event {
                 , sql::<Nullable<Text>>("users.email AS email")
                 , sql::<Nullable<Text>>("users.phone AS phone")
                 , sql::<Nullable<Text>>("CASE WHEN events.redeem_date IS NULL OR NOW() >= events.redeem_date OR NOW() >= events.event_start - INTERVAL '1 day 1 minute' THEN ticket_instances.redeem_key ELSE NULL END AS redeem_key")
+                , sql::<Nullable<Text>>("CASE WHEN events.redeem_date IS NULL OR NOW() >= events.redeem_date OR NOW() >= events.event_start - INTERVAL '1 day 1 minute' THEN ticket_instances.redeem_secret ELSE NULL END AS redeem_secret")
                 , sql::<Nullable<Timestamp>>("events.redeem_date AS redeem_date")
+                , sql::<Nullable<Integer>>("events.barcode_rotation_seconds AS barcode_rotation_seconds")
                 , sql::<Text>("ticket_instances.status AS status")
                 , sql::<dUuid>("events.id AS event_id")
                 , sql::<Text>("events.name AS event_name")
@@ -1835,6 +1950,9 @@ impl Event {
                 .load(conn)
                 .to_db_error(ErrorCode::QueryError, "Could not load pending transfers")?;
 
+        let checkout_answers =
+            CheckoutAnswer::find_display_for_orders(tickets.iter().map(|t| t.order_id).collect(), conn)?;
+
         let mut pending_transfers_by_ticket: HashMap<Uuid, PendingTransfer> = HashMap::new();
         for pending_transfer in pending_transfers {
             if pending_transfer.ticket_instance_id.is_some() {
@@ -1852,10 +1970,16 @@ impl Event {
                 }
             }
             let pending_transfer = pending_transfers_by_ticket.get(&t.id).map(|x| x.clone());
+            let ticket_checkout_answers = checkout_answers
+                .iter()
+                .filter(|a| a.order_id == t.order_id && a.ticket_instance_id.map(|id| id == t.id).unwrap_or(true))
+                .cloned()
+                .collect();
             guests.push(GuestListItem {
                 ticket: t.clone(),
                 providers,
                 pending_transfer,
+                checkout_answers: ticket_checkout_answers,
             })
         }
 
@@ -2101,6 +2225,14 @@ impl Event {
         Organization::find(self.organization_id, conn)
     }
 
+    /// Currency the event is sold in, events use their organization's currency unless overridden
+    pub fn currency(&self, conn: &PgConnection) -> Result<Currencies, DatabaseError> {
+        match self.currency {
+            Some(currency) => Ok(currency),
+            None => Ok(self.organization(conn)?.currency),
+        }
+    }
+
     pub fn venue(&self, conn: &PgConnection) -> Result<Option<Venue>, DatabaseError> {
         match self.venue_id {
             Some(venue_id) => {
@@ -2279,6 +2411,8 @@ impl Event {
             venue: display_venue,
             max_ticket_price,
             min_ticket_price,
+            currency: self.currency(conn)?,
+            barcode_rotation_seconds: self.barcode_rotation_seconds,
             video_url: self.video_url.clone(),
             is_external: self.is_external,
             external_url: self.external_url.clone(),
@@ -2306,6 +2440,8 @@ pub struct DisplayEvent {
     pub venue: Option<DisplayVenue>,
     pub min_ticket_price: Option<i64>,
     pub max_ticket_price: Option<i64>,
+    pub currency: Currencies,
+    pub barcode_rotation_seconds: Option<i32>,
     pub video_url: Option<String>,
     pub is_external: bool,
     pub external_url: Option<String>,
@@ -2418,4 +2554,5 @@ pub struct GuestListItem {
     pub ticket: RedeemableTicket,
     pub providers: Vec<String>,
     pub pending_transfer: Option<PendingTransfer>,
+    pub checkout_answers: Vec<DisplayCheckoutAnswer>,
 }
@@ -6,7 +6,7 @@ use diesel::query_dsl::{QueryDsl, RunQueryDsl};
 use diesel::serialize::{self, Output, ToSql};
 use diesel::sql_types::Jsonb;
 use diesel::ExpressionMethods;
-use models::{EventOverrideStatus, EventStatus, EventTypes, Organization, Venue};
+use models::{Currencies, EventOverrideStatus, EventStatus, EventTypes, Organization, Venue};
 use schema::events;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -111,6 +111,9 @@ pub(crate) struct EventData {
     pub settled_at: Option<NaiveDateTime>,
     pub cloned_from_event_id: Option<Uuid>,
     pub additional_json: EventAdditionalJson,
+    pub currency: Option<Currencies>,
+    pub barcode_rotation_seconds: Option<i32>,
+    pub capacity: Option<i64>,
 }
 
 impl From<EventData> for Event {
@@ -151,6 +154,9 @@ impl From<EventData> for Event {
             top_line_info: event.additional_json.top_line_info,
             additional_info: event.additional_json.additional_info,
             promo_image_url: event.additional_json.promo_image_url,
+            currency: event.currency,
+            barcode_rotation_seconds: event.barcode_rotation_seconds,
+            capacity: event.capacity,
         }
     }
 }
@@ -190,6 +196,9 @@ impl From<Event> for EventData {
             settled_at: event.settled_at,
             cloned_from_event_id: event.cloned_from_event_id,
             additional_json,
+            currency: event.currency,
+            barcode_rotation_seconds: event.barcode_rotation_seconds,
+            capacity: event.capacity,
         }
     }
 }
@@ -223,6 +232,9 @@ pub(crate) struct NewEventData {
     pub facebook_event_id: Option<String>,
     pub cloned_from_event_id: Option<Uuid>,
     pub additional_json: EventAdditionalJson,
+    pub currency: Option<Currencies>,
+    pub barcode_rotation_seconds: Option<i32>,
+    pub capacity: Option<i64>,
 }
 
 impl From<NewEvent> for NewEventData {
@@ -249,6 +261,9 @@ impl From<NewEvent> for NewEventData {
             facebook_event_id: event.facebook_event_id.clone(),
             cloned_from_event_id: event.cloned_from_event_id,
             additional_json,
+            currency: event.currency,
+            barcode_rotation_seconds: event.barcode_rotation_seconds,
+            capacity: event.capacity,
         }
     }
 }
@@ -275,6 +290,9 @@ pub(crate) struct EventEditableAttributesData {
     pub facebook_event_id: Option<Option<String>>,
     pub cloned_from_event_id: Option<Option<Uuid>>,
     pub additional_json: Option<EventAdditionalJson>,
+    pub currency: Option<Option<Currencies>>,
+    pub barcode_rotation_seconds: Option<Option<i32>>,
+    pub capacity: Option<Option<i64>>,
 }
 
 impl EventEditableAttributesData {
@@ -304,6 +322,9 @@ impl EventEditableAttributesData {
             cloned_from_event_id: event.cloned_from_event_id,
             cancelled_at: event.cancelled_at,
             sendgrid_list_id: event.sendgrid_list_id,
+            currency: event.currency,
+            barcode_rotation_seconds: event.barcode_rotation_seconds,
+            capacity: event.capacity,
             additional_json,
         })
     }
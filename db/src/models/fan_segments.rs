@@ -0,0 +1,323 @@
+use chrono::prelude::*;
+use chrono::Duration;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double, Integer, Nullable, Text, Timestamp, Uuid as dUuid};
+use models::*;
+use schema::fan_segments;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationError, ValidationErrors};
+use validators::{self, *};
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "fan_segments"]
+pub struct FanSegment {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub min_events_attended: Option<i32>,
+    pub min_revenue_in_cents: Option<i64>,
+    pub genre_id: Option<Uuid>,
+    pub inactive_days: Option<i32>,
+    pub venue_id: Option<Uuid>,
+    pub venue_radius_in_km: Option<f64>,
+    pub redemption_code: Option<String>,
+    pub sendgrid_list_id: Option<i64>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Default, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "fan_segments"]
+pub struct NewFanSegment {
+    #[serde(default)]
+    pub organization_id: Uuid,
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub min_events_attended: Option<i32>,
+    pub min_revenue_in_cents: Option<i64>,
+    pub genre_id: Option<Uuid>,
+    pub inactive_days: Option<i32>,
+    pub venue_id: Option<Uuid>,
+    pub venue_radius_in_km: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub redemption_code: Option<String>,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "fan_segments"]
+pub struct FanSegmentEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub min_events_attended: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub min_revenue_in_cents: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub genre_id: Option<Option<Uuid>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub inactive_days: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub venue_id: Option<Option<Uuid>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub venue_radius_in_km: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub redemption_code: Option<Option<String>>,
+}
+
+#[derive(QueryableByName)]
+struct FanSegmentMember {
+    #[sql_type = "BigInt"]
+    total: i64,
+    #[diesel(embed)]
+    fan: DisplayFan,
+}
+
+impl FanSegment {
+    pub fn create(organization_id: Uuid, name: String) -> NewFanSegment {
+        NewFanSegment {
+            organization_id,
+            name,
+            ..Default::default()
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<FanSegment, DatabaseError> {
+        fan_segments::table
+            .filter(fan_segments::id.eq(id))
+            .filter(fan_segments::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load fan segment")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        page: u32,
+        limit: u32,
+        conn: &PgConnection,
+    ) -> Result<Payload<FanSegment>, DatabaseError> {
+        use utils::pagination::Paginate;
+
+        let (segments, total) = fan_segments::table
+            .filter(fan_segments::organization_id.eq(organization_id))
+            .filter(fan_segments::deleted_at.is_null())
+            .order_by(fan_segments::name.asc())
+            .paginate(page as i64)
+            .per_page(limit as i64)
+            .load_and_count_pages(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load fan segments for organization")?;
+
+        Ok(Payload::from_data(segments, page, limit, Some(total as u64)))
+    }
+
+    pub fn organization(&self, conn: &PgConnection) -> Result<Organization, DatabaseError> {
+        Organization::find(self.organization_id, conn)
+    }
+
+    pub fn update(
+        &self,
+        attributes: FanSegmentEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<FanSegment, DatabaseError> {
+        FanSegment::validate_filters(
+            attributes.validate(),
+            attributes.min_events_attended.unwrap_or(self.min_events_attended),
+            attributes.min_revenue_in_cents.unwrap_or(self.min_revenue_in_cents),
+            attributes.inactive_days.unwrap_or(self.inactive_days),
+            attributes.venue_id.unwrap_or(self.venue_id),
+            attributes.venue_radius_in_km.unwrap_or(self.venue_radius_in_km),
+        )?;
+
+        let segment: FanSegment = diesel::update(self)
+            .set((attributes, fan_segments::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update fan segment")?;
+
+        DomainEvent::create(
+            DomainEventTypes::FanSegmentUpdated,
+            format!("Fan segment '{}' updated", &segment.name),
+            Tables::FanSegments,
+            Some(segment.id),
+            current_user_id,
+            Some(json!(&segment)),
+        )
+        .commit(conn)?;
+
+        Ok(segment)
+    }
+
+    pub fn set_sendgrid_list_id(
+        &self,
+        sendgrid_list_id: i64,
+        conn: &PgConnection,
+    ) -> Result<FanSegment, DatabaseError> {
+        diesel::update(self)
+            .set((
+                fan_segments::sendgrid_list_id.eq(sendgrid_list_id),
+                fan_segments::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update fan segment Sendgrid list")
+    }
+
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::FanSegmentDeleted,
+            format!("Fan segment '{}' deleted", &self.name),
+            Tables::FanSegments,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((
+                fan_segments::deleted_at.eq(dsl::now),
+                fan_segments::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete fan segment")?;
+
+        Ok(())
+    }
+
+    /// Fans matching all of the segment's filters, most recently interacted first.
+    /// The venue filter matches fans who bought tickets to events at venues within
+    /// `venue_radius_in_km` of the chosen venue as we do not store fan home addresses.
+    pub fn fans(&self, page: u32, limit: u32, conn: &PgConnection) -> Result<Payload<DisplayFan>, DatabaseError> {
+        let (fans, total) = self.load_fans(Some(limit as i64), (page * limit) as i64, conn)?;
+        Ok(Payload::from_data(fans, page, limit, Some(total as u64)))
+    }
+
+    /// All fans in the segment, used for exports, list syncs and broadcasts
+    pub fn all_fans(&self, conn: &PgConnection) -> Result<Vec<DisplayFan>, DatabaseError> {
+        Ok(self.load_fans(None, 0, conn)?.0)
+    }
+
+    pub fn all_users(&self, conn: &PgConnection) -> Result<Vec<User>, DatabaseError> {
+        let user_ids: Vec<Uuid> = self.all_fans(conn)?.into_iter().map(|f| f.user_id).collect();
+        User::find_by_ids(&user_ids, conn)
+    }
+
+    fn load_fans(
+        &self,
+        limit: Option<i64>,
+        offset: i64,
+        conn: &PgConnection,
+    ) -> Result<(Vec<DisplayFan>, i64), DatabaseError> {
+        let inactive_since = self
+            .inactive_days
+            .map(|days| Utc::now().naive_utc() - Duration::days(days as i64));
+
+        let query = include_str!("../queries/fan_segment_members.sql");
+        let members: Vec<FanSegmentMember> = diesel::sql_query(query)
+            .bind::<dUuid, _>(self.organization_id)
+            .bind::<Nullable<Integer>, _>(self.min_events_attended)
+            .bind::<Nullable<BigInt>, _>(self.min_revenue_in_cents)
+            .bind::<Nullable<dUuid>, _>(self.genre_id)
+            .bind::<Nullable<Timestamp>, _>(inactive_since)
+            .bind::<Nullable<dUuid>, _>(self.venue_id)
+            .bind::<Nullable<Double>, _>(self.venue_radius_in_km)
+            .bind::<Nullable<Text>, _>(self.redemption_code.clone())
+            .bind::<Nullable<BigInt>, _>(limit)
+            .bind::<BigInt, _>(offset)
+            .get_results(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load fans for fan segment")?;
+
+        let total = members.first().map(|m| m.total).unwrap_or(0);
+        Ok((members.into_iter().map(|m| m.fan).collect(), total))
+    }
+
+    fn validate_filters(
+        validation_errors: Result<(), ValidationErrors>,
+        min_events_attended: Option<i32>,
+        min_revenue_in_cents: Option<i64>,
+        inactive_days: Option<i32>,
+        venue_id: Option<Uuid>,
+        venue_radius_in_km: Option<f64>,
+    ) -> Result<(), DatabaseError> {
+        let mut validation_errors = validators::append_validation_error(
+            validation_errors,
+            "min_events_attended",
+            validate_greater_than_or_equal(
+                min_events_attended.unwrap_or(0),
+                0,
+                "min_events_attended_negative",
+                "Minimum events attended cannot be negative",
+            ),
+        );
+        validation_errors = validators::append_validation_error(
+            validation_errors,
+            "min_revenue_in_cents",
+            validate_greater_than_or_equal(
+                min_revenue_in_cents.unwrap_or(0),
+                0,
+                "min_revenue_in_cents_negative",
+                "Minimum revenue cannot be negative",
+            ),
+        );
+        validation_errors = validators::append_validation_error(
+            validation_errors,
+            "inactive_days",
+            validate_greater_than_or_equal(
+                inactive_days.unwrap_or(0),
+                0,
+                "inactive_days_negative",
+                "Inactive days cannot be negative",
+            ),
+        );
+        validation_errors = validators::append_validation_error(
+            validation_errors,
+            "venue_radius_in_km",
+            FanSegment::venue_filter_valid(venue_id, venue_radius_in_km),
+        );
+        Ok(validation_errors?)
+    }
+
+    fn venue_filter_valid(venue_id: Option<Uuid>, venue_radius_in_km: Option<f64>) -> Result<(), ValidationError> {
+        match (venue_id, venue_radius_in_km) {
+            (None, None) => Ok(()),
+            (Some(_), Some(radius)) if radius > 0.0 => Ok(()),
+            _ => Err(create_validation_error(
+                "venue_radius_required",
+                "A venue filter requires both a venue and a radius greater than zero",
+            )),
+        }
+    }
+}
+
+impl NewFanSegment {
+    pub fn commit(self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<FanSegment, DatabaseError> {
+        FanSegment::validate_filters(
+            self.validate(),
+            self.min_events_attended,
+            self.min_revenue_in_cents,
+            self.inactive_days,
+            self.venue_id,
+            self.venue_radius_in_km,
+        )?;
+
+        let segment: FanSegment = diesel::insert_into(fan_segments::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create fan segment")?;
+
+        DomainEvent::create(
+            DomainEventTypes::FanSegmentCreated,
+            format!("Fan segment '{}' created", &segment.name),
+            Tables::FanSegments,
+            Some(segment.id),
+            current_user_id,
+            Some(json!(&segment)),
+        )
+        .commit(conn)?;
+
+        Ok(segment)
+    }
+}
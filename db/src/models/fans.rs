@@ -1,22 +1,36 @@
 use chrono::NaiveDateTime;
-use diesel::sql_types::{BigInt, Nullable, Timestamp, Uuid as dUuid};
+use diesel::sql_types::{BigInt, Nullable, Text, Timestamp, Uuid as dUuid};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, PartialEq, Queryable, Serialize)]
+#[derive(Clone, Debug, PartialEq, Queryable, QueryableByName, Serialize)]
 pub struct DisplayFan {
+    #[sql_type = "dUuid"]
     pub user_id: Uuid,
+    #[sql_type = "Nullable<Text>"]
     pub first_name: Option<String>,
+    #[sql_type = "Nullable<Text>"]
     pub last_name: Option<String>,
+    #[sql_type = "Nullable<Text>"]
     pub email: Option<String>,
+    #[sql_type = "Nullable<Text>"]
     pub phone: Option<String>,
+    #[sql_type = "Nullable<Text>"]
     pub thumb_profile_pic_url: Option<String>,
+    #[sql_type = "dUuid"]
     pub organization_id: Uuid,
+    #[sql_type = "Nullable<BigInt>"]
     pub order_count: Option<i64>,
+    #[sql_type = "Timestamp"]
     pub created_at: NaiveDateTime,
+    #[sql_type = "Nullable<Timestamp>"]
     pub first_order_time: Option<NaiveDateTime>,
+    #[sql_type = "Nullable<Timestamp>"]
     pub last_order_time: Option<NaiveDateTime>,
+    #[sql_type = "Nullable<BigInt>"]
     pub revenue_in_cents: Option<i64>,
+    #[sql_type = "Nullable<Timestamp>"]
     pub first_interaction_time: Option<NaiveDateTime>,
+    #[sql_type = "Nullable<Timestamp>"]
     pub last_interaction_time: Option<NaiveDateTime>,
 }
 
@@ -403,6 +403,13 @@ impl Hold {
     pub fn set_quantity(&self, user_id: Option<Uuid>, quantity: u32, conn: &PgConnection) -> Result<(), DatabaseError> {
         let (count, _available) = self.quantity(conn)?;
         if count < quantity {
+            // Comps are taken from their hold's tickets which already count towards capacity
+            if self.parent_hold_id.is_none() {
+                let ticket_type = TicketType::find(self.ticket_type_id, conn)?;
+                ticket_type
+                    .event(conn)?
+                    .check_capacity(&ticket_type, quantity - count, conn)?;
+            }
             TicketInstance::add_to_hold(
                 user_id,
                 self.id,
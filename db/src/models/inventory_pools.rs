@@ -0,0 +1,293 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Uuid as dUuid};
+use models::*;
+use schema::{inventory_pools, ticket_types};
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationError, ValidationErrors};
+use validators::{self, *};
+
+/// Ticket types in a pool share one stock of tickets, whichever sells first uses up the pool. Tickets are
+/// moved to the ticket type they are sold or held as so sales stay attributed to that ticket type.
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "inventory_pools"]
+pub struct InventoryPool {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub name: String,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "inventory_pools"]
+pub struct NewInventoryPool {
+    #[serde(default)]
+    pub event_id: Uuid,
+    #[validate(length(min = 1, message = "Name cannot be blank"))]
+    pub name: String,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "inventory_pools"]
+pub struct InventoryPoolEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, message = "Name cannot be blank"))]
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, QueryableByName, Serialize)]
+pub struct InventoryPoolCounts {
+    #[sql_type = "BigInt"]
+    pub capacity: i64,
+    #[sql_type = "BigInt"]
+    pub available: i64,
+    #[sql_type = "BigInt"]
+    pub held: i64,
+    #[sql_type = "BigInt"]
+    pub sold: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayInventoryPool {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub name: String,
+    pub ticket_type_ids: Vec<Uuid>,
+    pub capacity: i64,
+    pub available: i64,
+    pub held: i64,
+    pub sold: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl InventoryPool {
+    pub fn create(event_id: Uuid, name: String) -> NewInventoryPool {
+        NewInventoryPool { event_id, name }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<InventoryPool, DatabaseError> {
+        inventory_pools::table
+            .filter(inventory_pools::id.eq(id))
+            .filter(inventory_pools::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load inventory pool")
+    }
+
+    pub fn find_for_event(event_id: Uuid, conn: &PgConnection) -> Result<Vec<InventoryPool>, DatabaseError> {
+        inventory_pools::table
+            .filter(inventory_pools::event_id.eq(event_id))
+            .filter(inventory_pools::deleted_at.is_null())
+            .order_by(inventory_pools::name.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load inventory pools")
+    }
+
+    pub fn event(&self, conn: &PgConnection) -> Result<Event, DatabaseError> {
+        Event::find(self.event_id, conn)
+    }
+
+    pub fn ticket_type_ids(&self, conn: &PgConnection) -> Result<Vec<Uuid>, DatabaseError> {
+        ticket_types::table
+            .filter(ticket_types::inventory_pool_id.eq(self.id))
+            .filter(ticket_types::deleted_at.is_null())
+            .select(ticket_types::id)
+            .order_by(ticket_types::rank)
+            .then_order_by(ticket_types::name)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load inventory pool ticket types")
+    }
+
+    /// Replaces the pool's ticket types, a ticket type added here leaves any other pool it was in
+    pub fn update_ticket_types(
+        &self,
+        ticket_type_ids: Vec<Uuid>,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        validators::append_validation_error(
+            Ok(()),
+            "ticket_type_ids",
+            InventoryPool::ticket_types_valid(self.event_id, &ticket_type_ids, conn)?,
+        )?;
+
+        diesel::update(
+            ticket_types::table
+                .filter(ticket_types::inventory_pool_id.eq(self.id))
+                .filter(ticket_types::id.ne_all(ticket_type_ids.clone())),
+        )
+        .set((
+            ticket_types::inventory_pool_id.eq(None::<Uuid>),
+            ticket_types::updated_at.eq(dsl::now),
+        ))
+        .execute(conn)
+        .to_db_error(
+            ErrorCode::UpdateError,
+            "Could not remove ticket types from inventory pool",
+        )?;
+        diesel::update(ticket_types::table.filter(ticket_types::id.eq_any(ticket_type_ids.clone())))
+            .set((
+                ticket_types::inventory_pool_id.eq(self.id),
+                ticket_types::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not add ticket types to inventory pool")?;
+
+        DomainEvent::create(
+            DomainEventTypes::InventoryPoolTicketTypesUpdated,
+            "Inventory pool ticket types updated".to_string(),
+            Tables::InventoryPools,
+            Some(self.id),
+            current_user_id,
+            Some(json!({ "ticket_type_ids": ticket_type_ids })),
+        )
+        .commit(conn)?;
+
+        Ok(())
+    }
+
+    /// Counts across the tickets of every ticket type in the pool
+    pub fn counts(&self, conn: &PgConnection) -> Result<InventoryPoolCounts, DatabaseError> {
+        let query = r#"
+            SELECT
+                COUNT(ti.id) FILTER (WHERE ti.status <> 'Nullified') AS capacity,
+                COUNT(ti.id) FILTER (
+                    WHERE ti.hold_id IS NULL
+                    AND (ti.status = 'Available' OR (ti.status = 'Reserved' AND ti.reserved_until < now()))
+                ) AS available,
+                COUNT(ti.id) FILTER (WHERE ti.hold_id IS NOT NULL AND ti.status IN ('Available', 'Reserved')) AS held,
+                COUNT(ti.id) FILTER (WHERE ti.status IN ('Purchased', 'Redeemed')) AS sold
+            FROM ticket_types tt
+            JOIN assets a ON a.ticket_type_id = tt.id
+            JOIN ticket_instances ti ON ti.asset_id = a.id AND ti.parent_id IS NULL
+            WHERE tt.inventory_pool_id = $1
+            AND tt.deleted_at IS NULL;"#;
+
+        diesel::sql_query(query)
+            .bind::<dUuid, _>(self.id)
+            .get_result(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load inventory pool counts")
+    }
+
+    pub fn for_display(&self, conn: &PgConnection) -> Result<DisplayInventoryPool, DatabaseError> {
+        let counts = self.counts(conn)?;
+        Ok(DisplayInventoryPool {
+            id: self.id,
+            event_id: self.event_id,
+            name: self.name.clone(),
+            ticket_type_ids: self.ticket_type_ids(conn)?,
+            capacity: counts.capacity,
+            available: counts.available,
+            held: counts.held,
+            sold: counts.sold,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
+    }
+
+    pub fn update(
+        &self,
+        attributes: InventoryPoolEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<InventoryPool, DatabaseError> {
+        InventoryPool::validate_record(attributes.validate())?;
+
+        let inventory_pool: InventoryPool = diesel::update(self)
+            .set((attributes, inventory_pools::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update inventory pool")?;
+
+        DomainEvent::create(
+            DomainEventTypes::InventoryPoolUpdated,
+            "Inventory pool updated".to_string(),
+            Tables::InventoryPools,
+            Some(inventory_pool.id),
+            current_user_id,
+            Some(json!(&inventory_pool)),
+        )
+        .commit(conn)?;
+
+        Ok(inventory_pool)
+    }
+
+    /// Ticket types in the pool go back to selling only their own tickets
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        self.update_ticket_types(vec![], current_user_id, conn)?;
+
+        DomainEvent::create(
+            DomainEventTypes::InventoryPoolDeleted,
+            "Inventory pool deleted".to_string(),
+            Tables::InventoryPools,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((
+                inventory_pools::deleted_at.eq(dsl::now),
+                inventory_pools::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete inventory pool")?;
+
+        Ok(())
+    }
+
+    fn validate_record(validation_errors: Result<(), ValidationErrors>) -> Result<(), DatabaseError> {
+        Ok(validation_errors?)
+    }
+
+    fn ticket_types_valid(
+        event_id: Uuid,
+        ticket_type_ids: &[Uuid],
+        conn: &PgConnection,
+    ) -> Result<Result<(), ValidationError>, DatabaseError> {
+        for ticket_type_id in ticket_type_ids {
+            let ticket_type = TicketType::find(*ticket_type_id, conn)?;
+            if ticket_type.event_id != event_id {
+                return Ok(Err(create_validation_error(
+                    "ticket_type_does_not_belong_to_event",
+                    "Ticket types must belong to the inventory pool's event",
+                )));
+            }
+            if ticket_type.ticket_type_type != TicketTypeType::Token {
+                return Ok(Err(create_validation_error(
+                    "ticket_type_cannot_be_pooled",
+                    "Only token ticket types can share an inventory pool",
+                )));
+            }
+        }
+        Ok(Ok(()))
+    }
+}
+
+impl NewInventoryPool {
+    pub fn commit(self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<InventoryPool, DatabaseError> {
+        InventoryPool::validate_record(self.validate())?;
+
+        let inventory_pool: InventoryPool = diesel::insert_into(inventory_pools::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create inventory pool")?;
+
+        DomainEvent::create(
+            DomainEventTypes::InventoryPoolCreated,
+            "Inventory pool created".to_string(),
+            Tables::InventoryPools,
+            Some(inventory_pool.id),
+            current_user_id,
+            Some(json!(&inventory_pool)),
+        )
+        .commit(conn)?;
+
+        Ok(inventory_pool)
+    }
+}
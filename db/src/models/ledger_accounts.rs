@@ -0,0 +1,137 @@
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable};
+use models::*;
+use schema::{ledger_accounts, ledger_lines};
+use utils::errors::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "ledger_accounts"]
+pub struct LedgerAccount {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub account_type: LedgerAccountTypes,
+    pub currency: Currencies,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "ledger_accounts"]
+pub struct NewLedgerAccount {
+    pub organization_id: Uuid,
+    pub account_type: LedgerAccountTypes,
+    pub currency: Currencies,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TrialBalanceAccount {
+    pub ledger_account_id: Uuid,
+    pub account_type: LedgerAccountTypes,
+    pub debit_in_cents: i64,
+    pub credit_in_cents: i64,
+    /// Balance in the account's normal direction, debits less credits for cash and credits less
+    /// debits for the remaining accounts
+    pub balance_in_cents: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TrialBalance {
+    pub organization_id: Uuid,
+    pub currency: Currencies,
+    pub accounts: Vec<TrialBalanceAccount>,
+    pub total_debit_in_cents: i64,
+    pub total_credit_in_cents: i64,
+    pub balanced: bool,
+}
+
+impl LedgerAccount {
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<LedgerAccount, DatabaseError> {
+        ledger_accounts::table
+            .find(id)
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ledger account")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        currency: Currencies,
+        conn: &PgConnection,
+    ) -> Result<Vec<LedgerAccount>, DatabaseError> {
+        ledger_accounts::table
+            .filter(ledger_accounts::organization_id.eq(organization_id))
+            .filter(ledger_accounts::currency.eq(currency))
+            .order_by(ledger_accounts::account_type)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ledger accounts")
+    }
+
+    /// Accounts are opened the first time an amount is recorded against them
+    pub fn find_or_create(
+        organization_id: Uuid,
+        account_type: LedgerAccountTypes,
+        currency: Currencies,
+        conn: &PgConnection,
+    ) -> Result<LedgerAccount, DatabaseError> {
+        diesel::insert_into(ledger_accounts::table)
+            .values(&NewLedgerAccount {
+                organization_id,
+                account_type,
+                currency,
+            })
+            .on_conflict((
+                ledger_accounts::organization_id,
+                ledger_accounts::account_type,
+                ledger_accounts::currency,
+            ))
+            .do_update()
+            .set(ledger_accounts::updated_at.eq(dsl::now))
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not open ledger account")
+    }
+
+    pub fn trial_balance(
+        organization_id: Uuid,
+        currency: Currencies,
+        conn: &PgConnection,
+    ) -> Result<TrialBalance, DatabaseError> {
+        let mut accounts = Vec::new();
+        for account in LedgerAccount::find_for_organization(organization_id, currency, conn)? {
+            let (debit_in_cents, credit_in_cents): (Option<i64>, Option<i64>) = ledger_lines::table
+                .filter(ledger_lines::ledger_account_id.eq(account.id))
+                .select((
+                    dsl::sql::<Nullable<BigInt>>("CAST(SUM(debit_in_cents) AS BIGINT)"),
+                    dsl::sql::<Nullable<BigInt>>("CAST(SUM(credit_in_cents) AS BIGINT)"),
+                ))
+                .first(conn)
+                .to_db_error(ErrorCode::QueryError, "Unable to load ledger account totals")?;
+            let debit_in_cents = debit_in_cents.unwrap_or(0);
+            let credit_in_cents = credit_in_cents.unwrap_or(0);
+            accounts.push(TrialBalanceAccount {
+                ledger_account_id: account.id,
+                account_type: account.account_type,
+                debit_in_cents,
+                credit_in_cents,
+                balance_in_cents: if account.account_type.is_debit_normal() {
+                    debit_in_cents - credit_in_cents
+                } else {
+                    credit_in_cents - debit_in_cents
+                },
+            });
+        }
+
+        let total_debit_in_cents = accounts.iter().map(|a| a.debit_in_cents).sum();
+        let total_credit_in_cents = accounts.iter().map(|a| a.credit_in_cents).sum();
+        Ok(TrialBalance {
+            organization_id,
+            currency,
+            accounts,
+            total_debit_in_cents,
+            total_credit_in_cents,
+            balanced: total_debit_in_cents == total_credit_in_cents,
+        })
+    }
+}
@@ -0,0 +1,501 @@
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Text, Uuid as dUuid};
+use models::*;
+use schema::{events, ledger_journal_entries, ledger_lines, refund_items, settlement_entries};
+use std::collections::HashMap;
+use utils::errors::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "ledger_journal_entries"]
+pub struct LedgerJournalEntry {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub currency: Currencies,
+    pub source_table: Tables,
+    pub source_id: Uuid,
+    pub description: String,
+    pub reverses_ledger_journal_entry_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "ledger_lines"]
+pub struct LedgerLine {
+    pub id: Uuid,
+    pub ledger_journal_entry_id: Uuid,
+    pub ledger_account_id: Uuid,
+    pub debit_in_cents: i64,
+    pub credit_in_cents: i64,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "ledger_lines"]
+struct NewLedgerLine {
+    ledger_journal_entry_id: Uuid,
+    ledger_account_id: Uuid,
+    debit_in_cents: i64,
+    credit_in_cents: i64,
+}
+
+/// Journal entry under construction, amounts posted to the same account are netted into a single
+/// line and the entry must balance before it can be committed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewLedgerJournalEntry {
+    pub organization_id: Uuid,
+    pub currency: Currencies,
+    pub source_table: Tables,
+    pub source_id: Uuid,
+    pub description: String,
+    pub reverses_ledger_journal_entry_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    amounts: Vec<(LedgerAccountTypes, i64)>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, QueryableByName, Serialize)]
+pub struct LedgerIntegrityIssue {
+    #[sql_type = "Text"]
+    pub issue_type: String,
+    #[sql_type = "Text"]
+    pub source_table: String,
+    #[sql_type = "dUuid"]
+    pub source_id: Uuid,
+    #[sql_type = "Nullable<dUuid>"]
+    pub ledger_journal_entry_id: Option<Uuid>,
+    #[sql_type = "Text"]
+    pub details: String,
+}
+
+impl NewLedgerJournalEntry {
+    /// Negative amounts are posted to the opposite side
+    pub fn debit(mut self, account_type: LedgerAccountTypes, amount_in_cents: i64) -> NewLedgerJournalEntry {
+        self.post(account_type, amount_in_cents);
+        self
+    }
+
+    /// Negative amounts are posted to the opposite side
+    pub fn credit(mut self, account_type: LedgerAccountTypes, amount_in_cents: i64) -> NewLedgerJournalEntry {
+        self.post(account_type, -amount_in_cents);
+        self
+    }
+
+    fn post(&mut self, account_type: LedgerAccountTypes, amount_in_cents: i64) {
+        match self.amounts.iter_mut().find(|(a, _)| *a == account_type) {
+            Some(amount) => amount.1 += amount_in_cents,
+            None => self.amounts.push((account_type, amount_in_cents)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.amounts.iter().all(|(_, amount)| *amount == 0)
+    }
+
+    pub fn commit(self, conn: &PgConnection) -> Result<LedgerJournalEntry, DatabaseError> {
+        if self.is_empty() {
+            return DatabaseError::business_process_error("Ledger journal entries must include at least one amount");
+        }
+        if self.amounts.iter().map(|(_, amount)| amount).sum::<i64>() != 0 {
+            return DatabaseError::business_process_error("Ledger journal entry debits and credits do not balance");
+        }
+
+        let entry: LedgerJournalEntry = diesel::insert_into(ledger_journal_entries::table)
+            .values((
+                ledger_journal_entries::organization_id.eq(self.organization_id),
+                ledger_journal_entries::currency.eq(self.currency),
+                ledger_journal_entries::source_table.eq(self.source_table),
+                ledger_journal_entries::source_id.eq(self.source_id),
+                ledger_journal_entries::description.eq(&self.description),
+                ledger_journal_entries::reverses_ledger_journal_entry_id.eq(self.reverses_ledger_journal_entry_id),
+                ledger_journal_entries::created_by.eq(self.created_by),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create ledger journal entry")?;
+
+        let mut lines = Vec::new();
+        for (account_type, amount_in_cents) in self.amounts.into_iter().filter(|(_, amount)| *amount != 0) {
+            let account = LedgerAccount::find_or_create(entry.organization_id, account_type, entry.currency, conn)?;
+            lines.push(NewLedgerLine {
+                ledger_journal_entry_id: entry.id,
+                ledger_account_id: account.id,
+                debit_in_cents: amount_in_cents.max(0),
+                credit_in_cents: (-amount_in_cents).max(0),
+            });
+        }
+        diesel::insert_into(ledger_lines::table)
+            .values(&lines)
+            .execute(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create ledger lines")?;
+
+        Ok(entry)
+    }
+}
+
+impl LedgerJournalEntry {
+    pub fn create(
+        organization_id: Uuid,
+        currency: Currencies,
+        source_table: Tables,
+        source_id: Uuid,
+        description: String,
+        created_by: Option<Uuid>,
+    ) -> NewLedgerJournalEntry {
+        NewLedgerJournalEntry {
+            organization_id,
+            currency,
+            source_table,
+            source_id,
+            description,
+            reverses_ledger_journal_entry_id: None,
+            created_by,
+            amounts: Vec::new(),
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<LedgerJournalEntry, DatabaseError> {
+        ledger_journal_entries::table
+            .find(id)
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ledger journal entry")
+    }
+
+    pub fn find_for_source(
+        source_table: Tables,
+        source_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<LedgerJournalEntry>, DatabaseError> {
+        ledger_journal_entries::table
+            .filter(ledger_journal_entries::source_table.eq(source_table))
+            .filter(ledger_journal_entries::source_id.eq(source_id))
+            .order_by(ledger_journal_entries::created_at)
+            .then_order_by(ledger_journal_entries::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ledger journal entries")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        currency: Currencies,
+        start_utc: Option<NaiveDateTime>,
+        end_utc: Option<NaiveDateTime>,
+        conn: &PgConnection,
+    ) -> Result<Vec<LedgerJournalEntry>, DatabaseError> {
+        let mut query = ledger_journal_entries::table
+            .filter(ledger_journal_entries::organization_id.eq(organization_id))
+            .filter(ledger_journal_entries::currency.eq(currency))
+            .into_boxed();
+        if let Some(start_utc) = start_utc {
+            query = query.filter(ledger_journal_entries::created_at.ge(start_utc));
+        }
+        if let Some(end_utc) = end_utc {
+            query = query.filter(ledger_journal_entries::created_at.le(end_utc));
+        }
+        query
+            .order_by(ledger_journal_entries::created_at)
+            .then_order_by(ledger_journal_entries::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ledger journal entries")
+    }
+
+    pub fn lines(&self, conn: &PgConnection) -> Result<Vec<LedgerLine>, DatabaseError> {
+        ledger_lines::table
+            .filter(ledger_lines::ledger_journal_entry_id.eq(self.id))
+            .order_by(ledger_lines::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ledger lines")
+    }
+
+    /// Records the sale once an order is fully paid, one entry per organization selling items on
+    /// the order. Cash is debited with the amount charged and the item's account credited.
+    pub fn record_order_payment(
+        order: &Order,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Vec<LedgerJournalEntry>, DatabaseError> {
+        let items = order.items(conn)?;
+        let amounts = items
+            .iter()
+            .map(|item| (item, item.unit_price_in_cents * item.quantity))
+            .filter(|(_, amount)| *amount != 0)
+            .collect::<Vec<(&OrderItem, i64)>>();
+
+        let mut entries = Vec::new();
+        for (organization_id, amounts) in LedgerJournalEntry::amounts_by_organization(order, &amounts, conn)? {
+            let mut entry = LedgerJournalEntry::create(
+                organization_id,
+                order.currency,
+                Tables::Orders,
+                order.id,
+                format!("Order {} paid", order.id),
+                current_user_id,
+            );
+            for (item_type, amount_in_cents) in amounts {
+                entry = entry
+                    .debit(LedgerAccountTypes::Cash, amount_in_cents)
+                    .credit(LedgerAccountTypes::for_order_item_type(item_type), amount_in_cents);
+            }
+            if !entry.is_empty() {
+                entries.push(entry.commit(conn)?);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Records the amounts returned to the customer for each refunded order item
+    pub fn record_refund(
+        order: &Order,
+        refund: &Refund,
+        conn: &PgConnection,
+    ) -> Result<Vec<LedgerJournalEntry>, DatabaseError> {
+        let refunded: Vec<(Uuid, i64)> = refund_items::table
+            .filter(refund_items::refund_id.eq(refund.id))
+            .select((refund_items::order_item_id, refund_items::amount))
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load refund items")?;
+        let items = order.items(conn)?;
+        let amounts = refunded
+            .iter()
+            .filter_map(|(order_item_id, amount)| {
+                items
+                    .iter()
+                    .find(|item| item.id == *order_item_id)
+                    .map(|item| (item, *amount))
+            })
+            .collect::<Vec<(&OrderItem, i64)>>();
+
+        let mut entries = Vec::new();
+        for (organization_id, amounts) in LedgerJournalEntry::amounts_by_organization(order, &amounts, conn)? {
+            let mut entry = LedgerJournalEntry::create(
+                organization_id,
+                order.currency,
+                Tables::Refunds,
+                refund.id,
+                format!("Refund {} for order {}", refund.id, order.id),
+                Some(refund.user_id),
+            );
+            for (item_type, amount_in_cents) in amounts {
+                entry = entry
+                    .debit(LedgerAccountTypes::for_order_item_type(item_type), amount_in_cents)
+                    .credit(LedgerAccountTypes::Cash, amount_in_cents);
+            }
+            if !entry.is_empty() {
+                entries.push(entry.commit(conn)?);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Moves the organization's share of settled sales into the amount payable to them, one entry
+    /// per currency the settled orders were paid in.
+    pub fn record_settlement(
+        settlement: &Settlement,
+        conn: &PgConnection,
+    ) -> Result<Vec<LedgerJournalEntry>, DatabaseError> {
+        let settlement_entries: Vec<SettlementEntry> = settlement_entries::table
+            .filter(settlement_entries::settlement_id.eq(settlement.id))
+            .order_by(settlement_entries::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load settlement entries")?;
+        let mut currencies: Vec<Currencies> = settlement_entries.iter().map(|e| e.currency).collect();
+        currencies.sort_by_key(|currency| currency.to_string());
+        currencies.dedup();
+
+        let mut entries = Vec::new();
+        for currency in currencies {
+            let mut entry = LedgerJournalEntry::create(
+                settlement.organization_id,
+                currency,
+                Tables::Settlements,
+                settlement.id,
+                format!("Settlement {} finalized", settlement.id),
+                None,
+            );
+            for settlement_entry in settlement_entries.iter().filter(|e| e.currency == currency) {
+                // Tax was held apart from sales when the order was paid
+                let face_value_account = match settlement_entry.settlement_entry_type {
+                    SettlementEntryTypes::Tax => LedgerAccountTypes::TaxCollected,
+                    _ => LedgerAccountTypes::GrossSales,
+                };
+                entry = entry
+                    .debit(
+                        face_value_account,
+                        settlement_entry.face_value_in_cents * settlement_entry.online_sold_quantity,
+                    )
+                    .debit(
+                        LedgerAccountTypes::PlatformFees,
+                        settlement_entry.revenue_share_value_in_cents * settlement_entry.fee_sold_quantity,
+                    )
+                    .credit(
+                        LedgerAccountTypes::PayableToOrganization,
+                        settlement_entry.total_sales_in_cents,
+                    );
+                // Commissions are taken from the organization's share and are owed to the partner or affiliate
+                match settlement_entry.settlement_entry_type {
+                    SettlementEntryTypes::PartnerCommission => {
+                        entry = entry.credit(
+                            LedgerAccountTypes::PartnerCommissions,
+                            -settlement_entry.total_sales_in_cents,
+                        );
+                    }
+                    SettlementEntryTypes::AffiliateCommission => {
+                        entry = entry.credit(
+                            LedgerAccountTypes::AffiliateCommissions,
+                            -settlement_entry.total_sales_in_cents,
+                        );
+                    }
+                    _ => (),
+                }
+            }
+            if !entry.is_empty() {
+                entries.push(entry.commit(conn)?);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Adjustments are recorded in the organization's currency, credits and deductions move
+    /// amounts between platform fees and the payable balance while chargebacks are taken from cash.
+    pub fn record_settlement_adjustment(
+        adjustment: &SettlementAdjustment,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Option<LedgerJournalEntry>, DatabaseError> {
+        let settlement = Settlement::find(adjustment.settlement_id, conn)?;
+        let organization = Organization::find(settlement.organization_id, conn)?;
+        let amount_in_cents = adjustment.amount_in_cents.abs();
+        let entry = LedgerJournalEntry::create(
+            organization.id,
+            organization.currency,
+            Tables::SettlementAdjustments,
+            adjustment.id,
+            format!("Settlement {} adjustment", settlement.id),
+            current_user_id,
+        );
+        let entry = match adjustment.settlement_adjustment_type {
+            SettlementAdjustmentTypes::ManualCredit => entry
+                .debit(LedgerAccountTypes::PlatformFees, amount_in_cents)
+                .credit(LedgerAccountTypes::PayableToOrganization, amount_in_cents),
+            SettlementAdjustmentTypes::ManualDeduction => entry
+                .debit(LedgerAccountTypes::PayableToOrganization, amount_in_cents)
+                .credit(LedgerAccountTypes::PlatformFees, amount_in_cents),
+            SettlementAdjustmentTypes::Chargeback => entry
+                .debit(LedgerAccountTypes::PayableToOrganization, amount_in_cents)
+                .credit(LedgerAccountTypes::Cash, amount_in_cents),
+        };
+
+        if entry.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(entry.commit(conn)?))
+    }
+
+    /// Records the payout leaving the platform's bank account once it is confirmed as paid
+    pub fn record_settlement_payout(
+        payout: &SettlementPayout,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<LedgerJournalEntry, DatabaseError> {
+        let settlement = payout.settlement(conn)?;
+        LedgerJournalEntry::create(
+            settlement.organization_id,
+            payout.currency,
+            Tables::SettlementPayouts,
+            payout.id,
+            format!("Settlement {} payout paid", settlement.id),
+            current_user_id,
+        )
+        .debit(LedgerAccountTypes::PayableToOrganization, payout.amount_in_cents)
+        .credit(LedgerAccountTypes::Cash, payout.amount_in_cents)
+        .commit(conn)
+    }
+
+    /// The journal is append-only so records that are removed or returned are reversed by
+    /// recording entries with the debits and credits swapped.
+    pub fn reverse_for_source(
+        source_table: Tables,
+        source_id: Uuid,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Vec<LedgerJournalEntry>, DatabaseError> {
+        let entries = LedgerJournalEntry::find_for_source(source_table, source_id, conn)?;
+        let mut reversals = Vec::new();
+        for entry in entries.iter().filter(|e| e.reverses_ledger_journal_entry_id.is_none()) {
+            if entries
+                .iter()
+                .any(|e| e.reverses_ledger_journal_entry_id == Some(entry.id))
+            {
+                continue;
+            }
+
+            let mut reversal = LedgerJournalEntry::create(
+                entry.organization_id,
+                entry.currency,
+                entry.source_table,
+                entry.source_id,
+                format!("Reversal of {}", entry.description),
+                current_user_id,
+            );
+            reversal.reverses_ledger_journal_entry_id = Some(entry.id);
+            for line in entry.lines(conn)? {
+                let account = LedgerAccount::find(line.ledger_account_id, conn)?;
+                reversal = reversal
+                    .debit(account.account_type, line.credit_in_cents)
+                    .credit(account.account_type, line.debit_in_cents);
+            }
+            reversals.push(reversal.commit(conn)?);
+        }
+
+        Ok(reversals)
+    }
+
+    /// Entries that do not balance, lines posted to accounts of another organization or currency
+    /// and paid orders missing their journal entry
+    pub fn check_integrity(conn: &PgConnection) -> Result<Vec<LedgerIntegrityIssue>, DatabaseError> {
+        let query = include_str!("../queries/ledger_integrity_issues.sql");
+        diesel::sql_query(query)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not check ledger integrity")
+    }
+
+    /// Groups order item amounts by the organization running the item's event, items not linked
+    /// to an event (e.g. credit card fees) belong to the order's main event.
+    fn amounts_by_organization(
+        order: &Order,
+        amounts: &[(&OrderItem, i64)],
+        conn: &PgConnection,
+    ) -> Result<Vec<(Uuid, Vec<(OrderItemTypes, i64)>)>, DatabaseError> {
+        let mut event_ids: Vec<Uuid> = amounts.iter().filter_map(|(item, _)| item.event_id).collect();
+        event_ids.sort();
+        event_ids.dedup();
+        let organization_ids: HashMap<Uuid, Uuid> = events::table
+            .filter(events::id.eq_any(&event_ids))
+            .select((events::id, events::organization_id))
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load event organizations")?
+            .into_iter()
+            .collect();
+
+        let mut grouped: Vec<(Uuid, Vec<(OrderItemTypes, i64)>)> = Vec::new();
+        for (item, amount_in_cents) in amounts {
+            let event_id = match item.event_id {
+                Some(event_id) => event_id,
+                None => order.main_event_id(conn)?,
+            };
+            let organization_id = match organization_ids.get(&event_id) {
+                Some(organization_id) => *organization_id,
+                None => Event::find(event_id, conn)?.organization_id,
+            };
+            match grouped.iter_mut().find(|(id, _)| *id == organization_id) {
+                Some(group) => group.1.push((item.item_type, *amount_in_cents)),
+                None => grouped.push((organization_id, vec![(item.item_type, *amount_in_cents)])),
+            }
+        }
+
+        Ok(grouped)
+    }
+}
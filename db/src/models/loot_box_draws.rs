@@ -0,0 +1,346 @@
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use diesel::sql_types::{Integer, Nullable, Uuid as dUuid};
+use models::*;
+use schema::{loot_box_contents, loot_box_draws, rarities, ticket_instances};
+use utils::errors::*;
+use utils::hash::sha256;
+use utils::rand::random_alpha_string;
+use uuid::Uuid;
+
+const SERVER_SEED_LENGTH: usize = 64;
+const CLIENT_SEED_LENGTH: usize = 16;
+const CLIENT_SEED_MAX_LENGTH: usize = 100;
+
+/// Commit-reveal record for a loot box ticket. The hash of the server seed is published when the
+/// box is purchased and the seed itself is only revealed once the box is opened, so neither side
+/// can influence the draw after the fact. The candidates the tickets were drawn from are recorded
+/// in draw order with the revealed seed so anyone can replay the draw.
+#[derive(Clone, Debug, Deserialize, Identifiable, PartialEq, Queryable, Serialize)]
+#[table_name = "loot_box_draws"]
+pub struct LootBoxDraw {
+    pub id: Uuid,
+    pub ticket_instance_id: Uuid,
+    #[serde(skip_serializing)]
+    pub server_seed: String,
+    pub server_seed_hash: String,
+    pub client_seed: Option<String>,
+    pub candidate_ticket_instance_ids: Vec<Uuid>,
+    pub drawn_ticket_instance_ids: Vec<Uuid>,
+    pub opened_by_user_id: Option<Uuid>,
+    pub opened_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayLootBoxDraw {
+    pub ticket_instance_id: Uuid,
+    pub server_seed_hash: String,
+    /// Only revealed once the box has been opened
+    pub server_seed: Option<String>,
+    pub client_seed: Option<String>,
+    pub candidate_ticket_instance_ids: Vec<Uuid>,
+    pub drawn_ticket_instance_ids: Vec<Uuid>,
+    pub opened_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[table_name = "loot_box_draws"]
+pub struct NewLootBoxDraw {
+    pub ticket_instance_id: Uuid,
+    pub server_seed: String,
+    pub server_seed_hash: String,
+}
+
+#[derive(Clone, Debug, QueryableByName)]
+struct LootBoxDrawCandidate {
+    #[sql_type = "dUuid"]
+    id: Uuid,
+    #[sql_type = "dUuid"]
+    parent_id: Uuid,
+    #[sql_type = "dUuid"]
+    event_id: Uuid,
+    #[sql_type = "dUuid"]
+    ticket_type_id: Uuid,
+    #[sql_type = "Nullable<Integer>"]
+    rarity_rank: Option<i32>,
+}
+
+impl LootBoxDrawCandidate {
+    fn matches(&self, content: &LootBoxContent, (min_rank, max_rank): (Option<i32>, Option<i32>)) -> bool {
+        let rarity_rank = self.rarity_rank;
+        let above_min = min_rank.map(|min| rarity_rank.map(|r| r >= min).unwrap_or(false));
+        let below_max = max_rank.map(|max| rarity_rank.map(|r| r <= max).unwrap_or(false));
+        self.event_id == content.content_event_id
+            && content
+                .content_ticket_type_id
+                .map(|id| id == self.ticket_type_id)
+                .unwrap_or(true)
+            && above_min.unwrap_or(true)
+            && below_max.unwrap_or(true)
+    }
+}
+
+impl LootBoxDraw {
+    pub fn create(ticket_instance_id: Uuid) -> NewLootBoxDraw {
+        let server_seed = random_alpha_string(SERVER_SEED_LENGTH);
+        NewLootBoxDraw {
+            ticket_instance_id,
+            server_seed_hash: sha256::digest(&server_seed),
+            server_seed,
+        }
+    }
+
+    pub fn find_for_ticket_instance(
+        ticket_instance_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Option<LootBoxDraw>, DatabaseError> {
+        loot_box_draws::table
+            .filter(loot_box_draws::ticket_instance_id.eq(ticket_instance_id))
+            .first(conn)
+            .optional()
+            .to_db_error(ErrorCode::QueryError, "Unable to load loot box draw")
+    }
+
+    /// Boxes purchased before commitments were recorded get theirs when first requested, which is
+    /// still before the client seed is known
+    pub fn find_or_create_for_ticket_instance(
+        ticket_instance_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<LootBoxDraw, DatabaseError> {
+        match LootBoxDraw::find_for_ticket_instance(ticket_instance_id, conn)? {
+            Some(draw) => Ok(draw),
+            None => LootBoxDraw::create(ticket_instance_id).commit(conn),
+        }
+    }
+
+    pub fn for_display(&self) -> DisplayLootBoxDraw {
+        DisplayLootBoxDraw {
+            ticket_instance_id: self.ticket_instance_id,
+            server_seed_hash: self.server_seed_hash.clone(),
+            server_seed: self.opened_at.map(|_| self.server_seed.clone()),
+            client_seed: self.client_seed.clone(),
+            candidate_ticket_instance_ids: self.candidate_ticket_instance_ids.clone(),
+            drawn_ticket_instance_ids: self.drawn_ticket_instance_ids.clone(),
+            opened_at: self.opened_at,
+        }
+    }
+
+    /// Index into `count` candidates for the nth ticket drawn from a box, taken from the first 64 bits
+    /// of SHA-256("server_seed:client_seed:nonce")
+    pub fn draw_index(server_seed: &str, client_seed: &str, nonce: u32, count: usize) -> usize {
+        let hash = sha256::digest(&format!("{}:{}:{}", server_seed, client_seed, nonce));
+        let value = u64::from_str_radix(&hash[0..16], 16).unwrap_or(0);
+        (value % count as u64) as usize
+    }
+
+    /// Opens a loot box owned by the user. Each ticket is drawn from the contents still waiting in any
+    /// unopened box of the same ticket type, ordered by rarity, and is moved into the user's wallet.
+    /// Tickets drawn from other boxes are replaced with this box's remaining contents.
+    pub fn open(
+        ticket_instance_id: Uuid,
+        client_seed: Option<String>,
+        user_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<(LootBoxDraw, Vec<TicketInstance>), DatabaseError> {
+        let loot_box: TicketInstance = ticket_instances::table
+            .find(ticket_instance_id)
+            .for_update()
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ticket")?;
+        let ticket_type = loot_box.ticket_type(conn)?;
+        if ticket_type.ticket_type_type != TicketTypeType::LootBox {
+            return DatabaseError::business_process_error("Ticket is not a loot box");
+        }
+        if loot_box.status == TicketInstanceStatus::Redeemed {
+            return DatabaseError::business_process_error("Loot box has already been opened");
+        }
+        if loot_box.status != TicketInstanceStatus::Purchased
+            || Wallet::find(loot_box.wallet_id, conn)?.user_id != Some(user_id)
+        {
+            return DatabaseError::business_process_error("Loot box is not owned by the user");
+        }
+        if loot_box.listing_id.is_some() || loot_box.trade_id.is_some() || loot_box.has_pending_transfer(conn)? {
+            return DatabaseError::business_process_error(
+                "Loot box cannot be opened while it is listed, being transferred or traded",
+            );
+        }
+
+        let client_seed = match client_seed {
+            Some(seed) => {
+                let seed = seed.trim().to_string();
+                if seed.is_empty() || seed.len() > CLIENT_SEED_MAX_LENGTH {
+                    return DatabaseError::validation_error(
+                        "client_seed",
+                        "Client seed must be between 1 and 100 characters",
+                    );
+                }
+                seed
+            }
+            None => random_alpha_string(CLIENT_SEED_LENGTH),
+        };
+        let draw = LootBoxDraw::find_or_create_for_ticket_instance(loot_box.id, conn)?;
+
+        let contents: Vec<LootBoxContent> = loot_box_contents::table
+            .filter(loot_box_contents::ticket_type_id.eq(ticket_type.id))
+            .order_by(loot_box_contents::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load loot box contents")?;
+        let mut candidates: Vec<LootBoxDrawCandidate> =
+            diesel::sql_query(include_str!("../queries/loot_box_draw_candidates.sql"))
+                .bind::<dUuid, _>(ticket_type.id)
+                .load(conn)
+                .to_db_error(ErrorCode::QueryError, "Unable to load loot box contents")?;
+        let candidate_ids: Vec<Uuid> = candidates.iter().map(|c| c.id).collect();
+
+        let mut rank_ranges = Vec::new();
+        for content in &contents {
+            rank_ranges.push((
+                LootBoxDraw::rarity_rank(content.min_rarity_id, conn)?,
+                LootBoxDraw::rarity_rank(content.max_rarity_id, conn)?,
+            ));
+        }
+
+        let mut drawn: Vec<(LootBoxDrawCandidate, usize)> = Vec::new();
+        let mut nonce = 0;
+        for (content_index, content) in contents.iter().enumerate() {
+            for _ in 0..content.quantity_per_box {
+                let eligible: Vec<usize> = candidates
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| c.matches(content, rank_ranges[content_index]))
+                    .map(|(i, _)| i)
+                    .collect();
+                if eligible.is_empty() {
+                    return DatabaseError::business_process_error("Not enough tickets remaining to open loot box");
+                }
+                let index = eligible[LootBoxDraw::draw_index(&draw.server_seed, &client_seed, nonce, eligible.len())];
+                drawn.push((candidates.remove(index), content_index));
+                nonce += 1;
+            }
+        }
+
+        // Boxes that gave up a ticket receive one of the tickets originally packed into this box
+        let mut remaining: Vec<LootBoxDrawCandidate> =
+            candidates.into_iter().filter(|c| c.parent_id == loot_box.id).collect();
+        for (ticket, content_index) in drawn.iter().filter(|(t, _)| t.parent_id != loot_box.id) {
+            let replacement_index = match remaining
+                .iter()
+                .position(|c| c.matches(&contents[*content_index], rank_ranges[*content_index]))
+            {
+                Some(replacement_index) => replacement_index,
+                None => {
+                    return DatabaseError::business_process_error(
+                        "No ticket of the drawn rarity remaining to replace in the loot box it was drawn from",
+                    )
+                }
+            };
+            let replacement = remaining.remove(replacement_index);
+            diesel::update(ticket_instances::table.filter(ticket_instances::id.eq(replacement.id)))
+                .set((
+                    ticket_instances::parent_id.eq(ticket.parent_id),
+                    ticket_instances::updated_at.eq(dsl::now),
+                ))
+                .execute(conn)
+                .to_db_error(ErrorCode::UpdateError, "Could not move loot box contents")?;
+        }
+
+        let drawn_ids: Vec<Uuid> = drawn.iter().map(|(t, _)| t.id).collect();
+        let tickets: Vec<TicketInstance> =
+            diesel::update(ticket_instances::table.filter(ticket_instances::id.eq_any(&drawn_ids)))
+                .set((
+                    ticket_instances::parent_id.eq(loot_box.id),
+                    ticket_instances::wallet_id.eq(loot_box.wallet_id),
+                    ticket_instances::status.eq(TicketInstanceStatus::Purchased),
+                    ticket_instances::reserved_until.eq(None::<NaiveDateTime>),
+                    ticket_instances::updated_at.eq(dsl::now),
+                ))
+                .get_results(conn)
+                .to_db_error(ErrorCode::UpdateError, "Could not add loot box contents to wallet")?;
+        for ticket in &tickets {
+            let key = ticket.associate_redeem_key(conn)?;
+            DomainEvent::create(
+                DomainEventTypes::TicketInstanceAddedFromLootBox,
+                "Ticket added from loot box".to_string(),
+                Tables::TicketInstances,
+                Some(ticket.id),
+                Some(user_id),
+                Some(json!({ "loot_box_ticket_instance_id": loot_box.id, "wallet_id": loot_box.wallet_id, "redeem_key": key })),
+            )
+            .commit(conn)?;
+        }
+
+        diesel::update(ticket_instances::table.filter(ticket_instances::id.eq(loot_box.id)))
+            .set((
+                ticket_instances::status.eq(TicketInstanceStatus::Redeemed),
+                ticket_instances::redeemed_by_user_id.eq(user_id),
+                ticket_instances::redeemed_at.eq(dsl::now),
+                ticket_instances::check_in_source.eq(CheckInSource::LootBox),
+                ticket_instances::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not mark loot box as opened")?;
+
+        let draw: LootBoxDraw = diesel::update(&draw)
+            .set((
+                loot_box_draws::client_seed.eq(&client_seed),
+                loot_box_draws::candidate_ticket_instance_ids.eq(&candidate_ids),
+                loot_box_draws::drawn_ticket_instance_ids.eq(&drawn_ids),
+                loot_box_draws::opened_by_user_id.eq(user_id),
+                loot_box_draws::opened_at.eq(dsl::now.nullable()),
+                loot_box_draws::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update loot box draw")?;
+
+        DomainEvent::create(
+            DomainEventTypes::TicketInstanceLootBoxOpened,
+            "Loot box opened".to_string(),
+            Tables::TicketInstances,
+            Some(loot_box.id),
+            Some(user_id),
+            Some(json!({
+                "server_seed": draw.server_seed,
+                "server_seed_hash": draw.server_seed_hash,
+                "client_seed": draw.client_seed,
+                "candidate_ticket_instance_ids": draw.candidate_ticket_instance_ids,
+                "drawn_ticket_instance_ids": draw.drawn_ticket_instance_ids
+            })),
+        )
+        .commit(conn)?;
+
+        Ok((draw, tickets))
+    }
+
+    fn rarity_rank(rarity_id: Option<Uuid>, conn: &PgConnection) -> Result<Option<i32>, DatabaseError> {
+        match rarity_id {
+            Some(rarity_id) => rarities::table
+                .find(rarity_id)
+                .select(rarities::rank)
+                .first(conn)
+                .map(Some)
+                .to_db_error(ErrorCode::QueryError, "Unable to load rarity"),
+            None => Ok(None),
+        }
+    }
+}
+
+impl NewLootBoxDraw {
+    pub fn commit(self, conn: &PgConnection) -> Result<LootBoxDraw, DatabaseError> {
+        diesel::insert_into(loot_box_draws::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create loot box draw")
+    }
+}
+
+#[test]
+fn draw_index_is_deterministic() {
+    let index = LootBoxDraw::draw_index("server", "client", 0, 10);
+    assert!(index < 10);
+    assert_eq!(index, LootBoxDraw::draw_index("server", "client", 0, 10));
+    assert_eq!(LootBoxDraw::draw_index("server", "client", 3, 1), 0);
+}
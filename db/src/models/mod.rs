@@ -1,10 +1,20 @@
+pub use self::access_zones::*;
+pub use self::accounting_account_mappings::*;
+pub use self::accounting_exports::*;
 pub use self::activities::*;
+pub use self::affiliate_commission_rules::*;
+pub use self::affiliate_commissions::*;
+pub use self::affiliate_links::*;
 pub use self::announcement_engagements::*;
 pub use self::announcements::*;
 pub use self::artists::*;
+pub use self::asset_sync_issues::*;
 pub use self::assets::*;
 pub use self::auth::*;
 pub use self::broadcasts::*;
+pub use self::cash_drawer_sessions::*;
+pub use self::checkout_answers::*;
+pub use self::checkout_questions::*;
 pub use self::codes::*;
 pub use self::collection_items::*;
 pub use self::collections::*;
@@ -16,10 +26,12 @@ pub use self::enums::*;
 pub use self::event_artists::*;
 pub use self::event_interest::*;
 pub use self::event_report_subscribers::*;
+pub use self::event_series::*;
 pub use self::event_users::*;
 pub use self::events::*;
 pub use self::external_logins::FACEBOOK_SITE;
 pub use self::external_logins::*;
+pub use self::fan_segments::*;
 pub use self::fans::*;
 pub use self::fee_schedule_ranges::*;
 pub use self::fee_schedules::*;
@@ -28,23 +40,35 @@ pub use self::genres::*;
 pub use self::global::*;
 pub use self::history_item::*;
 pub use self::holds::*;
+pub use self::inventory_pools::*;
+pub use self::ledger_accounts::*;
+pub use self::ledger_journal_entries::*;
 pub use self::listings::*;
 pub use self::loot_box_contents::*;
+pub use self::loot_box_draws::*;
 pub use self::marketplace_accounts::*;
 pub use self::notes::*;
 pub use self::order_items::*;
 pub use self::orders::*;
+pub use self::organization_bank_accounts::*;
+pub use self::organization_brandings::*;
 pub use self::organization_interactions::*;
 pub use self::organization_invites::*;
 pub use self::organization_users::*;
 pub use self::organization_venues::*;
 pub use self::organizations::*;
+pub use self::package_ticket_types::*;
+pub use self::packages::*;
+pub use self::partners::*;
 pub use self::paging::*;
 pub use self::payment_methods::*;
 pub use self::payments::*;
 pub use self::platforms::*;
+pub use self::product_variants::*;
+pub use self::products::*;
 pub use self::push_notification_tokens::*;
 pub use self::rarities::*;
+pub use self::redeemable_add_on::*;
 pub use self::redeemable_ticket::*;
 pub use self::refund_items::*;
 pub use self::refunded_tickets::*;
@@ -54,19 +78,28 @@ pub use self::reports::*;
 pub use self::scopes::*;
 pub use self::settlement_adjustments::*;
 pub use self::settlement_entries::*;
+pub use self::settlement_payout_batches::*;
+pub use self::settlement_payouts::*;
+pub use self::settlement_statement_subscribers::*;
+pub use self::settlement_statements::*;
 pub use self::settlements::*;
 pub use self::slugs::*;
 pub use self::stages::*;
+pub use self::tax_rules::*;
 pub use self::temporary_users::*;
 pub use self::ticket_instances::RedeemResults;
 pub use self::ticket_instances::*;
 pub use self::ticket_pricing::*;
+pub use self::ticket_scans::*;
 pub use self::ticket_type_codes::*;
 pub use self::ticket_types::*;
+pub use self::trades::*;
 pub use self::transfer_tickets::*;
 pub use self::transfers::*;
+pub use self::user_credit_entries::*;
 pub use self::users::*;
 pub use self::venues::*;
+pub use self::wallet_passes::*;
 pub use self::wallets::*;
 
 use serde::{Deserialize, Deserializer};
@@ -74,14 +107,24 @@ use serde_json::Value;
 
 pub mod concerns;
 
+mod access_zones;
+mod accounting_account_mappings;
+mod accounting_exports;
 mod activities;
+mod affiliate_commission_rules;
+mod affiliate_commissions;
+mod affiliate_links;
 pub mod analytics;
 mod announcement_engagements;
 mod announcements;
 mod artists;
+mod asset_sync_issues;
 mod assets;
 mod auth;
 mod broadcasts;
+mod cash_drawer_sessions;
+mod checkout_answers;
+mod checkout_questions;
 mod codes;
 mod collection_items;
 mod collections;
@@ -93,9 +136,11 @@ pub mod enums;
 mod event_artists;
 mod event_interest;
 mod event_report_subscribers;
+mod event_series;
 mod event_users;
 mod events;
 mod external_logins;
+mod fan_segments;
 mod fans;
 mod fee_schedule_ranges;
 mod fee_schedules;
@@ -104,23 +149,35 @@ mod genres;
 pub mod global;
 mod history_item;
 mod holds;
+mod inventory_pools;
+mod ledger_accounts;
+mod ledger_journal_entries;
 mod listings;
 mod loot_box_contents;
+mod loot_box_draws;
 mod marketplace_accounts;
 mod notes;
 mod order_items;
 mod orders;
+mod organization_bank_accounts;
+mod organization_brandings;
 mod organization_interactions;
 mod organization_invites;
 mod organization_users;
 mod organization_venues;
 mod organizations;
+mod package_ticket_types;
+mod packages;
+mod partners;
 mod paging;
 mod payment_methods;
 mod payments;
 mod platforms;
+mod product_variants;
+mod products;
 mod push_notification_tokens;
 mod rarities;
+mod redeemable_add_on;
 mod redeemable_ticket;
 mod refund_items;
 mod refunded_tickets;
@@ -130,18 +187,27 @@ mod reports;
 pub mod scopes;
 mod settlement_adjustments;
 mod settlement_entries;
+mod settlement_payout_batches;
+mod settlement_payouts;
+mod settlement_statement_subscribers;
+mod settlement_statements;
 mod settlements;
 mod slugs;
 mod stages;
+mod tax_rules;
 mod temporary_users;
 mod ticket_instances;
 mod ticket_pricing;
+mod ticket_scans;
 mod ticket_type_codes;
 mod ticket_types;
+mod trades;
 mod transfer_tickets;
 mod transfers;
+mod user_credit_entries;
 mod users;
 mod venues;
+mod wallet_passes;
 mod wallets;
 
 pub fn deserialize_unless_blank<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
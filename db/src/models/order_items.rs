@@ -5,7 +5,7 @@ use diesel::prelude::*;
 use diesel::sql_types::{Array, BigInt, Nullable, Text, Uuid as dUuid};
 use itertools::Itertools;
 use models::*;
-use schema::{codes, events, order_items, ticket_instances, ticket_types};
+use schema::{codes, events, order_items, tax_rules, ticket_instances, ticket_types};
 use std::borrow::Cow;
 use std::cmp;
 use std::collections::HashMap;
@@ -39,6 +39,11 @@ pub struct OrderItem {
     pub company_fee_in_cents: i64,
     pub client_fee_in_cents: i64,
     pub refunded_quantity: i64,
+    pub product_variant_id: Option<Uuid>,
+    pub redeemed_quantity: i64,
+    pub package_id: Option<Uuid>,
+    pub tax_rule_id: Option<Uuid>,
+    pub tax_in_cents: i64,
 }
 
 impl OrderItem {
@@ -68,6 +73,14 @@ impl OrderItem {
             .to_db_error(ErrorCode::QueryError, "Could not retrieve order item discount")
     }
 
+    pub fn find_tax_items(&self, conn: &PgConnection) -> Result<Vec<OrderItem>, DatabaseError> {
+        order_items::table
+            .filter(order_items::parent_id.eq(self.id))
+            .filter(order_items::item_type.eq(OrderItemTypes::Tax))
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not retrieve order item taxes")
+    }
+
     pub fn description(&self, conn: &PgConnection) -> Result<String, DatabaseError> {
         use models::OrderItemTypes::*;
         let res = match self.item_type {
@@ -81,6 +94,27 @@ impl OrderItem {
             }
             Discount => "Discount".to_string(),
             CreditCardFees => "Credit Card Fees".to_string(),
+            AddOns => match self.product_variant_id {
+                Some(product_variant_id) => {
+                    let variant = ProductVariant::find(product_variant_id, conn)?;
+                    format!("{} - {}", variant.product(conn)?.name, variant.name)
+                }
+                None => "Add-on".to_string(),
+            },
+            Tax => match self.tax_rule_id {
+                Some(tax_rule_id) => {
+                    let tax_rule = tax_rules::table
+                        .find(tax_rule_id)
+                        .first::<TaxRule>(conn)
+                        .to_db_error(ErrorCode::QueryError, "Could not load tax rule for order item")?;
+                    if tax_rule.inclusive {
+                        format!("{} (included)", tax_rule.name)
+                    } else {
+                        tax_rule.name
+                    }
+                }
+                None => "Tax".to_string(),
+            },
             _ => {
                 let ticket_type = self.ticket_type(conn)?;
                 match ticket_type {
@@ -123,6 +157,11 @@ impl OrderItem {
             }
         }
 
+        // Tax charged on the unit is always returned with it
+        for mut tax_item in self.find_tax_items(conn)? {
+            refund_amount_in_cents += tax_item.refund_one_unit(true, conn)?;
+        }
+
         diesel::update(order_items::table.filter(order_items::id.eq(self.id)))
             .set((
                 order_items::updated_at.eq(dsl::now),
@@ -157,6 +196,7 @@ impl OrderItem {
             || self.item_type == OrderItemTypes::EventFees
             || self.item_type == OrderItemTypes::Discount
             || self.item_type == OrderItemTypes::CreditCardFees
+            || self.item_type == OrderItemTypes::Tax
         {
             return Ok(());
         }
@@ -480,6 +520,14 @@ impl OrderItem {
             event_id: Uuid,
             #[sql_type = "dUuid"]
             order_id: Uuid,
+            #[sql_type = "Nullable<dUuid>"]
+            product_variant_id: Option<Uuid>,
+            #[sql_type = "BigInt"]
+            redeemed_quantity: i64,
+            #[sql_type = "Nullable<dUuid>"]
+            package_id: Option<Uuid>,
+            #[sql_type = "BigInt"]
+            tax_in_cents: i64,
         }
 
         let results: Vec<R> = diesel::sql_query(
@@ -498,6 +546,8 @@ impl OrderItem {
              WHEN item_type = 'EventFees' THEN 'Event Fees - ' || e.name
              WHEN item_type = 'Discount' THEN 'Discount'
              WHEN item_type = 'CreditCardFees' THEN 'Credit Card Fees'
+             WHEN item_type = 'AddOns' THEN p.name || ' - ' || pv.name
+             WHEN item_type = 'Tax' THEN tr.name || CASE WHEN tr.inclusive THEN ' (included)' ELSE '' END
              ELSE e.name || ' - ' || tt.name
            END AS description,
            COALESCE(h.redemption_code, c.redemption_code) as redemption_code,
@@ -512,7 +562,11 @@ impl OrderItem {
              ELSE 'Valid'
            END AS cart_item_status,
            e.id AS event_id,
-           oi.order_id
+           oi.order_id,
+           oi.product_variant_id,
+           oi.redeemed_quantity,
+           oi.package_id,
+           oi.tax_in_cents
         FROM order_items oi
            JOIN orders o ON oi.order_id = o.id
            LEFT JOIN ticket_pricing tp ON tp.id = oi.ticket_pricing_id
@@ -532,6 +586,9 @@ impl OrderItem {
                LIMIT 1
            )
            LEFT JOIN codes c ON oi.code_id = c.id
+           LEFT JOIN product_variants pv ON oi.product_variant_id = pv.id
+           LEFT JOIN products p ON pv.product_id = p.id
+           LEFT JOIN tax_rules tr ON oi.tax_rule_id = tr.id
            LEFT JOIN (
                SELECT count(ti.id) as count, oi.id
                FROM order_items oi
@@ -580,6 +637,10 @@ impl OrderItem {
                     redemption_code: item.redemption_code,
                     cart_item_status: item.cart_item_status,
                     event_id: item.event_id,
+                    product_variant_id: item.product_variant_id,
+                    redeemed_quantity: item.redeemed_quantity,
+                    package_id: item.package_id,
+                    tax_in_cents: item.tax_in_cents,
                 });
             }
             order_items.insert(order_id, display_items);
@@ -630,6 +691,7 @@ pub(crate) struct NewTicketsOrderItem {
     pub ticket_pricing_id: Uuid,
     pub hold_id: Option<Uuid>,
     pub code_id: Option<Uuid>,
+    pub package_id: Option<Uuid>,
 }
 
 impl NewTicketsOrderItem {
@@ -667,6 +729,26 @@ impl NewTicketsOrderItem {
     }
 }
 
+#[derive(Insertable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name = "order_items"]
+pub(crate) struct NewAddOnOrderItem {
+    pub order_id: Uuid,
+    pub item_type: OrderItemTypes,
+    pub event_id: Option<Uuid>,
+    pub quantity: i64,
+    pub unit_price_in_cents: i64,
+    pub product_variant_id: Uuid,
+}
+
+impl NewAddOnOrderItem {
+    pub(crate) fn commit(self, conn: &PgConnection) -> Result<OrderItem, DatabaseError> {
+        diesel::insert_into(order_items::table)
+            .values(self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create order item")
+    }
+}
+
 #[derive(Insertable, Serialize, Deserialize, PartialEq, Debug)]
 #[table_name = "order_items"]
 pub(crate) struct NewFeesOrderItem {
@@ -712,6 +794,28 @@ impl NewDiscountOrderItem {
     }
 }
 
+#[derive(Insertable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name = "order_items"]
+pub(crate) struct NewTaxOrderItem {
+    pub order_id: Uuid,
+    pub item_type: OrderItemTypes,
+    pub event_id: Option<Uuid>,
+    pub quantity: i64,
+    pub unit_price_in_cents: i64,
+    pub tax_rule_id: Uuid,
+    pub tax_in_cents: i64,
+    pub parent_id: Option<Uuid>,
+}
+
+impl NewTaxOrderItem {
+    pub(crate) fn commit(self, conn: &PgConnection) -> Result<OrderItem, DatabaseError> {
+        diesel::insert_into(order_items::table)
+            .values(self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create order item")
+    }
+}
+
 #[derive(Deserialize, Queryable, QueryableByName, Serialize)]
 pub struct DisplayOrderItem {
     #[sql_type = "dUuid"]
@@ -740,4 +844,12 @@ pub struct DisplayOrderItem {
     pub cart_item_status: Option<CartItemStatus>,
     #[sql_type = "dUuid"]
     pub event_id: Uuid,
+    #[sql_type = "Nullable<dUuid>"]
+    pub product_variant_id: Option<Uuid>,
+    #[sql_type = "BigInt"]
+    pub redeemed_quantity: i64,
+    #[sql_type = "Nullable<dUuid>"]
+    pub package_id: Option<Uuid>,
+    #[sql_type = "BigInt"]
+    pub tax_in_cents: i64,
 }
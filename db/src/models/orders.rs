@@ -14,8 +14,8 @@ use itertools::Itertools;
 use log::Level::{self, Debug};
 use models::*;
 use schema::{
-    event_users, events, order_items, order_transfers, orders, organization_users, organizations, payments, refunds,
-    transfers, users,
+    event_users, events, order_items, order_transfers, orders, organization_users, organizations, partners, payments,
+    refunds, transfers, users,
 };
 use serde_json;
 use serde_json::Value;
@@ -64,6 +64,9 @@ pub struct Order {
     #[serde(skip_serializing)]
     pub settlement_id: Option<Uuid>,
     pub referrer: Option<String>,
+    pub currency: Currencies,
+    pub partner_id: Option<Uuid>,
+    pub affiliate_link_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -361,7 +364,7 @@ impl Order {
             let mut update_data = Vec::new();
             let redemption_code = self.redemption_code(conn)?;
             for item in self.items(conn)? {
-                if item.item_type != OrderItemTypes::Tickets {
+                if item.item_type != OrderItemTypes::Tickets || item.package_id.is_some() {
                     continue;
                 }
 
@@ -374,8 +377,15 @@ impl Order {
                 }
             }
 
+            let package_data: Vec<UpdatePackageItem> = self
+                .package_quantities(conn)?
+                .into_iter()
+                .map(|(package_id, quantity)| UpdatePackageItem { package_id, quantity })
+                .collect();
+
             let mut cart = Order::find_or_create_cart(&user, conn)?;
             cart.update_quantities(self.user_id, &update_data, false, true, conn)
+                .and_then(|_| cart.update_package_quantities(self.user_id, &package_data, true, conn))
                 .map_err(|_err| {
                     DatabaseError::business_process_error::<()>("Order is invalid for duplication").unwrap_err()
                 })?;
@@ -434,6 +444,11 @@ impl Order {
             "event_id",
             Order::order_contains_items_from_only_one_event(self.id, conn)?,
         );
+        let validation_errors = append_validation_error(
+            validation_errors,
+            "currency",
+            Order::order_contains_items_in_only_one_currency(self.id, conn)?,
+        );
 
         Ok(validation_errors?)
     }
@@ -442,9 +457,13 @@ impl Order {
         id: Uuid,
         conn: &PgConnection,
     ) -> Result<Result<(), ValidationError>, DatabaseError> {
+        // Packages bundle tickets for several events so only tickets sold outside of a package
+        // are limited to a single event
         let event_count = order_items::table
             .filter(order_items::order_id.eq(id))
             .filter(order_items::event_id.is_not_null())
+            .filter(order_items::item_type.eq(OrderItemTypes::Tickets))
+            .filter(order_items::package_id.is_null())
             .select(sql::<BigInt>("count(distinct event_id) AS event_count"))
             .get_result::<i64>(conn)
             .to_db_error(ErrorCode::QueryError, "Could not get count of unique events in cart")?;
@@ -458,6 +477,59 @@ impl Order {
         Ok(Ok(()))
     }
 
+    pub fn order_contains_items_in_only_one_currency(
+        id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Result<(), ValidationError>, DatabaseError> {
+        let currency_count = order_items::table
+            .inner_join(events::table.on(order_items::event_id.eq(events::id.nullable())))
+            .inner_join(organizations::table.on(events::organization_id.eq(organizations::id)))
+            .filter(order_items::order_id.eq(id))
+            .select(sql::<BigInt>(
+                "count(distinct COALESCE(events.currency, organizations.currency)) AS currency_count",
+            ))
+            .get_result::<i64>(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not get count of unique currencies in cart")?;
+
+        if currency_count > 1 {
+            let mut validation_error = create_validation_error(
+                "cart_currency_mismatch",
+                "Your cart contains items priced in another currency. Please clear your cart first to purchase these items.",
+            );
+            validation_error.add_param(Cow::from("order_id"), &id);
+            return Ok(Err(validation_error.into()));
+        }
+        Ok(Ok(()))
+    }
+
+    /// Sets the order currency to the currency its items are sold in. Carts without items keep
+    /// their current currency until something is added.
+    fn update_currency(&mut self, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let currency: Option<Currencies> = order_items::table
+            .inner_join(events::table.on(order_items::event_id.eq(events::id.nullable())))
+            .inner_join(organizations::table.on(events::organization_id.eq(organizations::id)))
+            .filter(order_items::order_id.eq(self.id))
+            .select(sql::<Text>(
+                "COALESCE(events.currency, organizations.currency)",
+            ))
+            .first::<String>(conn)
+            .optional()
+            .to_db_error(ErrorCode::QueryError, "Could not load currency for cart")?
+            .map(|currency| currency.parse::<Currencies>())
+            .transpose()?;
+
+        if let Some(currency) = currency {
+            if currency != self.currency {
+                *self = diesel::update(&*self)
+                    .set((orders::currency.eq(currency), orders::updated_at.eq(dsl::now)))
+                    .get_result(conn)
+                    .to_db_error(ErrorCode::UpdateError, "Could not update order currency")?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn destroy(&self, conn: &PgConnection) -> Result<usize, DatabaseError> {
         let cart_user: Option<User> = users::table
             .filter(users::last_cart_id.eq(self.id))
@@ -499,6 +571,17 @@ impl Order {
             );
         }
 
+        // delete items charged on the children first (taxes on per unit fees)
+        let child_ids: Vec<Uuid> = order_items::table
+            .filter(order_items::parent_id.eq(item_id))
+            .select(order_items::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load child order items")?;
+        diesel::delete(order_items::table.filter(order_items::parent_id.eq_any(child_ids)))
+            .execute(conn)
+            .map(|_| ())
+            .to_db_error(ErrorCode::DeleteError, "Could not delete child order item")?;
+
         // delete children order items
         diesel::delete(order_items::table.filter(order_items::parent_id.eq(item_id)))
             .execute(conn)
@@ -575,6 +658,16 @@ impl Order {
         self.lock_version(conn)?;
         let mut total_to_be_refunded: i64 = 0;
 
+        let mut refunded_ticket_instance_ids = Vec::new();
+        for refund_datum in refund_data {
+            if let Some(ticket_instance_id) = refund_datum.ticket_instance_id {
+                if OrderItem::find(refund_datum.order_item_id, conn)?.item_type == OrderItemTypes::Tickets {
+                    refunded_ticket_instance_ids.push(ticket_instance_id);
+                }
+            }
+        }
+        Package::validate_complete_packages(&refunded_ticket_instance_ids, conn)?;
+
         let refund = Refund::create(self.id, user_id, reason, manual_override).commit(conn)?;
         let previous_item_refund_counts: HashMap<Uuid, i64> =
             self.items(conn)?.iter().map(|i| (i.id, i.refunded_quantity)).collect();
@@ -583,6 +676,10 @@ impl Order {
             let mut order_item = OrderItem::find(refund_datum.order_item_id, conn)?;
             if order_item.item_type == OrderItemTypes::Discount {
                 return DatabaseError::business_process_error("Discount order items can not be refunded");
+            } else if order_item.item_type == OrderItemTypes::Tax {
+                return DatabaseError::business_process_error(
+                    "Tax order items are refunded with the item they were charged on",
+                );
             } else if order_item.order_id != self.id {
                 return DatabaseError::business_process_error("Order item id does not belong to this order");
             }
@@ -638,6 +735,8 @@ impl Order {
                 total_to_be_refunded, calculated_refunded_value
             ));
         }
+        LedgerJournalEntry::record_refund(self, &refund, conn)?;
+        AffiliateCommission::record_for_refund(&refund, conn)?;
 
         Ok((refund, total_to_be_refunded))
     }
@@ -764,6 +863,14 @@ impl Order {
             .to_db_error(ErrorCode::QueryError, "Error loading organizations")
     }
 
+    /// Base URL for links about the order, its organization's custom domain when one is configured
+    pub fn front_end_url(&self, default_front_end_url: &str, conn: &PgConnection) -> Result<String, DatabaseError> {
+        match self.organizations(conn)?.first() {
+            Some(organization) => organization.front_end_url(default_front_end_url, conn),
+            None => Ok(default_front_end_url.to_string()),
+        }
+    }
+
     pub fn is_expired(&self) -> bool {
         self.expires_at.is_some() && self.expires_at < Some(Utc::now().naive_utc())
     }
@@ -831,7 +938,9 @@ impl Order {
         self.lock_version(conn)?;
         self.updated_at = Utc::now().naive_utc();
 
-        let platform: Option<Platforms> = if self.box_office_pricing {
+        let platform: Option<Platforms> = if self.partner_id.is_some() {
+            Some(Platforms::Partner)
+        } else if self.box_office_pricing {
             Some(Platforms::BoxOffice)
         } else if user_agent.is_some() {
             Platforms::from_user_agent(user_agent.as_ref().map(|ua| ua.as_str()).unwrap()).ok()
@@ -862,6 +971,42 @@ impl Order {
         Ok(())
     }
 
+    /// Attributes the order to the partner whose checkout it is being placed through
+    pub fn set_partner(&mut self, partner: &Partner, conn: &PgConnection) -> Result<(), DatabaseError> {
+        self.lock_version(conn)?;
+        self.updated_at = Utc::now().naive_utc();
+        self.partner_id = Some(partner.id);
+        self.platform = Some(Platforms::Partner.to_string());
+        self.source = Some(partner.name.clone());
+
+        let affected_rows =
+            diesel::update(orders::table.filter(orders::id.eq(self.id).and(orders::version.eq(self.version))))
+                .set((
+                    orders::partner_id.eq(self.partner_id),
+                    orders::platform.eq(&self.platform),
+                    orders::source.eq(&self.source),
+                    orders::updated_at.eq(self.updated_at),
+                ))
+                .execute(conn)
+                .to_db_error(ErrorCode::UpdateError, "Could not set partner for order")?;
+        if affected_rows != 1 {
+            return DatabaseError::concurrency_error("Could not set partner for order.");
+        }
+
+        Ok(())
+    }
+
+    pub fn partner(&self, conn: &PgConnection) -> Result<Option<Partner>, DatabaseError> {
+        match self.partner_id {
+            Some(partner_id) => partners::table
+                .filter(partners::id.eq(partner_id))
+                .first(conn)
+                .optional()
+                .to_db_error(ErrorCode::QueryError, "Unable to load partner for order"),
+            None => Ok(None),
+        }
+    }
+
     pub fn set_tracking_data(
         &mut self,
         tracking_data: Option<serde_json::Value>,
@@ -901,6 +1046,23 @@ impl Order {
             term = td.get("utm_term").and_then(|t| t.as_str());
             content = td.get("utm_content").and_then(|c| c.as_str());
         }
+        // Orders placed through a partner's checkout stay attributed to the partner
+        if self.partner_id.is_some() {
+            source = self.source.clone();
+        }
+        // Affiliate links are attributed to the last link followed. The front end keeps the `aff` code
+        // from the link's URL and passes it in the tracking data, tracking data without a code keeps the
+        // existing attribution
+        let affiliate_code = tracking_data.as_ref().and_then(|td| {
+            td.get("affiliate_code")
+                .or(td.get(AFFILIATE_CODE_PARAM))
+                .and_then(|c| c.as_str())
+        });
+        if let Some(affiliate_code) = affiliate_code {
+            if let Some(affiliate_link) = AffiliateLink::find_by_code(affiliate_code, conn).optional()? {
+                self.affiliate_link_id = Some(affiliate_link.id);
+            }
+        }
 
         diesel::update(orders::table.filter(orders::id.eq(self.id)))
             .set((
@@ -911,6 +1073,7 @@ impl Order {
                 orders::campaign.eq(campaign),
                 orders::term.eq(term),
                 orders::content.eq(content),
+                orders::affiliate_link_id.eq(self.affiliate_link_id),
                 orders::updated_at.eq(self.updated_at),
             ))
             .execute(conn)
@@ -1321,6 +1484,10 @@ impl Order {
         self.lock_version(conn)?;
 
         for current_line in self.items(conn)? {
+            if current_line.item_type == OrderItemTypes::AddOns {
+                self.destroy_item(current_line.id, conn)?;
+                continue;
+            }
             if current_line.item_type != OrderItemTypes::Tickets {
                 continue;
             }
@@ -1402,7 +1569,8 @@ impl Order {
         }
 
         for mut current_line in current_items {
-            if current_line.item_type != OrderItemTypes::Tickets {
+            // Package tickets are managed through update_package_quantities
+            if current_line.item_type != OrderItemTypes::Tickets || current_line.package_id.is_some() {
                 continue;
             }
 
@@ -1458,6 +1626,7 @@ impl Order {
                                 unit_price_in_cents: price_in_cents,
                                 hold_id: match_data.hold_id,
                                 code_id: match_data.code_id,
+                                package_id: None,
                             }
                             .commit(conn)?;
                             TicketInstance::reserve_tickets(
@@ -1532,6 +1701,7 @@ impl Order {
                 unit_price_in_cents: price_in_cents,
                 hold_id: match_data.hold_id,
                 code_id: match_data.code_id,
+                package_id: None,
             }
             .commit(conn);
 
@@ -1545,17 +1715,304 @@ impl Order {
             )?;
         }
 
+        self.remove_add_ons_without_tickets(conn)?;
+
         // if the cart is empty at this point, it is effectively a new cart, remove expiration
         if self.items(conn)?.len() == 0 {
             self.remove_expiry(current_user_id, conn)?;
         }
+        self.validate_ticket_limits(check_ticket_limits, conn)?;
+        self.update_fees_and_discounts(conn)?;
+        self.validate_record(conn)?;
+        self.update_currency(conn)?;
+        // Beware there could be multiple orders that meet this condition
+        for (ticket_type_id, remaining) in self.ticket_types(conn)? {
+            if remaining == 0 {
+                TicketType::find(ticket_type_id, conn)?.check_for_sold_out_triggers(Some(current_user_id), conn)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the quantity of each add-on in the cart. Add-ons can only be bought alongside tickets
+    /// to the event they are offered for and are limited by the variant's remaining inventory.
+    pub fn update_add_on_quantities(
+        &mut self,
+        current_user_id: Uuid,
+        items: &[UpdateAddOnItem],
+        remove_others: bool,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        self.lock_version(conn)?;
+
+        jlog!(Debug, "Update order add-on quantities", {"items": items, "remove_others": remove_others, "user_id": current_user_id});
+
+        if self.status != OrderStatus::Draft {
+            return DatabaseError::business_process_error("Add-ons can only be changed while the order is in draft");
+        }
+
+        let ticket_event_ids = self.ticket_event_ids(conn)?;
+        let mut ticket_events = Vec::new();
+        for event_id in &ticket_event_ids {
+            ticket_events.push(Event::find(*event_id, conn)?);
+        }
+
+        let mut validation_errors: Result<(), ValidationErrors> = Ok(());
+        let mut changes: Vec<(&UpdateAddOnItem, ProductVariant, Product, Uuid)> = Vec::new();
+        for item in items {
+            let variant = ProductVariant::find(item.product_variant_id, conn)?;
+            let product = variant.product(conn)?;
+            let event = ticket_events.iter().find(|e| product.available_for_event(e));
+            let result = match event {
+                None => Err(create_validation_error(
+                    "tickets_required",
+                    "Add-ons can only be purchased with tickets to the event they are offered for",
+                )),
+                Some(event) => {
+                    let available = variant.available(Some(self.id), conn)?;
+                    if item.quantity as i64 > available {
+                        let mut error =
+                            create_validation_error("insufficient_inventory", "Not enough of this add-on is available");
+                        error.add_param(Cow::from("available"), &available);
+                        Err(error)
+                    } else {
+                        changes.push((item, variant, product, event.id));
+                        Ok(())
+                    }
+                }
+            };
+            validation_errors = append_validation_error(
+                validation_errors,
+                "add_ons",
+                result.map_err(|mut e| {
+                    e.add_param(Cow::from("product_variant_id"), &item.product_variant_id);
+                    e
+                }),
+            );
+        }
+        validation_errors?;
+
+        let current_items: Vec<OrderItem> = self
+            .items(conn)?
+            .into_iter()
+            .filter(|i| i.item_type == OrderItemTypes::AddOns)
+            .collect();
+
+        if remove_others {
+            for current_line in &current_items {
+                if !items
+                    .iter()
+                    .any(|i| Some(i.product_variant_id) == current_line.product_variant_id)
+                {
+                    self.destroy_item(current_line.id, conn)?;
+                }
+            }
+        }
+
+        for (item, variant, product, event_id) in changes {
+            let existing = current_items.iter().find(|i| i.product_variant_id == Some(variant.id));
+            match existing {
+                Some(current_line) if item.quantity == 0 => self.destroy_item(current_line.id, conn)?,
+                Some(current_line) => {
+                    let mut current_line = current_line.clone();
+                    current_line.quantity = item.quantity as i64;
+                    current_line.unit_price_in_cents = variant.unit_price_in_cents(&product);
+                    current_line.update(conn)?;
+                }
+                None if item.quantity == 0 => (),
+                None => {
+                    NewAddOnOrderItem {
+                        order_id: self.id,
+                        item_type: OrderItemTypes::AddOns,
+                        event_id: Some(event_id),
+                        quantity: item.quantity as i64,
+                        unit_price_in_cents: variant.unit_price_in_cents(&product),
+                        product_variant_id: variant.id,
+                    }
+                    .commit(conn)?;
+                }
+            }
+        }
+
+        self.update_fees_and_discounts(conn)?;
+        self.validate_record(conn)?;
+        self.update_currency(conn)?;
+
+        Ok(())
+    }
+
+    fn ticket_event_ids(&self, conn: &PgConnection) -> Result<Vec<Uuid>, DatabaseError> {
+        Ok(self
+            .items(conn)?
+            .into_iter()
+            .filter(|i| i.item_type == OrderItemTypes::Tickets)
+            .filter_map(|i| i.event_id)
+            .unique()
+            .collect())
+    }
+
+    /// Add-ons are dropped from the cart once it no longer holds tickets for their event
+    fn remove_add_ons_without_tickets(&self, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let ticket_event_ids = self.ticket_event_ids(conn)?;
+        for item in self.items(conn)? {
+            if item.item_type == OrderItemTypes::AddOns
+                && !item.event_id.map(|id| ticket_event_ids.contains(&id)).unwrap_or(false)
+            {
+                self.destroy_item(item.id, conn)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the number of each package in the cart. Every ticket in a package is reserved when the
+    /// package is added, priced at the share of the package price allocated to its ticket type.
+    pub fn update_package_quantities(
+        &mut self,
+        current_user_id: Uuid,
+        items: &[UpdatePackageItem],
+        remove_others: bool,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        self.lock_version(conn)?;
+
+        jlog!(Debug, "Update order packages", {"items": items, "remove_others": remove_others, "user_id": current_user_id});
+
+        if self.status != OrderStatus::Draft {
+            return DatabaseError::business_process_error("Cannot update packages for an order that is not in draft");
+        }
+
+        let current_quantities = self.package_quantities(conn)?;
+        for (package_id, current_quantity) in &current_quantities {
+            let remove = match items.iter().find(|i| i.package_id == *package_id) {
+                Some(item) => item.quantity != *current_quantity,
+                None => remove_others,
+            };
+            if remove {
+                self.remove_package_items(*package_id, current_user_id, conn)?;
+            }
+        }
+
+        // Set cart expiration time if not currently set (empty carts have no expiration)
+        if self.expires_at.is_none() {
+            self.set_expiry(Some(current_user_id), None, false, conn)?;
+        }
+
+        let mut check_ticket_limits: Vec<LimitCheck> = vec![];
+        for item in items {
+            if item.quantity == 0 || current_quantities.get(&item.package_id) == Some(&item.quantity) {
+                continue;
+            }
+
+            let package = Package::find(item.package_id, conn)?;
+            let package_ticket_types = package.ticket_types(conn)?;
+            if package_ticket_types.is_empty() {
+                return DatabaseError::business_process_error("Package does not include any tickets");
+            }
+
+            for package_ticket_type in package_ticket_types {
+                let ticket_type = package_ticket_type.ticket_type(conn)?;
+                check_ticket_limits.push(LimitCheck {
+                    ticket_type_id: ticket_type.id,
+                    hold_id: None,
+                    code_id: None,
+                    limit_per_person: ticket_type.limit_per_person as u32,
+                    redemption_code: None,
+                });
+                let ticket_pricing =
+                    TicketPricing::get_current_ticket_pricing(ticket_type.id, self.box_office_pricing, false, conn)?;
+                let quantity = package_ticket_type.quantity * item.quantity as i64;
+
+                let order_item = NewTicketsOrderItem {
+                    order_id: self.id,
+                    item_type: OrderItemTypes::Tickets,
+                    quantity,
+                    ticket_type_id: ticket_type.id,
+                    ticket_pricing_id: ticket_pricing.id,
+                    event_id: Some(ticket_type.event_id),
+                    unit_price_in_cents: package_ticket_type.price_in_cents,
+                    hold_id: None,
+                    code_id: None,
+                    package_id: Some(package.id),
+                }
+                .commit(conn)?;
+
+                TicketInstance::reserve_tickets(
+                    &order_item,
+                    self.expires_at,
+                    ticket_type.id,
+                    None,
+                    quantity as u32,
+                    conn,
+                )?;
+            }
+        }
+
+        self.remove_add_ons_without_tickets(conn)?;
+
+        // if the cart is empty at this point, it is effectively a new cart, remove expiration
+        if self.items(conn)?.len() == 0 {
+            self.remove_expiry(current_user_id, conn)?;
+        }
+        self.validate_ticket_limits(check_ticket_limits, conn)?;
+
+        self.update_fees_and_discounts(conn)?;
+        self.validate_record(conn)?;
+        self.update_currency(conn)?;
+
+        Ok(())
+    }
+
+    /// Number of each package currently in the order
+    pub fn package_quantities(&self, conn: &PgConnection) -> Result<HashMap<Uuid, u32>, DatabaseError> {
+        let mut quantities: HashMap<Uuid, u32> = HashMap::new();
+        for item in self.items(conn)? {
+            if let (Some(package_id), Some(ticket_type_id)) = (item.package_id, item.ticket_type_id) {
+                if quantities.contains_key(&package_id) {
+                    continue;
+                }
+                let package_ticket_type = PackageTicketType::find_for_package(package_id, conn)?
+                    .into_iter()
+                    .find(|ptt| ptt.ticket_type_id == ticket_type_id);
+                if let Some(package_ticket_type) = package_ticket_type {
+                    quantities.insert(package_id, (item.quantity / package_ticket_type.quantity) as u32);
+                }
+            }
+        }
+        Ok(quantities)
+    }
+
+    fn remove_package_items(
+        &self,
+        package_id: Uuid,
+        current_user_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        for item in self.items(conn)? {
+            if item.package_id != Some(package_id) {
+                continue;
+            }
+            // Use calculated quantity as reserved may have been taken in the meantime
+            let quantity = item.calculate_quantity(conn)?;
+            TicketInstance::release_tickets(&item, quantity as u32, Some(current_user_id), conn)?;
+            self.destroy_item(item.id, conn)?;
+        }
+        Ok(())
+    }
+
+    /// Validates the ticket type, hold and code per person limits against everything the user has ordered
+    fn validate_ticket_limits(
+        &self,
+        check_ticket_limits: Vec<LimitCheck>,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
         for limit_check in check_ticket_limits {
             let ordered_quantity = Order::quantity_for_user_for_ticket_type(
                 self.user_id,
                 limit_check.ticket_type_id,
                 limit_check.hold_id,
                 limit_check.code_id,
-                &conn,
+                conn,
             )?;
 
             if limit_check.limit_per_person > 0 && ordered_quantity > limit_check.limit_per_person.into() {
@@ -1585,15 +2042,6 @@ impl Order {
                 return Err(errors.into());
             }
         }
-        self.update_fees_and_discounts(conn)?;
-        self.validate_record(conn)?;
-        // Beware there could be multiple orders that meet this condition
-        for (ticket_type_id, remaining) in self.ticket_types(conn)? {
-            if remaining == 0 {
-                TicketType::find(ticket_type_id, conn)?.check_for_sold_out_triggers(Some(current_user_id), conn)?;
-            }
-        }
-
         Ok(())
     }
 
@@ -1640,16 +2088,18 @@ impl Order {
             match o.item_type {
                 OrderItemTypes::EventFees => self.destroy_item(o.id, conn)?,
                 OrderItemTypes::CreditCardFees => self.destroy_item(o.id, conn)?,
+                OrderItemTypes::Tax => self.destroy_item(o.id, conn)?,
                 _ => {}
             }
         }
 
-        // Box office purchased tickets do not have fees at this time
+        // Box office purchased tickets do not have fees at this time but are still taxed
         if self.box_office_pricing {
-            return Ok(());
+            return self.update_taxes(conn);
         }
 
         let mut per_event_fees_included: HashMap<Uuid, bool> = HashMap::new();
+        let mut credit_card_fee: Option<(Uuid, f32)> = None;
 
         for ((event_id, hold_id), items) in self
             .items(conn)?
@@ -1724,24 +2174,85 @@ impl Order {
                     per_event_fees_included.insert(event_id, true);
                 }
 
-                if org.cc_fee_percent > 0f32 {
-                    let cc_fee = (self.calculate_total(conn)? as f32 * (org.cc_fee_percent / 100f32)).round() as i64;
-                    NewFeesOrderItem {
-                        order_id: self.id,
-                        item_type: OrderItemTypes::CreditCardFees,
-                        event_id: Some(event.id),
-                        unit_price_in_cents: cc_fee,
-                        fee_schedule_range_id: None,
-                        company_fee_in_cents: cc_fee,
-                        client_fee_in_cents: 0,
-                        quantity: 1,
-                        parent_id: None,
-                    }
-                    .commit(conn)?;
+                if org.cc_fee_percent > 0f32 && credit_card_fee.is_none() {
+                    credit_card_fee = Some((event.id, org.cc_fee_percent));
                 }
             }
         }
 
+        self.update_taxes(conn)?;
+
+        // Credit card fees apply to the order total so they are only added once, after the fees for
+        // every event in the order (orders containing packages can span several events)
+        if let Some((event_id, cc_fee_percent)) = credit_card_fee {
+            let cc_fee = (self.calculate_total(conn)? as f32 * (cc_fee_percent / 100f32)).round() as i64;
+            NewFeesOrderItem {
+                order_id: self.id,
+                item_type: OrderItemTypes::CreditCardFees,
+                event_id: Some(event_id),
+                unit_price_in_cents: cc_fee,
+                fee_schedule_range_id: None,
+                company_fee_in_cents: cc_fee,
+                client_fee_in_cents: 0,
+                quantity: 1,
+                parent_id: None,
+            }
+            .commit(conn)?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds a tax item for each tax rule that applies to the tickets, add-ons and, where the rule
+    /// taxes them, fees in the order. Tax items are children of the item they are charged on so that
+    /// they are refunded and removed along with it. Inclusive taxes are already part of the item's
+    /// price so they are recorded in `tax_in_cents` without adding to the order total.
+    fn update_taxes(&self, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let mut event_tax_rules: HashMap<Uuid, Vec<TaxRule>> = HashMap::new();
+
+        for item in self.items(conn)? {
+            let event_id = match item.event_id {
+                Some(event_id) => event_id,
+                None => continue,
+            };
+            let unit_price_in_cents = match item.item_type {
+                OrderItemTypes::Tickets | OrderItemTypes::AddOns => match item.find_discount_item(conn)? {
+                    Some(discount_item) => item.unit_price_in_cents + discount_item.unit_price_in_cents,
+                    None => item.unit_price_in_cents,
+                },
+                OrderItemTypes::PerUnitFees | OrderItemTypes::EventFees => item.unit_price_in_cents,
+                _ => continue,
+            };
+
+            if !event_tax_rules.contains_key(&event_id) {
+                let event = Event::find(event_id, conn)?;
+                event_tax_rules.insert(event_id, TaxRule::find_for_event(&event, conn)?);
+            }
+
+            for tax_rule in &event_tax_rules[&event_id] {
+                if item.item_type.is_fee() && !tax_rule.fees_taxable {
+                    continue;
+                }
+
+                let tax_in_cents = tax_rule.tax_for_unit_price(unit_price_in_cents);
+                if tax_in_cents <= 0 {
+                    continue;
+                }
+
+                NewTaxOrderItem {
+                    order_id: self.id,
+                    item_type: OrderItemTypes::Tax,
+                    event_id: Some(event_id),
+                    quantity: item.quantity,
+                    unit_price_in_cents: if tax_rule.inclusive { 0 } else { tax_in_cents },
+                    tax_rule_id: tax_rule.id,
+                    tax_in_cents,
+                    parent_id: Some(item.id),
+                }
+                .commit(conn)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -2043,6 +2554,10 @@ impl Order {
             total_in_cents: i64,
             #[sql_type = "BigInt"]
             total_refunded_in_cents: i64,
+            #[sql_type = "BigInt"]
+            total_tax_in_cents: i64,
+            #[sql_type = "Text"]
+            currency: Currencies,
             #[sql_type = "Nullable<Array<Text>>"]
             allowed_payment_providers: Option<Vec<String>>,
             #[sql_type = "Nullable<Array<dUuid>>"]
@@ -2069,6 +2584,8 @@ impl Order {
                 p.providers,
                 CAST(COALESCE(SUM(oi.unit_price_in_cents * oi.quantity), 0) as BigInt) as total_in_cents,
                 CAST(COALESCE(SUM(oi.unit_price_in_cents * oi.refunded_quantity), 0) as BigInt) as total_refunded_in_cents,
+                CAST(COALESCE(SUM(oi.tax_in_cents * (oi.quantity - oi.refunded_quantity)), 0) as BigInt) as total_tax_in_cents,
+                o.currency,
                 ARRAY_AGG(DISTINCT SUBSTRING(orgs.allowed_payment_providers::text from 2 for char_length(orgs.allowed_payment_providers::text) - 2)) FILTER (WHERE orgs.allowed_payment_providers IS NOT NULL) as allowed_payment_providers,
                 ARRAY_AGG(DISTINCT e.organization_id) FILTER (WHERE e.organization_id IS NOT NULL) as organization_ids,
                 ARRAY_AGG(DISTINCT e.id) FILTER (WHERE e.id IS NOT NULL) as event_ids
@@ -2105,6 +2622,7 @@ impl Order {
                 o.expires_at,
                 o.checkout_url_expires,
                 o.checkout_url,
+                o.currency,
                 p.payment_methods,
                 p.providers
             ORDER BY o.order_date desc
@@ -2274,6 +2792,8 @@ impl Order {
                 limited_tickets_remaining,
                 total_in_cents: result.total_in_cents,
                 total_refunded_in_cents: result.total_refunded_in_cents,
+                total_tax_in_cents: result.total_tax_in_cents,
+                currency: result.currency,
                 seconds_until_expiry,
                 user_id: result.user_id,
                 user,
@@ -2410,6 +2930,53 @@ impl Order {
         self.add_payment(payment, Some(current_user_id), conn)
     }
 
+    /// Pays the order from the user's credit balance in the order's currency, e.g. cash received
+    /// in a trade
+    pub fn add_credit_payment(&mut self, current_user_id: Uuid, conn: &PgConnection) -> Result<Payment, DatabaseError> {
+        let user_id = self.on_behalf_of_user_id.unwrap_or(self.user_id);
+        // Lock the user so the same credit cannot be spent by concurrent checkouts
+        users::table
+            .find(user_id)
+            .select(users::id)
+            .for_update()
+            .first::<Uuid>(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not lock user for credit payment")?;
+
+        let amount = self.calculate_total(conn)?;
+        let balance_in_cents = UserCreditEntry::balances_for_user(user_id, conn)?
+            .into_iter()
+            .find(|b| b.currency == self.currency)
+            .map(|b| b.balance_in_cents)
+            .unwrap_or(0);
+        if balance_in_cents < amount {
+            return DatabaseError::business_process_error("Credit balance is too low to pay for this order");
+        }
+        UserCreditEntry::create(
+            user_id,
+            -amount,
+            self.currency,
+            Tables::Orders,
+            self.id,
+            "Spent on order".to_string(),
+            Some(current_user_id),
+        )
+        .commit(conn)?;
+
+        let payment = Payment::create(
+            self.id,
+            Some(current_user_id),
+            PaymentStatus::Completed,
+            PaymentMethods::Credit,
+            PaymentProviders::Credit,
+            None,
+            amount,
+            None,
+            None,
+            None,
+        );
+        self.add_payment(payment, Some(current_user_id), conn)
+    }
+
     pub fn add_external_payment(
         &mut self,
         external_reference: Option<String>,
@@ -2420,7 +2987,7 @@ impl Order {
     ) -> Result<Payment, DatabaseError> {
         self.set_external_payment_type(external_payment_type, current_user_id, conn)?;
 
-        let payment = Payment::create(
+        let mut payment = Payment::create(
             self.id,
             Some(current_user_id),
             PaymentStatus::Completed,
@@ -2432,6 +2999,16 @@ impl Order {
             None,
             None,
         );
+        // Attribute the payment to the box office drawer the staff member is working
+        if let Some(cash_drawer_session) = CashDrawerSession::find_open_for_user(current_user_id, conn)? {
+            if self
+                .organizations(conn)?
+                .iter()
+                .any(|o| o.id == cash_drawer_session.organization_id)
+            {
+                payment.cash_drawer_session_id = Some(cash_drawer_session.id);
+            }
+        }
         self.add_payment(payment, Some(current_user_id), conn)
     }
 
@@ -2587,6 +3164,8 @@ impl Order {
         let total_required = self.calculate_total(conn)?;
         if total_paid >= total_required {
             self.update_status(current_user_id, OrderStatus::Paid, conn)?;
+            LedgerJournalEntry::record_order_payment(self, current_user_id, conn)?;
+            AffiliateCommission::record_for_order(self, conn)?;
             //Mark tickets as Purchased
             let order_items = OrderItem::find_for_order(self.id, conn)?;
             for item in order_items
@@ -2667,12 +3246,23 @@ impl Order {
         self.lock_version(conn)?;
 
         let order_items = self.order_items_in_invalid_state(conn)?;
+        let mut invalid_package_ids = Vec::new();
         for item in order_items {
-            // Use calculated quantity as reserved may have been taken in the meantime
-            let quantity = item.calculate_quantity(conn)?;
-            TicketInstance::release_tickets(&item, quantity as u32, Some(user_id), conn)?;
+            if item.item_type == OrderItemTypes::Tickets {
+                // Use calculated quantity as reserved may have been taken in the meantime
+                let quantity = item.calculate_quantity(conn)?;
+                TicketInstance::release_tickets(&item, quantity as u32, Some(user_id), conn)?;
+            }
+            if let Some(package_id) = item.package_id {
+                invalid_package_ids.push(package_id);
+            }
             self.destroy_item(item.id, conn)?;
         }
+        // Packages can only be purchased whole so the rest of an invalid package is removed as well
+        for package_id in invalid_package_ids.into_iter().unique() {
+            self.remove_package_items(package_id, user_id, conn)?;
+        }
+        self.remove_add_ons_without_tickets(conn)?;
 
         Ok(())
     }
@@ -2865,6 +3455,8 @@ pub struct DisplayOrder {
     pub limited_tickets_remaining: Vec<TicketsRemaining>,
     pub total_in_cents: i64,
     pub total_refunded_in_cents: i64,
+    pub total_tax_in_cents: i64,
+    pub currency: Currencies,
     pub user_id: Uuid,
     pub user: DisplayUser,
     pub order_number: String,
@@ -2911,6 +3503,18 @@ pub struct UpdateOrderItem {
     pub redemption_code: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct UpdateAddOnItem {
+    pub product_variant_id: Uuid,
+    pub quantity: u32,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct UpdatePackageItem {
+    pub package_id: Uuid,
+    pub quantity: u32,
+}
+
 #[test]
 fn parse_order_number() {
     let id = Uuid::parse_str("01234567-1234-1234-1234-1234567890ab").unwrap();
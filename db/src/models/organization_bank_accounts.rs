@@ -0,0 +1,312 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::pg::upsert::excluded;
+use diesel::prelude::*;
+use models::*;
+use schema::organization_bank_accounts;
+use utils::encryption::*;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+use validators;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "organization_bank_accounts"]
+pub struct OrganizationBankAccount {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub payout_method: PayoutMethods,
+    pub account_holder_name: String,
+    #[serde(skip_serializing)]
+    pub routing_number: Option<String>,
+    #[serde(skip_serializing)]
+    pub account_number: Option<String>,
+    #[serde(skip_serializing)]
+    pub iban: Option<String>,
+    pub bic: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "organization_bank_accounts"]
+pub struct NewOrganizationBankAccount {
+    #[serde(default)]
+    pub organization_id: Uuid,
+    pub payout_method: PayoutMethods,
+    #[validate(length(min = 1, max = 255))]
+    pub account_holder_name: String,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub routing_number: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub account_number: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub iban: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub bic: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayOrganizationBankAccount {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub payout_method: PayoutMethods,
+    pub account_holder_name: String,
+    pub account_number_last_four: Option<String>,
+    pub iban_last_four: Option<String>,
+    pub bic: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl OrganizationBankAccount {
+    pub fn create(
+        organization_id: Uuid,
+        payout_method: PayoutMethods,
+        account_holder_name: String,
+    ) -> NewOrganizationBankAccount {
+        NewOrganizationBankAccount {
+            organization_id,
+            payout_method,
+            account_holder_name,
+            routing_number: None,
+            account_number: None,
+            iban: None,
+            bic: None,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<OrganizationBankAccount, DatabaseError> {
+        organization_bank_accounts::table
+            .filter(organization_bank_accounts::id.eq(id))
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load bank account")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<OrganizationBankAccount, DatabaseError> {
+        organization_bank_accounts::table
+            .filter(organization_bank_accounts::organization_id.eq(organization_id))
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load bank account for organization")
+    }
+
+    pub fn decrypt(&mut self, encryption_key: &str) -> Result<(), DatabaseError> {
+        if encryption_key.len() > 0 {
+            if let Some(value) = self.routing_number.clone() {
+                self.routing_number = Some(decrypt(&value, encryption_key)?);
+            }
+            if let Some(value) = self.account_number.clone() {
+                self.account_number = Some(decrypt(&value, encryption_key)?);
+            }
+            if let Some(value) = self.iban.clone() {
+                self.iban = Some(decrypt(&value, encryption_key)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Expects a decrypted bank account, only the last four digits of account identifiers are displayed
+    pub fn for_display(&self) -> DisplayOrganizationBankAccount {
+        DisplayOrganizationBankAccount {
+            id: self.id,
+            organization_id: self.organization_id,
+            payout_method: self.payout_method,
+            account_holder_name: self.account_holder_name.clone(),
+            account_number_last_four: self.account_number.as_ref().map(|a| last_four(a)),
+            iban_last_four: self.iban.as_ref().map(|i| last_four(i)),
+            bic: self.bic.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+impl NewOrganizationBankAccount {
+    /// Creates the organization's bank account, replacing the details of any existing account
+    pub fn commit(
+        mut self,
+        current_user_id: Option<Uuid>,
+        encryption_key: &str,
+        conn: &PgConnection,
+    ) -> Result<OrganizationBankAccount, DatabaseError> {
+        self.normalize();
+        self.validate_record(self.validate())?;
+        self.encrypt(encryption_key)?;
+
+        let bank_account: OrganizationBankAccount = diesel::insert_into(organization_bank_accounts::table)
+            .values(&self)
+            .on_conflict(organization_bank_accounts::organization_id)
+            .do_update()
+            .set((
+                organization_bank_accounts::payout_method.eq(excluded(organization_bank_accounts::payout_method)),
+                organization_bank_accounts::account_holder_name
+                    .eq(excluded(organization_bank_accounts::account_holder_name)),
+                organization_bank_accounts::routing_number.eq(excluded(organization_bank_accounts::routing_number)),
+                organization_bank_accounts::account_number.eq(excluded(organization_bank_accounts::account_number)),
+                organization_bank_accounts::iban.eq(excluded(organization_bank_accounts::iban)),
+                organization_bank_accounts::bic.eq(excluded(organization_bank_accounts::bic)),
+                organization_bank_accounts::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not save bank account")?;
+
+        // Account details are left out of the domain event as they are only ever stored encrypted
+        DomainEvent::create(
+            DomainEventTypes::OrganizationBankAccountUpdated,
+            "Organization bank account updated".to_string(),
+            Tables::Organizations,
+            Some(bank_account.organization_id),
+            current_user_id,
+            Some(json!({
+                "organization_bank_account_id": bank_account.id,
+                "payout_method": bank_account.payout_method
+            })),
+        )
+        .commit(conn)?;
+
+        Ok(bank_account)
+    }
+
+    fn normalize(&mut self) {
+        let strip = |value: &Option<String>| {
+            value.as_ref().map(|v| {
+                v.chars()
+                    .filter(|c| !c.is_whitespace())
+                    .collect::<String>()
+                    .to_uppercase()
+            })
+        };
+        self.routing_number = strip(&self.routing_number);
+        self.account_number = strip(&self.account_number);
+        self.iban = strip(&self.iban);
+        self.bic = strip(&self.bic);
+    }
+
+    fn encrypt(&mut self, encryption_key: &str) -> Result<(), DatabaseError> {
+        if encryption_key.len() > 0 {
+            if let Some(value) = self.routing_number.clone() {
+                self.routing_number = Some(encrypt(&value, encryption_key)?);
+            }
+            if let Some(value) = self.account_number.clone() {
+                self.account_number = Some(encrypt(&value, encryption_key)?);
+            }
+            if let Some(value) = self.iban.clone() {
+                self.iban = Some(encrypt(&value, encryption_key)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_record(&self, validation_errors: Result<(), ValidationErrors>) -> Result<(), DatabaseError> {
+        let mut validation_errors = validation_errors;
+        match self.payout_method {
+            PayoutMethods::Nacha => {
+                if !self
+                    .routing_number
+                    .as_ref()
+                    .map(|r| valid_routing_number(r))
+                    .unwrap_or(false)
+                {
+                    validation_errors = validators::append_validation_error(
+                        validation_errors,
+                        "routing_number",
+                        Err(validators::create_validation_error(
+                            "invalid_routing_number",
+                            "Routing number must be a valid 9 digit ABA routing number",
+                        )),
+                    );
+                }
+                let valid_account_number = self
+                    .account_number
+                    .as_ref()
+                    .map(|a| a.len() >= 4 && a.len() <= 17 && a.chars().all(|c| c.is_ascii_digit()))
+                    .unwrap_or(false);
+                if !valid_account_number {
+                    validation_errors = validators::append_validation_error(
+                        validation_errors,
+                        "account_number",
+                        Err(validators::create_validation_error(
+                            "invalid_account_number",
+                            "Account number must be between 4 and 17 digits",
+                        )),
+                    );
+                }
+            }
+            PayoutMethods::Sepa => {
+                if !self.iban.as_ref().map(|i| valid_iban(i)).unwrap_or(false) {
+                    validation_errors = validators::append_validation_error(
+                        validation_errors,
+                        "iban",
+                        Err(validators::create_validation_error("invalid_iban", "IBAN is invalid")),
+                    );
+                }
+                let valid_bic = self
+                    .bic
+                    .as_ref()
+                    .map(|b| (b.len() == 8 || b.len() == 11) && b.chars().all(|c| c.is_ascii_alphanumeric()))
+                    .unwrap_or(false);
+                if !valid_bic {
+                    validation_errors = validators::append_validation_error(
+                        validation_errors,
+                        "bic",
+                        Err(validators::create_validation_error(
+                            "invalid_bic",
+                            "BIC must be 8 or 11 characters",
+                        )),
+                    );
+                }
+            }
+        }
+        Ok(validation_errors?)
+    }
+}
+
+fn last_four(value: &str) -> String {
+    value.chars().skip(value.chars().count().saturating_sub(4)).collect()
+}
+
+/// ABA routing numbers are 9 digits with a weighted (3, 7, 1) checksum
+fn valid_routing_number(routing_number: &str) -> bool {
+    if routing_number.len() != 9 || !routing_number.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    let checksum: u32 = routing_number
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .zip([3, 7, 1].iter().cycle())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+    checksum % 10 == 0
+}
+
+/// IBANs are validated by moving the country code and check digits to the end and checking the
+/// numeric representation is congruent to 1 mod 97
+fn valid_iban(iban: &str) -> bool {
+    if iban.len() < 15 || iban.len() > 34 || !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return false;
+    }
+    let (prefix, rest) = iban.split_at(4);
+    if !prefix[..2].chars().all(|c| c.is_ascii_alphabetic()) || !prefix[2..].chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let mut remainder = 0u32;
+    for c in rest.chars().chain(prefix.chars()) {
+        let value = match c.to_digit(36) {
+            Some(value) => value,
+            None => return false,
+        };
+        remainder = if value >= 10 {
+            (remainder * 100 + value) % 97
+        } else {
+            (remainder * 10 + value) % 97
+        };
+    }
+    remainder == 1
+}
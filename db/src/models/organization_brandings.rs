@@ -0,0 +1,225 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::pg::upsert::excluded;
+use diesel::prelude::*;
+use models::*;
+use schema::organization_brandings;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationError, ValidationErrors};
+use validators;
+
+/// White-label settings used for an organization's communications and links. Anything left unset falls
+/// back to the platform default.
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "organization_brandings"]
+pub struct OrganizationBranding {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub custom_domain: Option<String>,
+    pub logo_url: Option<String>,
+    pub primary_color: Option<String>,
+    pub secondary_color: Option<String>,
+    pub sender_email: Option<String>,
+    pub sender_name: Option<String>,
+    pub support_email: Option<String>,
+    pub support_phone: Option<String>,
+    pub support_url: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Default, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "organization_brandings"]
+pub struct NewOrganizationBranding {
+    #[serde(default)]
+    pub organization_id: Uuid,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub custom_domain: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(url(message = "Logo URL is invalid"))]
+    pub logo_url: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub primary_color: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub secondary_color: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(email(message = "Sender email is invalid"))]
+    pub sender_email: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(max = 255, message = "Sender name must be 255 characters or less"))]
+    pub sender_name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(email(message = "Support email is invalid"))]
+    pub support_email: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub support_phone: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(url(message = "Support URL is invalid"))]
+    pub support_url: Option<String>,
+}
+
+impl OrganizationBranding {
+    pub fn create(organization_id: Uuid) -> NewOrganizationBranding {
+        NewOrganizationBranding {
+            organization_id,
+            ..Default::default()
+        }
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<OrganizationBranding, DatabaseError> {
+        organization_brandings::table
+            .filter(organization_brandings::organization_id.eq(organization_id))
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load branding for organization")
+    }
+
+    pub fn find_by_custom_domain(
+        custom_domain: &str,
+        conn: &PgConnection,
+    ) -> Result<OrganizationBranding, DatabaseError> {
+        organization_brandings::table
+            .filter(organization_brandings::custom_domain.eq(custom_domain.trim().to_lowercase()))
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load branding for domain")
+    }
+
+    /// Base URL for links to the organization's storefront when it has a custom domain
+    pub fn front_end_url(&self) -> Option<String> {
+        self.custom_domain
+            .as_ref()
+            .map(|custom_domain| format!("https://{}", custom_domain))
+    }
+}
+
+impl NewOrganizationBranding {
+    /// Creates the organization's branding, replacing any existing branding
+    pub fn commit(
+        mut self,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<OrganizationBranding, DatabaseError> {
+        self.normalize();
+        self.validate_record(conn)?;
+
+        let branding: OrganizationBranding = diesel::insert_into(organization_brandings::table)
+            .values(&self)
+            .on_conflict(organization_brandings::organization_id)
+            .do_update()
+            .set((
+                organization_brandings::custom_domain.eq(excluded(organization_brandings::custom_domain)),
+                organization_brandings::logo_url.eq(excluded(organization_brandings::logo_url)),
+                organization_brandings::primary_color.eq(excluded(organization_brandings::primary_color)),
+                organization_brandings::secondary_color.eq(excluded(organization_brandings::secondary_color)),
+                organization_brandings::sender_email.eq(excluded(organization_brandings::sender_email)),
+                organization_brandings::sender_name.eq(excluded(organization_brandings::sender_name)),
+                organization_brandings::support_email.eq(excluded(organization_brandings::support_email)),
+                organization_brandings::support_phone.eq(excluded(organization_brandings::support_phone)),
+                organization_brandings::support_url.eq(excluded(organization_brandings::support_url)),
+                organization_brandings::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not save organization branding")?;
+
+        DomainEvent::create(
+            DomainEventTypes::OrganizationBrandingUpdated,
+            "Organization branding updated".to_string(),
+            Tables::Organizations,
+            Some(branding.organization_id),
+            current_user_id,
+            Some(json!(&branding)),
+        )
+        .commit(conn)?;
+
+        Ok(branding)
+    }
+
+    fn normalize(&mut self) {
+        let trim = |value: &Option<String>| value.as_ref().map(|v| v.trim().to_string());
+        self.custom_domain = self.custom_domain.as_ref().map(|d| d.trim().to_lowercase());
+        self.primary_color = self.primary_color.as_ref().map(|c| c.trim().to_uppercase());
+        self.secondary_color = self.secondary_color.as_ref().map(|c| c.trim().to_uppercase());
+        self.logo_url = trim(&self.logo_url);
+        self.sender_email = trim(&self.sender_email);
+        self.sender_name = trim(&self.sender_name);
+        self.support_email = trim(&self.support_email);
+        self.support_phone = trim(&self.support_phone);
+        self.support_url = trim(&self.support_url);
+    }
+
+    fn validate_record(&self, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let mut validation_errors: Result<(), ValidationErrors> = self.validate();
+        if let Some(ref custom_domain) = self.custom_domain {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "custom_domain",
+                self.custom_domain_valid(custom_domain, conn)?,
+            );
+        }
+        if !self.primary_color.as_ref().map(|c| valid_color(c)).unwrap_or(true) {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "primary_color",
+                Err(validators::create_validation_error(
+                    "invalid_color",
+                    "Primary color must be a hex color such as #1A2B3C",
+                )),
+            );
+        }
+        if !self.secondary_color.as_ref().map(|c| valid_color(c)).unwrap_or(true) {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "secondary_color",
+                Err(validators::create_validation_error(
+                    "invalid_color",
+                    "Secondary color must be a hex color such as #1A2B3C",
+                )),
+            );
+        }
+        Ok(validation_errors?)
+    }
+
+    fn custom_domain_valid(
+        &self,
+        custom_domain: &str,
+        conn: &PgConnection,
+    ) -> Result<Result<(), ValidationError>, DatabaseError> {
+        if !valid_hostname(custom_domain) {
+            return Ok(Err(validators::create_validation_error(
+                "invalid_custom_domain",
+                "Custom domain must be a host name such as tickets.example.com",
+            )));
+        }
+        match OrganizationBranding::find_by_custom_domain(custom_domain, conn).optional()? {
+            Some(branding) if branding.organization_id != self.organization_id => {
+                Ok(Err(validators::create_validation_error(
+                    "custom_domain_taken",
+                    "Custom domain is already in use by another organization",
+                )))
+            }
+            _ => Ok(Ok(())),
+        }
+    }
+}
+
+fn valid_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Host names only, links are always built as https://{custom_domain}
+fn valid_hostname(hostname: &str) -> bool {
+    let labels: Vec<&str> = hostname.split('.').collect();
+    hostname.len() <= 253
+        && labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
@@ -54,6 +54,8 @@ pub struct Organization {
     pub slug_id: Option<Uuid>,
     pub google_ads_conversion_id: Option<String>,
     pub google_ads_conversion_labels: Vec<String>,
+    pub currency: Currencies,
+    pub default_locale: Locales,
 }
 
 #[derive(Serialize)]
@@ -90,6 +92,8 @@ pub struct NewOrganization {
     pub globee_api_key: Option<String>,
     pub max_instances_per_ticket_type: Option<i64>,
     pub settlement_type: Option<SettlementTypes>,
+    pub currency: Option<Currencies>,
+    pub default_locale: Option<Locales>,
 }
 
 #[derive(Default, Serialize, Clone, Deserialize, Debug, PartialEq)]
@@ -152,6 +156,7 @@ impl NewOrganization {
         diesel::update(fee_schedules::table.filter(fee_schedules::id.eq(org.fee_schedule_id)))
             .set((
                 fee_schedules::organization_id.eq(org.id),
+                fee_schedules::currency.eq(org.currency),
                 fee_schedules::updated_at.eq(dsl::now),
             ))
             .execute(conn)
@@ -214,6 +219,8 @@ pub struct OrganizationEditableAttributes {
     pub google_ads_conversion_id: Option<Option<String>>,
     #[serde(default)]
     pub google_ads_conversion_labels: Option<Vec<String>>,
+    pub currency: Option<Currencies>,
+    pub default_locale: Option<Locales>,
 }
 
 impl Organization {
@@ -280,6 +287,18 @@ impl Organization {
         }
     }
 
+    pub fn branding(&self, conn: &PgConnection) -> Result<Option<OrganizationBranding>, DatabaseError> {
+        OrganizationBranding::find_for_organization(self.id, conn).optional()
+    }
+
+    /// Base URL for links to the organization's storefront, its custom domain when one is configured
+    pub fn front_end_url(&self, default_front_end_url: &str, conn: &PgConnection) -> Result<String, DatabaseError> {
+        Ok(self
+            .branding(conn)?
+            .and_then(|branding| branding.front_end_url())
+            .unwrap_or(default_front_end_url.to_string()))
+    }
+
     pub fn timezone(&self) -> Result<Tz, DatabaseError> {
         self.timezone
             .clone()
@@ -388,6 +407,15 @@ impl Organization {
             }
         }
 
+        if let Some(currency) = attributes.currency {
+            if currency != self.currency && self.first_order_date(conn).optional()?.is_some() {
+                return DatabaseError::validation_error(
+                    "currency",
+                    "Currency cannot be changed once orders have been placed",
+                );
+            }
+        }
+
         let event_fee = attributes
             .client_event_fee_in_cents
             .clone()
@@ -405,6 +433,15 @@ impl Organization {
             ))
             .get_result::<Organization>(conn)
             .to_db_error(ErrorCode::UpdateError, "Could not update organization")?;
+        if organization.currency != self.currency {
+            diesel::update(fee_schedules::table.filter(fee_schedules::organization_id.eq(organization.id)))
+                .set((
+                    fee_schedules::currency.eq(organization.currency),
+                    fee_schedules::updated_at.eq(dsl::now),
+                ))
+                .execute(conn)
+                .to_db_error(ErrorCode::UpdateError, "Could not update fee schedule currency")?;
+        }
         organization.schedule_domain_actions(settlement_period_in_days, conn)?;
 
         Ok(organization)
@@ -763,6 +800,7 @@ impl Organization {
         diesel::update(fee_schedule)
             .set((
                 fee_schedules::organization_id.eq(self.id),
+                fee_schedules::currency.eq(self.currency),
                 fee_schedules::updated_at.eq(dsl::now),
             ))
             .execute(conn)
@@ -1178,6 +1216,8 @@ impl Organization {
             postal_code: self.postal_code.clone(),
             phone: self.phone.clone(),
             timezone: self.timezone.clone(),
+            currency: self.currency,
+            default_locale: self.default_locale,
             slug: Slug::primary_slug(self.id, Tables::Organizations, conn)?.slug,
         })
     }
@@ -1194,5 +1234,7 @@ pub struct DisplayOrganization {
     pub postal_code: Option<String>,
     pub phone: Option<String>,
     pub timezone: Option<String>,
+    pub currency: Currencies,
+    pub default_locale: Locales,
     pub slug: String,
 }
@@ -0,0 +1,196 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::prelude::*;
+use models::*;
+use schema::{events, package_ticket_types, ticket_types};
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+use validators::{self, *};
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "package_ticket_types"]
+pub struct PackageTicketType {
+    pub id: Uuid,
+    pub package_id: Uuid,
+    pub ticket_type_id: Uuid,
+    pub quantity: i64,
+    pub price_in_cents: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "package_ticket_types"]
+pub struct NewPackageTicketType {
+    #[serde(default)]
+    pub package_id: Uuid,
+    pub ticket_type_id: Uuid,
+    pub quantity: i64,
+    pub price_in_cents: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Queryable, Serialize)]
+pub struct DisplayPackageTicketType {
+    pub id: Uuid,
+    pub ticket_type_id: Uuid,
+    pub ticket_type_name: String,
+    pub event_id: Uuid,
+    pub event_name: String,
+    pub quantity: i64,
+    pub price_in_cents: i64,
+}
+
+impl PackageTicketType {
+    pub fn create(package_id: Uuid, ticket_type_id: Uuid, quantity: i64, price_in_cents: i64) -> NewPackageTicketType {
+        NewPackageTicketType {
+            package_id,
+            ticket_type_id,
+            quantity,
+            price_in_cents,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<PackageTicketType, DatabaseError> {
+        package_ticket_types::table
+            .filter(package_ticket_types::id.eq(id))
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load package ticket type")
+    }
+
+    pub fn find_for_package(package_id: Uuid, conn: &PgConnection) -> Result<Vec<PackageTicketType>, DatabaseError> {
+        package_ticket_types::table
+            .inner_join(ticket_types::table.inner_join(events::table))
+            .filter(package_ticket_types::package_id.eq(package_id))
+            .order_by(events::event_start.asc())
+            .then_order_by(ticket_types::rank.asc())
+            .select(package_ticket_types::all_columns)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load package ticket types")
+    }
+
+    pub fn find_for_display(
+        package_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<DisplayPackageTicketType>, DatabaseError> {
+        package_ticket_types::table
+            .inner_join(ticket_types::table.inner_join(events::table))
+            .filter(package_ticket_types::package_id.eq(package_id))
+            .order_by(events::event_start.asc())
+            .then_order_by(ticket_types::rank.asc())
+            .select((
+                package_ticket_types::id,
+                package_ticket_types::ticket_type_id,
+                ticket_types::name,
+                events::id,
+                events::name,
+                package_ticket_types::quantity,
+                package_ticket_types::price_in_cents,
+            ))
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load package ticket types")
+    }
+
+    pub fn ticket_type(&self, conn: &PgConnection) -> Result<TicketType, DatabaseError> {
+        TicketType::find(self.ticket_type_id, conn)
+    }
+
+    pub fn destroy(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let package = Package::find(self.package_id, conn)?;
+        package.validate_components_editable(conn)?;
+
+        diesel::delete(self)
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not remove ticket type from package")?;
+
+        DomainEvent::create(
+            DomainEventTypes::PackageUpdated,
+            format!("Ticket type removed from package '{}'", &package.name),
+            Tables::Packages,
+            Some(package.id),
+            current_user_id,
+            Some(json!({ "removed_ticket_type_id": self.ticket_type_id })),
+        )
+        .commit(conn)?;
+
+        Ok(())
+    }
+
+    fn validate_record(
+        validation_errors: Result<(), ValidationErrors>,
+        package: &Package,
+        ticket_type_id: Uuid,
+        quantity: i64,
+        price_in_cents: i64,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        let ticket_type = TicketType::find(ticket_type_id, conn)?;
+        let mut validation_errors = validators::append_validation_error(
+            validation_errors,
+            "quantity",
+            validators::validate_greater_than_or_equal(
+                quantity,
+                1,
+                "must_be_greater_than_zero",
+                "Quantity must be at least 1",
+            ),
+        );
+        validation_errors = validators::append_validation_error(
+            validation_errors,
+            "price_in_cents",
+            validators::validate_greater_than_or_equal(
+                price_in_cents,
+                0,
+                "must_not_be_negative",
+                "Price must not be negative",
+            ),
+        );
+        validation_errors = validators::append_validation_error(
+            validation_errors,
+            "ticket_type_id",
+            validators::event_ids_belong_to_organization_validation(
+                true,
+                package.organization_id,
+                &vec![ticket_type.event_id],
+                conn,
+            )?,
+        );
+        Ok(validation_errors?)
+    }
+}
+
+impl NewPackageTicketType {
+    pub fn commit(
+        self,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<PackageTicketType, DatabaseError> {
+        let package = Package::find(self.package_id, conn)?;
+        package.validate_components_editable(conn)?;
+        PackageTicketType::validate_record(
+            self.validate(),
+            &package,
+            self.ticket_type_id,
+            self.quantity,
+            self.price_in_cents,
+            conn,
+        )?;
+
+        let package_ticket_type: PackageTicketType = diesel::insert_into(package_ticket_types::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not add ticket type to package")?;
+
+        DomainEvent::create(
+            DomainEventTypes::PackageUpdated,
+            format!("Ticket type added to package '{}'", &package.name),
+            Tables::Packages,
+            Some(package.id),
+            current_user_id,
+            Some(json!(&package_ticket_type)),
+        )
+        .commit(conn)?;
+
+        Ok(package_ticket_type)
+    }
+}
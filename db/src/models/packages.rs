@@ -0,0 +1,295 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::dsl::{exists, select};
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use itertools::Itertools;
+use models::*;
+use schema::{order_items, package_ticket_types, packages, ticket_instances, ticket_types};
+use std::cmp;
+use std::collections::HashMap;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "packages"]
+pub struct Package {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "packages"]
+pub struct NewPackage {
+    #[serde(default)]
+    pub organization_id: Uuid,
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub description: Option<String>,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "packages"]
+pub struct PackageEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub description: Option<Option<String>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayPackage {
+    #[serde(flatten)]
+    pub package: Package,
+    pub price_in_cents: i64,
+    pub available: i64,
+    pub ticket_types: Vec<DisplayPackageTicketType>,
+}
+
+impl Package {
+    pub fn create(organization_id: Uuid, name: String) -> NewPackage {
+        NewPackage {
+            organization_id,
+            name,
+            description: None,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<Package, DatabaseError> {
+        packages::table
+            .filter(packages::id.eq(id))
+            .filter(packages::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load package")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        page: u32,
+        limit: u32,
+        conn: &PgConnection,
+    ) -> Result<Payload<Package>, DatabaseError> {
+        use utils::pagination::Paginate;
+
+        let (packages, total) = packages::table
+            .filter(packages::organization_id.eq(organization_id))
+            .filter(packages::deleted_at.is_null())
+            .order_by(packages::name.asc())
+            .paginate(page as i64)
+            .per_page(limit as i64)
+            .load_and_count_pages(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load packages for organization")?;
+
+        Ok(Payload::from_data(packages, page, limit, Some(total as u64)))
+    }
+
+    /// Packages that include at least one ticket type for the event
+    pub fn find_for_event(event_id: Uuid, conn: &PgConnection) -> Result<Vec<Package>, DatabaseError> {
+        packages::table
+            .inner_join(package_ticket_types::table.inner_join(ticket_types::table))
+            .filter(ticket_types::event_id.eq(event_id))
+            .filter(packages::deleted_at.is_null())
+            .select(packages::all_columns)
+            .distinct()
+            .order_by(packages::name.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load packages for event")
+    }
+
+    pub fn organization(&self, conn: &PgConnection) -> Result<Organization, DatabaseError> {
+        Organization::find(self.organization_id, conn)
+    }
+
+    pub fn ticket_types(&self, conn: &PgConnection) -> Result<Vec<PackageTicketType>, DatabaseError> {
+        PackageTicketType::find_for_package(self.id, conn)
+    }
+
+    /// The package is sold for the sum of the prices allocated to each of its ticket types, which
+    /// is also how its revenue is split between events at settlement
+    pub fn price_in_cents(&self, conn: &PgConnection) -> Result<i64, DatabaseError> {
+        Ok(self
+            .ticket_types(conn)?
+            .iter()
+            .map(|ptt| ptt.price_in_cents * ptt.quantity)
+            .sum())
+    }
+
+    /// Number of complete packages that can still be issued from the remaining ticket inventory
+    pub fn available(&self, conn: &PgConnection) -> Result<i64, DatabaseError> {
+        let mut available: Option<i64> = None;
+        for package_ticket_type in self.ticket_types(conn)? {
+            let ticket_count = package_ticket_type
+                .ticket_type(conn)?
+                .valid_available_ticket_count(conn)? as i64;
+            let package_count = ticket_count / package_ticket_type.quantity;
+            available = Some(available.map(|a| cmp::min(a, package_count)).unwrap_or(package_count));
+        }
+        Ok(available.unwrap_or(0))
+    }
+
+    pub fn has_order_items(&self, conn: &PgConnection) -> Result<bool, DatabaseError> {
+        select(exists(order_items::table.filter(order_items::package_id.eq(self.id))))
+            .get_result(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not check if package has order items")
+    }
+
+    /// Ticket types can only be changed until the package is first added to an order, otherwise
+    /// existing orders could no longer be refunded or transferred as complete packages
+    pub(crate) fn validate_components_editable(&self, conn: &PgConnection) -> Result<(), DatabaseError> {
+        if self.has_order_items(conn)? {
+            return DatabaseError::business_process_error(
+                "Ticket types cannot be changed once the package has been added to an order",
+            );
+        }
+        Ok(())
+    }
+
+    pub fn for_display(self, conn: &PgConnection) -> Result<DisplayPackage, DatabaseError> {
+        Ok(DisplayPackage {
+            price_in_cents: self.price_in_cents(conn)?,
+            available: self.available(conn)?,
+            ticket_types: PackageTicketType::find_for_display(self.id, conn)?,
+            package: self,
+        })
+    }
+
+    pub fn update(
+        &self,
+        attributes: PackageEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Package, DatabaseError> {
+        attributes.validate()?;
+
+        let package: Package = diesel::update(self)
+            .set((attributes, packages::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update package")?;
+
+        DomainEvent::create(
+            DomainEventTypes::PackageUpdated,
+            format!("Package '{}' updated", &package.name),
+            Tables::Packages,
+            Some(package.id),
+            current_user_id,
+            Some(json!(&package)),
+        )
+        .commit(conn)?;
+
+        Ok(package)
+    }
+
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::PackageDeleted,
+            format!("Package '{}' deleted", &self.name),
+            Tables::Packages,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((packages::deleted_at.eq(dsl::now), packages::updated_at.eq(dsl::now)))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete package")?;
+
+        Ok(())
+    }
+
+    /// Tickets issued for a package can only be refunded or transferred together with the rest of
+    /// the package. Checks that, for every order and package represented in `ticket_instance_ids`,
+    /// the same number of complete packages is covered by each of the package's ticket types.
+    pub(crate) fn validate_complete_packages(
+        ticket_instance_ids: &[Uuid],
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        let package_tickets: Vec<(Uuid, Option<Uuid>, Option<Uuid>)> = ticket_instances::table
+            .inner_join(order_items::table)
+            .filter(ticket_instances::id.eq_any(ticket_instance_ids))
+            .filter(order_items::package_id.is_not_null())
+            .select((
+                order_items::order_id,
+                order_items::package_id,
+                order_items::ticket_type_id,
+            ))
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load package tickets")?;
+
+        for ((_, package_id), tickets) in package_tickets
+            .into_iter()
+            .sorted_by_key(|(order_id, package_id, _)| (*order_id, *package_id))
+            .into_iter()
+            .group_by(|(order_id, package_id, _)| (*order_id, *package_id))
+            .into_iter()
+        {
+            let package_id = match package_id {
+                Some(package_id) => package_id,
+                None => continue,
+            };
+            let mut ticket_counts: HashMap<Uuid, i64> = HashMap::new();
+            for (_, _, ticket_type_id) in tickets {
+                if let Some(ticket_type_id) = ticket_type_id {
+                    *ticket_counts.entry(ticket_type_id).or_insert(0) += 1;
+                }
+            }
+
+            let mut package_count: Option<i64> = None;
+            let mut complete = true;
+            for package_ticket_type in PackageTicketType::find_for_package(package_id, conn)? {
+                let count = ticket_counts
+                    .get(&package_ticket_type.ticket_type_id)
+                    .cloned()
+                    .unwrap_or(0);
+                let count_for_ticket_type = count / package_ticket_type.quantity;
+                if count % package_ticket_type.quantity != 0
+                    || package_count.map(|c| c != count_for_ticket_type).unwrap_or(false)
+                {
+                    complete = false;
+                    break;
+                }
+                package_count = Some(count_for_ticket_type);
+            }
+
+            if !complete || package_count.unwrap_or(0) == 0 {
+                return DatabaseError::business_process_error(
+                    "Tickets purchased as part of a package must include every ticket in the package",
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NewPackage {
+    pub fn commit(self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<Package, DatabaseError> {
+        self.validate()?;
+
+        let package: Package = diesel::insert_into(packages::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create package")?;
+
+        DomainEvent::create(
+            DomainEventTypes::PackageCreated,
+            format!("Package '{}' created", &package.name),
+            Tables::Packages,
+            Some(package.id),
+            current_user_id,
+            Some(json!(&package)),
+        )
+        .commit(conn)?;
+
+        Ok(package)
+    }
+}
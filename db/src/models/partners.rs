@@ -0,0 +1,295 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::{partner_guests, partners, users};
+use url::Url;
+use utils::errors::*;
+use utils::hash::sha256;
+use utils::rand::random_alpha_string;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+use validators;
+
+const API_KEY_PREFIX: &str = "pk_";
+const API_KEY_LENGTH: usize = 40;
+
+/// Third party sites selling inventory through the partner checkout API. Partners authenticate with an
+/// API key, only the key's hash is stored so the key itself is shown once when it is generated.
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "partners"]
+pub struct Partner {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(skip)]
+    pub api_key_hash: Option<String>,
+    pub allowed_origins: Vec<String>,
+    pub commission_rate_percent: f32,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "partners"]
+pub struct NewPartner {
+    #[validate(length(min = 1, max = 255, message = "Name cannot be blank"))]
+    pub name: String,
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    #[serde(default)]
+    pub commission_rate_percent: f32,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "partners"]
+pub struct PartnerEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, max = 255, message = "Name cannot be blank"))]
+    pub name: Option<String>,
+    pub allowed_origins: Option<Vec<String>>,
+    pub commission_rate_percent: Option<f32>,
+}
+
+impl Partner {
+    pub fn create(name: String, allowed_origins: Vec<String>, commission_rate_percent: f32) -> NewPartner {
+        NewPartner {
+            name,
+            allowed_origins,
+            commission_rate_percent,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<Partner, DatabaseError> {
+        partners::table
+            .filter(partners::id.eq(id))
+            .filter(partners::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load partner")
+    }
+
+    pub fn all(conn: &PgConnection) -> Result<Vec<Partner>, DatabaseError> {
+        partners::table
+            .filter(partners::deleted_at.is_null())
+            .order_by(partners::name.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load partners")
+    }
+
+    pub fn find_by_api_key(api_key: &str, conn: &PgConnection) -> Result<Partner, DatabaseError> {
+        partners::table
+            .filter(partners::api_key_hash.eq(sha256::digest(api_key.trim())))
+            .filter(partners::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load partner")
+    }
+
+    /// Whether any partner embeds the checkout on the origin, used to answer CORS preflight requests
+    /// which do not include the partner's API key
+    pub fn origin_allowed_for_any(origin: &str, conn: &PgConnection) -> Result<bool, DatabaseError> {
+        let origin = match normalize_origin(origin) {
+            Some(origin) => origin,
+            None => return Ok(false),
+        };
+        diesel::select(dsl::exists(
+            partners::table
+                .filter(partners::allowed_origins.contains(vec![origin]))
+                .filter(partners::deleted_at.is_null()),
+        ))
+        .get_result(conn)
+        .to_db_error(ErrorCode::QueryError, "Unable to check partner origins")
+    }
+
+    pub fn origin_allowed(&self, origin: &str) -> bool {
+        normalize_origin(origin)
+            .map(|origin| self.allowed_origins.contains(&origin))
+            .unwrap_or(false)
+    }
+
+    /// Replaces the partner's API key, returning the new key. Requests made with the previous key are
+    /// rejected from this point on.
+    pub fn generate_api_key(
+        &self,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<String, DatabaseError> {
+        let api_key = format!("{}{}", API_KEY_PREFIX, random_alpha_string(API_KEY_LENGTH));
+        diesel::update(self)
+            .set((
+                partners::api_key_hash.eq(sha256::digest(&api_key)),
+                partners::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not generate partner API key")?;
+
+        DomainEvent::create(
+            DomainEventTypes::PartnerApiKeyGenerated,
+            format!("API key generated for partner '{}'", &self.name),
+            Tables::Partners,
+            Some(self.id),
+            current_user_id,
+            None,
+        )
+        .commit(conn)?;
+
+        Ok(api_key)
+    }
+
+    pub fn update(
+        &self,
+        mut attributes: PartnerEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Partner, DatabaseError> {
+        attributes.allowed_origins = attributes.allowed_origins.map(normalize_origins);
+        Partner::validate_record(
+            attributes.validate(),
+            attributes.allowed_origins.as_ref().unwrap_or(&self.allowed_origins),
+            attributes
+                .commission_rate_percent
+                .unwrap_or(self.commission_rate_percent),
+        )?;
+
+        let partner: Partner = diesel::update(self)
+            .set((attributes, partners::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update partner")?;
+
+        DomainEvent::create(
+            DomainEventTypes::PartnerUpdated,
+            format!("Partner '{}' updated", &partner.name),
+            Tables::Partners,
+            Some(partner.id),
+            current_user_id,
+            Some(json!(&partner)),
+        )
+        .commit(conn)?;
+
+        Ok(partner)
+    }
+
+    /// Records a user registered by email only through the partner's checkout
+    pub fn add_guest(&self, user_id: Uuid, conn: &PgConnection) -> Result<(), DatabaseError> {
+        diesel::insert_into(partner_guests::table)
+            .values((
+                partner_guests::partner_id.eq(self.id),
+                partner_guests::user_id.eq(user_id),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not add partner guest")?;
+        Ok(())
+    }
+
+    /// Returning guest of the partner with the email, guests who have since set a password of their
+    /// own must sign in instead
+    pub fn find_guest_by_email(&self, email: &str, conn: &PgConnection) -> Result<Option<User>, DatabaseError> {
+        partner_guests::table
+            .inner_join(users::table.on(partner_guests::user_id.eq(users::id)))
+            .filter(partner_guests::partner_id.eq(self.id))
+            .filter(users::email.eq(email.trim().to_lowercase()))
+            .filter(users::password_modified_at.eq(users::created_at))
+            .filter(users::deleted_at.is_null())
+            .select(users::all_columns)
+            .first(conn)
+            .optional()
+            .to_db_error(ErrorCode::QueryError, "Could not load partner guest")
+    }
+
+    /// Removes the partner, its API key stops working but orders already placed keep their attribution
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::PartnerDeleted,
+            format!("Partner '{}' deleted", &self.name),
+            Tables::Partners,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((
+                partners::api_key_hash.eq(None::<String>),
+                partners::deleted_at.eq(dsl::now),
+                partners::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete partner")?;
+
+        Ok(())
+    }
+
+    fn validate_record(
+        validation_errors: Result<(), ValidationErrors>,
+        allowed_origins: &[String],
+        commission_rate_percent: f32,
+    ) -> Result<(), DatabaseError> {
+        let mut validation_errors = validation_errors;
+        if allowed_origins
+            .iter()
+            .any(|origin| normalize_origin(origin).as_ref() != Some(origin))
+        {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "allowed_origins",
+                Err(validators::create_validation_error(
+                    "invalid_origin",
+                    "Allowed origins must be a scheme and host such as https://tickets.example.com",
+                )),
+            );
+        }
+        if commission_rate_percent < 0f32 || commission_rate_percent > 100f32 {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "commission_rate_percent",
+                Err(validators::create_validation_error(
+                    "invalid_rate",
+                    "Commission rate must be between 0 and 100 percent",
+                )),
+            );
+        }
+        Ok(validation_errors?)
+    }
+}
+
+impl NewPartner {
+    pub fn commit(mut self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<Partner, DatabaseError> {
+        self.allowed_origins = normalize_origins(self.allowed_origins);
+        Partner::validate_record(self.validate(), &self.allowed_origins, self.commission_rate_percent)?;
+
+        let partner: Partner = diesel::insert_into(partners::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create partner")?;
+
+        DomainEvent::create(
+            DomainEventTypes::PartnerCreated,
+            format!("Partner '{}' created", &partner.name),
+            Tables::Partners,
+            Some(partner.id),
+            current_user_id,
+            Some(json!(&partner)),
+        )
+        .commit(conn)?;
+
+        Ok(partner)
+    }
+}
+
+/// Origins are compared the way browsers send them in the `Origin` header, e.g. `https://example.com`
+fn normalize_origin(origin: &str) -> Option<String> {
+    match Url::parse(origin.trim()) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => Some(url.origin().ascii_serialization()),
+        _ => None,
+    }
+}
+
+/// Invalid origins are kept as entered so validation can report them
+fn normalize_origins(origins: Vec<String>) -> Vec<String> {
+    origins
+        .into_iter()
+        .map(|origin| normalize_origin(&origin).unwrap_or(origin))
+        .collect()
+}
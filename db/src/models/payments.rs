@@ -27,6 +27,7 @@ pub struct Payment {
     updated_at: NaiveDateTime,
     pub url_nonce: Option<String>,
     pub refund_id: Option<Uuid>,
+    pub cash_drawer_session_id: Option<Uuid>,
 }
 
 impl Payment {
@@ -53,6 +54,7 @@ impl Payment {
             raw_data,
             url_nonce,
             refund_id,
+            cash_drawer_session_id: None,
         }
     }
 
@@ -104,7 +106,7 @@ impl Payment {
         refund_data: Option<serde_json::Value>,
         conn: &PgConnection,
     ) -> Result<Payment, DatabaseError> {
-        let refund_payment = Payment::create(
+        let mut refund_payment = Payment::create(
             self.order_id,
             self.created_by,
             PaymentStatus::Refunded,
@@ -115,8 +117,15 @@ impl Payment {
             refund_data.clone(),
             None,
             Some(refund.id),
-        )
-        .commit(Some(current_user_id), conn)?;
+        );
+        // Box office refunds are paid out of the refunding staff member's drawer
+        if let Some(cash_drawer_session_id) = self.cash_drawer_session_id {
+            let organization_id = CashDrawerSession::find(cash_drawer_session_id, conn)?.organization_id;
+            refund_payment.cash_drawer_session_id = CashDrawerSession::find_open_for_user(current_user_id, conn)?
+                .filter(|session| session.organization_id == organization_id)
+                .map(|session| session.id);
+        }
+        let refund_payment = refund_payment.commit(Some(current_user_id), conn)?;
 
         DomainEvent::create(
             DomainEventTypes::PaymentRefund,
@@ -289,6 +298,7 @@ pub struct NewPayment {
     raw_data: Option<serde_json::Value>,
     url_nonce: Option<String>,
     refund_id: Option<Uuid>,
+    pub cash_drawer_session_id: Option<Uuid>,
 }
 
 impl NewPayment {
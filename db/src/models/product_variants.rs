@@ -0,0 +1,259 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable, Uuid as dUuid};
+use models::*;
+use schema::product_variants;
+use std::cmp;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+use validators::{self, *};
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "product_variants"]
+pub struct ProductVariant {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub name: String,
+    pub price_in_cents: Option<i64>,
+    pub inventory: i64,
+    pub rank: i32,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "product_variants"]
+pub struct NewProductVariant {
+    #[serde(default)]
+    pub product_id: Uuid,
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub price_in_cents: Option<i64>,
+    pub inventory: i64,
+    #[serde(default)]
+    pub rank: i32,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "product_variants"]
+pub struct ProductVariantEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub price_in_cents: Option<Option<i64>>,
+    pub inventory: Option<i64>,
+    pub rank: Option<i32>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayProductVariant {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub name: String,
+    pub price_in_cents: i64,
+    pub inventory: i64,
+    pub available: i64,
+    pub rank: i32,
+}
+
+impl ProductVariant {
+    pub fn create(product_id: Uuid, name: String, inventory: i64) -> NewProductVariant {
+        NewProductVariant {
+            product_id,
+            name,
+            price_in_cents: None,
+            inventory,
+            rank: 0,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<ProductVariant, DatabaseError> {
+        product_variants::table
+            .filter(product_variants::id.eq(id))
+            .filter(product_variants::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load product variant")
+    }
+
+    pub fn find_for_product(product_id: Uuid, conn: &PgConnection) -> Result<Vec<ProductVariant>, DatabaseError> {
+        product_variants::table
+            .filter(product_variants::product_id.eq(product_id))
+            .filter(product_variants::deleted_at.is_null())
+            .order_by(product_variants::rank.asc())
+            .then_order_by(product_variants::name.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load product variants")
+    }
+
+    pub fn product(&self, conn: &PgConnection) -> Result<Product, DatabaseError> {
+        Product::find(self.product_id, conn)
+    }
+
+    /// Variants without their own price are sold at the product's price
+    pub fn unit_price_in_cents(&self, product: &Product) -> i64 {
+        self.price_in_cents.unwrap_or(product.price_in_cents)
+    }
+
+    /// Quantity held by paid orders and by carts that have not yet expired. Pass the cart being
+    /// updated as `excluding_order_id` to ignore the quantity it already holds.
+    pub fn sold_quantity(&self, excluding_order_id: Option<Uuid>, conn: &PgConnection) -> Result<i64, DatabaseError> {
+        #[derive(QueryableByName)]
+        struct R {
+            #[sql_type = "BigInt"]
+            quantity: i64,
+        }
+
+        let result: R = diesel::sql_query(
+            r#"
+            SELECT CAST(COALESCE(SUM(oi.quantity - oi.refunded_quantity), 0) AS BIGINT) AS quantity
+            FROM order_items oi
+            JOIN orders o ON o.id = oi.order_id
+            WHERE oi.product_variant_id = $1
+            AND oi.item_type = 'AddOns'
+            AND ($2 IS NULL OR o.id <> $2)
+            AND (
+                o.status = 'Paid'
+                OR (o.status IN ('Draft', 'PendingPayment') AND o.expires_at > now())
+            )
+            "#,
+        )
+        .bind::<dUuid, _>(self.id)
+        .bind::<Nullable<dUuid>, _>(excluding_order_id)
+        .get_result(conn)
+        .to_db_error(
+            ErrorCode::QueryError,
+            "Could not load sold quantity for product variant",
+        )?;
+
+        Ok(result.quantity)
+    }
+
+    pub fn available(&self, excluding_order_id: Option<Uuid>, conn: &PgConnection) -> Result<i64, DatabaseError> {
+        Ok(cmp::max(
+            0,
+            self.inventory - self.sold_quantity(excluding_order_id, conn)?,
+        ))
+    }
+
+    pub fn for_display(&self, product: &Product, conn: &PgConnection) -> Result<DisplayProductVariant, DatabaseError> {
+        Ok(DisplayProductVariant {
+            id: self.id,
+            product_id: self.product_id,
+            name: self.name.clone(),
+            price_in_cents: self.unit_price_in_cents(product),
+            inventory: self.inventory,
+            available: self.available(None, conn)?,
+            rank: self.rank,
+        })
+    }
+
+    pub fn update(
+        &self,
+        attributes: ProductVariantEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<ProductVariant, DatabaseError> {
+        ProductVariant::validate_record(
+            attributes.validate(),
+            attributes.price_in_cents.unwrap_or(self.price_in_cents),
+            attributes.inventory.unwrap_or(self.inventory),
+        )?;
+
+        let variant: ProductVariant = diesel::update(self)
+            .set((attributes, product_variants::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update product variant")?;
+
+        DomainEvent::create(
+            DomainEventTypes::ProductVariantUpdated,
+            format!("Product variant '{}' updated", &variant.name),
+            Tables::ProductVariants,
+            Some(variant.id),
+            current_user_id,
+            Some(json!(&variant)),
+        )
+        .commit(conn)?;
+
+        Ok(variant)
+    }
+
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::ProductVariantDeleted,
+            format!("Product variant '{}' deleted", &self.name),
+            Tables::ProductVariants,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((
+                product_variants::deleted_at.eq(dsl::now),
+                product_variants::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete product variant")?;
+
+        Ok(())
+    }
+
+    fn validate_record(
+        validation_errors: Result<(), ValidationErrors>,
+        price_in_cents: Option<i64>,
+        inventory: i64,
+    ) -> Result<(), DatabaseError> {
+        let mut validation_errors = validators::append_validation_error(
+            validation_errors,
+            "inventory",
+            validators::validate_greater_than_or_equal(
+                inventory,
+                0,
+                "must_not_be_negative",
+                "Inventory must not be negative",
+            ),
+        );
+        if let Some(price_in_cents) = price_in_cents {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "price_in_cents",
+                validators::validate_greater_than_or_equal(
+                    price_in_cents,
+                    0,
+                    "must_not_be_negative",
+                    "Price must not be negative",
+                ),
+            );
+        }
+        Ok(validation_errors?)
+    }
+}
+
+impl NewProductVariant {
+    pub fn commit(self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<ProductVariant, DatabaseError> {
+        ProductVariant::validate_record(self.validate(), self.price_in_cents, self.inventory)?;
+
+        let variant: ProductVariant = diesel::insert_into(product_variants::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create product variant")?;
+
+        DomainEvent::create(
+            DomainEventTypes::ProductVariantCreated,
+            format!("Product variant '{}' created", &variant.name),
+            Tables::ProductVariants,
+            Some(variant.id),
+            current_user_id,
+            Some(json!(&variant)),
+        )
+        .commit(conn)?;
+
+        Ok(variant)
+    }
+}
@@ -0,0 +1,243 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::products;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+use validators::{self, *};
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "products"]
+pub struct Product {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub event_id: Option<Uuid>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price_in_cents: i64,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "products"]
+pub struct NewProduct {
+    #[serde(default)]
+    pub organization_id: Uuid,
+    pub event_id: Option<Uuid>,
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    pub description: Option<String>,
+    pub price_in_cents: i64,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "products"]
+pub struct ProductEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option_deserialize_unless_blank")]
+    pub description: Option<Option<String>>,
+    pub price_in_cents: Option<i64>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayProduct {
+    #[serde(flatten)]
+    pub product: Product,
+    pub variants: Vec<DisplayProductVariant>,
+}
+
+impl Product {
+    pub fn create(organization_id: Uuid, event_id: Option<Uuid>, name: String, price_in_cents: i64) -> NewProduct {
+        NewProduct {
+            organization_id,
+            event_id,
+            name,
+            description: None,
+            price_in_cents,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<Product, DatabaseError> {
+        products::table
+            .filter(products::id.eq(id))
+            .filter(products::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load product")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        page: u32,
+        limit: u32,
+        conn: &PgConnection,
+    ) -> Result<Payload<Product>, DatabaseError> {
+        use utils::pagination::Paginate;
+
+        let (products, total) = products::table
+            .filter(products::organization_id.eq(organization_id))
+            .filter(products::deleted_at.is_null())
+            .order_by(products::name.asc())
+            .paginate(page as i64)
+            .per_page(limit as i64)
+            .load_and_count_pages(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load products for organization")?;
+
+        Ok(Payload::from_data(products, page, limit, Some(total as u64)))
+    }
+
+    /// Products offered for the event, including the organization's products that are not tied to a single event
+    pub fn find_for_event(event_id: Uuid, conn: &PgConnection) -> Result<Vec<Product>, DatabaseError> {
+        let event = Event::find(event_id, conn)?;
+        products::table
+            .filter(products::organization_id.eq(event.organization_id))
+            .filter(products::event_id.eq(event_id).or(products::event_id.is_null()))
+            .filter(products::deleted_at.is_null())
+            .order_by(products::name.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load products for event")
+    }
+
+    pub fn organization(&self, conn: &PgConnection) -> Result<Organization, DatabaseError> {
+        Organization::find(self.organization_id, conn)
+    }
+
+    pub fn variants(&self, conn: &PgConnection) -> Result<Vec<ProductVariant>, DatabaseError> {
+        ProductVariant::find_for_product(self.id, conn)
+    }
+
+    /// Add-ons can be bought for the product's event, or for any of the organization's events when
+    /// the product is not tied to a single event
+    pub fn available_for_event(&self, event: &Event) -> bool {
+        match self.event_id {
+            Some(event_id) => event_id == event.id,
+            None => self.organization_id == event.organization_id,
+        }
+    }
+
+    pub fn for_display(self, conn: &PgConnection) -> Result<DisplayProduct, DatabaseError> {
+        let mut variants = Vec::new();
+        for variant in self.variants(conn)? {
+            variants.push(variant.for_display(&self, conn)?);
+        }
+
+        Ok(DisplayProduct {
+            product: self,
+            variants,
+        })
+    }
+
+    pub fn update(
+        &self,
+        attributes: ProductEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Product, DatabaseError> {
+        Product::validate_record(
+            attributes.validate(),
+            self.organization_id,
+            self.event_id,
+            attributes.price_in_cents.unwrap_or(self.price_in_cents),
+            conn,
+        )?;
+
+        let product: Product = diesel::update(self)
+            .set((attributes, products::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update product")?;
+
+        DomainEvent::create(
+            DomainEventTypes::ProductUpdated,
+            format!("Product '{}' updated", &product.name),
+            Tables::Products,
+            Some(product.id),
+            current_user_id,
+            Some(json!(&product)),
+        )
+        .commit(conn)?;
+
+        Ok(product)
+    }
+
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::ProductDeleted,
+            format!("Product '{}' deleted", &self.name),
+            Tables::Products,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((products::deleted_at.eq(dsl::now), products::updated_at.eq(dsl::now)))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete product")?;
+
+        Ok(())
+    }
+
+    fn validate_record(
+        validation_errors: Result<(), ValidationErrors>,
+        organization_id: Uuid,
+        event_id: Option<Uuid>,
+        price_in_cents: i64,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        let mut validation_errors = validators::append_validation_error(
+            validation_errors,
+            "price_in_cents",
+            validators::validate_greater_than_or_equal(
+                price_in_cents,
+                0,
+                "must_not_be_negative",
+                "Price must not be negative",
+            ),
+        );
+        if let Some(event_id) = event_id {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "event_id",
+                validators::event_ids_belong_to_organization_validation(true, organization_id, &vec![event_id], conn)?,
+            );
+        }
+        Ok(validation_errors?)
+    }
+}
+
+impl NewProduct {
+    pub fn commit(self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<Product, DatabaseError> {
+        Product::validate_record(
+            self.validate(),
+            self.organization_id,
+            self.event_id,
+            self.price_in_cents,
+            conn,
+        )?;
+
+        let product: Product = diesel::insert_into(products::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create product")?;
+
+        DomainEvent::create(
+            DomainEventTypes::ProductCreated,
+            format!("Product '{}' created", &product.name),
+            Tables::Products,
+            Some(product.id),
+            current_user_id,
+            Some(json!(&product)),
+        )
+        .commit(conn)?;
+
+        Ok(product)
+    }
+}
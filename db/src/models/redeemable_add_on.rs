@@ -0,0 +1,116 @@
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::{order_items, orders, product_variants, products};
+use utils::errors::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Queryable, Serialize)]
+pub struct RedeemableAddOn {
+    pub order_item_id: Uuid,
+    pub order_id: Uuid,
+    pub product_id: Uuid,
+    pub product_variant_id: Uuid,
+    pub product_name: String,
+    pub variant_name: String,
+    pub quantity: i64,
+    pub refunded_quantity: i64,
+    pub redeemed_quantity: i64,
+}
+
+impl RedeemableAddOn {
+    pub fn remaining_quantity(&self) -> i64 {
+        self.quantity - self.refunded_quantity - self.redeemed_quantity
+    }
+
+    /// Paid add-ons for the event that were bought in the same order as the scanned ticket
+    pub fn find_for_ticket(
+        ticket: &TicketInstance,
+        event_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<RedeemableAddOn>, DatabaseError> {
+        let order_item_id = match ticket.order_item_id {
+            Some(order_item_id) => order_item_id,
+            None => return Ok(Vec::new()),
+        };
+        let order_id = OrderItem::find(order_item_id, conn)?.order_id;
+
+        order_items::table
+            .inner_join(orders::table)
+            .inner_join(product_variants::table.inner_join(products::table))
+            .filter(order_items::order_id.eq(order_id))
+            .filter(order_items::event_id.eq(event_id))
+            .filter(order_items::item_type.eq(OrderItemTypes::AddOns))
+            .filter(orders::status.eq(OrderStatus::Paid))
+            .order_by(products::name.asc())
+            .then_order_by(product_variants::rank.asc())
+            .select((
+                order_items::id,
+                order_items::order_id,
+                products::id,
+                product_variants::id,
+                products::name,
+                product_variants::name,
+                order_items::quantity,
+                order_items::refunded_quantity,
+                order_items::redeemed_quantity,
+            ))
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load add-ons for ticket")
+    }
+
+    /// Marks `quantity` units of an add-on attached to the scanned ticket as handed out at the door
+    pub fn redeem(
+        ticket: &TicketInstance,
+        event_id: Uuid,
+        order_item_id: Uuid,
+        quantity: i64,
+        current_user_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<RedeemableAddOn, DatabaseError> {
+        let add_on = match RedeemableAddOn::find_for_ticket(ticket, event_id, conn)?
+            .into_iter()
+            .find(|a| a.order_item_id == order_item_id)
+        {
+            Some(add_on) => add_on,
+            None => {
+                return DatabaseError::business_process_error("Add-on was not purchased with this ticket");
+            }
+        };
+
+        if quantity <= 0 {
+            return DatabaseError::validation_error("quantity", "Quantity must be greater than 0");
+        }
+        if quantity > add_on.remaining_quantity() {
+            return DatabaseError::business_process_error("Add-on quantity has already been redeemed");
+        }
+
+        diesel::update(order_items::table.filter(order_items::id.eq(order_item_id)))
+            .set((
+                order_items::redeemed_quantity.eq(order_items::redeemed_quantity + quantity),
+                order_items::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not redeem add-on")?;
+
+        DomainEvent::create(
+            DomainEventTypes::AddOnRedeemed,
+            format!("Add-on '{} - {}' redeemed", &add_on.product_name, &add_on.variant_name),
+            Tables::Orders,
+            Some(add_on.order_id),
+            Some(current_user_id),
+            Some(json!({
+                "order_item_id": order_item_id,
+                "ticket_instance_id": ticket.id,
+                "quantity": quantity
+            })),
+        )
+        .commit(conn)?;
+
+        Ok(RedeemableAddOn {
+            redeemed_quantity: add_on.redeemed_quantity + quantity,
+            ..add_on
+        })
+    }
+}
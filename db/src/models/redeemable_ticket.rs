@@ -1,5 +1,5 @@
 use chrono::prelude::*;
-use diesel::sql_types::{BigInt, Nullable, Text, Timestamp, Uuid as dUuid};
+use diesel::sql_types::{BigInt, Integer, Nullable, Text, Timestamp, Uuid as dUuid};
 use models::*;
 use uuid::Uuid;
 
@@ -27,8 +27,13 @@ pub struct RedeemableTicket {
     pub phone: Option<String>,
     #[sql_type = "Nullable<Text>"]
     pub redeem_key: Option<String>,
+    /// Lets scanners verify rotating redeem codes while offline
+    #[sql_type = "Nullable<Text>"]
+    pub redeem_secret: Option<String>,
     #[sql_type = "Nullable<Timestamp>"]
     pub redeem_date: Option<NaiveDateTime>,
+    #[sql_type = "Nullable<Integer>"]
+    pub barcode_rotation_seconds: Option<i32>,
     #[sql_type = "Text"]
     pub status: TicketInstanceStatus,
     #[sql_type = "dUuid"]
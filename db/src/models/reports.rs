@@ -3,7 +3,7 @@ use chrono::Duration;
 use chrono_tz::Tz;
 use diesel;
 use diesel::prelude::*;
-use diesel::sql_types::{BigInt, Bool, Nullable, Text, Time, Timestamp, Uuid as dUuid};
+use diesel::sql_types::{BigInt, Bool, Float, Nullable, Text, Time, Timestamp, Uuid as dUuid};
 use itertools::Itertools;
 use models::*;
 use std::collections::HashMap;
@@ -198,6 +198,8 @@ pub struct SalesSummaryReportRow {
     #[sql_type = "Nullable<Timestamp>"]
     pub event_date: Option<NaiveDateTime>,
     #[sql_type = "Text"]
+    pub currency: Currencies,
+    #[sql_type = "Text"]
     pub ticket_name: String,
     #[sql_type = "BigInt"]
     pub face_value_in_cents: i64,
@@ -232,6 +234,8 @@ pub struct DomainTransactionReportRow {
     pub ticket_type_name: String,
     #[sql_type = "Timestamp"]
     pub transaction_date: NaiveDateTime,
+    #[sql_type = "Text"]
+    pub currency: Currencies,
     #[sql_type = "Nullable<Text>"]
     pub point_of_sale: Option<String>,
     #[sql_type = "Text"]
@@ -303,6 +307,8 @@ pub struct TransactionReportRow {
     pub payment_provider: Option<String>,
     #[sql_type = "Timestamp"]
     pub transaction_date: NaiveDateTime,
+    #[sql_type = "Text"]
+    pub currency: Currencies,
     #[sql_type = "Nullable<Text>"]
     pub redemption_code: Option<String>,
     #[sql_type = "dUuid"]
@@ -378,6 +384,8 @@ pub struct BoxOfficeSalesSummaryReportRow {
     pub event_date: Option<NaiveDateTime>,
     #[sql_type = "Nullable<Text>"]
     pub external_payment_type: Option<ExternalPaymentType>,
+    #[sql_type = "Text"]
+    pub currency: Currencies,
     #[sql_type = "BigInt"]
     pub number_of_tickets: i64,
     #[sql_type = "BigInt"]
@@ -392,11 +400,14 @@ pub struct BoxOfficeSalesSummaryReportRow {
 pub struct BoxOfficeSalesSummaryReport {
     pub operators: Vec<BoxOfficeSalesSummaryOperatorRow>,
     pub payments: Vec<BoxOfficeSalesSummaryPaymentRow>,
+    /// Cash drawer shifts opened during the period
+    pub shifts: Vec<DisplayCashDrawerSession>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct BoxOfficeSalesSummaryPaymentRow {
     pub payment_type: ExternalPaymentType,
+    pub currency: Currencies,
     pub quantity: u32,
     pub total_sales_in_cents: u32,
 }
@@ -413,6 +424,7 @@ pub struct BoxOfficeSalesSummaryOperatorRow {
 pub struct BoxOfficeSalesSummaryOperatorEventRow {
     pub event_name: Option<String>,
     pub event_date: Option<NaiveDateTime>,
+    pub currency: Currencies,
     pub number_of_tickets: u32,
     pub face_value_in_cents: u32,
     pub revenue_share_value_in_cents: u32,
@@ -510,6 +522,58 @@ pub struct ScanCountReportRow {
     pub not_scanned_count: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Queryable, QueryableByName)]
+pub struct TaxLiabilityReportRow {
+    #[sql_type = "dUuid"]
+    pub tax_rule_id: Uuid,
+    #[sql_type = "Text"]
+    pub jurisdiction: String,
+    #[sql_type = "Text"]
+    pub tax_name: String,
+    #[sql_type = "Float"]
+    pub rate_percent: f32,
+    #[sql_type = "Bool"]
+    pub inclusive: bool,
+    #[sql_type = "Timestamp"]
+    pub period_start: NaiveDateTime,
+    #[sql_type = "Text"]
+    pub currency: Currencies,
+    #[sql_type = "BigInt"]
+    pub taxable_sales_in_cents: i64,
+    #[sql_type = "BigInt"]
+    pub refunded_taxable_sales_in_cents: i64,
+    #[sql_type = "BigInt"]
+    pub tax_collected_in_cents: i64,
+    #[sql_type = "BigInt"]
+    pub tax_refunded_in_cents: i64,
+    #[sql_type = "BigInt"]
+    pub net_tax_in_cents: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Queryable, QueryableByName)]
+pub struct EventSeriesReportRow {
+    #[sql_type = "dUuid"]
+    pub event_id: Uuid,
+    #[sql_type = "Text"]
+    pub event_name: String,
+    #[sql_type = "Nullable<Timestamp>"]
+    pub event_start: Option<NaiveDateTime>,
+    #[sql_type = "Timestamp"]
+    pub occurrence_start: NaiveDateTime,
+    #[sql_type = "dUuid"]
+    pub ticket_type_id: Uuid,
+    #[sql_type = "Text"]
+    pub ticket_type_name: String,
+    #[sql_type = "BigInt"]
+    pub sold_count: i64,
+    #[sql_type = "BigInt"]
+    pub comp_count: i64,
+    #[sql_type = "BigInt"]
+    pub sales_total_in_cents: i64,
+    #[sql_type = "BigInt"]
+    pub redeemed_count: i64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ReconciliationSummaryResult {
     pub payment_method: String,
@@ -766,35 +830,39 @@ impl Report {
             .get_results(conn)
             .to_db_error(ErrorCode::QueryError, "Could not fetch report results")?;
 
-        let mut payment_totals: HashMap<ExternalPaymentType, BoxOfficeSalesSummaryPaymentRow> = HashMap::new();
+        let mut payment_totals: HashMap<(ExternalPaymentType, Currencies), BoxOfficeSalesSummaryPaymentRow> =
+            HashMap::new();
         let mut operator_data: Vec<BoxOfficeSalesSummaryOperatorRow> = Vec::new();
         let mut operator_payments: HashMap<Uuid, Vec<BoxOfficeSalesSummaryPaymentRow>> = HashMap::new();
         for (operator_id, group) in &payment_box_office_summary_rows
             .into_iter()
             .group_by(|row| row.operator_id)
         {
-            let mut payments: HashMap<ExternalPaymentType, BoxOfficeSalesSummaryPaymentRow> = HashMap::new();
+            let mut payments: HashMap<(ExternalPaymentType, Currencies), BoxOfficeSalesSummaryPaymentRow> =
+                HashMap::new();
             for group_item in group {
                 if let Some(external_payment_type) = group_item.external_payment_type {
                     payment_totals
-                        .entry(external_payment_type)
+                        .entry((external_payment_type, group_item.currency))
                         .and_modify(|e| {
                             e.quantity += group_item.number_of_tickets as u32;
                             e.total_sales_in_cents += group_item.total_sales_in_cents as u32;
                         })
                         .or_insert_with(|| BoxOfficeSalesSummaryPaymentRow {
                             payment_type: external_payment_type,
+                            currency: group_item.currency,
                             quantity: group_item.number_of_tickets as u32,
                             total_sales_in_cents: group_item.total_sales_in_cents as u32,
                         });
                     payments
-                        .entry(external_payment_type)
+                        .entry((external_payment_type, group_item.currency))
                         .and_modify(|e| {
                             e.quantity += group_item.number_of_tickets as u32;
                             e.total_sales_in_cents += group_item.total_sales_in_cents as u32;
                         })
                         .or_insert_with(|| BoxOfficeSalesSummaryPaymentRow {
                             payment_type: external_payment_type,
+                            currency: group_item.currency,
                             quantity: group_item.number_of_tickets as u32,
                             total_sales_in_cents: group_item.total_sales_in_cents as u32,
                         });
@@ -805,7 +873,7 @@ impl Report {
                 .values()
                 .map(|v| (*v).clone())
                 .collect::<Vec<BoxOfficeSalesSummaryPaymentRow>>();
-            payments.sort_by_key(|p| p.payment_type.to_string());
+            payments.sort_by_key(|p| (p.payment_type.to_string(), p.currency.to_string()));
             operator_payments.insert(operator_id, payments);
         }
 
@@ -820,6 +888,7 @@ impl Report {
                 events.push(BoxOfficeSalesSummaryOperatorEventRow {
                     event_name: group_item.event_name.clone(),
                     event_date: group_item.event_date,
+                    currency: group_item.currency,
                     number_of_tickets: group_item.number_of_tickets as u32,
                     face_value_in_cents: group_item.face_value_in_cents as u32,
                     revenue_share_value_in_cents: group_item.revenue_share_value_in_cents as u32,
@@ -839,11 +908,17 @@ impl Report {
             .values()
             .map(|v| (*v).clone())
             .collect::<Vec<BoxOfficeSalesSummaryPaymentRow>>();
-        payment_totals.sort_by_key(|p| p.payment_type.to_string());
+        payment_totals.sort_by_key(|p| (p.payment_type.to_string(), p.currency.to_string()));
+
+        let shifts = CashDrawerSession::find_for_organization_in_period(organization_id, start, end, conn)?
+            .iter()
+            .map(|session| session.for_display(conn))
+            .collect::<Result<Vec<DisplayCashDrawerSession>, DatabaseError>>()?;
 
         Ok(BoxOfficeSalesSummaryReport {
             operators: operator_data,
             payments: payment_totals,
+            shifts,
         })
     }
 
@@ -1083,6 +1158,35 @@ impl Report {
         Ok(Payload::new(scan_count_rows, paging))
     }
 
+    /// Tax collected and refunded for the organization's tax rules, grouped by jurisdiction and period
+    pub fn tax_liability_report(
+        organization_id: Uuid,
+        start: Option<NaiveDateTime>,
+        end: Option<NaiveDateTime>,
+        period: ReportPeriods,
+        conn: &PgConnection,
+    ) -> Result<Vec<TaxLiabilityReportRow>, DatabaseError> {
+        let query = include_str!("../queries/reports/reports_tax_liability.sql");
+        diesel::sql_query(query)
+            .bind::<dUuid, _>(organization_id)
+            .bind::<Nullable<Timestamp>, _>(start)
+            .bind::<Nullable<Timestamp>, _>(end)
+            .bind::<Text, _>(period.to_string().to_lowercase())
+            .get_results(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not fetch report results")
+    }
+
+    pub fn event_series_report(
+        event_series_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<EventSeriesReportRow>, DatabaseError> {
+        let query = include_str!("../queries/reports/reports_event_series.sql");
+        diesel::sql_query(query)
+            .bind::<dUuid, _>(event_series_id)
+            .get_results(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not fetch report results")
+    }
+
     pub fn promo_code_report(
         event_id: Option<Uuid>,
         organization_id: Option<Uuid>,
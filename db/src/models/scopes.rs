@@ -35,6 +35,7 @@ pub enum Scopes {
     EventScan,
     EventViewGuests,
     EventWrite,
+    FanSegmentWrite,
     HoldRead,
     HoldWrite,
     ListingWrite,
@@ -132,6 +133,7 @@ impl fmt::Display for Scopes {
             Scopes::EventReports => "event:reports",
             Scopes::EventScan => "event:scan",
             Scopes::EventViewGuests => "event:view-guests",
+            Scopes::FanSegmentWrite => "fan-segment:write",
             Scopes::HoldRead => "hold:read",
             Scopes::HoldWrite => "hold:write",
             Scopes::ListingWrite => "listing:write",
@@ -225,6 +227,7 @@ impl FromStr for Scopes {
             "event:reports" => Scopes::EventReports,
             "event:scan" => Scopes::EventScan,
             "event:view-guests" => Scopes::EventViewGuests,
+            "fan-segment:write" => Scopes::FanSegmentWrite,
             "hold:read" => Scopes::HoldRead,
             "hold:write" => Scopes::HoldWrite,
             "listing:write" => Scopes::ListingWrite,
@@ -415,6 +418,7 @@ fn get_scopes_for_role(role: Roles) -> Vec<Scopes> {
                 Scopes::EventScan,
                 Scopes::EventViewGuests,
                 Scopes::EventWrite,
+                Scopes::FanSegmentWrite,
                 Scopes::HoldRead,
                 Scopes::HoldWrite,
                 Scopes::LootBoxWrite,
@@ -517,6 +521,7 @@ fn get_scopes_for_role_test() {
             Scopes::CompWrite,
             Scopes::DashboardRead,
             Scopes::EventBroadcast,
+            Scopes::FanSegmentWrite,
             Scopes::EventCancel,
             Scopes::EventClone,
             Scopes::EventDataRead,
@@ -599,6 +604,7 @@ fn get_scopes_test() {
             "comp:write",
             "dashboard:read",
             "event:broadcast",
+            "fan-segment:write",
             "event:cancel",
             "event:clone",
             "event:data-read",
@@ -675,6 +681,7 @@ fn get_scopes_test() {
             "comp:write",
             "dashboard:read",
             "event:broadcast",
+            "fan-segment:write",
             "event:cancel",
             "event:clone",
             "event:data-read",
@@ -767,6 +774,7 @@ fn get_scopes_test() {
             "comp:write",
             "dashboard:read",
             "event:broadcast",
+            "fan-segment:write",
             "event:cancel",
             "event:clone",
             "event:data-read",
@@ -858,6 +866,7 @@ fn get_scopes_test() {
             "comp:write",
             "dashboard:read",
             "event:broadcast",
+            "fan-segment:write",
             "event:cancel",
             "event:clone",
             "event:data-read",
@@ -952,6 +961,7 @@ fn get_scopes_test() {
             "comp:write",
             "dashboard:read",
             "event:broadcast",
+            "fan-segment:write",
             "event:cancel",
             "event:clone",
             "event:data-read",
@@ -42,7 +42,18 @@ impl SettlementAdjustment {
             .to_db_error(ErrorCode::QueryError, "Could not load Settlement Adjustment")
     }
 
+    /// Credits add to the amount owed to the organization while deductions and chargebacks reduce it
+    pub fn signed_amount_in_cents(&self) -> i64 {
+        match self.settlement_adjustment_type {
+            SettlementAdjustmentTypes::ManualCredit => self.amount_in_cents.abs(),
+            SettlementAdjustmentTypes::ManualDeduction | SettlementAdjustmentTypes::Chargeback => {
+                -self.amount_in_cents.abs()
+            }
+        }
+    }
+
     pub fn destroy(self, conn: &PgConnection) -> Result<usize, DatabaseError> {
+        LedgerJournalEntry::reverse_for_source(Tables::SettlementAdjustments, self.id, None, conn)?;
         diesel::delete(settlement_adjustments::table.filter(settlement_adjustments::id.eq(self.id)))
             .execute(conn)
             .to_db_error(ErrorCode::DeleteError, "Error removing settlement adjustment")
@@ -59,12 +70,14 @@ pub struct NewSettlementAdjustment {
 }
 impl NewSettlementAdjustment {
     pub fn commit(&self, conn: &PgConnection) -> Result<SettlementAdjustment, DatabaseError> {
-        DatabaseError::wrap(
+        let adjustment = DatabaseError::wrap(
             ErrorCode::InsertError,
             "Could not create new settlement adjustment",
             diesel::insert_into(settlement_adjustments::table)
                 .values(self)
                 .get_result::<SettlementAdjustment>(conn),
-        )
+        )?;
+        LedgerJournalEntry::record_settlement_adjustment(&adjustment, None, conn)?;
+        Ok(adjustment)
     }
 }
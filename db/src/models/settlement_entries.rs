@@ -5,7 +5,7 @@ use diesel::prelude::*;
 use diesel::sql_types::{Nullable, Text};
 use itertools::Itertools;
 use models::*;
-use schema::{events, settlement_entries, ticket_types};
+use schema::{affiliate_links, events, partners, settlement_entries, ticket_types};
 use utils::errors::ConvertToDatabaseError;
 use utils::errors::DatabaseError;
 use utils::errors::ErrorCode;
@@ -26,6 +26,10 @@ pub struct SettlementEntry {
     pub settlement_entry_type: SettlementEntryTypes,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub product_variant_id: Option<Uuid>,
+    pub currency: Currencies,
+    pub partner_id: Option<Uuid>,
+    pub affiliate_link_id: Option<Uuid>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Queryable, Serialize)]
@@ -35,12 +39,19 @@ pub struct DisplaySettlementEntry {
     pub event_id: Uuid,
     pub ticket_type_id: Option<Uuid>,
     pub ticket_type_name: Option<String>,
+    pub product_variant_id: Option<Uuid>,
+    pub product_name: Option<String>,
+    pub partner_id: Option<Uuid>,
+    pub partner_name: Option<String>,
+    pub affiliate_link_id: Option<Uuid>,
+    pub affiliate_link_name: Option<String>,
     pub face_value_in_cents: i64,
     pub revenue_share_value_in_cents: i64,
     pub online_sold_quantity: i64,
     pub fee_sold_quantity: i64,
     pub total_sales_in_cents: i64,
     pub settlement_entry_type: SettlementEntryTypes,
+    pub currency: Currencies,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -58,6 +69,10 @@ impl SettlementEntry {
     ) -> Result<Vec<EventGroupedSettlementEntry>, DatabaseError> {
         let entries: Vec<DisplaySettlementEntry> = settlement_entries::table
             .left_join(ticket_types::table.on(settlement_entries::ticket_type_id.eq(ticket_types::id.nullable())))
+            .left_join(partners::table.on(settlement_entries::partner_id.eq(partners::id.nullable())))
+            .left_join(
+                affiliate_links::table.on(settlement_entries::affiliate_link_id.eq(affiliate_links::id.nullable())),
+            )
             .inner_join(events::table.on(events::id.eq(settlement_entries::event_id)))
             .filter(settlement_entries::settlement_id.eq(settlement.id))
             .select((
@@ -66,12 +81,21 @@ impl SettlementEntry {
                 settlement_entries::event_id,
                 settlement_entries::ticket_type_id,
                 sql::<Nullable<Text>>("ticket_types.name AS ticket_type_name"),
+                settlement_entries::product_variant_id,
+                sql::<Nullable<Text>>(
+                    "(SELECT p.name || ' - ' || pv.name FROM product_variants pv JOIN products p ON p.id = pv.product_id WHERE pv.id = settlement_entries.product_variant_id) AS product_name",
+                ),
+                settlement_entries::partner_id,
+                partners::name.nullable(),
+                settlement_entries::affiliate_link_id,
+                affiliate_links::name.nullable(),
                 settlement_entries::face_value_in_cents,
                 settlement_entries::revenue_share_value_in_cents,
                 settlement_entries::online_sold_quantity,
                 settlement_entries::fee_sold_quantity,
                 settlement_entries::total_sales_in_cents,
                 settlement_entries::settlement_entry_type,
+                settlement_entries::currency,
                 settlement_entries::created_at,
                 settlement_entries::updated_at,
             ))
@@ -79,6 +103,7 @@ impl SettlementEntry {
             .then_order_by(settlement_entries::event_id)
             .then_order_by(settlement_entries::settlement_entry_type.nullable().desc())
             .then_order_by(ticket_types::rank)
+            .then_order_by(settlement_entries::product_variant_id)
             .then_order_by(settlement_entries::face_value_in_cents)
             .load(conn)
             .to_db_error(ErrorCode::QueryError, "Could not load Settlement Entries")?;
@@ -108,6 +133,7 @@ impl SettlementEntry {
         online_sold_quantity: i64,
         fee_sold_quantity: i64,
         total_sales_in_cents: i64,
+        currency: Currencies,
     ) -> NewSettlementEntry {
         NewSettlementEntry {
             settlement_id,
@@ -119,6 +145,10 @@ impl SettlementEntry {
             settlement_entry_type,
             fee_sold_quantity,
             total_sales_in_cents,
+            product_variant_id: None,
+            currency,
+            partner_id: None,
+            affiliate_link_id: None,
         }
     }
 }
@@ -135,6 +165,10 @@ pub struct NewSettlementEntry {
     pub fee_sold_quantity: i64,
     pub total_sales_in_cents: i64,
     pub settlement_entry_type: SettlementEntryTypes,
+    pub product_variant_id: Option<Uuid>,
+    pub currency: Currencies,
+    pub partner_id: Option<Uuid>,
+    pub affiliate_link_id: Option<Uuid>,
 }
 impl NewSettlementEntry {
     pub fn commit(&self, conn: &PgConnection) -> Result<SettlementEntry, DatabaseError> {
@@ -0,0 +1,119 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::{settlement_payout_batches, settlement_payouts};
+use utils::errors::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "settlement_payout_batches"]
+pub struct SettlementPayoutBatch {
+    pub id: Uuid,
+    pub payout_method: PayoutMethods,
+    pub created_by_user_id: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name = "settlement_payout_batches"]
+pub struct NewSettlementPayoutBatch {
+    pub payout_method: PayoutMethods,
+    pub created_by_user_id: Option<Uuid>,
+}
+
+impl SettlementPayoutBatch {
+    /// Creates a batch from the initiated payouts of the payout method that have not been sent to
+    /// the bank in an earlier batch. The payouts are locked while they are claimed so concurrent
+    /// exports cannot include the same payout twice.
+    pub fn create_for_unbatched_payouts(
+        payout_method: PayoutMethods,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<SettlementPayoutBatch, DatabaseError> {
+        let payout_ids: Vec<Uuid> = settlement_payouts::table
+            .filter(settlement_payouts::payout_method.eq(payout_method))
+            .filter(settlement_payouts::status.eq(SettlementPayoutStatus::Initiated))
+            .filter(settlement_payouts::settlement_payout_batch_id.is_null())
+            .select(settlement_payouts::id)
+            .for_update()
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load settlement payouts")?;
+        if payout_ids.is_empty() {
+            return DatabaseError::business_process_error("No payouts to include in the batch file");
+        }
+
+        let batch: SettlementPayoutBatch = diesel::insert_into(settlement_payout_batches::table)
+            .values(&NewSettlementPayoutBatch {
+                payout_method,
+                created_by_user_id: current_user_id,
+            })
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create settlement payout batch")?;
+
+        diesel::update(settlement_payouts::table.filter(settlement_payouts::id.eq_any(payout_ids)))
+            .set((
+                settlement_payouts::settlement_payout_batch_id.eq(batch.id),
+                settlement_payouts::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not add settlement payouts to batch")?;
+
+        DomainEvent::create(
+            DomainEventTypes::SettlementPayoutBatchCreated,
+            "Settlement payout batch created".to_string(),
+            Tables::SettlementPayoutBatches,
+            Some(batch.id),
+            current_user_id,
+            Some(json!(&batch)),
+        )
+        .commit(conn)?;
+
+        Ok(batch)
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<SettlementPayoutBatch, DatabaseError> {
+        settlement_payout_batches::table
+            .filter(settlement_payout_batches::id.eq(id))
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load settlement payout batch")
+    }
+
+    pub fn find_all(
+        page: u32,
+        limit: u32,
+        conn: &PgConnection,
+    ) -> Result<Payload<SettlementPayoutBatch>, DatabaseError> {
+        use utils::pagination::Paginate;
+
+        let (batches, total) = settlement_payout_batches::table
+            .order_by(settlement_payout_batches::created_at.desc())
+            .paginate(page as i64)
+            .per_page(limit as i64)
+            .load_and_count_pages(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load settlement payout batches")?;
+
+        Ok(Payload::from_data(batches, page, limit, Some(total as u64)))
+    }
+
+    pub fn payouts(&self, conn: &PgConnection) -> Result<Vec<SettlementPayout>, DatabaseError> {
+        settlement_payouts::table
+            .filter(settlement_payouts::settlement_payout_batch_id.eq(self.id))
+            .order_by(settlement_payouts::created_at.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load settlement payouts for batch")
+    }
+
+    /// Bank transfer file for the batch, regenerating it for an existing batch produces the same
+    /// file so it can be downloaded again without paying the payouts twice.
+    pub fn file(
+        &self,
+        originator: &PayoutOriginator,
+        encryption_key: &str,
+        conn: &PgConnection,
+    ) -> Result<String, DatabaseError> {
+        SettlementPayout::batch_file(self, &self.payouts(conn)?, originator, encryption_key, conn)
+    }
+}
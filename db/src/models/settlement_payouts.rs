@@ -0,0 +1,420 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::settlement_payouts;
+use std::borrow::Cow;
+use utils::errors::*;
+use uuid::Uuid;
+use validators;
+
+const NACHA_RECORD_LENGTH: usize = 94;
+const NACHA_BLOCKING_FACTOR: usize = 10;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "settlement_payouts"]
+pub struct SettlementPayout {
+    pub id: Uuid,
+    pub settlement_id: Uuid,
+    pub organization_bank_account_id: Uuid,
+    pub payout_method: PayoutMethods,
+    pub amount_in_cents: i64,
+    pub currency: Currencies,
+    pub status: SettlementPayoutStatus,
+    pub note: Option<String>,
+    pub settlement_payout_batch_id: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name = "settlement_payouts"]
+pub struct NewSettlementPayout {
+    pub settlement_id: Uuid,
+    pub organization_bank_account_id: Uuid,
+    pub payout_method: PayoutMethods,
+    pub amount_in_cents: i64,
+    pub currency: Currencies,
+}
+
+/// Account the payouts are sent from, written into the headers of the generated batch files
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PayoutOriginator {
+    pub name: String,
+    pub routing_number: Option<String>,
+    pub company_id: Option<String>,
+    pub iban: Option<String>,
+    pub bic: Option<String>,
+}
+
+impl SettlementPayout {
+    pub fn create(
+        settlement_id: Uuid,
+        organization_bank_account_id: Uuid,
+        payout_method: PayoutMethods,
+        amount_in_cents: i64,
+        currency: Currencies,
+    ) -> NewSettlementPayout {
+        NewSettlementPayout {
+            settlement_id,
+            organization_bank_account_id,
+            payout_method,
+            amount_in_cents,
+            currency,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<SettlementPayout, DatabaseError> {
+        settlement_payouts::table
+            .filter(settlement_payouts::id.eq(id))
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load settlement payout")
+    }
+
+    pub fn find_for_settlement(
+        settlement_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<SettlementPayout>, DatabaseError> {
+        settlement_payouts::table
+            .filter(settlement_payouts::settlement_id.eq(settlement_id))
+            .order_by(settlement_payouts::created_at.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load settlement payouts")
+    }
+
+    pub fn settlement(&self, conn: &PgConnection) -> Result<Settlement, DatabaseError> {
+        Settlement::find(self.settlement_id, conn)
+    }
+
+    /// Payouts start out initiated when their settlement is finalized and are sent to the bank as
+    /// part of a payout batch, they are later confirmed as paid or returned by the receiving bank
+    /// (paid payouts can still be returned).
+    pub fn update_status(
+        &self,
+        status: SettlementPayoutStatus,
+        note: Option<String>,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<SettlementPayout, DatabaseError> {
+        self.validate_status_transition(status)?;
+
+        let payout: SettlementPayout = diesel::update(self)
+            .set((
+                settlement_payouts::status.eq(status),
+                settlement_payouts::note.eq(note.or_else(|| self.note.clone())),
+                settlement_payouts::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update settlement payout")?;
+
+        match status {
+            SettlementPayoutStatus::Paid => {
+                LedgerJournalEntry::record_settlement_payout(&payout, current_user_id, conn)?;
+            }
+            SettlementPayoutStatus::Returned => {
+                LedgerJournalEntry::reverse_for_source(Tables::SettlementPayouts, payout.id, current_user_id, conn)?;
+            }
+            SettlementPayoutStatus::Initiated => (),
+        }
+
+        let event_type = match status {
+            SettlementPayoutStatus::Paid => DomainEventTypes::SettlementPayoutPaid,
+            SettlementPayoutStatus::Returned => DomainEventTypes::SettlementPayoutReturned,
+            SettlementPayoutStatus::Initiated => DomainEventTypes::SettlementPayoutInitiated,
+        };
+        DomainEvent::create(
+            event_type,
+            format!("Settlement payout {}", status.to_string().to_lowercase()),
+            Tables::SettlementPayouts,
+            Some(payout.id),
+            current_user_id,
+            Some(json!(&payout)),
+        )
+        .commit(conn)?;
+
+        Ok(payout)
+    }
+
+    fn validate_status_transition(&self, status: SettlementPayoutStatus) -> Result<(), DatabaseError> {
+        let valid_transition = match (self.status, status) {
+            (SettlementPayoutStatus::Initiated, SettlementPayoutStatus::Paid) => true,
+            (SettlementPayoutStatus::Initiated, SettlementPayoutStatus::Returned) => true,
+            (SettlementPayoutStatus::Paid, SettlementPayoutStatus::Returned) => true,
+            _ => false,
+        };
+
+        let mut validation_errors = Ok(());
+        if !valid_transition {
+            let mut validation_error = validators::create_validation_error(
+                "invalid_status_transition",
+                "Payout status cannot be changed to the requested status",
+            );
+            validation_error.add_param(Cow::from("status"), &self.status);
+            validation_error.add_param(Cow::from("new_status"), &status);
+            validation_errors = validators::append_validation_error(validation_errors, "status", Err(validation_error));
+        }
+        Ok(validation_errors?)
+    }
+
+    /// Generates the bank transfer file for the payouts of a batch, a NACHA ACH file for US bank
+    /// accounts or a SEPA pain.001 credit transfer XML document for European accounts.
+    pub fn batch_file(
+        batch: &SettlementPayoutBatch,
+        payouts: &[SettlementPayout],
+        originator: &PayoutOriginator,
+        encryption_key: &str,
+        conn: &PgConnection,
+    ) -> Result<String, DatabaseError> {
+        if payouts.is_empty() {
+            return DatabaseError::business_process_error("No payouts to include in the batch file");
+        }
+        if payouts.iter().any(|p| p.payout_method != batch.payout_method) {
+            return DatabaseError::business_process_error(
+                "Batch files can only include payouts of a single payout method",
+            );
+        }
+
+        let mut entries: Vec<(SettlementPayout, OrganizationBankAccount)> = Vec::new();
+        for payout in payouts {
+            let mut bank_account = OrganizationBankAccount::find(payout.organization_bank_account_id, conn)?;
+            bank_account.decrypt(encryption_key)?;
+            entries.push((payout.clone(), bank_account));
+        }
+
+        match batch.payout_method {
+            PayoutMethods::Nacha => nacha_file(&entries, originator, batch.created_at),
+            PayoutMethods::Sepa => sepa_file(&entries, originator, batch.id, batch.created_at),
+        }
+    }
+}
+
+impl NewSettlementPayout {
+    pub fn commit(self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<SettlementPayout, DatabaseError> {
+        let payout: SettlementPayout = diesel::insert_into(settlement_payouts::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create settlement payout")?;
+
+        DomainEvent::create(
+            DomainEventTypes::SettlementPayoutInitiated,
+            "Settlement payout initiated".to_string(),
+            Tables::SettlementPayouts,
+            Some(payout.id),
+            current_user_id,
+            Some(json!(&payout)),
+        )
+        .commit(conn)?;
+
+        Ok(payout)
+    }
+}
+
+fn nacha_file(
+    entries: &[(SettlementPayout, OrganizationBankAccount)],
+    originator: &PayoutOriginator,
+    now: NaiveDateTime,
+) -> Result<String, DatabaseError> {
+    let (originator_routing_number, company_id) = match (&originator.routing_number, &originator.company_id) {
+        (Some(routing_number), Some(company_id)) if routing_number.len() == 9 => (routing_number, company_id),
+        _ => {
+            return DatabaseError::business_process_error(
+                "Payout originator routing number and company id are required for NACHA files",
+            );
+        }
+    };
+    let odfi = &originator_routing_number[..8];
+    let batch_number = 1;
+
+    let mut records: Vec<String> = Vec::new();
+    records.push(format!(
+        "101 {}{}{}{}A094101{}{}{}",
+        originator_routing_number,
+        nacha_alpha(company_id, 10),
+        now.format("%y%m%d"),
+        now.format("%H%M"),
+        nacha_alpha("", 23),
+        nacha_alpha(&originator.name, 23),
+        nacha_alpha("", 8),
+    ));
+    records.push(format!(
+        "5220{}{}{}CCD{}{}{}   1{}{}",
+        nacha_alpha(&originator.name, 16),
+        nacha_alpha("", 20),
+        nacha_alpha(company_id, 10),
+        nacha_alpha("SETTLEMENT", 10),
+        now.format("%y%m%d"),
+        now.format("%y%m%d"),
+        odfi,
+        nacha_numeric(batch_number, 7),
+    ));
+
+    let mut entry_hash: i64 = 0;
+    let mut total_credit: i64 = 0;
+    for (index, (payout, bank_account)) in entries.iter().enumerate() {
+        let (routing_number, account_number) = match (&bank_account.routing_number, &bank_account.account_number) {
+            (Some(routing_number), Some(account_number)) => (routing_number, account_number),
+            _ => {
+                return DatabaseError::business_process_error("Bank account is missing routing or account number");
+            }
+        };
+        entry_hash += routing_number[..8].parse::<i64>().unwrap_or(0);
+        total_credit += payout.amount_in_cents;
+        records.push(format!(
+            "622{}{}{}{}{}  0{}{}",
+            routing_number,
+            nacha_alpha(account_number, 17),
+            nacha_numeric(payout.amount_in_cents, 10),
+            nacha_alpha(&payout.id.simple().to_string(), 15),
+            nacha_alpha(&bank_account.account_holder_name, 22),
+            odfi,
+            nacha_numeric(index as i64 + 1, 7),
+        ));
+    }
+
+    let entry_hash = entry_hash % 10_000_000_000;
+    records.push(format!(
+        "8220{}{}{}{}{}{}{}{}",
+        nacha_numeric(entries.len() as i64, 6),
+        nacha_numeric(entry_hash, 10),
+        nacha_numeric(0, 12),
+        nacha_numeric(total_credit, 12),
+        nacha_alpha(company_id, 10),
+        nacha_alpha("", 25),
+        odfi,
+        nacha_numeric(batch_number, 7),
+    ));
+
+    // Block count includes the file control record and is rounded up to the blocking factor
+    let block_count = (records.len() + 1 + NACHA_BLOCKING_FACTOR - 1) / NACHA_BLOCKING_FACTOR;
+    records.push(format!(
+        "9{}{}{}{}{}{}{}",
+        nacha_numeric(1, 6),
+        nacha_numeric(block_count as i64, 6),
+        nacha_numeric(entries.len() as i64, 8),
+        nacha_numeric(entry_hash, 10),
+        nacha_numeric(0, 12),
+        nacha_numeric(total_credit, 12),
+        nacha_alpha("", 39),
+    ));
+    while records.len() % NACHA_BLOCKING_FACTOR != 0 {
+        records.push("9".repeat(NACHA_RECORD_LENGTH));
+    }
+
+    let mut file = records.join("\n");
+    file.push('\n');
+    Ok(file)
+}
+
+fn nacha_alpha(value: &str, length: usize) -> String {
+    let value: String = value
+        .chars()
+        .filter(|c| c.is_ascii() && !c.is_ascii_control())
+        .take(length)
+        .collect::<String>()
+        .to_uppercase();
+    format!("{:<width$}", value, width = length)
+}
+
+fn nacha_numeric(value: i64, length: usize) -> String {
+    format!("{:0>width$}", value, width = length)
+}
+
+fn sepa_file(
+    entries: &[(SettlementPayout, OrganizationBankAccount)],
+    originator: &PayoutOriginator,
+    batch_id: Uuid,
+    now: NaiveDateTime,
+) -> Result<String, DatabaseError> {
+    let (originator_iban, originator_bic) = match (&originator.iban, &originator.bic) {
+        (Some(iban), Some(bic)) => (iban, bic),
+        _ => {
+            return DatabaseError::business_process_error("Payout originator IBAN and BIC are required for SEPA files");
+        }
+    };
+
+    let message_id = batch_id.simple().to_string();
+    let control_sum = sepa_amount(entries.iter().map(|(p, _)| p.amount_in_cents).sum());
+
+    let mut transactions = String::new();
+    for (payout, bank_account) in entries {
+        let (iban, bic) = match (&bank_account.iban, &bank_account.bic) {
+            (Some(iban), Some(bic)) => (iban, bic),
+            _ => {
+                return DatabaseError::business_process_error("Bank account is missing IBAN or BIC");
+            }
+        };
+        transactions.push_str(&format!(
+            r#"
+      <CdtTrfTxInf>
+        <PmtId><EndToEndId>{}</EndToEndId></PmtId>
+        <Amt><InstdAmt Ccy="{}">{}</InstdAmt></Amt>
+        <CdtrAgt><FinInstnId><BIC>{}</BIC></FinInstnId></CdtrAgt>
+        <Cdtr><Nm>{}</Nm></Cdtr>
+        <CdtrAcct><Id><IBAN>{}</IBAN></Id></CdtrAcct>
+        <RmtInf><Ustrd>Settlement {}</Ustrd></RmtInf>
+      </CdtTrfTxInf>"#,
+            payout.id.simple(),
+            payout.currency,
+            sepa_amount(payout.amount_in_cents),
+            xml_escape(bic),
+            xml_escape(&sepa_text(&bank_account.account_holder_name, 70)),
+            xml_escape(iban),
+            payout.settlement_id,
+        ));
+    }
+
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:pain.001.001.03" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+  <CstmrCdtTrfInitn>
+    <GrpHdr>
+      <MsgId>{message_id}</MsgId>
+      <CreDtTm>{created_at}</CreDtTm>
+      <NbOfTxs>{count}</NbOfTxs>
+      <CtrlSum>{control_sum}</CtrlSum>
+      <InitgPty><Nm>{name}</Nm></InitgPty>
+    </GrpHdr>
+    <PmtInf>
+      <PmtInfId>{message_id}</PmtInfId>
+      <PmtMtd>TRF</PmtMtd>
+      <NbOfTxs>{count}</NbOfTxs>
+      <CtrlSum>{control_sum}</CtrlSum>
+      <PmtTpInf><SvcLvl><Cd>SEPA</Cd></SvcLvl></PmtTpInf>
+      <ReqdExctnDt>{execution_date}</ReqdExctnDt>
+      <Dbtr><Nm>{name}</Nm></Dbtr>
+      <DbtrAcct><Id><IBAN>{iban}</IBAN></Id></DbtrAcct>
+      <DbtrAgt><FinInstnId><BIC>{bic}</BIC></FinInstnId></DbtrAgt>
+      <ChrgBr>SLEV</ChrgBr>{transactions}
+    </PmtInf>
+  </CstmrCdtTrfInitn>
+</Document>
+"#,
+        message_id = message_id,
+        created_at = now.format("%Y-%m-%dT%H:%M:%S"),
+        count = entries.len(),
+        control_sum = control_sum,
+        name = xml_escape(&sepa_text(&originator.name, 70)),
+        execution_date = now.format("%Y-%m-%d"),
+        iban = xml_escape(originator_iban),
+        bic = xml_escape(originator_bic),
+        transactions = transactions,
+    ))
+}
+
+fn sepa_amount(amount_in_cents: i64) -> String {
+    format!("{}.{:02}", amount_in_cents / 100, amount_in_cents % 100)
+}
+
+fn sepa_text(value: &str, length: usize) -> String {
+    value.chars().take(length).collect()
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
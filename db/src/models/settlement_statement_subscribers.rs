@@ -0,0 +1,148 @@
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::prelude::*;
+use models::*;
+use schema::{organization_users, settlement_statement_subscribers, users};
+use utils::errors::*;
+use uuid::Uuid;
+use validator::ValidationErrors;
+use validators;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Queryable, Serialize)]
+#[table_name = "settlement_statement_subscribers"]
+pub struct SettlementStatementSubscriber {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "settlement_statement_subscribers"]
+pub struct NewSettlementStatementSubscriber {
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+}
+
+impl NewSettlementStatementSubscriber {
+    pub fn commit(
+        self,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<SettlementStatementSubscriber, DatabaseError> {
+        self.validate_record(conn)?;
+        let subscriber: SettlementStatementSubscriber = diesel::insert_into(settlement_statement_subscribers::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(
+                ErrorCode::InsertError,
+                "Could not create new settlement statement subscriber",
+            )?;
+
+        DomainEvent::create(
+            DomainEventTypes::SettlementStatementSubscriberCreated,
+            "Settlement statement subscriber created".to_string(),
+            Tables::Organizations,
+            Some(subscriber.organization_id),
+            current_user_id,
+            Some(json!({"user_id": subscriber.user_id, "settlement_statement_subscriber_id": subscriber.id })),
+        )
+        .commit(conn)?;
+
+        Ok(subscriber)
+    }
+
+    fn validate_record(&self, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let mut validation_errors: Result<(), ValidationErrors> = Ok(());
+        if OrganizationUser::find_by_user_id(self.user_id, self.organization_id, conn)
+            .optional()?
+            .is_none()
+        {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "user_id",
+                Err(validators::create_validation_error(
+                    "not_organization_member",
+                    "Only organization members can receive settlement statements",
+                )),
+            );
+        } else if SettlementStatementSubscriber::find_for_organization(self.organization_id, conn)?
+            .iter()
+            .any(|subscriber| subscriber.user_id == self.user_id)
+        {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "user_id",
+                Err(validators::create_validation_error(
+                    "already_subscribed",
+                    "User is already subscribed to settlement statements",
+                )),
+            );
+        }
+        Ok(validation_errors?)
+    }
+}
+
+impl SettlementStatementSubscriber {
+    pub fn create(organization_id: Uuid, user_id: Uuid) -> NewSettlementStatementSubscriber {
+        NewSettlementStatementSubscriber {
+            organization_id,
+            user_id,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<SettlementStatementSubscriber, DatabaseError> {
+        settlement_statement_subscribers::table
+            .find(id)
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Error loading settlement statement subscriber")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<SettlementStatementSubscriber>, DatabaseError> {
+        settlement_statement_subscribers::table
+            .filter(settlement_statement_subscribers::organization_id.eq(organization_id))
+            .order_by(settlement_statement_subscribers::created_at)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Error loading settlement statement subscribers")
+    }
+
+    /// Subscribed users that are still members of the organization
+    pub fn find_users_for_organization(organization_id: Uuid, conn: &PgConnection) -> Result<Vec<User>, DatabaseError> {
+        settlement_statement_subscribers::table
+            .inner_join(users::table)
+            .inner_join(
+                organization_users::table.on(organization_users::user_id
+                    .eq(settlement_statement_subscribers::user_id)
+                    .and(organization_users::organization_id.eq(settlement_statement_subscribers::organization_id))),
+            )
+            .filter(settlement_statement_subscribers::organization_id.eq(organization_id))
+            .select(users::all_columns)
+            .order_by(users::email)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Error loading settlement statement subscribers")
+    }
+
+    pub fn destroy(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        diesel::delete(
+            settlement_statement_subscribers::table.filter(settlement_statement_subscribers::id.eq(self.id)),
+        )
+        .execute(conn)
+        .to_db_error(ErrorCode::DeleteError, "Error removing settlement statement subscriber")?;
+
+        DomainEvent::create(
+            DomainEventTypes::SettlementStatementSubscriberDeleted,
+            "Settlement statement subscriber deleted".to_string(),
+            Tables::Organizations,
+            Some(self.organization_id),
+            current_user_id,
+            Some(json!({"user_id": self.user_id, "settlement_statement_subscriber_id": self.id })),
+        )
+        .commit(conn)?;
+
+        Ok(())
+    }
+}
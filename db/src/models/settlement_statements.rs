@@ -0,0 +1,483 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::pg::upsert::excluded;
+use diesel::prelude::*;
+use models::*;
+use schema::settlement_statements;
+use utils::csv::CsvWriter;
+use utils::errors::*;
+use utils::pdf::PdfWriter;
+use uuid::Uuid;
+
+const STATEMENT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M %Z";
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "settlement_statements"]
+pub struct SettlementStatement {
+    pub id: Uuid,
+    pub settlement_id: Uuid,
+    #[serde(skip_serializing)]
+    pub csv_content: String,
+    #[serde(skip_serializing)]
+    pub pdf_content: Vec<u8>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[table_name = "settlement_statements"]
+pub struct NewSettlementStatement {
+    pub settlement_id: Uuid,
+    pub csv_content: String,
+    pub pdf_content: Vec<u8>,
+}
+
+/// Sales totals for a single currency, split by where the amounts came from
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SettlementStatementFeeBreakdown {
+    pub face_value_in_cents: i64,
+    pub ticket_fees_in_cents: i64,
+    pub event_fees_in_cents: i64,
+    pub partner_commissions_in_cents: i64,
+    pub affiliate_commissions_in_cents: i64,
+    pub tax_in_cents: i64,
+    pub total_sales_in_cents: i64,
+}
+
+impl NewSettlementStatement {
+    pub fn commit(self, conn: &PgConnection) -> Result<SettlementStatement, DatabaseError> {
+        diesel::insert_into(settlement_statements::table)
+            .values(&self)
+            .on_conflict(settlement_statements::settlement_id)
+            .do_update()
+            .set((
+                settlement_statements::csv_content.eq(excluded(settlement_statements::csv_content)),
+                settlement_statements::pdf_content.eq(excluded(settlement_statements::pdf_content)),
+                settlement_statements::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not save settlement statement")
+    }
+}
+
+impl SettlementStatement {
+    pub fn find_for_settlement(settlement_id: Uuid, conn: &PgConnection) -> Result<SettlementStatement, DatabaseError> {
+        settlement_statements::table
+            .filter(settlement_statements::settlement_id.eq(settlement_id))
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load settlement statement")
+    }
+
+    /// Renders and stores the statement for the settlement, replacing any previously generated statement
+    pub fn generate(settlement: &Settlement, conn: &PgConnection) -> Result<SettlementStatement, DatabaseError> {
+        let statement = SettlementStatement::render(settlement, conn)?.commit(conn)?;
+
+        DomainEvent::create(
+            DomainEventTypes::SettlementStatementGenerated,
+            "Settlement statement generated".to_string(),
+            Tables::Organizations,
+            Some(settlement.organization_id),
+            None,
+            Some(json!({
+                "settlement_id": settlement.id,
+                "settlement_statement_id": statement.id
+            })),
+        )
+        .commit(conn)?;
+
+        Ok(statement)
+    }
+
+    /// Renders the statement from the settlement's current entries, adjustments and payouts without storing it
+    pub fn render(settlement: &Settlement, conn: &PgConnection) -> Result<NewSettlementStatement, DatabaseError> {
+        let organization = Organization::find(settlement.organization_id, conn)?;
+        let event_entries = SettlementEntry::find_for_settlement_by_event(settlement, conn)?;
+        let adjustments = settlement.adjustments(conn)?;
+        let payouts = settlement.payouts(conn)?;
+        let payout_totals = settlement.payout_totals(conn)?;
+
+        let mut fee_breakdowns: Vec<(Currencies, SettlementStatementFeeBreakdown)> = Vec::new();
+        for entry in event_entries.iter().flat_map(|e| e.entries.iter()) {
+            let index = match fee_breakdowns
+                .iter()
+                .position(|(currency, _)| *currency == entry.currency)
+            {
+                Some(index) => index,
+                None => {
+                    fee_breakdowns.push((entry.currency, SettlementStatementFeeBreakdown::default()));
+                    fee_breakdowns.len() - 1
+                }
+            };
+            let breakdown = &mut fee_breakdowns[index].1;
+            let fees = entry.revenue_share_value_in_cents * entry.fee_sold_quantity;
+            match entry.settlement_entry_type {
+                SettlementEntryTypes::Tax => breakdown.tax_in_cents += entry.total_sales_in_cents,
+                _ => breakdown.face_value_in_cents += entry.face_value_in_cents * entry.online_sold_quantity,
+            }
+            match entry.settlement_entry_type {
+                SettlementEntryTypes::EventFees => breakdown.event_fees_in_cents += fees,
+                SettlementEntryTypes::PartnerCommission => {
+                    breakdown.partner_commissions_in_cents += entry.total_sales_in_cents
+                }
+                SettlementEntryTypes::AffiliateCommission => {
+                    breakdown.affiliate_commissions_in_cents += entry.total_sales_in_cents
+                }
+                _ => breakdown.ticket_fees_in_cents += fees,
+            }
+            breakdown.total_sales_in_cents += entry.total_sales_in_cents;
+        }
+
+        // CSV
+        let mut csv = CsvWriter::new();
+        csv.write_row(&[
+            "Section",
+            "Event",
+            "Item",
+            "Type",
+            "Currency",
+            "Face Value",
+            "Revenue Share",
+            "Online Sold Quantity",
+            "Fee Sold Quantity",
+            "Total",
+        ]);
+        for event_entry in &event_entries {
+            for entry in &event_entry.entries {
+                csv.write_row(&[
+                    "Entry".to_string(),
+                    event_entry.event.name.clone(),
+                    entry_name(entry),
+                    entry.settlement_entry_type.to_string(),
+                    entry.currency.to_string(),
+                    format_cents(entry.face_value_in_cents),
+                    format_cents(entry.revenue_share_value_in_cents),
+                    entry.online_sold_quantity.to_string(),
+                    entry.fee_sold_quantity.to_string(),
+                    format_cents(entry.total_sales_in_cents),
+                ]);
+            }
+        }
+        for (currency, breakdown) in &fee_breakdowns {
+            csv.write_row(&summary_row(
+                "Fee Breakdown",
+                "Face Value",
+                "",
+                *currency,
+                breakdown.face_value_in_cents,
+            ));
+            csv.write_row(&summary_row(
+                "Fee Breakdown",
+                "Ticket Fees",
+                "",
+                *currency,
+                breakdown.ticket_fees_in_cents,
+            ));
+            csv.write_row(&summary_row(
+                "Fee Breakdown",
+                "Event Fees",
+                "",
+                *currency,
+                breakdown.event_fees_in_cents,
+            ));
+            if breakdown.partner_commissions_in_cents != 0 {
+                csv.write_row(&summary_row(
+                    "Fee Breakdown",
+                    "Partner Commissions",
+                    "",
+                    *currency,
+                    breakdown.partner_commissions_in_cents,
+                ));
+            }
+            if breakdown.affiliate_commissions_in_cents != 0 {
+                csv.write_row(&summary_row(
+                    "Fee Breakdown",
+                    "Affiliate Commissions",
+                    "",
+                    *currency,
+                    breakdown.affiliate_commissions_in_cents,
+                ));
+            }
+            if breakdown.tax_in_cents != 0 {
+                csv.write_row(&summary_row(
+                    "Fee Breakdown",
+                    "Tax",
+                    "",
+                    *currency,
+                    breakdown.tax_in_cents,
+                ));
+            }
+        }
+        for adjustment in &adjustments {
+            csv.write_row(&summary_row(
+                "Adjustment",
+                &adjustment.note.clone().unwrap_or("".to_string()),
+                &adjustment.settlement_adjustment_type.to_string(),
+                organization.currency,
+                adjustment.signed_amount_in_cents(),
+            ));
+        }
+        for payout in &payouts {
+            csv.write_row(&summary_row(
+                "Payout",
+                &payout.payout_method.to_string(),
+                &payout.status.to_string(),
+                payout.currency,
+                payout.amount_in_cents,
+            ));
+        }
+        for (currency, amount_in_cents) in &payout_totals {
+            csv.write_row(&summary_row("Total", "", "", *currency, *amount_in_cents));
+        }
+
+        // PDF
+        let timezone = organization.timezone()?;
+        let format_time = |time: &NaiveDateTime| {
+            timezone
+                .from_utc_datetime(time)
+                .format(STATEMENT_DATE_FORMAT)
+                .to_string()
+        };
+        let mut pdf = PdfWriter::new(&format!("{} Settlement Statement", organization.name));
+        pdf.line(&format!(
+            "Period: {} to {}",
+            format_time(&settlement.start_time),
+            format_time(&settlement.end_time)
+        ));
+        pdf.line(&format!("Status: {}", settlement.status));
+        pdf.line(&format!("Settlement: {}", settlement.id));
+        if let Some(ref comment) = settlement.comment {
+            pdf.line(&format!("Comment: {}", comment));
+        }
+
+        for event_entry in &event_entries {
+            pdf.heading(&event_entry.event.name);
+            pdf.row(
+                &[
+                    (0.0, "Item"),
+                    (200.0, "Face Value"),
+                    (270.0, "Rev Share"),
+                    (340.0, "Online"),
+                    (390.0, "Fees"),
+                    (440.0, "Total"),
+                ],
+                9.0,
+                true,
+            );
+            for entry in &event_entry.entries {
+                pdf.row(
+                    &[
+                        (0.0, entry_name(entry)),
+                        (200.0, format_amount(entry.face_value_in_cents, entry.currency)),
+                        (270.0, format_amount(entry.revenue_share_value_in_cents, entry.currency)),
+                        (340.0, entry.online_sold_quantity.to_string()),
+                        (390.0, entry.fee_sold_quantity.to_string()),
+                        (440.0, format_amount(entry.total_sales_in_cents, entry.currency)),
+                    ],
+                    9.0,
+                    false,
+                );
+            }
+        }
+        if event_entries.is_empty() {
+            pdf.heading("Events");
+            pdf.line("No sales were settled in this period");
+        }
+
+        pdf.heading("Fee Breakdown");
+        for (currency, breakdown) in &fee_breakdowns {
+            pdf.row(
+                &[
+                    (0.0, "Face value".to_string()),
+                    (270.0, format_amount(breakdown.face_value_in_cents, *currency)),
+                ],
+                10.0,
+                false,
+            );
+            pdf.row(
+                &[
+                    (0.0, "Ticket fees".to_string()),
+                    (270.0, format_amount(breakdown.ticket_fees_in_cents, *currency)),
+                ],
+                10.0,
+                false,
+            );
+            pdf.row(
+                &[
+                    (0.0, "Event fees".to_string()),
+                    (270.0, format_amount(breakdown.event_fees_in_cents, *currency)),
+                ],
+                10.0,
+                false,
+            );
+            if breakdown.partner_commissions_in_cents != 0 {
+                pdf.row(
+                    &[
+                        (0.0, "Partner commissions".to_string()),
+                        (270.0, format_amount(breakdown.partner_commissions_in_cents, *currency)),
+                    ],
+                    10.0,
+                    false,
+                );
+            }
+            if breakdown.affiliate_commissions_in_cents != 0 {
+                pdf.row(
+                    &[
+                        (0.0, "Affiliate commissions".to_string()),
+                        (
+                            270.0,
+                            format_amount(breakdown.affiliate_commissions_in_cents, *currency),
+                        ),
+                    ],
+                    10.0,
+                    false,
+                );
+            }
+            if breakdown.tax_in_cents != 0 {
+                pdf.row(
+                    &[
+                        (0.0, "Tax".to_string()),
+                        (270.0, format_amount(breakdown.tax_in_cents, *currency)),
+                    ],
+                    10.0,
+                    false,
+                );
+            }
+            pdf.row(
+                &[
+                    (0.0, format!("Total sales ({})", currency)),
+                    (270.0, format_amount(breakdown.total_sales_in_cents, *currency)),
+                ],
+                10.0,
+                true,
+            );
+        }
+
+        pdf.heading("Adjustments");
+        if adjustments.is_empty() {
+            pdf.line("No adjustments");
+        }
+        for adjustment in &adjustments {
+            pdf.row(
+                &[
+                    (0.0, adjustment.settlement_adjustment_type.to_string()),
+                    (130.0, adjustment.note.clone().unwrap_or("".to_string())),
+                    (
+                        440.0,
+                        format_amount(adjustment.signed_amount_in_cents(), organization.currency),
+                    ),
+                ],
+                10.0,
+                false,
+            );
+        }
+
+        if !payouts.is_empty() {
+            pdf.heading("Payouts");
+            for payout in &payouts {
+                pdf.row(
+                    &[
+                        (0.0, payout.payout_method.to_string()),
+                        (130.0, payout.status.to_string()),
+                        (440.0, format_amount(payout.amount_in_cents, payout.currency)),
+                    ],
+                    10.0,
+                    false,
+                );
+            }
+        }
+
+        pdf.heading("Net Amount Owed");
+        for (currency, amount_in_cents) in &payout_totals {
+            pdf.row(
+                &[
+                    (0.0, currency.to_string()),
+                    (440.0, format_amount(*amount_in_cents, *currency)),
+                ],
+                10.0,
+                true,
+            );
+        }
+        pdf.spacer();
+        pdf.line(&format!("Generated {}", format_time(&Utc::now().naive_utc())));
+
+        Ok(NewSettlementStatement {
+            settlement_id: settlement.id,
+            csv_content: csv.into_string(),
+            pdf_content: pdf.into_bytes(),
+        })
+    }
+
+    /// Download file name for the statement, e.g. `settlement-2020-04-01-2020-04-08.pdf`
+    pub fn file_name(settlement: &Settlement, extension: &str) -> String {
+        format!(
+            "settlement-{}-{}.{}",
+            settlement.start_time.format("%Y-%m-%d"),
+            settlement.end_time.format("%Y-%m-%d"),
+            extension
+        )
+    }
+}
+
+fn entry_name(entry: &DisplaySettlementEntry) -> String {
+    match entry.settlement_entry_type {
+        SettlementEntryTypes::EventFees => "Event Fees".to_string(),
+        SettlementEntryTypes::Tax => "Tax".to_string(),
+        SettlementEntryTypes::PartnerCommission => format!(
+            "{} Commission",
+            entry.partner_name.clone().unwrap_or("Partner".to_string())
+        ),
+        SettlementEntryTypes::AffiliateCommission => format!(
+            "{} Commission",
+            entry.affiliate_link_name.clone().unwrap_or("Affiliate".to_string())
+        ),
+        _ => entry
+            .ticket_type_name
+            .clone()
+            .or(entry.product_name.clone())
+            .unwrap_or("".to_string()),
+    }
+}
+
+/// Row for the totals sections of the CSV, only the item, type, currency and total columns are used
+fn summary_row(section: &str, item: &str, row_type: &str, currency: Currencies, amount_in_cents: i64) -> Vec<String> {
+    vec![
+        section.to_string(),
+        "".to_string(),
+        item.to_string(),
+        row_type.to_string(),
+        currency.to_string(),
+        "".to_string(),
+        "".to_string(),
+        "".to_string(),
+        "".to_string(),
+        format_cents(amount_in_cents),
+    ]
+}
+
+/// Formats cents as a decimal amount without a currency symbol
+pub fn format_cents(amount_in_cents: i64) -> String {
+    let sign = if amount_in_cents < 0 { "-" } else { "" };
+    format!(
+        "{}{}.{:02}",
+        sign,
+        amount_in_cents.abs() / 100,
+        amount_in_cents.abs() % 100
+    )
+}
+
+pub fn format_amount(amount_in_cents: i64, currency: Currencies) -> String {
+    let formatted = format_cents(amount_in_cents);
+    if formatted.starts_with('-') {
+        format!("-{}{}", currency.symbol(), &formatted[1..])
+    } else {
+        format!("{}{}", currency.symbol(), formatted)
+    }
+}
+
+#[test]
+fn format_amount_includes_sign_and_symbol() {
+    assert_eq!(format_amount(123456, Currencies::USD), "$1234.56");
+    assert_eq!(format_amount(-505, Currencies::EUR), "-€5.05");
+    assert_eq!(format_amount(0, Currencies::GBP), "£0.00");
+}
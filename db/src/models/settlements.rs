@@ -6,10 +6,11 @@ use diesel::prelude::*;
 use diesel::sql_types::{Nullable, Timestamp, Uuid as dUuid};
 use diesel::{self, dsl};
 use models::*;
-use schema::{settlement_adjustments, settlements};
+use schema::{settlement_adjustments, settlement_entries, settlements};
 use utils::errors::ConvertToDatabaseError;
 use utils::errors::DatabaseError;
 use utils::errors::ErrorCode;
+use utils::errors::Optional;
 use utils::pagination::*;
 use uuid::Uuid;
 use validators;
@@ -47,7 +48,16 @@ pub struct NewSettlement {
 pub struct DisplaySettlement {
     pub settlement: Settlement,
     pub adjustments: Vec<SettlementAdjustment>,
+    pub payouts: Vec<SettlementPayout>,
     pub event_entries: Vec<EventGroupedSettlementEntry>,
+    pub totals: Vec<DisplaySettlementTotal>,
+}
+
+/// Net amount owed to the organization in a single currency
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplaySettlementTotal {
+    pub currency: Currencies,
+    pub total_in_cents: i64,
 }
 
 impl NewSettlement {
@@ -240,7 +250,16 @@ impl Settlement {
         Ok(DisplaySettlement {
             settlement: self.clone(),
             adjustments,
+            payouts: self.payouts(conn)?,
             event_entries: SettlementEntry::find_for_settlement_by_event(self, conn)?,
+            totals: self
+                .payout_totals(conn)?
+                .into_iter()
+                .map(|(currency, total_in_cents)| DisplaySettlementTotal {
+                    currency,
+                    total_in_cents,
+                })
+                .collect(),
         })
     }
 
@@ -251,7 +270,13 @@ impl Settlement {
             .to_db_error(ErrorCode::QueryError, "Could not load settlement adjustments")
     }
 
+    /// Removed settlements and their adjustments are reversed in the ledger, the journal itself
+    /// is never deleted from
     pub fn destroy(self, conn: &PgConnection) -> Result<usize, DatabaseError> {
+        for adjustment in self.adjustments(conn)? {
+            LedgerJournalEntry::reverse_for_source(Tables::SettlementAdjustments, adjustment.id, None, conn)?;
+        }
+        LedgerJournalEntry::reverse_for_source(Tables::Settlements, self.id, None, conn)?;
         diesel::delete(settlements::table.filter(settlements::id.eq(self.id)))
             .execute(conn)
             .to_db_error(ErrorCode::DeleteError, "Error removing settlement")
@@ -313,16 +338,99 @@ impl Settlement {
         Ok(())
     }
 
-    pub fn finalize_settlements(conn: &PgConnection) -> Result<(), DatabaseError> {
-        diesel::update(settlements::table.filter(settlements::status.eq(SettlementStatus::PendingSettlement)))
-            .set((
-                settlements::status.eq(SettlementStatus::FinalizedSettlement),
-                settlements::updated_at.eq(dsl::now),
-            ))
-            .execute(conn)
-            .to_db_error(ErrorCode::UpdateError, "Could not finalize settlements")?;
+    /// Finalizes pending settlements, creating their payouts and statements
+    pub fn finalize_settlements(conn: &PgConnection) -> Result<Vec<Settlement>, DatabaseError> {
+        let finalized_settlements: Vec<Settlement> =
+            diesel::update(settlements::table.filter(settlements::status.eq(SettlementStatus::PendingSettlement)))
+                .set((
+                    settlements::status.eq(SettlementStatus::FinalizedSettlement),
+                    settlements::updated_at.eq(dsl::now),
+                ))
+                .get_results(conn)
+                .to_db_error(ErrorCode::UpdateError, "Could not finalize settlements")?;
+
+        for settlement in &finalized_settlements {
+            LedgerJournalEntry::record_settlement(settlement, conn)?;
+            settlement.create_payouts(None, conn)?;
+            SettlementStatement::generate(settlement, conn)?;
+        }
 
-        Ok(())
+        Ok(finalized_settlements)
+    }
+
+    /// Net amount owed to the organization per currency, entries are recorded in the currency the
+    /// orders were paid in while adjustments are made in the organization's currency.
+    pub fn payout_totals(&self, conn: &PgConnection) -> Result<Vec<(Currencies, i64)>, DatabaseError> {
+        let mut totals: Vec<(Currencies, i64)> = Vec::new();
+        let entries: Vec<SettlementEntry> = settlement_entries::table
+            .filter(settlement_entries::settlement_id.eq(self.id))
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load settlement entries")?;
+        for entry in entries {
+            match totals.iter_mut().find(|(currency, _)| *currency == entry.currency) {
+                Some(total) => total.1 += entry.total_sales_in_cents,
+                None => totals.push((entry.currency, entry.total_sales_in_cents)),
+            }
+        }
+
+        let adjustments_total: i64 = self
+            .adjustments(conn)?
+            .iter()
+            .map(|adjustment| adjustment.signed_amount_in_cents())
+            .sum();
+        if adjustments_total != 0 {
+            let currency = Organization::find(self.organization_id, conn)?.currency;
+            match totals.iter_mut().find(|(c, _)| *c == currency) {
+                Some(total) => total.1 += adjustments_total,
+                None => totals.push((currency, adjustments_total)),
+            }
+        }
+
+        Ok(totals)
+    }
+
+    /// Creates payouts to the organization's bank account for finalized settlements. Only amounts
+    /// in the currency supported by the account's payout method are paid out automatically, any
+    /// other currencies are left to be settled by hand.
+    pub fn create_payouts(
+        &self,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Vec<SettlementPayout>, DatabaseError> {
+        if self.status != SettlementStatus::FinalizedSettlement {
+            return DatabaseError::business_process_error("Payouts can only be created for finalized settlements");
+        }
+        if !self.payouts(conn)?.is_empty() {
+            return DatabaseError::business_process_error("Payouts have already been created for this settlement");
+        }
+        let bank_account =
+            match OrganizationBankAccount::find_for_organization(self.organization_id, conn).optional()? {
+                Some(bank_account) => bank_account,
+                None => return Ok(Vec::new()),
+            };
+
+        let mut payouts = Vec::new();
+        for (currency, amount_in_cents) in self.payout_totals(conn)? {
+            if currency != bank_account.payout_method.currency() || amount_in_cents <= 0 {
+                continue;
+            }
+            payouts.push(
+                SettlementPayout::create(
+                    self.id,
+                    bank_account.id,
+                    bank_account.payout_method,
+                    amount_in_cents,
+                    currency,
+                )
+                .commit(current_user_id, conn)?,
+            );
+        }
+
+        Ok(payouts)
+    }
+
+    pub fn payouts(&self, conn: &PgConnection) -> Result<Vec<SettlementPayout>, DatabaseError> {
+        SettlementPayout::find_for_settlement(self.id, conn)
     }
 
     pub fn find_for_organization(
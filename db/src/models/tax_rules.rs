@@ -0,0 +1,232 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::tax_rules;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
+use validators;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "tax_rules"]
+pub struct TaxRule {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub venue_id: Option<Uuid>,
+    pub name: String,
+    pub jurisdiction: String,
+    pub rate_percent: f32,
+    pub inclusive: bool,
+    pub fees_taxable: bool,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Clone, Insertable, Serialize, Deserialize, PartialEq, Debug, Validate)]
+#[table_name = "tax_rules"]
+pub struct NewTaxRule {
+    #[serde(default)]
+    pub organization_id: Uuid,
+    pub venue_id: Option<Uuid>,
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[validate(length(min = 1, max = 255))]
+    pub jurisdiction: String,
+    pub rate_percent: f32,
+    #[serde(default)]
+    pub inclusive: bool,
+    #[serde(default)]
+    pub fees_taxable: bool,
+}
+
+#[derive(AsChangeset, Default, Deserialize, Debug, Validate)]
+#[table_name = "tax_rules"]
+pub struct TaxRuleEditableAttributes {
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_unless_blank")]
+    #[validate(length(min = 1, max = 255))]
+    pub jurisdiction: Option<String>,
+    pub rate_percent: Option<f32>,
+    pub inclusive: Option<bool>,
+    pub fees_taxable: Option<bool>,
+}
+
+impl TaxRule {
+    pub fn create(
+        organization_id: Uuid,
+        venue_id: Option<Uuid>,
+        name: String,
+        jurisdiction: String,
+        rate_percent: f32,
+    ) -> NewTaxRule {
+        NewTaxRule {
+            organization_id,
+            venue_id,
+            name,
+            jurisdiction,
+            rate_percent,
+            inclusive: false,
+            fees_taxable: false,
+        }
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<TaxRule, DatabaseError> {
+        tax_rules::table
+            .filter(tax_rules::id.eq(id))
+            .filter(tax_rules::deleted_at.is_null())
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load tax rule")
+    }
+
+    pub fn find_for_organization(
+        organization_id: Uuid,
+        page: u32,
+        limit: u32,
+        conn: &PgConnection,
+    ) -> Result<Payload<TaxRule>, DatabaseError> {
+        use utils::pagination::Paginate;
+
+        let (tax_rules, total) = tax_rules::table
+            .filter(tax_rules::organization_id.eq(organization_id))
+            .filter(tax_rules::deleted_at.is_null())
+            .order_by(tax_rules::jurisdiction.asc())
+            .then_order_by(tax_rules::name.asc())
+            .paginate(page as i64)
+            .per_page(limit as i64)
+            .load_and_count_pages(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load tax rules for organization")?;
+
+        Ok(Payload::from_data(tax_rules, page, limit, Some(total as u64)))
+    }
+
+    /// Tax rules charged on sales for the event. Rules set up for the event's venue replace the
+    /// organization wide rules, so organizations only need venue rules where the tax differs.
+    pub fn find_for_event(event: &Event, conn: &PgConnection) -> Result<Vec<TaxRule>, DatabaseError> {
+        let tax_rules: Vec<TaxRule> = tax_rules::table
+            .filter(tax_rules::organization_id.eq(event.organization_id))
+            .filter(tax_rules::venue_id.is_null().or(tax_rules::venue_id.eq(event.venue_id)))
+            .filter(tax_rules::deleted_at.is_null())
+            .order_by(tax_rules::name.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load tax rules for event")?;
+
+        if tax_rules.iter().any(|tr| tr.venue_id.is_some()) {
+            Ok(tax_rules.into_iter().filter(|tr| tr.venue_id.is_some()).collect())
+        } else {
+            Ok(tax_rules)
+        }
+    }
+
+    /// Tax due on a single unit sold for `unit_price_in_cents`. For inclusive rules the tax is the
+    /// portion of the price that is already made up of tax.
+    pub fn tax_for_unit_price(&self, unit_price_in_cents: i64) -> i64 {
+        if unit_price_in_cents <= 0 {
+            return 0;
+        }
+
+        let rate = self.rate_percent as f64 / 100f64;
+        if self.inclusive {
+            unit_price_in_cents - (unit_price_in_cents as f64 / (1f64 + rate)).round() as i64
+        } else {
+            (unit_price_in_cents as f64 * rate).round() as i64
+        }
+    }
+
+    pub fn organization(&self, conn: &PgConnection) -> Result<Organization, DatabaseError> {
+        Organization::find(self.organization_id, conn)
+    }
+
+    pub fn update(
+        &self,
+        attributes: TaxRuleEditableAttributes,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<TaxRule, DatabaseError> {
+        TaxRule::validate_record(
+            attributes.validate(),
+            attributes.rate_percent.unwrap_or(self.rate_percent),
+        )?;
+
+        let tax_rule: TaxRule = diesel::update(self)
+            .set((attributes, tax_rules::updated_at.eq(dsl::now)))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update tax rule")?;
+
+        DomainEvent::create(
+            DomainEventTypes::TaxRuleUpdated,
+            format!("Tax rule '{}' updated", &tax_rule.name),
+            Tables::TaxRules,
+            Some(tax_rule.id),
+            current_user_id,
+            Some(json!(&tax_rule)),
+        )
+        .commit(conn)?;
+
+        Ok(tax_rule)
+    }
+
+    pub fn delete(&self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<(), DatabaseError> {
+        DomainEvent::create(
+            DomainEventTypes::TaxRuleDeleted,
+            format!("Tax rule '{}' deleted", &self.name),
+            Tables::TaxRules,
+            Some(self.id),
+            current_user_id,
+            Some(json!(&self)),
+        )
+        .commit(conn)?;
+
+        diesel::update(self)
+            .set((tax_rules::deleted_at.eq(dsl::now), tax_rules::updated_at.eq(dsl::now)))
+            .execute(conn)
+            .to_db_error(ErrorCode::DeleteError, "Could not delete tax rule")?;
+
+        Ok(())
+    }
+
+    fn validate_record(
+        validation_errors: Result<(), ValidationErrors>,
+        rate_percent: f32,
+    ) -> Result<(), DatabaseError> {
+        let mut validation_errors = validation_errors;
+        if rate_percent < 0f32 || rate_percent > 100f32 {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "rate_percent",
+                Err(validators::create_validation_error(
+                    "invalid_rate",
+                    "Rate must be between 0 and 100 percent",
+                )),
+            );
+        }
+        Ok(validation_errors?)
+    }
+}
+
+impl NewTaxRule {
+    pub fn commit(self, current_user_id: Option<Uuid>, conn: &PgConnection) -> Result<TaxRule, DatabaseError> {
+        TaxRule::validate_record(self.validate(), self.rate_percent)?;
+
+        let tax_rule: TaxRule = diesel::insert_into(tax_rules::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create tax rule")?;
+
+        DomainEvent::create(
+            DomainEventTypes::TaxRuleCreated,
+            format!("Tax rule '{}' created", &tax_rule.name),
+            Tables::TaxRules,
+            Some(tax_rule.id),
+            current_user_id,
+            Some(json!(&tax_rule)),
+        )
+        .commit(conn)?;
+
+        Ok(tax_rule)
+    }
+}
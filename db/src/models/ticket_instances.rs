@@ -18,10 +18,15 @@ use schema::{
 use std::cmp;
 use tari_client::*;
 use utils::errors::*;
+use utils::rand::random_alpha_string;
+use utils::totp;
 use uuid::Uuid;
 use validators::*;
 
 const TICKET_NUMBER_LENGTH: usize = 8;
+const REDEEM_SECRET_LENGTH: usize = 32;
+// Rotating redeem codes from the previous or next period are still accepted
+const REDEEM_CODE_DRIFT_PERIODS: i64 = 1;
 
 #[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable, QueryableByName)]
 #[table_name = "ticket_instances"]
@@ -44,6 +49,11 @@ pub struct TicketInstance {
     pub check_in_source: Option<CheckInSource>,
     parent_id: Option<Uuid>,
     pub listing_id: Option<Uuid>,
+    pub trade_id: Option<Uuid>,
+    #[serde(skip_serializing)]
+    pub redeem_secret: Option<String>,
+    /// Ticket type a pooled ticket is sold or held as, the ticket stays on its own ticket type's asset
+    pub sold_as_ticket_type_id: Option<Uuid>,
 }
 
 #[derive(AsChangeset, Clone, Deserialize, Serialize)]
@@ -124,7 +134,11 @@ impl TicketInstance {
             .to_db_error(ErrorCode::QueryError, "Could not load owner for ticket instance")
     }
 
+    /// Ticket type the ticket was sold or held as, falling back to its asset's ticket type
     pub fn ticket_type(&self, conn: &PgConnection) -> Result<TicketType, DatabaseError> {
+        if let Some(sold_as_ticket_type_id) = self.sold_as_ticket_type_id {
+            return TicketType::find(sold_as_ticket_type_id, conn);
+        }
         ticket_instances::table
             .inner_join(assets::table.on(ticket_instances::asset_id.eq(assets::id)))
             .inner_join(ticket_types::table.on(assets::ticket_type_id.eq(ticket_types::id)))
@@ -200,7 +214,9 @@ impl TicketInstance {
         let ticket_intermediary = ticket_instances::table
             .inner_join(assets::table.on(ticket_instances::asset_id.eq(assets::id)))
             .inner_join(order_items::table.on(ticket_instances::order_item_id.eq(order_items::id.nullable())))
-            .inner_join(ticket_types::table.on(assets::ticket_type_id.eq(ticket_types::id)))
+            .inner_join(ticket_types::table.on(sql(
+                "ticket_types.id = COALESCE(ticket_instances.sold_as_ticket_type_id, assets.ticket_type_id)",
+            )))
             .inner_join(wallets::table.on(ticket_instances::wallet_id.eq(wallets::id)))
             .inner_join(events::table.on(ticket_types::event_id.eq(events::id)))
             .left_join(transfers::table.on(sql("transfers.id = (
@@ -224,13 +240,14 @@ impl TicketInstance {
                     0) as BigInt)
                     ",
                 ),
-                assets::ticket_type_id,
+                ticket_types::id,
                 ticket_types::name,
                 wallets::user_id,
                 events::id,
                 events::venue_id,
                 ticket_instances::status,
                 ticket_instances::redeem_key,
+                ticket_instances::redeem_secret,
                 events::redeem_date,
                 events::event_start,
                 sql::<Bool>("transfers.id is not null AS pending_transfer"),
@@ -275,7 +292,9 @@ impl TicketInstance {
     ) -> Result<ProcessingTicketIntermediary, DatabaseError> {
         let ticket_intermediary = ticket_instances::table
             .inner_join(assets::table.on(ticket_instances::asset_id.eq(assets::id)))
-            .inner_join(ticket_types::table.on(assets::ticket_type_id.eq(ticket_types::id)))
+            .inner_join(ticket_types::table.on(sql(
+                "ticket_types.id = COALESCE(ticket_instances.sold_as_ticket_type_id, assets.ticket_type_id)",
+            )))
             .inner_join(wallets::table.on(ticket_instances::wallet_id.eq(wallets::id)))
             .inner_join(events::table.on(ticket_types::event_id.eq(events::id)))
             .filter(ticket_instances::id.eq(id))
@@ -307,7 +326,9 @@ impl TicketInstance {
         let mut query = ticket_instances::table
             .inner_join(assets::table.on(ticket_instances::asset_id.eq(assets::id)))
             .inner_join(order_items::table.on(ticket_instances::order_item_id.eq(order_items::id.nullable())))
-            .inner_join(ticket_types::table.on(assets::ticket_type_id.eq(ticket_types::id)))
+            .inner_join(ticket_types::table.on(sql(
+                "ticket_types.id = COALESCE(ticket_instances.sold_as_ticket_type_id, assets.ticket_type_id)",
+            )))
             .inner_join(wallets::table.on(ticket_instances::wallet_id.eq(wallets::id)))
             .inner_join(events::table.on(ticket_types::event_id.eq(events::id)))
             .left_join(transfers::table.on(sql("transfers.id = (
@@ -342,13 +363,14 @@ impl TicketInstance {
                     0) as BigInt)
                     ",
                 ),
-                assets::ticket_type_id,
+                ticket_types::id,
                 ticket_types::name,
                 wallets::user_id,
                 events::id,
                 events::venue_id,
                 ticket_instances::status,
                 ticket_instances::redeem_key,
+                ticket_instances::redeem_secret,
                 events::redeem_date,
                 events::event_start,
                 sql::<Bool>("transfers.id is not null AS pending_transfer"),
@@ -460,6 +482,14 @@ impl TicketInstance {
             Some("Expiration date was not set on cart prior to reserving tickets".to_string()),
         ))?;
 
+        // Tickets reserved from a hold already count towards capacity
+        if ticket_holding_id.is_none() {
+            let ticket_type = TicketType::find(ticket_type_id, conn)?;
+            ticket_type
+                .event(conn)?
+                .check_capacity(&ticket_type, quantity, conn)?;
+        }
+
         let query = include_str!("../queries/reserve_tickets.sql");
         let q = diesel::sql_query(query)
             .bind::<sql_types::Uuid, _>(order_item.id)
@@ -591,16 +621,6 @@ impl TicketInstance {
         quantity: i64,
         conn: &PgConnection,
     ) -> Result<Vec<TicketInstance>, DatabaseError> {
-        println!("{:?}", parent_ticket_instance_id.to_string());
-        println!("{:?}", event_id.to_string());
-        println!(
-            "{:?}",
-            [
-                ticket_type_id.unwrap_or(Uuid::nil()).to_string(),
-                min_rarity_id.unwrap_or(Uuid::nil()).to_string(),
-                max_rarity_id.unwrap_or(Uuid::nil()).to_string()
-            ]
-        );
         let query = include_str!("../queries/add_tickets_to_loot_box_instance.sql");
         let q = diesel::sql_query(query)
             .bind::<sql_types::Uuid, _>(parent_ticket_instance_id)
@@ -861,7 +881,13 @@ impl TicketInstance {
 
         let mut query = ticket_instances::table
             .inner_join(assets::table)
-            .filter(assets::ticket_type_id.eq(ticket_type_id))
+            .filter(
+                ticket_instances::sold_as_ticket_type_id.eq(ticket_type_id).or(
+                    ticket_instances::sold_as_ticket_type_id
+                        .is_null()
+                        .and(assets::ticket_type_id.eq(ticket_type_id)),
+                ),
+            )
             .into_boxed();
 
         if include_children {
@@ -1003,6 +1029,15 @@ impl TicketInstance {
                 "order_id" : order_item.order_id, "wallet_id": wallet[0].id(), "order_item_id": order_item.id, "redeem_key": key}
             ))).commit(conn)?;
         }
+
+        // Loot boxes commit to the seed used to draw their contents as soon as they are purchased
+        if let Some(ticket_type_id) = order_item.ticket_type_id {
+            if TicketType::find(ticket_type_id, conn)?.ticket_type_type == TicketTypeType::LootBox {
+                for t in &tickets {
+                    LootBoxDraw::find_or_create_for_ticket_instance(t.id, conn)?;
+                }
+            }
+        }
         Ok(())
     }
 
@@ -1015,14 +1050,39 @@ impl TicketInstance {
             key = generate_redeem_key(9);
         }
 
+        // The secret rotating redeem codes are derived from is re-keyed with the redeem key so codes
+        // generated by a previous owner stop working
         diesel::update(self)
-            .set(ticket_instances::redeem_key.eq(key.clone()))
+            .set((
+                ticket_instances::redeem_key.eq(key.clone()),
+                ticket_instances::redeem_secret.eq(random_alpha_string(REDEEM_SECRET_LENGTH)),
+            ))
             .execute(conn)
             .to_db_error(ErrorCode::InternalError, "Could not write redeem key")?;
 
         Ok(key)
     }
 
+    /// Events using rotating barcodes require the short lived code shown by the holder's app along
+    /// with the redeem key, other events only need the redeem key
+    pub fn verify_redeem_code(&self, redeem_code: Option<&str>, conn: &PgConnection) -> Result<bool, DatabaseError> {
+        let period_seconds = match self.event(conn)?.barcode_rotation_seconds {
+            Some(period_seconds) => period_seconds,
+            None => return Ok(true),
+        };
+
+        match (&self.redeem_secret, redeem_code) {
+            (Some(redeem_secret), Some(redeem_code)) => Ok(totp::verify(
+                redeem_secret,
+                redeem_code,
+                Utc::now().timestamp(),
+                period_seconds as i64,
+                REDEEM_CODE_DRIFT_PERIODS,
+            )),
+            _ => Ok(false),
+        }
+    }
+
     pub fn has_pending_transfer(&self, conn: &PgConnection) -> Result<bool, DatabaseError> {
         Ok(TransferTicket::pending_transfer(self.id, conn)?.is_some())
     }
@@ -1040,6 +1100,9 @@ impl TicketInstance {
             .to_db_error(ErrorCode::QueryError, "Unable to load ticket")?;
         if ticket.has_pending_transfer(conn)? {
             return Ok(RedeemResults::TicketTransferInProcess);
+        } else if ticket.ticket_type(conn)?.ticket_type_type == TicketTypeType::LootBox {
+            // Loot boxes are opened by their owner rather than scanned at the door
+            return Ok(RedeemResults::TicketInvalid);
         } else if ticket.status == TicketInstanceStatus::Purchased
             && ticket.redeem_key.is_some()
             && ticket.redeem_key.clone().unwrap() == redeem_key
@@ -1134,7 +1197,9 @@ impl TicketInstance {
         for ti in ticket_ids {
             let mut found_and_purchased = false;
             for t in &tickets {
-                if t.id == *ti && t.status == TicketInstanceStatus::Purchased {
+                if t.id == *ti && t.trade_id.is_some() {
+                    return DatabaseError::business_process_error("Tickets in a pending trade cannot be transferred");
+                } else if t.id == *ti && t.status == TicketInstanceStatus::Purchased {
                     found_and_purchased = true;
                     ticket_ids_and_updated_at.push((*ti, t.updated_at));
                     wallet_id = t.wallet_id;
@@ -1170,6 +1235,7 @@ impl TicketInstance {
         //Confirm that tickets are purchased and owned by user
         let (wallet_id, ticket_ids_and_updated_at) =
             TicketInstance::verify_tickets_belong_to_user(user.id, ticket_ids, conn)?;
+        Package::validate_complete_packages(ticket_ids, conn)?;
 
         //Generate transfer_key and store keys and set transfer_expiry date
         let transfer_key = Uuid::new_v4();
@@ -1394,6 +1460,7 @@ pub struct DisplayTicket {
     pub ticket_type_name: String,
     pub status: TicketInstanceStatus,
     pub redeem_key: Option<String>,
+    pub redeem_secret: Option<String>,
     pub pending_transfer: bool,
     pub first_name_override: Option<String>,
     pub last_name_override: Option<String>,
@@ -1426,6 +1493,8 @@ pub struct DisplayTicketIntermediary {
     pub status: TicketInstanceStatus,
     #[sql_type = "Nullable<Text>"]
     pub redeem_key: Option<String>,
+    #[sql_type = "Nullable<Text>"]
+    pub redeem_secret: Option<String>,
     #[sql_type = "Nullable<Timestamp>"]
     pub redeem_date: Option<NaiveDateTime>,
     #[sql_type = "Nullable<Timestamp>"]
@@ -1467,10 +1536,13 @@ impl From<DisplayTicketIntermediary> for DisplayTicket {
             .or(day_before_event_start)
             .unwrap();
 
-        let redeem_key = if Utc::now().naive_utc() > redemption_allowed_after_date {
-            ticket_intermediary.redeem_key.clone()
+        let (redeem_key, redeem_secret) = if Utc::now().naive_utc() > redemption_allowed_after_date {
+            (
+                ticket_intermediary.redeem_key.clone(),
+                ticket_intermediary.redeem_secret.clone(),
+            )
         } else {
-            None
+            (None, None)
         };
 
         DisplayTicket {
@@ -1484,6 +1556,7 @@ impl From<DisplayTicketIntermediary> for DisplayTicket {
             first_name_override: ticket_intermediary.first_name_override,
             last_name_override: ticket_intermediary.last_name_override,
             redeem_key,
+            redeem_secret,
             transfer_id: ticket_intermediary.transfer_id,
             transfer_key: ticket_intermediary.transfer_key,
             transfer_address: ticket_intermediary.transfer_address,
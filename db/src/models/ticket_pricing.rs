@@ -310,6 +310,11 @@ impl TicketPricing {
         Ok(())
     }
 
+    /// Prices are in the currency of the ticket type's event
+    pub fn currency(&self, conn: &PgConnection) -> Result<Currencies, DatabaseError> {
+        TicketType::find(self.ticket_type_id, conn)?.currency(conn)
+    }
+
     pub fn get_default(ticket_type_id: Uuid, conn: &PgConnection) -> Result<TicketPricing, DatabaseError> {
         ticket_pricing::table
             .filter(ticket_pricing::ticket_type_id.eq(ticket_type_id))
@@ -0,0 +1,232 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::prelude::*;
+use models::*;
+use schema::ticket_scans;
+use utils::errors::*;
+use utils::pagination::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Identifiable, PartialEq, Deserialize, Serialize, Queryable)]
+#[table_name = "ticket_scans"]
+pub struct TicketScan {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub ticket_instance_id: Option<Uuid>,
+    pub access_zone_id: Option<Uuid>,
+    pub direction: ScanDirections,
+    pub result: ScanResults,
+    pub rejection_reason: Option<ScanRejectionReasons>,
+    pub redeem_key: Option<String>,
+    pub scanned_by_user_id: Uuid,
+    pub device_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub seq: i64,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[table_name = "ticket_scans"]
+pub struct NewTicketScan {
+    pub event_id: Uuid,
+    pub ticket_instance_id: Option<Uuid>,
+    pub access_zone_id: Option<Uuid>,
+    pub direction: ScanDirections,
+    pub result: ScanResults,
+    pub rejection_reason: Option<ScanRejectionReasons>,
+    pub redeem_key: Option<String>,
+    pub scanned_by_user_id: Uuid,
+    pub device_id: Option<String>,
+}
+
+impl TicketScan {
+    pub fn create(
+        event_id: Uuid,
+        ticket_instance_id: Option<Uuid>,
+        access_zone_id: Option<Uuid>,
+        direction: ScanDirections,
+        rejection_reason: Option<ScanRejectionReasons>,
+        redeem_key: Option<String>,
+        scanned_by_user_id: Uuid,
+        device_id: Option<String>,
+    ) -> NewTicketScan {
+        NewTicketScan {
+            event_id,
+            ticket_instance_id,
+            access_zone_id,
+            direction,
+            result: if rejection_reason.is_some() {
+                ScanResults::Rejected
+            } else {
+                ScanResults::Accepted
+            },
+            rejection_reason,
+            redeem_key,
+            scanned_by_user_id,
+            device_id,
+        }
+    }
+
+    /// Scans the ticket into or out of the access zone and records the attempt. The first accepted
+    /// entry redeems the ticket, later entries are re-entries governed by the zone's policy.
+    pub fn scan(
+        access_zone: &AccessZone,
+        ticket: Option<&TicketInstance>,
+        redeem_key: String,
+        redeem_code: Option<&str>,
+        direction: ScanDirections,
+        scanned_by_user_id: Uuid,
+        device_id: Option<String>,
+        conn: &PgConnection,
+    ) -> Result<TicketScan, DatabaseError> {
+        let rejection_reason = match ticket {
+            Some(ticket) => TicketScan::process(
+                access_zone,
+                ticket,
+                &redeem_key,
+                redeem_code,
+                direction,
+                scanned_by_user_id,
+                conn,
+            )?,
+            None => Some(ScanRejectionReasons::InvalidTicket),
+        };
+
+        TicketScan::create(
+            access_zone.event_id,
+            ticket.map(|t| t.id),
+            Some(access_zone.id),
+            direction,
+            rejection_reason,
+            Some(redeem_key),
+            scanned_by_user_id,
+            device_id,
+        )
+        .commit(conn)
+    }
+
+    fn process(
+        access_zone: &AccessZone,
+        ticket: &TicketInstance,
+        redeem_key: &str,
+        redeem_code: Option<&str>,
+        direction: ScanDirections,
+        scanned_by_user_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Option<ScanRejectionReasons>, DatabaseError> {
+        let ticket_type = ticket.ticket_type(conn)?;
+        if ticket_type.event_id != access_zone.event_id {
+            return Ok(Some(ScanRejectionReasons::InvalidTicket));
+        }
+        if !ticket.verify_redeem_code(redeem_code, conn)? {
+            return Ok(Some(ScanRejectionReasons::ExpiredCode));
+        }
+        if !access_zone.admits(ticket_type.id, conn)? {
+            return Ok(Some(ScanRejectionReasons::WrongZone));
+        }
+
+        let last_scan = TicketScan::find_latest_accepted(ticket.id, access_zone.id, conn)?;
+        let inside = last_scan.as_ref().map(|s| s.direction) == Some(ScanDirections::Entry);
+        if direction == ScanDirections::Exit {
+            return Ok(if inside {
+                None
+            } else {
+                Some(ScanRejectionReasons::NotInside)
+            });
+        }
+
+        if inside {
+            return Ok(Some(ScanRejectionReasons::AlreadyInside));
+        }
+        if last_scan.is_some() && access_zone.re_entry_policy == ReEntryPolicies::NoReEntry {
+            return Ok(Some(ScanRejectionReasons::ReEntryNotAllowed));
+        }
+
+        match ticket.status {
+            TicketInstanceStatus::Purchased => Ok(TicketInstance::redeem_ticket(
+                ticket.id,
+                redeem_key.to_string(),
+                scanned_by_user_id,
+                CheckInSource::Scanned,
+                conn,
+            )?
+            .scan_rejection_reason()),
+            // Already let in at another zone
+            TicketInstanceStatus::Redeemed => Ok(None),
+            _ => Ok(Some(ScanRejectionReasons::InvalidTicket)),
+        }
+    }
+
+    pub fn find_latest_accepted(
+        ticket_instance_id: Uuid,
+        access_zone_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Option<TicketScan>, DatabaseError> {
+        ticket_scans::table
+            .filter(ticket_scans::ticket_instance_id.eq(ticket_instance_id))
+            .filter(ticket_scans::access_zone_id.eq(access_zone_id))
+            .filter(ticket_scans::result.eq(ScanResults::Accepted))
+            .order_by(ticket_scans::seq.desc())
+            .first(conn)
+            .optional()
+            .to_db_error(ErrorCode::QueryError, "Unable to load latest ticket scan")
+    }
+
+    pub fn find_for_ticket(ticket_instance_id: Uuid, conn: &PgConnection) -> Result<Vec<TicketScan>, DatabaseError> {
+        ticket_scans::table
+            .filter(ticket_scans::ticket_instance_id.eq(ticket_instance_id))
+            .order_by(ticket_scans::seq.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ticket scans")
+    }
+
+    /// Scan log for the event, most recent first
+    pub fn find_for_event(
+        event_id: Uuid,
+        access_zone_id: Option<Uuid>,
+        result: Option<ScanResults>,
+        page: u32,
+        limit: u32,
+        conn: &PgConnection,
+    ) -> Result<Payload<TicketScan>, DatabaseError> {
+        let mut query = ticket_scans::table
+            .filter(ticket_scans::event_id.eq(event_id))
+            .into_boxed();
+        if let Some(access_zone_id) = access_zone_id {
+            query = query.filter(ticket_scans::access_zone_id.eq(access_zone_id));
+        }
+        if let Some(result) = result {
+            query = query.filter(ticket_scans::result.eq(result));
+        }
+
+        let (scans, record_count): (Vec<TicketScan>, i64) = query
+            .order_by(ticket_scans::seq.desc())
+            .paginate(page as i64)
+            .per_page(limit as i64)
+            .load_and_count_pages(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load ticket scans")?;
+
+        Ok(Payload::from_data(scans, page, limit, Some(record_count as u64)))
+    }
+}
+
+impl NewTicketScan {
+    pub fn commit(self, conn: &PgConnection) -> Result<TicketScan, DatabaseError> {
+        diesel::insert_into(ticket_scans::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not record ticket scan")
+    }
+}
+
+impl RedeemResults {
+    pub fn scan_rejection_reason(&self) -> Option<ScanRejectionReasons> {
+        match self {
+            RedeemResults::TicketRedeemSuccess => None,
+            RedeemResults::TicketAlreadyRedeemed => Some(ScanRejectionReasons::AlreadyRedeemed),
+            RedeemResults::TicketInvalid => Some(ScanRejectionReasons::InvalidTicket),
+            RedeemResults::TicketTransferInProcess => Some(ScanRejectionReasons::TransferInProgress),
+        }
+    }
+}
@@ -48,6 +48,10 @@ pub struct TicketType {
     pub ticket_type_type: TicketTypeType,
     pub promo_image_url: Option<String>,
     pub content_url: Option<String>,
+    /// Sales count towards the stage's capacity as well as the event's
+    pub stage_id: Option<Uuid>,
+    /// Sells from the tickets of every ticket type in the pool
+    pub inventory_pool_id: Option<Uuid>,
 }
 
 impl PartialOrd for TicketType {
@@ -96,6 +100,10 @@ impl TicketType {
         Ok(res)
     }
 
+    pub fn currency(&self, conn: &PgConnection) -> Result<Currencies, DatabaseError> {
+        self.event(conn)?.currency(conn)
+    }
+
     pub fn fee_schedule(&self, conn: &PgConnection) -> Result<FeeSchedule, DatabaseError> {
         ticket_types::table
             .inner_join(events::table.inner_join(organizations::table.inner_join(fee_schedules::table)))
@@ -271,6 +279,47 @@ impl TicketType {
         Ok(result)
     }
 
+    /// Assigns the ticket type to one of the stages of the event's venue, or removes it from its stage
+    pub fn update_stage(
+        self,
+        stage_id: Option<Uuid>,
+        current_user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<TicketType, DatabaseError> {
+        if let Some(stage_id) = stage_id {
+            if Some(Stage::find(stage_id, conn)?.venue_id) != self.event(conn)?.venue_id {
+                return Ok(validators::append_validation_error(
+                    Ok(()),
+                    "stage_id",
+                    Err(create_validation_error(
+                        "stage_venue_mismatch",
+                        "Stage must belong to the event's venue",
+                    )),
+                )?);
+            }
+        }
+
+        let result: TicketType = diesel::update(&self)
+            .set((
+                ticket_types::stage_id.eq(stage_id),
+                ticket_types::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update ticket type stage")?;
+
+        DomainEvent::create(
+            DomainEventTypes::TicketTypeUpdated,
+            format!("Ticket type '{}' stage updated", &self.name),
+            Tables::TicketTypes,
+            Some(self.id),
+            current_user_id,
+            Some(json!({ "stage_id": stage_id })),
+        )
+        .commit(conn)?;
+
+        Ok(result)
+    }
+
     pub fn cancel(self, conn: &PgConnection) -> Result<TicketType, DatabaseError> {
         let result: TicketType = diesel::update(&self)
             .set((
@@ -604,10 +653,17 @@ impl TicketType {
         Ok(valid_unsold_ticket_count as u32)
     }
 
+    /// Pooled tickets count towards the ticket type they were sold as
     pub fn valid_sold_and_reserved_ticket_count(&self, conn: &PgConnection) -> Result<u32, DatabaseError> {
         let valid_unsold_ticket_count: i64 = ticket_instances::table
             .inner_join(assets::table)
-            .filter(assets::ticket_type_id.eq(self.id))
+            .filter(
+                ticket_instances::sold_as_ticket_type_id
+                    .eq(self.id)
+                    .or(ticket_instances::sold_as_ticket_type_id
+                        .is_null()
+                        .and(assets::ticket_type_id.eq(self.id))),
+            )
             .filter(ticket_instances::status.eq_any(vec![
                 TicketInstanceStatus::Purchased,
                 TicketInstanceStatus::Reserved,
@@ -619,11 +675,16 @@ impl TicketType {
         Ok(valid_unsold_ticket_count as u32)
     }
 
+    /// Pooled ticket types can sell any unsold ticket in their pool
     pub fn valid_available_ticket_count(&self, conn: &PgConnection) -> Result<u32, DatabaseError> {
+        let inventory_ticket_type_ids = match self.inventory_pool_id {
+            Some(inventory_pool_id) => InventoryPool::find(inventory_pool_id, conn)?.ticket_type_ids(conn)?,
+            None => vec![self.id],
+        };
         let query = ticket_instances::table
             .inner_join(assets::table)
             .filter(
-                assets::ticket_type_id.eq(self.id).and(
+                assets::ticket_type_id.eq_any(inventory_ticket_type_ids).and(
                     ticket_instances::status
                         .eq(TicketInstanceStatus::Available)
                         .or(sql("(ticket_instances.status=")
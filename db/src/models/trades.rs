@@ -0,0 +1,593 @@
+use chrono::prelude::*;
+use chrono::Duration;
+use diesel;
+use diesel::expression::dsl;
+use diesel::prelude::*;
+use models::*;
+use schema::{assets, events, ticket_instances, ticket_types, trade_items, trades};
+use std::collections::HashSet;
+use utils::errors::*;
+use uuid::Uuid;
+use validator::ValidationErrors;
+use validators;
+
+/// Card authorizations for cash offered in a trade are only held by the payment provider for 7 days
+pub const TRADE_EXPIRY_DAYS: i64 = 7;
+
+#[derive(Clone, Debug, Deserialize, Identifiable, PartialEq, Queryable, Serialize)]
+pub struct Trade {
+    pub id: Uuid,
+    pub proposed_by_user_id: Uuid,
+    pub recipient_user_id: Uuid,
+    pub status: TradeStatus,
+    pub message: Option<String>,
+    pub cash_in_cents: i64,
+    pub currency: Currencies,
+    pub payment_provider: Option<PaymentProviders>,
+    #[serde(skip_serializing)]
+    pub external_payment_reference: Option<String>,
+    pub expires_at: NaiveDateTime,
+    pub responded_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "trades"]
+struct NewTrade {
+    proposed_by_user_id: Uuid,
+    recipient_user_id: Uuid,
+    message: Option<String>,
+    cash_in_cents: i64,
+    currency: Currencies,
+    expires_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Identifiable, PartialEq, Queryable, Serialize)]
+pub struct TradeItem {
+    pub id: Uuid,
+    pub trade_id: Uuid,
+    pub ticket_instance_id: Uuid,
+    pub from_user_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "trade_items"]
+struct NewTradeItem {
+    trade_id: Uuid,
+    ticket_instance_id: Uuid,
+    from_user_id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Queryable, Serialize)]
+pub struct DisplayTradeItem {
+    pub ticket_instance_id: Uuid,
+    pub from_user_id: Uuid,
+    pub ticket_type_id: Uuid,
+    pub ticket_type_name: String,
+    pub rarity_id: Option<Uuid>,
+    pub event_id: Uuid,
+    pub event_name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DisplayTrade {
+    pub id: Uuid,
+    pub proposed_by_user_id: Uuid,
+    pub recipient_user_id: Uuid,
+    pub status: TradeStatus,
+    pub message: Option<String>,
+    pub cash_in_cents: i64,
+    pub currency: Currencies,
+    pub offered_items: Vec<DisplayTradeItem>,
+    pub requested_items: Vec<DisplayTradeItem>,
+    pub expires_at: NaiveDateTime,
+    pub responded_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl Trade {
+    /// Proposes swapping the offered tickets, optionally along with cash, for the recipient's
+    /// requested tickets. Tickets on both sides are locked until the trade is accepted, declined,
+    /// cancelled or expires.
+    pub fn propose(
+        proposed_by_user_id: Uuid,
+        recipient_user_id: Uuid,
+        offered_ticket_ids: &[Uuid],
+        requested_ticket_ids: &[Uuid],
+        cash_in_cents: i64,
+        message: Option<String>,
+        conn: &PgConnection,
+    ) -> Result<Trade, DatabaseError> {
+        Trade::validate_proposal(
+            proposed_by_user_id,
+            recipient_user_id,
+            offered_ticket_ids,
+            requested_ticket_ids,
+            cash_in_cents,
+        )?;
+
+        let offered_tickets = TicketInstance::find_by_ids(offered_ticket_ids, conn)?;
+        let requested_tickets = TicketInstance::find_by_ids(requested_ticket_ids, conn)?;
+        if offered_tickets.len() != offered_ticket_ids.len() || requested_tickets.len() != requested_ticket_ids.len() {
+            return DatabaseError::business_process_error("Could not find all tickets in the trade");
+        }
+        Trade::verify_tradable(&offered_tickets, proposed_by_user_id, None, conn)?;
+        Trade::verify_tradable(&requested_tickets, recipient_user_id, None, conn)?;
+        let currency = Trade::currency_for_tickets(&requested_tickets, cash_in_cents, conn)?;
+
+        let trade: Trade = diesel::insert_into(trades::table)
+            .values(NewTrade {
+                proposed_by_user_id,
+                recipient_user_id,
+                message,
+                cash_in_cents,
+                currency,
+                expires_at: Utc::now().naive_utc() + Duration::days(TRADE_EXPIRY_DAYS),
+            })
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create trade")?;
+
+        let mut items = Vec::new();
+        for ticket in &offered_tickets {
+            items.push(NewTradeItem {
+                trade_id: trade.id,
+                ticket_instance_id: ticket.id,
+                from_user_id: proposed_by_user_id,
+            });
+        }
+        for ticket in &requested_tickets {
+            items.push(NewTradeItem {
+                trade_id: trade.id,
+                ticket_instance_id: ticket.id,
+                from_user_id: recipient_user_id,
+            });
+        }
+        diesel::insert_into(trade_items::table)
+            .values(&items)
+            .execute(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create trade items")?;
+
+        let ticket_ids: Vec<Uuid> = offered_ticket_ids.iter().chain(requested_ticket_ids).cloned().collect();
+        let locked = diesel::update(
+            ticket_instances::table
+                .filter(ticket_instances::id.eq_any(&ticket_ids))
+                .filter(ticket_instances::trade_id.is_null()),
+        )
+        .set((
+            ticket_instances::trade_id.eq(trade.id),
+            ticket_instances::updated_at.eq(dsl::now),
+        ))
+        .execute(conn)
+        .to_db_error(ErrorCode::UpdateError, "Could not lock tickets for trade")?;
+        if locked != ticket_ids.len() {
+            return DatabaseError::concurrency_error("Tickets were added to another trade, please try again");
+        }
+
+        DomainEvent::create(
+            DomainEventTypes::TradeProposed,
+            "Trade proposed".to_string(),
+            Tables::Trades,
+            Some(trade.id),
+            Some(proposed_by_user_id),
+            Some(json!({
+                "recipient_user_id": recipient_user_id,
+                "offered_ticket_ids": offered_ticket_ids,
+                "requested_ticket_ids": requested_ticket_ids,
+                "cash_in_cents": cash_in_cents
+            })),
+        )
+        .commit(conn)?;
+
+        let mut action = DomainAction::create(
+            None,
+            DomainActionTypes::ExpireTrade,
+            None,
+            json!({}),
+            Some(Tables::Trades),
+            Some(trade.id),
+        );
+        action.schedule_at(trade.expires_at);
+        action.commit(conn)?;
+
+        Ok(trade)
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<Trade, DatabaseError> {
+        trades::table
+            .find(id)
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not find trade")
+    }
+
+    /// Trades the user proposed or received, most recent first
+    pub fn find_for_user(
+        user_id: Uuid,
+        status: Option<TradeStatus>,
+        conn: &PgConnection,
+    ) -> Result<Vec<Trade>, DatabaseError> {
+        let mut query = trades::table
+            .filter(
+                trades::proposed_by_user_id
+                    .eq(user_id)
+                    .or(trades::recipient_user_id.eq(user_id)),
+            )
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(trades::status.eq(status));
+        }
+        query
+            .order_by(trades::created_at.desc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load trades")
+    }
+
+    pub fn items(&self, conn: &PgConnection) -> Result<Vec<TradeItem>, DatabaseError> {
+        trade_items::table
+            .filter(trade_items::trade_id.eq(self.id))
+            .order_by(trade_items::created_at.asc())
+            .then_order_by(trade_items::ticket_instance_id.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load trade items")
+    }
+
+    /// Tickets in the trade given up by the user
+    pub fn tickets_from(&self, user_id: Uuid, conn: &PgConnection) -> Result<Vec<TicketInstance>, DatabaseError> {
+        ticket_instances::table
+            .inner_join(trade_items::table.on(trade_items::ticket_instance_id.eq(ticket_instances::id)))
+            .filter(trade_items::trade_id.eq(self.id))
+            .filter(trade_items::from_user_id.eq(user_id))
+            .select(ticket_instances::all_columns)
+            .order_by(ticket_instances::id.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load tickets for trade")
+    }
+
+    pub fn for_display(&self, conn: &PgConnection) -> Result<DisplayTrade, DatabaseError> {
+        let items: Vec<DisplayTradeItem> = trade_items::table
+            .inner_join(ticket_instances::table.on(trade_items::ticket_instance_id.eq(ticket_instances::id)))
+            .inner_join(assets::table.on(ticket_instances::asset_id.eq(assets::id)))
+            .inner_join(ticket_types::table.on(assets::ticket_type_id.eq(ticket_types::id)))
+            .inner_join(events::table.on(ticket_types::event_id.eq(events::id)))
+            .filter(trade_items::trade_id.eq(self.id))
+            .select((
+                trade_items::ticket_instance_id,
+                trade_items::from_user_id,
+                ticket_types::id,
+                ticket_types::name,
+                ticket_types::rarity_id,
+                events::id,
+                events::name,
+            ))
+            .order_by(events::name.asc())
+            .then_order_by(ticket_types::name.asc())
+            .then_order_by(trade_items::ticket_instance_id.asc())
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load trade items")?;
+        let (offered_items, requested_items): (Vec<DisplayTradeItem>, Vec<DisplayTradeItem>) = items
+            .into_iter()
+            .partition(|i| i.from_user_id == self.proposed_by_user_id);
+
+        Ok(DisplayTrade {
+            id: self.id,
+            proposed_by_user_id: self.proposed_by_user_id,
+            recipient_user_id: self.recipient_user_id,
+            status: self.status,
+            message: self.message.clone(),
+            cash_in_cents: self.cash_in_cents,
+            currency: self.currency,
+            offered_items,
+            requested_items,
+            expires_at: self.expires_at,
+            responded_at: self.responded_at,
+            created_at: self.created_at,
+        })
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now().naive_utc()
+    }
+
+    /// Records the card authorization holding the offered cash in escrow until the trade is resolved
+    pub fn set_payment(
+        &self,
+        payment_provider: PaymentProviders,
+        external_payment_reference: String,
+        conn: &PgConnection,
+    ) -> Result<Trade, DatabaseError> {
+        diesel::update(self)
+            .set((
+                trades::payment_provider.eq(payment_provider),
+                trades::external_payment_reference.eq(external_payment_reference),
+                trades::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update trade payment")
+    }
+
+    /// Swaps the offered and requested tickets between both users' default wallets in one step and
+    /// credits any cash offered to the recipient's credit balance, which they can spend at checkout.
+    /// Moving the tokens on the blockchain and capturing the cash is left to the caller.
+    pub fn accept(&self, user_id: Uuid, conn: &PgConnection) -> Result<Trade, DatabaseError> {
+        if user_id != self.recipient_user_id {
+            return DatabaseError::business_process_error("Only the recipient can accept a trade");
+        }
+        self.verify_pending()?;
+
+        let offered_tickets = self.locked_tickets_from(self.proposed_by_user_id, conn)?;
+        let requested_tickets = self.locked_tickets_from(self.recipient_user_id, conn)?;
+        Trade::verify_tradable(&offered_tickets, self.proposed_by_user_id, Some(self.id), conn)?;
+        Trade::verify_tradable(&requested_tickets, self.recipient_user_id, Some(self.id), conn)?;
+        for tickets in &[&offered_tickets, &requested_tickets] {
+            let ticket_ids: Vec<Uuid> = tickets.iter().map(|t| t.id).collect();
+            Package::validate_complete_packages(&ticket_ids, conn)?;
+        }
+
+        let proposer_wallet = Wallet::find_default_for_user(self.proposed_by_user_id, conn)?;
+        let recipient_wallet = Wallet::find_default_for_user(self.recipient_user_id, conn)?;
+        for (tickets, wallet) in &[
+            (&offered_tickets, &recipient_wallet),
+            (&requested_tickets, &proposer_wallet),
+        ] {
+            for ticket in tickets.iter() {
+                let name_override: Option<String> = None;
+                diesel::update(ticket)
+                    .set((
+                        ticket_instances::wallet_id.eq(wallet.id),
+                        ticket_instances::trade_id.eq(None::<Uuid>),
+                        ticket_instances::first_name_override.eq(&name_override),
+                        ticket_instances::last_name_override.eq(&name_override),
+                        ticket_instances::updated_at.eq(dsl::now),
+                    ))
+                    .execute(conn)
+                    .to_db_error(ErrorCode::UpdateError, "Could not update ticket instance")?;
+                ticket.associate_redeem_key(conn)?;
+            }
+        }
+
+        if self.cash_in_cents > 0 {
+            UserCreditEntry::create(
+                self.recipient_user_id,
+                self.cash_in_cents,
+                self.currency,
+                Tables::Trades,
+                self.id,
+                "Cash received in trade".to_string(),
+                Some(user_id),
+            )
+            .commit(conn)?;
+        }
+
+        self.close(
+            TradeStatus::Accepted,
+            DomainEventTypes::TradeAccepted,
+            Some(user_id),
+            conn,
+        )
+    }
+
+    pub fn decline(&self, user_id: Uuid, conn: &PgConnection) -> Result<Trade, DatabaseError> {
+        if user_id != self.recipient_user_id {
+            return DatabaseError::business_process_error("Only the recipient can decline a trade");
+        }
+        self.verify_pending()?;
+        self.close(
+            TradeStatus::Declined,
+            DomainEventTypes::TradeDeclined,
+            Some(user_id),
+            conn,
+        )
+    }
+
+    pub fn cancel(&self, user_id: Uuid, conn: &PgConnection) -> Result<Trade, DatabaseError> {
+        if user_id != self.proposed_by_user_id {
+            return DatabaseError::business_process_error("Only the proposer can cancel a trade");
+        }
+        if self.status != TradeStatus::Pending {
+            return DatabaseError::business_process_error("Only pending trades can be cancelled");
+        }
+        self.close(
+            TradeStatus::Cancelled,
+            DomainEventTypes::TradeCancelled,
+            Some(user_id),
+            conn,
+        )
+    }
+
+    pub fn expire(&self, conn: &PgConnection) -> Result<Trade, DatabaseError> {
+        if self.status != TradeStatus::Pending {
+            return DatabaseError::business_process_error("Only pending trades can expire");
+        }
+        if !self.is_expired() {
+            return DatabaseError::business_process_error("Trade has not reached its expiry date");
+        }
+        self.close(TradeStatus::Expired, DomainEventTypes::TradeExpired, None, conn)
+    }
+
+    fn verify_pending(&self) -> Result<(), DatabaseError> {
+        if self.status != TradeStatus::Pending {
+            return DatabaseError::business_process_error("Trade is no longer pending");
+        }
+        if self.is_expired() {
+            return DatabaseError::business_process_error("Trade has expired");
+        }
+        Ok(())
+    }
+
+    /// Loads the user's side of the trade, locking the rows until the swap is complete
+    fn locked_tickets_from(&self, user_id: Uuid, conn: &PgConnection) -> Result<Vec<TicketInstance>, DatabaseError> {
+        let ticket_ids: Vec<Uuid> = trade_items::table
+            .filter(trade_items::trade_id.eq(self.id))
+            .filter(trade_items::from_user_id.eq(user_id))
+            .select(trade_items::ticket_instance_id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load trade items")?;
+        ticket_instances::table
+            .filter(ticket_instances::id.eq_any(ticket_ids))
+            .order_by(ticket_instances::id.asc())
+            .for_update()
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load tickets for trade")
+    }
+
+    fn close(
+        &self,
+        status: TradeStatus,
+        event_type: DomainEventTypes,
+        user_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<Trade, DatabaseError> {
+        diesel::update(ticket_instances::table.filter(ticket_instances::trade_id.eq(self.id)))
+            .set((
+                ticket_instances::trade_id.eq(None::<Uuid>),
+                ticket_instances::updated_at.eq(dsl::now),
+            ))
+            .execute(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not release tickets from trade")?;
+
+        let trade: Trade = diesel::update(self)
+            .set((
+                trades::status.eq(status),
+                trades::responded_at.eq(dsl::now.nullable()),
+                trades::updated_at.eq(dsl::now),
+            ))
+            .get_result(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update trade")?;
+
+        if status != TradeStatus::Expired {
+            if let Some(action) = DomainAction::upcoming_domain_action(
+                Some(Tables::Trades),
+                Some(self.id),
+                DomainActionTypes::ExpireTrade,
+                conn,
+            )? {
+                action.set_cancelled(conn)?;
+            }
+        }
+
+        DomainEvent::create(
+            event_type,
+            format!("Trade {}", status.to_string().to_lowercase()),
+            Tables::Trades,
+            Some(trade.id),
+            user_id,
+            Some(json!({ "cash_in_cents": trade.cash_in_cents })),
+        )
+        .commit(conn)?;
+
+        Ok(trade)
+    }
+
+    /// Tickets can only be traded from the owner's default wallet and not while they are listed,
+    /// being transferred or locked by another trade
+    fn verify_tradable(
+        tickets: &[TicketInstance],
+        user_id: Uuid,
+        trade_id: Option<Uuid>,
+        conn: &PgConnection,
+    ) -> Result<(), DatabaseError> {
+        let wallet = Wallet::find_default_for_user(user_id, conn)?;
+        for ticket in tickets {
+            if ticket.wallet_id != wallet.id {
+                return DatabaseError::business_process_error("User does not own all tickets in the trade");
+            }
+            if ticket.status != TicketInstanceStatus::Purchased {
+                return DatabaseError::business_process_error("Only purchased tickets can be traded");
+            }
+            if ticket.listing_id.is_some() || ticket.trade_id != trade_id || ticket.has_pending_transfer(conn)? {
+                return DatabaseError::business_process_error(
+                    "Tickets that are listed, being transferred or in another trade cannot be traded",
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Cash is offered in the currency the requested tickets were sold in
+    fn currency_for_tickets(
+        tickets: &[TicketInstance],
+        cash_in_cents: i64,
+        conn: &PgConnection,
+    ) -> Result<Currencies, DatabaseError> {
+        let mut currencies = HashSet::new();
+        for ticket in tickets {
+            currencies.insert(ticket.ticket_type(conn)?.currency(conn)?);
+        }
+        if cash_in_cents > 0 && currencies.len() > 1 {
+            let validation_errors: Result<(), ValidationErrors> = validators::append_validation_error(
+                Ok(()),
+                "cash_in_cents",
+                Err(validators::create_validation_error(
+                    "trade_currency_mismatch",
+                    "Cash can only be offered for tickets sold in a single currency",
+                )),
+            );
+            validation_errors?;
+        }
+        Ok(currencies.into_iter().next().unwrap_or(Currencies::USD))
+    }
+
+    fn validate_proposal(
+        proposed_by_user_id: Uuid,
+        recipient_user_id: Uuid,
+        offered_ticket_ids: &[Uuid],
+        requested_ticket_ids: &[Uuid],
+        cash_in_cents: i64,
+    ) -> Result<(), DatabaseError> {
+        let mut validation_errors: Result<(), ValidationErrors> = Ok(());
+        if proposed_by_user_id == recipient_user_id {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "recipient_user_id",
+                Err(validators::create_validation_error(
+                    "trade_with_self",
+                    "Trades must be proposed to another user",
+                )),
+            );
+        }
+        if requested_ticket_ids.is_empty() {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "requested_ticket_ids",
+                Err(validators::create_validation_error(
+                    "required",
+                    "Trades must request at least one ticket",
+                )),
+            );
+        }
+        if offered_ticket_ids.is_empty() && cash_in_cents == 0 {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "offered_ticket_ids",
+                Err(validators::create_validation_error(
+                    "required",
+                    "Trades must offer at least one ticket or cash",
+                )),
+            );
+        }
+        if cash_in_cents < 0 {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "cash_in_cents",
+                Err(validators::create_validation_error(
+                    "invalid_amount",
+                    "Cash offered cannot be negative",
+                )),
+            );
+        }
+        let unique_ticket_ids: HashSet<&Uuid> = offered_ticket_ids.iter().chain(requested_ticket_ids).collect();
+        if unique_ticket_ids.len() != offered_ticket_ids.len() + requested_ticket_ids.len() {
+            validation_errors = validators::append_validation_error(
+                validation_errors,
+                "requested_ticket_ids",
+                Err(validators::create_validation_error(
+                    "duplicate_ticket",
+                    "Tickets can only appear once in a trade",
+                )),
+            );
+        }
+        Ok(validation_errors?)
+    }
+}
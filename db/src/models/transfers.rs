@@ -14,6 +14,8 @@ use tari_client::*;
 use utils::errors::ConvertToDatabaseError;
 use utils::errors::DatabaseError;
 use utils::errors::ErrorCode;
+use utils::errors::Optional;
+use utils::localization;
 use utils::pagination::Paginate;
 use uuid::Uuid;
 use validator::*;
@@ -96,6 +98,7 @@ impl Transfer {
         event: &Event,
         source_or_destination: SourceOrDestination,
         include_links: bool,
+        locale: Locales,
         environment: Environment,
         conn: &PgConnection,
     ) -> Result<String, DatabaseError> {
@@ -110,6 +113,19 @@ impl Transfer {
         } else {
             event.days_until_event()
         };
+        let time_of_day_text = |conn: &PgConnection| -> Result<String, DatabaseError> {
+            let (is_pm, hour) = event
+                .get_all_localized_times(event.venue(conn)?.as_ref())
+                .event_start
+                .unwrap()
+                .hour12();
+            let key = if !is_pm || hour < 5 {
+                "transfers.drip.today"
+            } else {
+                "transfers.drip.tonight"
+            };
+            Ok(localization::translate(locale, key, &[]))
+        };
         Ok(match units_until_event {
             Some(units_until_event) => match source_or_destination {
                 SourceOrDestination::Source => {
@@ -119,14 +135,25 @@ impl Transfer {
                             format!("<a href='mailto:{}'>{}</a>", destination_address, destination_address);
                     }
                     match units_until_event {
-                            0 => {
-                                let (is_pm, hour) = event.get_all_localized_times(event.venue(conn)?.as_ref()).event_start.unwrap().hour12();
-                                let time_of_day_text = (if !is_pm || hour < 5 { "today" } else { "tonight" }).to_string();
-                                format!("Time to take action! The show is {} and those tickets you sent to {} still haven't been claimed. Give them a nudge!", time_of_day_text, destination_address)
-                            },
-                            1 => format!("Uh oh! The show is tomorrow and those tickets you sent to {} still haven't been claimed. Give them a nudge!", destination_address),
-                            _ => format!("Those tickets you sent to {} still haven't been claimed. Give them a nudge!", destination_address),
-                        }
+                        0 => localization::translate(
+                            locale,
+                            "transfers.drip.source.day_of",
+                            &[
+                                ("time_of_day", &time_of_day_text(conn)?),
+                                ("recipient", &destination_address),
+                            ],
+                        ),
+                        1 => localization::translate(
+                            locale,
+                            "transfers.drip.source.day_before",
+                            &[("recipient", &destination_address)],
+                        ),
+                        _ => localization::translate(
+                            locale,
+                            "transfers.drip.source.reminder",
+                            &[("recipient", &destination_address)],
+                        ),
+                    }
                 }
                 SourceOrDestination::Destination => {
                     let source_user = User::find(self.source_user_id, conn)?;
@@ -136,25 +163,69 @@ impl Transfer {
                         name = format!("<a href='mailto:{}'>{}</a>", source_user.email.unwrap(), name);
                     }
                     match units_until_event {
-                            0 => {
-                                let (is_pm, hour) = event.get_all_localized_times(event.venue(conn)?.as_ref()).event_start.unwrap().hour12();
-                                let time_of_day_text = (if !is_pm || hour < 5 { "today" } else { "tonight" }).to_string();
-                                format!("Time to take action! The event is {} and the tickets {} sent you are still waiting!", time_of_day_text, name)
-                            },
-                            1 => format!("Get your tickets! The event is TOMORROW and you still need to get the tickets that {} sent you!", name),
-                            7 => format!("The event is only one week away and you still need to get the tickets that {} sent you!", name),
-                            _ => format!("You still need to get the tickets that {} sent you!", name)
+                        0 => localization::translate(
+                            locale,
+                            "transfers.drip.destination.day_of",
+                            &[("time_of_day", &time_of_day_text(conn)?), ("sender", &name)],
+                        ),
+                        1 => localization::translate(
+                            locale,
+                            "transfers.drip.destination.day_before",
+                            &[("sender", &name)],
+                        ),
+                        7 => localization::translate(
+                            locale,
+                            "transfers.drip.destination.week_before",
+                            &[("sender", &name)],
+                        ),
+                        _ => {
+                            localization::translate(locale, "transfers.drip.destination.reminder", &[("sender", &name)])
                         }
+                    }
                 }
             },
             None => "".to_string(),
         })
     }
 
+    /// Locale for notifications sent to the sender or recipient of the transfer, recipients that have not
+    /// yet accepted are matched on their email address when they already have an account
+    pub fn locale(
+        &self,
+        source_or_destination: SourceOrDestination,
+        conn: &PgConnection,
+    ) -> Result<Locales, DatabaseError> {
+        let user = match source_or_destination {
+            SourceOrDestination::Source => Some(User::find(self.source_user_id, conn)?),
+            SourceOrDestination::Destination => match (self.destination_user_id, &self.transfer_address) {
+                (Some(destination_user_id), _) => Some(User::find(destination_user_id, conn)?),
+                (None, Some(transfer_address)) if self.transfer_message_type == Some(TransferMessageType::Email) => {
+                    User::find_by_email(transfer_address, false, conn).optional()?
+                }
+                _ => None,
+            },
+        };
+        let organization_locale = self.organizations(conn)?.first().map(|o| o.default_locale);
+
+        Ok(localization::recipient_locale(
+            user.and_then(|u| u.locale),
+            organization_locale,
+        ))
+    }
+
+    /// Base URL for links about the transfer, its organization's custom domain when one is configured
+    pub fn front_end_url(&self, default_front_end_url: &str, conn: &PgConnection) -> Result<String, DatabaseError> {
+        match self.organizations(conn)?.first() {
+            Some(organization) => organization.front_end_url(default_front_end_url, conn),
+            None => Ok(default_front_end_url.to_string()),
+        }
+    }
+
+    /// Points at the custom domain of the transferred tickets' organization when one is configured
     pub fn receive_url(&self, front_end_url: &str, conn: &PgConnection) -> Result<String, DatabaseError> {
         Ok(format!(
             "{}/tickets/transfers/receive?sender_user_id={}&transfer_key={}&num_tickets={}&signature={}",
-            front_end_url,
+            self.front_end_url(front_end_url, conn)?,
             self.source_user_id,
             self.transfer_key,
             self.transfer_tickets(conn)?.len(),
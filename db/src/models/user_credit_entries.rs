@@ -0,0 +1,117 @@
+use chrono::NaiveDateTime;
+use diesel;
+use diesel::dsl;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use models::*;
+use schema::user_credit_entries;
+use utils::errors::*;
+use uuid::Uuid;
+
+/// Entry in a user's credit ledger, the user's balance in a currency is the sum of their entries
+#[derive(Clone, Debug, Deserialize, Identifiable, PartialEq, Queryable, Serialize)]
+#[table_name = "user_credit_entries"]
+pub struct UserCreditEntry {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub amount_in_cents: i64,
+    pub currency: Currencies,
+    pub source_table: Tables,
+    pub source_id: Uuid,
+    pub description: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "user_credit_entries"]
+pub struct NewUserCreditEntry {
+    pub user_id: Uuid,
+    pub amount_in_cents: i64,
+    pub currency: Currencies,
+    pub source_table: Tables,
+    pub source_id: Uuid,
+    pub description: String,
+    pub created_by: Option<Uuid>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UserCreditBalance {
+    pub currency: Currencies,
+    pub balance_in_cents: i64,
+}
+
+impl UserCreditEntry {
+    pub fn create(
+        user_id: Uuid,
+        amount_in_cents: i64,
+        currency: Currencies,
+        source_table: Tables,
+        source_id: Uuid,
+        description: String,
+        created_by: Option<Uuid>,
+    ) -> NewUserCreditEntry {
+        NewUserCreditEntry {
+            user_id,
+            amount_in_cents,
+            currency,
+            source_table,
+            source_id,
+            description,
+            created_by,
+        }
+    }
+
+    pub fn find_for_user(user_id: Uuid, conn: &PgConnection) -> Result<Vec<UserCreditEntry>, DatabaseError> {
+        user_credit_entries::table
+            .filter(user_credit_entries::user_id.eq(user_id))
+            .order_by(user_credit_entries::created_at.desc())
+            .then_order_by(user_credit_entries::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load user credit entries")
+    }
+
+    pub fn find_for_source(
+        source_table: Tables,
+        source_id: Uuid,
+        conn: &PgConnection,
+    ) -> Result<Vec<UserCreditEntry>, DatabaseError> {
+        user_credit_entries::table
+            .filter(user_credit_entries::source_table.eq(source_table))
+            .filter(user_credit_entries::source_id.eq(source_id))
+            .order_by(user_credit_entries::created_at)
+            .then_order_by(user_credit_entries::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load user credit entries")
+    }
+
+    pub fn balances_for_user(user_id: Uuid, conn: &PgConnection) -> Result<Vec<UserCreditBalance>, DatabaseError> {
+        let balances: Vec<(Currencies, i64)> = user_credit_entries::table
+            .filter(user_credit_entries::user_id.eq(user_id))
+            .group_by(user_credit_entries::currency)
+            .select((
+                user_credit_entries::currency,
+                dsl::sql::<BigInt>("CAST(SUM(amount_in_cents) AS BIGINT)"),
+            ))
+            .order_by(user_credit_entries::currency)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Unable to load user credit balances")?;
+
+        Ok(balances
+            .into_iter()
+            .map(|(currency, balance_in_cents)| UserCreditBalance {
+                currency,
+                balance_in_cents,
+            })
+            .collect())
+    }
+}
+
+impl NewUserCreditEntry {
+    pub fn commit(self, conn: &PgConnection) -> Result<UserCreditEntry, DatabaseError> {
+        diesel::insert_into(user_credit_entries::table)
+            .values(&self)
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create user credit entry")
+    }
+}
@@ -60,6 +60,7 @@ pub struct User {
     pub accepted_terms_date: Option<NaiveDateTime>,
     pub invited_at: Option<NaiveDateTime>,
     pub deleted_at: Option<NaiveDateTime>,
+    pub locale: Option<Locales>,
 }
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
@@ -73,6 +74,7 @@ pub struct DisplayUser {
     pub thumb_profile_pic_url: Option<String>,
     pub cover_photo_url: Option<String>,
     pub is_org_owner: bool,
+    pub locale: Option<Locales>,
 }
 
 #[derive(AsChangeset, Default, Deserialize, Validate, Clone, Serialize)]
@@ -91,6 +93,7 @@ pub struct UserEditableAttributes {
     pub thumb_profile_pic_url: Option<Option<String>>,
     #[validate(url(message = "Cover photo URL is invalid"))]
     pub cover_photo_url: Option<Option<String>>,
+    pub locale: Option<Option<Locales>>,
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
@@ -1185,6 +1188,7 @@ impl From<User> for DisplayUser {
             thumb_profile_pic_url: user.thumb_profile_pic_url,
             cover_photo_url: user.cover_photo_url,
             is_org_owner: false,
+            locale: user.locale,
         }
     }
 }
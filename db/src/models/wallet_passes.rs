@@ -0,0 +1,332 @@
+use chrono::prelude::*;
+use diesel;
+use diesel::dsl;
+use diesel::expression::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Bool;
+use models::*;
+use schema::{assets, ticket_instances, ticket_types, wallet_pass_registrations, wallet_passes};
+use utils::errors::*;
+use utils::rand::random_alpha_string;
+use uuid::Uuid;
+
+const AUTHENTICATION_TOKEN_LENGTH: usize = 32;
+
+/// Ticket pass issued to a ticket holder's Apple Wallet or Google Pay app. The pass id doubles as
+/// the serial number the wallet apps use to identify the pass.
+#[derive(Clone, Debug, Deserialize, Identifiable, PartialEq, Queryable, Serialize)]
+pub struct WalletPass {
+    pub id: Uuid,
+    pub ticket_instance_id: Uuid,
+    pub wallet_id: Uuid,
+    pub pass_type: WalletPassTypes,
+    #[serde(skip_serializing)]
+    pub authentication_token: String,
+    pub voided_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "wallet_passes"]
+struct NewWalletPass {
+    ticket_instance_id: Uuid,
+    wallet_id: Uuid,
+    pass_type: WalletPassTypes,
+    authentication_token: String,
+}
+
+/// Device that has added a pass and asked to be notified when it changes
+#[derive(Clone, Debug, Deserialize, Identifiable, PartialEq, Queryable, Serialize)]
+pub struct WalletPassRegistration {
+    pub id: Uuid,
+    pub wallet_pass_id: Uuid,
+    pub device_library_identifier: String,
+    pub push_token: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "wallet_pass_registrations"]
+struct NewWalletPassRegistration {
+    wallet_pass_id: Uuid,
+    device_library_identifier: String,
+    push_token: String,
+}
+
+/// Everything printed on a pass, independent of the wallet app it is rendered for
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WalletPassDetails {
+    pub serial_number: Uuid,
+    pub pass_type: WalletPassTypes,
+    pub authentication_token: String,
+    pub voided: bool,
+    pub ticket_instance_id: Uuid,
+    pub ticket_type_name: String,
+    pub holder_name: Option<String>,
+    pub status: TicketInstanceStatus,
+    pub redeem_key: Option<String>,
+    pub barcode_rotation_seconds: Option<i32>,
+    pub event_id: Uuid,
+    pub event_name: String,
+    pub event_start: Option<NaiveDateTime>,
+    pub door_time: Option<NaiveDateTime>,
+    pub event_end: Option<NaiveDateTime>,
+    pub organization_name: String,
+    pub venue: Option<Venue>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl WalletPass {
+    /// Returns the current owner's pass for the ticket, issuing a new one if they don't have one yet
+    pub fn find_or_create_for_ticket(
+        ticket: &TicketInstance,
+        pass_type: WalletPassTypes,
+        conn: &PgConnection,
+    ) -> Result<WalletPass, DatabaseError> {
+        if ticket.status != TicketInstanceStatus::Purchased && ticket.status != TicketInstanceStatus::Redeemed {
+            return DatabaseError::business_process_error("Passes can only be issued for purchased tickets");
+        }
+
+        let existing_pass = wallet_passes::table
+            .filter(wallet_passes::ticket_instance_id.eq(ticket.id))
+            .filter(wallet_passes::wallet_id.eq(ticket.wallet_id))
+            .filter(wallet_passes::pass_type.eq(pass_type))
+            .filter(wallet_passes::voided_at.is_null())
+            .first(conn)
+            .optional()
+            .to_db_error(ErrorCode::QueryError, "Could not load wallet pass")?;
+        if let Some(pass) = existing_pass {
+            return Ok(pass);
+        }
+
+        diesel::insert_into(wallet_passes::table)
+            .values(NewWalletPass {
+                ticket_instance_id: ticket.id,
+                wallet_id: ticket.wallet_id,
+                pass_type,
+                authentication_token: random_alpha_string(AUTHENTICATION_TOKEN_LENGTH),
+            })
+            .get_result(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create wallet pass")
+    }
+
+    pub fn find(id: Uuid, conn: &PgConnection) -> Result<WalletPass, DatabaseError> {
+        wallet_passes::table
+            .find(id)
+            .first(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load wallet pass")
+    }
+
+    pub fn find_for_ticket(ticket_instance_id: Uuid, conn: &PgConnection) -> Result<Vec<WalletPass>, DatabaseError> {
+        wallet_passes::table
+            .filter(wallet_passes::ticket_instance_id.eq(ticket_instance_id))
+            .order_by(wallet_passes::created_at)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load wallet passes for ticket")
+    }
+
+    /// Wallet apps authenticate with the token embedded in the pass, a missing pass and a wrong
+    /// token are reported the same way so serial numbers can't be probed
+    pub fn find_authenticated(
+        serial_number: Uuid,
+        pass_type: WalletPassTypes,
+        authentication_token: &str,
+        conn: &PgConnection,
+    ) -> Result<Option<WalletPass>, DatabaseError> {
+        wallet_passes::table
+            .filter(wallet_passes::id.eq(serial_number))
+            .filter(wallet_passes::pass_type.eq(pass_type))
+            .filter(wallet_passes::authentication_token.eq(authentication_token))
+            .first(conn)
+            .optional()
+            .to_db_error(ErrorCode::QueryError, "Could not load wallet pass")
+    }
+
+    pub fn is_voided(&self) -> bool {
+        self.voided_at.is_some()
+    }
+
+    pub fn details(&self, conn: &PgConnection) -> Result<WalletPassDetails, DatabaseError> {
+        let ticket = TicketInstance::find(self.ticket_instance_id, conn)?;
+        let ticket_type = ticket.ticket_type(conn)?;
+        let event = ticket.event(conn)?;
+        let organization = event.organization(conn)?;
+        let venue = event.venue(conn)?;
+
+        let holder_name = if ticket.first_name_override.is_some() || ticket.last_name_override.is_some() {
+            Some(
+                vec![ticket.first_name_override.clone(), ticket.last_name_override.clone()]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            )
+        } else {
+            match Wallet::find(self.wallet_id, conn)?.user_id {
+                Some(user_id) => Some(User::find(user_id, conn)?.full_name().trim().to_string()),
+                None => None,
+            }
+        }
+        .filter(|name| !name.is_empty());
+
+        Ok(WalletPassDetails {
+            serial_number: self.id,
+            pass_type: self.pass_type,
+            authentication_token: self.authentication_token.clone(),
+            voided: self.is_voided(),
+            ticket_instance_id: ticket.id,
+            ticket_type_name: ticket_type.name,
+            holder_name,
+            status: ticket.status,
+            // The redeem key now belongs to the new owner so it is left off voided passes
+            redeem_key: if self.is_voided() { None } else { ticket.redeem_key },
+            barcode_rotation_seconds: event.barcode_rotation_seconds,
+            event_id: event.id,
+            event_name: event.name,
+            event_start: event.event_start,
+            door_time: event.door_time,
+            event_end: event.event_end,
+            organization_name: organization.name,
+            venue,
+            updated_at: self.updated_at,
+        })
+    }
+
+    /// Returns true if the device was not registered for the pass before
+    pub fn register_device(
+        &self,
+        device_library_identifier: &str,
+        push_token: &str,
+        conn: &PgConnection,
+    ) -> Result<bool, DatabaseError> {
+        let updated = diesel::update(
+            wallet_pass_registrations::table
+                .filter(wallet_pass_registrations::wallet_pass_id.eq(self.id))
+                .filter(wallet_pass_registrations::device_library_identifier.eq(device_library_identifier)),
+        )
+        .set((
+            wallet_pass_registrations::push_token.eq(push_token),
+            wallet_pass_registrations::updated_at.eq(dsl::now),
+        ))
+        .execute(conn)
+        .to_db_error(ErrorCode::UpdateError, "Could not update wallet pass registration")?;
+        if updated > 0 {
+            return Ok(false);
+        }
+
+        diesel::insert_into(wallet_pass_registrations::table)
+            .values(NewWalletPassRegistration {
+                wallet_pass_id: self.id,
+                device_library_identifier: device_library_identifier.to_string(),
+                push_token: push_token.to_string(),
+            })
+            .execute(conn)
+            .to_db_error(ErrorCode::InsertError, "Could not create wallet pass registration")?;
+        Ok(true)
+    }
+
+    pub fn unregister_device(&self, device_library_identifier: &str, conn: &PgConnection) -> Result<(), DatabaseError> {
+        diesel::delete(
+            wallet_pass_registrations::table
+                .filter(wallet_pass_registrations::wallet_pass_id.eq(self.id))
+                .filter(wallet_pass_registrations::device_library_identifier.eq(device_library_identifier)),
+        )
+        .execute(conn)
+        .to_db_error(ErrorCode::DeleteError, "Could not remove wallet pass registration")?;
+        Ok(())
+    }
+
+    pub fn registrations(&self, conn: &PgConnection) -> Result<Vec<WalletPassRegistration>, DatabaseError> {
+        wallet_pass_registrations::table
+            .filter(wallet_pass_registrations::wallet_pass_id.eq(self.id))
+            .order_by(wallet_pass_registrations::created_at)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load wallet pass registrations")
+    }
+
+    /// Passes registered on the device that changed after the given time
+    pub fn find_updated_for_device(
+        device_library_identifier: &str,
+        pass_type: WalletPassTypes,
+        updated_since: Option<NaiveDateTime>,
+        conn: &PgConnection,
+    ) -> Result<Vec<WalletPass>, DatabaseError> {
+        let mut query = wallet_passes::table
+            .inner_join(wallet_pass_registrations::table)
+            .filter(wallet_pass_registrations::device_library_identifier.eq(device_library_identifier))
+            .filter(wallet_passes::pass_type.eq(pass_type))
+            .select(wallet_passes::all_columns)
+            .into_boxed();
+        if let Some(updated_since) = updated_since {
+            query = query.filter(wallet_passes::updated_at.gt(updated_since));
+        }
+
+        query
+            .order_by(wallet_passes::updated_at)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load updated wallet passes")
+    }
+
+    /// Marks the live passes for the tickets as updated and schedules the wallet apps to be told.
+    /// Passes held by anyone other than the current owner, or for refunded tickets, are voided.
+    pub fn refresh_for_tickets(ticket_ids: &[Uuid], conn: &PgConnection) -> Result<(), DatabaseError> {
+        let pass_ids: Vec<Uuid> = wallet_passes::table
+            .filter(wallet_passes::ticket_instance_id.eq_any(ticket_ids))
+            .filter(wallet_passes::voided_at.is_null())
+            .select(wallet_passes::id)
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load wallet passes for tickets")?;
+        if pass_ids.is_empty() {
+            return Ok(());
+        }
+
+        diesel::update(
+            wallet_passes::table
+                .filter(wallet_passes::id.eq_any(&pass_ids))
+                .filter(sql::<Bool>(
+                    "EXISTS (
+                        SELECT 1 FROM ticket_instances ti
+                        WHERE ti.id = wallet_passes.ticket_instance_id
+                        AND (ti.wallet_id <> wallet_passes.wallet_id OR ti.status = 'Nullified')
+                    )",
+                )),
+        )
+        .set(wallet_passes::voided_at.eq(dsl::now.nullable()))
+        .execute(conn)
+        .to_db_error(ErrorCode::UpdateError, "Could not void wallet passes")?;
+
+        diesel::update(wallet_passes::table.filter(wallet_passes::id.eq_any(&pass_ids)))
+            .set(wallet_passes::updated_at.eq(dsl::now))
+            .execute(conn)
+            .to_db_error(ErrorCode::UpdateError, "Could not update wallet passes")?;
+
+        DomainAction::create(
+            None,
+            DomainActionTypes::UpdateWalletPasses,
+            None,
+            json!({ "wallet_pass_ids": pass_ids }),
+            Some(Tables::WalletPasses),
+            None,
+        )
+        .commit(conn)?;
+
+        Ok(())
+    }
+
+    pub fn refresh_for_event(event_id: Uuid, conn: &PgConnection) -> Result<(), DatabaseError> {
+        let ticket_ids: Vec<Uuid> = wallet_passes::table
+            .inner_join(ticket_instances::table)
+            .inner_join(assets::table.on(ticket_instances::asset_id.eq(assets::id)))
+            .inner_join(ticket_types::table.on(assets::ticket_type_id.eq(ticket_types::id)))
+            .filter(ticket_types::event_id.eq(event_id))
+            .filter(wallet_passes::voided_at.is_null())
+            .select(wallet_passes::ticket_instance_id)
+            .distinct()
+            .load(conn)
+            .to_db_error(ErrorCode::QueryError, "Could not load wallet passes for event")?;
+
+        WalletPass::refresh_for_tickets(&ticket_ids, conn)
+    }
+}
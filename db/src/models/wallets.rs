@@ -29,6 +29,14 @@ impl Wallet {
             .to_db_error(errors::ErrorCode::QueryError, "Could not find wallet")
     }
 
+    pub fn find_by_public_key(public_key: &str, conn: &PgConnection) -> Result<Option<Wallet>, DatabaseError> {
+        wallets::table
+            .filter(wallets::public_key.eq(public_key))
+            .first(conn)
+            .optional()
+            .to_db_error(errors::ErrorCode::QueryError, "Could not find wallet")
+    }
+
     pub fn create_for_user(
         user_id: Uuid,
         name: String,
@@ -1,15 +1,20 @@
 WITH r AS (SELECT t.id
            FROM ticket_instances AS t
                     INNER JOIN assets AS a ON t.asset_id = a.id
+                    INNER JOIN ticket_types AS tt ON a.ticket_type_id = tt.id
            WHERE ((t.reserved_until < now() AND t.status = 'Reserved') OR t.status = 'Available')
-             AND a.ticket_type_id = $2
+             -- Holds on a pooled ticket type are taken from the whole inventory pool
+             AND (a.ticket_type_id = $2 OR (tt.deleted_at IS NULL AND tt.inventory_pool_id =
+                                                                    (SELECT inventory_pool_id FROM ticket_types WHERE id = $2)))
              and t.parent_id is null
              AND coalesce($4, 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11') =
                  coalesce(t.hold_id, 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11') -- dummy guid
-           ORDER BY t.status, t.reserved_until -- Grab available tickets first, then old reserved
+           ORDER BY a.ticket_type_id <> $2, t.status, t.reserved_until -- Grab available tickets first, then old reserved
            LIMIT $3 FOR UPDATE OF t SKIP LOCKED)
 UPDATE ticket_instances
 SET hold_id    = $1,
+    -- Pooled tickets keep their asset and are held as the hold's ticket type
+    sold_as_ticket_type_id = $2,
     updated_at = now()
 FROM r
 WHERE ticket_instances.id = r.id RETURNING
@@ -0,0 +1,20 @@
+-- Commission earned per affiliate link and event, refunds are recorded as negative commissions
+SELECT al.id              AS affiliate_link_id,
+       al.name            AS affiliate_link_name,
+       al.code,
+       o.id               AS organization_id,
+       o.name             AS organization_name,
+       e.id               AS event_id,
+       e.name             AS event_name,
+       ac.currency,
+       CAST(COALESCE(SUM(ac.quantity), 0) AS BIGINT)                                                   AS tickets_sold,
+       CAST(COALESCE(SUM(ac.amount_in_cents), 0) AS BIGINT)                                            AS commission_in_cents,
+       CAST(COALESCE(SUM(ac.amount_in_cents) FILTER (WHERE ac.settlement_id IS NOT NULL), 0) AS BIGINT) AS settled_commission_in_cents
+FROM affiliate_links al
+         JOIN organizations o ON o.id = al.organization_id
+         LEFT JOIN affiliate_commissions ac ON ac.affiliate_link_id = al.id
+         LEFT JOIN events e ON e.id = ac.event_id
+WHERE al.user_id = $1
+  AND (al.deleted_at IS NULL OR ac.id IS NOT NULL)
+GROUP BY al.id, al.name, al.code, o.id, o.name, e.id, e.name, e.event_start, ac.currency
+ORDER BY al.name, e.event_start, e.name;
@@ -0,0 +1,16 @@
+-- Tickets taking up capacity per stage: sold, reserved in an unexpired cart or held for a hold or comp.
+-- Pooled tickets take up capacity on the stage of the ticket type they were sold or held as.
+SELECT tt.stage_id, COUNT(ti.id) AS used
+FROM ticket_types tt
+         LEFT JOIN (SELECT ti.id, COALESCE(ti.sold_as_ticket_type_id, a.ticket_type_id) AS ticket_type_id
+                    FROM ticket_instances ti
+                             INNER JOIN assets a ON a.id = ti.asset_id
+                             INNER JOIN ticket_types att ON att.id = a.ticket_type_id
+                    WHERE att.event_id = $1
+                      AND ti.parent_id IS NULL
+                      AND (ti.status IN ('Purchased', 'Redeemed')
+                        OR (ti.status = 'Reserved' AND ti.reserved_until >= now())
+                        OR (ti.status IN ('Available', 'Reserved') AND ti.hold_id IS NOT NULL))) ti
+                   ON ti.ticket_type_id = tt.id
+WHERE tt.event_id = $1
+GROUP BY tt.stage_id;
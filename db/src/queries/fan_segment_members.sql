@@ -0,0 +1,97 @@
+-- Fans of an organization matching every filter of a saved fan segment
+-- Parameters: $1 organization_id, $2 min_events_attended, $3 min_revenue_in_cents, $4 genre_id,
+-- $5 last interaction before, $6 venue_id, $7 venue_radius_in_km, $8 redemption_code, $9 limit, $10 offset
+WITH fan_orders AS (
+    SELECT COALESCE(o.on_behalf_of_user_id, o.user_id)                                                        AS user_id,
+           CAST(COUNT(DISTINCT o.id) AS BIGINT)                                                               AS order_count,
+           MIN(o.order_date)                                                                                  AS first_order_time,
+           MAX(o.order_date)                                                                                  AS last_order_time,
+           CAST(COALESCE(SUM(oi.unit_price_in_cents * (oi.quantity - oi.refunded_quantity)), 0) AS BIGINT) AS revenue_in_cents
+    FROM orders o
+             INNER JOIN order_items oi ON oi.order_id = o.id
+             INNER JOIN events e ON e.id = oi.event_id
+    WHERE e.organization_id = $1
+      AND o.status = 'Paid'
+    GROUP BY COALESCE(o.on_behalf_of_user_id, o.user_id)
+),
+     fan_attendance AS (
+         SELECT w.user_id,
+                COUNT(DISTINCT tt.event_id) AS events_attended
+         FROM ticket_instances ti
+                  INNER JOIN wallets w ON w.id = ti.wallet_id
+                  INNER JOIN assets a ON a.id = ti.asset_id
+                  INNER JOIN ticket_types tt ON tt.id = a.ticket_type_id
+                  INNER JOIN events e ON e.id = tt.event_id
+         WHERE e.organization_id = $1
+           AND ti.status = 'Redeemed'
+           AND w.user_id IS NOT NULL
+         GROUP BY w.user_id
+     ),
+     fan_events AS (
+         SELECT DISTINCT COALESCE(o.on_behalf_of_user_id, o.user_id) AS user_id,
+                         oi.event_id,
+                         oi.code_id
+         FROM orders o
+                  INNER JOIN order_items oi ON oi.order_id = o.id
+                  INNER JOIN events e ON e.id = oi.event_id
+         WHERE e.organization_id = $1
+           AND o.status = 'Paid'
+     )
+SELECT COUNT(*) OVER ()                                   AS total,
+       u.id                                               AS user_id,
+       u.first_name,
+       u.last_name,
+       u.email,
+       u.phone,
+       u.thumb_profile_pic_url,
+       oint.organization_id,
+       CAST(COALESCE(fo.order_count, 0) AS BIGINT)        AS order_count,
+       u.created_at,
+       fo.first_order_time,
+       fo.last_order_time,
+       CAST(COALESCE(fo.revenue_in_cents, 0) AS BIGINT)   AS revenue_in_cents,
+       oint.first_interaction                             AS first_interaction_time,
+       oint.last_interaction                              AS last_interaction_time
+FROM organization_interactions oint
+         INNER JOIN users u ON u.id = oint.user_id
+         LEFT JOIN fan_orders fo ON fo.user_id = u.id
+         LEFT JOIN fan_attendance fa ON fa.user_id = u.id
+WHERE oint.organization_id = $1
+  AND u.deleted_at IS NULL
+  AND ($2 IS NULL OR COALESCE(fa.events_attended, 0) >= $2)
+  AND ($3 IS NULL OR COALESCE(fo.revenue_in_cents, 0) >= $3)
+  AND ($4 IS NULL OR EXISTS(
+        SELECT 1
+        FROM fan_events fe
+                 INNER JOIN event_genres eg ON eg.event_id = fe.event_id
+        WHERE fe.user_id = u.id
+          AND eg.genre_id = $4
+    ))
+  AND ($5 IS NULL OR oint.last_interaction < $5)
+  AND ($6 IS NULL OR EXISTS(
+        SELECT 1
+        FROM fan_events fe
+                 INNER JOIN events e ON e.id = fe.event_id
+                 INNER JOIN venues v ON v.id = e.venue_id
+                 INNER JOIN venues target ON target.id = $6
+        WHERE fe.user_id = u.id
+          AND v.latitude IS NOT NULL
+          AND v.longitude IS NOT NULL
+          AND target.latitude IS NOT NULL
+          AND target.longitude IS NOT NULL
+          -- Haversine distance in kilometers
+          AND 6371 * 2 * ASIN(SQRT(
+                    POWER(SIN(RADIANS(v.latitude - target.latitude) / 2), 2) +
+                    COS(RADIANS(target.latitude)) * COS(RADIANS(v.latitude)) *
+                    POWER(SIN(RADIANS(v.longitude - target.longitude) / 2), 2)
+            )) <= COALESCE($7, 0)
+    ))
+  AND ($8 IS NULL OR EXISTS(
+        SELECT 1
+        FROM fan_events fe
+                 INNER JOIN codes c ON c.id = fe.code_id
+        WHERE fe.user_id = u.id
+          AND LOWER(c.redemption_code) = LOWER($8)
+    ))
+ORDER BY oint.last_interaction DESC, u.id
+LIMIT $9 OFFSET $10;
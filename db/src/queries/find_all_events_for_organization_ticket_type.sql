@@ -20,9 +20,10 @@ SELECT t2.event_id,
           AND oi.item_type = 'Tickets'
           AND o.status = 'Paid')                                                                                      as sales_total_in_cents
 FROM ticket_instances ti
-       INNER JOIN assets a
-       INNER JOIN ticket_types t2
-       INNER JOIN events e ON t2.event_id = e.id ON a.ticket_type_id = t2.id ON ti.asset_id = a.id
+       INNER JOIN assets a ON ti.asset_id = a.id
+       -- Pooled tickets count towards the ticket type they were sold or held as
+       INNER JOIN ticket_types t2 ON COALESCE(ti.sold_as_ticket_type_id, a.ticket_type_id) = t2.id
+       INNER JOIN events e ON t2.event_id = e.id
        LEFT JOIN refunded_tickets rt ON (ti.id = rt.ticket_instance_id AND rt.order_item_id = ti.order_item_id)
 WHERE e.organization_id = $1
   AND CASE
@@ -0,0 +1,29 @@
+-- Journal entries where debits and credits do not balance
+SELECT 'UnbalancedEntry' AS issue_type, je.source_table, je.source_id, je.id AS ledger_journal_entry_id,
+       'Debits of ' || COALESCE(SUM(ll.debit_in_cents), 0) || ' do not match credits of ' || COALESCE(SUM(ll.credit_in_cents), 0) AS details
+FROM ledger_journal_entries je
+LEFT JOIN ledger_lines ll ON ll.ledger_journal_entry_id = je.id
+GROUP BY je.id, je.source_table, je.source_id
+HAVING COALESCE(SUM(ll.debit_in_cents), 0) <> COALESCE(SUM(ll.credit_in_cents), 0) OR COUNT(ll.id) = 0
+UNION ALL
+-- Lines posted to an account belonging to another organization or currency
+SELECT DISTINCT 'AccountMismatch' AS issue_type, je.source_table, je.source_id, je.id AS ledger_journal_entry_id,
+       'Line posted to ' || la.account_type || ' account ' || la.id || ' of another organization or currency' AS details
+FROM ledger_journal_entries je
+JOIN ledger_lines ll ON ll.ledger_journal_entry_id = je.id
+JOIN ledger_accounts la ON la.id = ll.ledger_account_id
+WHERE la.organization_id <> je.organization_id OR la.currency <> je.currency
+UNION ALL
+-- Orders paid since the ledger was introduced without a journal entry
+SELECT 'MissingOrderEntry' AS issue_type, 'Orders' AS source_table, o.id AS source_id, NULL AS ledger_journal_entry_id,
+       'Paid order totalling ' || SUM(oi.unit_price_in_cents * oi.quantity) || ' has no journal entry' AS details
+FROM orders o
+JOIN order_items oi ON oi.order_id = o.id
+WHERE o.status = 'Paid'
+AND o.paid_at >= (SELECT MIN(created_at) FROM ledger_journal_entries)
+AND NOT EXISTS (
+    SELECT 1 FROM ledger_journal_entries je WHERE je.source_table = 'Orders' AND je.source_id = o.id
+)
+GROUP BY o.id
+HAVING SUM(oi.unit_price_in_cents * oi.quantity) <> 0
+ORDER BY issue_type, source_id;
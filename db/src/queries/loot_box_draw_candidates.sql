@@ -0,0 +1,17 @@
+-- Contents still waiting inside unopened boxes of the loot box ticket type, ordered so the draw is reproducible
+SELECT ti.id,
+       ti.parent_id,
+       tt.event_id,
+       tt.id    AS ticket_type_id,
+       r.rank   AS rarity_rank
+FROM ticket_instances ti
+         INNER JOIN ticket_instances parent ON ti.parent_id = parent.id
+         INNER JOIN assets parent_asset ON parent.asset_id = parent_asset.id
+         INNER JOIN assets a ON ti.asset_id = a.id
+         INNER JOIN ticket_types tt ON a.ticket_type_id = tt.id
+         LEFT JOIN rarities r ON tt.rarity_id = r.id
+WHERE parent_asset.ticket_type_id = $1
+  AND parent.status NOT IN ('Redeemed', 'Nullified')
+  AND ti.status IN ('Available', 'Reserved')
+ORDER BY r.rank NULLS FIRST, ti.id
+FOR UPDATE OF ti;
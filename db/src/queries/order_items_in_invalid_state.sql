@@ -4,6 +4,9 @@ LEFT JOIN holds h ON oi.hold_id = h.id
 LEFT JOIN ticket_instances ti ON ti.order_item_id = oi.id
 LEFT JOIN codes c ON oi.code_id = c.id
 LEFT JOIN refunded_tickets rt ON oi.id = rt.order_item_id
+LEFT JOIN product_variants pv ON oi.product_variant_id = pv.id
+LEFT JOIN products p ON pv.product_id = p.id
+LEFT JOIN packages pk ON oi.package_id = pk.id
 LEFT JOIN (
     SELECT count(ti.id) as count, oi.id
     FROM order_items oi
@@ -11,12 +14,38 @@ LEFT JOIN (
     WHERE oi.order_id = $1
     GROUP BY oi.id
 ) oit on oit.id = oi.id
+LEFT JOIN (
+    -- Add-on quantity held by other paid orders and unexpired carts
+    SELECT oi.product_variant_id, SUM(oi.quantity - oi.refunded_quantity) as quantity
+    FROM order_items oi
+    JOIN orders o ON o.id = oi.order_id
+    WHERE oi.item_type = 'AddOns'
+    AND o.id <> $1
+    AND (
+        o.status = 'Paid'
+        OR (o.status IN ('Draft', 'PendingPayment') AND o.expires_at > now())
+    )
+    GROUP BY oi.product_variant_id
+) pvs on pvs.product_variant_id = oi.product_variant_id
 WHERE oi.order_id = $1
-AND item_type = 'Tickets'
 AND (
-    ti.status = 'Nullified'
-    OR ti.reserved_until < now()
-    OR c.end_date < now()
-    OR h.end_at < now()
-    OR oit.count <> oi.quantity
+    (
+        item_type = 'Tickets'
+        AND (
+            ti.status = 'Nullified'
+            OR ti.reserved_until < now()
+            OR c.end_date < now()
+            OR h.end_at < now()
+            OR oit.count <> oi.quantity
+            OR pk.deleted_at IS NOT NULL
+        )
+    )
+    OR (
+        item_type = 'AddOns'
+        AND (
+            pv.deleted_at IS NOT NULL
+            OR p.deleted_at IS NOT NULL
+            OR COALESCE(pvs.quantity, 0) + oi.quantity > pv.inventory
+        )
+    )
 )
@@ -10,6 +10,8 @@ SET order_item_id  = NULL,
     reserved_until = NULL,
     redeem_key     = NULL,
     status         = $5,
+    -- Held tickets go back to the hold's ticket type, other pooled tickets go back to the pool
+    sold_as_ticket_type_id = (SELECT h.ticket_type_id FROM holds h WHERE h.id = ticket_instances.hold_id),
     updated_at     = now()
 FROM cte
 WHERE cte.id = ticket_instances.id RETURNING ticket_instances.*;
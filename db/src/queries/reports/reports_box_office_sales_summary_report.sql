@@ -4,6 +4,7 @@ SELECT
   entries.event_name,
   entries.event_date,
   entries.external_payment_type,
+  entries.currency,
   CAST(SUM(entries.number_of_tickets) AS BIGINT) as number_of_tickets,
   entries.face_value_in_cents,
   entries.revenue_share_value_in_cents,
@@ -16,6 +17,7 @@ FROM (
     e.event_start as event_date,
     -- If set to false, the logic does not group on external payment type allowing the collection to reflect box office entries
     CASE WHEN $4 THEN o.external_payment_type ELSE null END as external_payment_type,
+    o.currency,
     CAST(SUM(oi.quantity - oi.refunded_quantity) FILTER (WHERE oi.item_type = 'Tickets') AS BIGINT) as number_of_tickets,
     CASE oi.item_type WHEN 'EventFees' THEN 0 ELSE CAST(oi.unit_price_in_cents + COALESCE(oi_promo_code.unit_price_in_cents, 0) AS BIGINT) END as face_value_in_cents,
     -- Event fees record list the fee as part of the revenue share for that item with 0 face value
@@ -47,6 +49,7 @@ FROM (
     event_name,
     event_date,
     o.external_payment_type,
+    o.currency,
     oi.item_type,
     oi.unit_price_in_cents,
     oi.client_fee_in_cents,
@@ -59,6 +62,7 @@ GROUP BY
   entries.event_name,
   entries.event_date,
   entries.external_payment_type,
+  entries.currency,
   entries.face_value_in_cents,
   entries.revenue_share_value_in_cents
 ORDER BY
@@ -66,5 +70,6 @@ ORDER BY
   entries.operator_id,
   entries.event_date,
   entries.event_name,
+  entries.currency,
   entries.face_value_in_cents,
   entries.revenue_share_value_in_cents;
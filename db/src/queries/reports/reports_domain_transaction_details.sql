@@ -7,6 +7,7 @@ SELECT COUNT(*) OVER ()
         ,e.event_start                                                                                                      AS event_date
         ,tt.name                                                                                                            AS ticket_type_name
         ,o.paid_at                                                                                                          AS transaction_date
+        ,o.currency                                                                                                         AS currency
         ,o.platform                                                                                                         AS point_of_sale
         ,p.payment_method                                                                                                   AS payment_method
         ,oi_tickets.quantity                                                                                                AS qty_tickets_sold
@@ -0,0 +1,43 @@
+-- Ticket sales for each occurrence of an event series, one row per ticket type
+SELECT
+  e.id                                                       AS event_id,
+  e.name                                                     AS event_name,
+  e.event_start,
+  eso.occurrence_start,
+  tt.id                                                      AS ticket_type_id,
+  tt.name                                                    AS ticket_type_name,
+  CAST(COALESCE(sales.sold_count, 0) AS BIGINT)              AS sold_count,
+  CAST(COALESCE(sales.comp_count, 0) AS BIGINT)              AS comp_count,
+  CAST(COALESCE(sales.sales_total_in_cents, 0) AS BIGINT)    AS sales_total_in_cents,
+  CAST(COALESCE(redemptions.redeemed_count, 0) AS BIGINT)    AS redeemed_count
+FROM event_series_occurrences eso
+JOIN events e ON e.id = eso.event_id
+JOIN ticket_types tt ON tt.event_id = e.id
+LEFT JOIN (
+  SELECT
+    oi.ticket_type_id,
+    SUM(oi.quantity - oi.refunded_quantity) FILTER (WHERE h.hold_type IS DISTINCT FROM 'Comp')       AS sold_count,
+    SUM(oi.quantity - oi.refunded_quantity) FILTER (WHERE h.hold_type = 'Comp')                      AS comp_count,
+    SUM((oi.unit_price_in_cents + COALESCE(d.unit_price_in_cents, 0)) * (oi.quantity - oi.refunded_quantity)) AS sales_total_in_cents
+  FROM order_items oi
+  JOIN orders o ON o.id = oi.order_id
+  LEFT JOIN holds h ON h.id = oi.hold_id
+  LEFT JOIN order_items d ON d.parent_id = oi.id AND d.item_type = 'Discount'
+  WHERE oi.item_type = 'Tickets'
+  AND o.status = 'Paid'
+  AND oi.event_id IN (SELECT event_id FROM event_series_occurrences WHERE event_series_id = $1)
+  GROUP BY oi.ticket_type_id
+) sales ON sales.ticket_type_id = tt.id
+LEFT JOIN (
+  SELECT rtt.id AS ticket_type_id, COUNT(ti.id) AS redeemed_count
+  FROM ticket_instances ti
+  JOIN assets a ON a.id = ti.asset_id
+  JOIN ticket_types rtt ON rtt.id = COALESCE(ti.sold_as_ticket_type_id, a.ticket_type_id)
+  WHERE ti.status = 'Redeemed'
+  AND rtt.event_id IN (SELECT event_id FROM event_series_occurrences WHERE event_series_id = $1)
+  GROUP BY rtt.id
+) redemptions ON redemptions.ticket_type_id = tt.id
+WHERE eso.event_series_id = $1
+AND e.deleted_at IS NULL
+AND tt.deleted_at IS NULL
+ORDER BY eso.occurrence_start, tt.rank, tt.name;
@@ -4,8 +4,14 @@ SELECT
   CAST(COALESCE(COUNT(DISTINCT ti.id) FILTER(WHERE ti.status = 'Redeemed'), 0) AS BIGINT)   AS scanned_count,
   CAST(COALESCE(COUNT(DISTINCT ti.id) FILTER(WHERE ti.status = 'Purchased'), 0) AS BIGINT)  AS not_scanned_count
 FROM ticket_types tt
-JOIN assets a ON tt.id = a.ticket_type_id
-LEFT JOIN ticket_instances ti ON a.id = ti.asset_id
+LEFT JOIN (
+  -- Pooled tickets count towards the ticket type they were sold as
+  SELECT ti.*, COALESCE(ti.sold_as_ticket_type_id, a.ticket_type_id) AS ticket_type_id
+  FROM ticket_instances ti
+  JOIN assets a ON a.id = ti.asset_id
+  JOIN ticket_types att ON att.id = a.ticket_type_id
+  WHERE att.event_id = $1
+) ti ON ti.ticket_type_id = tt.id
 -- Confirm this isn't a refunded redeemed (they keep their redeemed status and order association unlike normal refunds)
 LEFT JOIN refunded_tickets rt ON rt.ticket_instance_id = ti.id AND ti.order_item_id = rt.order_item_id
 WHERE tt.event_id = $1
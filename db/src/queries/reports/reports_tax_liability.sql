@@ -0,0 +1,54 @@
+-- Tax is reported in the period it was collected, refunds of tax are reported in the period they were refunded
+SELECT
+  tr.id                                                             AS tax_rule_id,
+  tr.jurisdiction,
+  tr.name                                                           AS tax_name,
+  tr.rate_percent,
+  tr.inclusive,
+  t.period_start,
+  t.currency,
+  CAST(COALESCE(SUM(t.taxable_sales_in_cents), 0) AS BIGINT)        AS taxable_sales_in_cents,
+  CAST(COALESCE(SUM(t.refunded_taxable_sales_in_cents), 0) AS BIGINT) AS refunded_taxable_sales_in_cents,
+  CAST(COALESCE(SUM(t.tax_collected_in_cents), 0) AS BIGINT)        AS tax_collected_in_cents,
+  CAST(COALESCE(SUM(t.tax_refunded_in_cents), 0) AS BIGINT)         AS tax_refunded_in_cents,
+  CAST(COALESCE(SUM(t.tax_collected_in_cents), 0) - COALESCE(SUM(t.tax_refunded_in_cents), 0) AS BIGINT) AS net_tax_in_cents
+FROM (
+  SELECT
+    oi.tax_rule_id,
+    date_trunc($4, o.paid_at)                                                              AS period_start,
+    o.currency,
+    (p.unit_price_in_cents + COALESCE(d.unit_price_in_cents, 0)) * oi.quantity             AS taxable_sales_in_cents,
+    0                                                                                      AS refunded_taxable_sales_in_cents,
+    oi.tax_in_cents * oi.quantity                                                          AS tax_collected_in_cents,
+    0                                                                                      AS tax_refunded_in_cents
+  FROM order_items oi
+  JOIN orders o ON oi.order_id = o.id
+  JOIN order_items p ON oi.parent_id = p.id
+  LEFT JOIN order_items d ON d.parent_id = p.id AND d.item_type = 'Discount'
+  WHERE oi.item_type = 'Tax'
+  AND o.paid_at IS NOT NULL
+  AND ($2 IS NULL OR o.paid_at >= $2)
+  AND ($3 IS NULL OR o.paid_at <= $3)
+  UNION ALL
+  SELECT
+    oi.tax_rule_id,
+    date_trunc($4, r.created_at)                                                           AS period_start,
+    o.currency,
+    0                                                                                      AS taxable_sales_in_cents,
+    (p.unit_price_in_cents + COALESCE(d.unit_price_in_cents, 0)) * ri.quantity             AS refunded_taxable_sales_in_cents,
+    0                                                                                      AS tax_collected_in_cents,
+    oi.tax_in_cents * ri.quantity                                                          AS tax_refunded_in_cents
+  FROM refund_items ri
+  JOIN refunds r ON ri.refund_id = r.id
+  JOIN order_items oi ON ri.order_item_id = oi.id
+  JOIN orders o ON oi.order_id = o.id
+  JOIN order_items p ON oi.parent_id = p.id
+  LEFT JOIN order_items d ON d.parent_id = p.id AND d.item_type = 'Discount'
+  WHERE oi.item_type = 'Tax'
+  AND ($2 IS NULL OR r.created_at >= $2)
+  AND ($3 IS NULL OR r.created_at <= $3)
+) t
+JOIN tax_rules tr ON t.tax_rule_id = tr.id
+WHERE tr.organization_id = $1
+GROUP BY tr.id, tr.jurisdiction, tr.name, tr.rate_percent, tr.inclusive, t.period_start, t.currency
+ORDER BY tr.jurisdiction, t.period_start, tr.name, t.currency;
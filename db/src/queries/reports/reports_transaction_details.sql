@@ -26,6 +26,7 @@ SELECT COUNT(*) OVER ()
             COALESCE(oi_event_fees.refunded_quantity, 0)) AS BIGINT)                                  AS event_fee_client_in_cents_total,
     oi_fees.fee_schedule_range_id                                                                      AS fee_range_id,
     o.paid_at                                                                                          AS transaction_date,
+    o.currency,
     o.order_type,
     p.payment_method,
     p.payment_provider,
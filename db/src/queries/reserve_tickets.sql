@@ -1,11 +1,15 @@
 WITH r AS (SELECT t.id
            FROM ticket_instances AS t
                     INNER JOIN assets AS a ON t.asset_id = a.id
+                    INNER JOIN ticket_types AS tt ON a.ticket_type_id = tt.id
            WHERE ((t.reserved_until < now() AND t.status = 'Reserved') OR t.status = 'Available')
-             AND a.ticket_type_id = $3
+             -- Ticket types sharing an inventory pool sell from each other's tickets
+             AND (a.ticket_type_id = $3 OR (tt.deleted_at IS NULL AND tt.inventory_pool_id =
+                                                                    (SELECT inventory_pool_id FROM ticket_types WHERE id = $3)))
              and t.parent_id is null
              AND coalesce($4, 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11') =
                  coalesce(t.hold_id, 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11') -- dummy guid
+           ORDER BY a.ticket_type_id <> $3 -- Grab the ticket type's own tickets first
            LIMIT $5 FOR UPDATE OF t SKIP LOCKED)
 
 UPDATE ticket_instances
@@ -13,6 +17,8 @@ UPDATE ticket_instances
 SET order_item_id  = $1,
     reserved_until = $2,
     status         = 'Reserved',
+    -- Pooled tickets keep their asset, the ticket type being bought is recorded for attribution
+    sold_as_ticket_type_id = $3,
     updated_at     = now()
 FROM r
 WHERE ticket_instances.id = r.id RETURNING ticket_instances.*;
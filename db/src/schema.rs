@@ -1,3 +1,81 @@
+table! {
+    access_zone_ticket_types (id) {
+        id -> Uuid,
+        access_zone_id -> Uuid,
+        ticket_type_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    access_zones (id) {
+        id -> Uuid,
+        event_id -> Uuid,
+        name -> Text,
+        re_entry_policy -> Text,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    accounting_account_mappings (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        account_type -> Text,
+        account_code -> Text,
+        account_name -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    affiliate_commission_rules (id) {
+        id -> Uuid,
+        affiliate_link_id -> Uuid,
+        ticket_type_id -> Nullable<Uuid>,
+        commission_in_cents -> Nullable<Int8>,
+        commission_as_percentage -> Nullable<Int8>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    affiliate_commissions (id) {
+        id -> Uuid,
+        affiliate_link_id -> Uuid,
+        order_id -> Uuid,
+        order_item_id -> Uuid,
+        event_id -> Uuid,
+        refund_id -> Nullable<Uuid>,
+        settlement_id -> Nullable<Uuid>,
+        quantity -> Int8,
+        amount_in_cents -> Int8,
+        currency -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    affiliate_links (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        user_id -> Uuid,
+        event_id -> Nullable<Uuid>,
+        name -> Text,
+        code -> Text,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        visit_count -> Int8,
+    }
+}
+
 table! {
     analytics_page_views (id) {
         id -> Uuid,
@@ -78,6 +156,21 @@ table! {
     }
 }
 
+table! {
+    asset_sync_issues (id) {
+        id -> Uuid,
+        asset_id -> Uuid,
+        ticket_instance_id -> Nullable<Uuid>,
+        token_id -> Nullable<Int8>,
+        issue_type -> Text,
+        database_value -> Nullable<Text>,
+        blockchain_value -> Nullable<Text>,
+        repaired -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     assets (id) {
         id -> Uuid,
@@ -87,6 +180,8 @@ table! {
         status -> Text,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        last_synced_at -> Nullable<Timestamp>,
+        sync_error -> Nullable<Text>,
     }
 }
 
@@ -108,6 +203,67 @@ table! {
         subject -> Nullable<Text>,
         audience -> Varchar,
         preview_email -> Nullable<Text>,
+        fan_segment_id -> Nullable<Uuid>,
+    }
+}
+
+table! {
+    cash_drawer_movements (id) {
+        id -> Uuid,
+        cash_drawer_session_id -> Uuid,
+        movement_type -> Text,
+        amount_in_cents -> Int8,
+        reason -> Nullable<Text>,
+        created_by_user_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    cash_drawer_sessions (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        user_id -> Uuid,
+        status -> Text,
+        opening_float_in_cents -> Int8,
+        expected_cash_in_cents -> Nullable<Int8>,
+        counted_cash_in_cents -> Nullable<Int8>,
+        closing_notes -> Nullable<Text>,
+        closed_by_user_id -> Nullable<Uuid>,
+        closed_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    checkout_answers (id) {
+        id -> Uuid,
+        checkout_question_id -> Uuid,
+        order_id -> Uuid,
+        ticket_instance_id -> Nullable<Uuid>,
+        answer -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    checkout_questions (id) {
+        id -> Uuid,
+        event_id -> Uuid,
+        ticket_type_id -> Nullable<Uuid>,
+        question -> Text,
+        question_type -> Text,
+        answer_scope -> Text,
+        options -> Array<Text>,
+        required -> Bool,
+        rank -> Int4,
+        editable_until -> Nullable<Timestamp>,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -261,6 +417,31 @@ table! {
     }
 }
 
+table! {
+    event_series (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        template_event_id -> Uuid,
+        name -> Text,
+        recurrence_rule -> Text,
+        generated_until -> Nullable<Timestamp>,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    event_series_occurrences (id) {
+        id -> Uuid,
+        event_series_id -> Uuid,
+        event_id -> Uuid,
+        occurrence_start -> Timestamp,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     event_users (id) {
         id -> Uuid,
@@ -305,6 +486,9 @@ table! {
         settled_at -> Nullable<Timestamp>,
         cloned_from_event_id -> Nullable<Uuid>,
         additional_json -> Jsonb,
+        currency -> Nullable<Text>,
+        barcode_rotation_seconds -> Nullable<Int4>,
+        capacity -> Nullable<Int8>,
     }
 }
 
@@ -322,6 +506,25 @@ table! {
     }
 }
 
+table! {
+    fan_segments (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        name -> Text,
+        min_events_attended -> Nullable<Int4>,
+        min_revenue_in_cents -> Nullable<Int8>,
+        genre_id -> Nullable<Uuid>,
+        inactive_days -> Nullable<Int4>,
+        venue_id -> Nullable<Uuid>,
+        venue_radius_in_km -> Nullable<Float8>,
+        redemption_code -> Nullable<Text>,
+        sendgrid_list_id -> Nullable<Int8>,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     fee_schedule_ranges (id) {
         id -> Uuid,
@@ -343,6 +546,7 @@ table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         organization_id -> Uuid,
+        currency -> Text,
     }
 }
 
@@ -375,6 +579,53 @@ table! {
     }
 }
 
+table! {
+    inventory_pools (id) {
+        id -> Uuid,
+        event_id -> Uuid,
+        name -> Text,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    ledger_accounts (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        account_type -> Text,
+        currency -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    ledger_journal_entries (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        currency -> Text,
+        source_table -> Text,
+        source_id -> Uuid,
+        description -> Text,
+        reverses_ledger_journal_entry_id -> Nullable<Uuid>,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    ledger_lines (id) {
+        id -> Uuid,
+        ledger_journal_entry_id -> Uuid,
+        ledger_account_id -> Uuid,
+        debit_in_cents -> Int8,
+        credit_in_cents -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
 table! {
     listings (id) {
         id -> Uuid,
@@ -403,6 +654,22 @@ table! {
     }
 }
 
+table! {
+    loot_box_draws (id) {
+        id -> Uuid,
+        ticket_instance_id -> Uuid,
+        server_seed -> Text,
+        server_seed_hash -> Text,
+        client_seed -> Nullable<Text>,
+        candidate_ticket_instance_ids -> Array<Uuid>,
+        drawn_ticket_instance_ids -> Array<Uuid>,
+        opened_by_user_id -> Nullable<Uuid>,
+        opened_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     marketplace_accounts (id) {
         id -> Uuid,
@@ -450,6 +717,11 @@ table! {
         company_fee_in_cents -> Int8,
         client_fee_in_cents -> Int8,
         refunded_quantity -> Int8,
+        product_variant_id -> Nullable<Uuid>,
+        redeemed_quantity -> Int8,
+        package_id -> Nullable<Uuid>,
+        tax_rule_id -> Nullable<Uuid>,
+        tax_in_cents -> Int8,
     }
 }
 
@@ -491,6 +763,42 @@ table! {
         platform -> Nullable<Text>,
         settlement_id -> Nullable<Uuid>,
         referrer -> Nullable<Text>,
+        currency -> Text,
+        partner_id -> Nullable<Uuid>,
+        affiliate_link_id -> Nullable<Uuid>,
+    }
+}
+
+table! {
+    organization_bank_accounts (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        payout_method -> Text,
+        account_holder_name -> Text,
+        routing_number -> Nullable<Text>,
+        account_number -> Nullable<Text>,
+        iban -> Nullable<Text>,
+        bic -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    organization_brandings (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        custom_domain -> Nullable<Text>,
+        logo_url -> Nullable<Text>,
+        primary_color -> Nullable<Text>,
+        secondary_color -> Nullable<Text>,
+        sender_email -> Nullable<Text>,
+        sender_name -> Nullable<Text>,
+        support_email -> Nullable<Text>,
+        support_phone -> Nullable<Text>,
+        support_url -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -575,6 +883,55 @@ table! {
         slug_id -> Nullable<Uuid>,
         google_ads_conversion_id -> Nullable<Text>,
         google_ads_conversion_labels -> Array<Text>,
+        currency -> Text,
+        default_locale -> Text,
+    }
+}
+
+table! {
+    package_ticket_types (id) {
+        id -> Uuid,
+        package_id -> Uuid,
+        ticket_type_id -> Uuid,
+        quantity -> Int8,
+        price_in_cents -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    packages (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        name -> Text,
+        description -> Nullable<Text>,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    partner_guests (id) {
+        id -> Uuid,
+        partner_id -> Uuid,
+        user_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    partners (id) {
+        id -> Uuid,
+        name -> Text,
+        api_key_hash -> Nullable<Text>,
+        allowed_origins -> Array<Text>,
+        commission_rate_percent -> Float4,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -606,6 +963,35 @@ table! {
         updated_at -> Timestamp,
         url_nonce -> Nullable<Text>,
         refund_id -> Nullable<Uuid>,
+        cash_drawer_session_id -> Nullable<Uuid>,
+    }
+}
+
+table! {
+    product_variants (id) {
+        id -> Uuid,
+        product_id -> Uuid,
+        name -> Text,
+        price_in_cents -> Nullable<Int8>,
+        inventory -> Int8,
+        rank -> Int4,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    products (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        event_id -> Nullable<Uuid>,
+        name -> Text,
+        description -> Nullable<Text>,
+        price_in_cents -> Int8,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -704,6 +1090,57 @@ table! {
         settlement_entry_type -> Text,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        product_variant_id -> Nullable<Uuid>,
+        currency -> Text,
+        partner_id -> Nullable<Uuid>,
+        affiliate_link_id -> Nullable<Uuid>,
+    }
+}
+
+table! {
+    settlement_payout_batches (id) {
+        id -> Uuid,
+        payout_method -> Text,
+        created_by_user_id -> Nullable<Uuid>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    settlement_payouts (id) {
+        id -> Uuid,
+        settlement_id -> Uuid,
+        organization_bank_account_id -> Uuid,
+        payout_method -> Text,
+        amount_in_cents -> Int8,
+        currency -> Text,
+        status -> Text,
+        note -> Nullable<Text>,
+        settlement_payout_batch_id -> Nullable<Uuid>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    settlement_statement_subscribers (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        user_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    settlement_statements (id) {
+        id -> Uuid,
+        settlement_id -> Uuid,
+        csv_content -> Text,
+        pdf_content -> Bytea,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -757,6 +1194,22 @@ table! {
     }
 }
 
+table! {
+    tax_rules (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        venue_id -> Nullable<Uuid>,
+        name -> Text,
+        jurisdiction -> Text,
+        rate_percent -> Float4,
+        inclusive -> Bool,
+        fees_taxable -> Bool,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     temporary_user_links (temporary_user_id, user_id) {
         temporary_user_id -> Uuid,
@@ -796,6 +1249,9 @@ table! {
         check_in_source -> Nullable<Text>,
         parent_id -> Nullable<Uuid>,
         listing_id -> Nullable<Uuid>,
+        trade_id -> Nullable<Uuid>,
+        redeem_secret -> Nullable<Text>,
+        sold_as_ticket_type_id -> Nullable<Uuid>,
     }
 }
 
@@ -815,6 +1271,24 @@ table! {
     }
 }
 
+table! {
+    ticket_scans (id) {
+        id -> Uuid,
+        event_id -> Uuid,
+        ticket_instance_id -> Nullable<Uuid>,
+        access_zone_id -> Nullable<Uuid>,
+        direction -> Text,
+        result -> Text,
+        rejection_reason -> Nullable<Text>,
+        redeem_key -> Nullable<Text>,
+        scanned_by_user_id -> Uuid,
+        device_id -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        seq -> Int8,
+    }
+}
+
 table! {
     ticket_type_codes (id) {
         id -> Uuid,
@@ -853,6 +1327,37 @@ table! {
         ticket_type_type -> Varchar,
         promo_image_url -> Nullable<Text>,
         content_url -> Nullable<Text>,
+        stage_id -> Nullable<Uuid>,
+        inventory_pool_id -> Nullable<Uuid>,
+    }
+}
+
+table! {
+    trade_items (id) {
+        id -> Uuid,
+        trade_id -> Uuid,
+        ticket_instance_id -> Uuid,
+        from_user_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    trades (id) {
+        id -> Uuid,
+        proposed_by_user_id -> Uuid,
+        recipient_user_id -> Uuid,
+        status -> Text,
+        message -> Nullable<Text>,
+        cash_in_cents -> Int8,
+        currency -> Text,
+        payment_provider -> Nullable<Text>,
+        external_payment_reference -> Nullable<Text>,
+        expires_at -> Timestamp,
+        responded_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -883,6 +1388,20 @@ table! {
     }
 }
 
+table! {
+    user_credit_entries (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        amount_in_cents -> Int8,
+        currency -> Text,
+        source_table -> Text,
+        source_id -> Uuid,
+        description -> Text,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamp,
+    }
+}
+
 table! {
     user_genres (id) {
         id -> Uuid,
@@ -916,6 +1435,7 @@ table! {
         accepted_terms_date -> Nullable<Timestamp>,
         invited_at -> Nullable<Timestamp>,
         deleted_at -> Nullable<Timestamp>,
+        locale -> Nullable<Text>,
     }
 }
 
@@ -942,6 +1462,30 @@ table! {
     }
 }
 
+table! {
+    wallet_pass_registrations (id) {
+        id -> Uuid,
+        wallet_pass_id -> Uuid,
+        device_library_identifier -> Text,
+        push_token -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    wallet_passes (id) {
+        id -> Uuid,
+        ticket_instance_id -> Uuid,
+        wallet_id -> Uuid,
+        pass_type -> Text,
+        authentication_token -> Text,
+        voided_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 table! {
     wallets (id) {
         id -> Uuid,
@@ -956,6 +1500,21 @@ table! {
     }
 }
 
+joinable!(access_zone_ticket_types -> access_zones (access_zone_id));
+joinable!(access_zone_ticket_types -> ticket_types (ticket_type_id));
+joinable!(access_zones -> events (event_id));
+joinable!(accounting_account_mappings -> organizations (organization_id));
+joinable!(affiliate_commission_rules -> affiliate_links (affiliate_link_id));
+joinable!(affiliate_commission_rules -> ticket_types (ticket_type_id));
+joinable!(affiliate_commissions -> affiliate_links (affiliate_link_id));
+joinable!(affiliate_commissions -> events (event_id));
+joinable!(affiliate_commissions -> order_items (order_item_id));
+joinable!(affiliate_commissions -> orders (order_id));
+joinable!(affiliate_commissions -> refunds (refund_id));
+joinable!(affiliate_commissions -> settlements (settlement_id));
+joinable!(affiliate_links -> events (event_id));
+joinable!(affiliate_links -> organizations (organization_id));
+joinable!(affiliate_links -> users (user_id));
 joinable!(announcement_engagements -> announcements (announcement_id));
 joinable!(announcement_engagements -> users (user_id));
 joinable!(announcements -> organizations (organization_id));
@@ -963,8 +1522,19 @@ joinable!(artist_genres -> artists (artist_id));
 joinable!(artist_genres -> genres (genre_id));
 joinable!(artists -> genres (main_genre_id));
 joinable!(artists -> organizations (organization_id));
+joinable!(asset_sync_issues -> assets (asset_id));
+joinable!(asset_sync_issues -> ticket_instances (ticket_instance_id));
 joinable!(assets -> ticket_types (ticket_type_id));
 joinable!(broadcasts -> events (event_id));
+joinable!(broadcasts -> fan_segments (fan_segment_id));
+joinable!(cash_drawer_movements -> cash_drawer_sessions (cash_drawer_session_id));
+joinable!(cash_drawer_movements -> users (created_by_user_id));
+joinable!(cash_drawer_sessions -> organizations (organization_id));
+joinable!(checkout_answers -> checkout_questions (checkout_question_id));
+joinable!(checkout_answers -> orders (order_id));
+joinable!(checkout_answers -> ticket_instances (ticket_instance_id));
+joinable!(checkout_questions -> events (event_id));
+joinable!(checkout_questions -> ticket_types (ticket_type_id));
 joinable!(codes -> events (event_id));
 joinable!(collection_items -> collections (collection_id));
 joinable!(collection_items -> ticket_types (collectible_id));
@@ -984,27 +1554,49 @@ joinable!(event_genres -> genres (genre_id));
 joinable!(event_interest -> events (event_id));
 joinable!(event_interest -> users (user_id));
 joinable!(event_report_subscribers -> events (event_id));
+joinable!(event_series -> events (template_event_id));
+joinable!(event_series -> organizations (organization_id));
+joinable!(event_series_occurrences -> event_series (event_series_id));
+joinable!(event_series_occurrences -> events (event_id));
 joinable!(event_users -> events (event_id));
 joinable!(event_users -> users (user_id));
 joinable!(events -> organizations (organization_id));
 joinable!(events -> venues (venue_id));
 joinable!(external_logins -> users (user_id));
+joinable!(fan_segments -> genres (genre_id));
+joinable!(fan_segments -> organizations (organization_id));
+joinable!(fan_segments -> venues (venue_id));
 joinable!(fee_schedule_ranges -> fee_schedules (fee_schedule_id));
 joinable!(holds -> events (event_id));
 joinable!(holds -> ticket_types (ticket_type_id));
+joinable!(inventory_pools -> events (event_id));
+joinable!(ledger_accounts -> organizations (organization_id));
+joinable!(ledger_journal_entries -> organizations (organization_id));
+joinable!(ledger_journal_entries -> users (created_by));
+joinable!(ledger_lines -> ledger_accounts (ledger_account_id));
+joinable!(ledger_lines -> ledger_journal_entries (ledger_journal_entry_id));
 joinable!(listings -> users (user_id));
 joinable!(loot_box_contents -> events (content_event_id));
+joinable!(loot_box_draws -> ticket_instances (ticket_instance_id));
+joinable!(loot_box_draws -> users (opened_by_user_id));
 joinable!(marketplace_accounts -> users (user_id));
 joinable!(order_items -> codes (code_id));
 joinable!(order_items -> events (event_id));
 joinable!(order_items -> fee_schedule_ranges (fee_schedule_range_id));
 joinable!(order_items -> holds (hold_id));
 joinable!(order_items -> orders (order_id));
+joinable!(order_items -> packages (package_id));
+joinable!(order_items -> product_variants (product_variant_id));
+joinable!(order_items -> tax_rules (tax_rule_id));
 joinable!(order_items -> ticket_pricing (ticket_pricing_id));
 joinable!(order_items -> ticket_types (ticket_type_id));
 joinable!(order_transfers -> orders (order_id));
 joinable!(order_transfers -> transfers (transfer_id));
+joinable!(orders -> affiliate_links (affiliate_link_id));
+joinable!(orders -> partners (partner_id));
 joinable!(orders -> settlements (settlement_id));
+joinable!(organization_bank_accounts -> organizations (organization_id));
+joinable!(organization_brandings -> organizations (organization_id));
 joinable!(organization_interactions -> organizations (organization_id));
 joinable!(organization_interactions -> users (user_id));
 joinable!(organization_invites -> organizations (organization_id));
@@ -1013,10 +1605,19 @@ joinable!(organization_users -> users (user_id));
 joinable!(organization_venues -> organizations (organization_id));
 joinable!(organization_venues -> venues (venue_id));
 joinable!(organizations -> fee_schedules (fee_schedule_id));
+joinable!(package_ticket_types -> packages (package_id));
+joinable!(package_ticket_types -> ticket_types (ticket_type_id));
+joinable!(packages -> organizations (organization_id));
+joinable!(partner_guests -> partners (partner_id));
+joinable!(partner_guests -> users (user_id));
 joinable!(payment_methods -> users (user_id));
+joinable!(payments -> cash_drawer_sessions (cash_drawer_session_id));
 joinable!(payments -> orders (order_id));
 joinable!(payments -> refunds (refund_id));
 joinable!(payments -> users (created_by));
+joinable!(product_variants -> products (product_id));
+joinable!(products -> events (event_id));
+joinable!(products -> organizations (organization_id));
 joinable!(push_notification_tokens -> users (user_id));
 joinable!(rarities -> events (event_id));
 joinable!(refund_items -> order_items (order_item_id));
@@ -1027,38 +1628,75 @@ joinable!(refunds -> orders (order_id));
 joinable!(refunds -> settlements (settlement_id));
 joinable!(refunds -> users (user_id));
 joinable!(settlement_adjustments -> settlements (settlement_id));
+joinable!(settlement_entries -> affiliate_links (affiliate_link_id));
 joinable!(settlement_entries -> events (event_id));
+joinable!(settlement_entries -> partners (partner_id));
+joinable!(settlement_entries -> product_variants (product_variant_id));
 joinable!(settlement_entries -> settlements (settlement_id));
 joinable!(settlement_entries -> ticket_types (ticket_type_id));
+joinable!(settlement_payout_batches -> users (created_by_user_id));
+joinable!(settlement_payouts -> organization_bank_accounts (organization_bank_account_id));
+joinable!(settlement_payouts -> settlement_payout_batches (settlement_payout_batch_id));
+joinable!(settlement_payouts -> settlements (settlement_id));
+joinable!(settlement_statement_subscribers -> organizations (organization_id));
+joinable!(settlement_statement_subscribers -> users (user_id));
+joinable!(settlement_statements -> settlements (settlement_id));
 joinable!(settlements -> organizations (organization_id));
+joinable!(tax_rules -> organizations (organization_id));
+joinable!(tax_rules -> venues (venue_id));
 joinable!(temporary_user_links -> temporary_users (temporary_user_id));
 joinable!(temporary_user_links -> users (user_id));
 joinable!(ticket_instances -> assets (asset_id));
 joinable!(ticket_instances -> holds (hold_id));
 joinable!(ticket_instances -> listings (listing_id));
 joinable!(ticket_instances -> order_items (order_item_id));
+joinable!(ticket_instances -> ticket_types (sold_as_ticket_type_id));
+joinable!(ticket_instances -> trades (trade_id));
 joinable!(ticket_instances -> wallets (wallet_id));
 joinable!(ticket_pricing -> ticket_types (ticket_type_id));
+joinable!(ticket_scans -> access_zones (access_zone_id));
+joinable!(ticket_scans -> events (event_id));
+joinable!(ticket_scans -> ticket_instances (ticket_instance_id));
+joinable!(ticket_scans -> users (scanned_by_user_id));
 joinable!(ticket_type_codes -> codes (code_id));
 joinable!(ticket_type_codes -> ticket_types (ticket_type_id));
 joinable!(ticket_types -> events (event_id));
+joinable!(ticket_types -> inventory_pools (inventory_pool_id));
 joinable!(ticket_types -> rarities (rarity_id));
+joinable!(ticket_types -> stages (stage_id));
+joinable!(trade_items -> ticket_instances (ticket_instance_id));
+joinable!(trade_items -> trades (trade_id));
+joinable!(trade_items -> users (from_user_id));
 joinable!(transfer_tickets -> ticket_instances (ticket_instance_id));
 joinable!(transfer_tickets -> transfers (transfer_id));
 joinable!(user_genres -> genres (genre_id));
 joinable!(user_genres -> users (user_id));
 joinable!(venues -> regions (region_id));
+joinable!(wallet_pass_registrations -> wallet_passes (wallet_pass_id));
+joinable!(wallet_passes -> ticket_instances (ticket_instance_id));
+joinable!(wallet_passes -> wallets (wallet_id));
 joinable!(wallets -> organizations (organization_id));
 joinable!(wallets -> users (user_id));
 
 allow_tables_to_appear_in_same_query!(
+    access_zone_ticket_types,
+    access_zones,
+    accounting_account_mappings,
+    affiliate_commission_rules,
+    affiliate_commissions,
+    affiliate_links,
     analytics_page_views,
     announcement_engagements,
     announcements,
     artist_genres,
     artists,
+    asset_sync_issues,
     assets,
     broadcasts,
+    cash_drawer_movements,
+    cash_drawer_sessions,
+    checkout_answers,
+    checkout_questions,
     codes,
     collection_items,
     collections,
@@ -1070,27 +1708,43 @@ allow_tables_to_appear_in_same_query!(
     event_genres,
     event_interest,
     event_report_subscribers,
+    event_series,
+    event_series_occurrences,
     event_users,
     events,
     external_logins,
+    fan_segments,
     fee_schedule_ranges,
     fee_schedules,
     genres,
     holds,
+    inventory_pools,
+    ledger_accounts,
+    ledger_journal_entries,
+    ledger_lines,
     listings,
     loot_box_contents,
+    loot_box_draws,
     marketplace_accounts,
     notes,
     order_items,
     order_transfers,
     orders,
+    organization_bank_accounts,
+    organization_brandings,
     organization_interactions,
     organization_invites,
     organization_users,
     organization_venues,
     organizations,
+    package_ticket_types,
+    packages,
+    partner_guests,
+    partners,
     payment_methods,
     payments,
+    product_variants,
+    products,
     push_notification_tokens,
     rarities,
     refund_items,
@@ -1099,20 +1753,31 @@ allow_tables_to_appear_in_same_query!(
     regions,
     settlement_adjustments,
     settlement_entries,
+    settlement_payout_batches,
+    settlement_payouts,
+    settlement_statement_subscribers,
+    settlement_statements,
     settlements,
     slugs,
     source_aliases,
     stages,
+    tax_rules,
     temporary_user_links,
     temporary_users,
     ticket_instances,
     ticket_pricing,
+    ticket_scans,
     ticket_type_codes,
     ticket_types,
+    trade_items,
+    trades,
     transfer_tickets,
     transfers,
+    user_credit_entries,
     user_genres,
     users,
     venues,
+    wallet_pass_registrations,
+    wallet_passes,
     wallets,
 );
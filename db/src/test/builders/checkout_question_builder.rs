@@ -0,0 +1,83 @@
+use diesel::prelude::*;
+use models::*;
+use test::builders::*;
+use uuid::Uuid;
+
+pub struct CheckoutQuestionBuilder<'a> {
+    event_id: Option<Uuid>,
+    ticket_type_id: Option<Uuid>,
+    question: String,
+    question_type: CheckoutQuestionType,
+    answer_scope: CheckoutQuestionScope,
+    options: Vec<String>,
+    required: bool,
+    connection: &'a PgConnection,
+}
+
+impl<'a> CheckoutQuestionBuilder<'a> {
+    pub fn new(connection: &'a PgConnection) -> CheckoutQuestionBuilder<'a> {
+        CheckoutQuestionBuilder {
+            connection,
+            event_id: None,
+            ticket_type_id: None,
+            question: "Company".to_string(),
+            question_type: CheckoutQuestionType::Text,
+            answer_scope: CheckoutQuestionScope::Order,
+            options: Vec::new(),
+            required: false,
+        }
+    }
+
+    pub fn with_event(mut self, event: &Event) -> CheckoutQuestionBuilder<'a> {
+        self.event_id = Some(event.id);
+        self
+    }
+
+    pub fn with_ticket_type(mut self, ticket_type: &TicketType) -> CheckoutQuestionBuilder<'a> {
+        self.event_id = Some(ticket_type.event_id);
+        self.ticket_type_id = Some(ticket_type.id);
+        self
+    }
+
+    pub fn with_question(mut self, question: &str) -> CheckoutQuestionBuilder<'a> {
+        self.question = question.to_string();
+        self
+    }
+
+    pub fn with_options(mut self, options: Vec<&str>) -> CheckoutQuestionBuilder<'a> {
+        self.question_type = CheckoutQuestionType::Select;
+        self.options = options.into_iter().map(|o| o.to_string()).collect();
+        self
+    }
+
+    pub fn checkbox(mut self) -> CheckoutQuestionBuilder<'a> {
+        self.question_type = CheckoutQuestionType::Checkbox;
+        self
+    }
+
+    pub fn per_attendee(mut self) -> CheckoutQuestionBuilder<'a> {
+        self.answer_scope = CheckoutQuestionScope::Attendee;
+        self
+    }
+
+    pub fn required(mut self) -> CheckoutQuestionBuilder<'a> {
+        self.required = true;
+        self
+    }
+
+    pub fn finish(self) -> CheckoutQuestion {
+        let event_id = self
+            .event_id
+            .unwrap_or_else(|| EventBuilder::new(self.connection).with_tickets().finish().id);
+        let mut new_checkout_question = CheckoutQuestion::create(
+            event_id,
+            self.question,
+            self.question_type,
+            self.answer_scope,
+            self.required,
+        );
+        new_checkout_question.ticket_type_id = self.ticket_type_id;
+        new_checkout_question.options = self.options;
+        new_checkout_question.commit(None, self.connection).unwrap()
+    }
+}
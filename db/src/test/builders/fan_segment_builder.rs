@@ -0,0 +1,56 @@
+use diesel::prelude::*;
+use models::*;
+use rand::prelude::*;
+use test::builders::*;
+use uuid::Uuid;
+
+pub struct FanSegmentBuilder<'a> {
+    organization_id: Option<Uuid>,
+    name: String,
+    min_revenue_in_cents: Option<i64>,
+    redemption_code: Option<String>,
+    connection: &'a PgConnection,
+}
+
+impl<'a> FanSegmentBuilder<'a> {
+    pub fn new(connection: &'a PgConnection) -> FanSegmentBuilder<'a> {
+        let x: u32 = random();
+        FanSegmentBuilder {
+            connection,
+            organization_id: None,
+            name: format!("Fan segment {}", x),
+            min_revenue_in_cents: None,
+            redemption_code: None,
+        }
+    }
+
+    pub fn with_organization(mut self, organization: &Organization) -> FanSegmentBuilder<'a> {
+        self.organization_id = Some(organization.id);
+        self
+    }
+
+    pub fn with_name(mut self, name: String) -> FanSegmentBuilder<'a> {
+        self.name = name;
+        self
+    }
+
+    pub fn with_min_revenue_in_cents(mut self, min_revenue_in_cents: i64) -> FanSegmentBuilder<'a> {
+        self.min_revenue_in_cents = Some(min_revenue_in_cents);
+        self
+    }
+
+    pub fn with_redemption_code(mut self, redemption_code: String) -> FanSegmentBuilder<'a> {
+        self.redemption_code = Some(redemption_code);
+        self
+    }
+
+    pub fn finish(self) -> FanSegment {
+        let organization_id = self
+            .organization_id
+            .unwrap_or_else(|| OrganizationBuilder::new(self.connection).finish().id);
+        let mut new_fan_segment = FanSegment::create(organization_id, self.name);
+        new_fan_segment.min_revenue_in_cents = self.min_revenue_in_cents;
+        new_fan_segment.redemption_code = self.redemption_code;
+        new_fan_segment.commit(None, self.connection).unwrap()
+    }
+}
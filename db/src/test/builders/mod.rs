@@ -2,6 +2,7 @@ pub use self::announcement_builder::*;
 pub use self::announcement_engagement_builder::*;
 pub use self::artist_builder::*;
 pub use self::broadcast_builder::*;
+pub use self::checkout_question_builder::*;
 pub use self::code_builder::*;
 pub use self::comp_builder::*;
 pub use self::domain_action_builder::*;
@@ -10,15 +11,19 @@ pub use self::event_artist_builder::*;
 pub use self::event_builder::*;
 pub use self::event_interest_builder::*;
 pub use self::event_report_subscriber_builder::*;
+pub use self::fan_segment_builder::*;
 pub use self::fee_schedule_builder::*;
 pub use self::genre_builder::*;
 pub use self::hold_builder::*;
 pub use self::note_builder::*;
 pub use self::order_builder::*;
+pub use self::organization_bank_account_builder::*;
 pub use self::organization_builder::*;
 pub use self::organization_invite_builder::*;
+pub use self::package_builder::*;
 pub use self::payment_builder::*;
 pub use self::payment_method_builder::*;
+pub use self::product_builder::*;
 pub use self::refund_builder::*;
 pub use self::region_builder::*;
 pub use self::settlement_adjustment_builder::*;
@@ -26,6 +31,7 @@ pub use self::settlement_builder::*;
 pub use self::settlement_entry_builder::*;
 pub use self::slug_builder::*;
 pub use self::stage_builder::*;
+pub use self::tax_rule_builder::*;
 pub use self::ticket_type_builder::*;
 pub use self::user_builder::*;
 pub use self::venue_builder::*;
@@ -34,6 +40,7 @@ mod announcement_builder;
 mod announcement_engagement_builder;
 mod artist_builder;
 mod broadcast_builder;
+mod checkout_question_builder;
 mod code_builder;
 mod comp_builder;
 mod domain_action_builder;
@@ -42,15 +49,19 @@ mod event_artist_builder;
 mod event_builder;
 mod event_interest_builder;
 mod event_report_subscriber_builder;
+mod fan_segment_builder;
 mod fee_schedule_builder;
 mod genre_builder;
 mod hold_builder;
 mod note_builder;
 mod order_builder;
+mod organization_bank_account_builder;
 mod organization_builder;
 mod organization_invite_builder;
+mod package_builder;
 mod payment_builder;
 mod payment_method_builder;
+mod product_builder;
 mod refund_builder;
 mod region_builder;
 mod settlement_adjustment_builder;
@@ -58,6 +69,7 @@ mod settlement_builder;
 mod settlement_entry_builder;
 mod slug_builder;
 mod stage_builder;
+mod tax_rule_builder;
 mod ticket_type_builder;
 mod user_builder;
 mod venue_builder;
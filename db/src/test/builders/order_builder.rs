@@ -14,6 +14,8 @@ pub struct OrderBuilder<'a> {
     external_payment_type: Option<ExternalPaymentType>,
     redemption_code: Option<String>,
     is_box_office: bool,
+    add_ons: Vec<UpdateAddOnItem>,
+    packages: Vec<UpdatePackageItem>,
 }
 
 impl<'a> OrderBuilder<'a> {
@@ -29,6 +31,8 @@ impl<'a> OrderBuilder<'a> {
             external_payment_type: None,
             redemption_code: None,
             is_box_office: false,
+            add_ons: Vec::new(),
+            packages: Vec::new(),
         }
     }
 
@@ -82,6 +86,22 @@ impl<'a> OrderBuilder<'a> {
         self
     }
 
+    pub fn with_add_on(mut self, product_variant: &ProductVariant, quantity: u32) -> OrderBuilder<'a> {
+        self.add_ons.push(UpdateAddOnItem {
+            product_variant_id: product_variant.id,
+            quantity,
+        });
+        self
+    }
+
+    pub fn with_package(mut self, package: &Package, quantity: u32) -> OrderBuilder<'a> {
+        self.packages.push(UpdatePackageItem {
+            package_id: package.id,
+            quantity,
+        });
+        self
+    }
+
     pub fn finish(mut self) -> Order {
         if self.user.is_none() {
             let user = UserBuilder::new(self.connection).finish();
@@ -117,6 +137,16 @@ impl<'a> OrderBuilder<'a> {
         )
         .unwrap();
 
+        if !self.packages.is_empty() {
+            cart.update_package_quantities(user.id, &self.packages, false, self.connection)
+                .unwrap();
+        }
+
+        if !self.add_ons.is_empty() {
+            cart.update_add_on_quantities(user.id, &self.add_ons, false, self.connection)
+                .unwrap();
+        }
+
         if let Some(on_behalf_of_user) = self.on_behalf_of_user {
             cart.set_behalf_of_user(on_behalf_of_user, user.id, self.connection)
                 .unwrap();
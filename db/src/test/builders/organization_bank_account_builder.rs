@@ -0,0 +1,56 @@
+use diesel::prelude::*;
+use models::*;
+use test::builders::*;
+use uuid::Uuid;
+
+pub struct OrganizationBankAccountBuilder<'a> {
+    organization_id: Option<Uuid>,
+    payout_method: PayoutMethods,
+    account_holder_name: String,
+    connection: &'a PgConnection,
+}
+
+impl<'a> OrganizationBankAccountBuilder<'a> {
+    pub fn new(connection: &'a PgConnection) -> OrganizationBankAccountBuilder<'a> {
+        OrganizationBankAccountBuilder {
+            connection,
+            organization_id: None,
+            payout_method: PayoutMethods::Nacha,
+            account_holder_name: "Organization Holdings".to_string(),
+        }
+    }
+
+    pub fn with_organization(mut self, organization: &Organization) -> OrganizationBankAccountBuilder<'a> {
+        self.organization_id = Some(organization.id);
+        self
+    }
+
+    pub fn with_account_holder_name(mut self, account_holder_name: &str) -> OrganizationBankAccountBuilder<'a> {
+        self.account_holder_name = account_holder_name.to_string();
+        self
+    }
+
+    pub fn sepa(mut self) -> OrganizationBankAccountBuilder<'a> {
+        self.payout_method = PayoutMethods::Sepa;
+        self
+    }
+
+    pub fn finish(self) -> OrganizationBankAccount {
+        let organization_id = self
+            .organization_id
+            .unwrap_or_else(|| OrganizationBuilder::new(self.connection).finish().id);
+        let mut bank_account =
+            OrganizationBankAccount::create(organization_id, self.payout_method, self.account_holder_name);
+        match self.payout_method {
+            PayoutMethods::Nacha => {
+                bank_account.routing_number = Some("021000021".to_string());
+                bank_account.account_number = Some("123456789".to_string());
+            }
+            PayoutMethods::Sepa => {
+                bank_account.iban = Some("DE89370400440532013000".to_string());
+                bank_account.bic = Some("COBADEFFXXX".to_string());
+            }
+        }
+        bank_account.commit(None, "encryption_key", self.connection).unwrap()
+    }
+}
@@ -18,6 +18,7 @@ pub struct OrganizationBuilder<'a> {
     additional_fee: i64,
     timezone: Option<String>,
     settlement_type: Option<SettlementTypes>,
+    default_locale: Option<Locales>,
 }
 
 impl<'a> OrganizationBuilder<'a> {
@@ -36,6 +37,7 @@ impl<'a> OrganizationBuilder<'a> {
             additional_fee: 0,
             timezone: None,
             settlement_type: None,
+            default_locale: None,
         }
     }
 
@@ -59,6 +61,11 @@ impl<'a> OrganizationBuilder<'a> {
         self
     }
 
+    pub fn with_default_locale(mut self, default_locale: Locales) -> Self {
+        self.default_locale = Some(default_locale);
+        self
+    }
+
     pub fn with_name(mut self, name: String) -> Self {
         self.name = name;
         self
@@ -107,6 +114,7 @@ impl<'a> OrganizationBuilder<'a> {
 
         let mut organization = Organization::create(&self.name, self.fee_schedule.unwrap().id);
         organization.settlement_type = self.settlement_type;
+        organization.default_locale = self.default_locale;
         let mut organization = organization
             .commit(None, "encryption_key", None, self.connection)
             .unwrap();
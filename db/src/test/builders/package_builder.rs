@@ -0,0 +1,63 @@
+use diesel::prelude::*;
+use models::*;
+use test::builders::*;
+use uuid::Uuid;
+
+pub struct PackageBuilder<'a> {
+    organization_id: Option<Uuid>,
+    name: String,
+    ticket_types: Vec<(TicketType, i64, i64)>,
+    connection: &'a PgConnection,
+}
+
+impl<'a> PackageBuilder<'a> {
+    pub fn new(connection: &'a PgConnection) -> PackageBuilder<'a> {
+        PackageBuilder {
+            connection,
+            organization_id: None,
+            name: "Festival Pass".to_string(),
+            ticket_types: Vec::new(),
+        }
+    }
+
+    pub fn with_organization(mut self, organization: &Organization) -> PackageBuilder<'a> {
+        self.organization_id = Some(organization.id);
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> PackageBuilder<'a> {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn with_ticket_type(
+        mut self,
+        ticket_type: &TicketType,
+        quantity: i64,
+        price_in_cents: i64,
+    ) -> PackageBuilder<'a> {
+        self.ticket_types.push((ticket_type.clone(), quantity, price_in_cents));
+        self
+    }
+
+    pub fn finish(self) -> Package {
+        let organization_id = match self.organization_id {
+            Some(organization_id) => organization_id,
+            None => match self.ticket_types.first() {
+                Some((ticket_type, _, _)) => ticket_type.event(self.connection).unwrap().organization_id,
+                None => OrganizationBuilder::new(self.connection).finish().id,
+            },
+        };
+        let package = Package::create(organization_id, self.name)
+            .commit(None, self.connection)
+            .unwrap();
+
+        for (ticket_type, quantity, price_in_cents) in self.ticket_types {
+            PackageTicketType::create(package.id, ticket_type.id, quantity, price_in_cents)
+                .commit(None, self.connection)
+                .unwrap();
+        }
+
+        package
+    }
+}
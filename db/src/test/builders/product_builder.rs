@@ -0,0 +1,74 @@
+use diesel::prelude::*;
+use models::*;
+use test::builders::*;
+use uuid::Uuid;
+
+pub struct ProductBuilder<'a> {
+    organization_id: Option<Uuid>,
+    event_id: Option<Uuid>,
+    name: String,
+    price_in_cents: i64,
+    variants: Vec<(String, i64)>,
+    connection: &'a PgConnection,
+}
+
+impl<'a> ProductBuilder<'a> {
+    pub fn new(connection: &'a PgConnection) -> ProductBuilder<'a> {
+        ProductBuilder {
+            connection,
+            organization_id: None,
+            event_id: None,
+            name: "Parking Pass".to_string(),
+            price_in_cents: 1500,
+            variants: Vec::new(),
+        }
+    }
+
+    pub fn with_organization(mut self, organization: &Organization) -> ProductBuilder<'a> {
+        self.organization_id = Some(organization.id);
+        self
+    }
+
+    pub fn with_event(mut self, event: &Event) -> ProductBuilder<'a> {
+        self.organization_id = Some(event.organization_id);
+        self.event_id = Some(event.id);
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> ProductBuilder<'a> {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn with_price(mut self, price_in_cents: i64) -> ProductBuilder<'a> {
+        self.price_in_cents = price_in_cents;
+        self
+    }
+
+    pub fn with_variant(mut self, name: &str, inventory: i64) -> ProductBuilder<'a> {
+        self.variants.push((name.to_string(), inventory));
+        self
+    }
+
+    pub fn finish(self) -> Product {
+        let organization_id = self
+            .organization_id
+            .unwrap_or_else(|| OrganizationBuilder::new(self.connection).finish().id);
+        let product = Product::create(organization_id, self.event_id, self.name, self.price_in_cents)
+            .commit(None, self.connection)
+            .unwrap();
+
+        let variants = if self.variants.is_empty() {
+            vec![("Default".to_string(), 100)]
+        } else {
+            self.variants
+        };
+        for (name, inventory) in variants {
+            ProductVariant::create(product.id, name, inventory)
+                .commit(None, self.connection)
+                .unwrap();
+        }
+
+        product
+    }
+}
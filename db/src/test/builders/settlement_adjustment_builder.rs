@@ -5,6 +5,7 @@ use uuid::Uuid;
 
 pub struct SettlementAdjustmentBuilder<'a> {
     settlement_id: Option<Uuid>,
+    settlement_adjustment_type: SettlementAdjustmentTypes,
     amount_in_cents: i64,
     note: Option<String>,
     connection: &'a PgConnection,
@@ -14,12 +15,18 @@ impl<'a> SettlementAdjustmentBuilder<'a> {
     pub fn new(connection: &PgConnection) -> SettlementAdjustmentBuilder {
         SettlementAdjustmentBuilder {
             settlement_id: None,
+            settlement_adjustment_type: SettlementAdjustmentTypes::ManualDeduction,
             note: None,
             amount_in_cents: 100,
             connection,
         }
     }
 
+    pub fn with_settlement_adjustment_type(mut self, settlement_adjustment_type: SettlementAdjustmentTypes) -> Self {
+        self.settlement_adjustment_type = settlement_adjustment_type;
+        self
+    }
+
     pub fn with_amount_in_cents(mut self, amount_in_cents: i64) -> Self {
         self.amount_in_cents = amount_in_cents;
         self
@@ -43,7 +50,7 @@ impl<'a> SettlementAdjustmentBuilder<'a> {
 
         SettlementAdjustment::create(
             settlement_id,
-            SettlementAdjustmentTypes::ManualDeduction,
+            self.settlement_adjustment_type,
             self.note.clone(),
             self.amount_in_cents,
         )
@@ -12,6 +12,7 @@ pub struct SettlementBuilder<'a> {
     comment: Option<String>,
     only_finished_events: bool,
     status: SettlementStatus,
+    with_entries: bool,
     connection: &'a PgConnection,
 }
 
@@ -24,6 +25,7 @@ impl<'a> SettlementBuilder<'a> {
             comment: None,
             only_finished_events: true,
             status: SettlementStatus::PendingSettlement,
+            with_entries: false,
             connection,
         }
     }
@@ -58,6 +60,13 @@ impl<'a> SettlementBuilder<'a> {
         self
     }
 
+    /// Adds entries for an event of the organization, 2 tickets at $1.00 with 2 fees at $0.10 and
+    /// a single $0.50 event fee
+    pub fn with_entries(mut self) -> Self {
+        self.with_entries = true;
+        self
+    }
+
     pub fn finish(&mut self) -> Settlement {
         let organization_id = self
             .organization_id
@@ -66,7 +75,7 @@ impl<'a> SettlementBuilder<'a> {
         let start_time = self.start_time.unwrap_or(dates::now().add_days(-5).finish());
         let end_time = self.end_time.unwrap_or(start_time.into_builder().add_days(5).finish());
 
-        Settlement::create(
+        let settlement = Settlement::create(
             organization_id,
             start_time,
             end_time,
@@ -75,6 +84,30 @@ impl<'a> SettlementBuilder<'a> {
             self.only_finished_events,
         )
         .commit(None, self.connection)
-        .unwrap()
+        .unwrap();
+
+        if self.with_entries {
+            let organization = Organization::find(organization_id, self.connection).unwrap();
+            let event = EventBuilder::new(self.connection)
+                .with_organization(&organization)
+                .with_tickets()
+                .finish();
+            let ticket_type = &event.ticket_types(true, None, self.connection).unwrap()[0];
+            SettlementEntryBuilder::new(self.connection)
+                .with_settlement(&settlement)
+                .with_event(&event)
+                .with_ticket_type_id(ticket_type.id)
+                .finish();
+            SettlementEntryBuilder::new(self.connection)
+                .with_settlement(&settlement)
+                .with_event(&event)
+                .with_face_value_in_cents(0)
+                .with_online_sold_quantity(0)
+                .with_revenue_share_value_in_cents(50)
+                .with_fee_sold_quantity(1)
+                .finish();
+        }
+
+        settlement
     }
 }
@@ -43,6 +43,11 @@ impl<'a> SettlementEntryBuilder<'a> {
         self
     }
 
+    pub fn with_fee_sold_quantity(mut self, fee_sold_quantity: i64) -> Self {
+        self.fee_sold_quantity = fee_sold_quantity;
+        self
+    }
+
     pub fn with_event(mut self, event: &Event) -> Self {
         self.event_id = Some(event.id);
         self
@@ -92,6 +97,7 @@ impl<'a> SettlementEntryBuilder<'a> {
             self.fee_sold_quantity,
             self.online_sold_quantity * self.face_value_in_cents
                 + self.fee_sold_quantity * self.revenue_share_value_in_cents,
+            event.currency(self.connection).unwrap(),
         )
         .commit(self.connection)
         .unwrap()
@@ -0,0 +1,81 @@
+use diesel::prelude::*;
+use models::*;
+use test::builders::*;
+use uuid::Uuid;
+
+pub struct TaxRuleBuilder<'a> {
+    organization_id: Option<Uuid>,
+    venue_id: Option<Uuid>,
+    name: String,
+    jurisdiction: String,
+    rate_percent: f32,
+    inclusive: bool,
+    fees_taxable: bool,
+    connection: &'a PgConnection,
+}
+
+impl<'a> TaxRuleBuilder<'a> {
+    pub fn new(connection: &'a PgConnection) -> TaxRuleBuilder<'a> {
+        TaxRuleBuilder {
+            connection,
+            organization_id: None,
+            venue_id: None,
+            name: "Sales Tax".to_string(),
+            jurisdiction: "New York".to_string(),
+            rate_percent: 10f32,
+            inclusive: false,
+            fees_taxable: false,
+        }
+    }
+
+    pub fn with_organization(mut self, organization: &Organization) -> TaxRuleBuilder<'a> {
+        self.organization_id = Some(organization.id);
+        self
+    }
+
+    pub fn with_venue(mut self, venue: &Venue) -> TaxRuleBuilder<'a> {
+        self.venue_id = Some(venue.id);
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> TaxRuleBuilder<'a> {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn with_jurisdiction(mut self, jurisdiction: &str) -> TaxRuleBuilder<'a> {
+        self.jurisdiction = jurisdiction.to_string();
+        self
+    }
+
+    pub fn with_rate(mut self, rate_percent: f32) -> TaxRuleBuilder<'a> {
+        self.rate_percent = rate_percent;
+        self
+    }
+
+    pub fn inclusive(mut self) -> TaxRuleBuilder<'a> {
+        self.inclusive = true;
+        self
+    }
+
+    pub fn fees_taxable(mut self) -> TaxRuleBuilder<'a> {
+        self.fees_taxable = true;
+        self
+    }
+
+    pub fn finish(self) -> TaxRule {
+        let organization_id = self
+            .organization_id
+            .unwrap_or_else(|| OrganizationBuilder::new(self.connection).finish().id);
+        let mut tax_rule = TaxRule::create(
+            organization_id,
+            self.venue_id,
+            self.name,
+            self.jurisdiction,
+            self.rate_percent,
+        );
+        tax_rule.inclusive = self.inclusive;
+        tax_rule.fees_taxable = self.fees_taxable;
+        tax_rule.commit(None, self.connection).unwrap()
+    }
+}
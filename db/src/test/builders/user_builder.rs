@@ -1,5 +1,5 @@
 use diesel::prelude::*;
-use models::User;
+use models::{Locales, User, UserEditableAttributes};
 use uuid::Uuid;
 
 pub struct UserBuilder<'a> {
@@ -8,6 +8,7 @@ pub struct UserBuilder<'a> {
     email: Option<String>,
     phone: Option<String>,
     password: String,
+    locale: Option<Locales>,
     connection: &'a PgConnection,
 }
 
@@ -20,6 +21,7 @@ impl<'a> UserBuilder<'a> {
             email: Some(format!("jeff{}@tari.com", x).into()),
             phone: Some("555-555-5555".into()),
             password: "examplePassword".into(),
+            locale: None,
             connection,
         }
     }
@@ -54,13 +56,18 @@ impl<'a> UserBuilder<'a> {
         self
     }
 
+    pub fn with_locale(mut self, locale: Locales) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
     pub fn with_no_phone(mut self) -> Self {
         self.phone = None;
         self
     }
 
     pub fn finish(&self) -> User {
-        User::create(
+        let user = User::create(
             Some(self.first_name.to_string()),
             Some(self.last_name.to_string()),
             self.email.clone(),
@@ -68,6 +75,20 @@ impl<'a> UserBuilder<'a> {
             &self.password,
         )
         .commit(None, self.connection)
-        .unwrap()
+        .unwrap();
+
+        match self.locale {
+            Some(locale) => user
+                .update(
+                    UserEditableAttributes {
+                        locale: Some(Some(locale)),
+                        ..Default::default()
+                    },
+                    None,
+                    self.connection,
+                )
+                .unwrap(),
+            None => user,
+        }
     }
 }
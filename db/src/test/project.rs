@@ -71,6 +71,10 @@ impl TestProject {
         BroadcastBuilder::new(&self.connection)
     }
 
+    pub fn create_checkout_question(&self) -> CheckoutQuestionBuilder {
+        CheckoutQuestionBuilder::new(&self.connection)
+    }
+
     pub fn create_code(&self) -> CodeBuilder {
         CodeBuilder::new(&self.connection)
     }
@@ -91,6 +95,10 @@ impl TestProject {
         EventBuilder::new(&self.connection)
     }
 
+    pub fn create_fan_segment(&self) -> FanSegmentBuilder {
+        FanSegmentBuilder::new(&self.connection)
+    }
+
     pub fn create_genre(&self) -> GenreBuilder {
         GenreBuilder::new(&self.connection)
     }
@@ -115,10 +123,18 @@ impl TestProject {
         OrganizationBuilder::new(&self.connection)
     }
 
+    pub fn create_organization_bank_account(&self) -> OrganizationBankAccountBuilder {
+        OrganizationBankAccountBuilder::new(&self.connection)
+    }
+
     pub fn create_organization_invite(&self) -> OrgInviteBuilder {
         OrgInviteBuilder::new(&self.connection)
     }
 
+    pub fn create_package(&self) -> PackageBuilder {
+        PackageBuilder::new(&self.connection)
+    }
+
     pub fn create_payment_method(&self) -> PaymentMethodBuilder {
         PaymentMethodBuilder::new(&self.connection)
     }
@@ -127,6 +143,10 @@ impl TestProject {
         PaymentBuilder::new(&self.connection)
     }
 
+    pub fn create_product(&self) -> ProductBuilder {
+        ProductBuilder::new(&self.connection)
+    }
+
     pub fn create_region(&self) -> RegionBuilder {
         RegionBuilder::new(&self.connection)
     }
@@ -147,6 +167,10 @@ impl TestProject {
         StageBuilder::new(&self.connection)
     }
 
+    pub fn create_tax_rule(&self) -> TaxRuleBuilder {
+        TaxRuleBuilder::new(&self.connection)
+    }
+
     pub fn create_event_artist(&self) -> EventArtistBuilder {
         EventArtistBuilder::new(&self.connection)
     }
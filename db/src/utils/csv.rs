@@ -0,0 +1,50 @@
+/// Minimal CSV builder used for the file exports served by the API
+#[derive(Default)]
+pub struct CsvWriter {
+    buffer: String,
+}
+
+impl CsvWriter {
+    pub fn new() -> CsvWriter {
+        CsvWriter { buffer: String::new() }
+    }
+
+    pub fn write_row<T: AsRef<str>>(&mut self, fields: &[T]) {
+        let row = fields
+            .iter()
+            .map(|f| escape_field(f.as_ref()))
+            .collect::<Vec<String>>()
+            .join(",");
+        self.buffer.push_str(&row);
+        self.buffer.push_str("\r\n");
+    }
+
+    pub fn into_string(self) -> String {
+        self.buffer
+    }
+}
+
+/// Quotes a field if it contains a delimiter, quote or line break
+pub fn escape_field(field: &str) -> String {
+    if field.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", field.replace("\"", "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[test]
+fn escape_field_quotes_when_required() {
+    assert_eq!(escape_field("plain"), "plain");
+    assert_eq!(escape_field("Smith, John"), "\"Smith, John\"");
+    assert_eq!(escape_field("The \"Best\" Fan"), "\"The \"\"Best\"\" Fan\"");
+    assert_eq!(escape_field("line\nbreak"), "\"line\nbreak\"");
+}
+
+#[test]
+fn write_row() {
+    let mut writer = CsvWriter::new();
+    writer.write_row(&["Email", "Name"]);
+    writer.write_row(&["a@example.com".to_string(), "Doe, Jane".to_string()]);
+    assert_eq!(writer.into_string(), "Email,Name\r\na@example.com,\"Doe, Jane\"\r\n");
+}
@@ -16,3 +16,22 @@ pub mod sha1 {
         assert_eq!(sha, "3abef1a14ccecd20d6ce892cbe042ae6d74946c8");
     }
 }
+
+pub mod sha256 {
+    use ring::digest;
+
+    pub fn digest(s: &str) -> String {
+        let sha = digest::digest(&digest::SHA256, s.as_bytes());
+        sha.as_ref()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<String>>()
+            .join("")
+    }
+
+    #[test]
+    fn sha256_digest() {
+        let sha = digest("testme");
+        assert_eq!(sha, "3bcc367a3488e113dca68b67e5fa262fe4fd2df48b1b72fd3292b30358911aab");
+    }
+}
@@ -0,0 +1,93 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Transfer drip reminders
+    ("transfers.drip.today", "today"),
+    ("transfers.drip.tonight", "tonight"),
+    (
+        "transfers.drip.source.day_of",
+        "Time to take action! The show is {time_of_day} and those tickets you sent to {recipient} still haven't been claimed. Give them a nudge!",
+    ),
+    (
+        "transfers.drip.source.day_before",
+        "Uh oh! The show is tomorrow and those tickets you sent to {recipient} still haven't been claimed. Give them a nudge!",
+    ),
+    (
+        "transfers.drip.source.reminder",
+        "Those tickets you sent to {recipient} still haven't been claimed. Give them a nudge!",
+    ),
+    (
+        "transfers.drip.destination.day_of",
+        "Time to take action! The event is {time_of_day} and the tickets {sender} sent you are still waiting!",
+    ),
+    (
+        "transfers.drip.destination.day_before",
+        "Get your tickets! The event is TOMORROW and you still need to get the tickets that {sender} sent you!",
+    ),
+    (
+        "transfers.drip.destination.week_before",
+        "The event is only one week away and you still need to get the tickets that {sender} sent you!",
+    ),
+    (
+        "transfers.drip.destination.reminder",
+        "You still need to get the tickets that {sender} sent you!",
+    ),
+    // SMS
+    (
+        "sms.box_office.checkin_instructions",
+        "Thank you for your purchase! Your order number is #{order_number}. Please head to the entry and let the door person know your first and last name so that they can check you in.",
+    ),
+    ("sms.transfers.cancelled", "{sender} has cancelled their transferred tickets."),
+    ("sms.transfers.drip_reminder", "{header} Follow this link to receive them: {link}"),
+    (
+        "sms.transfers.sent",
+        "{sender} has sent you some tickets. Follow this link to receive them: {link}",
+    ),
+    // Push notifications
+    ("push.transfers.received", "{sender} has sent you some tickets."),
+    // Email subjects
+    ("email.orders.confirmation.title", "{site_name} Purchase Completed"),
+    ("email.orders.refund.title", "{site_name} Refund"),
+    ("email.organization_invites.title", "{site_name} Invites"),
+    ("email.reports.ticket_counts.title", "{site_name} Ticket Counts"),
+    ("email.settlements.statement.title", "{site_name} Settlement Statement"),
+    ("email.transfers.sent.title", "{sender_name} has sent you some tickets"),
+    ("email.transfers.drip_reminder.title", "{site_name}: Ticket transfer reminder"),
+    ("email.transfers.sent_receipt.title", "{site_name}: Ticket transfer sent"),
+    ("email.transfers.cancelled_receipt.title", "{site_name}: Cancelled ticket transfer"),
+    (
+        "email.transfers.cancelled.title",
+        "{sender_name} has cancelled their transfer of tickets",
+    ),
+    ("email.users.registered.title", "{site_name} Registration"),
+    ("email.users.password_reset.title", "{site_name} Password reset request"),
+    ("email.users.invite.title", "{site_name} Invite"),
+    ("email.users.magic_link.title", "Welcome to {site_name}"),
+    // Errors
+    ("errors.connection", "Connection error"),
+    ("errors.delete", "Could not delete record"),
+    ("errors.duplicate_record", "Duplicate record exists"),
+    ("errors.insert", "Could not insert record"),
+    ("errors.internal", "Internal error"),
+    ("errors.invalid_input", "Invalid input"),
+    ("errors.missing_input", "Missing input"),
+    ("errors.no_results", "No results"),
+    ("errors.not_found", "Not found"),
+    ("errors.query", "Query error"),
+    ("errors.unknown", "Unknown error"),
+    ("errors.unknown_cause", "Unknown Cause"),
+    ("errors.update", "Could not update record"),
+    ("errors.validation", "Validation error"),
+    // Validation messages
+    ("validation.answer_required", "An answer is required"),
+    (
+        "validation.cart_event_limit_reached",
+        "You already have another event ticket in your cart. Please clear your cart first to purchase tickets to this event.",
+    ),
+    ("validation.code_not_valid", "Code not valid for current datetime"),
+    ("validation.custom_message_empty", "Custom messages cannot be blank"),
+    ("validation.email_in_use", "Email is already in use"),
+    ("validation.email_invalid", "Email is invalid"),
+    ("validation.iban_invalid", "IBAN is invalid"),
+    ("validation.insufficient_add_on_inventory", "Not enough of this add-on is available"),
+    ("validation.max_uses_reached", "Redemption code maximum uses limit exceeded"),
+    ("validation.url_invalid", "URL is invalid"),
+];
@@ -0,0 +1,102 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Transfer drip reminders
+    ("transfers.drip.today", "hoy"),
+    ("transfers.drip.tonight", "esta noche"),
+    (
+        "transfers.drip.source.day_of",
+        "¡Es hora de actuar! El show es {time_of_day} y los boletos que enviaste a {recipient} todavía no han sido reclamados. ¡Recuérdaselo!",
+    ),
+    (
+        "transfers.drip.source.day_before",
+        "¡Ay no! El show es mañana y los boletos que enviaste a {recipient} todavía no han sido reclamados. ¡Recuérdaselo!",
+    ),
+    (
+        "transfers.drip.source.reminder",
+        "Los boletos que enviaste a {recipient} todavía no han sido reclamados. ¡Recuérdaselo!",
+    ),
+    (
+        "transfers.drip.destination.day_of",
+        "¡Es hora de actuar! ¡El evento es {time_of_day} y los boletos que {sender} te envió todavía te esperan!",
+    ),
+    (
+        "transfers.drip.destination.day_before",
+        "¡Consigue tus boletos! ¡El evento es MAÑANA y todavía tienes que recibir los boletos que {sender} te envió!",
+    ),
+    (
+        "transfers.drip.destination.week_before",
+        "¡Falta solo una semana para el evento y todavía tienes que recibir los boletos que {sender} te envió!",
+    ),
+    (
+        "transfers.drip.destination.reminder",
+        "¡Todavía tienes que recibir los boletos que {sender} te envió!",
+    ),
+    // SMS
+    (
+        "sms.box_office.checkin_instructions",
+        "¡Gracias por tu compra! Tu número de orden es #{order_number}. Dirígete a la entrada y dile tu nombre y apellido al encargado de la puerta para que pueda registrar tu entrada.",
+    ),
+    ("sms.transfers.cancelled", "{sender} canceló la transferencia de sus boletos."),
+    ("sms.transfers.drip_reminder", "{header} Sigue este enlace para recibirlos: {link}"),
+    (
+        "sms.transfers.sent",
+        "{sender} te envió boletos. Sigue este enlace para recibirlos: {link}",
+    ),
+    // Push notifications
+    ("push.transfers.received", "{sender} te envió boletos."),
+    // Email subjects
+    ("email.orders.confirmation.title", "{site_name}: Compra completada"),
+    ("email.orders.refund.title", "{site_name}: Reembolso"),
+    ("email.organization_invites.title", "{site_name}: Invitaciones"),
+    ("email.reports.ticket_counts.title", "{site_name}: Conteo de boletos"),
+    ("email.settlements.statement.title", "{site_name}: Estado de liquidación"),
+    ("email.transfers.sent.title", "{sender_name} te envió boletos"),
+    (
+        "email.transfers.drip_reminder.title",
+        "{site_name}: Recordatorio de transferencia de boletos",
+    ),
+    ("email.transfers.sent_receipt.title", "{site_name}: Transferencia de boletos enviada"),
+    (
+        "email.transfers.cancelled_receipt.title",
+        "{site_name}: Transferencia de boletos cancelada",
+    ),
+    ("email.transfers.cancelled.title", "{sender_name} canceló su transferencia de boletos"),
+    ("email.users.registered.title", "{site_name}: Registro"),
+    (
+        "email.users.password_reset.title",
+        "{site_name}: Solicitud para restablecer la contraseña",
+    ),
+    ("email.users.invite.title", "{site_name}: Invitación"),
+    ("email.users.magic_link.title", "Bienvenido a {site_name}"),
+    // Errors
+    ("errors.connection", "Error de conexión"),
+    ("errors.delete", "No se pudo eliminar el registro"),
+    ("errors.duplicate_record", "Ya existe un registro duplicado"),
+    ("errors.insert", "No se pudo crear el registro"),
+    ("errors.internal", "Error interno"),
+    ("errors.invalid_input", "Entrada inválida"),
+    ("errors.missing_input", "Falta información"),
+    ("errors.no_results", "Sin resultados"),
+    ("errors.not_found", "No encontrado"),
+    ("errors.query", "Error de consulta"),
+    ("errors.unknown", "Error desconocido"),
+    ("errors.unknown_cause", "Causa desconocida"),
+    ("errors.update", "No se pudo actualizar el registro"),
+    ("errors.validation", "Error de validación"),
+    // Validation messages
+    ("validation.answer_required", "Se requiere una respuesta"),
+    (
+        "validation.cart_event_limit_reached",
+        "Ya tienes boletos de otro evento en tu carrito. Vacía tu carrito antes de comprar boletos para este evento.",
+    ),
+    ("validation.code_not_valid", "El código no es válido para la fecha y hora actuales"),
+    ("validation.custom_message_empty", "Los mensajes personalizados no pueden estar vacíos"),
+    ("validation.email_in_use", "El correo electrónico ya está en uso"),
+    ("validation.email_invalid", "El correo electrónico no es válido"),
+    ("validation.iban_invalid", "El IBAN no es válido"),
+    (
+        "validation.insufficient_add_on_inventory",
+        "No hay suficiente disponibilidad de este complemento",
+    ),
+    ("validation.max_uses_reached", "Se alcanzó el límite de usos del código"),
+    ("validation.url_invalid", "La URL no es válida"),
+];
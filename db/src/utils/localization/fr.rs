@@ -0,0 +1,102 @@
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Transfer drip reminders
+    ("transfers.drip.today", "aujourd'hui"),
+    ("transfers.drip.tonight", "ce soir"),
+    (
+        "transfers.drip.source.day_of",
+        "C'est le moment d'agir! Le spectacle a lieu {time_of_day} et les billets que vous avez envoyés à {recipient} n'ont toujours pas été réclamés. Faites un petit rappel!",
+    ),
+    (
+        "transfers.drip.source.day_before",
+        "Oh oh! Le spectacle a lieu demain et les billets que vous avez envoyés à {recipient} n'ont toujours pas été réclamés. Faites un petit rappel!",
+    ),
+    (
+        "transfers.drip.source.reminder",
+        "Les billets que vous avez envoyés à {recipient} n'ont toujours pas été réclamés. Faites un petit rappel!",
+    ),
+    (
+        "transfers.drip.destination.day_of",
+        "C'est le moment d'agir! L'événement a lieu {time_of_day} et les billets que {sender} vous a envoyés vous attendent toujours!",
+    ),
+    (
+        "transfers.drip.destination.day_before",
+        "Récupérez vos billets! L'événement a lieu DEMAIN et vous devez encore récupérer les billets que {sender} vous a envoyés!",
+    ),
+    (
+        "transfers.drip.destination.week_before",
+        "L'événement a lieu dans seulement une semaine et vous devez encore récupérer les billets que {sender} vous a envoyés!",
+    ),
+    (
+        "transfers.drip.destination.reminder",
+        "Vous devez encore récupérer les billets que {sender} vous a envoyés!",
+    ),
+    // SMS
+    (
+        "sms.box_office.checkin_instructions",
+        "Merci pour votre achat! Votre numéro de commande est le #{order_number}. Présentez-vous à l'entrée et donnez votre prénom et votre nom au portier pour qu'il puisse vous enregistrer.",
+    ),
+    ("sms.transfers.cancelled", "{sender} a annulé le transfert de ses billets."),
+    ("sms.transfers.drip_reminder", "{header} Suivez ce lien pour les recevoir : {link}"),
+    (
+        "sms.transfers.sent",
+        "{sender} vous a envoyé des billets. Suivez ce lien pour les recevoir : {link}",
+    ),
+    // Push notifications
+    ("push.transfers.received", "{sender} vous a envoyé des billets."),
+    // Email subjects
+    ("email.orders.confirmation.title", "{site_name} : Achat confirmé"),
+    ("email.orders.refund.title", "{site_name} : Remboursement"),
+    ("email.organization_invites.title", "{site_name} : Invitations"),
+    ("email.reports.ticket_counts.title", "{site_name} : Décompte des billets"),
+    ("email.settlements.statement.title", "{site_name} : Relevé de règlement"),
+    ("email.transfers.sent.title", "{sender_name} vous a envoyé des billets"),
+    (
+        "email.transfers.drip_reminder.title",
+        "{site_name} : Rappel de transfert de billets",
+    ),
+    ("email.transfers.sent_receipt.title", "{site_name} : Transfert de billets envoyé"),
+    ("email.transfers.cancelled_receipt.title", "{site_name} : Transfert de billets annulé"),
+    ("email.transfers.cancelled.title", "{sender_name} a annulé son transfert de billets"),
+    ("email.users.registered.title", "{site_name} : Inscription"),
+    (
+        "email.users.password_reset.title",
+        "{site_name} : Demande de réinitialisation du mot de passe",
+    ),
+    ("email.users.invite.title", "{site_name} : Invitation"),
+    ("email.users.magic_link.title", "Bienvenue sur {site_name}"),
+    // Errors
+    ("errors.connection", "Erreur de connexion"),
+    ("errors.delete", "Impossible de supprimer l'enregistrement"),
+    ("errors.duplicate_record", "Un enregistrement identique existe déjà"),
+    ("errors.insert", "Impossible d'ajouter l'enregistrement"),
+    ("errors.internal", "Erreur interne"),
+    ("errors.invalid_input", "Entrée invalide"),
+    ("errors.missing_input", "Entrée manquante"),
+    ("errors.no_results", "Aucun résultat"),
+    ("errors.not_found", "Introuvable"),
+    ("errors.query", "Erreur de requête"),
+    ("errors.unknown", "Erreur inconnue"),
+    ("errors.unknown_cause", "Cause inconnue"),
+    ("errors.update", "Impossible de mettre à jour l'enregistrement"),
+    ("errors.validation", "Erreur de validation"),
+    // Validation messages
+    ("validation.answer_required", "Une réponse est requise"),
+    (
+        "validation.cart_event_limit_reached",
+        "Vous avez déjà des billets pour un autre événement dans votre panier. Veuillez vider votre panier avant d'acheter des billets pour cet événement.",
+    ),
+    (
+        "validation.code_not_valid",
+        "Le code n'est pas valide pour la date et l'heure actuelles",
+    ),
+    ("validation.custom_message_empty", "Les messages personnalisés ne peuvent pas être vides"),
+    ("validation.email_in_use", "Ce courriel est déjà utilisé"),
+    ("validation.email_invalid", "Le courriel est invalide"),
+    ("validation.iban_invalid", "L'IBAN est invalide"),
+    (
+        "validation.insufficient_add_on_inventory",
+        "Cet extra n'est plus disponible en quantité suffisante",
+    ),
+    ("validation.max_uses_reached", "Le code a atteint sa limite d'utilisation"),
+    ("validation.url_invalid", "L'URL est invalide"),
+];
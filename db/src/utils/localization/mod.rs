@@ -0,0 +1,175 @@
+use models::Locales;
+
+mod en;
+mod es;
+mod fr;
+
+/// Message catalog for the locale, keyed by message key
+fn catalog(locale: Locales) -> &'static [(&'static str, &'static str)] {
+    match locale {
+        Locales::En => en::MESSAGES,
+        Locales::Es => es::MESSAGES,
+        Locales::Fr => fr::MESSAGES,
+    }
+}
+
+fn lookup(locale: Locales, key: &str) -> Option<&'static str> {
+    catalog(locale)
+        .iter()
+        .find(|(message_key, _)| *message_key == key)
+        .map(|(_, message)| *message)
+}
+
+/// Looks up the message for the key, falling back to English and then to the key itself when the message
+/// is missing from the catalog. `{name}` placeholders are replaced with matching arguments, unmatched
+/// placeholders are left in place for downstream templating.
+pub fn translate(locale: Locales, key: &str, args: &[(&str, &str)]) -> String {
+    let mut message = lookup(locale, key)
+        .or_else(|| lookup(Locales::En, key))
+        .unwrap_or(key)
+        .to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), value);
+    }
+    message
+}
+
+/// Translates a message that was generated in English (e.g. validation messages), leaving it as is when
+/// it is not part of the catalog
+pub fn translate_message(locale: Locales, message: &str) -> String {
+    en::MESSAGES
+        .iter()
+        .find(|(_, english_message)| *english_message == message)
+        .and_then(|(key, _)| lookup(locale, key))
+        .unwrap_or(message)
+        .to_string()
+}
+
+/// Picks the best supported locale from an `Accept-Language` header honouring quality values,
+/// regional variants like `fr-CA` match on their primary language
+pub fn locale_from_accept_language(accept_language: &str) -> Option<Locales> {
+    let mut languages: Vec<(&str, f32)> = accept_language
+        .split(',')
+        .filter_map(|language_range| {
+            let mut parts = language_range.split(';').map(|p| p.trim());
+            let tag = parts.next().filter(|t| !t.is_empty())?;
+            let quality = parts
+                .find_map(|p| {
+                    if p.starts_with("q=") {
+                        p[2..].parse::<f32>().ok()
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(1.0);
+            Some((tag, quality))
+        })
+        .filter(|(_, quality)| *quality > 0.0)
+        .collect();
+    // Stable sort keeps header order for equal quality values
+    languages.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(::std::cmp::Ordering::Equal));
+
+    languages.into_iter().find_map(|(tag, _)| {
+        let primary_language = tag.split('-').next().unwrap_or(tag);
+        primary_language.parse::<Locales>().ok()
+    })
+}
+
+/// Locale for a request, an explicit user preference wins over the browser's `Accept-Language` which
+/// wins over the organization default
+pub fn resolve_locale(
+    user_locale: Option<Locales>,
+    accept_language: Option<&str>,
+    organization_locale: Option<Locales>,
+) -> Locales {
+    user_locale
+        .or_else(|| accept_language.and_then(locale_from_accept_language))
+        .or(organization_locale)
+        .unwrap_or_default()
+}
+
+/// Locale for messages sent outside of a request (emails, SMS and push notifications)
+pub fn recipient_locale(user_locale: Option<Locales>, organization_locale: Option<Locales>) -> Locales {
+    resolve_locale(user_locale, None, organization_locale)
+}
+
+#[test]
+fn translate_with_fallbacks() {
+    assert_eq!(
+        translate(Locales::Fr, "push.transfers.received", &[("sender", "Bob M.")]),
+        "Bob M. vous a envoyé des billets."
+    );
+    assert_eq!(
+        translate(Locales::Es, "push.transfers.received", &[("sender", "Bob M.")]),
+        "Bob M. te envió boletos."
+    );
+    assert_eq!(
+        translate(Locales::En, "push.transfers.received", &[("sender", "Bob M.")]),
+        "Bob M. has sent you some tickets."
+    );
+    // Placeholders without arguments are left for templating
+    assert_eq!(
+        translate(Locales::Es, "email.transfers.sent.title", &[]),
+        "{sender_name} te envió boletos"
+    );
+    assert_eq!(translate(Locales::Fr, "missing.key", &[]), "missing.key");
+}
+
+#[test]
+fn translate_message_from_english() {
+    assert_eq!(
+        translate_message(Locales::Fr, "Email is invalid"),
+        "Le courriel est invalide"
+    );
+    assert_eq!(
+        translate_message(Locales::Es, "Email is invalid"),
+        "El correo electrónico no es válido"
+    );
+    assert_eq!(
+        translate_message(Locales::Fr, "Not in the catalog"),
+        "Not in the catalog"
+    );
+}
+
+#[test]
+fn catalogs_cover_english_keys() {
+    for locale in &[Locales::Es, Locales::Fr] {
+        for (key, _) in en::MESSAGES {
+            assert!(
+                lookup(*locale, key).is_some(),
+                "{} missing from {} catalog",
+                key,
+                locale
+            );
+        }
+        assert_eq!(catalog(*locale).len(), en::MESSAGES.len());
+    }
+}
+
+#[test]
+fn locale_from_accept_language_header() {
+    assert_eq!(
+        locale_from_accept_language("fr-CA,fr;q=0.9,en;q=0.8"),
+        Some(Locales::Fr)
+    );
+    assert_eq!(
+        locale_from_accept_language("de-DE, es-MX;q=0.7, en;q=0.5"),
+        Some(Locales::Es)
+    );
+    assert_eq!(locale_from_accept_language("en;q=0.5, es;q=0.9"), Some(Locales::Es));
+    assert_eq!(locale_from_accept_language("fr;q=0, en-US"), Some(Locales::En));
+    assert_eq!(locale_from_accept_language("de, *"), None);
+    assert_eq!(locale_from_accept_language(""), None);
+}
+
+#[test]
+fn resolve_locale_precedence() {
+    assert_eq!(
+        resolve_locale(Some(Locales::Es), Some("fr-CA"), Some(Locales::En)),
+        Locales::Es
+    );
+    assert_eq!(resolve_locale(None, Some("fr-CA"), Some(Locales::Es)), Locales::Fr);
+    assert_eq!(resolve_locale(None, Some("de"), Some(Locales::Es)), Locales::Es);
+    assert_eq!(resolve_locale(None, None, None), Locales::En);
+    assert_eq!(recipient_locale(None, Some(Locales::Fr)), Locales::Fr);
+}
@@ -1,14 +1,19 @@
+pub mod csv;
 pub mod dates;
 pub mod encryption;
 pub mod errors;
 pub mod hash;
 pub mod iterators;
+pub mod localization;
 mod math;
 pub mod migration;
 pub mod pagination;
 pub mod passwords;
+pub mod pdf;
 pub mod rand;
+pub mod recurrence;
 pub mod regexes;
 pub mod text;
+pub mod totp;
 pub use self::math::*;
 pub mod boxed_query;
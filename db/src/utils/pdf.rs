@@ -0,0 +1,203 @@
+/// Minimal PDF builder used for the printable statements served by the API. Documents are text
+/// only, laid out top to bottom on US Letter pages using the standard Helvetica fonts so no font
+/// data needs to be embedded.
+pub struct PdfWriter {
+    title: String,
+    pages: Vec<String>,
+    content: String,
+    cursor: f32,
+}
+
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 50.0;
+const HEADER_HEIGHT: f32 = 60.0;
+const LINE_SPACING: f32 = 1.4;
+// Average Helvetica glyph width relative to font size, used to truncate columns
+const AVERAGE_GLYPH_WIDTH: f32 = 0.5;
+
+impl PdfWriter {
+    /// The title is drawn in the branded header band at the top of every page
+    pub fn new(title: &str) -> PdfWriter {
+        let mut writer = PdfWriter {
+            title: title.to_string(),
+            pages: Vec::new(),
+            content: String::new(),
+            cursor: 0.0,
+        };
+        writer.start_page();
+        writer
+    }
+
+    pub fn heading(&mut self, text: &str) {
+        self.spacer();
+        self.row(&[(0.0, text)], 13.0, true);
+    }
+
+    pub fn line(&mut self, text: &str) {
+        self.row(&[(0.0, text)], 10.0, false);
+    }
+
+    /// Writes a line of text columns, each column is offset from the left margin and truncated
+    /// so it does not run into the following column
+    pub fn row<T: AsRef<str>>(&mut self, columns: &[(f32, T)], size: f32, bold: bool) {
+        let height = size * LINE_SPACING;
+        if self.cursor - height < MARGIN {
+            self.finish_page();
+            self.start_page();
+        }
+        self.cursor -= height;
+
+        let font = if bold { "F2" } else { "F1" };
+        for (index, (offset, text)) in columns.iter().enumerate() {
+            let available_width = columns
+                .get(index + 1)
+                .map(|(next_offset, _)| next_offset - offset)
+                .unwrap_or(PAGE_WIDTH - 2.0 * MARGIN - offset);
+            let max_chars = (available_width / (size * AVERAGE_GLYPH_WIDTH)) as usize;
+            let text: String = text.as_ref().chars().take(max_chars.saturating_sub(1)).collect();
+            self.text(MARGIN + offset, self.cursor, size, font, &text);
+        }
+    }
+
+    pub fn spacer(&mut self) {
+        self.cursor -= 10.0;
+    }
+
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        self.finish_page();
+
+        let page_count = self.pages.len();
+        let mut objects: Vec<String> = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                (0..page_count)
+                    .map(|index| format!("{} 0 R", 5 + index * 2))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                page_count
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+        ];
+        for (index, content) in self.pages.iter().enumerate() {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                6 + index * 2
+            ));
+            objects.push(format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content));
+        }
+
+        let mut output = "%PDF-1.4\n".to_string();
+        let mut offsets = Vec::new();
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(output.len());
+            output.push_str(&format!("{} 0 obj\n{}\nendobj\n", index + 1, object));
+        }
+        let xref_offset = output.len();
+        output.push_str(&format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1));
+        for offset in offsets {
+            output.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        output.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        ));
+
+        output.into_bytes()
+    }
+
+    fn start_page(&mut self) {
+        let band_bottom = PAGE_HEIGHT - HEADER_HEIGHT;
+        self.content = format!(
+            "0.12 0.16 0.29 rg\n0 {} {} {} re f\n1 1 1 rg\n",
+            band_bottom, PAGE_WIDTH, HEADER_HEIGHT
+        );
+        let title = self.title.clone();
+        self.text(MARGIN, band_bottom + 24.0, 18.0, "F2", &title);
+        self.content.push_str("0 0 0 rg\n");
+        self.cursor = band_bottom - 20.0;
+    }
+
+    fn finish_page(&mut self) {
+        let page_number = self.pages.len() + 1;
+        let footer = format!("Page {}", page_number);
+        self.text(MARGIN, MARGIN / 2.0, 8.0, "F1", &footer);
+        self.pages.push(self.content.clone());
+        self.content = String::new();
+    }
+
+    fn text(&mut self, x: f32, y: f32, size: f32, font: &str, text: &str) {
+        self.content.push_str(&format!(
+            "BT /{} {} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            font,
+            size,
+            x,
+            y,
+            escape_text(text)
+        ));
+    }
+}
+
+/// Escapes a string for use in a PDF literal string. Characters outside of ASCII are written as
+/// WinAnsi octal codes where one exists, otherwise they are replaced with `?`.
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' | '\r' | '\t' => escaped.push(' '),
+            ' '..='~' => escaped.push(c),
+            '€' => escaped.push_str("\\200"),
+            '\u{a0}'..='\u{ff}' => escaped.push_str(&format!("\\{:03o}", c as u32)),
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+#[test]
+fn escape_text_escapes_delimiters_and_encodes_symbols() {
+    assert_eq!(escape_text("plain"), "plain");
+    assert_eq!(escape_text("Total (net)"), "Total \\(net\\)");
+    assert_eq!(escape_text("back\\slash"), "back\\\\slash");
+    assert_eq!(escape_text("€5 £5"), "\\2005 \\2435");
+    assert_eq!(escape_text("日本"), "??");
+}
+
+#[test]
+fn into_bytes() {
+    let mut writer = PdfWriter::new("Statement");
+    writer.heading("Summary");
+    writer.row(&[(0.0, "Name"), (100.0, "Total")], 10.0, false);
+    let document = String::from_utf8(writer.into_bytes()).unwrap();
+    assert!(document.starts_with("%PDF-1.4\n"));
+    assert!(document.contains("(Statement) Tj"));
+    assert!(document.contains("(Summary) Tj"));
+    assert!(document.contains("(Total) Tj"));
+    assert!(document.contains("/Count 1"));
+    assert!(document.ends_with("%%EOF\n"));
+
+    // Cross reference offsets point at the start of each object
+    let xref_offset: usize = document.lines().rev().nth(1).unwrap().parse().unwrap();
+    let first_offset: usize = document[xref_offset..].lines().nth(3).unwrap()[..10].parse().unwrap();
+    assert!(document[first_offset..].starts_with("1 0 obj"));
+}
+
+#[test]
+fn row_adds_pages_when_full() {
+    let mut writer = PdfWriter::new("Statement");
+    for _ in 0..60 {
+        writer.line("Entry");
+    }
+    let document = String::from_utf8(writer.into_bytes()).unwrap();
+    assert!(document.contains("/Count 2"));
+    assert!(document.contains("(Page 2) Tj"));
+}
@@ -0,0 +1,369 @@
+use chrono::prelude::*;
+use chrono::Duration;
+use std::str::FromStr;
+
+// Safety net for rules that rarely match (e.g. the 31st of every month) so generation always terminates
+const MAX_PERIODS: i64 = 5000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Weekday in a `BYDAY` list, monthly rules can prefix it with an ordinal (`1FR` first Friday, `-1SA`
+/// last Saturday)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ByDay {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+/// Subset of the iCalendar RRULE (RFC 5545) used for event series: `FREQ` (`DAILY`, `WEEKLY` or
+/// `MONTHLY`), `INTERVAL`, `BYDAY`, `BYMONTHDAY` and either `COUNT` or `UNTIL`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub by_month_day: Vec<i32>,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDateTime>,
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = rule.trim();
+        let rule = if rule.to_uppercase().starts_with("RRULE:") {
+            &rule[6..]
+        } else {
+            rule
+        };
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = vec![];
+        let mut by_month_day = vec![];
+        let mut count = None;
+        let mut until = None;
+        for part in rule.split(';').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            let mut name_value = part.splitn(2, '=');
+            let name = name_value.next().unwrap_or("").to_uppercase();
+            let value = name_value
+                .next()
+                .ok_or_else(|| format!("{} is missing a value", name))?
+                .to_uppercase();
+            match name.as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(format!("Unsupported frequency {}", value)),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|i| *i > 0)
+                        .ok_or_else(|| "INTERVAL must be a positive number".to_string())?
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        by_day.push(parse_by_day(day)?);
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for day in value.split(',') {
+                        by_month_day.push(
+                            day.parse::<i32>()
+                                .ok()
+                                .filter(|d| *d != 0 && d.abs() <= 31)
+                                .ok_or_else(|| format!("Invalid BYMONTHDAY {}", day))?,
+                        );
+                    }
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|c| *c > 0)
+                            .ok_or_else(|| "COUNT must be a positive number".to_string())?,
+                    )
+                }
+                "UNTIL" => until = Some(parse_until(&value)?),
+                _ => return Err(format!("Unsupported rule part {}", name)),
+            }
+        }
+
+        let frequency = frequency.ok_or_else(|| "FREQ is required".to_string())?;
+        if count.is_some() && until.is_some() {
+            return Err("COUNT and UNTIL cannot both be specified".to_string());
+        }
+        if frequency != Frequency::Monthly && by_day.iter().any(|d| d.ordinal.is_some()) {
+            return Err("BYDAY ordinals are only supported for monthly rules".to_string());
+        }
+        if frequency != Frequency::Monthly && !by_month_day.is_empty() {
+            return Err("BYMONTHDAY is only supported for monthly rules".to_string());
+        }
+        if frequency == Frequency::Daily && !by_day.is_empty() {
+            return Err("BYDAY is not supported for daily rules".to_string());
+        }
+
+        Ok(RecurrenceRule {
+            frequency,
+            interval,
+            by_day,
+            by_month_day,
+            count,
+            until,
+        })
+    }
+}
+
+impl RecurrenceRule {
+    /// Occurrences matching the rule on or after `start`, keeping its time of day. Generation stops at
+    /// the rule's `COUNT` or `UNTIL`, at `end` (inclusive) or after `limit` occurrences, whichever is
+    /// first. All times are local to the series' venue.
+    pub fn occurrences(&self, start: NaiveDateTime, end: NaiveDateTime, limit: usize) -> Vec<NaiveDateTime> {
+        let mut occurrences = vec![];
+        let limit = match self.count {
+            Some(count) => limit.min(count as usize),
+            None => limit,
+        };
+        let end = match self.until {
+            Some(until) => end.min(until),
+            None => end,
+        };
+
+        for period in 0..MAX_PERIODS {
+            for date in self.dates_in_period(start.date(), period) {
+                if date < start.date() {
+                    continue;
+                }
+                let occurrence = date.and_time(start.time());
+                if occurrence > end || occurrences.len() >= limit {
+                    return occurrences;
+                }
+                occurrences.push(occurrence);
+            }
+        }
+
+        occurrences
+    }
+
+    fn dates_in_period(&self, start: NaiveDate, period: i64) -> Vec<NaiveDate> {
+        let step = period * self.interval as i64;
+        let mut dates = match self.frequency {
+            Frequency::Daily => vec![start + Duration::days(step)],
+            Frequency::Weekly => {
+                let week_start =
+                    start - Duration::days(start.weekday().num_days_from_monday() as i64) + Duration::weeks(step);
+                if self.by_day.is_empty() {
+                    vec![week_start + Duration::days(start.weekday().num_days_from_monday() as i64)]
+                } else {
+                    self.by_day
+                        .iter()
+                        .map(|d| week_start + Duration::days(d.weekday.num_days_from_monday() as i64))
+                        .collect()
+                }
+            }
+            Frequency::Monthly => {
+                let months = start.year() as i64 * 12 + start.month0() as i64 + step;
+                let (year, month) = ((months / 12) as i32, (months % 12) as u32 + 1);
+                let days_in_month = days_in_month(year, month);
+                let mut dates = vec![];
+                for month_day in &self.by_month_day {
+                    let day = if *month_day > 0 {
+                        *month_day
+                    } else {
+                        days_in_month as i32 + month_day + 1
+                    };
+                    if day >= 1 && day <= days_in_month as i32 {
+                        dates.push(NaiveDate::from_ymd(year, month, day as u32));
+                    }
+                }
+                for by_day in &self.by_day {
+                    let matching_days = (1..=days_in_month)
+                        .map(|day| NaiveDate::from_ymd(year, month, day))
+                        .filter(|date| date.weekday() == by_day.weekday)
+                        .collect::<Vec<NaiveDate>>();
+                    match by_day.ordinal {
+                        Some(ordinal) if ordinal > 0 => {
+                            dates.extend(matching_days.get(ordinal as usize - 1));
+                        }
+                        Some(ordinal) => {
+                            let from_end = (-ordinal) as usize;
+                            if from_end <= matching_days.len() {
+                                dates.push(matching_days[matching_days.len() - from_end]);
+                            }
+                        }
+                        None => dates.extend(matching_days),
+                    }
+                }
+                if self.by_month_day.is_empty() && self.by_day.is_empty() && start.day() <= days_in_month {
+                    dates.push(NaiveDate::from_ymd(year, month, start.day()));
+                }
+                dates
+            }
+        };
+        dates.sort();
+        dates.dedup();
+        dates
+    }
+}
+
+fn parse_by_day(day: &str) -> Result<ByDay, String> {
+    let day = day.trim();
+    if day.len() < 2 {
+        return Err(format!("Invalid BYDAY {}", day));
+    }
+    let (ordinal, weekday) = day.split_at(day.len() - 2);
+    let weekday = match weekday {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return Err(format!("Invalid BYDAY {}", day)),
+    };
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        Some(
+            ordinal
+                .trim_start_matches('+')
+                .parse::<i32>()
+                .ok()
+                .filter(|o| *o != 0 && o.abs() <= 5)
+                .ok_or_else(|| format!("Invalid BYDAY {}", day))?,
+        )
+    };
+    Ok(ByDay { ordinal, weekday })
+}
+
+fn parse_until(until: &str) -> Result<NaiveDateTime, String> {
+    let until = until.trim_end_matches('Z');
+    NaiveDateTime::parse_from_str(until, "%Y%m%dT%H%M%S")
+        .or_else(|_| NaiveDate::parse_from_str(until, "%Y%m%d").map(|date| date.and_hms(23, 59, 59)))
+        .map_err(|_| format!("Invalid UNTIL {}", until))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd(next_year, next_month, 1).pred().day()
+}
+
+#[test]
+fn parse_rules() {
+    let rule: RecurrenceRule = "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;COUNT=10".parse().unwrap();
+    assert_eq!(rule.frequency, Frequency::Weekly);
+    assert_eq!(rule.interval, 2);
+    assert_eq!(
+        rule.by_day,
+        vec![
+            ByDay {
+                ordinal: None,
+                weekday: Weekday::Tue
+            },
+            ByDay {
+                ordinal: None,
+                weekday: Weekday::Thu
+            }
+        ]
+    );
+    assert_eq!(rule.count, Some(10));
+
+    let rule: RecurrenceRule = "freq=monthly;byday=-1fr;until=20201231".parse().unwrap();
+    assert_eq!(
+        rule.by_day,
+        vec![ByDay {
+            ordinal: Some(-1),
+            weekday: Weekday::Fri
+        }]
+    );
+    assert_eq!(rule.until, Some(NaiveDate::from_ymd(2020, 12, 31).and_hms(23, 59, 59)));
+
+    assert!("INTERVAL=2".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=YEARLY".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=WEEKLY;COUNT=2;UNTIL=20201231".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=WEEKLY;BYDAY=1FR".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=WEEKLY;BYSETPOS=1".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=DAILY;INTERVAL=0".parse::<RecurrenceRule>().is_err());
+}
+
+#[test]
+fn weekly_occurrences() {
+    // Wednesday 8pm
+    let start = NaiveDate::from_ymd(2020, 6, 3).and_hms(20, 0, 0);
+    let end = NaiveDate::from_ymd(2021, 1, 1).and_hms(0, 0, 0);
+    let rule: RecurrenceRule = "FREQ=WEEKLY;COUNT=3".parse().unwrap();
+    assert_eq!(
+        rule.occurrences(start, end, 100),
+        vec![
+            NaiveDate::from_ymd(2020, 6, 3).and_hms(20, 0, 0),
+            NaiveDate::from_ymd(2020, 6, 10).and_hms(20, 0, 0),
+            NaiveDate::from_ymd(2020, 6, 17).and_hms(20, 0, 0),
+        ]
+    );
+
+    let rule: RecurrenceRule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE,FR".parse().unwrap();
+    assert_eq!(
+        rule.occurrences(start, end, 4),
+        vec![
+            NaiveDate::from_ymd(2020, 6, 3).and_hms(20, 0, 0),
+            NaiveDate::from_ymd(2020, 6, 5).and_hms(20, 0, 0),
+            NaiveDate::from_ymd(2020, 6, 15).and_hms(20, 0, 0),
+            NaiveDate::from_ymd(2020, 6, 17).and_hms(20, 0, 0),
+        ]
+    );
+
+    let rule: RecurrenceRule = "FREQ=DAILY;UNTIL=20200605T200000Z".parse().unwrap();
+    assert_eq!(rule.occurrences(start, end, 100).len(), 3);
+}
+
+#[test]
+fn monthly_occurrences() {
+    let start = NaiveDate::from_ymd(2020, 1, 31).and_hms(19, 30, 0);
+    let end = NaiveDate::from_ymd(2020, 6, 1).and_hms(0, 0, 0);
+
+    // Months without a 31st are skipped
+    let rule: RecurrenceRule = "FREQ=MONTHLY".parse().unwrap();
+    assert_eq!(
+        rule.occurrences(start, end, 100),
+        vec![
+            NaiveDate::from_ymd(2020, 1, 31).and_hms(19, 30, 0),
+            NaiveDate::from_ymd(2020, 3, 31).and_hms(19, 30, 0),
+            NaiveDate::from_ymd(2020, 5, 31).and_hms(19, 30, 0),
+        ]
+    );
+
+    let rule: RecurrenceRule = "FREQ=MONTHLY;BYMONTHDAY=-1".parse().unwrap();
+    assert_eq!(
+        rule.occurrences(start, end, 3),
+        vec![
+            NaiveDate::from_ymd(2020, 1, 31).and_hms(19, 30, 0),
+            NaiveDate::from_ymd(2020, 2, 29).and_hms(19, 30, 0),
+            NaiveDate::from_ymd(2020, 3, 31).and_hms(19, 30, 0),
+        ]
+    );
+
+    let start = NaiveDate::from_ymd(2020, 1, 1).and_hms(19, 30, 0);
+    let rule: RecurrenceRule = "FREQ=MONTHLY;BYDAY=1FR,-1SA".parse().unwrap();
+    assert_eq!(
+        rule.occurrences(start, end, 4),
+        vec![
+            NaiveDate::from_ymd(2020, 1, 3).and_hms(19, 30, 0),
+            NaiveDate::from_ymd(2020, 1, 25).and_hms(19, 30, 0),
+            NaiveDate::from_ymd(2020, 2, 7).and_hms(19, 30, 0),
+            NaiveDate::from_ymd(2020, 2, 29).and_hms(19, 30, 0),
+        ]
+    );
+}
@@ -0,0 +1,56 @@
+use ring::constant_time::verify_slices_are_equal;
+use ring::{digest, hmac};
+
+const CODE_DIGITS: u32 = 6;
+
+/// Time based one time password (RFC 6238) for the given secret, rotating every `period_seconds`
+pub fn code(secret: &str, timestamp: i64, period_seconds: i64) -> String {
+    hotp(secret, (timestamp / period_seconds) as u64)
+}
+
+/// Accepts codes from up to `drift_periods` periods either side of the current one to allow for
+/// clock differences between the ticket holder's device and the server
+pub fn verify(secret: &str, code: &str, timestamp: i64, period_seconds: i64, drift_periods: i64) -> bool {
+    let counter = timestamp / period_seconds;
+    ((counter - drift_periods)..=(counter + drift_periods))
+        .filter(|counter| *counter >= 0)
+        .any(|counter| verify_slices_are_equal(hotp(secret, counter as u64).as_bytes(), code.as_bytes()).is_ok())
+}
+
+fn hotp(secret: &str, counter: u64) -> String {
+    let key = hmac::SigningKey::new(&digest::SHA1, secret.as_bytes());
+    let signature = hmac::sign(&key, &counter.to_be_bytes());
+    let hash = signature.as_ref();
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let truncated = (u32::from(hash[offset]) & 0x7f) << 24
+        | u32::from(hash[offset + 1]) << 16
+        | u32::from(hash[offset + 2]) << 8
+        | u32::from(hash[offset + 3]);
+    format!(
+        "{:0width$}",
+        truncated % 10u32.pow(CODE_DIGITS),
+        width = CODE_DIGITS as usize
+    )
+}
+
+#[test]
+fn rfc_6238_test_vectors() {
+    // Last six digits of the SHA-1 vectors from RFC 6238 appendix B
+    let secret = "12345678901234567890";
+    assert_eq!(code(secret, 59, 30), "287082");
+    assert_eq!(code(secret, 1111111109, 30), "081804");
+    assert_eq!(code(secret, 1234567890, 30), "005924");
+    assert_eq!(code(secret, 2000000000, 30), "279037");
+}
+
+#[test]
+fn verify_within_drift() {
+    let secret = "12345678901234567890";
+    let current = code(secret, 1111111109, 30);
+    assert!(verify(secret, &current, 1111111109, 30, 1));
+    assert!(verify(secret, &current, 1111111109 + 30, 30, 1));
+    assert!(verify(secret, &current, 1111111109 - 30, 30, 1));
+    assert!(!verify(secret, &current, 1111111109 + 60, 30, 1));
+    assert!(!verify(secret, "000000", 1111111109, 30, 1));
+    assert!(!verify("another secret", &current, 1111111109, 30, 1));
+}
@@ -0,0 +1,218 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().finish();
+
+    let access_zone = AccessZone::create(event.id, "VIP deck".to_string(), ReEntryPolicies::NoReEntry)
+        .commit(Some(user.id), connection)
+        .unwrap();
+    assert_eq!(access_zone.event_id, event.id);
+    assert_eq!(access_zone.name, "VIP deck".to_string());
+    assert_eq!(access_zone.re_entry_policy, ReEntryPolicies::NoReEntry);
+
+    let domain_events = DomainEvent::find(
+        Tables::AccessZones,
+        Some(access_zone.id),
+        Some(DomainEventTypes::AccessZoneCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn commit_blank_name() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().finish();
+
+    let result = AccessZone::create(event.id, "".to_string(), ReEntryPolicies::Unlimited).commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("name"));
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn update() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().finish();
+    let access_zone = AccessZone::create(event.id, "VIP".to_string(), ReEntryPolicies::Unlimited)
+        .commit(None, connection)
+        .unwrap();
+
+    let attributes = AccessZoneEditableAttributes {
+        name: Some("Backstage".to_string()),
+        re_entry_policy: Some(ReEntryPolicies::NoReEntry),
+    };
+    let access_zone = access_zone.update(attributes, None, connection).unwrap();
+    assert_eq!(access_zone.name, "Backstage".to_string());
+    assert_eq!(access_zone.re_entry_policy, ReEntryPolicies::NoReEntry);
+
+    let domain_events = DomainEvent::find(
+        Tables::AccessZones,
+        Some(access_zone.id),
+        Some(DomainEventTypes::AccessZoneUpdated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn delete() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().finish();
+    let access_zone = AccessZone::create(event.id, "VIP".to_string(), ReEntryPolicies::Unlimited)
+        .commit(None, connection)
+        .unwrap();
+    let access_zone2 = AccessZone::create(event.id, "GA".to_string(), ReEntryPolicies::Unlimited)
+        .commit(None, connection)
+        .unwrap();
+    assert_eq!(
+        AccessZone::find_for_event(event.id, connection).unwrap(),
+        vec![access_zone2.clone(), access_zone.clone()]
+    );
+
+    access_zone.delete(None, connection).unwrap();
+    assert!(AccessZone::find(access_zone.id, connection).is_err());
+    assert_eq!(
+        AccessZone::find_for_event(event.id, connection).unwrap(),
+        vec![access_zone2]
+    );
+}
+
+#[test]
+fn update_ticket_types() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_ticket_type_count(2).with_tickets().finish();
+    let ticket_types = event.ticket_types(true, None, connection).unwrap();
+    let other_event = project.create_event().with_tickets().finish();
+    let other_ticket_type = other_event.ticket_types(true, None, connection).unwrap().remove(0);
+    let access_zone = AccessZone::create(event.id, "VIP".to_string(), ReEntryPolicies::Unlimited)
+        .commit(None, connection)
+        .unwrap();
+    assert!(!access_zone.admits(ticket_types[0].id, connection).unwrap());
+
+    access_zone
+        .update_ticket_types(vec![ticket_types[0].id, ticket_types[1].id], connection)
+        .unwrap();
+    assert_eq!(
+        access_zone.ticket_type_ids(connection).unwrap(),
+        vec![ticket_types[0].id, ticket_types[1].id]
+    );
+    assert!(access_zone.admits(ticket_types[1].id, connection).unwrap());
+
+    access_zone
+        .update_ticket_types(vec![ticket_types[1].id], connection)
+        .unwrap();
+    assert_eq!(
+        access_zone.ticket_type_ids(connection).unwrap(),
+        vec![ticket_types[1].id]
+    );
+    assert!(!access_zone.admits(ticket_types[0].id, connection).unwrap());
+
+    // Ticket types of other events can't be mapped to the zone
+    let result = access_zone.update_ticket_types(vec![other_ticket_type.id], connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(
+                    errors["ticket_type_ids"][0].code,
+                    "ticket_type_does_not_belong_to_event"
+                );
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn occupancy() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_ticket_pricing().finish();
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let door_person = project.create_user().finish();
+    let user = project.create_user().finish();
+    project
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(2)
+        .is_paid()
+        .finish();
+    let tickets = TicketInstance::find_for_user(user.id, connection).unwrap();
+    let access_zone = AccessZone::create(event.id, "VIP".to_string(), ReEntryPolicies::Unlimited)
+        .commit(None, connection)
+        .unwrap();
+    access_zone
+        .update_ticket_types(vec![ticket_type.id], connection)
+        .unwrap();
+    let access_zone2 = AccessZone::create(event.id, "GA".to_string(), ReEntryPolicies::Unlimited)
+        .commit(None, connection)
+        .unwrap();
+    assert_eq!(access_zone.occupancy(connection).unwrap(), 0);
+
+    let scan = |ticket: &TicketInstance, direction: ScanDirections| {
+        TicketScan::scan(
+            &access_zone,
+            Some(ticket),
+            ticket.redeem_key.clone().unwrap(),
+            None,
+            direction,
+            door_person.id,
+            None,
+            connection,
+        )
+        .unwrap()
+    };
+    scan(&tickets[0], ScanDirections::Entry);
+    scan(&tickets[1], ScanDirections::Entry);
+    assert_eq!(access_zone.occupancy(connection).unwrap(), 2);
+
+    scan(&tickets[0], ScanDirections::Exit);
+    assert_eq!(access_zone.occupancy(connection).unwrap(), 1);
+    // Rejected scans don't change the occupancy
+    scan(&tickets[1], ScanDirections::Entry);
+    assert_eq!(access_zone.occupancy(connection).unwrap(), 1);
+
+    assert_eq!(
+        AccessZone::occupancy_for_event(event.id, connection).unwrap(),
+        vec![
+            AccessZoneOccupancy {
+                access_zone_id: access_zone2.id,
+                name: "GA".to_string(),
+                occupancy: 0,
+            },
+            AccessZoneOccupancy {
+                access_zone_id: access_zone.id,
+                name: "VIP".to_string(),
+                occupancy: 1,
+            },
+        ]
+    );
+
+    let display_access_zone = access_zone.for_display(connection).unwrap();
+    assert_eq!(display_access_zone.ticket_type_ids, vec![ticket_type.id]);
+    assert_eq!(display_access_zone.occupancy, 1);
+}
@@ -0,0 +1,103 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+
+    let mapping = AccountingAccountMapping::create(
+        organization.id,
+        LedgerAccountTypes::GrossSales,
+        " 4000 ".to_string(),
+        "Ticket Revenue".to_string(),
+    )
+    .commit(Some(user.id), connection)
+    .unwrap();
+    assert_eq!(mapping.organization_id, organization.id);
+    assert_eq!(mapping.account_code, "4000".to_string());
+
+    // Existing mappings are replaced
+    let updated_mapping = AccountingAccountMapping::create(
+        organization.id,
+        LedgerAccountTypes::GrossSales,
+        "4010".to_string(),
+        "Event Revenue".to_string(),
+    )
+    .commit(Some(user.id), connection)
+    .unwrap();
+    assert_eq!(updated_mapping.id, mapping.id);
+    assert_eq!(updated_mapping.account_code, "4010".to_string());
+    assert_eq!(updated_mapping.account_name, "Event Revenue".to_string());
+
+    let domain_events = DomainEvent::find(
+        Tables::Organizations,
+        Some(organization.id),
+        Some(DomainEventTypes::AccountingAccountMappingUpdated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(2, domain_events.len());
+}
+
+#[test]
+fn commit_with_invalid_details() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+
+    let result = AccountingAccountMapping::create(
+        organization.id,
+        LedgerAccountTypes::Cash,
+        "".to_string(),
+        "Bank\tAccount".to_string(),
+    )
+    .commit(None, connection);
+    match result {
+        Ok(_) => panic!("Expected validation error"),
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("account_code"));
+                assert_eq!(errors["account_code"][0].code, "length");
+                assert!(errors.contains_key("account_name"));
+                assert_eq!(errors["account_name"][0].code, "invalid_characters");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn resolve_for_organization() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    AccountingAccountMapping::create(
+        organization.id,
+        LedgerAccountTypes::Cash,
+        "1000".to_string(),
+        "Checking".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+
+    let mappings = AccountingAccountMapping::resolve_for_organization(organization.id, connection).unwrap();
+    assert_eq!(mappings.len(), LEDGER_ACCOUNT_TYPES.len());
+    assert_eq!(
+        mappings[0],
+        DisplayAccountingAccountMapping {
+            account_type: LedgerAccountTypes::Cash,
+            account_code: "1000".to_string(),
+            account_name: "Checking".to_string(),
+            is_default: false,
+        }
+    );
+    assert_eq!(
+        mappings[2],
+        AccountingAccountMapping::default_mapping(LedgerAccountTypes::GrossSales)
+    );
+    assert!(mappings[2].is_default);
+}
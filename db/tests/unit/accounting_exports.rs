@@ -0,0 +1,131 @@
+use chrono::prelude::*;
+use db::dev::TestProject;
+use db::models::*;
+
+fn paid_order_export(format: AccountingExportFormats) -> (AccountingExport, i64, i64, String) {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().with_fees().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let order = project.create_order().for_event(&event).quantity(2).is_paid().finish();
+    AccountingAccountMapping::create(
+        organization.id,
+        LedgerAccountTypes::GrossSales,
+        "4000".to_string(),
+        "Ticket Revenue".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+
+    let items = order.items(connection).unwrap();
+    let gross_sales: i64 = items
+        .iter()
+        .filter(|i| i.item_type == OrderItemTypes::Tickets)
+        .map(|i| i.unit_price_in_cents * i.quantity)
+        .sum();
+    let total: i64 = items.iter().map(|i| i.unit_price_in_cents * i.quantity).sum();
+    let entry = LedgerJournalEntry::find_for_source(Tables::Orders, order.id, connection)
+        .unwrap()
+        .remove(0);
+    let date = organization
+        .timezone()
+        .unwrap()
+        .from_utc_datetime(&entry.created_at)
+        .naive_local()
+        .date();
+    let date = match format {
+        AccountingExportFormats::QuickbooksIif => date.format("%m/%d/%Y").to_string(),
+        AccountingExportFormats::XeroCsv => date.format("%Y-%m-%d").to_string(),
+    };
+
+    let export = AccountingExport::generate(&organization, Currencies::USD, None, None, format, connection).unwrap();
+    (export, gross_sales, total, date)
+}
+
+#[test]
+fn generate_quickbooks_iif() {
+    let (export, gross_sales, total, date) = paid_order_export(AccountingExportFormats::QuickbooksIif);
+    assert_eq!(export.file_name, "journal-usd-all-all.iif".to_string());
+
+    let lines: Vec<&str> = export.content.lines().collect();
+    assert_eq!(lines[0], "!TRNS\tTRNSID\tTRNSTYPE\tDATE\tACCNT\tAMOUNT\tDOCNUM\tMEMO");
+    assert_eq!(lines[1], "!SPL\tSPLID\tTRNSTYPE\tDATE\tACCNT\tAMOUNT\tDOCNUM\tMEMO");
+    assert_eq!(lines[2], "!ENDTRNS");
+    // The cash debit opens the transaction and the credits follow as splits
+    assert!(lines[3].starts_with(&format!(
+        "TRNS\t\tGENERAL JOURNAL\t{}\tUndeposited Funds\t{}.{:02}\t",
+        date,
+        total / 100,
+        total % 100
+    )));
+    assert!(lines.iter().any(|l| l.starts_with(&format!(
+        "SPL\t\tGENERAL JOURNAL\t{}\tTicket Revenue\t-{}.{:02}\t",
+        date,
+        gross_sales / 100,
+        gross_sales % 100
+    ))));
+    assert_eq!(lines.last(), Some(&"ENDTRNS"));
+}
+
+#[test]
+fn generate_xero_csv() {
+    let (export, gross_sales, total, date) = paid_order_export(AccountingExportFormats::XeroCsv);
+    assert_eq!(export.file_name, "journal-usd-all-all.csv".to_string());
+
+    let lines: Vec<&str> = export.content.lines().collect();
+    assert_eq!(lines[0], "*Narration,*Date,Description,*AccountCode,*TaxRate,*Amount");
+    assert!(lines[1].ends_with(&format!(
+        ",{},Undeposited Funds,090,Tax Exempt,{}.{:02}",
+        date,
+        total / 100,
+        total % 100
+    )));
+    assert!(lines.iter().any(|l| l.ends_with(&format!(
+        ",{},Ticket Revenue,4000,Tax Exempt,-{}.{:02}",
+        date,
+        gross_sales / 100,
+        gross_sales % 100
+    ))));
+}
+
+#[test]
+fn generate_filters_by_date_and_currency() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    project.create_order().for_event(&event).is_paid().finish();
+
+    let start_utc = NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 0);
+    let end_utc = NaiveDate::from_ymd(2020, 1, 31).and_hms(23, 59, 59);
+    let export = AccountingExport::generate(
+        &organization,
+        Currencies::USD,
+        Some(start_utc),
+        Some(end_utc),
+        AccountingExportFormats::XeroCsv,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(export.file_name, "journal-usd-20200101-20200131.csv".to_string());
+    assert_eq!(export.content.lines().count(), 1);
+
+    let export = AccountingExport::generate(
+        &organization,
+        Currencies::EUR,
+        None,
+        None,
+        AccountingExportFormats::XeroCsv,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(export.content.lines().count(), 1);
+}
@@ -0,0 +1,344 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+use diesel::PgConnection;
+use uuid::Uuid;
+
+fn paid_order_through_link(
+    project: &TestProject,
+    event: &Event,
+    affiliate_link: &AffiliateLink,
+    quantity: u32,
+    connection: &PgConnection,
+) -> Order {
+    let mut order = project.create_order().for_event(event).quantity(quantity).finish();
+    order
+        .set_tracking_data(Some(json!({ "aff": affiliate_link.code })), None, connection)
+        .unwrap();
+    let total = order.calculate_total(connection).unwrap();
+    order
+        .add_credit_card_payment(
+            order.user_id,
+            total,
+            PaymentProviders::Stripe,
+            "blah".to_string(),
+            PaymentStatus::Completed,
+            json!(""),
+            connection,
+        )
+        .unwrap();
+    order
+}
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let promoter = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project.create_event().with_organization(&organization).finish();
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, Some(event.id), "Newsletter".to_string())
+        .commit(Some(user.id), connection)
+        .unwrap();
+
+    assert_eq!(affiliate_link.name, "Newsletter".to_string());
+    assert_eq!(affiliate_link.code.len(), 10);
+    assert_eq!(
+        AffiliateLink::find_by_code(&affiliate_link.code.to_lowercase(), connection).unwrap(),
+        affiliate_link
+    );
+    assert_eq!(
+        affiliate_link.url("https://example.com"),
+        format!("https://example.com/events/{}?aff={}", event.id, affiliate_link.code)
+    );
+
+    let domain_events = DomainEvent::find(
+        Tables::AffiliateLinks,
+        Some(affiliate_link.id),
+        Some(DomainEventTypes::AffiliateLinkCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn commit_with_event_for_other_organization() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let promoter = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project.create_event().finish();
+    let result =
+        AffiliateLink::create(organization.id, promoter.id, Some(event.id), "".to_string()).commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("name"));
+                assert_eq!(errors["event_id"][0].code, "invalid_event");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn update_and_delete() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let promoter = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project.create_event().with_organization(&organization).finish();
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, Some(event.id), "Newsletter".to_string())
+        .commit(None, connection)
+        .unwrap();
+
+    let attributes = AffiliateLinkEditableAttributes {
+        name: Some("Instagram".to_string()),
+        event_id: Some(None),
+    };
+    let affiliate_link = affiliate_link.update(attributes, None, connection).unwrap();
+    assert_eq!(affiliate_link.name, "Instagram".to_string());
+    assert_eq!(affiliate_link.event_id, None);
+    assert_eq!(
+        AffiliateLink::find_for_user(promoter.id, connection).unwrap(),
+        vec![affiliate_link.clone()]
+    );
+
+    affiliate_link.delete(None, connection).unwrap();
+    assert!(AffiliateLink::find(affiliate_link.id, connection).is_err());
+    assert!(AffiliateLink::find_by_code(&affiliate_link.code, connection).is_err());
+}
+
+#[test]
+fn commit_commission_rule_with_invalid_values() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let promoter = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let other_event = project.create_event().with_ticket_pricing().finish();
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, None, "Newsletter".to_string())
+        .commit(None, connection)
+        .unwrap();
+    let ticket_type = &event.ticket_types(true, None, connection).unwrap()[0];
+    let other_ticket_type = &other_event.ticket_types(true, None, connection).unwrap()[0];
+
+    let result = AffiliateCommissionRule::create(affiliate_link.id, Some(other_ticket_type.id), Some(100), Some(10))
+        .commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(
+                    errors["commission_in_cents"][0].code,
+                    "only_single_commission_type_allowed"
+                );
+                assert_eq!(errors["ticket_type_id"][0].code, "invalid_ticket_type");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    let result = AffiliateCommissionRule::create(affiliate_link.id, None, None, Some(101)).commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(errors["commission_as_percentage"][0].code, "invalid_commission");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    AffiliateCommissionRule::create(affiliate_link.id, Some(ticket_type.id), Some(100), None)
+        .commit(None, connection)
+        .unwrap();
+    let result = AffiliateCommissionRule::create(affiliate_link.id, Some(ticket_type.id), None, Some(10))
+        .commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(errors["ticket_type_id"][0].code, "duplicate_rule");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn commission_for_unit_price() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let promoter = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let ticket_type = &event.ticket_types(true, None, connection).unwrap()[0];
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, None, "Newsletter".to_string())
+        .commit(None, connection)
+        .unwrap();
+    let flat_rule = AffiliateCommissionRule::create(affiliate_link.id, Some(ticket_type.id), Some(250), None)
+        .commit(None, connection)
+        .unwrap();
+    let percentage_rule = AffiliateCommissionRule::create(affiliate_link.id, None, None, Some(10))
+        .commit(None, connection)
+        .unwrap();
+
+    assert_eq!(flat_rule.commission_for_unit_price(1500), 250);
+    assert_eq!(flat_rule.commission_for_unit_price(100), 100);
+    assert_eq!(percentage_rule.commission_for_unit_price(1505), 151);
+    assert_eq!(percentage_rule.commission_for_unit_price(0), 0);
+
+    let rules = affiliate_link.commission_rules(connection).unwrap();
+    assert_eq!(
+        AffiliateCommissionRule::rule_for_ticket_type(&rules, ticket_type.id),
+        Some(&flat_rule)
+    );
+    assert_eq!(
+        AffiliateCommissionRule::rule_for_ticket_type(&rules, Uuid::new_v4()),
+        Some(&percentage_rule)
+    );
+}
+
+#[test]
+fn record_for_order_and_refund() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let promoter = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, None, "Newsletter".to_string())
+        .commit(None, connection)
+        .unwrap();
+    AffiliateCommissionRule::create(affiliate_link.id, None, Some(100), None)
+        .commit(None, connection)
+        .unwrap();
+
+    let mut order = paid_order_through_link(&project, &event, &affiliate_link, 4, connection);
+    assert_eq!(order.affiliate_link_id, Some(affiliate_link.id));
+    let commissions = AffiliateCommission::find_for_order(order.id, connection).unwrap();
+    assert_eq!(commissions.len(), 1);
+    assert_eq!(commissions[0].affiliate_link_id, affiliate_link.id);
+    assert_eq!(commissions[0].event_id, event.id);
+    assert_eq!(commissions[0].quantity, 4);
+    assert_eq!(commissions[0].amount_in_cents, 400);
+    assert_eq!(commissions[0].currency, order.currency);
+
+    // Tracking data without a code keeps the attribution
+    order
+        .set_tracking_data(Some(json!({"utm_source": "newsletter"})), None, connection)
+        .unwrap();
+    assert_eq!(
+        Order::find(order.id, connection).unwrap().affiliate_link_id,
+        Some(affiliate_link.id)
+    );
+
+    let order_item = order
+        .items(connection)
+        .unwrap()
+        .into_iter()
+        .find(|i| i.item_type == OrderItemTypes::Tickets)
+        .unwrap();
+    let ticket = &TicketInstance::find_for_order_item(order_item.id, connection).unwrap()[0];
+    let refund_items = vec![RefundItemRequest {
+        order_item_id: order_item.id,
+        ticket_instance_id: Some(ticket.id),
+    }];
+    let (refund, _) = order
+        .refund(&refund_items, order.user_id, None, false, connection)
+        .unwrap();
+    let commissions = AffiliateCommission::find_for_order(order.id, connection).unwrap();
+    assert_eq!(commissions.len(), 2);
+    assert_eq!(commissions[1].refund_id, Some(refund.id));
+    assert_eq!(commissions[1].quantity, -1);
+    assert_eq!(commissions[1].amount_in_cents, -100);
+
+    let earnings = AffiliateCommission::earnings_for_user(promoter.id, connection).unwrap();
+    assert_eq!(earnings.len(), 1);
+    assert_eq!(earnings[0].affiliate_link_id, affiliate_link.id);
+    assert_eq!(earnings[0].organization_id, organization.id);
+    assert_eq!(earnings[0].event_id, Some(event.id));
+    assert_eq!(earnings[0].tickets_sold, 3);
+    assert_eq!(earnings[0].commission_in_cents, 300);
+    assert_eq!(earnings[0].settled_commission_in_cents, 0);
+}
+
+#[test]
+fn record_for_order_without_commission() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let promoter = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let other_event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, Some(event.id), "Newsletter".to_string())
+        .commit(None, connection)
+        .unwrap();
+    AffiliateCommissionRule::create(affiliate_link.id, None, None, Some(10))
+        .commit(None, connection)
+        .unwrap();
+
+    // Tickets for other events do not earn commission
+    let order = paid_order_through_link(&project, &other_event, &affiliate_link, 2, connection);
+    assert!(AffiliateCommission::find_for_order(order.id, connection)
+        .unwrap()
+        .is_empty());
+
+    // Promoters do not earn commission on their own purchases
+    let mut order = project.create_order().for_user(&promoter).for_event(&event).finish();
+    order
+        .set_tracking_data(Some(json!({ "affiliate_code": affiliate_link.code })), None, connection)
+        .unwrap();
+    let total = order.calculate_total(connection).unwrap();
+    order
+        .add_credit_card_payment(
+            promoter.id,
+            total,
+            PaymentProviders::Stripe,
+            "blah".to_string(),
+            PaymentStatus::Completed,
+            json!(""),
+            connection,
+        )
+        .unwrap();
+    assert_eq!(order.affiliate_link_id, Some(affiliate_link.id));
+    assert!(AffiliateCommission::find_for_order(order.id, connection)
+        .unwrap()
+        .is_empty());
+
+    let earnings = AffiliateCommission::earnings_for_user(promoter.id, connection).unwrap();
+    assert_eq!(earnings.len(), 1);
+    assert_eq!(earnings[0].event_id, None);
+    assert_eq!(earnings[0].commission_in_cents, 0);
+}
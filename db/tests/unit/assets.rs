@@ -4,6 +4,7 @@ use db::schema::ticket_instances;
 
 use chrono::NaiveDate;
 use diesel::prelude::*;
+use tari_client::*;
 use uuid::Uuid;
 
 #[test]
@@ -99,3 +100,127 @@ fn asset_find() {
     let found_wallet = Wallet::find(wallet_id, conn).unwrap();
     assert_eq!(found_wallet.id, wallet_id);
 }
+
+/// Puts the event's asset on a simulated blockchain with every token still held by the organization
+fn simulate_asset(project: &TestProject, event: &Event, simulator: &TariSimulator) -> Asset {
+    let conn = project.get_connection();
+    let ticket_type = &event.ticket_types(true, None, conn).unwrap()[0];
+    let asset = Asset::find_by_ticket_type(ticket_type.id, conn)
+        .unwrap()
+        .update_blockchain_id(Uuid::new_v4().to_string(), conn)
+        .unwrap();
+    let supply: i64 = ticket_instances::table
+        .filter(ticket_instances::asset_id.eq(asset.id))
+        .count()
+        .get_result(conn)
+        .unwrap();
+    let issuer = Wallet::find_default_for_organization(event.organization_id, conn).unwrap();
+    simulator.register_asset(
+        asset.blockchain_asset_id.as_ref().unwrap(),
+        &issuer.public_key,
+        supply as u64,
+    );
+    asset
+}
+
+#[test]
+fn update_sync_status() {
+    let project = TestProject::new();
+    let conn = project.get_connection();
+    let event = project.create_event().with_ticket_pricing().finish();
+    let ticket_type = &event.ticket_types(true, None, conn).unwrap()[0];
+    let asset = Asset::find_by_ticket_type(ticket_type.id, conn).unwrap();
+    assert_eq!(asset.status, AssetStatus::Unsynced);
+
+    // Reconciliation has to start before it can finish
+    assert!(asset.update_sync_status(AssetStatus::Synced, None, conn).is_err());
+
+    let asset = asset.update_sync_status(AssetStatus::Syncing, None, conn).unwrap();
+    assert_eq!(asset.status, AssetStatus::Syncing);
+    assert_eq!(asset.last_synced_at, None);
+
+    let asset = asset
+        .update_sync_status(AssetStatus::Error, Some("Mismatch".to_string()), conn)
+        .unwrap();
+    assert_eq!(asset.status, AssetStatus::Error);
+    assert_eq!(asset.sync_error, Some("Mismatch".to_string()));
+    assert!(asset.last_synced_at.is_some());
+    assert!(asset.update_sync_status(AssetStatus::Synced, None, conn).is_err());
+
+    let asset = asset.update_sync_status(AssetStatus::Syncing, None, conn).unwrap();
+    let asset = asset.update_sync_status(AssetStatus::Synced, None, conn).unwrap();
+    assert_eq!(asset.status, AssetStatus::Synced);
+    assert_eq!(asset.sync_error, None);
+}
+
+#[test]
+fn reconcile() {
+    let project = TestProject::new();
+    let conn = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_ticket_pricing().finish();
+    let simulator = TariSimulator::new();
+    let asset = simulate_asset(&project, &event, &simulator);
+    let blockchain_asset_id = asset.blockchain_asset_id.clone().unwrap();
+    project
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(2)
+        .is_paid()
+        .finish();
+    let user_wallet = user.default_wallet(conn).unwrap();
+    let user_tickets = TicketInstance::find_for_user(user.id, conn).unwrap();
+    assert_eq!(user_tickets.len(), 2);
+
+    // The purchased tokens were never transferred on the blockchain
+    let asset = asset.reconcile(&simulator, false, conn).unwrap();
+    assert_eq!(asset.status, AssetStatus::Error);
+    assert!(asset.sync_error.is_some());
+    let issues = AssetSyncIssue::find_for_asset(asset.id, conn).unwrap();
+    assert_eq!(issues.len(), 2);
+    for issue in &issues {
+        assert_eq!(issue.issue_type, AssetSyncIssueTypes::OwnerMismatch);
+        assert_eq!(issue.database_value, Some(user_wallet.public_key.clone()));
+        assert!(!issue.repaired);
+    }
+    let report = AssetSyncIssue::report(AssetStatus::Error, conn).unwrap();
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].asset_id, asset.id);
+    assert_eq!(report[0].issues, issues);
+
+    let asset = asset.reconcile(&simulator, true, conn).unwrap();
+    assert_eq!(asset.status, AssetStatus::Synced);
+    assert_eq!(asset.sync_error, None);
+    let issues = AssetSyncIssue::find_for_asset(asset.id, conn).unwrap();
+    assert_eq!(issues.len(), 2);
+    assert!(issues.iter().all(|i| i.repaired));
+    for ticket in &user_tickets {
+        assert_eq!(
+            simulator.owner_of(&blockchain_asset_id, ticket.token_id as u64),
+            Some(user_wallet.public_key.clone())
+        );
+    }
+
+    // Once repaired there is nothing left to report
+    let asset = asset.reconcile(&simulator, false, conn).unwrap();
+    assert_eq!(asset.status, AssetStatus::Synced);
+    assert!(AssetSyncIssue::find_for_asset(asset.id, conn).unwrap().is_empty());
+    assert!(AssetSyncIssue::report(AssetStatus::Error, conn).unwrap().is_empty());
+}
+
+#[test]
+fn reconcile_when_blockchain_asset_is_missing() {
+    let project = TestProject::new();
+    let conn = project.get_connection();
+    let event = project.create_event().with_ticket_pricing().finish();
+    let ticket_type = &event.ticket_types(true, None, conn).unwrap()[0];
+    let asset = Asset::find_by_ticket_type(ticket_type.id, conn)
+        .unwrap()
+        .update_blockchain_id(Uuid::new_v4().to_string(), conn)
+        .unwrap();
+
+    let asset = asset.reconcile(&TariSimulator::new(), true, conn).unwrap();
+    assert_eq!(asset.status, AssetStatus::Error);
+    assert!(asset.sync_error.unwrap().contains("does not exist"));
+}
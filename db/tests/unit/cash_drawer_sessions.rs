@@ -0,0 +1,328 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::{BusinessProcessError, ValidationError};
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let user = project.create_user().finish();
+
+    let session = CashDrawerSession::create(organization.id, user.id, 10000)
+        .commit(connection)
+        .unwrap();
+    assert_eq!(session.organization_id, organization.id);
+    assert_eq!(session.user_id, user.id);
+    assert_eq!(session.status, CashDrawerSessionStatus::Open);
+    assert_eq!(session.opening_float_in_cents, 10000);
+    assert_eq!(
+        CashDrawerSession::find_open_for_user(user.id, connection).unwrap(),
+        Some(session.clone())
+    );
+
+    let domain_events = DomainEvent::find(
+        Tables::CashDrawerSessions,
+        Some(session.id),
+        Some(DomainEventTypes::CashDrawerSessionOpened),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+
+    // Staff can only work one drawer at a time
+    let result = CashDrawerSession::create(organization.id, user.id, 0).commit(connection);
+    match result {
+        Ok(_) => panic!("Expected business process error"),
+        Err(error) => match &error.error_code {
+            BusinessProcessError => {}
+            _ => panic!("Expected business process error"),
+        },
+    }
+}
+
+#[test]
+fn commit_negative_float() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let user = project.create_user().finish();
+
+    let result = CashDrawerSession::create(organization.id, user.id, -1).commit(connection);
+    match result {
+        Ok(_) => panic!("Expected validation error"),
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("opening_float_in_cents"));
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn add_movement() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let user = project.create_user().finish();
+    let session = CashDrawerSession::create(organization.id, user.id, 10000)
+        .commit(connection)
+        .unwrap();
+
+    let movement = session
+        .add_movement(
+            CashDrawerMovementTypes::Payout,
+            2500,
+            Some("Runner for ice".to_string()),
+            user.id,
+            connection,
+        )
+        .unwrap();
+    assert_eq!(movement.cash_drawer_session_id, session.id);
+    assert_eq!(movement.movement_type, CashDrawerMovementTypes::Payout);
+    assert_eq!(movement.amount_in_cents, 2500);
+    assert_eq!(session.movements(connection).unwrap(), vec![movement]);
+
+    assert!(session
+        .add_movement(CashDrawerMovementTypes::Drop, 0, None, user.id, connection)
+        .is_err());
+
+    let session = session.close(7500, None, user.id, connection).unwrap();
+    assert!(session
+        .add_movement(CashDrawerMovementTypes::Drop, 100, None, user.id, connection)
+        .is_err());
+}
+
+#[test]
+fn external_payments_attributed_to_open_session() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let box_office_user = project.create_user().finish();
+    let user = project.create_user().finish();
+    let organization = project
+        .create_organization()
+        .with_member(&box_office_user, Roles::OrgBoxOffice)
+        .finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let other_event = project.create_event().with_tickets().with_ticket_pricing().finish();
+
+    // Sales made before the drawer is opened aren't part of the shift
+    let before_order = project
+        .create_order()
+        .for_event(&event)
+        .for_user(&box_office_user)
+        .on_behalf_of_user(&user)
+        .is_paid()
+        .with_external_payment_type(ExternalPaymentType::Cash)
+        .finish();
+    assert_eq!(
+        before_order.payments(connection).unwrap()[0].cash_drawer_session_id,
+        None
+    );
+
+    let session = CashDrawerSession::create(organization.id, box_office_user.id, 10000)
+        .commit(connection)
+        .unwrap();
+    let cash_order = project
+        .create_order()
+        .for_event(&event)
+        .quantity(2)
+        .for_user(&box_office_user)
+        .on_behalf_of_user(&user)
+        .is_paid()
+        .with_external_payment_type(ExternalPaymentType::Cash)
+        .finish();
+    let credit_card_order = project
+        .create_order()
+        .for_event(&event)
+        .for_user(&box_office_user)
+        .on_behalf_of_user(&user)
+        .is_paid()
+        .with_external_payment_type(ExternalPaymentType::CreditCard)
+        .finish();
+    // Sales for other organizations are not taken through this drawer
+    let other_order = project
+        .create_order()
+        .for_event(&other_event)
+        .for_user(&box_office_user)
+        .on_behalf_of_user(&user)
+        .is_paid()
+        .with_external_payment_type(ExternalPaymentType::Cash)
+        .finish();
+    assert_eq!(
+        other_order.payments(connection).unwrap()[0].cash_drawer_session_id,
+        None
+    );
+
+    let cash_payment = cash_order.payments(connection).unwrap().remove(0);
+    let credit_card_payment = credit_card_order.payments(connection).unwrap().remove(0);
+    assert_eq!(cash_payment.cash_drawer_session_id, Some(session.id));
+    assert_eq!(credit_card_payment.cash_drawer_session_id, Some(session.id));
+
+    // Refunds by the staff member come out of their drawer
+    let refund = project.create_refund().finish();
+    let refund_payment = cash_payment
+        .log_refund(box_office_user.id, &refund, 100, None, connection)
+        .unwrap();
+    assert_eq!(refund_payment.cash_drawer_session_id, Some(session.id));
+
+    session
+        .add_movement(
+            CashDrawerMovementTypes::Payout,
+            500,
+            None,
+            box_office_user.id,
+            connection,
+        )
+        .unwrap();
+    session
+        .add_movement(
+            CashDrawerMovementTypes::Drop,
+            2000,
+            None,
+            box_office_user.id,
+            connection,
+        )
+        .unwrap();
+
+    let display_session = session.for_display(connection).unwrap();
+    assert_eq!(display_session.operator_name, box_office_user.full_name());
+    assert_eq!(
+        display_session.payments,
+        vec![
+            CashDrawerSessionPaymentTotal {
+                external_payment_type: Some(ExternalPaymentType::Cash),
+                payment_count: 1,
+                sales_in_cents: cash_payment.amount,
+                refunds_in_cents: 100,
+            },
+            CashDrawerSessionPaymentTotal {
+                external_payment_type: Some(ExternalPaymentType::CreditCard),
+                payment_count: 1,
+                sales_in_cents: credit_card_payment.amount,
+                refunds_in_cents: 0,
+            },
+        ]
+    );
+    assert_eq!(display_session.cash_sales_in_cents, cash_payment.amount);
+    assert_eq!(display_session.cash_refunds_in_cents, 100);
+    assert_eq!(display_session.payouts_in_cents, 500);
+    assert_eq!(display_session.drops_in_cents, 2000);
+    let expected_cash_in_cents = 10000 + cash_payment.amount - 100 - 500 - 2000;
+    assert_eq!(display_session.expected_cash_in_cents, expected_cash_in_cents);
+    assert_eq!(display_session.counted_cash_in_cents, None);
+    assert_eq!(display_session.variance_in_cents, None);
+}
+
+#[test]
+fn close() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let box_office_user = project.create_user().finish();
+    let manager = project.create_user().finish();
+    let user = project.create_user().finish();
+    let organization = project
+        .create_organization()
+        .with_member(&box_office_user, Roles::OrgBoxOffice)
+        .finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let session = CashDrawerSession::create(organization.id, box_office_user.id, 10000)
+        .commit(connection)
+        .unwrap();
+    let order = project
+        .create_order()
+        .for_event(&event)
+        .for_user(&box_office_user)
+        .on_behalf_of_user(&user)
+        .is_paid()
+        .with_external_payment_type(ExternalPaymentType::Cash)
+        .finish();
+    let expected_cash_in_cents = 10000 + order.payments(connection).unwrap()[0].amount;
+
+    // Drawer is 2 dollars short
+    let closed_session = session
+        .close(
+            expected_cash_in_cents - 200,
+            Some("Short at close".to_string()),
+            manager.id,
+            connection,
+        )
+        .unwrap();
+    assert_eq!(closed_session.status, CashDrawerSessionStatus::Closed);
+    assert_eq!(closed_session.expected_cash_in_cents, Some(expected_cash_in_cents));
+    assert_eq!(closed_session.counted_cash_in_cents, Some(expected_cash_in_cents - 200));
+    assert_eq!(closed_session.closing_notes, Some("Short at close".to_string()));
+    assert_eq!(closed_session.closed_by_user_id, Some(manager.id));
+    assert!(closed_session.closed_at.is_some());
+    assert_eq!(
+        CashDrawerSession::find_open_for_user(box_office_user.id, connection).unwrap(),
+        None
+    );
+
+    let display_session = closed_session.for_display(connection).unwrap();
+    assert_eq!(display_session.expected_cash_in_cents, expected_cash_in_cents);
+    assert_eq!(display_session.variance_in_cents, Some(-200));
+
+    let domain_events = DomainEvent::find(
+        Tables::CashDrawerSessions,
+        Some(session.id),
+        Some(DomainEventTypes::CashDrawerSessionClosed),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+
+    // Closed drawers can't be closed again but the staff member can open a new one
+    assert!(closed_session.close(0, None, manager.id, connection).is_err());
+    assert!(CashDrawerSession::create(organization.id, box_office_user.id, 5000)
+        .commit(connection)
+        .is_ok());
+}
+
+#[test]
+fn find_for_organization() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let session = CashDrawerSession::create(organization.id, user.id, 10000)
+        .commit(connection)
+        .unwrap();
+    let session = session.close(10000, None, user.id, connection).unwrap();
+    let session2 = CashDrawerSession::create(organization.id, user2.id, 10000)
+        .commit(connection)
+        .unwrap();
+    project.create_organization().finish();
+
+    let payload = CashDrawerSession::find_for_organization(organization.id, None, None, 0, 100, connection).unwrap();
+    assert_eq!(payload.paging.total, 2);
+    assert!(payload.data.contains(&session));
+    assert!(payload.data.contains(&session2));
+
+    let payload =
+        CashDrawerSession::find_for_organization(organization.id, Some(user.id), None, 0, 100, connection).unwrap();
+    assert_eq!(payload.data, vec![session]);
+
+    let payload = CashDrawerSession::find_for_organization(
+        organization.id,
+        None,
+        Some(CashDrawerSessionStatus::Open),
+        0,
+        100,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(payload.data, vec![session2]);
+}
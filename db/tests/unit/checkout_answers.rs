@@ -0,0 +1,217 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn save_for_order() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let order_question = project.create_checkout_question().with_event(&event).finish();
+    let attendee_question = project
+        .create_checkout_question()
+        .with_event(&event)
+        .with_options(vec!["S", "M", "L"])
+        .per_attendee()
+        .finish();
+    let order = project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(2)
+        .finish();
+    let tickets = order.tickets(None, connection).unwrap();
+
+    let answers = CheckoutAnswer::save_for_order(
+        &order,
+        vec![
+            CheckoutAnswerAttributes {
+                checkout_question_id: order_question.id,
+                ticket_instance_id: None,
+                answer: "Acme".to_string(),
+            },
+            CheckoutAnswerAttributes {
+                checkout_question_id: attendee_question.id,
+                ticket_instance_id: Some(tickets[0].id),
+                answer: "M".to_string(),
+            },
+        ],
+        Some(user.id),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(answers.len(), 2);
+
+    // Saving again replaces the existing answer
+    CheckoutAnswer::save_for_order(
+        &order,
+        vec![CheckoutAnswerAttributes {
+            checkout_question_id: attendee_question.id,
+            ticket_instance_id: Some(tickets[0].id),
+            answer: "L".to_string(),
+        }],
+        Some(user.id),
+        connection,
+    )
+    .unwrap();
+    let answers = CheckoutAnswer::find_for_order(order.id, connection).unwrap();
+    assert_eq!(answers.len(), 2);
+    assert!(answers
+        .iter()
+        .any(|a| a.ticket_instance_id == Some(tickets[0].id) && a.answer == "L".to_string()));
+
+    let domain_events = DomainEvent::find(
+        Tables::Orders,
+        Some(order.id),
+        Some(DomainEventTypes::CheckoutAnswersUpdated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(2, domain_events.len());
+}
+
+#[test]
+fn save_for_order_with_invalid_answers() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let order_question = project.create_checkout_question().with_event(&event).finish();
+    let attendee_question = project
+        .create_checkout_question()
+        .with_event(&event)
+        .with_options(vec!["S", "M", "L"])
+        .per_attendee()
+        .finish();
+    let other_event_question = project.create_checkout_question().finish();
+    let order = project.create_order().for_event(&event).quantity(1).finish();
+    let other_order = project.create_order().for_event(&event).quantity(1).finish();
+    let other_ticket = &other_order.tickets(None, connection).unwrap()[0];
+
+    let result = CheckoutAnswer::save_for_order(
+        &order,
+        vec![
+            CheckoutAnswerAttributes {
+                checkout_question_id: order_question.id,
+                ticket_instance_id: Some(other_ticket.id),
+                answer: "Acme".to_string(),
+            },
+            CheckoutAnswerAttributes {
+                checkout_question_id: attendee_question.id,
+                ticket_instance_id: Some(other_ticket.id),
+                answer: "M".to_string(),
+            },
+            CheckoutAnswerAttributes {
+                checkout_question_id: other_event_question.id,
+                ticket_instance_id: None,
+                answer: "Acme".to_string(),
+            },
+        ],
+        None,
+        connection,
+    );
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("answers"));
+                assert_eq!(errors["answers"].len(), 3);
+                assert_eq!(errors["answers"][0].code, "ticket_instance_not_allowed");
+                assert_eq!(errors["answers"][1].code, "ticket_instance_not_in_order");
+                assert_eq!(errors["answers"][2].code, "question_not_in_order");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+    assert!(CheckoutAnswer::find_for_order(order.id, connection).unwrap().is_empty());
+}
+
+#[test]
+fn validate_for_checkout() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let attendee_question = project
+        .create_checkout_question()
+        .with_event(&event)
+        .per_attendee()
+        .required()
+        .finish();
+    project.create_checkout_question().with_event(&event).finish();
+    let order = project.create_order().for_event(&event).quantity(2).finish();
+    let tickets = order.tickets(None, connection).unwrap();
+
+    CheckoutAnswer::save_for_order(
+        &order,
+        vec![CheckoutAnswerAttributes {
+            checkout_question_id: attendee_question.id,
+            ticket_instance_id: Some(tickets[0].id),
+            answer: "Jane Doe".to_string(),
+        }],
+        None,
+        connection,
+    )
+    .unwrap();
+    match CheckoutAnswer::validate_for_checkout(&order, connection) {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(errors["answers"].len(), 1);
+                assert_eq!(errors["answers"][0].code, "required");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    CheckoutAnswer::save_for_order(
+        &order,
+        vec![CheckoutAnswerAttributes {
+            checkout_question_id: attendee_question.id,
+            ticket_instance_id: Some(tickets[1].id),
+            answer: "John Doe".to_string(),
+        }],
+        None,
+        connection,
+    )
+    .unwrap();
+    assert!(CheckoutAnswer::validate_for_checkout(&order, connection).is_ok());
+}
+
+#[test]
+fn find_display_for_orders() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let order_question = project
+        .create_checkout_question()
+        .with_event(&event)
+        .with_question("Company")
+        .finish();
+    let order = project.create_order().for_event(&event).quantity(1).finish();
+    CheckoutAnswer::save_for_order(
+        &order,
+        vec![CheckoutAnswerAttributes {
+            checkout_question_id: order_question.id,
+            ticket_instance_id: None,
+            answer: "Acme".to_string(),
+        }],
+        None,
+        connection,
+    )
+    .unwrap();
+
+    assert_eq!(
+        CheckoutAnswer::find_display_for_orders(vec![order.id], connection).unwrap(),
+        vec![DisplayCheckoutAnswer {
+            order_id: order.id,
+            ticket_instance_id: None,
+            checkout_question_id: order_question.id,
+            question: "Company".to_string(),
+            answer: "Acme".to_string(),
+        }]
+    );
+}
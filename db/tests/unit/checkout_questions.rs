@@ -0,0 +1,219 @@
+use chrono::prelude::*;
+use chrono::Duration;
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().finish();
+
+    let checkout_question = CheckoutQuestion::create(
+        event.id,
+        "Dietary requirements".to_string(),
+        CheckoutQuestionType::Text,
+        CheckoutQuestionScope::Attendee,
+        true,
+    )
+    .commit(Some(user.id), connection)
+    .unwrap();
+    assert_eq!(checkout_question.event_id, event.id);
+    assert_eq!(checkout_question.answer_scope, CheckoutQuestionScope::Attendee);
+    assert!(checkout_question.required);
+
+    let domain_events = DomainEvent::find(
+        Tables::CheckoutQuestions,
+        Some(checkout_question.id),
+        Some(DomainEventTypes::CheckoutQuestionCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn commit_select_without_options() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().finish();
+
+    let result = CheckoutQuestion::create(
+        event.id,
+        "T-shirt size".to_string(),
+        CheckoutQuestionType::Select,
+        CheckoutQuestionScope::Attendee,
+        true,
+    )
+    .commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("options"));
+                assert_eq!(errors["options"][0].code, "options_required");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn commit_with_ticket_type_from_other_event() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().finish();
+    let other_event = project.create_event().with_tickets().finish();
+    let other_ticket_type = &other_event.ticket_types(true, None, connection).unwrap()[0];
+
+    let mut new_checkout_question = CheckoutQuestion::create(
+        event.id,
+        "Company".to_string(),
+        CheckoutQuestionType::Text,
+        CheckoutQuestionScope::Order,
+        false,
+    );
+    new_checkout_question.ticket_type_id = Some(other_ticket_type.id);
+    match new_checkout_question.commit(None, connection) {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("ticket_type_id"));
+                assert_eq!(errors["ticket_type_id"][0].code, "ticket_type_does_not_belong_to_event");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn find_for_event() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().finish();
+    let checkout_question = project.create_checkout_question().with_event(&event).finish();
+    let checkout_question2 = project.create_checkout_question().with_event(&event).finish();
+    let deleted_question = project.create_checkout_question().with_event(&event).finish();
+    deleted_question.delete(None, connection).unwrap();
+    project.create_checkout_question().finish();
+
+    assert_eq!(
+        CheckoutQuestion::find_for_event(event.id, connection).unwrap(),
+        vec![checkout_question, checkout_question2]
+    );
+}
+
+#[test]
+fn find_for_order() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project
+        .create_event()
+        .with_ticket_type_count(2)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let ticket_types = event.ticket_types(true, None, connection).unwrap();
+    let event_question = project.create_checkout_question().with_event(&event).finish();
+    let ticket_type_question = project
+        .create_checkout_question()
+        .with_ticket_type(&ticket_types[0])
+        .finish();
+    project
+        .create_checkout_question()
+        .with_ticket_type(&ticket_types[1])
+        .finish();
+    let order = project
+        .create_order()
+        .for_tickets(ticket_types[0].id)
+        .quantity(1)
+        .finish();
+
+    assert_eq!(
+        CheckoutQuestion::find_for_order(&order, connection).unwrap(),
+        vec![event_question, ticket_type_question]
+    );
+}
+
+#[test]
+fn update() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let checkout_question = project.create_checkout_question().finish();
+
+    let attributes = CheckoutQuestionEditableAttributes {
+        question: Some("T-shirt size".to_string()),
+        question_type: Some(CheckoutQuestionType::Select),
+        options: Some(vec!["S".to_string(), "M".to_string(), "L".to_string()]),
+        required: Some(true),
+        ..Default::default()
+    };
+    let checkout_question = checkout_question.update(attributes, None, connection).unwrap();
+    assert_eq!(checkout_question.question, "T-shirt size".to_string());
+    assert_eq!(checkout_question.question_type, CheckoutQuestionType::Select);
+    assert_eq!(checkout_question.options.len(), 3);
+    assert!(checkout_question.required);
+}
+
+#[test]
+fn answers_editable() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project
+        .create_event()
+        .with_event_start(Utc::now().naive_utc() + Duration::days(2))
+        .with_tickets()
+        .finish();
+    let checkout_question = project.create_checkout_question().with_event(&event).finish();
+    assert!(checkout_question.answers_editable(connection).unwrap());
+
+    let attributes = CheckoutQuestionEditableAttributes {
+        editable_until: Some(Some(Utc::now().naive_utc() - Duration::hours(1))),
+        ..Default::default()
+    };
+    let checkout_question = checkout_question.update(attributes, None, connection).unwrap();
+    assert!(!checkout_question.answers_editable(connection).unwrap());
+}
+
+#[test]
+fn validate_answer() {
+    let project = TestProject::new();
+    let text_question = project.create_checkout_question().required().finish();
+    assert!(text_question.validate_answer("Acme").is_ok());
+    assert_eq!(text_question.validate_answer("  ").unwrap_err().code, "required");
+
+    let select_question = project
+        .create_checkout_question()
+        .with_options(vec!["S", "M", "L"])
+        .finish();
+    assert!(select_question.validate_answer("M").is_ok());
+    assert!(select_question.validate_answer("").is_ok());
+    assert_eq!(
+        select_question.validate_answer("XL").unwrap_err().code,
+        "invalid_option"
+    );
+
+    let checkbox_question = project.create_checkout_question().checkbox().required().finish();
+    assert!(checkbox_question.validate_answer("true").is_ok());
+    assert_eq!(checkbox_question.validate_answer("false").unwrap_err().code, "required");
+    assert_eq!(
+        checkbox_question.validate_answer("yes").unwrap_err().code,
+        "invalid_checkbox"
+    );
+}
+
+#[test]
+fn delete() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let checkout_question = project.create_checkout_question().finish();
+
+    checkout_question.delete(None, connection).unwrap();
+    assert!(CheckoutQuestion::find(checkout_question.id, connection).is_err());
+}
@@ -0,0 +1,442 @@
+use chrono::Duration;
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+use uuid::Uuid;
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().finish();
+
+    let event_series = EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Tuesday comedy".to_string(),
+        "FREQ=WEEKLY;COUNT=4".to_string(),
+    )
+    .commit(Some(user.id), connection)
+    .unwrap();
+    assert_eq!(event_series.template_event_id, event.id);
+    assert_eq!(event_series.name, "Tuesday comedy".to_string());
+
+    let occurrences = event_series.occurrences(connection).unwrap();
+    assert_eq!(occurrences.len(), 1);
+    assert_eq!(occurrences[0].event_id, event.id);
+    assert_eq!(Some(occurrences[0].occurrence_start), event.event_start);
+    assert_eq!(
+        EventSeries::find_for_event(event.id, connection).unwrap(),
+        Some(event_series.clone())
+    );
+
+    let domain_events = DomainEvent::find(
+        Tables::EventSeries,
+        Some(event_series.id),
+        Some(DomainEventTypes::EventSeriesCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn commit_with_validation_errors() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().finish();
+
+    let result = EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=HOURLY".to_string(),
+    )
+    .commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("recurrence_rule"));
+                assert_eq!(errors["recurrence_rule"].len(), 1);
+                assert_eq!(errors["recurrence_rule"][0].code, "invalid_recurrence_rule");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    // Events can only belong to one series
+    EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=DAILY;COUNT=2".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+    let result = EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=DAILY;COUNT=2".to_string(),
+    )
+    .commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("template_event_id"));
+                assert_eq!(errors["template_event_id"][0].code, "event_already_in_series");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn recurrence_rule_valid() {
+    assert!(EventSeries::recurrence_rule_valid("FREQ=WEEKLY;BYDAY=TU,TH").is_ok());
+    assert!(EventSeries::recurrence_rule_valid("RRULE:FREQ=MONTHLY;BYDAY=1FR;COUNT=12").is_ok());
+    assert_eq!(
+        EventSeries::recurrence_rule_valid("FREQ=WEEKLY;BYDAY=XX")
+            .unwrap_err()
+            .code,
+        "invalid_recurrence_rule"
+    );
+    assert_eq!(
+        EventSeries::recurrence_rule_valid("FREQ=DAILY;COUNT=1000")
+            .unwrap_err()
+            .code,
+        "too_many_occurrences"
+    );
+}
+
+#[test]
+fn generate_occurrences() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let ticket_type = &event.ticket_types(true, None, connection).unwrap()[0];
+    let hold = project
+        .create_hold()
+        .with_event(&event)
+        .with_ticket_type_id(ticket_type.id)
+        .with_quantity(10)
+        .finish();
+    project.create_comp().with_hold(&hold).with_quantity(2).finish();
+    let event_series = EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=WEEKLY;COUNT=3".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+
+    let events = event_series.generate_occurrences(None, connection).unwrap();
+    assert_eq!(events.len(), 2);
+    let event_start = event.event_start.unwrap();
+    assert_eq!(events[0].event_start, Some(event_start + Duration::weeks(1)));
+    assert_eq!(events[1].event_start, Some(event_start + Duration::weeks(2)));
+    assert_eq!(events[0].status, EventStatus::Published);
+
+    let generated_ticket_type = &events[0].ticket_types(true, None, connection).unwrap()[0];
+    assert_eq!(generated_ticket_type.name, ticket_type.name);
+    let ticket_pricing = ticket_type.valid_ticket_pricing(false, connection).unwrap();
+    let generated_ticket_pricing = generated_ticket_type.valid_ticket_pricing(false, connection).unwrap();
+    assert_eq!(generated_ticket_pricing.len(), ticket_pricing.len());
+    assert_eq!(
+        generated_ticket_pricing[0].start_date,
+        ticket_pricing[0].start_date + Duration::weeks(1)
+    );
+
+    // Holds are copied with their quantity, comps are not
+    let holds = Hold::find_by_ticket_type(generated_ticket_type.id, connection).unwrap();
+    assert_eq!(holds.len(), 1);
+    assert_eq!(holds[0].name, hold.name);
+    assert_eq!(holds[0].quantity(connection).unwrap().0, 10);
+
+    let event_series = EventSeries::find(event_series.id, connection).unwrap();
+    assert!(event_series.generated_until.is_some());
+    assert_eq!(
+        event_series.for_display(connection).unwrap().event_ids,
+        vec![event.id, events[0].id, events[1].id]
+    );
+
+    // Already generated occurrences are not generated again
+    assert!(event_series.generate_occurrences(None, connection).unwrap().is_empty());
+
+    // Counted rules are not topped up
+    assert!(!DomainAction::has_pending_action(
+        DomainActionTypes::GenerateEventSeriesOccurrences,
+        Tables::EventSeries,
+        event_series.id,
+        connection
+    )
+    .unwrap());
+}
+
+#[test]
+fn generate_occurrences_skips_deleted_events() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().finish();
+    let event_series = EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=WEEKLY".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+    let events = event_series.generate_occurrences(None, connection).unwrap();
+    let occurrence_count = events.len();
+    assert!(occurrence_count > 50);
+
+    // Open ended rules are topped up in the background
+    assert!(DomainAction::has_pending_action(
+        DomainActionTypes::GenerateEventSeriesOccurrences,
+        Tables::EventSeries,
+        event_series.id,
+        connection
+    )
+    .unwrap());
+
+    events[0].clone().delete(user.id, connection).unwrap();
+    let event_series = EventSeries::find(event_series.id, connection).unwrap();
+    assert!(event_series.generate_occurrences(None, connection).unwrap().is_empty());
+    assert_eq!(event_series.events(connection).unwrap().len(), occurrence_count);
+}
+
+#[test]
+fn update() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().finish();
+    let event_series = EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=DAILY;COUNT=4".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+    let events = event_series.generate_occurrences(None, connection).unwrap();
+    assert_eq!(events.len(), 3);
+
+    let attributes = EventSeriesEditableAttributes {
+        name: Some("Every other day".to_string()),
+        recurrence_rule: Some("FREQ=DAILY;INTERVAL=2;COUNT=2".to_string()),
+    };
+    let event_series = event_series.update(attributes, user.id, connection).unwrap();
+    assert_eq!(event_series.name, "Every other day".to_string());
+
+    // Occurrences no longer matching the rule are removed
+    assert_eq!(
+        event_series.for_display(connection).unwrap().event_ids,
+        vec![event.id, events[1].id]
+    );
+    assert!(Event::find(events[0].id, connection).unwrap().deleted_at.is_some());
+    assert!(Event::find(events[2].id, connection).unwrap().deleted_at.is_some());
+
+    let domain_events = DomainEvent::find(
+        Tables::EventSeries,
+        Some(event_series.id),
+        Some(DomainEventTypes::EventSeriesUpdated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+
+    let attributes = EventSeriesEditableAttributes {
+        recurrence_rule: Some("FREQ=DAILY;UNTIL=20200101T000000Z;COUNT=2".to_string()),
+        ..Default::default()
+    };
+    match event_series.update(attributes, user.id, connection) {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("recurrence_rule"));
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn update_events() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let artist = project.create_artist().finish();
+    let event = project.create_event().with_tickets().finish();
+    let event_series = EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=WEEKLY;COUNT=4".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+    let events = event_series.generate_occurrences(None, connection).unwrap();
+
+    // This event only
+    let updated_events = event_series
+        .update_events(
+            &events[0],
+            EventSeriesEditScopes::ThisEvent,
+            EventSeriesEventAttributes {
+                name: Some("Special edition".to_string()),
+                ..Default::default()
+            },
+            None,
+            connection,
+        )
+        .unwrap();
+    assert_eq!(updated_events.len(), 1);
+    assert_eq!(updated_events[0].id, events[0].id);
+    assert_eq!(updated_events[0].name, "Special edition".to_string());
+    assert_eq!(
+        EventSeries::find(event_series.id, connection)
+            .unwrap()
+            .template_event_id,
+        event.id
+    );
+
+    // This and future events, set times follow each event's start
+    let set_time = events[1].event_start.unwrap() + Duration::hours(1);
+    let updated_events = event_series
+        .update_events(
+            &events[1],
+            EventSeriesEditScopes::ThisAndFuture,
+            EventSeriesEventAttributes {
+                top_line_info: Some(Some("New lineup".to_string())),
+                artists: Some(vec![EventSeriesArtist {
+                    artist_id: artist.id,
+                    importance: 0,
+                    set_time: Some(set_time),
+                    stage_id: None,
+                }]),
+                ..Default::default()
+            },
+            None,
+            connection,
+        )
+        .unwrap();
+    assert_eq!(
+        updated_events.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![events[1].id, events[2].id]
+    );
+    for updated_event in &updated_events {
+        assert_eq!(updated_event.top_line_info, Some("New lineup".to_string()));
+        let event_artists = EventArtist::find_all_from_event(updated_event.id, connection).unwrap();
+        assert_eq!(event_artists.len(), 1);
+        assert_eq!(
+            event_artists[0].set_time,
+            Some(updated_event.event_start.unwrap() + Duration::hours(1))
+        );
+    }
+    assert_eq!(Event::find(event.id, connection).unwrap().top_line_info, None);
+
+    // Later occurrences are generated from the edited event
+    assert_eq!(
+        EventSeries::find(event_series.id, connection)
+            .unwrap()
+            .template_event_id,
+        events[1].id
+    );
+
+    let domain_events = DomainEvent::find(
+        Tables::EventSeries,
+        Some(event_series.id),
+        Some(DomainEventTypes::EventSeriesOccurrencesUpdated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(2, domain_events.len());
+}
+
+#[test]
+fn delete() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().finish();
+    let event_series = EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=WEEKLY;COUNT=2".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+    let events = event_series.generate_occurrences(None, connection).unwrap();
+
+    event_series.delete(None, connection).unwrap();
+    assert!(EventSeries::find(event_series.id, connection).is_err());
+    assert!(EventSeries::find_for_organization(event.organization_id, connection)
+        .unwrap()
+        .is_empty());
+
+    // Events are kept as standalone events
+    assert!(Event::find(events[0].id, connection).unwrap().deleted_at.is_none());
+    assert_eq!(EventSeries::find_for_event(events[0].id, connection).unwrap(), None);
+}
+
+#[test]
+fn dashboard() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let event_series = EventSeries::create(
+        event.organization_id,
+        event.id,
+        "Series".to_string(),
+        "FREQ=WEEKLY;COUNT=2".to_string(),
+    )
+    .commit(None, connection)
+    .unwrap();
+    let events = event_series.generate_occurrences(None, connection).unwrap();
+    project
+        .create_order()
+        .for_event(&event)
+        .quantity(2)
+        .for_user(&user)
+        .is_paid()
+        .finish();
+    project
+        .create_order()
+        .for_event(&events[0])
+        .quantity(3)
+        .for_user(&user)
+        .is_paid()
+        .finish();
+
+    let dashboard = event_series.dashboard(connection).unwrap();
+    assert_eq!(dashboard.event_series.id, event_series.id);
+    assert_eq!(
+        dashboard.events.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![event.id, events[0].id]
+    );
+    assert_eq!(dashboard.sold, 5);
+    assert_eq!(
+        dashboard.sales_total_in_cents,
+        dashboard
+            .events
+            .iter()
+            .map(|e| e.sales_total_in_cents.unwrap_or(0))
+            .sum::<u32>()
+    );
+
+    let report = Report::event_series_report(event_series.id, connection).unwrap();
+    let sold: Vec<(Uuid, i64)> = report.iter().map(|r| (r.event_id, r.sold_count)).collect();
+    assert_eq!(sold, vec![(event.id, 2), (events[0].id, 3)]);
+}
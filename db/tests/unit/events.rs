@@ -4475,6 +4475,286 @@ fn organization() {
     assert_eq!(event.organization(project.get_connection()).unwrap(), organization);
 }
 
+#[test]
+fn currency() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let event = project.create_event().with_organization(&organization).finish();
+    assert_eq!(organization.currency, Currencies::USD);
+    assert_eq!(event.currency, None);
+    assert_eq!(event.currency(connection).unwrap(), Currencies::USD);
+
+    let event = event
+        .update(
+            None,
+            EventEditableAttributes {
+                currency: Some(Some(Currencies::CAD)),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    assert_eq!(event.currency, Some(Currencies::CAD));
+    assert_eq!(event.currency(connection).unwrap(), Currencies::CAD);
+    assert_eq!(
+        Event::find(event.id, connection).unwrap().currency(connection).unwrap(),
+        Currencies::CAD
+    );
+}
+
+#[test]
+fn barcode_rotation_seconds() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().finish();
+    assert_eq!(event.barcode_rotation_seconds, None);
+
+    let event = event
+        .update(
+            None,
+            EventEditableAttributes {
+                barcode_rotation_seconds: Some(Some(30)),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    assert_eq!(event.barcode_rotation_seconds, Some(30));
+    assert_eq!(
+        Event::find(event.id, connection)
+            .unwrap()
+            .for_display(connection)
+            .unwrap()
+            .barcode_rotation_seconds,
+        Some(30)
+    );
+
+    for (barcode_rotation_seconds, code) in vec![(5, "barcode_rotation_too_short"), (600, "barcode_rotation_too_long")]
+    {
+        let result = event.update(
+            None,
+            EventEditableAttributes {
+                barcode_rotation_seconds: Some(Some(barcode_rotation_seconds)),
+                ..Default::default()
+            },
+            connection,
+        );
+        match result {
+            Ok(_) => {
+                panic!("Expected error");
+            }
+            Err(error) => match &error.error_code {
+                ValidationError { errors } => {
+                    assert!(errors.contains_key("event.barcode_rotation_seconds"));
+                    assert_eq!(errors["event.barcode_rotation_seconds"][0].code, code);
+                }
+                _ => panic!("Expected validation error"),
+            },
+        }
+    }
+
+    // Switching back to static barcodes
+    let event = event
+        .update(
+            None,
+            EventEditableAttributes {
+                barcode_rotation_seconds: Some(None),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    assert_eq!(event.barcode_rotation_seconds, None);
+}
+
+#[test]
+fn capacity() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let ticket_type = &event.ticket_types(true, None, connection).unwrap()[0];
+    let hold = project.create_hold().with_event(&event).with_quantity(2).finish();
+    assert_eq!(event.capacity, None);
+
+    let result = event.update(
+        None,
+        EventEditableAttributes {
+            capacity: Some(Some(0)),
+            ..Default::default()
+        },
+        connection,
+    );
+    match result {
+        Ok(_) => {
+            panic!("Expected error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("event.capacity"));
+                assert_eq!(errors["event.capacity"][0].code, "capacity_less_than_one");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    let event = event
+        .update(
+            None,
+            EventEditableAttributes {
+                capacity: Some(Some(5)),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    assert_eq!(event.capacity, Some(5));
+
+    // Held tickets already count towards capacity
+    let user = project.create_user().finish();
+    let mut cart = Order::find_or_create_cart(&user, connection).unwrap();
+    let result = cart.update_quantities(
+        user.id,
+        &[UpdateOrderItem {
+            ticket_type_id: ticket_type.id,
+            quantity: 4,
+            redemption_code: None,
+        }],
+        false,
+        false,
+        connection,
+    );
+    match result {
+        Ok(_) => {
+            panic!("Expected error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("quantity"));
+                assert_eq!(errors["quantity"][0].code, "event_capacity_exceeded");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    let user2 = project.create_user().finish();
+    let mut cart2 = Order::find_or_create_cart(&user2, connection).unwrap();
+    cart2
+        .update_quantities(
+            user2.id,
+            &[UpdateOrderItem {
+                ticket_type_id: ticket_type.id,
+                quantity: 3,
+                redemption_code: None,
+            }],
+            false,
+            false,
+            connection,
+        )
+        .unwrap();
+
+    let capacity_summary = event.capacity_summary(connection).unwrap();
+    assert_eq!(capacity_summary.capacity, Some(5));
+    assert_eq!(capacity_summary.used, 5);
+    assert_eq!(capacity_summary.remaining, Some(0));
+    assert!(capacity_summary.stages.is_empty());
+
+    // Growing the hold takes from general inventory so is limited by capacity
+    assert!(hold.set_quantity(None, 3, connection).is_err());
+
+    // Tickets reserved from the hold are already counted
+    let user3 = project.create_user().finish();
+    let mut cart3 = Order::find_or_create_cart(&user3, connection).unwrap();
+    cart3
+        .update_quantities(
+            user3.id,
+            &[UpdateOrderItem {
+                ticket_type_id: ticket_type.id,
+                quantity: 2,
+                redemption_code: hold.redemption_code.clone(),
+            }],
+            false,
+            false,
+            connection,
+        )
+        .unwrap();
+    assert_eq!(event.capacity_summary(connection).unwrap().used, 5);
+}
+
+#[test]
+fn stage_capacity() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let venue = project.create_venue().finish();
+    let stage = project.create_stage().with_venue_id(venue.id).with_capacity(2).finish();
+    let event = project
+        .create_event()
+        .with_venue(&venue)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let ticket_type = ticket_type.update_stage(Some(stage.id), None, connection).unwrap();
+    assert_eq!(ticket_type.stage_id, Some(stage.id));
+
+    let user = project.create_user().finish();
+    let mut cart = Order::find_or_create_cart(&user, connection).unwrap();
+    let result = cart.update_quantities(
+        user.id,
+        &[UpdateOrderItem {
+            ticket_type_id: ticket_type.id,
+            quantity: 3,
+            redemption_code: None,
+        }],
+        false,
+        false,
+        connection,
+    );
+    match result {
+        Ok(_) => {
+            panic!("Expected error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("quantity"));
+                assert_eq!(errors["quantity"][0].code, "stage_capacity_exceeded");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    let user2 = project.create_user().finish();
+    let mut cart2 = Order::find_or_create_cart(&user2, connection).unwrap();
+    cart2
+        .update_quantities(
+            user2.id,
+            &[UpdateOrderItem {
+                ticket_type_id: ticket_type.id,
+                quantity: 2,
+                redemption_code: None,
+            }],
+            false,
+            false,
+            connection,
+        )
+        .unwrap();
+
+    let capacity_summary = event.capacity_summary(connection).unwrap();
+    assert_eq!(capacity_summary.capacity, None);
+    assert_eq!(capacity_summary.used, 2);
+    assert_eq!(capacity_summary.remaining, None);
+    assert_eq!(
+        capacity_summary.stages,
+        vec![StageCapacity {
+            stage_id: stage.id,
+            name: stage.name.clone(),
+            capacity: Some(2),
+            used: 2,
+            remaining: Some(0),
+        }]
+    );
+}
+
 #[test]
 fn venue() {
     let project = TestProject::new();
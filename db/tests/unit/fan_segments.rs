@@ -0,0 +1,224 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let user = project.create_user().finish();
+
+    let fan_segment = FanSegment::create(organization.id, "Big spenders".to_string())
+        .commit(Some(user.id), connection)
+        .unwrap();
+    assert_eq!(fan_segment.organization_id, organization.id);
+    assert_eq!(fan_segment.name, "Big spenders".to_string());
+
+    let domain_events = DomainEvent::find(
+        Tables::FanSegments,
+        Some(fan_segment.id),
+        Some(DomainEventTypes::FanSegmentCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+    assert_eq!(domain_events[0].user_id, Some(user.id));
+}
+
+#[test]
+fn commit_with_invalid_venue_filter() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let venue = project.create_venue().finish();
+
+    let mut new_fan_segment = FanSegment::create(organization.id, "Locals".to_string());
+    new_fan_segment.venue_id = Some(venue.id);
+    let result = new_fan_segment.commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("venue_radius_in_km"));
+                assert_eq!(errors["venue_radius_in_km"].len(), 1);
+                assert_eq!(errors["venue_radius_in_km"][0].code, "venue_radius_required");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    let mut new_fan_segment = FanSegment::create(organization.id, "Locals".to_string());
+    new_fan_segment.venue_id = Some(venue.id);
+    new_fan_segment.venue_radius_in_km = Some(25.0);
+    assert!(new_fan_segment.commit(None, connection).is_ok());
+}
+
+#[test]
+fn commit_with_negative_filters() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+
+    let mut new_fan_segment = FanSegment::create(organization.id, "Negative".to_string());
+    new_fan_segment.min_revenue_in_cents = Some(-1);
+    new_fan_segment.inactive_days = Some(-30);
+    let result = new_fan_segment.commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("min_revenue_in_cents"));
+                assert_eq!(errors["min_revenue_in_cents"][0].code, "min_revenue_in_cents_negative");
+                assert!(errors.contains_key("inactive_days"));
+                assert_eq!(errors["inactive_days"][0].code, "inactive_days_negative");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn find_for_organization() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let fan_segment = project
+        .create_fan_segment()
+        .with_organization(&organization)
+        .with_name("A segment".to_string())
+        .finish();
+    let fan_segment2 = project
+        .create_fan_segment()
+        .with_organization(&organization)
+        .with_name("B segment".to_string())
+        .finish();
+    project.create_fan_segment().finish();
+
+    let payload = FanSegment::find_for_organization(organization.id, 0, 100, connection).unwrap();
+    assert_eq!(payload.data, vec![fan_segment, fan_segment2]);
+    assert_eq!(payload.paging.total, 2);
+}
+
+#[test]
+fn update() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let fan_segment = project.create_fan_segment().with_min_revenue_in_cents(1000).finish();
+
+    let attributes = FanSegmentEditableAttributes {
+        name: Some("Renamed".to_string()),
+        min_revenue_in_cents: Some(None),
+        inactive_days: Some(Some(90)),
+        ..Default::default()
+    };
+    let fan_segment = fan_segment.update(attributes, Some(user.id), connection).unwrap();
+    assert_eq!(fan_segment.name, "Renamed".to_string());
+    assert_eq!(fan_segment.min_revenue_in_cents, None);
+    assert_eq!(fan_segment.inactive_days, Some(90));
+
+    let domain_events = DomainEvent::find(
+        Tables::FanSegments,
+        Some(fan_segment.id),
+        Some(DomainEventTypes::FanSegmentUpdated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn delete() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let fan_segment = project.create_fan_segment().finish();
+
+    fan_segment.delete(None, connection).unwrap();
+    assert!(FanSegment::find(fan_segment.id, connection).is_err());
+    let payload = FanSegment::find_for_organization(fan_segment.organization_id, 0, 100, connection).unwrap();
+    assert!(payload.data.is_empty());
+}
+
+#[test]
+fn fans() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let user3 = project.create_user().finish();
+    project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(10)
+        .is_paid()
+        .finish();
+    project
+        .create_order()
+        .for_user(&user2)
+        .for_event(&event)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    let code = project
+        .create_code()
+        .with_event(&event)
+        .with_code_type(CodeTypes::Access)
+        .with_redemption_code("SEGMENTCODE".to_string())
+        .finish();
+    project
+        .create_order()
+        .for_user(&user3)
+        .for_event(&event)
+        .quantity(1)
+        .with_redemption_code(code.redemption_code.clone())
+        .is_paid()
+        .finish();
+
+    let all_fans = project.create_fan_segment().with_organization(&organization).finish();
+    let payload = all_fans.fans(0, 100, connection).unwrap();
+    assert_eq!(payload.paging.total, 3);
+
+    let user_revenue_in_cents = all_fans
+        .all_fans(connection)
+        .unwrap()
+        .into_iter()
+        .find(|f| f.user_id == user.id)
+        .and_then(|f| f.revenue_in_cents)
+        .unwrap();
+    let big_spenders = project
+        .create_fan_segment()
+        .with_organization(&organization)
+        .with_min_revenue_in_cents(user_revenue_in_cents)
+        .finish();
+    let fan_ids: Vec<_> = big_spenders
+        .all_fans(connection)
+        .unwrap()
+        .into_iter()
+        .map(|f| f.user_id)
+        .collect();
+    assert_eq!(fan_ids, vec![user.id]);
+
+    let code_users = project
+        .create_fan_segment()
+        .with_organization(&organization)
+        .with_redemption_code("segmentcode".to_string())
+        .finish();
+    let users = code_users.all_users(connection).unwrap();
+    assert_eq!(users.into_iter().map(|u| u.id).collect::<Vec<_>>(), vec![user3.id]);
+
+    let other_organization_segment = project.create_fan_segment().finish();
+    assert!(other_organization_segment.all_fans(connection).unwrap().is_empty());
+}
@@ -0,0 +1,258 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().finish();
+
+    let inventory_pool = InventoryPool::create(event.id, "Main floor".to_string())
+        .commit(Some(user.id), connection)
+        .unwrap();
+    assert_eq!(inventory_pool.event_id, event.id);
+    assert_eq!(inventory_pool.name, "Main floor".to_string());
+
+    let domain_events = DomainEvent::find(
+        Tables::InventoryPools,
+        Some(inventory_pool.id),
+        Some(DomainEventTypes::InventoryPoolCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn commit_blank_name() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().finish();
+
+    let result = InventoryPool::create(event.id, "".to_string()).commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("name"));
+                assert_eq!(errors["name"][0].message, Some("Name cannot be blank".into()));
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn update_ticket_types() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().with_ticket_type_count(3).finish();
+    let ticket_types = event.ticket_types(true, None, connection).unwrap();
+    let other_event = project.create_event().with_tickets().finish();
+    let other_ticket_type = &other_event.ticket_types(true, None, connection).unwrap()[0];
+    let inventory_pool = InventoryPool::create(event.id, "Main floor".to_string())
+        .commit(None, connection)
+        .unwrap();
+
+    inventory_pool
+        .update_ticket_types(vec![ticket_types[0].id, ticket_types[1].id], None, connection)
+        .unwrap();
+    assert_eq!(
+        inventory_pool.ticket_type_ids(connection).unwrap(),
+        vec![ticket_types[0].id, ticket_types[1].id]
+    );
+    assert_eq!(
+        TicketType::find(ticket_types[0].id, connection)
+            .unwrap()
+            .inventory_pool_id,
+        Some(inventory_pool.id)
+    );
+
+    // Replacing the ticket types takes removed ones out of the pool
+    inventory_pool
+        .update_ticket_types(vec![ticket_types[1].id, ticket_types[2].id], None, connection)
+        .unwrap();
+    assert_eq!(
+        inventory_pool.ticket_type_ids(connection).unwrap(),
+        vec![ticket_types[1].id, ticket_types[2].id]
+    );
+    assert_eq!(
+        TicketType::find(ticket_types[0].id, connection)
+            .unwrap()
+            .inventory_pool_id,
+        None
+    );
+
+    let result = inventory_pool.update_ticket_types(vec![other_ticket_type.id], None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("ticket_type_ids"));
+                assert_eq!(
+                    errors["ticket_type_ids"][0].code,
+                    "ticket_type_does_not_belong_to_event"
+                );
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn reserve_from_pool() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project
+        .create_event()
+        .with_a_specific_number_of_tickets(5)
+        .with_ticket_type_count(2)
+        .with_ticket_pricing()
+        .finish();
+    let ticket_types = event.ticket_types(true, None, connection).unwrap();
+    let early_bird = &ticket_types[0];
+    let general = &ticket_types[1];
+    let inventory_pool = InventoryPool::create(event.id, "Main floor".to_string())
+        .commit(None, connection)
+        .unwrap();
+    inventory_pool
+        .update_ticket_types(vec![early_bird.id, general.id], None, connection)
+        .unwrap();
+    let early_bird = TicketType::find(early_bird.id, connection).unwrap();
+    let general = TicketType::find(general.id, connection).unwrap();
+    assert_eq!(early_bird.valid_available_ticket_count(connection).unwrap(), 10);
+
+    // Early bird sells past its own tickets into general's
+    let user = project.create_user().finish();
+    let mut cart = Order::find_or_create_cart(&user, connection).unwrap();
+    cart.update_quantities(
+        user.id,
+        &[UpdateOrderItem {
+            ticket_type_id: early_bird.id,
+            quantity: 8,
+            redemption_code: None,
+        }],
+        false,
+        false,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(early_bird.valid_available_ticket_count(connection).unwrap(), 2);
+    assert_eq!(general.valid_available_ticket_count(connection).unwrap(), 2);
+
+    let user2 = project.create_user().finish();
+    let mut cart2 = Order::find_or_create_cart(&user2, connection).unwrap();
+    assert!(cart2
+        .update_quantities(
+            user2.id,
+            &[UpdateOrderItem {
+                ticket_type_id: general.id,
+                quantity: 3,
+                redemption_code: None,
+            }],
+            false,
+            false,
+            connection,
+        )
+        .is_err());
+
+    let total = cart.calculate_total(connection).unwrap();
+    cart.add_external_payment(
+        Some("test".to_string()),
+        ExternalPaymentType::CreditCard,
+        user.id,
+        total,
+        connection,
+    )
+    .unwrap();
+
+    // Sales stay attributed to the ticket type that was bought
+    assert_eq!(early_bird.valid_sold_and_reserved_ticket_count(connection).unwrap(), 8);
+    assert_eq!(general.valid_sold_and_reserved_ticket_count(connection).unwrap(), 0);
+    // Pooled tickets stay on their own ticket type's asset
+    let general_asset = Asset::find_by_ticket_type(general.id, connection).unwrap();
+    let tickets = TicketInstance::find_for_user(user.id, connection).unwrap();
+    assert_eq!(tickets.iter().filter(|t| t.asset_id == general_asset.id).count(), 3);
+    for ticket in tickets {
+        assert_eq!(ticket.sold_as_ticket_type_id, Some(early_bird.id));
+        assert_eq!(ticket.ticket_type(connection).unwrap().id, early_bird.id);
+    }
+
+    assert_eq!(
+        inventory_pool.counts(connection).unwrap(),
+        InventoryPoolCounts {
+            capacity: 10,
+            available: 2,
+            held: 0,
+            sold: 8,
+        }
+    );
+}
+
+#[test]
+fn hold_from_pool() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project
+        .create_event()
+        .with_a_specific_number_of_tickets(5)
+        .with_ticket_type_count(2)
+        .with_ticket_pricing()
+        .finish();
+    let ticket_types = event.ticket_types(true, None, connection).unwrap();
+    let inventory_pool = InventoryPool::create(event.id, "Main floor".to_string())
+        .commit(None, connection)
+        .unwrap();
+    inventory_pool
+        .update_ticket_types(vec![ticket_types[0].id, ticket_types[1].id], None, connection)
+        .unwrap();
+
+    let hold = project
+        .create_hold()
+        .with_ticket_type_id(ticket_types[0].id)
+        .with_quantity(7)
+        .finish();
+    assert_eq!(hold.quantity(connection).unwrap(), (7, 7));
+    assert_eq!(
+        TicketType::find(ticket_types[1].id, connection)
+            .unwrap()
+            .valid_available_ticket_count(connection)
+            .unwrap(),
+        3
+    );
+    assert_eq!(inventory_pool.counts(connection).unwrap().held, 7);
+
+    // Releasing held tickets returns them to the pool
+    hold.set_quantity(None, 2, connection).unwrap();
+    assert_eq!(inventory_pool.counts(connection).unwrap().available, 8);
+}
+
+#[test]
+fn delete() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().with_ticket_type_count(2).finish();
+    let ticket_types = event.ticket_types(true, None, connection).unwrap();
+    let inventory_pool = InventoryPool::create(event.id, "Main floor".to_string())
+        .commit(None, connection)
+        .unwrap();
+    inventory_pool
+        .update_ticket_types(vec![ticket_types[0].id, ticket_types[1].id], None, connection)
+        .unwrap();
+
+    inventory_pool.delete(None, connection).unwrap();
+    assert!(InventoryPool::find(inventory_pool.id, connection).is_err());
+    assert!(InventoryPool::find_for_event(event.id, connection).unwrap().is_empty());
+    assert_eq!(
+        TicketType::find(ticket_types[0].id, connection)
+            .unwrap()
+            .inventory_pool_id,
+        None
+    );
+}
@@ -0,0 +1,71 @@
+use db::dev::TestProject;
+use db::models::*;
+
+#[test]
+fn find_or_create() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+
+    let account =
+        LedgerAccount::find_or_create(organization.id, LedgerAccountTypes::Cash, Currencies::USD, connection).unwrap();
+    assert_eq!(account.organization_id, organization.id);
+    assert_eq!(account.account_type, LedgerAccountTypes::Cash);
+    assert_eq!(account.currency, Currencies::USD);
+    assert_eq!(
+        LedgerAccount::find_or_create(organization.id, LedgerAccountTypes::Cash, Currencies::USD, connection)
+            .unwrap()
+            .id,
+        account.id
+    );
+
+    // Accounts are kept per currency
+    let eur_account =
+        LedgerAccount::find_or_create(organization.id, LedgerAccountTypes::Cash, Currencies::EUR, connection).unwrap();
+    assert_ne!(eur_account.id, account.id);
+    assert_eq!(
+        LedgerAccount::find_for_organization(organization.id, Currencies::USD, connection).unwrap(),
+        vec![account]
+    );
+}
+
+#[test]
+fn trial_balance() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let order = project.create_order().quantity(2).is_paid().finish();
+    let organization_id = Event::find(order.main_event_id(connection).unwrap(), connection)
+        .unwrap()
+        .organization_id;
+    let order_total: i64 = order
+        .items(connection)
+        .unwrap()
+        .iter()
+        .map(|i| i.unit_price_in_cents * i.quantity)
+        .sum();
+
+    let trial_balance = LedgerAccount::trial_balance(organization_id, order.currency, connection).unwrap();
+    assert_eq!(trial_balance.organization_id, organization_id);
+    assert!(trial_balance.balanced);
+    assert_eq!(trial_balance.total_debit_in_cents, order_total);
+    assert_eq!(trial_balance.total_credit_in_cents, order_total);
+    let cash = trial_balance
+        .accounts
+        .iter()
+        .find(|a| a.account_type == LedgerAccountTypes::Cash)
+        .unwrap();
+    assert_eq!(cash.debit_in_cents, order_total);
+    assert_eq!(cash.balance_in_cents, order_total);
+    let gross_sales = trial_balance
+        .accounts
+        .iter()
+        .find(|a| a.account_type == LedgerAccountTypes::GrossSales)
+        .unwrap();
+    assert_eq!(gross_sales.debit_in_cents, 0);
+    assert!(gross_sales.balance_in_cents > 0);
+
+    // Nothing is recorded for other currencies
+    let trial_balance = LedgerAccount::trial_balance(organization_id, Currencies::EUR, connection).unwrap();
+    assert!(trial_balance.accounts.is_empty());
+    assert!(trial_balance.balanced);
+}
@@ -0,0 +1,411 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::schema::{ledger_journal_entries, orders};
+use diesel;
+use diesel::dsl;
+use diesel::prelude::*;
+
+fn account_totals(entry: &LedgerJournalEntry, connection: &PgConnection) -> Vec<(LedgerAccountTypes, i64, i64)> {
+    let mut totals: Vec<(LedgerAccountTypes, i64, i64)> = entry
+        .lines(connection)
+        .unwrap()
+        .into_iter()
+        .map(|line| {
+            let account = LedgerAccount::find(line.ledger_account_id, connection).unwrap();
+            (account.account_type, line.debit_in_cents, line.credit_in_cents)
+        })
+        .collect();
+    totals.sort_by_key(|(account_type, _, _)| account_type.to_string());
+    totals
+}
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let user = project.create_user().finish();
+
+    let entry = LedgerJournalEntry::create(
+        organization.id,
+        Currencies::USD,
+        Tables::Organizations,
+        organization.id,
+        "Test entry".to_string(),
+        Some(user.id),
+    )
+    .debit(LedgerAccountTypes::Cash, 150)
+    .credit(LedgerAccountTypes::GrossSales, 100)
+    .credit(LedgerAccountTypes::PlatformFees, 60)
+    .debit(LedgerAccountTypes::PlatformFees, 10)
+    .commit(connection)
+    .unwrap();
+    assert_eq!(entry.organization_id, organization.id);
+    assert_eq!(entry.created_by, Some(user.id));
+    assert_eq!(
+        account_totals(&entry, connection),
+        vec![
+            (LedgerAccountTypes::Cash, 150, 0),
+            (LedgerAccountTypes::GrossSales, 0, 100),
+            (LedgerAccountTypes::PlatformFees, 0, 50),
+        ]
+    );
+}
+
+#[test]
+fn commit_unbalanced() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+
+    let entry = LedgerJournalEntry::create(
+        organization.id,
+        Currencies::USD,
+        Tables::Organizations,
+        organization.id,
+        "Test entry".to_string(),
+        None,
+    );
+    assert!(entry.is_empty());
+    assert!(entry.clone().commit(connection).is_err());
+
+    let entry = entry
+        .debit(LedgerAccountTypes::Cash, 150)
+        .credit(LedgerAccountTypes::GrossSales, 100);
+    assert!(!entry.is_empty());
+    assert!(entry.commit(connection).is_err());
+}
+
+#[test]
+fn journal_is_append_only() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let order = project.create_order().is_paid().finish();
+    let entry = LedgerJournalEntry::find_for_source(Tables::Orders, order.id, connection)
+        .unwrap()
+        .remove(0);
+
+    assert!(
+        diesel::update(ledger_journal_entries::table.filter(ledger_journal_entries::id.eq(entry.id)))
+            .set(ledger_journal_entries::description.eq("Changed"))
+            .execute(connection)
+            .is_err()
+    );
+}
+
+#[test]
+fn record_order_payment() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let order = project.create_order().quantity(2).is_paid().finish();
+    let organization_id = Event::find(order.main_event_id(connection).unwrap(), connection)
+        .unwrap()
+        .organization_id;
+
+    let entries = LedgerJournalEntry::find_for_source(Tables::Orders, order.id, connection).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].organization_id, organization_id);
+    assert_eq!(entries[0].currency, order.currency);
+
+    let items = order.items(connection).unwrap();
+    let amount = |item_type: OrderItemTypes| -> i64 {
+        items
+            .iter()
+            .filter(|i| i.item_type == item_type)
+            .map(|i| i.unit_price_in_cents * i.quantity)
+            .sum()
+    };
+    let gross_sales = amount(OrderItemTypes::Tickets);
+    let platform_fees = amount(OrderItemTypes::PerUnitFees) + amount(OrderItemTypes::EventFees);
+    assert!(gross_sales > 0);
+    assert!(platform_fees > 0);
+    assert_eq!(
+        account_totals(&entries[0], connection),
+        vec![
+            (LedgerAccountTypes::Cash, gross_sales + platform_fees, 0),
+            (LedgerAccountTypes::GrossSales, 0, gross_sales),
+            (LedgerAccountTypes::PlatformFees, 0, platform_fees),
+        ]
+    );
+}
+
+#[test]
+fn record_refund() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let mut order = project.create_order().for_user(&user).quantity(2).is_paid().finish();
+    let items = order.items(connection).unwrap();
+    let order_item = items.iter().find(|i| i.item_type == OrderItemTypes::Tickets).unwrap();
+    let ticket = &TicketInstance::find_for_order_item(order_item.id, connection).unwrap()[0];
+    let refund_items = vec![RefundItemRequest {
+        order_item_id: order_item.id,
+        ticket_instance_id: Some(ticket.id),
+    }];
+
+    let (refund, amount) = order.refund(&refund_items, user.id, None, false, connection).unwrap();
+    let entries = LedgerJournalEntry::find_for_source(Tables::Refunds, refund.id, connection).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].created_by, Some(user.id));
+
+    let fee_item = order_item.find_fee_item(connection).unwrap().unwrap();
+    assert_eq!(amount, order_item.unit_price_in_cents + fee_item.unit_price_in_cents);
+    assert_eq!(
+        account_totals(&entries[0], connection),
+        vec![
+            (LedgerAccountTypes::Cash, 0, amount),
+            (LedgerAccountTypes::GrossSales, order_item.unit_price_in_cents, 0),
+            (LedgerAccountTypes::PlatformFees, fee_item.unit_price_in_cents, 0),
+        ]
+    );
+}
+
+#[test]
+fn record_settlement() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    Settlement::finalize_settlements(connection).unwrap();
+
+    let entries = LedgerJournalEntry::find_for_source(Tables::Settlements, settlement.id, connection).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        account_totals(&entries[0], connection),
+        vec![
+            (LedgerAccountTypes::GrossSales, 200, 0),
+            (LedgerAccountTypes::PayableToOrganization, 0, 270),
+            (LedgerAccountTypes::PlatformFees, 70, 0),
+        ]
+    );
+}
+
+#[test]
+fn record_settlement_with_partner_commission() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    let event = project.create_event().with_organization(&organization).finish();
+    let partner = Partner::create("Example Tickets".to_string(), vec![], 10f32)
+        .commit(None, connection)
+        .unwrap();
+    let mut commission_entry = SettlementEntry::create(
+        settlement.id,
+        SettlementEntryTypes::PartnerCommission,
+        event.id,
+        None,
+        0,
+        0,
+        2,
+        0,
+        -20,
+        organization.currency,
+    );
+    commission_entry.partner_id = Some(partner.id);
+    commission_entry.commit(connection).unwrap();
+    Settlement::finalize_settlements(connection).unwrap();
+
+    let entries = LedgerJournalEntry::find_for_source(Tables::Settlements, settlement.id, connection).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        account_totals(&entries[0], connection),
+        vec![
+            (LedgerAccountTypes::GrossSales, 200, 0),
+            (LedgerAccountTypes::PartnerCommissions, 0, 20),
+            (LedgerAccountTypes::PayableToOrganization, 0, 250),
+            (LedgerAccountTypes::PlatformFees, 70, 0),
+        ]
+    );
+}
+
+#[test]
+fn record_settlement_with_affiliate_commission() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    let event = project.create_event().with_organization(&organization).finish();
+    let promoter = project.create_user().finish();
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, None, "Newsletter".to_string())
+        .commit(None, connection)
+        .unwrap();
+    let mut commission_entry = SettlementEntry::create(
+        settlement.id,
+        SettlementEntryTypes::AffiliateCommission,
+        event.id,
+        None,
+        0,
+        0,
+        2,
+        0,
+        -30,
+        organization.currency,
+    );
+    commission_entry.affiliate_link_id = Some(affiliate_link.id);
+    commission_entry.commit(connection).unwrap();
+    Settlement::finalize_settlements(connection).unwrap();
+
+    let entries = LedgerJournalEntry::find_for_source(Tables::Settlements, settlement.id, connection).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        account_totals(&entries[0], connection),
+        vec![
+            (LedgerAccountTypes::AffiliateCommissions, 0, 30),
+            (LedgerAccountTypes::GrossSales, 200, 0),
+            (LedgerAccountTypes::PayableToOrganization, 0, 240),
+            (LedgerAccountTypes::PlatformFees, 70, 0),
+        ]
+    );
+}
+
+#[test]
+fn record_settlement_adjustment() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+
+    let credit = project
+        .create_settlement_adjustment()
+        .with_settlement(&settlement)
+        .with_settlement_adjustment_type(SettlementAdjustmentTypes::ManualCredit)
+        .with_amount_in_cents(30)
+        .finish();
+    let chargeback = project
+        .create_settlement_adjustment()
+        .with_settlement(&settlement)
+        .with_settlement_adjustment_type(SettlementAdjustmentTypes::Chargeback)
+        .finish();
+
+    let entries = LedgerJournalEntry::find_for_source(Tables::SettlementAdjustments, credit.id, connection).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].currency, organization.currency);
+    assert_eq!(
+        account_totals(&entries[0], connection),
+        vec![
+            (LedgerAccountTypes::PayableToOrganization, 0, 30),
+            (LedgerAccountTypes::PlatformFees, 30, 0),
+        ]
+    );
+    let entries =
+        LedgerJournalEntry::find_for_source(Tables::SettlementAdjustments, chargeback.id, connection).unwrap();
+    assert_eq!(
+        account_totals(&entries[0], connection),
+        vec![
+            (LedgerAccountTypes::Cash, 0, 100),
+            (LedgerAccountTypes::PayableToOrganization, 100, 0),
+        ]
+    );
+
+    // Removing the adjustment reverses its entry
+    let credit_id = credit.id;
+    credit.destroy(connection).unwrap();
+    let entries = LedgerJournalEntry::find_for_source(Tables::SettlementAdjustments, credit_id, connection).unwrap();
+    assert_eq!(entries.len(), 2);
+    let reversal = entries
+        .iter()
+        .find(|e| e.reverses_ledger_journal_entry_id.is_some())
+        .unwrap();
+    assert_eq!(
+        account_totals(reversal, connection),
+        vec![
+            (LedgerAccountTypes::PayableToOrganization, 30, 0),
+            (LedgerAccountTypes::PlatformFees, 0, 30),
+        ]
+    );
+
+    // Entries are only reversed once
+    assert!(
+        LedgerJournalEntry::reverse_for_source(Tables::SettlementAdjustments, credit_id, None, connection)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn record_settlement_payout() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    project
+        .create_organization_bank_account()
+        .with_organization(&organization)
+        .finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    Settlement::finalize_settlements(connection).unwrap();
+    let payout = settlement.payouts(connection).unwrap().remove(0);
+    assert!(
+        LedgerJournalEntry::find_for_source(Tables::SettlementPayouts, payout.id, connection)
+            .unwrap()
+            .is_empty()
+    );
+
+    let payout = payout
+        .update_status(SettlementPayoutStatus::Paid, None, Some(user.id), connection)
+        .unwrap();
+    let entries = LedgerJournalEntry::find_for_source(Tables::SettlementPayouts, payout.id, connection).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        account_totals(&entries[0], connection),
+        vec![
+            (LedgerAccountTypes::Cash, 0, 270),
+            (LedgerAccountTypes::PayableToOrganization, 270, 0),
+        ]
+    );
+
+    // Returned payouts are owed to the organization again
+    payout
+        .update_status(SettlementPayoutStatus::Returned, None, Some(user.id), connection)
+        .unwrap();
+    let entries = LedgerJournalEntry::find_for_source(Tables::SettlementPayouts, payout.id, connection).unwrap();
+    assert_eq!(entries.len(), 2);
+    let trial_balance = LedgerAccount::trial_balance(organization.id, Currencies::USD, connection).unwrap();
+    let payable = trial_balance
+        .accounts
+        .iter()
+        .find(|a| a.account_type == LedgerAccountTypes::PayableToOrganization)
+        .unwrap();
+    assert_eq!(payable.balance_in_cents, 270);
+}
+
+#[test]
+fn check_integrity() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    project.create_order().is_paid().finish();
+    assert!(LedgerJournalEntry::check_integrity(connection).unwrap().is_empty());
+
+    // Orders marked as paid without going through checkout are missing their journal entry
+    let order = project.create_order().finish();
+    diesel::update(orders::table.filter(orders::id.eq(order.id)))
+        .set((orders::status.eq(OrderStatus::Paid), orders::paid_at.eq(dsl::now)))
+        .execute(connection)
+        .unwrap();
+    let issues = LedgerJournalEntry::check_integrity(connection).unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].issue_type, "MissingOrderEntry");
+    assert_eq!(issues[0].source_table, Tables::Orders.to_string());
+    assert_eq!(issues[0].source_id, order.id);
+}
@@ -0,0 +1,295 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::schema::ticket_instances;
+use db::utils::dates;
+use db::utils::errors::ErrorCode::ValidationError;
+use db::utils::hash::sha256;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// Loot box ticket type holding 2 tickets per box drawn from 6 common and 2 rare tickets
+fn loot_box_ticket_type(project: &TestProject, quantity: u32) -> (Event, TicketType) {
+    let connection = project.get_connection();
+    let content_event = project.create_event().finish();
+    let common = NewRarity {
+        name: "Common".to_string(),
+        event_id: Some(content_event.id),
+        rank: 1,
+    }
+    .commit(connection)
+    .unwrap();
+    let rare = NewRarity {
+        name: "Rare".to_string(),
+        event_id: Some(content_event.id),
+        rank: 2,
+    }
+    .commit(connection)
+    .unwrap();
+    add_ticket_type(
+        &content_event,
+        "Common",
+        6,
+        TicketTypeType::Token,
+        vec![],
+        Some(common.id),
+        connection,
+    );
+    add_ticket_type(
+        &content_event,
+        "Rare",
+        2,
+        TicketTypeType::Token,
+        vec![],
+        Some(rare.id),
+        connection,
+    );
+
+    let loot_box_event = project.create_event().finish();
+    let ticket_type = add_ticket_type(
+        &loot_box_event,
+        "Loot Box",
+        quantity,
+        TicketTypeType::LootBox,
+        vec![NewLootBoxContent {
+            ticket_type_id: Uuid::nil(),
+            content_event_id: content_event.id,
+            min_rarity_id: Some(common.id),
+            max_rarity_id: Some(rare.id),
+            content_ticket_type_id: None,
+            quantity_per_box: 2,
+        }],
+        None,
+        connection,
+    );
+    ticket_type
+        .add_ticket_pricing(
+            "Standard".to_string(),
+            dates::now().add_days(-1).finish(),
+            dates::now().add_days(2).finish(),
+            500,
+            false,
+            None,
+            None,
+            connection,
+        )
+        .unwrap();
+    (content_event, ticket_type)
+}
+
+fn add_ticket_type(
+    event: &Event,
+    name: &str,
+    quantity: u32,
+    ticket_type_type: TicketTypeType,
+    contents: Vec<NewLootBoxContent>,
+    rarity_id: Option<Uuid>,
+    connection: &PgConnection,
+) -> TicketType {
+    event
+        .add_ticket_type(
+            name.to_string(),
+            None,
+            quantity,
+            Some(dates::now().add_days(-1).finish()),
+            Some(dates::now().add_days(2).finish()),
+            TicketTypeEndDateType::Manual,
+            None,
+            None,
+            0,
+            100,
+            TicketTypeVisibility::Always,
+            None,
+            0,
+            true,
+            true,
+            true,
+            ticket_type_type,
+            contents,
+            rarity_id,
+            None,
+            None,
+            None,
+            connection,
+        )
+        .unwrap()
+}
+
+fn purchase_loot_box(project: &TestProject, ticket_type: &TicketType, user: &User) -> TicketInstance {
+    let connection = project.get_connection();
+    let order = project
+        .create_order()
+        .for_tickets(ticket_type.id)
+        .for_user(user)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    let order_item = order
+        .items(connection)
+        .unwrap()
+        .into_iter()
+        .find(|i| i.ticket_type_id == Some(ticket_type.id))
+        .unwrap();
+    TicketInstance::find_for_order_item(order_item.id, connection)
+        .unwrap()
+        .remove(0)
+}
+
+#[test]
+fn commit_on_purchase() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let (_, ticket_type) = loot_box_ticket_type(&project, 2);
+    let loot_box = purchase_loot_box(&project, &ticket_type, &user);
+
+    let draw = LootBoxDraw::find_for_ticket_instance(loot_box.id, connection)
+        .unwrap()
+        .unwrap();
+    assert_eq!(draw.server_seed_hash, sha256::digest(&draw.server_seed));
+    assert_eq!(draw.opened_at, None);
+
+    // The seed stays secret until the box is opened
+    let display_draw = draw.for_display();
+    assert_eq!(display_draw.server_seed, None);
+    assert_eq!(display_draw.server_seed_hash, draw.server_seed_hash);
+    assert!(display_draw.candidate_ticket_instance_ids.is_empty());
+    assert!(display_draw.drawn_ticket_instance_ids.is_empty());
+
+    // Loot boxes cannot be scanned at the door
+    let result = TicketInstance::redeem_ticket(
+        loot_box.id,
+        loot_box.redeem_key.clone().unwrap(),
+        user.id,
+        CheckInSource::Scanned,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(result, RedeemResults::TicketInvalid);
+}
+
+#[test]
+fn open() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let (content_event, ticket_type) = loot_box_ticket_type(&project, 2);
+    let loot_box = purchase_loot_box(&project, &ticket_type, &user);
+    let committed_hash = LootBoxDraw::find_for_ticket_instance(loot_box.id, connection)
+        .unwrap()
+        .unwrap()
+        .server_seed_hash;
+
+    let (draw, tickets) = LootBoxDraw::open(loot_box.id, Some("lucky".to_string()), user.id, connection).unwrap();
+    assert_eq!(tickets.len(), 2);
+    assert_eq!(draw.server_seed_hash, committed_hash);
+    assert_eq!(draw.client_seed, Some("lucky".to_string()));
+    assert_eq!(draw.opened_by_user_id, Some(user.id));
+    let display_draw = draw.for_display();
+    assert_eq!(display_draw.server_seed, Some(draw.server_seed.clone()));
+    let mut drawn_ids: Vec<Uuid> = tickets.iter().map(|t| t.id).collect();
+    let mut expected_ids = draw.drawn_ticket_instance_ids.clone();
+    drawn_ids.sort();
+    expected_ids.sort();
+    assert_eq!(drawn_ids, expected_ids);
+
+    // The draw can be replayed from the revealed seeds and the recorded candidates
+    assert_eq!(
+        display_draw.candidate_ticket_instance_ids,
+        draw.candidate_ticket_instance_ids
+    );
+    let mut candidates = draw.candidate_ticket_instance_ids.clone();
+    assert_eq!(candidates.len(), 4);
+    let mut replayed_ids = Vec::new();
+    for nonce in 0..2 {
+        let index = LootBoxDraw::draw_index(&draw.server_seed, "lucky", nonce, candidates.len());
+        replayed_ids.push(candidates.remove(index));
+    }
+    assert_eq!(replayed_ids, draw.drawn_ticket_instance_ids);
+
+    let wallet = user.default_wallet(connection).unwrap();
+    for ticket in &tickets {
+        let ticket = TicketInstance::find(ticket.id, connection).unwrap();
+        assert_eq!(ticket.status, TicketInstanceStatus::Purchased);
+        assert_eq!(ticket.wallet_id, wallet.id);
+        assert_eq!(ticket.parent_id, Some(loot_box.id));
+        assert!(ticket.redeem_key.is_some());
+        assert_eq!(ticket.ticket_type(connection).unwrap().event_id, content_event.id);
+    }
+
+    let loot_box = TicketInstance::find(loot_box.id, connection).unwrap();
+    assert_eq!(loot_box.status, TicketInstanceStatus::Redeemed);
+    assert_eq!(loot_box.check_in_source, Some(CheckInSource::LootBox));
+    assert_eq!(
+        DomainEvent::find(
+            Tables::TicketInstances,
+            Some(loot_box.id),
+            Some(DomainEventTypes::TicketInstanceLootBoxOpened),
+            connection,
+        )
+        .unwrap()
+        .len(),
+        1
+    );
+
+    // The unopened box still holds a full set of contents
+    let asset = Asset::find_by_ticket_type(ticket_type.id, connection).unwrap();
+    let unopened_boxes: Vec<TicketInstance> = ticket_instances::table
+        .filter(ticket_instances::asset_id.eq(asset.id))
+        .filter(ticket_instances::id.ne(loot_box.id))
+        .load(connection)
+        .unwrap();
+    assert_eq!(unopened_boxes.len(), 1);
+    let children = unopened_boxes[0].find_children(connection).unwrap();
+    assert_eq!(children.len(), 2);
+    assert!(children.iter().all(|c| c.status == TicketInstanceStatus::Available));
+
+    // Drawn tickets count towards the user's collections
+    let collection = Collection::create("Collection", user.id).commit(connection).unwrap();
+    let drawn_ticket_type_id = tickets[0].ticket_type(connection).unwrap().id;
+    CollectionItem::create(collection.id, drawn_ticket_type_id)
+        .commit(connection)
+        .unwrap();
+    let items = CollectionItem::find_for_collection_with_num_owned(collection.id, user.id, connection).unwrap();
+    let expected_owned = tickets
+        .iter()
+        .filter(|t| t.ticket_type(connection).unwrap().id == drawn_ticket_type_id)
+        .count() as i64;
+    assert_eq!(items[0].number_owned, expected_owned);
+
+    // Boxes can only be opened once
+    assert!(LootBoxDraw::open(loot_box.id, None, user.id, connection).is_err());
+}
+
+#[test]
+fn open_requires_owner() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let (_, ticket_type) = loot_box_ticket_type(&project, 1);
+    let loot_box = purchase_loot_box(&project, &ticket_type, &user);
+
+    assert!(LootBoxDraw::open(loot_box.id, None, user2.id, connection).is_err());
+    let loot_box = TicketInstance::find(loot_box.id, connection).unwrap();
+    assert_eq!(loot_box.status, TicketInstanceStatus::Purchased);
+}
+
+#[test]
+fn open_with_invalid_client_seed() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let (_, ticket_type) = loot_box_ticket_type(&project, 1);
+    let loot_box = purchase_loot_box(&project, &ticket_type, &user);
+
+    let result = LootBoxDraw::open(loot_box.id, Some("  ".to_string()), user.id, connection);
+    match result {
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("client_seed"));
+            }
+            _ => panic!("Expected validation error"),
+        },
+        _ => panic!("Expected validation error"),
+    }
+}
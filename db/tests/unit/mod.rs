@@ -1,9 +1,16 @@
+pub mod access_zones;
+pub mod accounting_account_mappings;
+pub mod accounting_exports;
 pub mod activities;
+pub mod affiliate_links;
 pub mod announcement_engagements;
 pub mod announcements;
 pub mod artists;
 pub mod assets;
 pub mod broadcasts;
+pub mod cash_drawer_sessions;
+pub mod checkout_answers;
+pub mod checkout_questions;
 pub mod codes;
 pub mod collection_items;
 pub mod collections;
@@ -16,25 +23,36 @@ pub mod domain_events;
 pub mod event_artists;
 pub mod event_interest;
 pub mod event_report_subscribers;
+pub mod event_series;
 pub mod event_users;
 pub mod events;
 pub mod external_logins;
+pub mod fan_segments;
 pub mod fee_schedule_ranges;
 pub mod fee_schedules;
 pub mod genres;
 pub mod global;
 pub mod holds;
+pub mod inventory_pools;
+pub mod ledger_accounts;
+pub mod ledger_journal_entries;
+pub mod loot_box_draws;
 pub mod notes;
 pub mod order_items;
 pub mod orders;
+pub mod organization_bank_accounts;
+pub mod organization_brandings;
 pub mod organization_interactions;
 pub mod organization_invites;
 pub mod organization_users;
 pub mod organization_venues;
 pub mod organizations;
+pub mod packages;
+pub mod partners;
 pub mod paging;
 pub mod payment_methods;
 pub mod payments;
+pub mod products;
 pub mod push_notification_tokens;
 pub mod refund_items;
 pub mod refunded_tickets;
@@ -44,15 +62,22 @@ pub mod reports;
 pub mod services;
 pub mod settlement_adjustments;
 pub mod settlement_entries;
+pub mod settlement_payouts;
+pub mod settlement_statement_subscribers;
+pub mod settlement_statements;
 pub mod settlements;
 pub mod slugs;
 pub mod stages;
+pub mod tax_rules;
 pub mod temporary_users;
 pub mod ticket_instances;
 pub mod ticket_pricing;
+pub mod ticket_scans;
 pub mod ticket_type_codes;
 pub mod ticket_types;
+pub mod trades;
 pub mod transfer_tickets;
 pub mod transfers;
 pub mod users;
 pub mod venues;
+pub mod wallet_passes;
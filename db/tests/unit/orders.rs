@@ -5037,6 +5037,70 @@ fn calculate_cart_total() {
     assert_eq!(total, 5100);
 }
 
+#[test]
+fn add_credit_payment() {
+    let project = TestProject::new();
+    let conn = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let mut cart = Order::find_or_create_cart(&user, conn).unwrap();
+    let ticket_type = &event.ticket_types(true, None, conn).unwrap()[0];
+    cart.update_quantities(
+        user.id,
+        &[UpdateOrderItem {
+            ticket_type_id: ticket_type.id,
+            quantity: 2,
+            redemption_code: None,
+        }],
+        false,
+        false,
+        conn,
+    )
+    .unwrap();
+    let total = cart.calculate_total(conn).unwrap();
+
+    // Balance too low
+    UserCreditEntry::create(
+        user.id,
+        total - 1,
+        cart.currency,
+        Tables::Trades,
+        Uuid::new_v4(),
+        "Cash received in trade".to_string(),
+        None,
+    )
+    .commit(conn)
+    .unwrap();
+    assert!(cart.add_credit_payment(user.id, conn).is_err());
+
+    UserCreditEntry::create(
+        user.id,
+        1,
+        cart.currency,
+        Tables::Trades,
+        Uuid::new_v4(),
+        "Cash received in trade".to_string(),
+        None,
+    )
+    .commit(conn)
+    .unwrap();
+    let payment = cart.add_credit_payment(user.id, conn).unwrap();
+    assert_eq!(payment.payment_method, PaymentMethods::Credit);
+    assert_eq!(payment.amount, total);
+    assert_eq!(Order::find(cart.id, conn).unwrap().status, OrderStatus::Paid);
+
+    let entries = UserCreditEntry::find_for_source(Tables::Orders, cart.id, conn).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].amount_in_cents, -total);
+    assert_eq!(
+        UserCreditEntry::balances_for_user(user.id, conn).unwrap(),
+        vec![UserCreditBalance {
+            currency: cart.currency,
+            balance_in_cents: 0,
+        }]
+    );
+}
+
 #[test]
 fn add_external_payment() {
     let project = TestProject::new();
@@ -6612,3 +6676,112 @@ fn move_order_to_past(order: &Order, to_date: NaiveDateTime, connection: &PgConn
     .execute(connection)
     .unwrap();
 }
+
+#[test]
+fn update_quantities_sets_order_currency() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().with_fees().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let event = event
+        .update(
+            None,
+            EventEditableAttributes {
+                currency: Some(Some(Currencies::GBP)),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    let user = project.create_user().finish();
+    let mut cart = Order::find_or_create_cart(&user, connection).unwrap();
+    assert_eq!(cart.currency, Currencies::USD);
+
+    let ticket_type = &event.ticket_types(true, None, connection).unwrap()[0];
+    cart.update_quantities(
+        user.id,
+        &[UpdateOrderItem {
+            ticket_type_id: ticket_type.id,
+            quantity: 2,
+            redemption_code: None,
+        }],
+        false,
+        false,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(cart.currency, Currencies::GBP);
+    assert_eq!(Order::find(cart.id, connection).unwrap().currency, Currencies::GBP);
+}
+
+#[test]
+fn update_quantities_with_mixed_currencies() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().with_fees().finish();
+    let organization2 = project
+        .create_organization()
+        .with_fees()
+        .finish()
+        .update(
+            OrganizationEditableAttributes {
+                currency: Some(Currencies::EUR),
+                ..Default::default()
+            },
+            None,
+            &"encryption_key".to_string(),
+            connection,
+        )
+        .unwrap();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let event2 = project
+        .create_event()
+        .with_organization(&organization2)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let user = project.create_user().finish();
+    let mut cart = Order::find_or_create_cart(&user, connection).unwrap();
+    let ticket_type = &event.ticket_types(true, None, connection).unwrap()[0];
+    let ticket_type2 = &event2.ticket_types(true, None, connection).unwrap()[0];
+
+    let result = cart.update_quantities(
+        user.id,
+        &[
+            UpdateOrderItem {
+                ticket_type_id: ticket_type.id,
+                quantity: 1,
+                redemption_code: None,
+            },
+            UpdateOrderItem {
+                ticket_type_id: ticket_type2.id,
+                quantity: 1,
+                redemption_code: None,
+            },
+        ],
+        false,
+        false,
+        connection,
+    );
+    match result {
+        Ok(_) => panic!("Expected validation error"),
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("currency"));
+                assert_eq!(errors["currency"].len(), 1);
+                assert_eq!(errors["currency"][0].code, "cart_currency_mismatch");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
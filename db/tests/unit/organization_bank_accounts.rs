@@ -0,0 +1,104 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let mut new_bank_account =
+        OrganizationBankAccount::create(organization.id, PayoutMethods::Nacha, "Example Promotions".to_string());
+    new_bank_account.routing_number = Some("0210 00021".to_string());
+    new_bank_account.account_number = Some("123456789".to_string());
+    let bank_account = new_bank_account
+        .commit(Some(user.id), "encryption_key", connection)
+        .unwrap();
+
+    // Account details are stored encrypted
+    assert_eq!(bank_account.organization_id, organization.id);
+    assert_ne!(bank_account.routing_number, Some("021000021".to_string()));
+    assert_ne!(bank_account.account_number, Some("123456789".to_string()));
+
+    let mut bank_account = OrganizationBankAccount::find_for_organization(organization.id, connection).unwrap();
+    bank_account.decrypt("encryption_key").unwrap();
+    assert_eq!(bank_account.routing_number, Some("021000021".to_string()));
+    assert_eq!(bank_account.account_number, Some("123456789".to_string()));
+
+    let display_bank_account = bank_account.for_display();
+    assert_eq!(display_bank_account.account_number_last_four, Some("6789".to_string()));
+    assert_eq!(display_bank_account.iban_last_four, None);
+
+    let domain_events = DomainEvent::find(
+        Tables::Organizations,
+        Some(organization.id),
+        Some(DomainEventTypes::OrganizationBankAccountUpdated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn commit_replaces_existing_account() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let bank_account = project
+        .create_organization_bank_account()
+        .with_organization(&organization)
+        .finish();
+
+    let mut new_bank_account =
+        OrganizationBankAccount::create(organization.id, PayoutMethods::Sepa, "Example GmbH".to_string());
+    new_bank_account.iban = Some("DE89 3704 0044 0532 0130 00".to_string());
+    new_bank_account.bic = Some("cobadeffxxx".to_string());
+    let mut updated_bank_account = new_bank_account.commit(None, "encryption_key", connection).unwrap();
+    updated_bank_account.decrypt("encryption_key").unwrap();
+
+    assert_eq!(updated_bank_account.id, bank_account.id);
+    assert_eq!(updated_bank_account.payout_method, PayoutMethods::Sepa);
+    assert_eq!(updated_bank_account.account_holder_name, "Example GmbH".to_string());
+    assert_eq!(updated_bank_account.iban, Some("DE89370400440532013000".to_string()));
+    assert_eq!(updated_bank_account.bic, Some("COBADEFFXXX".to_string()));
+    assert_eq!(updated_bank_account.routing_number, None);
+    assert_eq!(updated_bank_account.account_number, None);
+}
+
+#[test]
+fn commit_with_invalid_details() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+
+    let mut new_bank_account =
+        OrganizationBankAccount::create(organization.id, PayoutMethods::Nacha, "Example Promotions".to_string());
+    new_bank_account.routing_number = Some("021000022".to_string());
+    new_bank_account.account_number = Some("12".to_string());
+    match new_bank_account.commit(None, "encryption_key", connection) {
+        Ok(_) => panic!("Expected validation error"),
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(errors["routing_number"][0].code, "invalid_routing_number");
+                assert_eq!(errors["account_number"][0].code, "invalid_account_number");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    let mut new_bank_account =
+        OrganizationBankAccount::create(organization.id, PayoutMethods::Sepa, "Example GmbH".to_string());
+    new_bank_account.iban = Some("DE89370400440532013001".to_string());
+    new_bank_account.bic = Some("COBADE".to_string());
+    match new_bank_account.commit(None, "encryption_key", connection) {
+        Ok(_) => panic!("Expected validation error"),
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(errors["iban"][0].code, "invalid_iban");
+                assert_eq!(errors["bic"][0].code, "invalid_bic");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
@@ -0,0 +1,131 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let mut new_branding = OrganizationBranding::create(organization.id);
+    new_branding.custom_domain = Some(" Tickets.Example.org ".to_string());
+    new_branding.primary_color = Some("#1a2b3c".to_string());
+    new_branding.sender_email = Some("tickets@example.org".to_string());
+    new_branding.sender_name = Some("Example Promotions".to_string());
+    let branding = new_branding.commit(Some(user.id), connection).unwrap();
+
+    assert_eq!(branding.organization_id, organization.id);
+    assert_eq!(branding.custom_domain, Some("tickets.example.org".to_string()));
+    assert_eq!(branding.primary_color, Some("#1A2B3C".to_string()));
+    assert_eq!(
+        branding.front_end_url(),
+        Some("https://tickets.example.org".to_string())
+    );
+    assert_eq!(
+        OrganizationBranding::find_by_custom_domain("tickets.example.org", connection).unwrap(),
+        branding
+    );
+
+    let domain_events = DomainEvent::find(
+        Tables::Organizations,
+        Some(organization.id),
+        Some(DomainEventTypes::OrganizationBrandingUpdated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn commit_replaces_existing_branding() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let mut new_branding = OrganizationBranding::create(organization.id);
+    new_branding.custom_domain = Some("tickets.example.org".to_string());
+    let branding = new_branding.commit(None, connection).unwrap();
+
+    let mut new_branding = OrganizationBranding::create(organization.id);
+    new_branding.logo_url = Some("https://example.org/logo.png".to_string());
+    let updated_branding = new_branding.commit(None, connection).unwrap();
+
+    assert_eq!(updated_branding.id, branding.id);
+    assert_eq!(updated_branding.custom_domain, None);
+    assert_eq!(
+        updated_branding.logo_url,
+        Some("https://example.org/logo.png".to_string())
+    );
+}
+
+#[test]
+fn commit_with_invalid_values() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let mut new_branding = OrganizationBranding::create(organization.id);
+    new_branding.custom_domain = Some("https://example.org/tickets".to_string());
+    new_branding.primary_color = Some("blue".to_string());
+    new_branding.sender_email = Some("not an email".to_string());
+
+    let result = new_branding.commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(errors["custom_domain"][0].code, "invalid_custom_domain");
+                assert_eq!(errors["primary_color"][0].code, "invalid_color");
+                assert!(errors.contains_key("sender_email"));
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn commit_with_custom_domain_in_use() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let other_organization = project.create_organization().finish();
+    let mut new_branding = OrganizationBranding::create(organization.id);
+    new_branding.custom_domain = Some("tickets.example.org".to_string());
+    new_branding.commit(None, connection).unwrap();
+
+    let mut new_branding = OrganizationBranding::create(other_organization.id);
+    new_branding.custom_domain = Some("TICKETS.example.org".to_string());
+    let result = new_branding.commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(errors["custom_domain"][0].code, "custom_domain_taken");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn organization_front_end_url() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    assert_eq!(organization.branding(connection).unwrap(), None);
+    assert_eq!(
+        organization.front_end_url("https://example.com", connection).unwrap(),
+        "https://example.com".to_string()
+    );
+
+    let mut new_branding = OrganizationBranding::create(organization.id);
+    new_branding.custom_domain = Some("tickets.example.org".to_string());
+    new_branding.commit(None, connection).unwrap();
+    assert_eq!(
+        organization.front_end_url("https://example.com", connection).unwrap(),
+        "https://tickets.example.org".to_string()
+    );
+}
@@ -1138,6 +1138,7 @@ pub fn get_additional_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -1222,6 +1223,7 @@ pub fn get_additional_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -1308,6 +1310,7 @@ pub fn get_additional_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -1393,6 +1396,7 @@ pub fn get_additional_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -1478,6 +1482,7 @@ pub fn get_additional_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -1562,6 +1567,7 @@ pub fn get_additional_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -1647,6 +1653,7 @@ pub fn get_additional_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -1731,6 +1738,7 @@ pub fn get_additional_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -1836,6 +1844,7 @@ pub fn get_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -1910,6 +1919,7 @@ pub fn get_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -2101,6 +2111,7 @@ pub fn get_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -2166,6 +2177,7 @@ pub fn get_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -2240,6 +2252,7 @@ pub fn get_scopes_for_user() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -3191,3 +3204,52 @@ fn credit_card_fees() {
         pre_cc_fee_total + (pre_cc_fee_total as f32 * (5f32 / 100f32)).round() as i64
     );
 }
+
+#[test]
+fn update_currency() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().with_fees().finish();
+    let organization = organization
+        .update(
+            OrganizationEditableAttributes {
+                currency: Some(Currencies::EUR),
+                ..Default::default()
+            },
+            None,
+            &"encryption_key".to_string(),
+            connection,
+        )
+        .unwrap();
+    assert_eq!(organization.currency, Currencies::EUR);
+    assert_eq!(
+        FeeSchedule::find(organization.fee_schedule_id, connection)
+            .unwrap()
+            .currency,
+        Currencies::EUR
+    );
+
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    project.create_order().for_event(&event).is_paid().finish();
+
+    // Currency is locked once orders exist for the organization
+    let result = organization.update(
+        OrganizationEditableAttributes {
+            currency: Some(Currencies::USD),
+            ..Default::default()
+        },
+        None,
+        &"encryption_key".to_string(),
+        connection,
+    );
+    assert!(result.is_err());
+    assert_eq!(
+        Organization::find(organization.id, connection).unwrap().currency,
+        Currencies::EUR
+    );
+}
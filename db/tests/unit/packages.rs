@@ -0,0 +1,414 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+fn festival_events(project: &TestProject) -> (Organization, Event, Event) {
+    let organization = project.create_organization().with_fees().with_event_fee().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let event2 = project
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    (organization, event, event2)
+}
+
+#[test]
+fn create() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let package = Package::create(organization.id, "Season Pass".to_string())
+        .commit(None, connection)
+        .unwrap();
+    assert_eq!(package.organization_id, organization.id);
+    assert_eq!(package.name, "Season Pass".to_string());
+
+    let domain_events = DomainEvent::find(
+        Tables::Packages,
+        Some(package.id),
+        Some(DomainEventTypes::PackageCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn for_display() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let (organization, event, event2) = festival_events(&project);
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let ticket_type2 = event2.ticket_types(true, None, connection).unwrap().remove(0);
+    let package = project
+        .create_package()
+        .with_organization(&organization)
+        .with_ticket_type(&ticket_type, 1, 6000)
+        .with_ticket_type(&ticket_type2, 2, 2000)
+        .finish();
+
+    let display_package = package.clone().for_display(connection).unwrap();
+    assert_eq!(display_package.package, package);
+    assert_eq!(display_package.price_in_cents, 10000);
+    assert_eq!(display_package.ticket_types.len(), 2);
+    assert_eq!(
+        display_package.available,
+        (ticket_type2.valid_available_ticket_count(connection).unwrap() / 2) as i64
+    );
+}
+
+#[test]
+fn add_ticket_type_from_other_organization() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let package = project.create_package().finish();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+
+    let result = PackageTicketType::create(package.id, ticket_type.id, 0, 1000).commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("ticket_type_id"));
+                assert!(errors.contains_key("quantity"));
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn ticket_types_cannot_change_after_package_is_ordered() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let (organization, event, event2) = festival_events(&project);
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let ticket_type2 = event2.ticket_types(true, None, connection).unwrap().remove(0);
+    let package = project
+        .create_package()
+        .with_organization(&organization)
+        .with_ticket_type(&ticket_type, 1, 5000)
+        .finish();
+    project.create_order().quantity(0).with_package(&package, 1).finish();
+
+    assert!(PackageTicketType::create(package.id, ticket_type2.id, 1, 5000)
+        .commit(None, connection)
+        .is_err());
+    assert!(package.ticket_types(connection).unwrap()[0]
+        .destroy(None, connection)
+        .is_err());
+}
+
+#[test]
+fn find_for_event() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let (organization, event, event2) = festival_events(&project);
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let ticket_type2 = event2.ticket_types(true, None, connection).unwrap().remove(0);
+    let package = project
+        .create_package()
+        .with_organization(&organization)
+        .with_ticket_type(&ticket_type, 1, 5000)
+        .with_ticket_type(&ticket_type2, 1, 5000)
+        .finish();
+    let package2 = project
+        .create_package()
+        .with_organization(&organization)
+        .with_name("Weekend Pass")
+        .with_ticket_type(&ticket_type2, 1, 5000)
+        .finish();
+
+    assert_eq!(
+        Package::find_for_event(event.id, connection).unwrap(),
+        vec![package.clone()]
+    );
+    assert_eq!(
+        Package::find_for_event(event2.id, connection).unwrap(),
+        vec![package, package2]
+    );
+}
+
+#[test]
+fn update_package_quantities() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let (organization, event, event2) = festival_events(&project);
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let ticket_type2 = event2.ticket_types(true, None, connection).unwrap().remove(0);
+    let package = project
+        .create_package()
+        .with_organization(&organization)
+        .with_ticket_type(&ticket_type, 1, 6000)
+        .with_ticket_type(&ticket_type2, 2, 2000)
+        .finish();
+    let mut cart = Order::find_or_create_cart(&user, connection).unwrap();
+
+    cart.update_package_quantities(
+        user.id,
+        &[UpdatePackageItem {
+            package_id: package.id,
+            quantity: 2,
+        }],
+        false,
+        connection,
+    )
+    .unwrap();
+
+    // Each ticket type is added for its own event at the price allocated by the package
+    let items: Vec<OrderItem> = cart
+        .items(connection)
+        .unwrap()
+        .into_iter()
+        .filter(|i| i.item_type == OrderItemTypes::Tickets)
+        .collect();
+    assert_eq!(items.len(), 2);
+    let item = items.iter().find(|i| i.event_id == Some(event.id)).unwrap();
+    assert_eq!(item.quantity, 2);
+    assert_eq!(item.unit_price_in_cents, 6000);
+    assert_eq!(item.package_id, Some(package.id));
+    let item2 = items.iter().find(|i| i.event_id == Some(event2.id)).unwrap();
+    assert_eq!(item2.quantity, 4);
+    assert_eq!(item2.unit_price_in_cents, 2000);
+    assert_eq!(item2.package_id, Some(package.id));
+    assert_eq!(
+        TicketInstance::find_for_order_item(item.id, connection).unwrap().len(),
+        2
+    );
+    assert_eq!(
+        TicketInstance::find_for_order_item(item2.id, connection).unwrap().len(),
+        4
+    );
+    assert_eq!(cart.package_quantities(connection).unwrap()[&package.id], 2);
+
+    // Event fees are charged for each event in the package
+    let event_fee_items = cart
+        .items(connection)
+        .unwrap()
+        .into_iter()
+        .filter(|i| i.item_type == OrderItemTypes::EventFees)
+        .count();
+    assert_eq!(event_fee_items, 2);
+
+    cart.update_package_quantities(
+        user.id,
+        &[UpdatePackageItem {
+            package_id: package.id,
+            quantity: 0,
+        }],
+        false,
+        connection,
+    )
+    .unwrap();
+    assert!(cart.items(connection).unwrap().is_empty());
+    assert_eq!(
+        TicketInstance::find_for_order_item(item.id, connection).unwrap().len(),
+        0
+    );
+}
+
+#[test]
+fn update_package_quantities_enforces_limit_per_person() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let (organization, event, event2) = festival_events(&project);
+    let ticket_type = event
+        .ticket_types(true, None, connection)
+        .unwrap()
+        .remove(0)
+        .update(
+            TicketTypeEditableAttributes {
+                limit_per_person: Some(2),
+                ..Default::default()
+            },
+            None,
+            connection,
+        )
+        .unwrap();
+    let ticket_type2 = event2.ticket_types(true, None, connection).unwrap().remove(0);
+    let package = project
+        .create_package()
+        .with_organization(&organization)
+        .with_ticket_type(&ticket_type, 1, 5000)
+        .with_ticket_type(&ticket_type2, 1, 5000)
+        .finish();
+    let mut cart = Order::find_or_create_cart(&user, connection).unwrap();
+
+    assert!(cart
+        .update_package_quantities(
+            user.id,
+            &[UpdatePackageItem {
+                package_id: package.id,
+                quantity: 2,
+            }],
+            false,
+            connection,
+        )
+        .is_ok());
+    let result = cart.update_package_quantities(
+        user.id,
+        &[UpdatePackageItem {
+            package_id: package.id,
+            quantity: 3,
+        }],
+        false,
+        connection,
+    );
+    match result.unwrap_err().error_code {
+        ValidationError { errors } => {
+            assert_eq!(errors["quantity"][0].code, "limit_per_person_exceeded");
+            assert_eq!(errors["quantity"][0].params["ticket_type_id"], json!(ticket_type.id));
+        }
+        _ => panic!("Expected validation error"),
+    }
+}
+
+#[test]
+fn update_quantities_leaves_packages_in_cart() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let (organization, event, event2) = festival_events(&project);
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let ticket_type2 = event2.ticket_types(true, None, connection).unwrap().remove(0);
+    let package = project
+        .create_package()
+        .with_organization(&organization)
+        .with_ticket_type(&ticket_type, 1, 5000)
+        .with_ticket_type(&ticket_type2, 1, 5000)
+        .finish();
+    let mut cart = project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(1)
+        .with_package(&package, 1)
+        .finish();
+
+    cart.update_quantities(user.id, &[], false, true, connection).unwrap();
+    let items = cart.items(connection).unwrap();
+    assert!(items
+        .iter()
+        .filter(|i| i.item_type == OrderItemTypes::Tickets)
+        .all(|i| i.package_id == Some(package.id)));
+    assert_eq!(cart.package_quantities(connection).unwrap()[&package.id], 1);
+}
+
+#[test]
+fn refund_requires_complete_package() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let (organization, event, event2) = festival_events(&project);
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let ticket_type2 = event2.ticket_types(true, None, connection).unwrap().remove(0);
+    let package = project
+        .create_package()
+        .with_organization(&organization)
+        .with_ticket_type(&ticket_type, 1, 5000)
+        .with_ticket_type(&ticket_type2, 1, 3000)
+        .finish();
+    let mut order = project
+        .create_order()
+        .for_user(&user)
+        .quantity(0)
+        .with_package(&package, 1)
+        .is_paid()
+        .finish();
+    assert_eq!(order.status, OrderStatus::Paid);
+
+    let tickets = order.tickets(None, connection).unwrap();
+    assert_eq!(tickets.len(), 2);
+    let refund_items: Vec<RefundItemRequest> = tickets
+        .iter()
+        .map(|t| RefundItemRequest {
+            order_item_id: t.order_item_id.unwrap(),
+            ticket_instance_id: Some(t.id),
+        })
+        .collect();
+
+    assert!(order
+        .refund(&refund_items[0..1], user.id, None, false, connection)
+        .is_err());
+
+    let (_, amount) = order.refund(&refund_items, user.id, None, false, connection).unwrap();
+    let package_items: Vec<OrderItem> = order
+        .items(connection)
+        .unwrap()
+        .into_iter()
+        .filter(|i| i.package_id == Some(package.id))
+        .collect();
+    assert!(package_items.iter().all(|i| i.refunded_quantity == 1));
+    assert!(amount >= 8000);
+}
+
+#[test]
+fn transfer_requires_complete_package() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let (organization, event, event2) = festival_events(&project);
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let ticket_type2 = event2.ticket_types(true, None, connection).unwrap().remove(0);
+    let package = project
+        .create_package()
+        .with_organization(&organization)
+        .with_ticket_type(&ticket_type, 1, 5000)
+        .with_ticket_type(&ticket_type2, 1, 3000)
+        .finish();
+    let order = project
+        .create_order()
+        .for_user(&user)
+        .quantity(0)
+        .with_package(&package, 1)
+        .is_paid()
+        .finish();
+    let ticket_ids: Vec<_> = order.tickets(None, connection).unwrap().iter().map(|t| t.id).collect();
+
+    assert!(TicketInstance::create_transfer(&user, &ticket_ids[0..1], None, None, false, connection).is_err());
+    assert!(TicketInstance::create_transfer(&user, &ticket_ids, None, None, false, connection).is_ok());
+}
+
+#[test]
+fn trade_requires_complete_package() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let (organization, event, event2) = festival_events(&project);
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let ticket_type2 = event2.ticket_types(true, None, connection).unwrap().remove(0);
+    let package = project
+        .create_package()
+        .with_organization(&organization)
+        .with_ticket_type(&ticket_type, 1, 5000)
+        .with_ticket_type(&ticket_type2, 1, 3000)
+        .finish();
+    let order = project
+        .create_order()
+        .for_user(&user)
+        .quantity(0)
+        .with_package(&package, 1)
+        .is_paid()
+        .finish();
+    let ticket_ids: Vec<_> = order.tickets(None, connection).unwrap().iter().map(|t| t.id).collect();
+
+    let trade = Trade::propose(user2.id, user.id, &[], &ticket_ids[0..1], 1000, None, connection).unwrap();
+    assert!(trade.accept(user.id, connection).is_err());
+    trade.cancel(user2.id, connection).unwrap();
+
+    let trade = Trade::propose(user2.id, user.id, &[], &ticket_ids, 1000, None, connection).unwrap();
+    assert!(trade.accept(user.id, connection).is_ok());
+}
@@ -0,0 +1,179 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn commit() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let partner = Partner::create(
+        "Example Tickets".to_string(),
+        vec![" https://Tickets.Example.com/ ".to_string()],
+        10f32,
+    )
+    .commit(Some(user.id), connection)
+    .unwrap();
+
+    assert_eq!(partner.name, "Example Tickets".to_string());
+    assert_eq!(partner.allowed_origins, vec!["https://tickets.example.com".to_string()]);
+    assert_eq!(partner.commission_rate_percent, 10f32);
+    assert_eq!(partner.api_key_hash, None);
+    assert_eq!(Partner::find(partner.id, connection).unwrap(), partner);
+
+    let domain_events = DomainEvent::find(
+        Tables::Partners,
+        Some(partner.id),
+        Some(DomainEventTypes::PartnerCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn commit_with_invalid_values() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let result =
+        Partner::create("".to_string(), vec!["tickets.example.com".to_string()], 101f32).commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("name"));
+                assert_eq!(errors["allowed_origins"][0].code, "invalid_origin");
+                assert_eq!(errors["commission_rate_percent"][0].code, "invalid_rate");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn generate_api_key() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let partner = Partner::create("Example Tickets".to_string(), vec![], 0f32)
+        .commit(None, connection)
+        .unwrap();
+    assert!(Partner::find_by_api_key("", connection).is_err());
+
+    let api_key = partner.generate_api_key(None, connection).unwrap();
+    assert!(api_key.starts_with("pk_"));
+    let found_partner = Partner::find_by_api_key(&api_key, connection).unwrap();
+    assert_eq!(found_partner.id, partner.id);
+    // Only the hash is stored and it is never serialized
+    assert_ne!(found_partner.api_key_hash, Some(api_key.clone()));
+    assert!(json!(&found_partner).get("api_key_hash").is_none());
+
+    // Generating a new key replaces the previous one
+    let new_api_key = partner.generate_api_key(None, connection).unwrap();
+    assert_ne!(new_api_key, api_key);
+    assert!(Partner::find_by_api_key(&api_key, connection).is_err());
+    assert_eq!(
+        Partner::find_by_api_key(&new_api_key, connection).unwrap().id,
+        partner.id
+    );
+
+    let domain_events = DomainEvent::find(
+        Tables::Partners,
+        Some(partner.id),
+        Some(DomainEventTypes::PartnerApiKeyGenerated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(2, domain_events.len());
+}
+
+#[test]
+fn origin_allowed() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let partner = Partner::create(
+        "Example Tickets".to_string(),
+        vec!["https://tickets.example.com".to_string()],
+        0f32,
+    )
+    .commit(None, connection)
+    .unwrap();
+
+    assert!(partner.origin_allowed("https://tickets.example.com"));
+    assert!(partner.origin_allowed("https://TICKETS.example.com:443"));
+    assert!(!partner.origin_allowed("http://tickets.example.com"));
+    assert!(!partner.origin_allowed("https://example.com"));
+    assert!(!partner.origin_allowed("null"));
+
+    assert!(Partner::origin_allowed_for_any("https://tickets.example.com", connection).unwrap());
+    assert!(!Partner::origin_allowed_for_any("https://example.com", connection).unwrap());
+}
+
+#[test]
+fn update() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let partner = Partner::create("Example Tickets".to_string(), vec![], 0f32)
+        .commit(None, connection)
+        .unwrap();
+
+    let attributes = PartnerEditableAttributes {
+        name: Some("Example".to_string()),
+        allowed_origins: Some(vec!["https://example.com/checkout".to_string()]),
+        commission_rate_percent: Some(7.5f32),
+    };
+    let partner = partner.update(attributes, None, connection).unwrap();
+    assert_eq!(partner.name, "Example".to_string());
+    assert_eq!(partner.allowed_origins, vec!["https://example.com".to_string()]);
+    assert_eq!(partner.commission_rate_percent, 7.5f32);
+
+    let attributes = PartnerEditableAttributes {
+        commission_rate_percent: Some(-1f32),
+        ..Default::default()
+    };
+    assert!(partner.update(attributes, None, connection).is_err());
+}
+
+#[test]
+fn delete() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let partner = Partner::create("Example Tickets".to_string(), vec![], 0f32)
+        .commit(None, connection)
+        .unwrap();
+    let api_key = partner.generate_api_key(None, connection).unwrap();
+
+    partner.delete(None, connection).unwrap();
+    assert!(Partner::find(partner.id, connection).is_err());
+    assert!(Partner::find_by_api_key(&api_key, connection).is_err());
+    assert!(Partner::all(connection).unwrap().is_empty());
+}
+
+#[test]
+fn order_set_partner() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let partner = Partner::create("Example Tickets".to_string(), vec![], 0f32)
+        .commit(None, connection)
+        .unwrap();
+    let mut cart = Order::find_or_create_cart(&user, connection).unwrap();
+    assert_eq!(cart.partner(connection).unwrap(), None);
+
+    cart.set_partner(&partner, connection).unwrap();
+    let mut cart = Order::find(cart.id, connection).unwrap();
+    assert_eq!(cart.partner_id, Some(partner.id));
+    assert_eq!(cart.platform, Some(Platforms::Partner.to_string()));
+    assert_eq!(cart.source, Some("Example Tickets".to_string()));
+    assert_eq!(cart.partner(connection).unwrap(), Some(partner));
+
+    // Browser and tracking data from the partner's site do not replace the attribution
+    cart.set_browser_data(Some("Mozilla/5.0".to_string()), false, connection)
+        .unwrap();
+    cart.set_tracking_data(Some(json!({"utm_source": "newsletter"})), None, connection)
+        .unwrap();
+    let cart = Order::find(cart.id, connection).unwrap();
+    assert_eq!(cart.platform, Some(Platforms::Partner.to_string()));
+    assert_eq!(cart.source, Some("Example Tickets".to_string()));
+}
@@ -0,0 +1,284 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn create() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().finish();
+    let product = Product::create(event.organization_id, Some(event.id), "Parking".to_string(), 1500)
+        .commit(None, connection)
+        .unwrap();
+    assert_eq!(product.event_id, Some(event.id));
+    assert_eq!(product.price_in_cents, 1500);
+
+    let domain_events = DomainEvent::find(
+        Tables::Products,
+        Some(product.id),
+        Some(DomainEventTypes::ProductCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn create_with_validation_errors() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let other_event = project.create_event().finish();
+    let result =
+        Product::create(organization.id, Some(other_event.id), "Parking".to_string(), -1).commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("price_in_cents"));
+                assert!(errors.contains_key("event_id"));
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn find_for_event() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().finish();
+    let other_event = project
+        .create_event()
+        .with_organization(&event.organization(connection).unwrap())
+        .finish();
+    let event_product = project.create_product().with_event(&event).finish();
+    let organization_product = project
+        .create_product()
+        .with_organization(&event.organization(connection).unwrap())
+        .with_name("Drink Token")
+        .finish();
+    project.create_product().with_event(&other_event).finish();
+    project.create_product().finish();
+
+    let products = Product::find_for_event(event.id, connection).unwrap();
+    assert_eq!(products, vec![organization_product, event_product]);
+}
+
+#[test]
+fn for_display() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let product = project
+        .create_product()
+        .with_event(&event)
+        .with_price(2500)
+        .with_variant("Small", 10)
+        .with_variant("Large", 5)
+        .finish();
+    let variants = product.variants(connection).unwrap();
+    let large = variants.iter().find(|v| v.name == "Large").unwrap();
+    large
+        .update(
+            ProductVariantEditableAttributes {
+                price_in_cents: Some(Some(3000)),
+                ..Default::default()
+            },
+            None,
+            connection,
+        )
+        .unwrap();
+    project
+        .create_order()
+        .for_event(&event)
+        .quantity(1)
+        .with_add_on(large, 2)
+        .is_paid()
+        .finish();
+
+    let display_product = product.for_display(connection).unwrap();
+    let display_large = display_product.variants.iter().find(|v| v.name == "Large").unwrap();
+    let display_small = display_product.variants.iter().find(|v| v.name == "Small").unwrap();
+    assert_eq!(display_large.price_in_cents, 3000);
+    assert_eq!(display_large.available, 3);
+    assert_eq!(display_small.price_in_cents, 2500);
+    assert_eq!(display_small.available, 10);
+}
+
+#[test]
+fn update_add_on_quantities() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let product = project
+        .create_product()
+        .with_event(&event)
+        .with_price(1500)
+        .with_variant("Default", 3)
+        .finish();
+    let variant = product.variants(connection).unwrap().remove(0);
+    let mut cart = project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(2)
+        .finish();
+    let total_before_add_ons = cart.calculate_total(connection).unwrap();
+
+    cart.update_add_on_quantities(
+        user.id,
+        &[UpdateAddOnItem {
+            product_variant_id: variant.id,
+            quantity: 2,
+        }],
+        true,
+        connection,
+    )
+    .unwrap();
+    let add_on_items: Vec<OrderItem> = cart
+        .items(connection)
+        .unwrap()
+        .into_iter()
+        .filter(|i| i.item_type == OrderItemTypes::AddOns)
+        .collect();
+    assert_eq!(add_on_items.len(), 1);
+    assert_eq!(add_on_items[0].quantity, 2);
+    assert_eq!(add_on_items[0].unit_price_in_cents, 1500);
+    assert_eq!(add_on_items[0].event_id, Some(event.id));
+    assert_eq!(add_on_items[0].product_variant_id, Some(variant.id));
+    assert_eq!(
+        add_on_items[0].description(connection).unwrap(),
+        "Parking Pass - Default".to_string()
+    );
+    assert_eq!(cart.calculate_total(connection).unwrap(), total_before_add_ons + 3000);
+    assert_eq!(variant.available(None, connection).unwrap(), 1);
+    assert_eq!(variant.available(Some(cart.id), connection).unwrap(), 3);
+
+    // Exceeding the remaining inventory is rejected
+    let result = cart.update_add_on_quantities(
+        user.id,
+        &[UpdateAddOnItem {
+            product_variant_id: variant.id,
+            quantity: 4,
+        }],
+        true,
+        connection,
+    );
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("add_ons"));
+                assert_eq!(errors["add_ons"].len(), 1);
+                assert_eq!(errors["add_ons"][0].code, "insufficient_inventory");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    // Removing the tickets removes the add-ons for the event
+    cart.update_quantities(user.id, &[], false, true, connection).unwrap();
+    assert!(cart.items(connection).unwrap().is_empty());
+}
+
+#[test]
+fn update_add_on_quantities_requires_tickets_for_event() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let other_event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let product = project.create_product().with_event(&other_event).finish();
+    let variant = product.variants(connection).unwrap().remove(0);
+    let mut cart = project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(1)
+        .finish();
+
+    let result = cart.update_add_on_quantities(
+        user.id,
+        &[UpdateAddOnItem {
+            product_variant_id: variant.id,
+            quantity: 1,
+        }],
+        false,
+        connection,
+    );
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("add_ons"));
+                assert_eq!(errors["add_ons"][0].code, "tickets_required");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn redeem_add_ons() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let door_person = project.create_user().finish();
+    let event = project.create_event().with_tickets().with_ticket_pricing().finish();
+    let product = project.create_product().with_event(&event).finish();
+    let variant = product.variants(connection).unwrap().remove(0);
+    let order = project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(1)
+        .with_add_on(&variant, 2)
+        .is_paid()
+        .finish();
+    let ticket = order.tickets(None, connection).unwrap().remove(0);
+
+    let add_ons = RedeemableAddOn::find_for_ticket(&ticket, event.id, connection).unwrap();
+    assert_eq!(add_ons.len(), 1);
+    assert_eq!(add_ons[0].product_variant_id, variant.id);
+    assert_eq!(add_ons[0].remaining_quantity(), 2);
+
+    let add_on = RedeemableAddOn::redeem(
+        &ticket,
+        event.id,
+        add_ons[0].order_item_id,
+        1,
+        door_person.id,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(add_on.redeemed_quantity, 1);
+    assert_eq!(add_on.remaining_quantity(), 1);
+
+    // Cannot hand out more than was purchased
+    assert!(RedeemableAddOn::redeem(
+        &ticket,
+        event.id,
+        add_ons[0].order_item_id,
+        2,
+        door_person.id,
+        connection,
+    )
+    .is_err());
+
+    let domain_events = DomainEvent::find(
+        Tables::Orders,
+        Some(order.id),
+        Some(DomainEventTypes::AddOnRedeemed),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
@@ -1585,6 +1585,7 @@ fn sales_summary_report() {
             total: 2,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: ticket_type.name.clone(),
             face_value_in_cents: ticket_pricing.price_in_cents,
             online_sale_count: ticket_quantity,
@@ -1596,6 +1597,7 @@ fn sales_summary_report() {
             total: 2,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: "Per Order Fee".to_string(),
             face_value_in_cents: 0,
             online_sale_count: 0,
@@ -1661,6 +1663,7 @@ fn sales_summary_report() {
             total: 3,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: ticket_type.name.clone(),
             face_value_in_cents: ticket_pricing.price_in_cents,
             online_sale_count: ticket_quantity + 1,
@@ -1672,6 +1675,7 @@ fn sales_summary_report() {
             total: 3,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: format!("{} - Hold - {}", ticket_type.name.clone(), comp.name),
             face_value_in_cents: 0,
             online_sale_count: 0,
@@ -1683,6 +1687,7 @@ fn sales_summary_report() {
             total: 3,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: "Per Order Fee".to_string(),
             face_value_in_cents: 0,
             online_sale_count: 0,
@@ -1742,6 +1747,7 @@ fn sales_summary_report() {
             total: 3,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: ticket_type.name.clone(),
             face_value_in_cents: ticket_pricing.price_in_cents,
             online_sale_count: ticket_quantity,
@@ -1753,6 +1759,7 @@ fn sales_summary_report() {
             total: 3,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: format!("{} - Hold - {}", ticket_type.name.clone(), comp.name),
             face_value_in_cents: 0,
             online_sale_count: 0,
@@ -1764,6 +1771,7 @@ fn sales_summary_report() {
             total: 3,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: "Per Order Fee".to_string(),
             face_value_in_cents: 0,
             online_sale_count: 0,
@@ -1793,6 +1801,7 @@ fn sales_summary_report() {
         total: 1,
         event_name: event.name.clone(),
         event_date: event.event_start,
+        currency: Currencies::USD,
         ticket_name: ticket_type.name.clone(),
         face_value_in_cents: ticket_pricing.price_in_cents,
         online_sale_count: -1,
@@ -1825,6 +1834,7 @@ fn sales_summary_report() {
         total: 1,
         event_name: event.name.clone(),
         event_date: event.event_start,
+        currency: Currencies::USD,
         ticket_name: "Per Order Fee".to_string(),
         face_value_in_cents: 0,
         online_sale_count: 0,
@@ -1858,6 +1868,7 @@ fn sales_summary_report() {
             total: 5,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: ticket_type.name.clone(),
             face_value_in_cents: ticket_pricing.price_in_cents,
             online_sale_count: ticket_quantity + 1,
@@ -1869,6 +1880,7 @@ fn sales_summary_report() {
             total: 5,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: format!("{} - Hold - {}", ticket_type.name.clone(), comp.name),
             face_value_in_cents: 0,
             online_sale_count: 0,
@@ -1880,6 +1892,7 @@ fn sales_summary_report() {
             total: 5,
             event_name: event.name.clone(),
             event_date: event.event_start,
+            currency: Currencies::USD,
             ticket_name: "Per Order Fee".to_string(),
             face_value_in_cents: 0,
             online_sale_count: 0,
@@ -1891,6 +1904,7 @@ fn sales_summary_report() {
             total: 5,
             event_name: event2.name.clone(),
             event_date: event2.event_start,
+            currency: Currencies::USD,
             ticket_name: ticket_type2.name.clone(),
             face_value_in_cents: ticket_pricing2.price_in_cents,
             online_sale_count: 1,
@@ -1902,6 +1916,7 @@ fn sales_summary_report() {
             total: 5,
             event_name: event2.name.clone(),
             event_date: event2.event_start,
+            currency: Currencies::USD,
             ticket_name: "Per Order Fee".to_string(),
             face_value_in_cents: 0,
             online_sale_count: 0,
@@ -1995,6 +2010,7 @@ fn domain_transaction_detail_report() {
         event_date: event.event_start.clone(),
         ticket_type_name: ticket_type.name.clone(),
         transaction_date: order.paid_at.unwrap(),
+        currency: order.currency,
         point_of_sale: None,
         payment_method: PaymentMethods::CreditCard.to_string(),
         qty_tickets_sold: 2,
@@ -2122,6 +2138,7 @@ fn box_office_sales_summary_report() {
                     BoxOfficeSalesSummaryOperatorEventRow {
                         event_name: Some("Event1".to_string()),
                         event_date: Some(NaiveDate::from_ymd(2016, 7, 8).and_hms(9, 10, 11)),
+                        currency: Currencies::USD,
                         number_of_tickets: 2,
                         face_value_in_cents: 150,
                         revenue_share_value_in_cents: 0,
@@ -2130,6 +2147,7 @@ fn box_office_sales_summary_report() {
                     BoxOfficeSalesSummaryOperatorEventRow {
                         event_name: Some("Event2".to_string()),
                         event_date: Some(NaiveDate::from_ymd(2017, 7, 8).and_hms(9, 10, 11)),
+                        currency: Currencies::USD,
                         number_of_tickets: 2,
                         face_value_in_cents: 150,
                         revenue_share_value_in_cents: 0,
@@ -2139,16 +2157,19 @@ fn box_office_sales_summary_report() {
                 payments: vec![
                     BoxOfficeSalesSummaryPaymentRow {
                         payment_type: ExternalPaymentType::Cash,
+                        currency: Currencies::USD,
                         quantity: 1,
                         total_sales_in_cents: 150,
                     },
                     BoxOfficeSalesSummaryPaymentRow {
                         payment_type: ExternalPaymentType::CreditCard,
+                        currency: Currencies::USD,
                         quantity: 1,
                         total_sales_in_cents: 150,
                     },
                     BoxOfficeSalesSummaryPaymentRow {
                         payment_type: ExternalPaymentType::Voucher,
+                        currency: Currencies::USD,
                         quantity: 2,
                         total_sales_in_cents: 300,
                     },
@@ -2161,6 +2182,7 @@ fn box_office_sales_summary_report() {
                     BoxOfficeSalesSummaryOperatorEventRow {
                         event_name: Some("Event1".to_string()),
                         event_date: Some(NaiveDate::from_ymd(2016, 7, 8).and_hms(9, 10, 11)),
+                        currency: Currencies::USD,
                         number_of_tickets: 1,
                         face_value_in_cents: 140,
                         revenue_share_value_in_cents: 0,
@@ -2169,6 +2191,7 @@ fn box_office_sales_summary_report() {
                     BoxOfficeSalesSummaryOperatorEventRow {
                         event_name: Some("Event1".to_string()),
                         event_date: Some(NaiveDate::from_ymd(2016, 7, 8).and_hms(9, 10, 11)),
+                        currency: Currencies::USD,
                         number_of_tickets: 2,
                         face_value_in_cents: 150,
                         revenue_share_value_in_cents: 0,
@@ -2177,6 +2200,7 @@ fn box_office_sales_summary_report() {
                 ],
                 payments: vec![BoxOfficeSalesSummaryPaymentRow {
                     payment_type: ExternalPaymentType::Cash,
+                    currency: Currencies::USD,
                     quantity: 3,
                     total_sales_in_cents: 440,
                 }],
@@ -2185,26 +2209,81 @@ fn box_office_sales_summary_report() {
         payments: vec![
             BoxOfficeSalesSummaryPaymentRow {
                 payment_type: ExternalPaymentType::Cash,
+                currency: Currencies::USD,
                 quantity: 4,
                 total_sales_in_cents: 590,
             },
             BoxOfficeSalesSummaryPaymentRow {
                 payment_type: ExternalPaymentType::CreditCard,
+                currency: Currencies::USD,
                 quantity: 1,
                 total_sales_in_cents: 150,
             },
             BoxOfficeSalesSummaryPaymentRow {
                 payment_type: ExternalPaymentType::Voucher,
+                currency: Currencies::USD,
                 quantity: 2,
                 total_sales_in_cents: 300,
             },
         ],
+        shifts: Vec::new(),
     };
 
     let report_data = Report::box_office_sales_summary_report(organization.id, None, None, connection).unwrap();
     assert_eq!(expected_report_data, report_data);
 }
 
+#[test]
+fn box_office_sales_summary_report_shifts() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let box_office_user = project.create_user().finish();
+    let user = project.create_user().finish();
+    let organization = project
+        .create_organization()
+        .with_member(&box_office_user, Roles::OrgBoxOffice)
+        .finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_tickets()
+        .with_ticket_pricing()
+        .finish();
+    let session = CashDrawerSession::create(organization.id, box_office_user.id, 10000)
+        .commit(connection)
+        .unwrap();
+    let order = project
+        .create_order()
+        .for_event(&event)
+        .for_user(&box_office_user)
+        .on_behalf_of_user(&user)
+        .is_paid()
+        .with_external_payment_type(ExternalPaymentType::Cash)
+        .finish();
+    let session = session
+        .close(
+            10000 + order.calculate_total(connection).unwrap(),
+            None,
+            box_office_user.id,
+            connection,
+        )
+        .unwrap();
+
+    let report_data = Report::box_office_sales_summary_report(organization.id, None, None, connection).unwrap();
+    assert_eq!(report_data.shifts, vec![session.for_display(connection).unwrap()]);
+    assert_eq!(report_data.shifts[0].variance_in_cents, Some(0));
+
+    // Shifts opened outside the period are excluded
+    let report_data = Report::box_office_sales_summary_report(
+        organization.id,
+        Some(session.created_at + Duration::days(1)),
+        None,
+        connection,
+    )
+    .unwrap();
+    assert!(report_data.shifts.is_empty());
+}
+
 fn build_transaction_report_row(
     total: i64,
     organization: &Organization,
@@ -2236,6 +2315,7 @@ fn build_transaction_report_row(
         payment_method: Some(PaymentMethods::CreditCard.to_string()),
         payment_provider: Some(PaymentProviders::Stripe.to_string()),
         transaction_date: order.paid_at.clone().unwrap(),
+        currency: order.currency,
         redemption_code: None,
         order_id: order.id,
         event_id: event.id,
@@ -2454,3 +2534,50 @@ fn promo_code_report() {
 
     assert_eq!(test_pass_count, 5);
 }
+
+#[test]
+fn tax_liability_report() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_type()
+        .with_price(1000)
+        .finish();
+    let tax_rule = project.create_tax_rule().with_organization(&organization).finish();
+    let mut order = project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(2)
+        .is_paid()
+        .finish();
+    let ticket = order.tickets(None, connection).unwrap().remove(0);
+    order
+        .refund(
+            &[RefundItemRequest {
+                order_item_id: ticket.order_item_id.unwrap(),
+                ticket_instance_id: Some(ticket.id),
+            }],
+            user.id,
+            None,
+            false,
+            connection,
+        )
+        .unwrap();
+
+    let report_data =
+        Report::tax_liability_report(organization.id, None, None, ReportPeriods::Year, connection).unwrap();
+    assert_eq!(report_data.len(), 1);
+    let row = &report_data[0];
+    assert_eq!(row.tax_rule_id, tax_rule.id);
+    assert_eq!(row.jurisdiction, tax_rule.jurisdiction);
+    assert_eq!(row.taxable_sales_in_cents, 2000);
+    assert_eq!(row.refunded_taxable_sales_in_cents, 1000);
+    assert_eq!(row.tax_collected_in_cents, 200);
+    assert_eq!(row.tax_refunded_in_cents, 100);
+    assert_eq!(row.net_tax_in_cents, 100);
+}
@@ -0,0 +1,320 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn payout_totals() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    project
+        .create_settlement_adjustment()
+        .with_settlement(&settlement)
+        .with_settlement_adjustment_type(SettlementAdjustmentTypes::ManualCredit)
+        .with_amount_in_cents(30)
+        .finish();
+    assert_eq!(
+        settlement.payout_totals(connection).unwrap(),
+        vec![(Currencies::USD, 300)]
+    );
+
+    project
+        .create_settlement_adjustment()
+        .with_settlement(&settlement)
+        .with_settlement_adjustment_type(SettlementAdjustmentTypes::Chargeback)
+        .finish();
+    assert_eq!(
+        settlement.payout_totals(connection).unwrap(),
+        vec![(Currencies::USD, 200)]
+    );
+}
+
+#[test]
+fn finalize_settlements_creates_payouts() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let bank_account = project
+        .create_organization_bank_account()
+        .with_organization(&organization)
+        .finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+
+    // Organizations without a bank account are paid out by hand
+    let organization2 = project.create_organization().finish();
+    let settlement2 = project
+        .create_settlement()
+        .with_organization(&organization2)
+        .with_entries()
+        .finish();
+
+    Settlement::finalize_settlements(connection).unwrap();
+
+    let payouts = settlement.payouts(connection).unwrap();
+    assert_eq!(payouts.len(), 1);
+    let payout = &payouts[0];
+    assert_eq!(payout.organization_bank_account_id, bank_account.id);
+    assert_eq!(payout.payout_method, PayoutMethods::Nacha);
+    assert_eq!(payout.amount_in_cents, 270);
+    assert_eq!(payout.currency, Currencies::USD);
+    assert_eq!(payout.status, SettlementPayoutStatus::Initiated);
+    assert!(settlement2.payouts(connection).unwrap().is_empty());
+
+    let domain_events = DomainEvent::find(
+        Tables::SettlementPayouts,
+        Some(payout.id),
+        Some(DomainEventTypes::SettlementPayoutInitiated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+
+    // Payouts are only created once per settlement
+    let settlement = Settlement::find(settlement.id, connection).unwrap();
+    assert!(settlement.create_payouts(None, connection).is_err());
+}
+
+#[test]
+fn create_payouts_skips_other_currencies() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    project
+        .create_organization_bank_account()
+        .with_organization(&organization)
+        .sepa()
+        .finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    Settlement::finalize_settlements(connection).unwrap();
+
+    // SEPA transfers are made in EUR so the USD settlement is left to be paid by hand
+    assert!(settlement.payouts(connection).unwrap().is_empty());
+}
+
+#[test]
+fn update_status() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    project
+        .create_organization_bank_account()
+        .with_organization(&organization)
+        .finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    Settlement::finalize_settlements(connection).unwrap();
+    let payout = settlement.payouts(connection).unwrap().remove(0);
+
+    let payout = payout
+        .update_status(SettlementPayoutStatus::Paid, None, Some(user.id), connection)
+        .unwrap();
+    assert_eq!(payout.status, SettlementPayoutStatus::Paid);
+    let domain_events = DomainEvent::find(
+        Tables::SettlementPayouts,
+        Some(payout.id),
+        Some(DomainEventTypes::SettlementPayoutPaid),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+
+    let result = payout.update_status(SettlementPayoutStatus::Initiated, None, Some(user.id), connection);
+    match result {
+        Ok(_) => panic!("Expected validation error"),
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("status"));
+                assert_eq!(errors["status"][0].code, "invalid_status_transition");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    let payout = payout
+        .update_status(
+            SettlementPayoutStatus::Returned,
+            Some("Account closed".to_string()),
+            Some(user.id),
+            connection,
+        )
+        .unwrap();
+    assert_eq!(payout.status, SettlementPayoutStatus::Returned);
+    assert_eq!(payout.note, Some("Account closed".to_string()));
+    let domain_events = DomainEvent::find(
+        Tables::SettlementPayouts,
+        Some(payout.id),
+        Some(DomainEventTypes::SettlementPayoutReturned),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn nacha_batch_file() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    project
+        .create_organization_bank_account()
+        .with_organization(&organization)
+        .with_account_holder_name("Example Promotions")
+        .finish();
+    project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    Settlement::finalize_settlements(connection).unwrap();
+
+    let batch = SettlementPayoutBatch::create_for_unbatched_payouts(PayoutMethods::Nacha, None, connection).unwrap();
+    assert_eq!(batch.payouts(connection).unwrap().len(), 1);
+    let originator = PayoutOriginator {
+        name: "Big Neon".to_string(),
+        routing_number: Some("011000015".to_string()),
+        company_id: Some("1234567890".to_string()),
+        ..Default::default()
+    };
+    let file = batch.file(&originator, "encryption_key", connection).unwrap();
+    let records: Vec<&str> = file.lines().collect();
+
+    // File header, batch header, entry, batch control and file control padded to a block of 10
+    assert_eq!(records.len(), 10);
+    assert!(records.iter().all(|r| r.len() == 94));
+    assert!(records[0].starts_with("101 011000015"));
+    assert!(records[1].starts_with("5220BIG NEON"));
+    assert!(records[2].starts_with("622021000021123456789"));
+    assert_eq!(&records[2][29..39], "0000000270");
+    assert_eq!(&records[2][54..76], "EXAMPLE PROMOTIONS    ");
+    assert!(records[3].starts_with("8220000001"));
+    assert!(records[4].starts_with("9000001000001"));
+    assert_eq!(records[9], "9".repeat(94));
+
+    // Originator details are required
+    assert!(batch
+        .file(&PayoutOriginator::default(), "encryption_key", connection)
+        .is_err());
+}
+
+#[test]
+fn sepa_batch_file() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project
+        .create_organization()
+        .finish()
+        .update(
+            OrganizationEditableAttributes {
+                currency: Some(Currencies::EUR),
+                ..Default::default()
+            },
+            None,
+            &"encryption_key".to_string(),
+            connection,
+        )
+        .unwrap();
+    project
+        .create_organization_bank_account()
+        .with_organization(&organization)
+        .with_account_holder_name("Example & Sons")
+        .sepa()
+        .finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    Settlement::finalize_settlements(connection).unwrap();
+
+    let batch = SettlementPayoutBatch::create_for_unbatched_payouts(PayoutMethods::Sepa, None, connection).unwrap();
+    let payouts = batch.payouts(connection).unwrap();
+    assert_eq!(payouts.len(), 1);
+    assert_eq!(payouts[0].currency, Currencies::EUR);
+    let originator = PayoutOriginator {
+        name: "Big Neon".to_string(),
+        iban: Some("GB29NWBK60161331926819".to_string()),
+        bic: Some("NWBKGB2L".to_string()),
+        ..Default::default()
+    };
+    let file = batch.file(&originator, "encryption_key", connection).unwrap();
+
+    assert!(file.contains("urn:iso:std:iso:20022:tech:xsd:pain.001.001.03"));
+    assert!(file.contains("<NbOfTxs>1</NbOfTxs>"));
+    assert!(file.contains("<CtrlSum>2.70</CtrlSum>"));
+    assert!(file.contains("<DbtrAcct><Id><IBAN>GB29NWBK60161331926819</IBAN></Id></DbtrAcct>"));
+    assert!(file.contains(r#"<InstdAmt Ccy="EUR">2.70</InstdAmt>"#));
+    assert!(file.contains("<CdtrAcct><Id><IBAN>DE89370400440532013000</IBAN></Id></CdtrAcct>"));
+    assert!(file.contains("<Cdtr><Nm>Example &amp; Sons</Nm></Cdtr>"));
+    assert!(file.contains(&format!("<Ustrd>Settlement {}</Ustrd>", settlement.id)));
+    assert!(file.contains(&format!("<MsgId>{}</MsgId>", batch.id.simple())));
+}
+
+#[test]
+fn create_for_unbatched_payouts() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    project
+        .create_organization_bank_account()
+        .with_organization(&organization)
+        .finish();
+    project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    Settlement::finalize_settlements(connection).unwrap();
+    let originator = PayoutOriginator {
+        name: "Big Neon".to_string(),
+        routing_number: Some("011000015".to_string()),
+        company_id: Some("1234567890".to_string()),
+        ..Default::default()
+    };
+
+    // No SEPA payouts to export
+    assert!(SettlementPayoutBatch::create_for_unbatched_payouts(PayoutMethods::Sepa, None, connection).is_err());
+
+    let batch =
+        SettlementPayoutBatch::create_for_unbatched_payouts(PayoutMethods::Nacha, Some(user.id), connection).unwrap();
+    assert_eq!(batch.payout_method, PayoutMethods::Nacha);
+    assert_eq!(batch.created_by_user_id, Some(user.id));
+    let payouts = batch.payouts(connection).unwrap();
+    assert_eq!(payouts.len(), 1);
+    assert_eq!(payouts[0].settlement_payout_batch_id, Some(batch.id));
+    let domain_events = DomainEvent::find(
+        Tables::SettlementPayoutBatches,
+        Some(batch.id),
+        Some(DomainEventTypes::SettlementPayoutBatchCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+
+    // Payouts already in a batch are not exported again
+    assert!(SettlementPayoutBatch::create_for_unbatched_payouts(PayoutMethods::Nacha, None, connection).is_err());
+
+    // Downloading the batch again produces the same file
+    let batch = SettlementPayoutBatch::find(batch.id, connection).unwrap();
+    assert_eq!(
+        batch.file(&originator, "encryption_key", connection).unwrap(),
+        batch.file(&originator, "encryption_key", connection).unwrap()
+    );
+}
@@ -0,0 +1,179 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+#[test]
+fn create() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project
+        .create_organization()
+        .with_member(&user, Roles::OrgAdmin)
+        .finish();
+
+    let subscriber = SettlementStatementSubscriber::create(organization.id, user.id)
+        .commit(Some(user.id), connection)
+        .unwrap();
+    assert_eq!(subscriber.organization_id, organization.id);
+    assert_eq!(subscriber.user_id, user.id);
+
+    let domain_events = DomainEvent::find(
+        Tables::Organizations,
+        Some(organization.id),
+        Some(DomainEventTypes::SettlementStatementSubscriberCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(domain_events.len(), 1);
+    assert_eq!(
+        domain_events[0].event_data,
+        Some(json!({"user_id": user.id, "settlement_statement_subscriber_id": subscriber.id }))
+    );
+}
+
+#[test]
+fn create_with_validation_errors() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+
+    match SettlementStatementSubscriber::create(organization.id, user.id).commit(None, connection) {
+        Ok(_) => panic!("Expected validation error"),
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(errors["user_id"][0].code, "not_organization_member");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    organization
+        .add_user(user.id, vec![Roles::OrgMember], Vec::new(), connection)
+        .unwrap();
+    SettlementStatementSubscriber::create(organization.id, user.id)
+        .commit(None, connection)
+        .unwrap();
+    match SettlementStatementSubscriber::create(organization.id, user.id).commit(None, connection) {
+        Ok(_) => panic!("Expected validation error"),
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert_eq!(errors["user_id"][0].code, "already_subscribed");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn find() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project
+        .create_organization()
+        .with_member(&user, Roles::OrgMember)
+        .finish();
+    let subscriber = SettlementStatementSubscriber::create(organization.id, user.id)
+        .commit(None, connection)
+        .unwrap();
+    assert_eq!(
+        subscriber,
+        SettlementStatementSubscriber::find(subscriber.id, connection).unwrap()
+    );
+}
+
+#[test]
+fn find_for_organization() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let organization = project
+        .create_organization()
+        .with_member(&user, Roles::OrgMember)
+        .with_member(&user2, Roles::OrgMember)
+        .finish();
+    let organization2 = project
+        .create_organization()
+        .with_member(&user, Roles::OrgMember)
+        .finish();
+    let subscriber = SettlementStatementSubscriber::create(organization.id, user.id)
+        .commit(None, connection)
+        .unwrap();
+    let subscriber2 = SettlementStatementSubscriber::create(organization.id, user2.id)
+        .commit(None, connection)
+        .unwrap();
+    let subscriber3 = SettlementStatementSubscriber::create(organization2.id, user.id)
+        .commit(None, connection)
+        .unwrap();
+
+    let mut subscribers = SettlementStatementSubscriber::find_for_organization(organization.id, connection).unwrap();
+    subscribers.sort_by_key(|s| s.id);
+    let mut expected_subscribers = vec![subscriber, subscriber2];
+    expected_subscribers.sort_by_key(|s| s.id);
+    assert_eq!(subscribers, expected_subscribers);
+    assert_eq!(
+        SettlementStatementSubscriber::find_for_organization(organization2.id, connection).unwrap(),
+        vec![subscriber3]
+    );
+}
+
+#[test]
+fn find_users_for_organization() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let organization = project
+        .create_organization()
+        .with_member(&user, Roles::OrgMember)
+        .with_member(&user2, Roles::OrgMember)
+        .finish();
+    SettlementStatementSubscriber::create(organization.id, user.id)
+        .commit(None, connection)
+        .unwrap();
+    SettlementStatementSubscriber::create(organization.id, user2.id)
+        .commit(None, connection)
+        .unwrap();
+    let mut users = SettlementStatementSubscriber::find_users_for_organization(organization.id, connection)
+        .unwrap()
+        .into_iter()
+        .map(|u| u.id)
+        .collect::<Vec<_>>();
+    users.sort();
+    let mut expected_users = vec![user.id, user2.id];
+    expected_users.sort();
+    assert_eq!(users, expected_users);
+
+    // Users who leave the organization no longer receive statements
+    organization.remove_user(user2.id, connection).unwrap();
+    let users = SettlementStatementSubscriber::find_users_for_organization(organization.id, connection).unwrap();
+    assert_eq!(users.into_iter().map(|u| u.id).collect::<Vec<_>>(), vec![user.id]);
+}
+
+#[test]
+fn destroy() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project
+        .create_organization()
+        .with_member(&user, Roles::OrgMember)
+        .finish();
+    let subscriber = SettlementStatementSubscriber::create(organization.id, user.id)
+        .commit(None, connection)
+        .unwrap();
+
+    subscriber.destroy(Some(user.id), connection).unwrap();
+    assert!(SettlementStatementSubscriber::find(subscriber.id, connection).is_err());
+    let domain_events = DomainEvent::find(
+        Tables::Organizations,
+        Some(organization.id),
+        Some(DomainEventTypes::SettlementStatementSubscriberDeleted),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(domain_events.len(), 1);
+}
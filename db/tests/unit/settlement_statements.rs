@@ -0,0 +1,124 @@
+use db::dev::TestProject;
+use db::models::*;
+
+#[test]
+fn render() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project
+        .create_organization()
+        .with_name("Statement Org".to_string())
+        .finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    project
+        .create_settlement_adjustment()
+        .with_settlement(&settlement)
+        .with_note(Some("Deduction, manual".to_string()))
+        .with_amount_in_cents(30)
+        .finish();
+    let event_name = SettlementEntry::find_for_settlement_by_event(&settlement, connection).unwrap()[0]
+        .event
+        .name
+        .clone();
+
+    let statement = SettlementStatement::render(&settlement, connection).unwrap();
+    assert_eq!(statement.settlement_id, settlement.id);
+
+    let csv_lines: Vec<&str> = statement.csv_content.lines().collect();
+    assert_eq!(
+        csv_lines[0],
+        "Section,Event,Item,Type,Currency,Face Value,Revenue Share,Online Sold Quantity,Fee Sold Quantity,Total"
+    );
+    assert_eq!(
+        csv_lines[1],
+        format!("Entry,{},Ticket Type 0,TicketType,USD,1.00,0.10,2,2,2.20", event_name)
+    );
+    assert_eq!(
+        csv_lines[2],
+        format!("Entry,{},Event Fees,EventFees,USD,0.00,0.50,0,1,0.50", event_name)
+    );
+    assert!(csv_lines.contains(&"Fee Breakdown,,Face Value,,USD,,,,,2.00"));
+    assert!(csv_lines.contains(&"Fee Breakdown,,Ticket Fees,,USD,,,,,0.20"));
+    assert!(csv_lines.contains(&"Fee Breakdown,,Event Fees,,USD,,,,,0.50"));
+    assert!(csv_lines.contains(&"Adjustment,,\"Deduction, manual\",ManualDeduction,USD,,,,,-0.30"));
+    assert_eq!(csv_lines.last(), Some(&"Total,,,,USD,,,,,2.40"));
+
+    let pdf = String::from_utf8(statement.pdf_content).unwrap();
+    assert!(pdf.starts_with("%PDF-1.4"));
+    assert!(pdf.contains("(Statement Org Settlement Statement) Tj"));
+    assert!(pdf.contains(&format!("({}) Tj", event_name)));
+    assert!(pdf.contains("(-$0.30) Tj"));
+    assert!(pdf.contains("($2.40) Tj"));
+}
+
+#[test]
+fn generate() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+
+    let statement = SettlementStatement::generate(&settlement, connection).unwrap();
+    assert_eq!(
+        statement,
+        SettlementStatement::find_for_settlement(settlement.id, connection).unwrap()
+    );
+    let domain_events = DomainEvent::find(
+        Tables::Organizations,
+        Some(organization.id),
+        Some(DomainEventTypes::SettlementStatementGenerated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(domain_events.len(), 1);
+
+    // Regenerating replaces the stored statement
+    project
+        .create_settlement_adjustment()
+        .with_settlement(&settlement)
+        .with_settlement_adjustment_type(SettlementAdjustmentTypes::ManualCredit)
+        .finish();
+    let regenerated_statement = SettlementStatement::generate(&settlement, connection).unwrap();
+    assert_eq!(regenerated_statement.id, statement.id);
+    assert!(regenerated_statement.csv_content.ends_with("Total,,,,USD,,,,,3.70\r\n"));
+}
+
+#[test]
+fn finalize_settlements_generates_statements() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let settlement = project
+        .create_settlement()
+        .with_organization(&organization)
+        .with_entries()
+        .finish();
+    assert!(SettlementStatement::find_for_settlement(settlement.id, connection).is_err());
+
+    let finalized_settlements = Settlement::finalize_settlements(connection).unwrap();
+    assert_eq!(finalized_settlements.len(), 1);
+    assert_eq!(finalized_settlements[0].id, settlement.id);
+    assert!(SettlementStatement::find_for_settlement(settlement.id, connection).is_ok());
+}
+
+#[test]
+fn file_name() {
+    let project = TestProject::new();
+    let settlement = project.create_settlement().finish();
+    assert_eq!(
+        SettlementStatement::file_name(&settlement, "pdf"),
+        format!(
+            "settlement-{}-{}.pdf",
+            settlement.start_time.format("%Y-%m-%d"),
+            settlement.end_time.format("%Y-%m-%d")
+        )
+    );
+}
@@ -1158,6 +1158,195 @@ fn create_rolling_entries() {
     assert_eq!(refund.settlement_id, Some(settlement3.id));
 }
 
+#[test]
+fn create_partner_commission_entries() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let partner = Partner::create("Example Tickets".to_string(), vec![], 10f32)
+        .commit(None, connection)
+        .unwrap();
+    let order = project.create_order().for_event(&event).quantity(4).is_paid().finish();
+    let mut order = Order::find(order.id, connection).unwrap();
+    order.set_partner(&partner, connection).unwrap();
+    project.create_order().for_event(&event).quantity(2).is_paid().finish();
+    let ticket_item = order
+        .items(connection)
+        .unwrap()
+        .into_iter()
+        .find(|item| item.item_type == OrderItemTypes::Tickets)
+        .unwrap();
+    let commission_in_cents = (4f64 * ticket_item.unit_price_in_cents as f64 * 0.1).round() as i64;
+
+    let settlement = project.create_settlement().with_organization(&organization).finish();
+    settlement
+        .create_entries_from_event_transactions(&event, true, connection)
+        .unwrap();
+    let event_entries = SettlementEntry::find_for_settlement_by_event(&settlement, connection).unwrap();
+    let commission_entries: Vec<&DisplaySettlementEntry> = event_entries[0]
+        .entries
+        .iter()
+        .filter(|entry| entry.settlement_entry_type == SettlementEntryTypes::PartnerCommission)
+        .collect();
+    assert_eq!(commission_entries.len(), 1);
+    assert_eq!(commission_entries[0].partner_id, Some(partner.id));
+    assert_eq!(commission_entries[0].partner_name, Some("Example Tickets".to_string()));
+    assert_eq!(commission_entries[0].online_sold_quantity, 4);
+    assert_eq!(commission_entries[0].total_sales_in_cents, -commission_in_cents);
+
+    // Commission is deducted from the amount paid out to the organization
+    let sales_in_cents: i64 = event_entries[0]
+        .entries
+        .iter()
+        .filter(|entry| entry.settlement_entry_type != SettlementEntryTypes::PartnerCommission)
+        .map(|entry| entry.total_sales_in_cents)
+        .sum();
+    assert_eq!(
+        settlement.payout_totals(connection).unwrap(),
+        vec![(organization.currency, sales_in_cents - commission_in_cents)]
+    );
+}
+
+#[test]
+fn create_affiliate_commission_entries() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let promoter = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let affiliate_link = AffiliateLink::create(organization.id, promoter.id, None, "Newsletter".to_string())
+        .commit(None, connection)
+        .unwrap();
+    AffiliateCommissionRule::create(affiliate_link.id, None, Some(100), None)
+        .commit(None, connection)
+        .unwrap();
+    let mut order = project.create_order().for_event(&event).quantity(4).finish();
+    order
+        .set_tracking_data(Some(json!({ "aff": affiliate_link.code })), None, connection)
+        .unwrap();
+    let total = order.calculate_total(connection).unwrap();
+    order
+        .add_credit_card_payment(
+            order.user_id,
+            total,
+            PaymentProviders::Stripe,
+            "blah".to_string(),
+            PaymentStatus::Completed,
+            json!(""),
+            connection,
+        )
+        .unwrap();
+    project.create_order().for_event(&event).quantity(2).is_paid().finish();
+
+    let settlement = project.create_settlement().with_organization(&organization).finish();
+    settlement
+        .create_entries_from_event_transactions(&event, true, connection)
+        .unwrap();
+    let event_entries = SettlementEntry::find_for_settlement_by_event(&settlement, connection).unwrap();
+    let commission_entries: Vec<&DisplaySettlementEntry> = event_entries[0]
+        .entries
+        .iter()
+        .filter(|entry| entry.settlement_entry_type == SettlementEntryTypes::AffiliateCommission)
+        .collect();
+    assert_eq!(commission_entries.len(), 1);
+    assert_eq!(commission_entries[0].affiliate_link_id, Some(affiliate_link.id));
+    assert_eq!(
+        commission_entries[0].affiliate_link_name,
+        Some("Newsletter".to_string())
+    );
+    assert_eq!(commission_entries[0].online_sold_quantity, 4);
+    assert_eq!(commission_entries[0].total_sales_in_cents, -400);
+
+    // Settled commissions are marked so they are not settled again
+    let commissions = AffiliateCommission::find_for_order(order.id, connection).unwrap();
+    assert_eq!(commissions[0].settlement_id, Some(settlement.id));
+
+    let sales_in_cents: i64 = event_entries[0]
+        .entries
+        .iter()
+        .filter(|entry| entry.settlement_entry_type != SettlementEntryTypes::AffiliateCommission)
+        .map(|entry| entry.total_sales_in_cents)
+        .sum();
+    assert_eq!(
+        settlement.payout_totals(connection).unwrap(),
+        vec![(organization.currency, sales_in_cents - 400)]
+    );
+}
+
+#[test]
+fn create_tax_entries() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_type()
+        .with_price(1000)
+        .finish();
+    project.create_tax_rule().with_organization(&organization).finish();
+    let mut order = project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(2)
+        .is_paid()
+        .finish();
+
+    let settlement = project.create_settlement().with_organization(&organization).finish();
+    settlement
+        .create_entries_from_event_transactions(&event, true, connection)
+        .unwrap();
+    let event_entries = SettlementEntry::find_for_settlement_by_event(&settlement, connection).unwrap();
+    let tax_entries: Vec<&DisplaySettlementEntry> = event_entries[0]
+        .entries
+        .iter()
+        .filter(|entry| entry.settlement_entry_type == SettlementEntryTypes::Tax)
+        .collect();
+    assert_eq!(tax_entries.len(), 1);
+    assert_eq!(tax_entries[0].face_value_in_cents, 100);
+    assert_eq!(tax_entries[0].online_sold_quantity, 2);
+    assert_eq!(tax_entries[0].total_sales_in_cents, 200);
+
+    // Refunding a ticket returns the tax charged on it
+    let ticket = order.tickets(None, connection).unwrap().remove(0);
+    order
+        .refund(
+            &[RefundItemRequest {
+                order_item_id: ticket.order_item_id.unwrap(),
+                ticket_instance_id: Some(ticket.id),
+            }],
+            user.id,
+            None,
+            false,
+            connection,
+        )
+        .unwrap();
+    let settlement = project.create_settlement().with_organization(&organization).finish();
+    settlement
+        .create_entries_from_event_transactions(&event, true, connection)
+        .unwrap();
+    let event_entries = SettlementEntry::find_for_settlement_by_event(&settlement, connection).unwrap();
+    let tax_entries: Vec<&DisplaySettlementEntry> = event_entries[0]
+        .entries
+        .iter()
+        .filter(|entry| entry.settlement_entry_type == SettlementEntryTypes::Tax)
+        .collect();
+    assert_eq!(tax_entries.len(), 1);
+    assert_eq!(tax_entries[0].online_sold_quantity, -1);
+    assert_eq!(tax_entries[0].total_sales_in_cents, -100);
+}
+
 #[test]
 fn create() {
     let project = TestProject::new();
@@ -0,0 +1,291 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+fn taxed_event(project: &TestProject, organization: &Organization) -> Event {
+    project
+        .create_event()
+        .with_organization(organization)
+        .with_ticket_type()
+        .with_price(1000)
+        .finish()
+}
+
+#[test]
+fn create() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let tax_rule = TaxRule::create(
+        organization.id,
+        None,
+        "VAT".to_string(),
+        "United Kingdom".to_string(),
+        20f32,
+    )
+    .commit(None, connection)
+    .unwrap();
+    assert_eq!(tax_rule.organization_id, organization.id);
+    assert_eq!(tax_rule.rate_percent, 20f32);
+    assert!(!tax_rule.inclusive);
+    assert!(!tax_rule.fees_taxable);
+
+    let domain_events = DomainEvent::find(
+        Tables::TaxRules,
+        Some(tax_rule.id),
+        Some(DomainEventTypes::TaxRuleCreated),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(1, domain_events.len());
+}
+
+#[test]
+fn create_with_validation_errors() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let result =
+        TaxRule::create(organization.id, None, "".to_string(), "New York".to_string(), 150f32).commit(None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected validation error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("name"));
+                assert!(errors.contains_key("rate_percent"));
+                assert_eq!(errors["rate_percent"][0].code, "invalid_rate");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+}
+
+#[test]
+fn find_for_event() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let venue = project.create_venue().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_venue(&venue)
+        .finish();
+    let other_event = project.create_event().with_organization(&organization).finish();
+    let organization_tax_rule = project.create_tax_rule().with_organization(&organization).finish();
+    project.create_tax_rule().finish();
+
+    assert_eq!(
+        TaxRule::find_for_event(&event, connection).unwrap(),
+        vec![organization_tax_rule.clone()]
+    );
+
+    // Venue rules replace the organization wide rules for events at the venue
+    let venue_tax_rule = project
+        .create_tax_rule()
+        .with_organization(&organization)
+        .with_venue(&venue)
+        .with_name("City Tax")
+        .finish();
+    assert_eq!(
+        TaxRule::find_for_event(&event, connection).unwrap(),
+        vec![venue_tax_rule]
+    );
+    assert_eq!(
+        TaxRule::find_for_event(&other_event, connection).unwrap(),
+        vec![organization_tax_rule]
+    );
+}
+
+#[test]
+fn tax_for_unit_price() {
+    let project = TestProject::new();
+    let tax_rule = project.create_tax_rule().with_rate(8.875).finish();
+    assert_eq!(tax_rule.tax_for_unit_price(1000), 89);
+    assert_eq!(tax_rule.tax_for_unit_price(0), 0);
+
+    let inclusive_tax_rule = project.create_tax_rule().with_rate(20f32).inclusive().finish();
+    assert_eq!(inclusive_tax_rule.tax_for_unit_price(1200), 200);
+}
+
+#[test]
+fn update_fees_and_discounts_adds_tax() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let event = taxed_event(&project, &organization);
+    let tax_rule = project.create_tax_rule().with_organization(&organization).finish();
+    let user = project.create_user().finish();
+    let mut order = project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(2)
+        .finish();
+
+    let items = order.items(connection).unwrap();
+    let ticket_item = items.iter().find(|i| i.item_type == OrderItemTypes::Tickets).unwrap();
+    let tax_items: Vec<&OrderItem> = items.iter().filter(|i| i.item_type == OrderItemTypes::Tax).collect();
+    assert_eq!(tax_items.len(), 1);
+    assert_eq!(tax_items[0].parent_id, Some(ticket_item.id));
+    assert_eq!(tax_items[0].tax_rule_id, Some(tax_rule.id));
+    assert_eq!(tax_items[0].quantity, 2);
+    assert_eq!(tax_items[0].unit_price_in_cents, 100);
+    assert_eq!(tax_items[0].tax_in_cents, 100);
+    assert_eq!(tax_items[0].description(connection).unwrap(), "Sales Tax".to_string());
+
+    let display_order = order.for_display(None, user.id, connection).unwrap();
+    assert_eq!(display_order.total_tax_in_cents, 200);
+    assert!(display_order
+        .items
+        .iter()
+        .any(|i| i.item_type == OrderItemTypes::Tax && i.description == "Sales Tax"));
+
+    // Tax follows the ticket quantity
+    order
+        .update_quantities(
+            user.id,
+            &[UpdateOrderItem {
+                ticket_type_id: ticket_item.ticket_type_id.unwrap(),
+                quantity: 3,
+                redemption_code: None,
+            }],
+            false,
+            false,
+            connection,
+        )
+        .unwrap();
+    let tax_items: Vec<OrderItem> = order
+        .items(connection)
+        .unwrap()
+        .into_iter()
+        .filter(|i| i.item_type == OrderItemTypes::Tax)
+        .collect();
+    assert_eq!(tax_items.len(), 1);
+    assert_eq!(tax_items[0].quantity, 3);
+}
+
+#[test]
+fn update_fees_and_discounts_taxes_fees() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().with_fees().with_event_fee().finish();
+    let event = taxed_event(&project, &organization);
+    project
+        .create_tax_rule()
+        .with_organization(&organization)
+        .fees_taxable()
+        .finish();
+    let order = project.create_order().for_event(&event).quantity(1).finish();
+
+    let items = order.items(connection).unwrap();
+    let event_fee_item = items.iter().find(|i| i.item_type == OrderItemTypes::EventFees).unwrap();
+    let per_unit_fee_item = items
+        .iter()
+        .find(|i| i.item_type == OrderItemTypes::PerUnitFees)
+        .unwrap();
+    let event_fee_tax = items
+        .iter()
+        .find(|i| i.item_type == OrderItemTypes::Tax && i.parent_id == Some(event_fee_item.id))
+        .unwrap();
+    assert_eq!(
+        event_fee_tax.unit_price_in_cents,
+        (event_fee_item.unit_price_in_cents as f64 * 0.1).round() as i64
+    );
+    assert!(items
+        .iter()
+        .any(|i| i.item_type == OrderItemTypes::Tax && i.parent_id == Some(per_unit_fee_item.id)));
+}
+
+#[test]
+fn update_fees_and_discounts_inclusive_tax() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().finish();
+    let event = taxed_event(&project, &organization);
+    project
+        .create_tax_rule()
+        .with_organization(&organization)
+        .with_name("VAT")
+        .with_rate(25f32)
+        .inclusive()
+        .finish();
+    let order = project.create_order().for_event(&event).quantity(1).finish();
+
+    let items = order.items(connection).unwrap();
+    let tax_item = items.iter().find(|i| i.item_type == OrderItemTypes::Tax).unwrap();
+    assert_eq!(tax_item.unit_price_in_cents, 0);
+    assert_eq!(tax_item.tax_in_cents, 200);
+    assert_eq!(tax_item.description(connection).unwrap(), "VAT (included)".to_string());
+
+    // Inclusive tax is already part of the face value so the total is unchanged
+    let untaxed_total: i64 = items
+        .iter()
+        .filter(|i| i.item_type != OrderItemTypes::Tax)
+        .map(|i| i.unit_price_in_cents * i.quantity)
+        .sum();
+    assert_eq!(order.calculate_total(connection).unwrap(), untaxed_total);
+    let display_order = order.for_display(None, order.user_id, connection).unwrap();
+    assert_eq!(display_order.total_tax_in_cents, 200);
+}
+
+#[test]
+fn refund_refunds_tax() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = taxed_event(&project, &organization);
+    project.create_tax_rule().with_organization(&organization).finish();
+    let mut order = project
+        .create_order()
+        .for_user(&user)
+        .for_event(&event)
+        .quantity(2)
+        .is_paid()
+        .finish();
+    let ticket = order.tickets(None, connection).unwrap().remove(0);
+    let tax_item = order
+        .items(connection)
+        .unwrap()
+        .into_iter()
+        .find(|i| i.item_type == OrderItemTypes::Tax)
+        .unwrap();
+
+    // Tax can only be refunded with the ticket it was charged on
+    assert!(order
+        .refund(
+            &[RefundItemRequest {
+                order_item_id: tax_item.id,
+                ticket_instance_id: None,
+            }],
+            user.id,
+            None,
+            false,
+            connection,
+        )
+        .is_err());
+
+    let (refund, amount) = order
+        .refund(
+            &[RefundItemRequest {
+                order_item_id: ticket.order_item_id.unwrap(),
+                ticket_instance_id: Some(ticket.id),
+            }],
+            user.id,
+            None,
+            false,
+            connection,
+        )
+        .unwrap();
+    let tax_item = OrderItem::find(tax_item.id, connection).unwrap();
+    assert_eq!(tax_item.refunded_quantity, 1);
+    assert!(amount >= 1100);
+    assert!(refund
+        .items(connection)
+        .unwrap()
+        .iter()
+        .any(|ri| ri.order_item_id == tax_item.id && ri.amount == 100));
+}
@@ -12,6 +12,7 @@ use db::dev::times;
 use db::dev::TestProject;
 use db::prelude::*;
 use db::utils::errors::ErrorCode::ValidationError;
+use db::utils::totp;
 
 #[test]
 fn event() {
@@ -168,14 +169,75 @@ fn associate_redeem_key() {
         .pop()
         .unwrap();
     let redeem_key = ticket.redeem_key.clone();
+    let redeem_secret = ticket.redeem_secret.clone();
+    assert!(redeem_secret.is_some());
     ticket.associate_redeem_key(connection).unwrap();
     let ticket = TicketInstance::find(ticket.id, connection).unwrap();
     let new_redeem_key = ticket.redeem_key.clone();
+    let new_redeem_secret = ticket.redeem_secret.clone();
     assert_ne!(redeem_key, new_redeem_key);
+    assert_ne!(redeem_secret, new_redeem_secret);
 
     ticket.associate_redeem_key(connection).unwrap();
     let ticket = TicketInstance::find(ticket.id, connection).unwrap();
     assert_ne!(new_redeem_key, ticket.redeem_key);
+    assert_ne!(new_redeem_secret, ticket.redeem_secret);
+}
+
+#[test]
+fn verify_redeem_code() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_ticket_pricing().finish();
+    let user = project.create_user().finish();
+    project
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    let ticket = TicketInstance::find_for_user(user.id, connection).unwrap().remove(0);
+    let redeem_secret = ticket.redeem_secret.clone().unwrap();
+    let now = Utc::now().timestamp();
+
+    // Static barcodes don't need a code
+    assert!(ticket.verify_redeem_code(None, connection).unwrap());
+
+    event
+        .update(
+            None,
+            EventEditableAttributes {
+                barcode_rotation_seconds: Some(Some(30)),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    assert!(!ticket.verify_redeem_code(None, connection).unwrap());
+    assert!(ticket
+        .verify_redeem_code(Some(&totp::code(&redeem_secret, now, 30)), connection)
+        .unwrap());
+    // Codes from the neighbouring periods are accepted to allow for clock drift
+    assert!(ticket
+        .verify_redeem_code(Some(&totp::code(&redeem_secret, now - 30, 30)), connection)
+        .unwrap());
+    assert!(!ticket
+        .verify_redeem_code(Some(&totp::code(&redeem_secret, now - 120, 30)), connection)
+        .unwrap());
+
+    // Codes generated from the previous secret stop working once the ticket is re-keyed
+    ticket.associate_redeem_key(connection).unwrap();
+    let ticket = TicketInstance::find(ticket.id, connection).unwrap();
+    assert!(!ticket
+        .verify_redeem_code(Some(&totp::code(&redeem_secret, now, 30)), connection)
+        .unwrap());
+    assert!(ticket
+        .verify_redeem_code(
+            Some(&totp::code(&ticket.redeem_secret.clone().unwrap(), now, 30)),
+            connection
+        )
+        .unwrap());
 }
 
 #[test]
@@ -0,0 +1,235 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::DatabaseError;
+
+fn setup(project: &TestProject, re_entry_policy: ReEntryPolicies) -> (Event, AccessZone, TicketInstance, User) {
+    let connection = project.get_connection();
+    let event = project.create_event().with_ticket_pricing().finish();
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    let user = project.create_user().finish();
+    project
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    let ticket = TicketInstance::find_for_user(user.id, connection).unwrap().remove(0);
+    let access_zone = AccessZone::create(event.id, "VIP".to_string(), re_entry_policy)
+        .commit(None, connection)
+        .unwrap();
+    access_zone
+        .update_ticket_types(vec![ticket_type.id], connection)
+        .unwrap();
+    let door_person = project.create_user().finish();
+    (event, access_zone, ticket, door_person)
+}
+
+fn scan(
+    access_zone: &AccessZone,
+    ticket: &TicketInstance,
+    direction: ScanDirections,
+    door_person: &User,
+    connection: &PgConnection,
+) -> Result<TicketScan, DatabaseError> {
+    // Reload the ticket as the door scanner would, earlier scans may have redeemed it
+    let ticket = TicketInstance::find(ticket.id, connection)?;
+    TicketScan::scan(
+        access_zone,
+        Some(&ticket),
+        ticket.redeem_key.clone().unwrap(),
+        None,
+        direction,
+        door_person.id,
+        Some("scanner-1".to_string()),
+        connection,
+    )
+}
+
+#[test]
+fn scan_entry_redeems_ticket() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let (event, access_zone, ticket, door_person) = setup(&project, ReEntryPolicies::Unlimited);
+
+    let ticket_scan = scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.result, ScanResults::Accepted);
+    assert_eq!(ticket_scan.rejection_reason, None);
+    assert_eq!(ticket_scan.event_id, event.id);
+    assert_eq!(ticket_scan.ticket_instance_id, Some(ticket.id));
+    assert_eq!(ticket_scan.access_zone_id, Some(access_zone.id));
+    assert_eq!(ticket_scan.scanned_by_user_id, door_person.id);
+    assert_eq!(ticket_scan.device_id, Some("scanner-1".to_string()));
+
+    let ticket = TicketInstance::find(ticket.id, connection).unwrap();
+    assert_eq!(ticket.status, TicketInstanceStatus::Redeemed);
+    assert_eq!(ticket.redeemed_by_user_id, Some(door_person.id));
+    assert_eq!(ticket.check_in_source, Some(CheckInSource::Scanned));
+}
+
+#[test]
+fn scan_re_entry() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let (_, access_zone, ticket, door_person) = setup(&project, ReEntryPolicies::Unlimited);
+
+    // Can't leave before entering
+    let ticket_scan = scan(&access_zone, &ticket, ScanDirections::Exit, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.rejection_reason, Some(ScanRejectionReasons::NotInside));
+
+    let ticket_scan = scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.result, ScanResults::Accepted);
+    let ticket_scan = scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.result, ScanResults::Rejected);
+    assert_eq!(ticket_scan.rejection_reason, Some(ScanRejectionReasons::AlreadyInside));
+
+    let ticket_scan = scan(&access_zone, &ticket, ScanDirections::Exit, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.result, ScanResults::Accepted);
+    let ticket_scan = scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.result, ScanResults::Accepted);
+
+    let directions: Vec<(ScanDirections, ScanResults)> = TicketScan::find_for_ticket(ticket.id, connection)
+        .unwrap()
+        .into_iter()
+        .map(|s| (s.direction, s.result))
+        .collect();
+    assert_eq!(
+        directions,
+        vec![
+            (ScanDirections::Exit, ScanResults::Rejected),
+            (ScanDirections::Entry, ScanResults::Accepted),
+            (ScanDirections::Entry, ScanResults::Rejected),
+            (ScanDirections::Exit, ScanResults::Accepted),
+            (ScanDirections::Entry, ScanResults::Accepted),
+        ]
+    );
+}
+
+#[test]
+fn scan_re_entry_not_allowed() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let (_, access_zone, ticket, door_person) = setup(&project, ReEntryPolicies::NoReEntry);
+
+    scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    let ticket_scan = scan(&access_zone, &ticket, ScanDirections::Exit, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.result, ScanResults::Accepted);
+    let ticket_scan = scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.result, ScanResults::Rejected);
+    assert_eq!(
+        ticket_scan.rejection_reason,
+        Some(ScanRejectionReasons::ReEntryNotAllowed)
+    );
+}
+
+#[test]
+fn scan_other_zones_after_redemption() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let (event, access_zone, ticket, door_person) = setup(&project, ReEntryPolicies::NoReEntry);
+    let backstage = AccessZone::create(event.id, "Backstage".to_string(), ReEntryPolicies::NoReEntry)
+        .commit(None, connection)
+        .unwrap();
+    let deck = AccessZone::create(event.id, "Deck".to_string(), ReEntryPolicies::NoReEntry)
+        .commit(None, connection)
+        .unwrap();
+    deck.update_ticket_types(access_zone.ticket_type_ids(connection).unwrap(), connection)
+        .unwrap();
+
+    scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+
+    // The ticket type isn't mapped to backstage
+    let ticket_scan = scan(&backstage, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.rejection_reason, Some(ScanRejectionReasons::WrongZone));
+
+    // The first entry to another zone isn't a re-entry
+    let ticket_scan = scan(&deck, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.result, ScanResults::Accepted);
+}
+
+#[test]
+fn scan_invalid_tickets() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let (event, access_zone, ticket, door_person) = setup(&project, ReEntryPolicies::Unlimited);
+
+    let ticket_scan = TicketScan::scan(
+        &access_zone,
+        None,
+        "UNKNOWN".to_string(),
+        None,
+        ScanDirections::Entry,
+        door_person.id,
+        None,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(ticket_scan.result, ScanResults::Rejected);
+    assert_eq!(ticket_scan.rejection_reason, Some(ScanRejectionReasons::InvalidTicket));
+    assert_eq!(ticket_scan.ticket_instance_id, None);
+    assert_eq!(ticket_scan.redeem_key, Some("UNKNOWN".to_string()));
+
+    // Rotating barcodes need the current code
+    event
+        .update(
+            None,
+            EventEditableAttributes {
+                barcode_rotation_seconds: Some(Some(30)),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    let ticket_scan = scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    assert_eq!(ticket_scan.rejection_reason, Some(ScanRejectionReasons::ExpiredCode));
+    let ticket = TicketInstance::find(ticket.id, connection).unwrap();
+    assert_eq!(ticket.status, TicketInstanceStatus::Purchased);
+}
+
+#[test]
+fn find_for_event() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let (event, access_zone, ticket, door_person) = setup(&project, ReEntryPolicies::Unlimited);
+    let other_zone = AccessZone::create(event.id, "GA".to_string(), ReEntryPolicies::Unlimited)
+        .commit(None, connection)
+        .unwrap();
+
+    let entry = scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    let already_inside = scan(&access_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+    let wrong_zone = scan(&other_zone, &ticket, ScanDirections::Entry, &door_person, connection).unwrap();
+
+    let payload = TicketScan::find_for_event(event.id, None, None, 0, 100, connection).unwrap();
+    assert_eq!(payload.paging.total, 3);
+    assert_eq!(
+        payload.data,
+        vec![wrong_zone.clone(), already_inside.clone(), entry.clone()]
+    );
+
+    let payload = TicketScan::find_for_event(event.id, Some(access_zone.id), None, 0, 100, connection).unwrap();
+    assert_eq!(payload.data, vec![already_inside.clone(), entry.clone()]);
+
+    let payload = TicketScan::find_for_event(event.id, None, Some(ScanResults::Rejected), 0, 100, connection).unwrap();
+    assert_eq!(payload.data, vec![wrong_zone, already_inside]);
+
+    let payload = TicketScan::find_for_event(event.id, None, None, 0, 1, connection).unwrap();
+    assert_eq!(payload.paging.total, 3);
+    assert_eq!(payload.data.len(), 1);
+}
+
+#[test]
+fn scan_rejection_reason() {
+    assert_eq!(RedeemResults::TicketRedeemSuccess.scan_rejection_reason(), None);
+    assert_eq!(
+        RedeemResults::TicketAlreadyRedeemed.scan_rejection_reason(),
+        Some(ScanRejectionReasons::AlreadyRedeemed)
+    );
+    assert_eq!(
+        RedeemResults::TicketInvalid.scan_rejection_reason(),
+        Some(ScanRejectionReasons::InvalidTicket)
+    );
+    assert_eq!(
+        RedeemResults::TicketTransferInProcess.scan_rejection_reason(),
+        Some(ScanRejectionReasons::TransferInProgress)
+    );
+}
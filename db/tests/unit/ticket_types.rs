@@ -962,6 +962,43 @@ fn update_rank() {
     );
 }
 
+#[test]
+fn update_stage() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let venue = project.create_venue().finish();
+    let stage = project.create_stage().with_venue_id(venue.id).finish();
+    let other_venue = project.create_venue().finish();
+    let other_stage = project.create_stage().with_venue_id(other_venue.id).finish();
+    let event = project.create_event().with_venue(&venue).with_tickets().finish();
+    let ticket_type = event.ticket_types(true, None, connection).unwrap().remove(0);
+    assert_eq!(ticket_type.stage_id, None);
+
+    let result = ticket_type.clone().update_stage(Some(other_stage.id), None, connection);
+    match result {
+        Ok(_) => {
+            panic!("Expected error");
+        }
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("stage_id"));
+                assert_eq!(errors["stage_id"][0].code, "stage_venue_mismatch");
+            }
+            _ => panic!("Expected validation error"),
+        },
+    }
+
+    let ticket_type = ticket_type.update_stage(Some(stage.id), None, connection).unwrap();
+    assert_eq!(ticket_type.stage_id, Some(stage.id));
+    assert_eq!(
+        TicketType::find(ticket_type.id, connection).unwrap().stage_id,
+        Some(stage.id)
+    );
+
+    let ticket_type = ticket_type.update_stage(None, None, connection).unwrap();
+    assert_eq!(ticket_type.stage_id, None);
+}
+
 #[test]
 fn cancel() {
     let db = TestProject::new();
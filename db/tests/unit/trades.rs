@@ -0,0 +1,364 @@
+use db::dev::TestProject;
+use db::models::*;
+use db::utils::errors::ErrorCode::ValidationError;
+
+fn purchased_ticket(project: &TestProject, user: &User) -> TicketInstance {
+    let event = project.create_event().with_ticket_pricing().finish();
+    project
+        .create_order()
+        .for_event(&event)
+        .for_user(user)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    TicketInstance::find_for_user(user.id, project.get_connection())
+        .unwrap()
+        .into_iter()
+        .find(|t| t.event(project.get_connection()).unwrap().id == event.id)
+        .unwrap()
+}
+
+#[test]
+fn propose() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let offered_ticket = purchased_ticket(&project, &user);
+    let requested_ticket = purchased_ticket(&project, &user2);
+
+    let trade = Trade::propose(
+        user.id,
+        user2.id,
+        &[offered_ticket.id],
+        &[requested_ticket.id],
+        500,
+        Some("Swap?".to_string()),
+        connection,
+    )
+    .unwrap();
+    assert_eq!(trade.status, TradeStatus::Pending);
+    assert_eq!(trade.cash_in_cents, 500);
+    assert!(!trade.is_expired());
+
+    // Tickets on both sides are locked until the trade is resolved
+    let offered_ticket = TicketInstance::find(offered_ticket.id, connection).unwrap();
+    assert_eq!(offered_ticket.trade_id, Some(trade.id));
+    assert_eq!(
+        TicketInstance::find(requested_ticket.id, connection).unwrap().trade_id,
+        Some(trade.id)
+    );
+
+    let display_trade = trade.for_display(connection).unwrap();
+    assert_eq!(display_trade.offered_items.len(), 1);
+    assert_eq!(display_trade.offered_items[0].ticket_instance_id, offered_ticket.id);
+    assert_eq!(display_trade.requested_items.len(), 1);
+    assert_eq!(display_trade.requested_items[0].ticket_instance_id, requested_ticket.id);
+
+    assert_eq!(
+        DomainAction::upcoming_domain_action(
+            Some(Tables::Trades),
+            Some(trade.id),
+            DomainActionTypes::ExpireTrade,
+            connection
+        )
+        .unwrap()
+        .unwrap()
+        .scheduled_at,
+        trade.expires_at
+    );
+    assert_eq!(
+        Trade::find_for_user(user2.id, None, connection).unwrap(),
+        vec![trade.clone()]
+    );
+
+    // Locked tickets cannot be offered again or transferred
+    let user3 = project.create_user().finish();
+    let ticket3 = purchased_ticket(&project, &user3);
+    assert!(Trade::propose(
+        user.id,
+        user3.id,
+        &[offered_ticket.id],
+        &[ticket3.id],
+        0,
+        None,
+        connection
+    )
+    .is_err());
+    assert!(TicketInstance::create_transfer(&user, &[offered_ticket.id], None, None, false, connection).is_err());
+    assert!(Trade::propose(
+        user3.id,
+        user2.id,
+        &[ticket3.id],
+        &[requested_ticket.id],
+        0,
+        None,
+        connection
+    )
+    .is_err());
+}
+
+#[test]
+fn propose_with_invalid_data() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let ticket = purchased_ticket(&project, &user);
+
+    let result = Trade::propose(user.id, user.id, &[], &[ticket.id], -100, None, connection);
+    match result {
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("recipient_user_id"));
+                assert!(errors.contains_key("cash_in_cents"));
+            }
+            _ => panic!("Expected validation error"),
+        },
+        _ => panic!("Expected validation error"),
+    }
+
+    let user2 = project.create_user().finish();
+    let result = Trade::propose(user.id, user2.id, &[], &[], 0, None, connection);
+    match result {
+        Err(error) => match &error.error_code {
+            ValidationError { errors } => {
+                assert!(errors.contains_key("offered_ticket_ids"));
+                assert!(errors.contains_key("requested_ticket_ids"));
+            }
+            _ => panic!("Expected validation error"),
+        },
+        _ => panic!("Expected validation error"),
+    }
+
+    // Requested tickets have to belong to the recipient
+    let offered_ticket = purchased_ticket(&project, &user);
+    assert!(Trade::propose(
+        user.id,
+        user2.id,
+        &[offered_ticket.id],
+        &[ticket.id],
+        0,
+        None,
+        connection
+    )
+    .is_err());
+}
+
+#[test]
+fn accept() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let offered_ticket = purchased_ticket(&project, &user);
+    let requested_ticket = purchased_ticket(&project, &user2);
+    let trade = Trade::propose(
+        user.id,
+        user2.id,
+        &[offered_ticket.id],
+        &[requested_ticket.id],
+        0,
+        None,
+        connection,
+    )
+    .unwrap();
+
+    assert!(trade.accept(user.id, connection).is_err());
+    let trade = trade.accept(user2.id, connection).unwrap();
+    assert_eq!(trade.status, TradeStatus::Accepted);
+    assert!(trade.responded_at.is_some());
+
+    let wallet = user.default_wallet(connection).unwrap();
+    let wallet2 = user2.default_wallet(connection).unwrap();
+    let offered_ticket_after = TicketInstance::find(offered_ticket.id, connection).unwrap();
+    let requested_ticket_after = TicketInstance::find(requested_ticket.id, connection).unwrap();
+    assert_eq!(offered_ticket_after.wallet_id, wallet2.id);
+    assert_eq!(offered_ticket_after.trade_id, None);
+    assert_ne!(offered_ticket_after.redeem_key, offered_ticket.redeem_key);
+    assert_eq!(requested_ticket_after.wallet_id, wallet.id);
+    assert_eq!(
+        DomainAction::upcoming_domain_action(
+            Some(Tables::Trades),
+            Some(trade.id),
+            DomainActionTypes::ExpireTrade,
+            connection
+        )
+        .unwrap(),
+        None
+    );
+
+    // Trades can only be resolved once
+    assert!(trade.accept(user2.id, connection).is_err());
+    assert!(trade.decline(user2.id, connection).is_err());
+}
+
+#[test]
+fn accept_credits_cash_to_recipient() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let organization = project.create_organization().finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    event
+        .update(
+            None,
+            EventEditableAttributes {
+                currency: Some(Some(Currencies::CAD)),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    project
+        .create_order()
+        .for_event(&event)
+        .for_user(&user2)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    let requested_ticket = TicketInstance::find_for_user(user2.id, connection).unwrap().remove(0);
+
+    // Cash is offered in the currency the requested tickets were sold in
+    let trade = Trade::propose(user.id, user2.id, &[], &[requested_ticket.id], 1500, None, connection).unwrap();
+    assert_eq!(trade.currency, Currencies::CAD);
+
+    let trade = trade.accept(user2.id, connection).unwrap();
+    let entries = UserCreditEntry::find_for_source(Tables::Trades, trade.id, connection).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].user_id, user2.id);
+    assert_eq!(entries[0].amount_in_cents, 1500);
+    assert_eq!(entries[0].currency, Currencies::CAD);
+    assert_eq!(
+        UserCreditEntry::balances_for_user(user2.id, connection).unwrap(),
+        vec![UserCreditBalance {
+            currency: Currencies::CAD,
+            balance_in_cents: 1500,
+        }]
+    );
+    assert!(UserCreditEntry::balances_for_user(user.id, connection)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn requested_tickets_cannot_be_transferred_while_pending() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let user3 = project.create_user().finish();
+    let offered_ticket = purchased_ticket(&project, &user);
+    let requested_ticket = purchased_ticket(&project, &user2);
+    let trade = Trade::propose(
+        user.id,
+        user2.id,
+        &[offered_ticket.id],
+        &[requested_ticket.id],
+        0,
+        None,
+        connection,
+    )
+    .unwrap();
+
+    assert!(TicketInstance::direct_transfer(
+        &user2,
+        &[requested_ticket.id],
+        "nowhere",
+        TransferMessageType::Email,
+        user3.id,
+        connection,
+    )
+    .is_err());
+
+    // Declining releases the requested tickets
+    trade.decline(user2.id, connection).unwrap();
+    assert!(TicketInstance::direct_transfer(
+        &user2,
+        &[requested_ticket.id],
+        "nowhere",
+        TransferMessageType::Email,
+        user3.id,
+        connection,
+    )
+    .is_ok());
+}
+
+#[test]
+fn decline_and_cancel() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    let offered_ticket = purchased_ticket(&project, &user);
+    let requested_ticket = purchased_ticket(&project, &user2);
+
+    let trade = Trade::propose(
+        user.id,
+        user2.id,
+        &[offered_ticket.id],
+        &[requested_ticket.id],
+        0,
+        None,
+        connection,
+    )
+    .unwrap();
+    assert!(trade.decline(user.id, connection).is_err());
+    let trade = trade.decline(user2.id, connection).unwrap();
+    assert_eq!(trade.status, TradeStatus::Declined);
+    assert_eq!(
+        TicketInstance::find(offered_ticket.id, connection).unwrap().trade_id,
+        None
+    );
+    assert_eq!(
+        TicketInstance::find(requested_ticket.id, connection).unwrap().trade_id,
+        None
+    );
+    assert_eq!(
+        DomainEvent::find(
+            Tables::Trades,
+            Some(trade.id),
+            Some(DomainEventTypes::TradeDeclined),
+            connection,
+        )
+        .unwrap()
+        .len(),
+        1
+    );
+
+    // Released tickets can be offered again
+    let trade = Trade::propose(
+        user.id,
+        user2.id,
+        &[offered_ticket.id],
+        &[requested_ticket.id],
+        0,
+        None,
+        connection,
+    )
+    .unwrap();
+    assert!(trade.cancel(user2.id, connection).is_err());
+    let trade = trade.cancel(user.id, connection).unwrap();
+    assert_eq!(trade.status, TradeStatus::Cancelled);
+    assert_eq!(
+        TicketInstance::find(offered_ticket.id, connection).unwrap().trade_id,
+        None
+    );
+
+    // Trades that have not reached their expiry date cannot expire
+    let trade = Trade::propose(
+        user.id,
+        user2.id,
+        &[offered_ticket.id],
+        &[requested_ticket.id],
+        0,
+        None,
+        connection,
+    )
+    .unwrap();
+    assert!(trade.expire(connection).is_err());
+}
@@ -33,10 +33,12 @@ fn regenerate_redeem_keys() {
     .unwrap();
 
     let previous_redeem_key = ticket.redeem_key.clone();
+    let previous_redeem_secret = TicketInstance::find(ticket.id, connection).unwrap().redeem_secret;
     transfer.regenerate_redeem_keys(connection).unwrap();
     let ticket = TicketInstance::find(ticket.id, connection).unwrap();
     let new_redeem_key = ticket.redeem_key.clone();
     assert_ne!(previous_redeem_key, new_redeem_key);
+    assert_ne!(previous_redeem_secret, ticket.redeem_secret);
 
     let previous_redeem_key = new_redeem_key;
     transfer.regenerate_redeem_keys(connection).unwrap();
@@ -244,6 +246,15 @@ fn receive_url() {
         )
         .to_string()
     );
+
+    // Organizations with a custom domain receive transfers on their own storefront
+    let mut branding = OrganizationBranding::create(event.organization_id);
+    branding.custom_domain = Some("tickets.example.org".to_string());
+    branding.commit(None, connection).unwrap();
+    assert!(transfer
+        .receive_url("http://example.com", connection)
+        .unwrap()
+        .starts_with("https://tickets.example.org/tickets/transfers/receive?"));
 }
 
 #[test]
@@ -309,6 +320,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Source,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -318,6 +330,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Source,
             false,
+            Locales::En,
             Environment::Staging,
             connection,
         )
@@ -331,6 +344,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Destination,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -350,6 +364,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Source,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -363,6 +378,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Destination,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -383,6 +399,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Source,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -395,6 +412,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Destination,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -427,6 +445,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Source,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -439,6 +458,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Destination,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -469,6 +489,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Source,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -481,6 +502,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Destination,
             false,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -491,7 +513,14 @@ fn drip_header() {
 
     // With links
     let drip_header = transfer
-        .drip_header(&event, SourceOrDestination::Source, true, Environment::Test, connection)
+        .drip_header(
+            &event,
+            SourceOrDestination::Source,
+            true,
+            Locales::En,
+            Environment::Test,
+            connection,
+        )
         .unwrap();
     assert!(drip_header.contains("<a href='mailto:test@tari.com'>test@tari.com</a>"));
     let drip_header = transfer
@@ -499,6 +528,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Destination,
             true,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -534,6 +564,7 @@ fn drip_header() {
             &event,
             SourceOrDestination::Destination,
             true,
+            Locales::En,
             Environment::Test,
             connection,
         )
@@ -549,12 +580,163 @@ fn drip_header() {
             &event,
             SourceOrDestination::Source,
             false,
+            Locales::En,
             Environment::Test,
             connection
         )
         .is_err());
 }
 
+#[test]
+fn drip_header_localized() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project
+        .create_user()
+        .with_first_name("Bob")
+        .with_last_name("Miller")
+        .finish();
+    let event = project
+        .create_event()
+        .with_event_start(dates::now().add_days(7).finish())
+        .with_event_end(dates::now().add_days(14).finish())
+        .with_ticket_pricing()
+        .finish();
+    let transfer = Transfer::create(
+        user.id,
+        Uuid::new_v4(),
+        Some(TransferMessageType::Email),
+        Some("test@tari.com".to_string()),
+        false,
+    )
+    .commit(connection)
+    .unwrap();
+
+    let drip_header = transfer
+        .drip_header(
+            &event,
+            SourceOrDestination::Destination,
+            false,
+            Locales::Fr,
+            Environment::Test,
+            connection,
+        )
+        .unwrap();
+    assert_eq!(
+        drip_header,
+        "L'événement a lieu dans seulement une semaine et vous devez encore récupérer les billets que Bob M. vous a envoyés!"
+    );
+    let drip_header = transfer
+        .drip_header(
+            &event,
+            SourceOrDestination::Destination,
+            false,
+            Locales::Es,
+            Environment::Test,
+            connection,
+        )
+        .unwrap();
+    assert_eq!(
+        drip_header,
+        "¡Falta solo una semana para el evento y todavía tienes que recibir los boletos que Bob M. te envió!"
+    );
+
+    let event = event
+        .update(
+            None,
+            EventEditableAttributes {
+                event_start: Some(dates::now().add_days(2).finish()),
+                ..Default::default()
+            },
+            connection,
+        )
+        .unwrap();
+    let drip_header = transfer
+        .drip_header(
+            &event,
+            SourceOrDestination::Source,
+            false,
+            Locales::Fr,
+            Environment::Test,
+            connection,
+        )
+        .unwrap();
+    assert_eq!(
+        drip_header,
+        "Les billets que vous avez envoyés à test@tari.com n'ont toujours pas été réclamés. Faites un petit rappel!"
+    );
+    let drip_header = transfer
+        .drip_header(
+            &event,
+            SourceOrDestination::Source,
+            false,
+            Locales::Es,
+            Environment::Test,
+            connection,
+        )
+        .unwrap();
+    assert_eq!(
+        drip_header,
+        "Los boletos que enviaste a test@tari.com todavía no han sido reclamados. ¡Recuérdaselo!"
+    );
+}
+
+#[test]
+fn locale() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let organization = project.create_organization().with_default_locale(Locales::Fr).finish();
+    let event = project
+        .create_event()
+        .with_organization(&organization)
+        .with_ticket_pricing()
+        .finish();
+    let user = project.create_user().with_locale(Locales::Es).finish();
+    let existing_user = project.create_user().with_locale(Locales::En).finish();
+    project
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(2)
+        .is_paid()
+        .finish();
+    let user_tickets = TicketInstance::find_for_user(user.id, connection).unwrap();
+
+    // Recipient without an account gets the organization default
+    let transfer = TicketInstance::create_transfer(
+        &user,
+        &[user_tickets[0].id],
+        Some("new@tari.com"),
+        Some(TransferMessageType::Email),
+        false,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(
+        transfer.locale(SourceOrDestination::Source, connection).unwrap(),
+        Locales::Es
+    );
+    assert_eq!(
+        transfer.locale(SourceOrDestination::Destination, connection).unwrap(),
+        Locales::Fr
+    );
+
+    // Recipient with an account uses their own preference
+    let transfer = TicketInstance::create_transfer(
+        &user,
+        &[user_tickets[1].id],
+        existing_user.email.as_ref().map(|e| e.as_str()),
+        Some(TransferMessageType::Email),
+        false,
+        connection,
+    )
+    .unwrap();
+    assert_eq!(
+        transfer.locale(SourceOrDestination::Destination, connection).unwrap(),
+        Locales::En
+    );
+}
+
 #[test]
 fn can_process_drips() {
     let project = TestProject::new();
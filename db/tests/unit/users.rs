@@ -2098,6 +2098,7 @@ fn get_scopes_by_organization() {
             Scopes::EventScan,
             Scopes::EventViewGuests,
             Scopes::EventWrite,
+            Scopes::FanSegmentWrite,
             Scopes::HoldRead,
             Scopes::HoldWrite,
             Scopes::ListingWrite,
@@ -2163,6 +2164,7 @@ fn get_scopes_by_organization() {
             Scopes::EventScan,
             Scopes::EventViewGuests,
             Scopes::EventWrite,
+            Scopes::FanSegmentWrite,
             Scopes::HoldRead,
             Scopes::HoldWrite,
             Scopes::ListingWrite,
@@ -2284,6 +2286,7 @@ fn get_global_scopes() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -2374,6 +2377,7 @@ fn get_global_scopes() {
             "event-report-subscriber:delete",
             "event-report-subscriber:read",
             "event-report-subscriber:write",
+            "fan-segment:write",
             "hold:read",
             "hold:write",
             "listing:write",
@@ -0,0 +1,214 @@
+use db::dev::TestProject;
+use db::models::*;
+
+fn pending_wallet_pass_updates(connection: &PgConnection) -> Vec<DomainAction> {
+    DomainAction::find_by_resource(
+        Some(Tables::WalletPasses),
+        None,
+        DomainActionTypes::UpdateWalletPasses,
+        DomainActionStatus::Pending,
+        connection,
+    )
+    .unwrap()
+}
+
+#[test]
+fn find_or_create_for_ticket() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    project.create_order().for_user(&user).quantity(1).is_paid().finish();
+    let ticket = &TicketInstance::find_for_user(user.id, connection).unwrap()[0];
+
+    let pass = WalletPass::find_or_create_for_ticket(ticket, WalletPassTypes::Apple, connection).unwrap();
+    assert_eq!(pass.ticket_instance_id, ticket.id);
+    assert_eq!(pass.wallet_id, ticket.wallet_id);
+    assert_eq!(pass.pass_type, WalletPassTypes::Apple);
+    assert_eq!(pass.authentication_token.len(), 32);
+    assert!(!pass.is_voided());
+
+    // The same pass is returned until the ticket changes hands
+    assert_eq!(
+        WalletPass::find_or_create_for_ticket(ticket, WalletPassTypes::Apple, connection).unwrap(),
+        pass
+    );
+    let google_pass = WalletPass::find_or_create_for_ticket(ticket, WalletPassTypes::Google, connection).unwrap();
+    assert_ne!(google_pass.id, pass.id);
+    assert_eq!(
+        WalletPass::find_for_ticket(ticket.id, connection).unwrap(),
+        vec![pass, google_pass]
+    );
+}
+
+#[test]
+fn details() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let venue = project.create_venue().with_name("Venue".to_string()).finish();
+    let event = project
+        .create_event()
+        .with_name("Event".to_string())
+        .with_venue(&venue)
+        .with_ticket_pricing()
+        .finish();
+    let user = project
+        .create_user()
+        .with_first_name("Jane")
+        .with_last_name("Doe")
+        .finish();
+    project
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    let ticket = &TicketInstance::find_for_user(user.id, connection).unwrap()[0];
+    let pass = WalletPass::find_or_create_for_ticket(ticket, WalletPassTypes::Apple, connection).unwrap();
+
+    let details = pass.details(connection).unwrap();
+    assert_eq!(details.serial_number, pass.id);
+    assert_eq!(details.authentication_token, pass.authentication_token);
+    assert!(!details.voided);
+    assert_eq!(details.ticket_instance_id, ticket.id);
+    assert_eq!(details.ticket_type_name, ticket.ticket_type(connection).unwrap().name);
+    assert_eq!(details.holder_name, Some("Jane Doe".to_string()));
+    assert_eq!(details.redeem_key, ticket.redeem_key);
+    assert_eq!(details.event_id, event.id);
+    assert_eq!(details.event_name, "Event".to_string());
+    assert_eq!(details.event_start, event.event_start);
+    assert_eq!(details.venue.map(|v| v.id), Some(venue.id));
+}
+
+#[test]
+fn register_and_unregister_device() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    project.create_order().for_user(&user).quantity(1).is_paid().finish();
+    let ticket = &TicketInstance::find_for_user(user.id, connection).unwrap()[0];
+    let pass = WalletPass::find_or_create_for_ticket(ticket, WalletPassTypes::Apple, connection).unwrap();
+
+    assert!(pass.register_device("device", "push-token", connection).unwrap());
+    // Registering again updates the push token
+    assert!(!pass.register_device("device", "new-push-token", connection).unwrap());
+    let registrations = pass.registrations(connection).unwrap();
+    assert_eq!(registrations.len(), 1);
+    assert_eq!(registrations[0].push_token, "new-push-token".to_string());
+
+    assert_eq!(
+        WalletPass::find_updated_for_device("device", WalletPassTypes::Apple, None, connection).unwrap(),
+        vec![pass.clone()]
+    );
+    assert!(
+        WalletPass::find_updated_for_device("device", WalletPassTypes::Apple, Some(pass.updated_at), connection)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        WalletPass::find_updated_for_device("other-device", WalletPassTypes::Apple, None, connection)
+            .unwrap()
+            .is_empty()
+    );
+
+    pass.unregister_device("device", connection).unwrap();
+    assert!(pass.registrations(connection).unwrap().is_empty());
+}
+
+#[test]
+fn find_authenticated() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    project.create_order().for_user(&user).quantity(1).is_paid().finish();
+    let ticket = &TicketInstance::find_for_user(user.id, connection).unwrap()[0];
+    let pass = WalletPass::find_or_create_for_ticket(ticket, WalletPassTypes::Apple, connection).unwrap();
+
+    assert_eq!(
+        WalletPass::find_authenticated(pass.id, WalletPassTypes::Apple, &pass.authentication_token, connection)
+            .unwrap(),
+        Some(pass.clone())
+    );
+    assert_eq!(
+        WalletPass::find_authenticated(pass.id, WalletPassTypes::Apple, "wrong-token", connection).unwrap(),
+        None
+    );
+    assert_eq!(
+        WalletPass::find_authenticated(pass.id, WalletPassTypes::Google, &pass.authentication_token, connection)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn refresh_on_transfer() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let user = project.create_user().finish();
+    let user2 = project.create_user().finish();
+    project.create_order().for_user(&user).quantity(1).is_paid().finish();
+    let ticket = TicketInstance::find_for_user(user.id, connection).unwrap().remove(0);
+    let pass = WalletPass::find_or_create_for_ticket(&ticket, WalletPassTypes::Apple, connection).unwrap();
+    assert!(pending_wallet_pass_updates(connection).is_empty());
+
+    TicketInstance::direct_transfer(
+        &user,
+        &[ticket.id],
+        "nowhere",
+        TransferMessageType::Email,
+        user2.id,
+        connection,
+    )
+    .unwrap();
+
+    // The previous owner's pass is voided and no longer shows the redeem key
+    let pass = WalletPass::find(pass.id, connection).unwrap();
+    assert!(pass.is_voided());
+    let details = pass.details(connection).unwrap();
+    assert!(details.voided);
+    assert_eq!(details.redeem_key, None);
+    let updates = pending_wallet_pass_updates(connection);
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].payload, json!({ "wallet_pass_ids": [pass.id] }));
+
+    // The new owner is issued a new pass with the new redeem key
+    let ticket = TicketInstance::find(ticket.id, connection).unwrap();
+    let new_pass = WalletPass::find_or_create_for_ticket(&ticket, WalletPassTypes::Apple, connection).unwrap();
+    assert_ne!(new_pass.id, pass.id);
+    assert_eq!(new_pass.wallet_id, user2.default_wallet(connection).unwrap().id);
+    assert_eq!(new_pass.details(connection).unwrap().redeem_key, ticket.redeem_key);
+}
+
+#[test]
+fn refresh_on_event_update() {
+    let project = TestProject::new();
+    let connection = project.get_connection();
+    let event = project.create_event().with_ticket_pricing().finish();
+    let user = project.create_user().finish();
+    project
+        .create_order()
+        .for_event(&event)
+        .for_user(&user)
+        .quantity(1)
+        .is_paid()
+        .finish();
+    let ticket = &TicketInstance::find_for_user(user.id, connection).unwrap()[0];
+    let pass = WalletPass::find_or_create_for_ticket(ticket, WalletPassTypes::Google, connection).unwrap();
+
+    let parameters = EventEditableAttributes {
+        name: Some("Renamed event".to_string()),
+        ..Default::default()
+    };
+    event.update(None, parameters, connection).unwrap();
+
+    // Passes stay live and the wallet apps are told to pick up the new details
+    let updated_pass = WalletPass::find(pass.id, connection).unwrap();
+    assert!(!updated_pass.is_voided());
+    assert_eq!(
+        updated_pass.details(connection).unwrap().event_name,
+        "Renamed event".to_string()
+    );
+    let updates = pending_wallet_pass_updates(connection);
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].payload, json!({ "wallet_pass_ids": [pass.id] }));
+}
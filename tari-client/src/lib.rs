@@ -24,6 +24,7 @@ mod cryptographic;
 mod tari_client;
 mod tari_error;
 mod tari_messages;
+mod tari_simulator;
 mod tari_test_client;
 
 pub use cryptographic::*;
@@ -31,4 +32,5 @@ pub use tari_client::HttpTariClient;
 pub use tari_client::TariClient;
 pub use tari_error::TariError;
 pub use tari_messages::*;
+pub use tari_simulator::{SimulatedAsset, TariSimulator};
 pub use tari_test_client::TariTestClient;
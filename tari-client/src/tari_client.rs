@@ -54,6 +54,8 @@ pub trait TariClient {
         asset_id: &String,
     ) -> Result<ResponsePayloadReadAsset, TariError>;
 
+    fn get_tokens(&self, secret_key: &String, public_key: &String, asset_id: &String) -> Result<Vec<Token>, TariError>;
+
     fn box_clone(&self) -> Box<dyn TariClient + Send + Sync>;
 }
 
@@ -277,6 +279,28 @@ impl TariClient for HttpTariClient {
         Ok(response_message_result)
     }
 
+    fn get_tokens(&self, secret_key: &String, public_key: &String, asset_id: &String) -> Result<Vec<Token>, TariError> {
+        let header_command = String::from("read_asset");
+        let msg_payload = serde_json::to_value(MessagePayloadReadAsset {
+            request_type: 1,
+            user: None,
+            asset_id: asset_id.clone(),
+            token_ids: None,
+        })?;
+        let secret_key = convert_hexstring_to_bytes(&secret_key);
+        let public_key = convert_hexstring_to_bytes(&public_key);
+        let jsonrpc_request = construct_jsonrpc_request(header_command, msg_payload, &secret_key, &public_key)?;
+
+        let client = Client::new();
+        let resp = client.post(&self.tari_url).json(&jsonrpc_request).send()?;
+        let raw: String = resp.text()?;
+        jlog!(Level::Info, &format!("Response from read_asset tokens: {}", raw));
+        let response_message: RPCResponse = serde_json::from_str(&raw)?;
+        let response_message_result: ResponsePayloadSuccessTokens = serde_json::from_value(response_message.result)?;
+
+        Ok(response_message_result.tokens.unwrap_or_default())
+    }
+
     fn box_clone(&self) -> Box<dyn TariClient + Send + Sync> {
         Box::new((*self).clone())
     }
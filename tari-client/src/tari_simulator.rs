@@ -0,0 +1,308 @@
+use super::tari_messages::*;
+use cryptographic::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tari_client::TariClient;
+use tari_error::TariError;
+use uuid::Uuid;
+
+const SIGNER_CHECK_MESSAGE: &str = "tari-simulator";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulatedAsset {
+    pub id: String,
+    pub name: String,
+    pub issuer: String,
+    pub total_supply: u64,
+    pub authorised_signers: Vec<String>,
+    pub rule_flags: i64,
+    pub rule_metadata: String,
+    pub expiry_date: i64,
+    /// Owner public key per token, tokens without an entry are still held by the issuer
+    pub token_owners: HashMap<u64, String>,
+    pub redeemed_tokens: HashSet<u64>,
+    pub nullified_tokens: HashSet<u64>,
+}
+
+impl SimulatedAsset {
+    pub fn owner_of(&self, token_id: u64) -> Option<String> {
+        if token_id >= self.total_supply {
+            return None;
+        }
+        Some(
+            self.token_owners
+                .get(&token_id)
+                .cloned()
+                .unwrap_or_else(|| self.issuer.clone()),
+        )
+    }
+}
+
+/// In memory Tari ledger that keeps track of assets and token ownership, rejecting calls that the
+/// network would reject. Clones share the same ledger so tests can inspect it after handing a copy
+/// to the application.
+#[derive(Clone, Default)]
+pub struct TariSimulator {
+    assets: Arc<Mutex<HashMap<String, SimulatedAsset>>>,
+}
+
+impl TariSimulator {
+    pub fn new() -> TariSimulator {
+        TariSimulator::default()
+    }
+
+    /// Adds an asset that was created outside of the simulator with all tokens held by the issuer
+    pub fn register_asset(&self, asset_id: &str, issuer_public_key: &str, total_supply: u64) {
+        self.assets.lock().unwrap().insert(
+            asset_id.to_string(),
+            SimulatedAsset {
+                id: asset_id.to_string(),
+                name: asset_id.to_string(),
+                issuer: issuer_public_key.to_string(),
+                total_supply,
+                authorised_signers: vec![issuer_public_key.to_string()],
+                rule_flags: 0,
+                rule_metadata: "".to_string(),
+                expiry_date: 0,
+                token_owners: HashMap::new(),
+                redeemed_tokens: HashSet::new(),
+                nullified_tokens: HashSet::new(),
+            },
+        );
+    }
+
+    pub fn asset(&self, asset_id: &str) -> Option<SimulatedAsset> {
+        self.assets.lock().unwrap().get(asset_id).cloned()
+    }
+
+    pub fn owner_of(&self, asset_id: &str, token_id: u64) -> Option<String> {
+        self.asset(asset_id).and_then(|a| a.owner_of(token_id))
+    }
+
+    pub fn tokens_owned_by(&self, asset_id: &str, public_key: &str) -> Vec<u64> {
+        match self.asset(asset_id) {
+            Some(asset) => (0..asset.total_supply)
+                .filter(|token_id| asset.owner_of(*token_id).as_ref().map(|o| o.as_str()) == Some(public_key))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn modify_asset<F>(
+        &self,
+        secret_key: &String,
+        public_key: &String,
+        asset_id: &String,
+        f: F,
+    ) -> Result<(), TariError>
+    where
+        F: FnOnce(&mut SimulatedAsset) -> Result<(), TariError>,
+    {
+        verify_signer(secret_key, public_key)?;
+        let mut assets = self.assets.lock().unwrap();
+        let asset = match assets.get_mut(asset_id) {
+            Some(asset) => asset,
+            None => return Err(simulator_error(format!("Asset {} does not exist", asset_id))),
+        };
+        // Changes are only applied when the whole call is valid
+        let mut modified = asset.clone();
+        f(&mut modified)?;
+        *asset = modified;
+        Ok(())
+    }
+}
+
+impl TariClient for TariSimulator {
+    fn create_asset(
+        &self,
+        secret_key: &String,
+        public_key: &String,
+        asset: MessagePayloadCreateAsset,
+    ) -> Result<String, TariError> {
+        verify_signer(secret_key, public_key)?;
+        let asset_id = Uuid::new_v4().to_string();
+        self.assets.lock().unwrap().insert(
+            asset_id.clone(),
+            SimulatedAsset {
+                id: asset_id.clone(),
+                name: asset.name,
+                issuer: public_key.clone(),
+                total_supply: asset.total_supply,
+                authorised_signers: asset.authorised_signers,
+                rule_flags: asset.rule_flags,
+                rule_metadata: asset.rule_metadata,
+                expiry_date: asset.expiry_date,
+                token_owners: HashMap::new(),
+                redeemed_tokens: HashSet::new(),
+                nullified_tokens: HashSet::new(),
+            },
+        );
+        Ok(asset_id)
+    }
+
+    fn modify_asset_increase_supply(
+        &self,
+        secret_key: &String,
+        public_key: &String,
+        asset_id: &String,
+        new_supply: u64,
+    ) -> Result<(), TariError> {
+        self.modify_asset(secret_key, public_key, asset_id, |asset| {
+            require_issuer(asset, public_key)?;
+            if new_supply < asset.total_supply {
+                return Err(simulator_error(format!(
+                    "Supply cannot be decreased from {} to {}",
+                    asset.total_supply, new_supply
+                )));
+            }
+            asset.total_supply = new_supply;
+            Ok(())
+        })
+    }
+
+    fn modify_asset_nullify_tokens(
+        &self,
+        secret_key: &String,
+        public_key: &String,
+        asset_id: &String,
+        token_ids: Vec<u64>,
+    ) -> Result<(), TariError> {
+        self.modify_asset(secret_key, public_key, asset_id, |asset| {
+            require_issuer(asset, public_key)?;
+            for token_id in token_ids {
+                require_owner(asset, token_id, public_key)?;
+                asset.nullified_tokens.insert(token_id);
+            }
+            Ok(())
+        })
+    }
+
+    fn modify_asset_redeem_token(
+        &self,
+        secret_key: &String,
+        public_key: &String,
+        asset_id: &String,
+        token_ids: Vec<u64>,
+    ) -> Result<(), TariError> {
+        self.modify_asset(secret_key, public_key, asset_id, |asset| {
+            for token_id in token_ids {
+                require_owner(asset, token_id, public_key)?;
+                if asset.redeemed_tokens.contains(&token_id) {
+                    return Err(simulator_error(format!("Token {} has already been redeemed", token_id)));
+                }
+                asset.redeemed_tokens.insert(token_id);
+            }
+            Ok(())
+        })
+    }
+
+    fn transfer_tokens(
+        &self,
+        secret_key: &String,
+        public_key: &String,
+        asset_id: &String,
+        token_ids: Vec<u64>,
+        new_owner: String,
+    ) -> Result<(), TariError> {
+        self.modify_asset(secret_key, public_key, asset_id, |asset| {
+            for token_id in token_ids {
+                require_owner(asset, token_id, public_key)?;
+                asset.token_owners.insert(token_id, new_owner.clone());
+            }
+            Ok(())
+        })
+    }
+
+    fn get_asset_info(
+        &self,
+        secret_key: &String,
+        public_key: &String,
+        asset_id: &String,
+    ) -> Result<ResponsePayloadReadAsset, TariError> {
+        verify_signer(secret_key, public_key)?;
+        match self.asset(asset_id) {
+            Some(asset) => Ok(ResponsePayloadReadAsset {
+                id: asset.id,
+                name: asset.name,
+                symbol: "".to_string(),
+                decimals: 0,
+                total_supply: asset.total_supply,
+                authorised_signers: asset.authorised_signers,
+                issuer: asset.issuer,
+                rule_flags: asset.rule_flags,
+                rule_metadata: asset.rule_metadata,
+                expired: false,
+            }),
+            None => Err(simulator_error(format!("Asset {} does not exist", asset_id))),
+        }
+    }
+
+    fn get_tokens(&self, secret_key: &String, public_key: &String, asset_id: &String) -> Result<Vec<Token>, TariError> {
+        verify_signer(secret_key, public_key)?;
+        match self.asset(asset_id) {
+            Some(asset) => Ok((0..asset.total_supply)
+                .map(|token_id| Token {
+                    id: token_id,
+                    asset_id: asset.id.clone(),
+                    owner: asset.owner_of(token_id).unwrap_or_default(),
+                    used: asset.redeemed_tokens.contains(&token_id),
+                    valid: !asset.nullified_tokens.contains(&token_id),
+                    metadata: 0,
+                })
+                .collect()),
+            None => Err(simulator_error(format!("Asset {} does not exist", asset_id))),
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn TariClient + Send + Sync> {
+        Box::new((*self).clone())
+    }
+}
+
+/// Signing with a secret key that does not belong to the public key would be rejected by the network
+fn verify_signer(secret_key: &String, public_key: &String) -> Result<(), TariError> {
+    let message = SIGNER_CHECK_MESSAGE.to_string();
+    let signature = cryptographic_signature(&message, &convert_hexstring_to_bytes(secret_key))?;
+    if cryptographic_verify(&signature, &message, &convert_hexstring_to_bytes(public_key)) {
+        Ok(())
+    } else {
+        Err(simulator_error(format!("Secret key does not belong to {}", public_key)))
+    }
+}
+
+fn require_issuer(asset: &SimulatedAsset, public_key: &String) -> Result<(), TariError> {
+    if &asset.issuer != public_key {
+        return Err(simulator_error(format!(
+            "Asset {} can only be modified by its issuer",
+            asset.id
+        )));
+    }
+    Ok(())
+}
+
+/// Tokens can only be used by their current owner and not once they have been nullified
+fn require_owner(asset: &SimulatedAsset, token_id: u64, public_key: &String) -> Result<(), TariError> {
+    if token_id >= asset.total_supply {
+        return Err(simulator_error(format!(
+            "Token {} does not exist for asset {}",
+            token_id, asset.id
+        )));
+    }
+    if asset.nullified_tokens.contains(&token_id) {
+        return Err(simulator_error(format!("Token {} has been nullified", token_id)));
+    }
+    if asset.owner_of(token_id).as_ref() != Some(public_key) {
+        return Err(simulator_error(format!(
+            "Token {} is not owned by {}",
+            token_id, public_key
+        )));
+    }
+    Ok(())
+}
+
+fn simulator_error(description: String) -> TariError {
+    TariError {
+        description: format!("Tari simulator rejected call: {}", description),
+        cause: None,
+    }
+}
@@ -85,6 +85,15 @@ impl TariClient for TariTestClient {
         })
     }
 
+    fn get_tokens(
+        &self,
+        _secret_key: &String,
+        _public_key: &String,
+        _asset_id: &String,
+    ) -> Result<Vec<Token>, TariError> {
+        Ok(Vec::new())
+    }
+
     fn box_clone(&self) -> Box<dyn TariClient + Send + Sync> {
         Box::new((*self).clone())
     }